@@ -0,0 +1,160 @@
+// crates/velocut-core/src/beat_grid.rs
+//
+// Project-level beat grid for cutting to music. The settings live on
+// `ProjectState` and are changed only through `EditorCommand::SetBeatGrid`.
+// The timeline draws a line per beat (brighter on each bar's downbeat) and
+// snaps moves, trims and the playhead to them like it does to markers.
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Tempos the grid accepts, in beats per minute.
+pub const BPM_RANGE: RangeInclusive<f64> = 20.0..=300.0;
+/// Allowed beats per bar (time-signature numerator).
+pub const BEATS_PER_BAR_RANGE: RangeInclusive<u32> = 1..=16;
+/// Tap-tempo gaps longer than this start a new tap sequence.
+pub const TAP_RESET_SECS: f64 = 2.0;
+
+/// Evenly spaced beats from a downbeat at `offset` seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BeatGrid {
+    pub bpm: f64,
+    /// Timeline time of a downbeat. Beats extend both ways from it, so any
+    /// downbeat of the song can be used.
+    pub offset: f64,
+    pub beats_per_bar: u32,
+}
+
+impl Default for BeatGrid {
+    fn default() -> Self {
+        Self {
+            bpm: 120.0,
+            offset: 0.0,
+            beats_per_bar: 4,
+        }
+    }
+}
+
+impl BeatGrid {
+    /// Reject settings the timeline menu can't produce.
+    pub fn validate(&self) -> Result<(), String> {
+        if !BPM_RANGE.contains(&self.bpm) {
+            return Err(format!(
+                "Tempo must be between {:.0} and {:.0} BPM",
+                BPM_RANGE.start(),
+                BPM_RANGE.end()
+            ));
+        }
+        if !self.offset.is_finite() {
+            return Err("Beat offset must be a finite time".into());
+        }
+        if !BEATS_PER_BAR_RANGE.contains(&self.beats_per_bar) {
+            return Err(format!(
+                "Beats per bar must be between {} and {}",
+                BEATS_PER_BAR_RANGE.start(),
+                BEATS_PER_BAR_RANGE.end()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Length of one beat in seconds.
+    pub fn beat_secs(&self) -> f64 {
+        60.0 / self.bpm
+    }
+
+    /// Beats in `[from, to]` as `(time, is_downbeat)`, ascending. Times
+    /// before 0 are skipped.
+    pub fn beats_in(&self, from: f64, to: f64) -> impl Iterator<Item = (f64, bool)> + '_ {
+        let step = self.beat_secs();
+        let first = ((from.max(0.0) - self.offset) / step).ceil() as i64;
+        let last = ((to - self.offset) / step).floor() as i64;
+        let bar = self.beats_per_bar.max(1) as i64;
+        (first..=last).map(move |n| (self.offset + n as f64 * step, n.rem_euclid(bar) == 0))
+    }
+
+    /// Beat time within `tolerance` seconds of `t`, if any.
+    pub fn beat_near(&self, t: f64, tolerance: f64) -> Option<f64> {
+        let step = self.beat_secs();
+        let beat = self.offset + ((t - self.offset) / step).round() * step;
+        (beat >= 0.0 && (beat - t).abs() < tolerance).then_some(beat)
+    }
+}
+
+/// Tempo from tap-tempo timestamps (seconds, ascending): the mean interval
+/// of the taps, as BPM. `None` until there are two taps, or when the result
+/// falls outside `BPM_RANGE`.
+pub fn tempo_from_taps(taps: &[f64]) -> Option<f64> {
+    let (first, last) = (taps.first()?, taps.last()?);
+    if taps.len() < 2 || last <= first {
+        return None;
+    }
+    let interval = (last - first) / (taps.len() - 1) as f64;
+    Some(60.0 / interval).filter(|bpm| BPM_RANGE.contains(bpm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beats_follow_tempo_and_mark_downbeats() {
+        let grid = BeatGrid {
+            bpm: 120.0,
+            offset: 0.25,
+            beats_per_bar: 4,
+        };
+        let beats: Vec<_> = grid.beats_in(0.0, 2.3).collect();
+        assert_eq!(
+            beats,
+            vec![
+                (0.25, true),
+                (0.75, false),
+                (1.25, false),
+                (1.75, false),
+                (2.25, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn beats_extend_before_the_offset_but_not_below_zero() {
+        let grid = BeatGrid {
+            bpm: 60.0,
+            offset: 2.5,
+            beats_per_bar: 2,
+        };
+        let beats: Vec<_> = grid.beats_in(0.0, 2.5).collect();
+        assert_eq!(beats, vec![(0.5, true), (1.5, false), (2.5, true)]);
+    }
+
+    #[test]
+    fn beat_near_respects_tolerance() {
+        let grid = BeatGrid::default();
+        assert_eq!(grid.beat_near(1.02, 0.05), Some(1.0));
+        assert_eq!(grid.beat_near(1.2, 0.05), None);
+    }
+
+    #[test]
+    fn taps_average_to_a_tempo() {
+        assert_eq!(tempo_from_taps(&[0.0]), None);
+        assert_eq!(tempo_from_taps(&[0.0, 0.5, 1.0, 1.5]), Some(120.0));
+        // One beat every 5 s is below the accepted range.
+        assert_eq!(tempo_from_taps(&[0.0, 5.0]), None);
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_values() {
+        assert_eq!(BeatGrid::default().validate(), Ok(()));
+        let slow = BeatGrid {
+            bpm: 5.0,
+            ..Default::default()
+        };
+        assert!(slow.validate().is_err());
+        let no_bar = BeatGrid {
+            beats_per_bar: 0,
+            ..Default::default()
+        };
+        assert!(no_bar.validate().is_err());
+    }
+}
@@ -4,9 +4,20 @@
 // Modules emit these; app.rs processes them after the UI pass.
 // Adding a new feature = add a variant here + one match arm in app.rs.
 
+use crate::beat_grid::BeatGrid;
 use crate::filters::FilterParams;
-use crate::state::{AspectRatio, ProjectState};
+use crate::helpers::edl::CutListFormat;
+use crate::helpers::storyboard::StoryboardOptions;
+use crate::media_types::{
+    DitherMode, ExportFormat, ExportMetadata, ExportTracks, TARGET_KBPS_RANGE,
+};
+use crate::prefs::{LayoutPrefs, NotifyPrefs, PerformancePrefs, PlaybackPrefs, StoragePrefs};
+use crate::state::{
+    AspectRatio, ClipType, ColorRangeMode, DeinterlaceMode, DropPart, Duck, ProjectState,
+    SpeechRegions, Stabilize, PREVIEW_RATES, PROJECT_FPS_RANGE,
+};
 use crate::transitions::TransitionType;
+use crate::watermark::Watermark;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -17,21 +28,84 @@ pub enum EditorCommand {
     Play,
     Pause,
     Stop,
+    /// Review an edit point: play from the pre-roll before this time to the
+    /// post-roll after it (`PlaybackPrefs`), then return the playhead here.
+    PlayAround(f64),
     SetVolume(f32),
     ToggleMute,
+    /// Preview-only playback rate; must be one of `PREVIEW_RATES`.
+    SetPreviewRate(f32),
+    /// Show the preview with clip filters skipped (`true`) or applied.
+    /// Never reaches export.
+    SetEffectsBypass(bool),
 
     // ── Library ──────────────────────────────────────────────────────────────
     ImportFile(PathBuf),
+    /// Merge the library of the project JSON at `path` into this one and,
+    /// with `timeline_at`, its timeline starting at that time. See
+    /// `ProjectState::import_project`.
+    ImportProject {
+        path: PathBuf,
+        timeline_at: Option<f64>,
+    },
+    /// Write the current project as JSON to `path` without switching to it,
+    /// so a version can be kept before a risky edit. Readable by
+    /// `ImportProject`.
+    SaveProjectCopy(PathBuf),
     DeleteLibraryClip(Uuid),
     SelectLibraryClip(Option<Uuid>),
+    /// Stop waiting for deferred startup re-probes (see
+    /// `ProjectState::deferred_probes`) and queue all of them now.
+    ProbeDeferredMedia,
 
     // ── Timeline ─────────────────────────────────────────────────────────────
+    /// `part` picks video-only / audio-only from a combined source.
     AddToTimeline {
         media_id: Uuid,
         at_time: f64,
         track_row: usize,
+        part: DropPart,
+    },
+    /// Insert a library clip at `at_time` and push later clips on the same
+    /// track right by its duration. See `ProjectState::ripple_insert`.
+    RippleInsertToTimeline {
+        media_id: Uuid,
+        at_time: f64,
+        track_row: usize,
+        part: DropPart,
+    },
+    /// Place several library clips end to end from `at_time`, in the given
+    /// order — a multi-card drag from the library. Each lands on the V or A
+    /// row of `track_row`'s pair that suits its type; `part` applies to video
+    /// sources only. With `ripple`, each is inserted like
+    /// `RippleInsertToTimeline`.
+    AddClipsToTimeline {
+        media_ids: Vec<Uuid>,
+        at_time: f64,
+        track_row: usize,
+        part: DropPart,
+        ripple: bool,
+    },
+    /// Open a library clip in the source viewer beside the Monitor.
+    /// `None` closes the viewer.
+    SetSourceViewer(Option<Uuid>),
+    /// Three-point insert: place source range `source_in..source_out` of a
+    /// library clip at `at_time` on its track, pushing later clips right.
+    /// See `ProjectState::insert_source_range`.
+    InsertSourceRange {
+        media_id: Uuid,
+        source_in: f64,
+        source_out: f64,
+        at_time: f64,
     },
     DeleteTimelineClip(Uuid),
+    /// Drop a ruler marker at `t` seconds.
+    AddMarker(f64),
+    MoveMarker {
+        id: Uuid,
+        time: f64,
+    },
+    DeleteMarker(Uuid),
     SelectTimelineClip(Option<Uuid>),
     MoveTimelineClip {
         id: Uuid,
@@ -48,14 +122,114 @@ pub enum EditorCommand {
         new_duration: f64,
     },
     SplitClipAt(f64),
+    /// Split the video clip under `at` and hold that frame for `duration`
+    /// seconds, rippling later clips on the track right.
+    InsertFreezeFrame {
+        at: f64,
+        duration: f64,
+    },
+    /// Ripple-insert a solid `color` (sRGB) slate clip of `duration` seconds
+    /// on V1 at `at`, splitting any clip under it.
+    InsertSlate {
+        at: f64,
+        duration: f64,
+        color: [u8; 3],
+    },
+    /// Select a timeline clip's source in the library at the matching source
+    /// timestamp. `None` = the clip under the playhead.
+    MatchFrame(Option<Uuid>),
+    /// Scan a video clip's source range for hard cuts. `threshold` (0–1) is
+    /// how different consecutive frames must be — lower finds more cuts.
+    /// The result arrives as `MediaResult::ScenesDetected`.
+    DetectScenes {
+        id: Uuid,
+        threshold: f32,
+    },
+    /// Split a clip at every cut found by the last `DetectScenes` on it.
+    SplitAtScenes(Uuid),
+    /// Scan a clip's audio for leading/trailing silence quieter than
+    /// `threshold_db` (dBFS). Gaps shorter than `min_gap` seconds are kept.
+    /// The result arrives as `MediaResult::SilenceDetected`.
+    DetectSilence {
+        id: Uuid,
+        threshold_db: f32,
+        min_gap: f64,
+    },
+    /// Trim a clip (and its group) to the sounding range found by the last
+    /// `DetectSilence` on it.
+    TrimSilence(Uuid),
+    /// Cross-correlate clip `id`'s audio against `reference`'s to find the
+    /// offset that puts them in sync. The result arrives as
+    /// `MediaResult::AudioSyncFound`.
+    DetectAudioSync {
+        id: Uuid,
+        reference: Uuid,
+    },
+    /// Move a clip (and its group) to the position found by the last
+    /// `DetectAudioSync` on it.
+    ApplyAudioSync(Uuid),
+    /// Scan speech clip `speech` for talking louder than `threshold_db`
+    /// (dBFS) so music clip `id` can be ducked under it. The result arrives
+    /// as `MediaResult::SpeechDetected`.
+    DetectSpeech {
+        id: Uuid,
+        speech: Uuid,
+        threshold_db: f32,
+    },
+    /// Replace music clip `id`'s volume keys with a duck under the speech
+    /// found by the last `DetectSpeech` on it.
+    ApplyDuck {
+        id: Uuid,
+        duck: Duck,
+    },
+    /// Remove every volume key from a clip.
+    ClearVolumeKeys(Uuid),
+    /// Render timeline range `start..end` to a scratch file and replace the
+    /// range with a single clip playing it. app.rs runs the render through
+    /// the export pipeline and swaps the clips in once it finishes.
+    NestRange {
+        start: f64,
+        end: f64,
+    },
+    /// Expand a nested clip back into the clips it was rendered from.
+    UnnestClip(Uuid),
     /// Extract the audio from a video timeline clip onto the A track below it.
     /// Mutes audio on the source video clip and creates a linked audio clip.
     ExtractAudioTrack(Uuid),
+    /// Group a clip with every clip on another track that overlaps it in time,
+    /// so they move, trim, split and delete together.
+    LinkOverlapping(Uuid),
+    /// Take a clip out of its group.
+    UnlinkClip(Uuid),
+    /// Choose which audio stream of a multi-stream library file feeds preview,
+    /// waveform and export. `None` = ffmpeg's best audio stream.
+    SetClipAudioStream {
+        media_id: Uuid,
+        stream: Option<usize>,
+    },
+    /// Override how export interprets a library file's YUV range
+    /// (`Auto` = trust the probed tag).
+    SetClipColorRange {
+        media_id: Uuid,
+        mode: ColorRangeMode,
+    },
+    /// Override whether export deinterlaces a library file
+    /// (`Auto` = trust the probed field order).
+    SetClipDeinterlace {
+        media_id: Uuid,
+        mode: DeinterlaceMode,
+    },
     /// Set per-clip gain (0.0–2.0). Applied multiplicatively with global volume.
     SetClipVolume {
         id: Uuid,
         volume: f32,
     },
+    /// Silence a clip's audio (`audio_muted`) in preview and export, or bring
+    /// it back. A video whose audio was extracted is muted via its A-row clip.
+    SetClipMuted {
+        id: Uuid,
+        muted: bool,
+    },
     /// Set per-clip audio fade-in duration (seconds, 0.0 = none).
     SetClipFadeIn {
         id: Uuid,
@@ -81,7 +255,8 @@ pub enum EditorCommand {
     /// Snapshot the current ProjectState onto the undo stack and clear redo.
     /// Emitted by timeline.rs immediately before any user-visible mutation
     /// (button click, drag_started). Never emitted during per-frame drag updates.
-    PushUndoSnapshot,
+    /// The label names the edit ("Trim clip") for the Undo / Redo tooltips.
+    PushUndoSnapshot(&'static str),
     /// Restore the most recent undo snapshot.
     Undo,
     /// Re-apply the most recently undone snapshot.
@@ -91,11 +266,52 @@ pub enum EditorCommand {
     /// Emitted by ExportModule when the user clicks Render. `filename` is the
     /// bare stem (no extension, no directory); app.rs opens the save dialog and
     /// calls MediaWorker::start_encode with the resolved PathBuf.
+    /// `format` picks the container/codec pair (MP4 or WebM) and therefore
+    /// the extension appended to `filename`.
+    /// `verbose_log` adds per-packet timestamps to the render log written
+    /// next to the output.
+    /// `error_resilient` drops corrupt source frames (holding the last good
+    /// one) instead of failing the render.
+    /// `dither` picks how >8-bit sources are quantized to 8 bits.
+    /// `target_kbps` switches H.264 formats from constant quality to a
+    /// two-pass encode averaging that video bitrate.
+    /// `limit_secs` renders only the first N seconds of the timeline to a temp
+    /// file instead (no save dialog) — the "test render" used to check
+    /// settings before committing to a full export.
+    /// `next_to_source` skips the dialog and writes beside the first timeline
+    /// clip's source file, numbering the name (`_2`, `_3`, …) rather than
+    /// overwriting an earlier render.
+    /// `metadata` holds the title/artist/comment/date tags written into the
+    /// output container.
     RenderMP4 {
         filename: String,
         width: u32,
         height: u32,
         fps: u32,
+        format: ExportFormat,
+        verbose_log: bool,
+        error_resilient: bool,
+        dither: DitherMode,
+        target_kbps: Option<u32>,
+        limit_secs: Option<f64>,
+        next_to_source: bool,
+        metadata: ExportMetadata,
+        /// Render every track, or only one (an A-row renders a WAV stem).
+        tracks: ExportTracks,
+    },
+    /// Write the timeline as an edit decision list. app.rs opens a save
+    /// dialog for `filename` + the format's extension; timecodes use `fps`.
+    ExportCutList {
+        filename: String,
+        fps: u32,
+        format: CutListFormat,
+    },
+    /// Render a contact sheet of the timeline to a PNG or JPEG. app.rs opens
+    /// a save dialog seeded with `filename` and hands the planned cells to
+    /// the media worker.
+    ExportStoryboard {
+        filename: String,
+        options: StoryboardOptions,
     },
     /// Request the active encode job (if any) to stop. The encode thread
     /// observes its cancel AtomicBool and exits after finishing the current frame.
@@ -103,6 +319,13 @@ pub enum EditorCommand {
     /// Clear encode_job / encode_progress / encode_done / encode_error in
     /// ProjectState. Emitted when the user dismisses a done/error banner.
     ClearEncodeStatus,
+    /// Open a finished render in the OS default player.
+    OpenExternally(PathBuf),
+    /// Set or clear (`None`) the project watermark burned into every
+    /// exported frame.
+    SetWatermark(Option<Watermark>),
+    /// Set or clear (`None`) the timeline beat grid.
+    SetBeatGrid(Option<BeatGrid>),
     /// Set the crossfade duration (in seconds) for ALL clip boundaries at once.
     /// Convenience for the global slider; sets a Crossfade transition on every
     /// adjacent touching pair. 0.0 clears all transitions (all become Cut).
@@ -120,11 +343,64 @@ pub enum EditorCommand {
         id: Uuid,
         filter: FilterParams,
     },
+    /// Enable or disable a clip and its group. Disabled clips are skipped by
+    /// preview and export but stay on the timeline.
+    SetClipEnabled {
+        id: Uuid,
+        enabled: bool,
+    },
+    /// Turn export-time stabilization on (with its settings) or off for a
+    /// video clip.
+    SetClipStabilize {
+        id: Uuid,
+        stabilize: Option<Stabilize>,
+    },
+    /// Set a clip's organisation tag color (`None` clears it) and note.
+    SetClipTag {
+        id: Uuid,
+        tag_color: Option<[u8; 3]>,
+        note: String,
+    },
+    /// Use the frame at source time `at` as the clip's poster thumbnail
+    /// (`None` = back to the library thumbnail).
+    SetClipPoster {
+        id: Uuid,
+        at: Option<f64>,
+    },
 
     // ── View / UI ────────────────────────────────────────────────────────────
     SetAspectRatio(AspectRatio),
+    /// Change the project frame rate the timeline steps, snaps and trims in.
+    /// With `conform`, every clip edge, marker and transition length is also
+    /// rounded onto the new frame grid (`ProjectState::conform_to_fps`).
+    SetProjectFps {
+        fps: u32,
+        conform: bool,
+    },
     SetTimelineZoom(f32),
+    /// Scroll the timeline so `t` seconds sits at the left edge of the visible
+    /// track area. Emitted by the overview strip; applied on the next frame.
+    ScrollTimelineTo(f64),
     ClearSaveStatus,
+    /// Replace the decode / cache limits. app.rs applies them to the frame
+    /// cache and `MediaWorker` immediately and persists them on next save.
+    SetPerformancePrefs(PerformancePrefs),
+    /// Replace the editing-feel preferences (Space toggle vs hold-to-play).
+    /// Persisted with the performance prefs on next save.
+    SetPlaybackPrefs(PlaybackPrefs),
+    /// Swap or hide the side panels. Persisted with the other prefs.
+    SetLayoutPrefs(LayoutPrefs),
+    /// Change the scratch folder for extracted audio. New extractions go
+    /// there; existing WAVs stay put. Persisted with the other prefs.
+    SetStoragePrefs(StoragePrefs),
+    /// Turn the render-finished chime / OS notification on or off.
+    /// Persisted with the other prefs.
+    SetNotifyPrefs(NotifyPrefs),
+    /// Delete VeloCut WAVs in the scratch folder that no library clip is
+    /// playing from.
+    ClearScratchCache,
+    /// Re-measure `ProjectState::scratch_usage`.
+    RefreshScratchUsage,
     SaveFrameToDisk {
         path: PathBuf,
         timestamp: f64,
@@ -147,6 +423,14 @@ impl EditorCommand {
     /// Returns `Ok(())` if valid, or an error message describing the problem.
     pub fn validate(&self, state: &ProjectState) -> Result<(), String> {
         match self {
+            EditorCommand::PlayAround(t) => {
+                if !t.is_finite() || *t < 0.0 {
+                    return Err("Play-around point must be a non-negative time".to_string());
+                }
+                if state.total_duration() <= 0.0 {
+                    return Err("Nothing on the timeline to play".to_string());
+                }
+            }
             EditorCommand::SetPlayhead(t) => {
                 if *t < 0.0 {
                     return Err("Playhead cannot be negative".to_string());
@@ -164,6 +448,20 @@ impl EditorCommand {
                     return Err("Volume must be between 0.0 and 2.0".to_string());
                 }
             }
+            EditorCommand::SetPreviewRate(r) => {
+                if !PREVIEW_RATES.contains(r) {
+                    return Err(format!("Unsupported preview rate {r}x"));
+                }
+            }
+            EditorCommand::SetProjectFps { fps, .. } => {
+                if !PROJECT_FPS_RANGE.contains(fps) {
+                    return Err(format!(
+                        "Project frame rate must be {}–{} fps",
+                        PROJECT_FPS_RANGE.start(),
+                        PROJECT_FPS_RANGE.end()
+                    ));
+                }
+            }
             EditorCommand::SetClipVolume { id, volume } => {
                 if state.timeline.iter().all(|c| c.id != *id) {
                     return Err("Clip not found in timeline".to_string());
@@ -172,6 +470,23 @@ impl EditorCommand {
                     return Err("Volume must be between 0.0 and 2.0".to_string());
                 }
             }
+            EditorCommand::SetClipMuted { id, muted } => {
+                let clip = state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == *id)
+                    .ok_or_else(|| "Clip not found in timeline".to_string())?;
+                if clip.track_row % 2 == 0 && clip.linked_clip_id.is_some() {
+                    return Err("Audio was extracted — mute the linked audio clip".to_string());
+                }
+                let is_image = state
+                    .library
+                    .iter()
+                    .any(|l| l.id == clip.media_id && l.clip_type == ClipType::Image);
+                if is_image && !*muted {
+                    return Err("Still images have no audio".to_string());
+                }
+            }
             EditorCommand::SetClipFadeIn { id, secs } => {
                 let clip = state.timeline.iter().find(|c| c.id == *id);
                 match clip {
@@ -251,8 +566,14 @@ impl EditorCommand {
                 if *new_start < 0.0 {
                     return Err("Clip start time cannot be negative".to_string());
                 }
-                if clip.linked_clip_id.is_some() && *new_row % 2 == 0 {
-                    return Err("Cannot move audio-extracted clip to video track".to_string());
+                // Clips move between tracks of their own kind only: V1 ↔ V2,
+                // A1 ↔ A2. Extracted audio is an A-row clip like any other.
+                if *new_row % 2 != clip.track_row % 2 {
+                    return Err(if clip.track_row % 2 == 0 {
+                        "Video clips can only move between V tracks".to_string()
+                    } else {
+                        "Audio clips can only move between A tracks".to_string()
+                    });
                 }
                 // Enforce row range: 0-3 for the 4-track layout
                 if *new_row > 3 {
@@ -275,12 +596,20 @@ impl EditorCommand {
                 }
             }
             EditorCommand::TrimClipEnd { id, new_duration } => {
-                if state.timeline.iter().all(|c| c.id != *id) {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
                     return Err("Clip not found in timeline".to_string());
-                }
+                };
                 if *new_duration <= 0.0 {
                     return Err("Duration must be positive".to_string());
                 }
+                if let Some(max) = state.max_clip_duration(clip) {
+                    if *new_duration > max + 1e-6 {
+                        return Err(format!(
+                            "Trim of {:.2}s exceeds the {:.2}s of source left after the in-point",
+                            new_duration, max
+                        ));
+                    }
+                }
             }
             EditorCommand::SplitClipAt(t) => {
                 if *t < 0.0 {
@@ -294,15 +623,8 @@ impl EditorCommand {
                     ));
                 }
                 // Check there's a clip at this position with room to split
-                let min_dur = 2.0 / 30.0;
-                let splittable = state.timeline.iter().any(|c| {
-                    *t > c.start_time + min_dur && *t < c.start_time + c.duration - min_dur
-                });
-                if !splittable {
-                    return Err(format!(
-                        "No clip at {:.2}s with enough room to split (need > {:.3}s on each side)",
-                        t, min_dur
-                    ));
+                if let Some(reason) = state.split_blocker(*t) {
+                    return Err(reason);
                 }
             }
             EditorCommand::SetTimelineZoom(z) => {
@@ -310,6 +632,17 @@ impl EditorCommand {
                     return Err("Zoom must be between 0.01 and 1000.0".to_string());
                 }
             }
+            EditorCommand::ScrollTimelineTo(t) => {
+                if *t < 0.0 {
+                    return Err("Scroll position cannot be negative".to_string());
+                }
+            }
+            EditorCommand::SetPerformancePrefs(prefs) => {
+                prefs.validate()?;
+            }
+            EditorCommand::SetStoragePrefs(prefs) => {
+                prefs.validate()?;
+            }
             EditorCommand::SetCrossfadeDuration(d) => {
                 if *d < 0.0 {
                     return Err("Crossfade duration cannot be negative".to_string());
@@ -319,31 +652,173 @@ impl EditorCommand {
                 }
             }
             EditorCommand::RenderMP4 {
-                width, height, fps, ..
+                width,
+                height,
+                fps,
+                format,
+                target_kbps,
+                limit_secs,
+                metadata,
+                tracks,
+                ..
             } => {
+                if !metadata.date_is_valid() {
+                    return Err(format!(
+                        "Metadata date \"{}\" must be YYYY-MM-DD",
+                        metadata.date.trim()
+                    ));
+                }
+                if let Some(kbps) = target_kbps {
+                    if !format.supports_two_pass() {
+                        return Err(format!(
+                            "{} export has no two-pass bitrate mode",
+                            format.name()
+                        ));
+                    }
+                    if !TARGET_KBPS_RANGE.contains(kbps) {
+                        return Err(format!(
+                            "Target bitrate must be between {} and {} kb/s",
+                            TARGET_KBPS_RANGE.start(),
+                            TARGET_KBPS_RANGE.end()
+                        ));
+                    }
+                }
+                if let Some(secs) = limit_secs {
+                    if !secs.is_finite() || *secs <= 0.0 {
+                        return Err("Test render length must be positive".to_string());
+                    }
+                }
                 if *width == 0 || *height == 0 {
                     return Err("Render dimensions must be non-zero".to_string());
                 }
                 if *width % 2 != 0 || *height % 2 != 0 {
                     return Err("Render dimensions must be even (YUV420P requirement)".to_string());
                 }
-                if ![24u32, 30, 60].contains(fps) {
-                    return Err("Frame rate must be 24, 30, or 60 fps".to_string());
+                if !(1..=120).contains(fps) {
+                    return Err("Frame rate must be between 1 and 120 fps".to_string());
                 }
                 if state.timeline.is_empty() {
                     return Err("Cannot render: timeline is empty".to_string());
                 }
+                let loading = state
+                    .library
+                    .iter()
+                    .any(|l| l.is_loading() && state.timeline.iter().any(|tc| tc.media_id == l.id));
+                if loading {
+                    return Err("Cannot render: media is still loading".to_string());
+                }
+                if let ExportTracks::Only(row) = tracks {
+                    if *row > 3 {
+                        return Err(format!("Track row {row} does not exist"));
+                    }
+                    // A muted clip still draws picture but adds nothing to a stem.
+                    let playable = state.timeline.iter().any(|tc| {
+                        tc.track_row == *row
+                            && tc.enabled
+                            && !(tracks.is_audio_stem() && tc.audio_muted)
+                    });
+                    if !playable {
+                        return Err("Cannot render: that track has nothing to play".to_string());
+                    }
+                }
+            }
+            EditorCommand::ExportCutList { fps, .. } => {
+                if *fps == 0 {
+                    return Err("Cut list frame rate must be non-zero".to_string());
+                }
+                if state.timeline.is_empty() {
+                    return Err("Cannot export cut list: timeline is empty".to_string());
+                }
+            }
+            EditorCommand::ExportStoryboard { options, .. } => {
+                options.validate()?;
+                if state.timeline.is_empty() {
+                    return Err("Cannot export storyboard: timeline is empty".to_string());
+                }
+            }
+            EditorCommand::OpenExternally(path) => {
+                if !path.exists() {
+                    return Err(format!("File not found: {}", path.display()));
+                }
             }
             EditorCommand::CancelEncode(job_id) => {
                 if state.encode_job != Some(*job_id) {
                     return Err("No active encode job with this ID".to_string());
                 }
             }
+            EditorCommand::AddMarker(t) => {
+                if !t.is_finite() || *t < 0.0 {
+                    return Err("Marker time must be non-negative".to_string());
+                }
+            }
+            EditorCommand::MoveMarker { id, time } => {
+                if !time.is_finite() || *time < 0.0 {
+                    return Err("Marker time must be non-negative".to_string());
+                }
+                if state.markers.iter().all(|m| m.id != *id) {
+                    return Err("Marker not found".to_string());
+                }
+            }
+            EditorCommand::DeleteMarker(id) => {
+                if state.markers.iter().all(|m| m.id != *id) {
+                    return Err("Marker not found".to_string());
+                }
+            }
+            EditorCommand::SetWatermark(Some(wm)) => {
+                wm.validate()?;
+            }
+            EditorCommand::SetWatermark(None) => {}
+            EditorCommand::SetBeatGrid(Some(grid)) => {
+                grid.validate()?;
+            }
+            EditorCommand::SetBeatGrid(None) => {}
+            EditorCommand::SetClipEnabled { id, .. } => {
+                if state.timeline.iter().all(|c| c.id != *id) {
+                    return Err("Clip not found in timeline".to_string());
+                }
+            }
             EditorCommand::SetClipFilter { id, .. } => {
                 if state.timeline.iter().all(|c| c.id != *id) {
                     return Err("Clip not found in timeline".to_string());
                 }
             }
+            EditorCommand::SetClipStabilize { id, stabilize } => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if let Some(stab) = stabilize {
+                    let still = state
+                        .library
+                        .iter()
+                        .any(|l| l.id == clip.media_id && l.clip_type == ClipType::Image);
+                    if clip.track_row % 2 != 0 || clip.freeze_at.is_some() || still {
+                        return Err("Stabilization needs a moving video clip".to_string());
+                    }
+                    stab.validate()?;
+                }
+            }
+            EditorCommand::SetClipTag { id, note, .. } => {
+                if state.timeline.iter().all(|c| c.id != *id) {
+                    return Err("Clip not found in timeline".to_string());
+                }
+                if note.chars().count() > 200 {
+                    return Err("Clip note cannot exceed 200 characters".to_string());
+                }
+            }
+            EditorCommand::SetClipPoster { id, at } => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if let Some(at) = at {
+                    if clip.track_row % 2 != 0 || clip.freeze_at.is_some() {
+                        return Err("Poster frames need a moving video clip".to_string());
+                    }
+                    let end = clip.source_offset + clip.duration;
+                    if *at < clip.source_offset || *at > end {
+                        return Err("Poster frame must lie within the clip".to_string());
+                    }
+                }
+            }
             EditorCommand::DeleteTimelineClip(id)
             | EditorCommand::SelectTimelineClip(Some(id))
             | EditorCommand::ExtractAudioTrack(id) => {
@@ -367,6 +842,335 @@ impl EditorCommand {
                     return Err("Clip not found in library".to_string());
                 }
             }
+            EditorCommand::AddToTimeline { media_id, part, .. }
+            | EditorCommand::RippleInsertToTimeline { media_id, part, .. } => {
+                let Some(lib) = state.library.iter().find(|c| c.id == *media_id) else {
+                    return Err("Clip not found in library".to_string());
+                };
+                if *part != DropPart::Both && lib.clip_type != ClipType::Video {
+                    return Err("Only video clips can be split into audio or video".to_string());
+                }
+                if let EditorCommand::RippleInsertToTimeline { at_time, .. } = self {
+                    if *at_time < 0.0 {
+                        return Err("Insert time cannot be negative".to_string());
+                    }
+                }
+            }
+            EditorCommand::AddClipsToTimeline {
+                media_ids, at_time, ..
+            } => {
+                if media_ids.is_empty() {
+                    return Err("No clips to add".to_string());
+                }
+                if media_ids
+                    .iter()
+                    .any(|id| !state.library.iter().any(|l| l.id == *id))
+                {
+                    return Err("Clip not found in library".to_string());
+                }
+                if *at_time < 0.0 {
+                    return Err("Drop time cannot be negative".to_string());
+                }
+            }
+            EditorCommand::SetSourceViewer(Some(id)) => {
+                let Some(lib) = state.library.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in library".to_string());
+                };
+                if lib.clip_type == ClipType::Image {
+                    return Err("Stills have no source range to mark".to_string());
+                }
+            }
+            EditorCommand::InsertSourceRange {
+                media_id,
+                source_in,
+                source_out,
+                at_time,
+            } => {
+                let Some(lib) = state.library.iter().find(|c| c.id == *media_id) else {
+                    return Err("Clip not found in library".to_string());
+                };
+                if lib.clip_type == ClipType::Image {
+                    return Err("Stills have no source range to insert".to_string());
+                }
+                if *source_in < 0.0 || *source_out - *source_in < state.frame_secs() {
+                    return Err(
+                        "Out point must be at least one frame after the in point".to_string()
+                    );
+                }
+                if lib.duration_probed && *source_out > lib.duration + 1e-3 {
+                    return Err("Out point is past the end of the source".to_string());
+                }
+                if *at_time < 0.0 {
+                    return Err("Insert time cannot be negative".to_string());
+                }
+            }
+            EditorCommand::InsertFreezeFrame { at, duration } => {
+                if *duration <= 0.0 || *duration > 60.0 {
+                    return Err("Freeze duration must be between 0 and 60 seconds".to_string());
+                }
+                let on_video = state.timeline.iter().any(|c| {
+                    c.track_row % 2 == 0 && *at >= c.start_time && *at < c.start_time + c.duration
+                });
+                if !on_video {
+                    return Err("No video clip under the playhead to freeze".to_string());
+                }
+            }
+            EditorCommand::InsertSlate { at, duration, .. } => {
+                if *duration <= 0.0 || *duration > 60.0 {
+                    return Err("Slate duration must be between 0 and 60 seconds".to_string());
+                }
+                if !at.is_finite() || *at < 0.0 {
+                    return Err("Slate position must be on the timeline".to_string());
+                }
+            }
+            EditorCommand::MatchFrame(clip_id) => {
+                let t = state.current_time;
+                let found = match clip_id {
+                    Some(id) => state.timeline.iter().any(|c| c.id == *id),
+                    None => state
+                        .timeline
+                        .iter()
+                        .any(|c| t >= c.start_time && t < c.start_time + c.duration),
+                };
+                if !found {
+                    return Err("No timeline clip to match".to_string());
+                }
+            }
+            EditorCommand::DetectScenes { id, threshold } => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if clip.track_row % 2 != 0 || clip.freeze_at.is_some() {
+                    return Err("Scene detection needs a moving video clip".to_string());
+                }
+                if !(*threshold > 0.0 && *threshold < 1.0) {
+                    return Err(format!(
+                        "Scene threshold {threshold:.2} must be between 0 and 1"
+                    ));
+                }
+                if state.scene_detecting.is_some() {
+                    return Err("Scene detection already running".to_string());
+                }
+            }
+            EditorCommand::SplitAtScenes(id) => {
+                if state.timeline.iter().all(|c| c.id != *id) {
+                    return Err("Clip not found in timeline".to_string());
+                }
+                match &state.scene_cuts {
+                    Some((cid, cuts)) if cid == id && !cuts.is_empty() => {}
+                    _ => return Err("No detected scene cuts for this clip".to_string()),
+                }
+            }
+            EditorCommand::DetectSilence {
+                id,
+                threshold_db,
+                min_gap,
+            } => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if clip.track_row % 2 == 0 && clip.linked_clip_id.is_some() {
+                    return Err("Audio was extracted — scan the linked audio clip".to_string());
+                }
+                let is_image = state
+                    .library
+                    .iter()
+                    .any(|l| l.id == clip.media_id && l.clip_type == ClipType::Image);
+                if is_image || clip.freeze_at.is_some() {
+                    return Err("Silence trim needs a clip with audio".to_string());
+                }
+                if !(-80.0..=0.0).contains(threshold_db) {
+                    return Err(format!(
+                        "Silence threshold {threshold_db:.0} dBFS must be between -80 and 0"
+                    ));
+                }
+                if !(*min_gap >= 0.0 && *min_gap <= 10.0) {
+                    return Err(format!("Minimum gap {min_gap:.2}s must be 0–10 s"));
+                }
+                if state.silence_detecting.is_some() {
+                    return Err("Silence detection already running".to_string());
+                }
+            }
+            EditorCommand::TrimSilence(id) => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                let Some((_, Some((start, end)))) = state.silence_range.filter(|(c, _)| c == id)
+                else {
+                    return Err("No detected sound range for this clip".to_string());
+                };
+                let (from, to) = (clip.source_offset, clip.source_offset + clip.duration);
+                if start <= from + 1e-3 && end >= to - 1e-3 {
+                    return Err("No silence to trim".to_string());
+                }
+                if end.min(to) - start.max(from) <= 0.0 {
+                    return Err("Sound range is outside the clip".to_string());
+                }
+            }
+            EditorCommand::DetectAudioSync { id, reference } => {
+                for cid in [id, reference] {
+                    let Some(clip) = state.timeline.iter().find(|c| c.id == *cid) else {
+                        return Err("Clip not found in timeline".to_string());
+                    };
+                    let is_image = state
+                        .library
+                        .iter()
+                        .any(|l| l.id == clip.media_id && l.clip_type == ClipType::Image);
+                    if is_image || clip.freeze_at.is_some() {
+                        return Err("Audio sync needs two clips with audio".to_string());
+                    }
+                }
+                if state.group_of(*id).contains(reference) {
+                    return Err("Clip is already linked to the reference".to_string());
+                }
+                if state.sync_detecting.is_some() {
+                    return Err("Audio sync already running".to_string());
+                }
+            }
+            EditorCommand::ApplyAudioSync(id) => {
+                let Some((_, reference, Some(offset))) =
+                    state.audio_sync.filter(|(c, _, _)| c == id)
+                else {
+                    return Err("No audio sync result for this clip".to_string());
+                };
+                let Some(start) = state.synced_start(*id, reference, offset) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if !state.move_is_clear(*id, start) {
+                    return Err(format!(
+                        "Can't move to {start:.2}s — it would start before 0 or overlap another clip"
+                    ));
+                }
+            }
+            EditorCommand::DetectSpeech {
+                id,
+                speech,
+                threshold_db,
+            } => {
+                for cid in [id, speech] {
+                    let Some(clip) = state.timeline.iter().find(|c| c.id == *cid) else {
+                        return Err("Clip not found in timeline".to_string());
+                    };
+                    let is_image = state
+                        .library
+                        .iter()
+                        .any(|l| l.id == clip.media_id && l.clip_type == ClipType::Image);
+                    if is_image || clip.audio_muted {
+                        return Err("Auto-duck needs two clips with audio".to_string());
+                    }
+                }
+                if id == speech {
+                    return Err("Pick a different clip to duck under".to_string());
+                }
+                if !(-70.0..=-10.0).contains(threshold_db) {
+                    return Err(format!(
+                        "Speech threshold {threshold_db:.0} dBFS must be between -70 and -10"
+                    ));
+                }
+                if state.duck_detecting.is_some() {
+                    return Err("Speech detection already running".to_string());
+                }
+            }
+            EditorCommand::ApplyDuck { id, duck } => {
+                duck.validate()?;
+                let Some(SpeechRegions {
+                    speech, regions, ..
+                }) = state.speech_regions.as_ref().filter(|r| r.clip == *id)
+                else {
+                    return Err("No detected speech for this clip".to_string());
+                };
+                if regions.is_empty() {
+                    return Err("No speech above the threshold".to_string());
+                }
+                for cid in [id, speech] {
+                    if state.timeline.iter().all(|c| c.id != *cid) {
+                        return Err("Clip not found in timeline".to_string());
+                    }
+                }
+            }
+            EditorCommand::ClearVolumeKeys(id) => {
+                let Some(clip) = state.timeline.iter().find(|c| c.id == *id) else {
+                    return Err("Clip not found in timeline".to_string());
+                };
+                if clip.volume_keys.is_empty() {
+                    return Err("Clip has no volume keys".to_string());
+                }
+            }
+            EditorCommand::NestRange { start, end } => {
+                if !start.is_finite() || !end.is_finite() || *start < 0.0 || end <= start {
+                    return Err("Nest range must be a positive span".to_string());
+                }
+                if state.encode_job.is_some() {
+                    return Err("Cannot nest while a render is running".to_string());
+                }
+                if !state.video_covers(*start, *end) {
+                    return Err("Cannot nest: the range has a gap in the video tracks".to_string());
+                }
+                let loading = state.library.iter().any(|l| {
+                    l.is_loading()
+                        && state.timeline.iter().any(|tc| {
+                            tc.media_id == l.id
+                                && tc.start_time < *end
+                                && *start < tc.start_time + tc.duration
+                        })
+                });
+                if loading {
+                    return Err("Cannot nest: media is still loading".to_string());
+                }
+            }
+            EditorCommand::UnnestClip(id) => {
+                if !state.nests.iter().any(|n| n.clip_id == *id) {
+                    return Err("Clip is not a nested clip".to_string());
+                }
+                if !state.can_unnest(*id) {
+                    return Err(
+                        "Cannot un-nest: the clip was trimmed or its clips would overlap others"
+                            .to_string(),
+                    );
+                }
+            }
+            EditorCommand::LinkOverlapping(id) => {
+                let clip = state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == *id)
+                    .ok_or_else(|| "Clip not found in timeline".to_string())?;
+                if !state.timeline.iter().any(|o| {
+                    o.track_row != clip.track_row
+                        && o.start_time < clip.start_time + clip.duration
+                        && clip.start_time < o.start_time + o.duration
+                }) {
+                    return Err("No clip on another track overlaps this one".to_string());
+                }
+            }
+            EditorCommand::UnlinkClip(id) => {
+                let clip = state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == *id)
+                    .ok_or_else(|| "Clip not found in timeline".to_string())?;
+                if clip.group_id.is_none() {
+                    return Err("Clip is not linked".to_string());
+                }
+            }
+            EditorCommand::SetClipAudioStream { media_id, stream } => {
+                let lib = state
+                    .library
+                    .iter()
+                    .find(|c| c.id == *media_id)
+                    .ok_or_else(|| "Clip not found in library".to_string())?;
+                if let Some(idx) = stream {
+                    if lib.audio_streams.iter().all(|s| s.index != *idx) {
+                        return Err(format!("Audio stream #{idx} not found in this file"));
+                    }
+                }
+            }
+            EditorCommand::SetClipColorRange { media_id, .. }
+            | EditorCommand::SetClipDeinterlace { media_id, .. } => {
+                if state.library.iter().all(|c| c.id != *media_id) {
+                    return Err("Clip not found in library".to_string());
+                }
+            }
             EditorCommand::SetTransition {
                 after_clip_id,
                 kind,
@@ -419,19 +1223,41 @@ impl EditorCommand {
                     return Err("Import path cannot be empty".to_string());
                 }
             }
+            EditorCommand::SaveProjectCopy(path) => {
+                if path.as_os_str().is_empty() {
+                    return Err("Project path cannot be empty".to_string());
+                }
+            }
+            EditorCommand::ImportProject { path, timeline_at } => {
+                if path.as_os_str().is_empty() {
+                    return Err("Project path cannot be empty".to_string());
+                }
+                if let Some(t) = timeline_at {
+                    if !t.is_finite() || *t < 0.0 {
+                        return Err("Import position must be a non-negative time".to_string());
+                    }
+                }
+            }
             // Commands with no validation requirements
             EditorCommand::Play
             | EditorCommand::Pause
             | EditorCommand::Stop
             | EditorCommand::ToggleMute
-            | EditorCommand::AddToTimeline { .. }
             | EditorCommand::SelectTimelineClip(None)
             | EditorCommand::SelectLibraryClip(None)
             | EditorCommand::SetAspectRatio(_)
             | EditorCommand::ClearSaveStatus
+            | EditorCommand::SetPlaybackPrefs(_)
+            | EditorCommand::SetLayoutPrefs(_)
+            | EditorCommand::SetNotifyPrefs(_)
+            | EditorCommand::SetEffectsBypass(_)
+            | EditorCommand::ClearScratchCache
+            | EditorCommand::ProbeDeferredMedia
+            | EditorCommand::RefreshScratchUsage
+            | EditorCommand::SetSourceViewer(None)
             | EditorCommand::ClearEncodeStatus
             | EditorCommand::ClearProject
-            | EditorCommand::PushUndoSnapshot
+            | EditorCommand::PushUndoSnapshot(_)
             | EditorCommand::Undo
             | EditorCommand::Redo => {}
         }
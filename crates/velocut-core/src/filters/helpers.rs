@@ -2,27 +2,43 @@
 //
 // Pure pixel-math for the filter system. No FFmpeg dependency.
 //
-// Two entry points:
-//   apply_filter_rgba  -- RGBA byte slice, scrub/playback path.
-//   apply_filter_yuv   -- in-place YUV420P planes, encode path.
+// Entry points:
+//   apply_filter_rgba  -- RGBA byte slice, scrub/playback path (spatial +
+//                         levels + color).
+//   apply_filter_yuv   -- in-place YUV420P planes, encode path (color only).
+//   apply_spatial_luma -- in-place strided 8-bit plane, encode path (spatial);
+//                         the encoder reaches it via helpers::yuv so ffmpeg
+//                         frame strides are honoured.
+//   apply_levels_luma  -- in-place strided 8-bit plane, encode path (levels),
+//                         also reached via helpers::yuv.
+//   luma_histogram_rgba -- 256-bin luma histogram for the levels editor.
 //
-// Both use rayon par_chunks_mut (same pattern as transition apply_rgba).
-// Both skip all work when params.is_identity().
+// All use rayon par_chunks_mut (same pattern as transition apply_rgba).
+// All skip work when the relevant half of the params is identity.
 //
-// Op order: brightness -> contrast -> gamma -> saturation -> hue -> temperature.
-// All values clamped to [0,1] before writing back.
+// Op order: sharpen/blur -> levels -> brightness -> contrast -> gamma ->
+// saturation -> hue -> temperature. All values clamped to [0,1] before writing back.
 
-use super::FilterParams;
+use super::{FilterParams, Levels, SpatialFilter, SpatialKind};
 use rayon::prelude::*;
 
 // ── RGBA path (scrub / playback) ──────────────────────────────────────────────
 
 /// Apply `params` in-place to an RGBA byte buffer (len == w * h * 4).
 /// No-ops immediately if params.is_identity().
-pub fn apply_filter_rgba(pixels: &mut [u8], params: &FilterParams) {
+pub fn apply_filter_rgba(pixels: &mut [u8], w: usize, h: usize, params: &FilterParams) {
     if params.is_identity() {
         return;
     }
+    if let Some(spatial) = params.spatial {
+        apply_spatial_rgba(pixels, w, h, &spatial);
+    }
+    if let Some(levels) = params.levels {
+        apply_levels_rgba(pixels, &levels);
+    }
+    if params.is_color_identity() {
+        return;
+    }
     let p = params.apply_strength();
 
     pixels.par_chunks_mut(4).for_each(|px| {
@@ -49,14 +65,17 @@ pub fn apply_filter_rgba(pixels: &mut [u8], params: &FilterParams) {
 ///   u_plane  (w/2) * (h/2) bytes
 ///   v_plane  (w/2) * (h/2) bytes
 ///
-/// No-ops immediately if params.is_identity().
+/// Color ops only — `params.spatial` needs plane dimensions and is applied
+/// separately through `apply_spatial_luma`.
+///
+/// No-ops immediately if params.is_color_identity().
 pub fn apply_filter_yuv(
     y_plane: &mut [u8],
     u_plane: &mut [u8],
     v_plane: &mut [u8],
     params: &FilterParams,
 ) {
-    if params.is_identity() {
+    if params.is_color_identity() {
         return;
     }
     let p = params.apply_strength();
@@ -120,6 +139,164 @@ pub fn apply_filter_yuv(
     }
 }
 
+// ── Spatial path (sharpen / blur) ─────────────────────────────────────────────
+
+/// Apply a sharpen / blur pass in-place to one 8-bit plane (luma).
+///
+/// `stride` is the byte distance between rows (>= w), so ffmpeg frame planes
+/// can be filtered without repacking; padding bytes are never written.
+/// Outputs are clamped to 0..=255. No-ops if `filter.is_identity()`.
+pub fn apply_spatial_luma(
+    plane: &mut [u8],
+    w: usize,
+    h: usize,
+    stride: usize,
+    filter: &SpatialFilter,
+) {
+    if filter.is_identity() || w == 0 || h == 0 || stride < w {
+        return;
+    }
+    if plane.len() < stride * (h - 1) + w {
+        return;
+    }
+    match filter.kind {
+        SpatialKind::Blur => {
+            let blurred = box_blur(plane, w, h, stride, filter.blur_radius(h));
+            plane
+                .par_chunks_mut(stride)
+                .zip(blurred.par_chunks(w))
+                .for_each(|(dst, src)| dst[..w].copy_from_slice(src));
+        }
+        SpatialKind::Sharpen => {
+            let blurred = box_blur(plane, w, h, stride, 1);
+            // 8.8 fixed point keeps the per-pixel loop integer-only.
+            let amount = (filter.sharpen_amount() * 256.0).round() as i32;
+            plane
+                .par_chunks_mut(stride)
+                .zip(blurred.par_chunks(w))
+                .for_each(|(dst, soft)| {
+                    for (y, &b) in dst[..w].iter_mut().zip(soft) {
+                        let v = *y as i32;
+                        *y = (v + (((v - b as i32) * amount) >> 8)).clamp(0, 255) as u8;
+                    }
+                });
+        }
+    }
+}
+
+/// Luma-only spatial pass on RGBA: filter a BT.601 luma plane, then add the
+/// per-pixel luma delta to R, G and B. Chroma is left as-is, matching what
+/// the encode path gets by filtering Y alone.
+fn apply_spatial_rgba(pixels: &mut [u8], w: usize, h: usize, filter: &SpatialFilter) {
+    if filter.is_identity() || pixels.len() < w * h * 4 {
+        return;
+    }
+    let luma: Vec<u8> = pixels[..w * h * 4].par_chunks(4).map(rgba_luma).collect();
+    let mut filtered = luma.clone();
+    apply_spatial_luma(&mut filtered, w, h, w, filter);
+
+    pixels
+        .par_chunks_mut(4)
+        .zip(luma.par_iter().zip(filtered.par_iter()))
+        .for_each(|(px, (&before, &after))| {
+            let d = after as i16 - before as i16;
+            for c in &mut px[..3] {
+                *c = (*c as i16 + d).clamp(0, 255) as u8;
+            }
+        });
+}
+
+// ── Levels path ───────────────────────────────────────────────────────────────
+
+/// Apply a levels remap in-place to one strided 8-bit luma plane.
+/// Padding bytes past `w` are never written. No-ops if `levels.is_identity()`.
+pub fn apply_levels_luma(plane: &mut [u8], w: usize, h: usize, stride: usize, levels: &Levels) {
+    if levels.is_identity() || w == 0 || h == 0 || stride < w {
+        return;
+    }
+    if plane.len() < stride * (h - 1) + w {
+        return;
+    }
+    let lut = levels.lut();
+    plane.par_chunks_mut(stride).for_each(|row| {
+        for y in &mut row[..w] {
+            *y = lut[*y as usize];
+        }
+    });
+}
+
+/// Luma-only levels on RGBA: remap BT.601 luma and add the delta to R, G and
+/// B, which is what the encode path gets by remapping Y with chroma untouched.
+fn apply_levels_rgba(pixels: &mut [u8], levels: &Levels) {
+    if levels.is_identity() {
+        return;
+    }
+    let lut = levels.lut();
+    pixels.par_chunks_mut(4).for_each(|px| {
+        let luma = rgba_luma(px);
+        let d = lut[luma as usize] as i16 - luma as i16;
+        for c in &mut px[..3] {
+            *c = (*c as i16 + d).clamp(0, 255) as u8;
+        }
+    });
+}
+
+/// 256-bin BT.601 luma histogram of an RGBA buffer, sampling every `step`th
+/// pixel (`step` 0 is treated as 1) so large preview frames stay cheap.
+pub fn luma_histogram_rgba(pixels: &[u8], step: usize) -> [u32; 256] {
+    let mut bins = [0u32; 256];
+    for px in pixels.chunks_exact(4).step_by(step.max(1)) {
+        bins[rgba_luma(px) as usize] += 1;
+    }
+    bins
+}
+
+#[inline]
+fn rgba_luma(px: &[u8]) -> u8 {
+    ((77 * px[0] as u32 + 150 * px[1] as u32 + 29 * px[2] as u32 + 128) >> 8) as u8
+}
+
+/// Separable box blur of radius `r` with edge clamping. Returns a packed
+/// `w * h` plane. Running sums keep the cost independent of `r`.
+fn box_blur(src: &[u8], w: usize, h: usize, stride: usize, r: usize) -> Vec<u8> {
+    let d = (2 * r + 1) as u32;
+    let half = d / 2;
+    let r = r as isize;
+
+    // Horizontal pass — one row per task.
+    let mut tmp = vec![0u8; w * h];
+    tmp.par_chunks_mut(w).enumerate().for_each(|(y, out)| {
+        let row = &src[y * stride..y * stride + w];
+        let at = |i: isize| row[i.clamp(0, w as isize - 1) as usize] as u32;
+        let mut sum: u32 = (-r..=r).map(at).sum();
+        for (x, o) in out.iter_mut().enumerate() {
+            *o = ((sum + half) / d) as u8;
+            sum = sum + at(x as isize + r + 1) - at(x as isize - r);
+        }
+    });
+
+    // Vertical pass — running column sums, rows walked top to bottom.
+    let row_at = |y: isize| {
+        let y = y.clamp(0, h as isize - 1) as usize;
+        &tmp[y * w..y * w + w]
+    };
+    let mut sums = vec![0u32; w];
+    for y in -r..=r {
+        for (s, &v) in sums.iter_mut().zip(row_at(y)) {
+            *s += v as u32;
+        }
+    }
+    let mut out = vec![0u8; w * h];
+    for (y, dst) in out.chunks_mut(w).enumerate() {
+        let (add, sub) = (row_at(y as isize + r + 1), row_at(y as isize - r));
+        for (((o, s), &a), &b) in dst.iter_mut().zip(sums.iter_mut()).zip(add).zip(sub) {
+            *o = ((*s + half) / d) as u8;
+            *s = *s + a as u32 - b as u32;
+        }
+    }
+    out
+}
+
 // ── Shared RGB helpers ────────────────────────────────────────────────────────
 
 #[inline]
@@ -219,13 +396,13 @@ fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::filters::{FilterKind, FilterParams};
+    use crate::filters::{FilterKind, FilterParams, Levels, SpatialFilter, SpatialKind};
 
     #[test]
     fn identity_rgba_no_op() {
         let orig = vec![128u8, 64, 192, 255, 200, 100, 50, 255];
         let mut px = orig.clone();
-        apply_filter_rgba(&mut px, &FilterParams::none());
+        apply_filter_rgba(&mut px, 2, 1, &FilterParams::none());
         assert_eq!(px, orig);
     }
 
@@ -235,7 +412,7 @@ mod tests {
         let mut px = orig.clone();
         let mut p = FilterParams::from_preset(FilterKind::Vivid);
         p.strength = 0.0;
-        apply_filter_rgba(&mut px, &p);
+        apply_filter_rgba(&mut px, 1, 1, &p);
         assert_eq!(px, orig);
     }
 
@@ -244,6 +421,8 @@ mod tests {
         let mut px = vec![200u8, 100, 50, 255];
         apply_filter_rgba(
             &mut px,
+            1,
+            1,
             &FilterParams::from_preset(FilterKind::BlackAndWhite),
         );
         // After full desaturate all RGB channels must be equal (greyscale luma)
@@ -265,6 +444,113 @@ mod tests {
         assert_eq!(v2, v);
     }
 
+    #[test]
+    fn blur_smooths_edge_and_skips_stride_padding() {
+        // 4x2 plane, stride 6: left half black, right half white, padding 7s.
+        let mut plane = vec![0u8, 0, 255, 255, 7, 7, 0, 0, 255, 255, 7, 7];
+        let f = SpatialFilter {
+            kind: SpatialKind::Blur,
+            strength: 1.0,
+        };
+        apply_spatial_luma(&mut plane, 4, 2, 6, &f);
+        // Radius clamps to 1 at this height: the edge pixels meet in the middle.
+        assert_eq!(&plane[..6], &[0, 85, 170, 255, 7, 7]);
+        assert_eq!(&plane[6..10], &[0, 85, 170, 255]);
+    }
+
+    #[test]
+    fn sharpen_steepens_edge_and_clamps() {
+        let mut plane = vec![50u8, 50, 200, 200];
+        let f = SpatialFilter {
+            kind: SpatialKind::Sharpen,
+            strength: 1.0,
+        };
+        apply_spatial_luma(&mut plane, 4, 1, 4, &f);
+        assert!(plane[1] < 50, "dark side of the edge gets darker");
+        assert!(plane[2] > 200, "bright side of the edge gets brighter");
+
+        let mut hot = vec![0u8, 255, 0];
+        apply_spatial_luma(&mut hot, 3, 1, 3, &f);
+        assert_eq!(hot, vec![0, 255, 0]);
+    }
+
+    #[test]
+    fn spatial_rgba_leaves_flat_color_untouched() {
+        let orig: Vec<u8> = [200u8, 40, 90, 255].repeat(9);
+        let mut px = orig.clone();
+        let p = FilterParams {
+            spatial: Some(SpatialFilter {
+                kind: SpatialKind::Sharpen,
+                strength: 1.0,
+            }),
+            ..FilterParams::none()
+        };
+        assert!(!p.is_identity());
+        apply_filter_rgba(&mut px, 3, 3, &p);
+        assert_eq!(px, orig);
+    }
+
+    #[test]
+    fn levels_stretch_range_and_skip_stride_padding() {
+        // 3x2 plane, stride 4: padding bytes are 7s.
+        let mut plane = vec![64u8, 128, 192, 7, 0, 255, 100, 7];
+        let l = Levels {
+            black: 64.0 / 255.0,
+            white: 192.0 / 255.0,
+            gamma: 1.0,
+        };
+        apply_levels_luma(&mut plane, 3, 2, 4, &l);
+        assert_eq!(&plane[..4], &[0, 128, 255, 7]);
+        assert_eq!(&plane[4..], &[0, 255, 72, 7]);
+    }
+
+    #[test]
+    fn levels_gamma_lifts_midtones_and_rgba_keeps_gray_neutral() {
+        let l = Levels {
+            gamma: 2.0,
+            ..Levels::default()
+        };
+        assert!(l.remap(0.25) > 0.25);
+        assert_eq!(l.remap(0.0), 0.0);
+        assert_eq!(l.remap(1.0), 1.0);
+
+        let mut px = vec![64u8, 64, 64, 255];
+        let p = FilterParams {
+            levels: Some(l),
+            ..FilterParams::none()
+        };
+        assert!(!p.is_identity());
+        apply_filter_rgba(&mut px, 1, 1, &p);
+        assert_eq!(px[0], px[1]);
+        assert_eq!(px[1], px[2]);
+        assert!(px[0] > 64);
+        assert_eq!(px[3], 255);
+    }
+
+    #[test]
+    fn levels_validated_keeps_points_apart() {
+        let l = Levels {
+            black: 0.6,
+            white: 0.5,
+            gamma: 9.0,
+        }
+        .validated();
+        assert_eq!(l.black, 0.6);
+        assert!(l.white >= l.black + crate::filters::LEVELS_MIN_SPAN - 1e-6);
+        assert_eq!(l.gamma, 4.0);
+    }
+
+    #[test]
+    fn histogram_counts_sampled_pixels() {
+        let px: Vec<u8> = [[0u8, 0, 0, 255], [255, 255, 255, 255]].concat().repeat(4);
+        let h = luma_histogram_rgba(&px, 1);
+        assert_eq!(h[0], 4);
+        assert_eq!(h[255], 4);
+        let h = luma_histogram_rgba(&px, 2);
+        assert_eq!(h[0], 4);
+        assert_eq!(h[255], 0);
+    }
+
     #[test]
     fn hsv_roundtrip() {
         let (r, g, b) = (0.8f32, 0.3, 0.5);
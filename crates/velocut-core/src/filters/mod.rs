@@ -7,6 +7,14 @@
 // both the encode and scrub paths skip processing entirely — zero cost for
 // clips with no filter applied.
 //
+// `FilterParams::spatial` adds an optional sharpen / blur pass on luma. It is
+// a neighbourhood op, so unlike the per-pixel color math it needs the frame
+// dimensions (see `helpers::apply_spatial_luma`).
+//
+// `FilterParams::levels` adds an optional input-levels remap on luma (black
+// point, white point, midtone gamma) for precise exposure fixes. Like
+// `spatial` it sits outside the preset and `strength` blend.
+//
 // ## Adding a preset
 // 1. Add a variant to `declare_filters!` below.
 // 2. Add its parameter values to `FilterParams::from_preset()`.
@@ -94,6 +102,9 @@ declare_filters! {
 /// | hue          | 0.0     | -180 .. 180 | degrees, wraps                   |
 /// | temperature  | 0.0     | -1.0 .. 1.0 | negative = cool/blue, positive = warm/amber |
 /// | strength     | 1.0     | 0.0 .. 1.0  | blends preset params with identity; 1.0 = full effect |
+///
+/// `spatial` and `levels` are independent of the preset and of `strength`:
+/// picking a preset keeps them, and `spatial` carries its own strength.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FilterParams {
     pub kind: FilterKind,
@@ -106,6 +117,13 @@ pub struct FilterParams {
     /// 0.0 = ignore all params (identity), 1.0 = full effect.
     /// Lets users dial-in partial preset intensity without exposing 6 sliders.
     pub strength: f32,
+    /// Optional sharpen / blur pass on luma. Absent in older project files.
+    #[serde(default)]
+    pub spatial: Option<SpatialFilter>,
+    /// Optional black / white point and midtone gamma remap on luma. Absent
+    /// in older project files.
+    #[serde(default)]
+    pub levels: Option<Levels>,
 }
 
 impl Default for FilterParams {
@@ -126,12 +144,31 @@ impl FilterParams {
             hue: 0.0,
             temperature: 0.0,
             strength: 1.0,
+            spatial: None,
+            levels: None,
         }
     }
 
     /// Returns true when this filter has no visible effect.
     /// Both encode and scrub paths call this before doing any work.
     pub fn is_identity(&self) -> bool {
+        self.is_color_identity() && !self.has_spatial() && !self.has_levels()
+    }
+
+    /// True when a sharpen / blur pass is set and would change pixels.
+    pub fn has_spatial(&self) -> bool {
+        self.spatial.is_some_and(|s| !s.is_identity())
+    }
+
+    /// True when a levels remap is set and would change pixels.
+    pub fn has_levels(&self) -> bool {
+        self.levels.is_some_and(|l| !l.is_identity())
+    }
+
+    /// Returns true when the color-correction half has no visible effect,
+    /// regardless of `spatial` and `levels`. `strength` only scales the color
+    /// params.
+    pub fn is_color_identity(&self) -> bool {
         self.strength == 0.0
             || (self.brightness == 0.0
                 && self.contrast == 1.0
@@ -157,6 +194,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.05,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Warm tint, reduced saturation, lifted blacks (analog feel)
@@ -169,6 +208,8 @@ impl FilterParams {
                 hue: 5.0,
                 temperature: 0.30,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Blue shift, slight contrast boost (overcast/moody)
@@ -181,6 +222,8 @@ impl FilterParams {
                 hue: -8.0,
                 temperature: -0.35,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Saturated, punchy (social media pop)
@@ -193,6 +236,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.0,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Greyscale
@@ -205,6 +250,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.0,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Low contrast, lifted shadows (film-fade / Instagram matte)
@@ -217,6 +264,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.08,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Warm orange/amber push (sunset / magic hour)
@@ -229,6 +278,8 @@ impl FilterParams {
                 hue: 8.0,
                 temperature: 0.45,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Deep cool blue — night / lo-fi
@@ -241,6 +292,8 @@ impl FilterParams {
                 hue: -15.0,
                 temperature: -0.50,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // High contrast, vivid, slight warm push — action / sport
@@ -253,6 +306,8 @@ impl FilterParams {
                 hue: 3.0,
                 temperature: 0.12,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
             // 180° hue rotation flips warm skin tones (orange ≈ +20°) into
             // blue/cyan — the "BGR channel swap" aesthetic without needing a
@@ -267,6 +322,8 @@ impl FilterParams {
                 hue: 180.0,
                 temperature: -0.20,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Blown highlights, heavy warm push, lifted midtones — simulates
@@ -281,6 +338,8 @@ impl FilterParams {
                 hue: 12.0,
                 temperature: 0.50,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Dreamy overexposed haze — low contrast, lifted blacks, slight
@@ -294,6 +353,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.10,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Hard B&W with crushed shadows — classic detective / neo-noir look.
@@ -307,6 +368,8 @@ impl FilterParams {
                 hue: 0.0,
                 temperature: 0.0,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
 
             // Hollywood split-tone: push shadows toward teal and highlights
@@ -321,6 +384,8 @@ impl FilterParams {
                 hue: -6.0,
                 temperature: 0.22,
                 strength: 1.0,
+                spatial: None,
+                levels: None,
             },
         };
         params.validated()
@@ -337,6 +402,8 @@ impl FilterParams {
             hue: self.hue.clamp(-180.0, 180.0),
             temperature: self.temperature.clamp(-1.0, 1.0),
             strength: self.strength.clamp(0.0, 1.0),
+            spatial: self.spatial.map(|s| s.validated()),
+            levels: self.levels.map(|l| l.validated()),
         }
     }
 
@@ -360,7 +427,142 @@ impl FilterParams {
             hue: lerp(id.hue, self.hue, s),
             temperature: lerp(id.temperature, self.temperature, s),
             strength: 1.0,
+            spatial: self.spatial,
+            levels: self.levels,
+        }
+    }
+}
+
+// ── Spatial filter ────────────────────────────────────────────────────────────
+
+/// Neighbourhood filter applied to the luma plane only (chroma untouched).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpatialKind {
+    /// Unsharp mask against a 3x3 box blur — recovers edge detail lost to
+    /// downscaling.
+    Sharpen,
+    /// Separable box blur — privacy or background softening.
+    Blur,
+}
+
+impl SpatialKind {
+    /// Human-readable label shown in the filter popup.
+    pub fn label(self) -> &'static str {
+        match self {
+            SpatialKind::Sharpen => "Sharpen",
+            SpatialKind::Blur => "Blur",
+        }
+    }
+
+    /// All variants in display order.
+    pub fn all() -> &'static [SpatialKind] {
+        &[SpatialKind::Sharpen, SpatialKind::Blur]
+    }
+}
+
+/// One sharpen / blur pass. `strength` is 0.0 ..= 1.0; 0.0 is identity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpatialFilter {
+    pub kind: SpatialKind,
+    pub strength: f32,
+}
+
+/// Unsharp-mask gain at strength 1.0.
+const MAX_SHARPEN_AMOUNT: f32 = 1.5;
+/// Frame height that gets a one-pixel blur radius per unit of strength ÷
+/// this. At 1080p full strength is an 8 px radius; the 320 px scrub proxy
+/// gets ~2 px, so preview and export look alike.
+const BLUR_HEIGHT_PER_RADIUS: f32 = 135.0;
+
+impl SpatialFilter {
+    pub fn is_identity(&self) -> bool {
+        self.strength <= 0.0
+    }
+
+    /// Clamp `strength` to 0.0 ..= 1.0.
+    pub fn validated(&self) -> Self {
+        Self {
+            kind: self.kind,
+            strength: self.strength.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Unsharp-mask gain for `Sharpen`.
+    pub fn sharpen_amount(&self) -> f32 {
+        self.strength.clamp(0.0, 1.0) * MAX_SHARPEN_AMOUNT
+    }
+
+    /// Box radius in pixels for `Blur` on a frame `height` pixels tall.
+    /// Scales with height so a proxy-sized preview matches the export.
+    pub fn blur_radius(&self, height: usize) -> usize {
+        let r = self.strength.clamp(0.0, 1.0) * height as f32 / BLUR_HEIGHT_PER_RADIUS;
+        (r.round() as usize).max(1)
+    }
+}
+
+// ── Levels ────────────────────────────────────────────────────────────────────
+
+/// Narrowest allowed gap between the black and white points, so the remap
+/// never divides by (nearly) zero.
+pub const LEVELS_MIN_SPAN: f32 = 0.02;
+
+/// Input levels on luma, all in 0.0 ..= 1.0 luma units except `gamma`.
+///
+/// Luma at or below `black` maps to 0, at or above `white` to 1, and the
+/// range between is stretched linearly and then bent by `gamma`
+/// (> 1.0 lifts midtones, < 1.0 darkens them).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Levels {
+    pub black: f32,
+    pub white: f32,
+    pub gamma: f32,
+}
+
+impl Default for Levels {
+    fn default() -> Self {
+        Self {
+            black: 0.0,
+            white: 1.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl Levels {
+    pub fn is_identity(&self) -> bool {
+        self.black <= 0.0 && self.white >= 1.0 && self.gamma == 1.0
+    }
+
+    /// Clamp to valid ranges, keeping `white` at least `LEVELS_MIN_SPAN`
+    /// above `black`.
+    pub fn validated(&self) -> Self {
+        let black = self.black.clamp(0.0, 1.0 - LEVELS_MIN_SPAN);
+        Self {
+            black,
+            white: self.white.clamp(black + LEVELS_MIN_SPAN, 1.0),
+            gamma: self.gamma.clamp(0.1, 4.0),
+        }
+    }
+
+    /// Remap one normalized luma value.
+    pub fn remap(&self, luma: f32) -> f32 {
+        let l = self.validated();
+        let t = ((luma - l.black) / (l.white - l.black)).clamp(0.0, 1.0);
+        if l.gamma == 1.0 || t <= 0.0 {
+            t
+        } else {
+            t.powf(1.0 / l.gamma)
+        }
+    }
+
+    /// The remap as an 8-bit lookup table, so the per-pixel pass is a
+    /// single index.
+    pub fn lut(&self) -> [u8; 256] {
+        let mut lut = [0u8; 256];
+        for (i, v) in lut.iter_mut().enumerate() {
+            *v = (self.remap(i as f32 / 255.0) * 255.0).round() as u8;
         }
+        lut
     }
 }
 
@@ -0,0 +1,341 @@
+// crates/velocut-core/src/helpers/edl.rs
+//
+// Edit-decision-list export — turns `state.timeline` + `state.library` into a
+// CMX3600 `.edl` or a flat JSON cut list so a rough cut can be finished in
+// another NLE. Pure string formatting: no I/O, no egui, no ffmpeg.
+//
+// Every timeline clip becomes one event with source in/out (from
+// `source_offset` + `duration`) and record in/out (from `start_time`), all
+// converted to non-drop-frame timecode at the export fps. Transitions are
+// recorded as comments only — every event is written as a cut.
+
+use crate::state::{ProjectState, TimelineClip};
+use std::path::Path;
+
+/// Output flavour for `ExportCutList`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CutListFormat {
+    /// CMX3600 edit decision list.
+    Edl,
+    /// Flat JSON array of clips with seconds and timecodes.
+    Json,
+}
+
+impl CutListFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            CutListFormat::Edl => "edl",
+            CutListFormat::Json => "json",
+        }
+    }
+
+    pub fn dialog_filter_name(self) -> &'static str {
+        match self {
+            CutListFormat::Edl => "CMX3600 EDL",
+            CutListFormat::Json => "JSON Cut List",
+        }
+    }
+
+    /// Render the cut list for `state` at `fps`. `title` fills the EDL header.
+    pub fn render(self, state: &ProjectState, title: &str, fps: u32) -> String {
+        match self {
+            CutListFormat::Edl => to_cmx3600(state, title, fps),
+            CutListFormat::Json => to_json(state, fps),
+        }
+    }
+}
+
+/// Seconds → whole frames at `fps`, rounded to the nearest frame.
+fn secs_to_frames(secs: f64, fps: u32) -> u64 {
+    (secs.max(0.0) * fps as f64).round() as u64
+}
+
+/// Format a frame count as non-drop-frame `HH:MM:SS:FF`.
+///
+/// ```
+/// use velocut_core::helpers::edl::frames_to_timecode;
+/// assert_eq!(frames_to_timecode(0, 30),      "00:00:00:00");
+/// assert_eq!(frames_to_timecode(95, 30),     "00:00:03:05");
+/// assert_eq!(frames_to_timecode(86_400, 24), "01:00:00:00");
+/// ```
+pub fn frames_to_timecode(frames: u64, fps: u32) -> String {
+    let fps = fps.max(1) as u64;
+    let ff = frames % fps;
+    let total_secs = frames / fps;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_secs / 3600,
+        (total_secs / 60) % 60,
+        total_secs % 60,
+        ff
+    )
+}
+
+/// CMX3600 reel name from a source path: file stem, uppercased, anything
+/// outside `[A-Z0-9_]` replaced by `_`, truncated to the 8-character limit.
+///
+/// ```
+/// use velocut_core::helpers::edl::reel_name;
+/// use std::path::Path;
+/// assert_eq!(reel_name(Path::new("/footage/beach day.mp4")), "BEACH_DA");
+/// assert_eq!(reel_name(Path::new("a1.mov")),                 "A1");
+/// ```
+pub fn reel_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let reel: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .take(8)
+        .collect();
+    if reel.is_empty() {
+        "AX".to_string()
+    } else {
+        reel
+    }
+}
+
+/// Human track label matching the timeline header (V1/A1/V2/A2).
+fn track_label(row: usize) -> &'static str {
+    match row {
+        0 => "V1",
+        1 => "A1",
+        2 => "V2",
+        _ => "A2",
+    }
+}
+
+/// CMX3600 channel field. Video rows carry their own audio (`B`) unless it
+/// has been extracted to an audio row, in which case the video event is `V`
+/// and the extracted clip becomes its own `A`/`A2` event.
+fn edl_channel(clip: &TimelineClip) -> &'static str {
+    match clip.track_row {
+        r if r % 2 == 0 && clip.audio_muted => "V",
+        r if r % 2 == 0 => "B",
+        1 => "A",
+        _ => "A2",
+    }
+}
+
+/// Enabled timeline clips with a resolvable library entry, in record order.
+fn events(state: &ProjectState) -> Vec<(&TimelineClip, &Path, &str)> {
+    let mut out: Vec<_> = state
+        .timeline
+        .iter()
+        .filter(|tc| tc.enabled)
+        .filter_map(|tc| {
+            state
+                .library
+                .iter()
+                .find(|lc| lc.id == tc.media_id)
+                .map(|lc| (tc, lc.path.as_path(), lc.name.as_str()))
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        a.0.start_time
+            .total_cmp(&b.0.start_time)
+            .then(a.0.track_row.cmp(&b.0.track_row))
+    });
+    out
+}
+
+/// Render a CMX3600 EDL. Record timecode starts at 00:00:00:00 so event
+/// times line up 1:1 with the VeloCut ruler.
+pub fn to_cmx3600(state: &ProjectState, title: &str, fps: u32) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("TITLE: {title}\n"));
+    out.push_str("FCM: NON-DROP FRAME\n\n");
+
+    for (n, (tc, path, name)) in events(state).into_iter().enumerate() {
+        let src_in = secs_to_frames(tc.source_offset, fps);
+        let src_out = secs_to_frames(tc.source_offset + tc.duration, fps);
+        let rec_in = secs_to_frames(tc.start_time, fps);
+        let rec_out = secs_to_frames(tc.start_time + tc.duration, fps);
+
+        out.push_str(&format!(
+            "{:03}  {:<8} {:<5} C        {} {} {} {}\n",
+            n + 1,
+            reel_name(path),
+            edl_channel(tc),
+            frames_to_timecode(src_in, fps),
+            frames_to_timecode(src_out, fps),
+            frames_to_timecode(rec_in, fps),
+            frames_to_timecode(rec_out, fps),
+        ));
+        // Freeze clips: CMX3600 motion-effect line at speed 0 holding src_in.
+        if tc.freeze_at.is_some() {
+            out.push_str(&format!(
+                "M2   {:<8}       000.0                {}\n",
+                reel_name(path),
+                frames_to_timecode(src_in, fps),
+            ));
+        }
+        out.push_str(&format!("* FROM CLIP NAME: {name}\n"));
+        out.push_str(&format!("* SOURCE FILE: {}\n", path.display()));
+        if let Some(t) = state
+            .transitions
+            .iter()
+            .find(|t| t.after_clip_id == tc.id)
+            .filter(|t| t.kind.kind != crate::transitions::TransitionKind::Cut)
+        {
+            out.push_str(&format!(
+                "* TRANSITION OUT: {:?} {} FRAMES\n",
+                t.kind.kind,
+                secs_to_frames(t.kind.duration_secs as f64, fps)
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape a string for inclusion in a JSON string literal.
+fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Render a flat JSON cut list — one object per clip with both seconds and
+/// timecode for source and record in/out.
+pub fn to_json(state: &ProjectState, fps: u32) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{{\n  \"fps\": {fps},\n  \"clips\": ["));
+    for (n, (tc, path, name)) in events(state).into_iter().enumerate() {
+        // A freeze frame consumes no source time.
+        let src_out = if tc.freeze_at.is_some() {
+            tc.source_offset
+        } else {
+            tc.source_offset + tc.duration
+        };
+        let rec_out = tc.start_time + tc.duration;
+        let tcode = |s: f64| json_str(&frames_to_timecode(secs_to_frames(s, fps), fps));
+        out.push_str(if n == 0 { "\n" } else { ",\n" });
+        out.push_str(&format!(
+            "    {{\n      \"event\": {},\n      \"name\": {},\n      \"source\": {},\n      \
+             \"reel\": {},\n      \"track\": {},\n      \
+             \"source_in\": {:.6},\n      \"source_out\": {:.6},\n      \
+             \"record_in\": {:.6},\n      \"record_out\": {:.6},\n      \
+             \"source_in_tc\": {},\n      \"source_out_tc\": {},\n      \
+             \"record_in_tc\": {},\n      \"record_out_tc\": {}\n    }}",
+            n + 1,
+            json_str(name),
+            json_str(&path.to_string_lossy()),
+            json_str(&reel_name(path)),
+            json_str(track_label(tc.track_row)),
+            tc.source_offset,
+            src_out,
+            tc.start_time,
+            rec_out,
+            tcode(tc.source_offset),
+            tcode(src_out),
+            tcode(tc.start_time),
+            tcode(rec_out),
+        ));
+    }
+    out.push_str("\n  ]\n}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::LibraryClip;
+    use uuid::Uuid;
+
+    fn state_with_clip(track_row: usize, audio_muted: bool) -> ProjectState {
+        let mut state = ProjectState::default();
+        let media_id = Uuid::new_v4();
+        state.library.push(LibraryClip::probed_video(
+            media_id,
+            "/footage/Interview \"A\".mp4",
+            20.0,
+        ));
+        state.timeline.push(TimelineClip {
+            id: Uuid::new_v4(),
+            media_id,
+            start_time: 2.0,
+            duration: 3.5,
+            track_row,
+            source_offset: 10.0,
+            audio_muted,
+            ..Default::default()
+        });
+        state
+    }
+
+    #[test]
+    fn timecode_rolls_over_each_unit() {
+        assert_eq!(frames_to_timecode(29, 30), "00:00:00:29");
+        assert_eq!(frames_to_timecode(30, 30), "00:00:01:00");
+        assert_eq!(frames_to_timecode(60 * 60, 60), "00:01:00:00");
+    }
+
+    #[test]
+    fn edl_event_maps_source_and_record() {
+        let edl = to_cmx3600(&state_with_clip(0, false), "seq", 30);
+        assert!(edl.starts_with("TITLE: seq\nFCM: NON-DROP FRAME\n"));
+        assert!(edl.contains(
+            "001  INTERVIE B     C        00:00:10:00 00:00:13:15 00:00:02:00 00:00:05:15\n"
+        ));
+    }
+
+    #[test]
+    fn freeze_clip_emits_motion_effect_and_zero_length_source() {
+        let mut state = state_with_clip(0, true);
+        state.timeline[0].freeze_at = Some(10.0);
+        let edl = to_cmx3600(&state, "seq", 30);
+        assert!(edl.contains("\nM2   INTERVIE       000.0                00:00:10:00\n"));
+        let json = to_json(&state, 30);
+        assert!(json.contains("\"source_in\": 10.000000,\n      \"source_out\": 10.000000"));
+    }
+
+    #[test]
+    fn edl_channel_follows_track_and_extraction() {
+        let muted = state_with_clip(0, true);
+        assert_eq!(edl_channel(&muted.timeline[0]), "V");
+        let a2 = state_with_clip(3, false);
+        assert_eq!(edl_channel(&a2.timeline[0]), "A2");
+    }
+
+    #[test]
+    fn json_escapes_names() {
+        let json = to_json(&state_with_clip(1, false), 24);
+        assert!(json.contains("\"name\": \"Interview \\\"A\\\".mp4\""));
+        assert!(json.contains("\"track\": \"A1\""));
+        assert!(json.contains("\"record_out_tc\": \"00:00:05:12\""));
+    }
+
+    #[test]
+    fn disabled_clips_are_left_out() {
+        let mut state = state_with_clip(0, false);
+        state.timeline[0].enabled = false;
+        assert!(events(&state).is_empty());
+    }
+
+    #[test]
+    fn empty_timeline_is_valid_json_shape() {
+        let json = to_json(&ProjectState::default(), 30);
+        assert_eq!(json, "{\n  \"fps\": 30,\n  \"clips\": [\n  ]\n}\n");
+    }
+}
@@ -55,6 +55,48 @@ pub fn aspect_ratio_label(ar: AspectRatio) -> &'static str {
     }
 }
 
+/// Ratio alone ("16:9"), for the monitor's picker and its Shift+A badge.
+pub fn aspect_ratio_short_label(ar: AspectRatio) -> &'static str {
+    match ar {
+        AspectRatio::SixteenNine => "16:9",
+        AspectRatio::NineSixteen => "9:16",
+        AspectRatio::TwoThree => "2:3",
+        AspectRatio::ThreeTwo => "3:2",
+        AspectRatio::FourThree => "4:3",
+        AspectRatio::OneOne => "1:1",
+        AspectRatio::FourFive => "4:5",
+        AspectRatio::TwentyOneNine => "21:9",
+        AspectRatio::Anamorphic => "2.39:1",
+    }
+}
+
+/// Every preset, in the order the monitor's picker lists them.
+pub const ASPECT_RATIOS: [AspectRatio; 9] = [
+    AspectRatio::SixteenNine,
+    AspectRatio::NineSixteen,
+    AspectRatio::TwoThree,
+    AspectRatio::ThreeTwo,
+    AspectRatio::FourThree,
+    AspectRatio::OneOne,
+    AspectRatio::FourFive,
+    AspectRatio::TwentyOneNine,
+    AspectRatio::Anamorphic,
+];
+
+/// The preset after `ar` in `ASPECT_RATIOS`, wrapping to the first — what
+/// Shift+A steps to.
+///
+/// ```
+/// use velocut_core::state::AspectRatio;
+/// use velocut_core::helpers::geometry::next_aspect_ratio;
+/// assert_eq!(next_aspect_ratio(AspectRatio::SixteenNine), AspectRatio::NineSixteen);
+/// assert_eq!(next_aspect_ratio(AspectRatio::Anamorphic), AspectRatio::SixteenNine);
+/// ```
+pub fn next_aspect_ratio(ar: AspectRatio) -> AspectRatio {
+    let i = ASPECT_RATIOS.iter().position(|&a| a == ar).unwrap_or(0);
+    ASPECT_RATIOS[(i + 1) % ASPECT_RATIOS.len()]
+}
+
 impl fmt::Display for AspectRatio {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", aspect_ratio_label(*self))
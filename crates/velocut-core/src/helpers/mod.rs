@@ -1,3 +1,5 @@
 // crates/velocut-core/src/helpers/mod.rs
+pub mod edl;
 pub mod geometry;
+pub mod storyboard;
 pub mod time;
@@ -0,0 +1,226 @@
+// crates/velocut-core/src/helpers/storyboard.rs
+//
+// Storyboard / contact-sheet planning — decides which timeline moments go on
+// the sheet and which source frame each one shows. Pure: no I/O, no ffmpeg.
+// velocut-media's `storyboard` module decodes the frames and composites the
+// grid.
+//
+// Only enabled clips on video tracks are sampled, with the same "first
+// matching clip wins" rule the preview uses. Moments that land in a gap are
+// skipped rather than drawn as black cells.
+
+use crate::state::{ClipType, ProjectState, TimelineClip};
+use std::ops::RangeInclusive;
+use uuid::Uuid;
+
+/// Allowed frame counts for `StoryboardSampling::Even`.
+pub const STORYBOARD_FRAMES_RANGE: RangeInclusive<u32> = 2..=200;
+/// Allowed grid widths, in cells.
+pub const STORYBOARD_COLUMNS_RANGE: RangeInclusive<u32> = 1..=12;
+/// Selectable cell widths, in pixels. Cell height follows the project
+/// aspect ratio.
+pub const STORYBOARD_THUMB_WIDTHS: [u32; 4] = [160, 240, 320, 480];
+
+/// Where the storyboard takes its frames from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoryboardSampling {
+    /// `frames` moments spread evenly over the whole timeline.
+    Even,
+    /// The middle frame of every clip, in timeline order.
+    PerClip,
+}
+
+impl StoryboardSampling {
+    pub fn label(self) -> &'static str {
+        match self {
+            StoryboardSampling::Even => "Evenly spaced",
+            StoryboardSampling::PerClip => "One per clip",
+        }
+    }
+}
+
+/// Settings for `EditorCommand::ExportStoryboard`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StoryboardOptions {
+    pub sampling: StoryboardSampling,
+    /// Frame count for `Even`; ignored for `PerClip`.
+    pub frames: u32,
+    pub columns: u32,
+    pub thumb_width: u32,
+    /// Burn each cell's record timecode into its corner.
+    pub timecode: bool,
+}
+
+impl Default for StoryboardOptions {
+    fn default() -> Self {
+        Self {
+            sampling: StoryboardSampling::Even,
+            frames: 12,
+            columns: 4,
+            thumb_width: 320,
+            timecode: true,
+        }
+    }
+}
+
+impl StoryboardOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !STORYBOARD_FRAMES_RANGE.contains(&self.frames) {
+            return Err(format!(
+                "Storyboard frame count must be {}–{}",
+                STORYBOARD_FRAMES_RANGE.start(),
+                STORYBOARD_FRAMES_RANGE.end()
+            ));
+        }
+        if !STORYBOARD_COLUMNS_RANGE.contains(&self.columns) {
+            return Err(format!(
+                "Storyboard columns must be {}–{}",
+                STORYBOARD_COLUMNS_RANGE.start(),
+                STORYBOARD_COLUMNS_RANGE.end()
+            ));
+        }
+        if !STORYBOARD_THUMB_WIDTHS.contains(&self.thumb_width) {
+            return Err("Unsupported storyboard thumbnail width".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// One cell of the sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoryboardShot {
+    pub clip_id: Uuid,
+    pub media_id: Uuid,
+    /// Record time on the timeline (what the timecode label shows).
+    pub timeline_time: f64,
+    /// Source time to decode, freeze frames and trims applied.
+    pub source_time: f64,
+}
+
+/// Pick the storyboard's moments. `Even` samples the middle of `frames`
+/// equal slices of the timeline; `PerClip` takes each clip's midpoint.
+pub fn plan_shots(
+    state: &ProjectState,
+    sampling: StoryboardSampling,
+    frames: u32,
+) -> Vec<StoryboardShot> {
+    let shot = |clip: &TimelineClip, t: f64| StoryboardShot {
+        clip_id: clip.id,
+        media_id: clip.media_id,
+        timeline_time: t,
+        source_time: clip.source_time(t - clip.start_time),
+    };
+    match sampling {
+        StoryboardSampling::Even => {
+            let total = state.total_duration();
+            if total <= 0.0 || frames == 0 {
+                return Vec::new();
+            }
+            let step = total / frames as f64;
+            (0..frames)
+                .map(|i| (i as f64 + 0.5) * step)
+                .filter_map(|t| picture_clip_at(state, t).map(|c| shot(c, t)))
+                .collect()
+        }
+        StoryboardSampling::PerClip => {
+            let mut clips: Vec<&TimelineClip> = state
+                .timeline
+                .iter()
+                .filter(|c| is_picture_clip(state, c))
+                .collect();
+            clips.sort_by(|a, b| {
+                a.start_time
+                    .total_cmp(&b.start_time)
+                    .then(a.track_row.cmp(&b.track_row))
+            });
+            clips
+                .into_iter()
+                .map(|c| shot(c, c.start_time + c.duration * 0.5))
+                .collect()
+        }
+    }
+}
+
+fn picture_clip_at(state: &ProjectState, t: f64) -> Option<&TimelineClip> {
+    state
+        .timeline
+        .iter()
+        .find(|c| is_picture_clip(state, c) && t >= c.start_time && t < c.start_time + c.duration)
+}
+
+/// Enabled clip on a video track whose source has a picture.
+fn is_picture_clip(state: &ProjectState, clip: &TimelineClip) -> bool {
+    clip.enabled
+        && clip.track_row.is_multiple_of(2)
+        && state
+            .library
+            .iter()
+            .any(|l| l.id == clip.media_id && l.clip_type != ClipType::Audio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::LibraryClip;
+
+    fn state_with_clips(clips: &[(f64, f64, usize)]) -> ProjectState {
+        let mut state = ProjectState::default();
+        let media_id = Uuid::new_v4();
+        state
+            .library
+            .push(LibraryClip::probed_video(media_id, "/footage/a.mp4", 60.0));
+        for &(start_time, duration, track_row) in clips {
+            state.timeline.push(TimelineClip {
+                id: Uuid::new_v4(),
+                media_id,
+                start_time,
+                duration,
+                track_row,
+                source_offset: 10.0,
+                ..Default::default()
+            });
+        }
+        state
+    }
+
+    #[test]
+    fn even_sampling_hits_slice_middles_and_skips_gaps() {
+        // 0–4 s clip, gap, 6–8 s clip: four 2 s slices → 1, 3, (5 gap), 7.
+        let state = state_with_clips(&[(0.0, 4.0, 0), (6.0, 2.0, 0)]);
+        let shots = plan_shots(&state, StoryboardSampling::Even, 4);
+        let times: Vec<f64> = shots.iter().map(|s| s.timeline_time).collect();
+        assert_eq!(times, vec![1.0, 3.0, 7.0]);
+        // Source time = offset + clip-local time.
+        assert_eq!(shots[0].source_time, 11.0);
+        assert_eq!(shots[2].source_time, 11.0);
+    }
+
+    #[test]
+    fn per_clip_takes_midpoints_of_video_clips_in_order() {
+        let mut state = state_with_clips(&[(4.0, 2.0, 0), (0.0, 4.0, 0), (0.0, 4.0, 1)]);
+        state.timeline[0].freeze_at = Some(3.0);
+        let shots = plan_shots(&state, StoryboardSampling::PerClip, 0);
+        assert_eq!(shots.len(), 2, "audio track clip is skipped");
+        assert_eq!(shots[0].timeline_time, 2.0);
+        assert_eq!(shots[1].timeline_time, 5.0);
+        assert_eq!(
+            shots[1].source_time, 3.0,
+            "freeze frame holds its source frame"
+        );
+    }
+
+    #[test]
+    fn options_validate_ranges() {
+        assert_eq!(StoryboardOptions::default().validate(), Ok(()));
+        let bad = StoryboardOptions {
+            columns: 0,
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+        let bad = StoryboardOptions {
+            thumb_width: 100,
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
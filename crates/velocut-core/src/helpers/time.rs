@@ -7,20 +7,23 @@
 // velocut-ui/src/modules/timeline.rs (as `format_time`) and
 // velocut-ui/src/modules/library.rs (as `format_duration`, diverged format).
 
-/// Format a duration in seconds as `MM:SS:FF` (minutes, seconds, frames at 30 fps).
+/// Format a duration in seconds as `MM:SS:FF` (minutes, seconds, frames at
+/// `fps` — the project frame rate).
 ///
 /// Used on the timeline ruler where frame-level precision matters.
 ///
 /// ```
 /// use velocut_core::helpers::time::format_time;
-/// assert_eq!(format_time(0.0),    "00:00:00");
-/// assert_eq!(format_time(61.5),   "01:01:15");
-/// assert_eq!(format_time(3599.0), "59:59:00");
+/// assert_eq!(format_time(0.0, 30),    "00:00:00");
+/// assert_eq!(format_time(61.5, 30),   "01:01:15");
+/// assert_eq!(format_time(61.5, 24),   "01:01:12");
+/// assert_eq!(format_time(3599.0, 30), "59:59:00");
 /// ```
-pub fn format_time(s: f64) -> String {
+pub fn format_time(s: f64, fps: u32) -> String {
+    let fps = fps.max(1);
     let m = (s / 60.0) as u32;
     let sc = (s % 60.0) as u32;
-    let fr = ((s * 30.0) as u32) % 30;
+    let fr = ((s * fps as f64) as u32) % fps;
     format!("{m:02}:{sc:02}:{fr:02}")
 }
 
@@ -54,3 +57,50 @@ pub fn format_duration(secs: f64) -> String {
         format!("{secs:.1}s")
     }
 }
+
+/// Format a source-file timestamp as `HH:MM:SS.mmm`.
+///
+/// Used for the selected clip's source timecode in the Monitor header, where
+/// millisecond precision is what you match against another camera or a
+/// transcript. Rounds to the nearest millisecond; negatives clamp to zero.
+///
+/// ```
+/// use velocut_core::helpers::time::format_source_time;
+/// assert_eq!(format_source_time(83.4),    "00:01:23.400");
+/// assert_eq!(format_source_time(3725.0),  "01:02:05.000");
+/// assert_eq!(format_source_time(59.9996), "00:01:00.000");
+/// ```
+pub fn format_source_time(secs: f64) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        ms % 1000,
+    )
+}
+
+/// Format a Unix timestamp (UTC) as a calendar date, `YYYY-MM-DD`.
+///
+/// Used to pre-fill the export metadata date. Days-to-civil conversion from
+/// Howard Hinnant's date algorithms, so no date crate is needed.
+///
+/// ```
+/// use velocut_core::helpers::time::format_iso_date;
+/// assert_eq!(format_iso_date(0),             "1970-01-01");
+/// assert_eq!(format_iso_date(951_782_400),   "2000-02-29");
+/// assert_eq!(format_iso_date(1_792_195_140), "2026-10-16");
+/// ```
+pub fn format_iso_date(unix_secs: u64) -> String {
+    let z = (unix_secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
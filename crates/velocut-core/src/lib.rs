@@ -1,8 +1,11 @@
 // crates/velocut-core/src/lib.rs
+pub mod beat_grid;
 pub mod commands;
 pub mod filters;
 pub mod helpers;
 pub mod media_types;
+pub mod prefs;
 pub mod state;
 pub mod transitions;
+pub mod watermark;
 pub mod windows;
@@ -3,7 +3,7 @@
 // Types that flow across the channel between velocut-media and velocut-ui.
 // No egui, no ffmpeg — just plain data.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Results sent from the MediaWorker background threads to the UI.
@@ -46,11 +46,87 @@ pub enum MediaResult {
     FrameSaved {
         path: PathBuf,
     },
+    /// A storyboard export finished writing `path`; `error` is set when it
+    /// failed and nothing (or a partial file) was written.
+    StoryboardSaved {
+        path: PathBuf,
+        error: Option<String>,
+    },
     AudioPath {
         id: Uuid,
         path: PathBuf,
         trimmed_offset: f64,
     },
+    /// Every audio stream in the source, in container order. Sent by the
+    /// probe pipeline; empty for files with no audio.
+    AudioStreams {
+        id: Uuid,
+        streams: Vec<AudioStreamInfo>,
+    },
+    /// Quantization range of the source's video stream. Sent by the probe
+    /// pipeline alongside `VideoSize`; never sent for audio-only files.
+    VideoColorRange {
+        id: Uuid,
+        range: ColorRange,
+    },
+    /// Whether the source's video stream is interlaced (field order other
+    /// than progressive / unknown). Sent alongside `VideoColorRange`.
+    VideoInterlaced {
+        id: Uuid,
+        interlaced: bool,
+    },
+    /// Average frame rate of the source's video stream, alongside `VideoSize`.
+    /// Not sent when the container reports no usable rate.
+    VideoFrameRate {
+        id: Uuid,
+        fps: f64,
+    },
+    /// The probe found audio but no video stream (cover art doesn't count).
+    /// Sent instead of `VideoSize`; the library entry becomes an audio clip
+    /// whatever its extension said.
+    AudioOnly {
+        id: Uuid,
+    },
+    /// Silence scan finished for timeline clip `id`. `sound` is the source
+    /// range (seconds) to keep once leading/trailing silence is trimmed;
+    /// `None` when nothing was above the threshold or the decode failed.
+    SilenceDetected {
+        id: Uuid,
+        sound: Option<(f64, f64)>,
+    },
+    /// Speech scan for auto-duck finished. `id` is the music clip being
+    /// ducked; `regions` are where the speech clip is talking, in its source
+    /// seconds (empty when nothing was loud enough or the decode failed).
+    SpeechDetected {
+        id: Uuid,
+        regions: Vec<(f64, f64)>,
+    },
+    /// Audio sync finished for timeline clip `id`. `offset` is the reference
+    /// clip's source time minus this clip's source time at the same moment;
+    /// `None` when no confident match was found or a decode failed.
+    AudioSyncFound {
+        id: Uuid,
+        offset: Option<f64>,
+    },
+    /// Last message of a `probe_clip` run for library clip `id`, whatever it
+    /// found — everything the probe produces has already been sent.
+    ProbeFinished {
+        id: Uuid,
+    },
+    /// On-disk size and container bit rate of the source file, sent alongside
+    /// `Duration`. `bit_rate` is `None` when the container reports none.
+    FileStats {
+        id: Uuid,
+        bytes: u64,
+        bit_rate: Option<u64>,
+    },
+    /// Scene-cut analysis finished for timeline clip `id`. `cuts` are source
+    /// timestamps (seconds, ascending); empty when none were found or the
+    /// decode failed.
+    ScenesDetected {
+        id: Uuid,
+        cuts: Vec<f64>,
+    },
     Error {
         id: Uuid,
         msg: String,
@@ -65,10 +141,18 @@ pub enum MediaResult {
         frame: u64,
         total_frames: u64,
     },
-    /// Encode completed successfully.
+    /// Encode completed successfully. `audio_peak` is the largest absolute
+    /// sample of the final audio mix before clamping (linear; > 1.0 = clipped).
+    /// `audio_codec` names the audio encoder used ("AAC", or a fallback such
+    /// as "MP3"); `None` = the FFmpeg build had none and the file is video-only.
+    /// `skipped_frames` counts corrupt video frames an error-resilient render
+    /// dropped (always 0 otherwise).
     EncodeDone {
         job_id: Uuid,
         path: PathBuf,
+        audio_peak: f32,
+        audio_codec: Option<&'static str>,
+        skipped_frames: u64,
     },
     /// Encode failed or was cancelled.
     EncodeError {
@@ -77,6 +161,53 @@ pub enum MediaResult {
     },
 }
 
+/// YUV quantization range of a source video stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorRange {
+    /// "TV" / MPEG range — luma 16–235, chroma 16–240. Typical camera footage
+    /// and the range every export is written in.
+    Limited,
+    /// "PC" / JPEG range — 0–255. Common in screen recordings and phone video.
+    Full,
+}
+
+impl ColorRange {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorRange::Limited => "Limited",
+            ColorRange::Full => "Full",
+        }
+    }
+}
+
+/// One audio stream found in a source file by the probe pipeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioStreamInfo {
+    /// Container stream index — what `LibraryClip::audio_stream_index` stores
+    /// and what the decode/encode paths select on.
+    pub index: usize,
+    pub channels: u32,
+    /// ISO 639 `language` tag, when the container has one.
+    pub language: Option<String>,
+    /// Free-form `title` tag (e.g. "Lav mic"), when the container has one.
+    pub title: Option<String>,
+}
+
+impl AudioStreamInfo {
+    /// Picker label, e.g. `#2 · eng · Lav mic · 1ch`.
+    pub fn label(&self) -> String {
+        let mut parts = vec![format!("#{}", self.index)];
+        if let Some(lang) = &self.language {
+            parts.push(lang.clone());
+        }
+        if let Some(title) = &self.title {
+            parts.push(title.clone());
+        }
+        parts.push(format!("{}ch", self.channels));
+        parts.join(" · ")
+    }
+}
+
 /// A decoded frame from the dedicated playback pipeline.
 pub struct PlaybackFrame {
     pub id: Uuid,
@@ -102,6 +233,7 @@ pub struct TransitionScrubRequest {
     /// Blend factor: 0.0 = fully clip_a, 1.0 = fully clip_b.
     pub alpha: f32,
     pub kind: crate::transitions::TransitionKind,
+    pub curve: crate::transitions::FadeCurve,
 }
 
 /// Passed to `MediaWorker::start_blend_playback` so the playback thread can
@@ -119,6 +251,7 @@ pub struct PlaybackTransitionSpec {
     pub blend_start_ts: f64,
     pub duration: f32,
     pub kind: crate::transitions::TransitionKind,
+    pub curve: crate::transitions::FadeCurve,
     /// Alpha value at the start of blending for this decoder's half of the transition.
     /// Clip_a side: 0.0  (fades out from pure clip_a → 0.5 at cut point).
     /// Clip_b side: 0.5  (continues from the cut point → 1.0 pure clip_b).
@@ -128,3 +261,300 @@ pub struct PlaybackTransitionSpec {
     /// clip_b but blend_rgba_transition expects clip_a as the first argument.
     pub invert_ab: bool,
 }
+
+/// Length of the Export panel's test render (first N seconds of the timeline).
+pub const TEST_RENDER_SECS: f64 = 5.0;
+
+/// Video bitrates (kb/s) the two-pass target accepts.
+pub const TARGET_KBPS_RANGE: std::ops::RangeInclusive<u32> = 250..=100_000;
+
+/// Output container + codec pairing for a render.
+///
+/// Selected in the Export panel and carried through `EditorCommand::RenderMP4`
+/// into `EncodeSpec`. The encode pipeline branches on this for the video and
+/// audio encoders and for the audio sample rate; everything else (FIFO,
+/// transitions, overlays, PTS strategy) is shared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// H.264 + AAC in MP4. Universal playback; HW-accelerated when available.
+    #[default]
+    Mp4,
+    /// VP9 + Opus in WebM. Web-native and royalty-free; software encode only.
+    WebM,
+    /// H.264 + AAC in Matroska. Same encoders as MP4; the muxer is more
+    /// forgiving of DTS/PTS edge cases and the file stays playable if the
+    /// render is interrupted.
+    Mkv,
+    /// H.264 + AAC in QuickTime MOV, for Apple / Final Cut workflows.
+    Mov,
+}
+
+impl ExportFormat {
+    /// All formats in display order — used to render the Export picker.
+    pub fn all() -> &'static [ExportFormat] {
+        &[
+            ExportFormat::Mp4,
+            ExportFormat::Mkv,
+            ExportFormat::Mov,
+            ExportFormat::WebM,
+        ]
+    }
+
+    /// File extension without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Mp4 => "mp4",
+            ExportFormat::WebM => "webm",
+            ExportFormat::Mkv => "mkv",
+            ExportFormat::Mov => "mov",
+        }
+    }
+
+    /// Short container name, e.g. for the "Render MP4" button.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Mp4 => "MP4",
+            ExportFormat::WebM => "WebM",
+            ExportFormat::Mkv => "MKV",
+            ExportFormat::Mov => "MOV",
+        }
+    }
+
+    /// Human-readable label shown in the Export panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Mp4 => "MP4   (H.264 + AAC)",
+            ExportFormat::WebM => "WebM  (VP9 + Opus)",
+            ExportFormat::Mkv => "MKV   (H.264 + AAC)",
+            ExportFormat::Mov => "MOV   (H.264 + AAC)",
+        }
+    }
+
+    /// True when the video encoder can do a two-pass average-bitrate encode
+    /// (libx264). WebM's VP9 stays constant-quality.
+    pub fn supports_two_pass(self) -> bool {
+        !matches!(self, ExportFormat::WebM)
+    }
+
+    /// True when the container can carry a cover image (MP4 / MOV `covr`).
+    pub fn supports_cover_art(self) -> bool {
+        matches!(self, ExportFormat::Mp4 | ExportFormat::Mov)
+    }
+
+    /// Filter name shown in the save dialog's file-type dropdown.
+    pub fn dialog_filter_name(self) -> &'static str {
+        match self {
+            ExportFormat::Mp4 => "MP4 Video",
+            ExportFormat::WebM => "WebM Video",
+            ExportFormat::Mkv => "Matroska Video",
+            ExportFormat::Mov => "QuickTime Movie",
+        }
+    }
+
+    /// Audio sample rate of the output stream.
+    ///
+    /// libopus only accepts 48/24/16/12/8 kHz, so WebM renders at 48 kHz.
+    /// The AAC containers keep 44.1 kHz to match the extracted preview WAVs.
+    pub fn audio_rate(self) -> u32 {
+        match self {
+            ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => 44_100,
+            ExportFormat::WebM => 48_000,
+        }
+    }
+
+    /// Format whose extension is `ext` (case-insensitive, no leading dot).
+    pub fn from_extension(ext: &str) -> Option<ExportFormat> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|f| f.extension().eq_ignore_ascii_case(ext))
+    }
+
+    /// `path` with this format's extension, so the muxer `open_output` picks
+    /// from the extension always matches the encoders chosen for the format.
+    /// Another container's extension (`cut.mp4` while MKV is selected) is
+    /// replaced; anything else stays part of the name (`take.v2` →
+    /// `take.v2.mkv`).
+    pub fn output_path(self, path: &Path) -> PathBuf {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case(self.extension()) => path.to_path_buf(),
+            Some(ext) if Self::from_extension(ext).is_some() => {
+                path.with_extension(self.extension())
+            }
+            _ => {
+                let mut name = path.file_name().unwrap_or_default().to_os_string();
+                name.push(".");
+                name.push(self.extension());
+                path.with_file_name(name)
+            }
+        }
+    }
+}
+
+/// How sources deeper than 8 bits (10-bit HDR phone / camera footage) are
+/// quantized to the 8-bit YUV420P the encoders take. 8-bit sources are never
+/// dithered — there is nothing to spread.
+///
+/// Selected in the Export panel and carried through `EditorCommand::RenderMP4`
+/// into `EncodeSpec`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherMode {
+    /// Round to nearest — smooth gradients such as skies can band.
+    Off,
+    /// 8×8 Bayer threshold pattern. Fixed per pixel, so static shots stay
+    /// static and the encoder sees no frame-to-frame noise.
+    #[default]
+    Ordered,
+    /// Floyd–Steinberg. Finest gradients, but the pattern shifts between
+    /// frames and costs some bitrate.
+    ErrorDiffusion,
+}
+
+impl DitherMode {
+    /// All modes in display order — used to render the Export picker.
+    pub fn all() -> &'static [DitherMode] {
+        &[
+            DitherMode::Ordered,
+            DitherMode::ErrorDiffusion,
+            DitherMode::Off,
+        ]
+    }
+
+    /// Human-readable label shown in the Export panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            DitherMode::Off => "Off",
+            DitherMode::Ordered => "Ordered",
+            DitherMode::ErrorDiffusion => "Error diffusion",
+        }
+    }
+}
+
+/// Container tags written into an export — MP4/MOV `udta` atoms, Matroska
+/// tags — for media managers and upload sites to read. Empty fields are
+/// left out.
+///
+/// Filled in the Export panel and carried through `EditorCommand::RenderMP4`
+/// into `EncodeSpec`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportMetadata {
+    pub title: String,
+    pub artist: String,
+    pub comment: String,
+    /// Creation date as `YYYY-MM-DD`. Also stamped as the file's creation
+    /// time, which players show as "date created".
+    pub date: String,
+    /// Which frame to embed as the cover image. Not a tag — app.rs resolves
+    /// it to a source frame for `EncodeSpec::cover`.
+    pub cover: CoverFrame,
+}
+
+/// Frame embedded in an export as its cover image — the poster players and
+/// upload sites show before playback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoverFrame {
+    /// No cover image.
+    Off,
+    /// The first frame of the render.
+    #[default]
+    First,
+    /// The frame under the playhead when the render starts.
+    Playhead,
+}
+
+impl CoverFrame {
+    /// All choices in display order — used to render the Export picker.
+    pub fn all() -> &'static [CoverFrame] {
+        &[CoverFrame::First, CoverFrame::Playhead, CoverFrame::Off]
+    }
+
+    /// Human-readable label shown in the Export panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            CoverFrame::Off => "None",
+            CoverFrame::First => "First frame",
+            CoverFrame::Playhead => "Playhead frame",
+        }
+    }
+}
+
+/// Which timeline tracks an export renders. A single video track keeps its
+/// clips' own sound but drops the A-row music; a single audio track is
+/// written as a WAV stem with no picture.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportTracks {
+    #[default]
+    All,
+    /// One track by `track_row`: V1 = 0, A1 = 1, V2 = 2, A2 = 3.
+    Only(usize),
+}
+
+impl ExportTracks {
+    /// All choices in display order — used to render the Export picker.
+    pub fn all() -> &'static [ExportTracks] {
+        &[
+            ExportTracks::All,
+            ExportTracks::Only(0),
+            ExportTracks::Only(2),
+            ExportTracks::Only(1),
+            ExportTracks::Only(3),
+        ]
+    }
+
+    /// Human-readable label shown in the Export panel.
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportTracks::All => "All tracks",
+            ExportTracks::Only(0) => "V1 only",
+            ExportTracks::Only(1) => "A1 only (WAV)",
+            ExportTracks::Only(2) => "V2 only",
+            ExportTracks::Only(3) => "A2 only (WAV)",
+            ExportTracks::Only(_) => "One track",
+        }
+    }
+
+    /// True for a single A-row: the render is an audio-only WAV stem.
+    pub fn is_audio_stem(self) -> bool {
+        matches!(self, ExportTracks::Only(row) if row % 2 == 1)
+    }
+}
+
+impl ExportMetadata {
+    /// FFmpeg metadata key/value pairs for the non-empty fields.
+    pub fn tags(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("title", self.title.trim()),
+            ("artist", self.artist.trim()),
+            ("comment", self.comment.trim()),
+            ("date", self.date.trim()),
+            ("creation_time", self.date.trim()),
+        ]
+        .into_iter()
+        .filter(|(_, v)| !v.is_empty())
+        .collect()
+    }
+
+    /// True when `date` is empty or a plausible `YYYY-MM-DD`.
+    pub fn date_is_valid(&self) -> bool {
+        let d = self.date.trim();
+        if d.is_empty() {
+            return true;
+        }
+        let field = |part: Option<&str>, len: usize| {
+            part.filter(|s| s.len() == len && s.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|s| s.parse::<u32>().ok())
+        };
+        let mut parts = d.split('-');
+        match (
+            field(parts.next(), 4),
+            field(parts.next(), 2),
+            field(parts.next(), 2),
+            parts.next(),
+        ) {
+            (Some(_), Some(month), Some(day), None) => {
+                (1..=12).contains(&month) && (1..=31).contains(&day)
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,445 @@
+// crates/velocut-core/src/prefs.rs
+//
+// Machine-level preferences — tuning that belongs to the install, not to the
+// edit. Persisted in `AppStorage` next to the project, mirrored into
+// `ProjectState` as runtime fields so modules can read them, and changed only
+// through `EditorCommand::SetPerformancePrefs` / `SetPlaybackPrefs` /
+// `SetLayoutPrefs` / `SetStoragePrefs` / `SetNotifyPrefs`.
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+/// Allowed scrub frame-cache budgets, in megabytes.
+pub const FRAME_CACHE_MB_RANGE: RangeInclusive<u32> = 64..=2048;
+/// Allowed number of concurrent full-resolution decode threads.
+pub const DECODE_THREADS_RANGE: RangeInclusive<u32> = 1..=8;
+/// Allowed playback read-ahead depths, in decoded frames.
+pub const PLAYBACK_AHEAD_RANGE: RangeInclusive<u32> = 2..=24;
+/// Allowed time spans (seconds) for a full-width drag across the preview.
+pub const PREVIEW_SCRUB_SECS_RANGE: RangeInclusive<u32> = 1..=60;
+/// Allowed pre-roll / post-roll lengths for "play around playhead", in ms.
+pub const ROLL_MS_RANGE: RangeInclusive<u32> = 0..=10_000;
+/// Allowed timeline clip corner radii, in pixels.
+pub const CLIP_RADIUS_RANGE: RangeInclusive<u8> = 0..=10;
+/// Selectable scrub proxy widths, in pixels. Height follows source AR.
+pub const PROXY_WIDTHS: [u32; 4] = [240, 320, 480, 640];
+
+/// Decode and cache limits. Defaults match the values VeloCut shipped with
+/// before they were configurable, so an old `AppStorage` behaves the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformancePrefs {
+    /// GPU-resident budget for the scrub look-ahead frame cache (MB).
+    pub frame_cache_mb: u32,
+    /// Max concurrent HQ / transition decode threads in `MediaWorker`.
+    /// Each one holds a native-res decoder (~16 MB) while it runs.
+    pub decode_threads: u32,
+    /// Output width of low-res scrub proxy frames (L1/L2 scrub).
+    pub proxy_width: u32,
+    /// Frames the playback thread decodes ahead of the playhead. More rides
+    /// out slow decodes of 4K sources at the cost of one native-res RGBA
+    /// frame of memory each (~33 MB at 3840×2160).
+    pub playback_ahead: u32,
+}
+
+impl Default for PerformancePrefs {
+    fn default() -> Self {
+        Self {
+            frame_cache_mb: 192,
+            decode_threads: 2,
+            proxy_width: 320,
+            playback_ahead: 6,
+        }
+    }
+}
+
+impl PerformancePrefs {
+    /// Frame-cache budget in bytes, as compared against `CacheContext`'s
+    /// running RGBA byte count.
+    pub fn frame_cache_bytes(&self) -> usize {
+        self.frame_cache_mb as usize * 1024 * 1024
+    }
+
+    /// Reject values outside the ranges the settings UI offers.
+    pub fn validate(&self) -> Result<(), String> {
+        if !FRAME_CACHE_MB_RANGE.contains(&self.frame_cache_mb) {
+            return Err(format!(
+                "Frame cache must be between {} and {} MB",
+                FRAME_CACHE_MB_RANGE.start(),
+                FRAME_CACHE_MB_RANGE.end()
+            ));
+        }
+        if !DECODE_THREADS_RANGE.contains(&self.decode_threads) {
+            return Err(format!(
+                "Decode threads must be between {} and {}",
+                DECODE_THREADS_RANGE.start(),
+                DECODE_THREADS_RANGE.end()
+            ));
+        }
+        if !PLAYBACK_AHEAD_RANGE.contains(&self.playback_ahead) {
+            return Err(format!(
+                "Playback read-ahead must be between {} and {} frames",
+                PLAYBACK_AHEAD_RANGE.start(),
+                PLAYBACK_AHEAD_RANGE.end()
+            ));
+        }
+        if !PROXY_WIDTHS.contains(&self.proxy_width) {
+            return Err(format!("Unsupported proxy width {}px", self.proxy_width));
+        }
+        Ok(())
+    }
+
+    /// Pull every field back into range. Used on load so a hand-edited or
+    /// stale `app.ron` can never hand the worker a zero thread limit.
+    pub fn clamped(self) -> Self {
+        let proxy_width = if PROXY_WIDTHS.contains(&self.proxy_width) {
+            self.proxy_width
+        } else {
+            Self::default().proxy_width
+        };
+        Self {
+            frame_cache_mb: self
+                .frame_cache_mb
+                .clamp(*FRAME_CACHE_MB_RANGE.start(), *FRAME_CACHE_MB_RANGE.end()),
+            decode_threads: self
+                .decode_threads
+                .clamp(*DECODE_THREADS_RANGE.start(), *DECODE_THREADS_RANGE.end()),
+            proxy_width,
+            playback_ahead: self
+                .playback_ahead
+                .clamp(*PLAYBACK_AHEAD_RANGE.start(), *PLAYBACK_AHEAD_RANGE.end()),
+        }
+    }
+}
+
+/// What the Space key does in the timeline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpaceBehavior {
+    /// Tap to play, tap again to pause.
+    #[default]
+    Toggle,
+    /// Play while Space is held, pause on release. Releasing with Shift held
+    /// also returns the playhead to where the hold started.
+    Momentary,
+}
+
+impl SpaceBehavior {
+    pub fn label(self) -> &'static str {
+        match self {
+            SpaceBehavior::Toggle => "Toggle",
+            SpaceBehavior::Momentary => "Hold to play",
+        }
+    }
+}
+
+/// How the timeline keeps the playhead in view while it moves on its own
+/// (playback, keyboard stepping).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoScroll {
+    /// Never scroll; the playhead may run off-screen.
+    Off,
+    /// Jump a page when the playhead nears the right edge (or leaves the
+    /// left one), leaving it near the left edge.
+    #[default]
+    Page,
+    /// Scroll continuously once the playhead reaches mid-view, keeping it
+    /// centred.
+    Follow,
+}
+
+impl AutoScroll {
+    pub fn all() -> &'static [AutoScroll] {
+        &[AutoScroll::Off, AutoScroll::Page, AutoScroll::Follow]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AutoScroll::Off => "Off",
+            AutoScroll::Page => "Page",
+            AutoScroll::Follow => "Follow",
+        }
+    }
+
+    /// New horizontal scroll offset (px) that brings a playhead at content
+    /// x `playhead_x` into a lane view `lane_w` px wide currently scrolled to
+    /// `offset`, or `None` when the view should stay where it is.
+    pub fn scroll_for(self, playhead_x: f32, offset: f32, lane_w: f32) -> Option<f32> {
+        let off_left = playhead_x < offset;
+        let target = match self {
+            AutoScroll::Off => return None,
+            AutoScroll::Page if off_left || playhead_x > offset + lane_w * 0.95 => {
+                playhead_x - lane_w * 0.05
+            }
+            AutoScroll::Follow if off_left || playhead_x > offset + lane_w * 0.5 => {
+                playhead_x - lane_w * 0.5
+            }
+            _ => return None,
+        };
+        Some(target.max(0.0))
+    }
+}
+
+/// Editing-feel preferences. Defaults keep the behaviour VeloCut had before
+/// they existed, except that the timeline now pages along with playback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybackPrefs {
+    pub space: SpaceBehavior,
+    pub auto_scroll: AutoScroll,
+    /// Seconds a drag across the full width of the preview image moves the
+    /// playhead (jog scrub).
+    pub preview_scrub_secs: u32,
+    /// Dragging a clip's head over the clip before it turns the overlap into
+    /// a crossfade straight away instead of asking first.
+    pub auto_crossfade: bool,
+    /// "Play around playhead" starts this long before the playhead (ms)…
+    pub pre_roll_ms: u32,
+    /// …and stops this long after it before jumping back (ms).
+    pub post_roll_ms: u32,
+}
+
+impl Default for PlaybackPrefs {
+    fn default() -> Self {
+        Self {
+            space: SpaceBehavior::default(),
+            auto_scroll: AutoScroll::default(),
+            preview_scrub_secs: 10,
+            auto_crossfade: false,
+            pre_roll_ms: 2000,
+            post_roll_ms: 2000,
+        }
+    }
+}
+
+impl PlaybackPrefs {
+    /// `preview_scrub_secs` clamped to `PREVIEW_SCRUB_SECS_RANGE`, so a
+    /// hand-edited `AppStorage` can't stall or fling the jog scrub.
+    pub fn preview_scrub_span(&self) -> f64 {
+        self.preview_scrub_secs.clamp(
+            *PREVIEW_SCRUB_SECS_RANGE.start(),
+            *PREVIEW_SCRUB_SECS_RANGE.end(),
+        ) as f64
+    }
+
+    /// (pre-roll, post-roll) in seconds, clamped to `ROLL_MS_RANGE`.
+    pub fn roll_secs(&self) -> (f64, f64) {
+        let secs = |ms: u32| ms.min(*ROLL_MS_RANGE.end()) as f64 / 1000.0;
+        (secs(self.pre_roll_ms), secs(self.post_roll_ms))
+    }
+}
+
+/// Window arrangement around the preview, and how densely the timeline draws
+/// its clips. The timeline always stays at the bottom — it hosts the menu
+/// that changes these.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutPrefs {
+    /// Library on the right and export on the left.
+    pub swap_sides: bool,
+    pub show_library: bool,
+    pub show_export: bool,
+    /// Keep the VeloCut window above other windows, for reviewing against
+    /// reference material or notes. Only the window level changes, so it
+    /// holds in and out of OS fullscreen.
+    pub always_on_top: bool,
+    /// Timeline clip corner radius (px); 0 draws square clips.
+    pub clip_radius: u8,
+    /// Tile source thumbnails along video clips. Off leaves a plain body
+    /// with the waveform, for a quieter timeline.
+    pub show_thumbnails: bool,
+    /// Waveform strength on timeline clips, 0–100 %.
+    pub waveform_opacity: u8,
+    /// Show each clip's length in its bottom-right corner.
+    pub show_durations: bool,
+}
+
+impl Default for LayoutPrefs {
+    fn default() -> Self {
+        Self {
+            swap_sides: false,
+            show_library: true,
+            show_export: true,
+            always_on_top: false,
+            clip_radius: 4,
+            show_thumbnails: true,
+            waveform_opacity: 100,
+            show_durations: true,
+        }
+    }
+}
+
+impl LayoutPrefs {
+    /// `clip_radius` clamped to `CLIP_RADIUS_RANGE`.
+    pub fn clip_corner(&self) -> u8 {
+        self.clip_radius.min(*CLIP_RADIUS_RANGE.end())
+    }
+
+    /// `waveform_opacity` as a 0.0–1.0 multiplier.
+    pub fn waveform_alpha(&self) -> f32 {
+        self.waveform_opacity.min(100) as f32 / 100.0
+    }
+}
+
+/// Where extracted audio WAVs are written. `None` uses the OS temp dir, the
+/// only location before this was configurable.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoragePrefs {
+    pub scratch_dir: Option<PathBuf>,
+}
+
+impl StoragePrefs {
+    /// The folder scratch files actually go to.
+    pub fn resolved(&self) -> PathBuf {
+        self.scratch_dir.clone().unwrap_or_else(std::env::temp_dir)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.scratch_dir {
+            Some(dir) if !dir.is_dir() => {
+                Err(format!("Scratch folder not found: {}", dir.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What happens when a render finishes or fails. Cancelled renders and
+/// nest renders stay silent either way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyPrefs {
+    /// Play a short chime on the output device.
+    pub chime: bool,
+    /// Post an OS notification naming the output file, but only while the
+    /// VeloCut window is in the background.
+    pub notification: bool,
+}
+
+impl Default for NotifyPrefs {
+    fn default() -> Self {
+        Self {
+            chime: true,
+            notification: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(PerformancePrefs::default().validate(), Ok(()));
+        assert_eq!(
+            PerformancePrefs::default().frame_cache_bytes(),
+            192 * 1024 * 1024
+        );
+    }
+
+    #[test]
+    fn clamped_repairs_out_of_range_values() {
+        let p = PerformancePrefs {
+            frame_cache_mb: 0,
+            decode_threads: 99,
+            proxy_width: 333,
+            playback_ahead: 0,
+        }
+        .clamped();
+        assert_eq!(p.frame_cache_mb, 64);
+        assert_eq!(p.decode_threads, 8);
+        assert_eq!(p.proxy_width, 320);
+        assert_eq!(p.playback_ahead, 2);
+        assert_eq!(p.validate(), Ok(()));
+    }
+
+    #[test]
+    fn preview_scrub_span_is_clamped() {
+        assert_eq!(PlaybackPrefs::default().preview_scrub_span(), 10.0);
+        let p = PlaybackPrefs {
+            preview_scrub_secs: 0,
+            ..Default::default()
+        };
+        assert_eq!(p.preview_scrub_span(), 1.0);
+    }
+
+    #[test]
+    fn roll_secs_converts_and_clamps() {
+        assert_eq!(PlaybackPrefs::default().roll_secs(), (2.0, 2.0));
+        let p = PlaybackPrefs {
+            pre_roll_ms: 500,
+            post_roll_ms: 60_000,
+            ..Default::default()
+        };
+        assert_eq!(p.roll_secs(), (0.5, 10.0));
+    }
+
+    #[test]
+    fn default_layout_shows_both_panels_unswapped() {
+        let p = LayoutPrefs::default();
+        assert!(!p.swap_sides);
+        assert!(p.show_library && p.show_export);
+        assert!(!p.always_on_top);
+    }
+
+    #[test]
+    fn clip_appearance_is_clamped() {
+        let p = LayoutPrefs {
+            clip_radius: 200,
+            waveform_opacity: 250,
+            ..Default::default()
+        };
+        assert_eq!(p.clip_corner(), 10);
+        assert_eq!(p.waveform_alpha(), 1.0);
+        assert_eq!(LayoutPrefs::default().clip_corner(), 4);
+    }
+
+    #[test]
+    fn render_alerts_default_on() {
+        let p = NotifyPrefs::default();
+        assert!(p.chime && p.notification);
+    }
+
+    #[test]
+    fn storage_defaults_to_os_temp_dir() {
+        let p = StoragePrefs::default();
+        assert_eq!(p.resolved(), std::env::temp_dir());
+        assert_eq!(p.validate(), Ok(()));
+    }
+
+    #[test]
+    fn storage_rejects_missing_folder() {
+        let p = StoragePrefs {
+            scratch_dir: Some(std::env::temp_dir().join("velocut_no_such_dir_4f1c")),
+        };
+        assert!(p.validate().is_err());
+    }
+
+    #[test]
+    fn auto_scroll_pages_or_follows_the_playhead() {
+        // 1000 px of lanes scrolled to 2000 px.
+        assert_eq!(AutoScroll::Page.scroll_for(2500.0, 2000.0, 1000.0), None);
+        assert_eq!(
+            AutoScroll::Page.scroll_for(2960.0, 2000.0, 1000.0),
+            Some(2910.0)
+        );
+        assert_eq!(AutoScroll::Page.scroll_for(30.0, 2000.0, 1000.0), Some(0.0));
+        assert_eq!(AutoScroll::Follow.scroll_for(2400.0, 2000.0, 1000.0), None);
+        assert_eq!(
+            AutoScroll::Follow.scroll_for(2600.0, 2000.0, 1000.0),
+            Some(2100.0)
+        );
+        assert_eq!(AutoScroll::Off.scroll_for(9000.0, 2000.0, 1000.0), None);
+    }
+
+    #[test]
+    fn validate_rejects_zero_threads() {
+        let p = PerformancePrefs {
+            decode_threads: 0,
+            ..Default::default()
+        };
+        assert!(p.validate().is_err());
+    }
+}
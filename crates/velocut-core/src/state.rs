@@ -1,8 +1,13 @@
 // crates/velocut-core/src/state.rs
 // Pure project data — no egui, no ffmpeg, no runtime handles.
 // Serializable via serde. Used by both velocut-ui and velocut-core consumers.
+use crate::beat_grid::BeatGrid;
+use crate::media_types::{AudioStreamInfo, ColorRange};
+use crate::prefs::{LayoutPrefs, NotifyPrefs, PerformancePrefs, PlaybackPrefs, StoragePrefs};
 use crate::transitions::TimelineTransition;
+use crate::watermark::Watermark;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -19,10 +24,165 @@ pub enum AspectRatio {
     Anamorphic,    // 2.39:1 — Anamorphic widescreen
 }
 
+/// Default timeline length of a still-image clip; trim to change it.
+pub const IMAGE_CLIP_SECS: f64 = 5.0;
+
+/// Timeline frame rate of new projects, and of projects saved before it
+/// was a setting.
+pub const DEFAULT_PROJECT_FPS: u32 = 30;
+
+/// Project frame rates `SetProjectFps` accepts — the same span as export.
+pub const PROJECT_FPS_RANGE: std::ops::RangeInclusive<u32> = 1..=120;
+
+/// Shortest length a trim or split may leave a clip, in project frames.
+/// See `ProjectState::min_clip_secs`.
+const MIN_CLIP_FRAMES: f64 = 2.0;
+
+/// Clips sharing a track may touch but not cross by more than this
+/// (float slack from snapping and frame rounding).
+const OVERLAP_EPS: f64 = 1e-6;
+
+/// True when `a` (starting at `a_start`) and `b` share time, ignoring the
+/// shared edge of two clips that butt against each other. Rows are not
+/// compared.
+fn spans_overlap(a: &TimelineClip, a_start: f64, b: &TimelineClip) -> bool {
+    a_start < b.start_time + b.duration - OVERLAP_EPS
+        && b.start_time < a_start + a.duration - OVERLAP_EPS
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ClipType {
     Video,
     Audio,
+    /// Still picture (PNG / JPEG). Lives on V rows; every timeline clip of it
+    /// is a freeze of its only frame, so it has no source length and no audio.
+    Image,
+}
+
+impl ClipType {
+    /// Classify an import by file extension. Anything unrecognised is
+    /// treated as video and left to the probe to sort out.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let ext = path
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        match ext.as_str() {
+            "mp3" | "wav" | "aac" | "flac" | "ogg" | "m4a" => ClipType::Audio,
+            "png" | "jpg" | "jpeg" => ClipType::Image,
+            _ => ClipType::Video,
+        }
+    }
+
+    /// The legal track row nearest to `preferred_row` for this clip type.
+    /// Video → even rows only (V1=0, V2=2); audio → odd rows only (A1=1, A2=3).
+    pub fn legal_row(self, preferred_row: usize) -> usize {
+        match self {
+            ClipType::Video | ClipType::Image => {
+                let r = if preferred_row.is_multiple_of(2) {
+                    preferred_row
+                } else {
+                    preferred_row.saturating_sub(1)
+                };
+                r.min(2)
+            }
+            ClipType::Audio => {
+                let r = if preferred_row % 2 == 1 {
+                    preferred_row
+                } else {
+                    preferred_row + 1
+                };
+                r.min(3)
+            }
+        }
+    }
+}
+
+/// Which half of a combined audio+video source a library drop brings in.
+/// Only meaningful for `ClipType::Video` sources; anything else drops whole.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DropPart {
+    /// The clip as-is: video with its embedded audio.
+    #[default]
+    Both,
+    /// Video on a V row with its audio muted.
+    VideoOnly,
+    /// Audio alone on an A row, as its own `[Audio]` library entry.
+    AudioOnly,
+}
+
+impl DropPart {
+    /// The clip type the dropped clip will have — decides which lane it
+    /// lands on.
+    pub fn clip_type(self, source: ClipType) -> ClipType {
+        match (self, source) {
+            (DropPart::AudioOnly, ClipType::Video) => ClipType::Audio,
+            _ => source,
+        }
+    }
+}
+
+/// How export interprets a library clip's YUV range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorRangeMode {
+    /// Trust the range the probe read from the stream (limited when untagged).
+    #[default]
+    Auto,
+    /// Treat the source as full range, for mis-tagged screen recordings.
+    Full,
+    /// Treat the source as limited range, for mis-tagged camera files.
+    Limited,
+}
+
+impl ColorRangeMode {
+    /// All modes in picker order.
+    pub fn all() -> &'static [ColorRangeMode] {
+        &[
+            ColorRangeMode::Auto,
+            ColorRangeMode::Full,
+            ColorRangeMode::Limited,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorRangeMode::Auto => "Auto (from file)",
+            ColorRangeMode::Full => "Force full (0–255)",
+            ColorRangeMode::Limited => "Force limited (16–235)",
+        }
+    }
+}
+
+/// Whether export deinterlaces a library clip's video.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeinterlaceMode {
+    /// Deinterlace when the probe found interlaced field order.
+    #[default]
+    Auto,
+    /// Always deinterlace, for interlaced files flagged progressive.
+    On,
+    /// Never deinterlace.
+    Off,
+}
+
+impl DeinterlaceMode {
+    /// All modes in picker order.
+    pub fn all() -> &'static [DeinterlaceMode] {
+        &[
+            DeinterlaceMode::Auto,
+            DeinterlaceMode::On,
+            DeinterlaceMode::Off,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DeinterlaceMode::Auto => "Auto (from file)",
+            DeinterlaceMode::On => "Force deinterlace",
+            DeinterlaceMode::Off => "Off",
+        }
+    }
 }
 
 /// Source file in the media bin
@@ -50,6 +210,101 @@ pub struct LibraryClip {
     /// into the WAV: `seek_t = elapsed + source_offset - audio_trimmed_offset`.
     #[serde(default)]
     pub audio_trimmed_offset: f64,
+    /// Audio streams found by the probe. Re-probed every launch, so not saved.
+    #[serde(skip)]
+    pub audio_streams: Vec<AudioStreamInfo>,
+    /// Container index of the audio stream this clip uses for preview WAV,
+    /// waveform and export. `None` = FFmpeg's "best" stream (the default).
+    #[serde(default)]
+    pub audio_stream_index: Option<usize>,
+    /// Video range reported by the probe (`None` until probed, or for audio
+    /// files). Re-probed every launch, so not saved.
+    #[serde(skip)]
+    pub color_range: Option<ColorRange>,
+    /// Average video frame rate reported by the probe (`None` until probed,
+    /// or for audio files and stills). Re-probed every launch, so not saved.
+    #[serde(skip)]
+    pub frame_rate: Option<f64>,
+    /// Size of the source file on disk, from the probe. Runtime only.
+    #[serde(skip)]
+    pub file_bytes: Option<u64>,
+    /// Container bit rate in bits/s, from the probe (`None` when the
+    /// container reports none). Runtime only.
+    #[serde(skip)]
+    pub bit_rate: Option<u64>,
+    /// Why the probe gave up on this source (unreadable file, no video
+    /// stream, …). A failed source no longer counts as loading. Runtime only.
+    #[serde(skip)]
+    pub probe_error: Option<String>,
+    /// Per-clip override of `color_range` used by export.
+    #[serde(default)]
+    pub color_range_mode: ColorRangeMode,
+    /// Whether the probe found interlaced field order (`None` until probed,
+    /// or for audio files). Re-probed every launch, so not saved.
+    #[serde(skip)]
+    pub interlaced: Option<bool>,
+    /// Per-clip override of `interlaced` used by export.
+    #[serde(default)]
+    pub deinterlace_mode: DeinterlaceMode,
+}
+
+impl LibraryClip {
+    /// Test fixture: a 1080p video source at `path`, already probed to
+    /// `duration` seconds.
+    #[cfg(test)]
+    pub(crate) fn probed_video(id: Uuid, path: &str, duration: f64) -> Self {
+        let path = PathBuf::from(path);
+        Self {
+            id,
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path,
+            duration,
+            clip_type: ClipType::Video,
+            thumbnail_path: None,
+            duration_probed: true,
+            waveform_peaks: Vec::new(),
+            video_size: Some((1920, 1080)),
+            audio_path: None,
+            audio_trimmed_offset: 0.0,
+            audio_streams: Vec::new(),
+            audio_stream_index: None,
+            color_range: None,
+            frame_rate: None,
+            file_bytes: None,
+            bit_rate: None,
+            probe_error: None,
+            color_range_mode: ColorRangeMode::Auto,
+            interlaced: None,
+            deinterlace_mode: DeinterlaceMode::Auto,
+        }
+    }
+
+    /// True while the probe has yet to report this source's duration or,
+    /// for video and stills, its frame size — export must wait for both.
+    pub fn is_loading(&self) -> bool {
+        self.probe_error.is_none()
+            && (!self.duration_probed
+                || (self.clip_type != ClipType::Audio && self.video_size.is_none()))
+    }
+
+    /// Whether export should treat this source as full range. Full sources
+    /// are remapped to limited so mixed-range timelines match.
+    pub fn is_full_range(&self) -> bool {
+        match self.color_range_mode {
+            ColorRangeMode::Auto => self.color_range == Some(ColorRange::Full),
+            ColorRangeMode::Full => true,
+            ColorRangeMode::Limited => false,
+        }
+    }
+
+    /// Whether export should deinterlace this source before scaling.
+    pub fn needs_deinterlace(&self) -> bool {
+        match self.deinterlace_mode {
+            DeinterlaceMode::Auto => self.interlaced == Some(true),
+            DeinterlaceMode::On => true,
+            DeinterlaceMode::Off => false,
+        }
+    }
 }
 
 /// An instance of a LibraryClip placed on the timeline
@@ -88,6 +343,154 @@ pub struct TimelineClip {
     pub fade_out_end_secs: f32,
     #[serde(default)]
     pub filter: crate::filters::FilterParams,
+    /// User-assigned organisation tag (sRGB). Replaces the default stripe color
+    /// on the timeline; `None` = untagged.
+    #[serde(default)]
+    pub tag_color: Option<[u8; 3]>,
+    /// Short free-text note shown in the clip tooltip and context menu.
+    #[serde(default)]
+    pub note: String,
+    /// Freeze-frame hold: source timestamp of the single frame this clip
+    /// repeats for its whole `duration`. Freeze clips are silent
+    /// (`audio_muted`) and never advance through the source.
+    #[serde(default)]
+    pub freeze_at: Option<f64>,
+    /// Poster frame: source timestamp whose frame is shown as this clip's
+    /// first thumbnail tile instead of the library thumbnail.
+    #[serde(default)]
+    pub poster_offset: Option<f64>,
+    /// Clips sharing a group move and trim together (Extract Audio links the
+    /// pair automatically; the context menu links / unlinks by hand).
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
+    /// Disabled clips stay on the timeline (drawn hatched, still selectable
+    /// and movable) but are skipped by preview, export and cut lists — for
+    /// A/B-ing alternate takes without deleting one.
+    #[serde(default = "default_clip_enabled")]
+    pub enabled: bool,
+    /// Export-time stabilization of shaky footage; `None` = off. Video rows
+    /// only.
+    #[serde(default)]
+    pub stabilize: Option<Stabilize>,
+    /// Gain automation, sorted by source time. Source- rather than
+    /// clip-relative so trims and splits leave every key where it was.
+    #[serde(default)]
+    pub volume_keys: Vec<VolumeKey>,
+    /// Solid-color slate (sRGB): a synthetic clip with no source file that
+    /// fills its whole `duration` with this color. Slates have a nil
+    /// `media_id`, are silent (`audio_muted`) and trim without a source
+    /// length limit.
+    #[serde(default)]
+    pub slate: Option<[u8; 3]>,
+}
+
+/// Per-clip stabilization settings. The camera path is measured from the
+/// clip's source range when it is exported, smoothed by `strength`, and the
+/// picture is zoomed in slightly so the crop can follow the smoothed path.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stabilize {
+    /// 0.1–1.0. Higher smooths over a longer window and zooms in further
+    /// (3 % of each edge at the low end, 10 % at full strength).
+    pub strength: f32,
+}
+
+impl Default for Stabilize {
+    fn default() -> Self {
+        Self { strength: 0.5 }
+    }
+}
+
+impl Stabilize {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.1..=1.0).contains(&self.strength) {
+            return Err(format!(
+                "Stabilization strength {:.2} must be between 0.1 and 1.0",
+                self.strength
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One point of a clip's volume automation. Gain is interpolated linearly
+/// in dB between keys and held flat before the first and after the last.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VolumeKey {
+    /// Source-file time in seconds.
+    pub source: f64,
+    /// Gain relative to the clip volume; 0 dB = unchanged.
+    pub gain_db: f32,
+}
+
+/// Linear gain of the automation `keys` at source time `t`; 1.0 with no keys.
+pub fn volume_key_gain(keys: &[VolumeKey], t: f64) -> f32 {
+    let db = match keys.iter().position(|k| k.source > t) {
+        None => keys.last().map_or(0.0, |k| k.gain_db),
+        Some(0) => keys[0].gain_db,
+        Some(i) => {
+            let (a, b) = (keys[i - 1], keys[i]);
+            let f = ((t - a.source) / (b.source - a.source).max(1e-9)) as f32;
+            a.gain_db + (b.gain_db - a.gain_db) * f
+        }
+    };
+    10f32.powf(db / 20.0)
+}
+
+/// Auto-duck settings: lower a music clip by `duck_db` wherever a speech
+/// clip is louder than `threshold_db`, ramping down over `attack` seconds
+/// before the speech starts and back up over `release` seconds after it ends.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Duck {
+    pub duck_db: f32,
+    pub threshold_db: f32,
+    pub attack: f64,
+    pub release: f64,
+}
+
+impl Default for Duck {
+    fn default() -> Self {
+        Self {
+            duck_db: -12.0,
+            threshold_db: -35.0,
+            attack: 0.15,
+            release: 0.4,
+        }
+    }
+}
+
+impl Duck {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-40.0..=-1.0).contains(&self.duck_db) {
+            return Err(format!(
+                "Duck amount {:.0} dB must be between -40 and -1",
+                self.duck_db
+            ));
+        }
+        if !(-70.0..=-10.0).contains(&self.threshold_db) {
+            return Err(format!(
+                "Speech threshold {:.0} dBFS must be between -70 and -10",
+                self.threshold_db
+            ));
+        }
+        if !(0.0..=2.0).contains(&self.attack) {
+            return Err(format!("Attack {:.2}s must be 0–2 s", self.attack));
+        }
+        if !(0.0..=5.0).contains(&self.release) {
+            return Err(format!("Release {:.2}s must be 0–5 s", self.release));
+        }
+        Ok(())
+    }
+}
+
+/// Last speech scan for auto-duck, kept until applied or re-scanned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeechRegions {
+    /// Music clip the duck lands on.
+    pub clip: Uuid,
+    /// Speech clip that was scanned.
+    pub speech: Uuid,
+    /// Speech regions in the speech clip's source seconds.
+    pub regions: Vec<(f64, f64)>,
 }
 
 impl TimelineClip {
@@ -95,12 +498,80 @@ impl TimelineClip {
     pub fn has_filter(&self) -> bool {
         !self.filter.is_identity()
     }
+
+    /// Source-file timestamp visible `local` seconds into the clip.
+    /// Freeze clips always show `freeze_at`.
+    pub fn source_time(&self, local: f64) -> f64 {
+        self.freeze_at
+            .unwrap_or(self.source_offset + local)
+            .max(0.0)
+    }
 }
 
 fn default_clip_volume() -> f32 {
     1.0
 }
 
+fn default_clip_enabled() -> bool {
+    true
+}
+
+/// An enabled, unity-volume clip with nil ids at 0 s on V1 — what a freshly
+/// deserialized clip gets for every `#[serde(default)]` field.
+impl Default for TimelineClip {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            media_id: Uuid::nil(),
+            start_time: 0.0,
+            duration: 0.0,
+            track_row: 0,
+            source_offset: 0.0,
+            volume: default_clip_volume(),
+            linked_clip_id: None,
+            audio_muted: false,
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+            filter: Default::default(),
+            tag_color: None,
+            note: String::new(),
+            freeze_at: None,
+            poster_offset: None,
+            group_id: None,
+            enabled: default_clip_enabled(),
+            stabilize: None,
+            volume_keys: Vec::new(),
+            slate: None,
+        }
+    }
+}
+
+/// A named point on the timeline ruler (beat, cue, sync point). Clip moves,
+/// trims and the playhead snap to markers; a dragged marker snaps to clip
+/// edges and the playhead.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    pub id: Uuid,
+    pub time: f64,
+}
+
+/// A timeline range flattened into one rendered clip. Keeps what was
+/// removed, at its original timeline positions, so the range can be
+/// expanded back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Nest {
+    /// The timeline clip that plays the render.
+    pub clip_id: Uuid,
+    pub start: f64,
+    pub end: f64,
+    pub clips: Vec<TimelineClip>,
+    /// Transitions that followed the removed clips, with their original
+    /// `after_clip_id`s.
+    pub transitions: Vec<TimelineTransition>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectState {
     /// Serialization format version. Increment when the project format changes
@@ -119,12 +590,40 @@ pub struct ProjectState {
     pub volume: f32,
     #[serde(default)]
     pub muted: bool,
+    /// Frame rate the timeline snaps, steps and trims in. Separate from the
+    /// export rate; change it with `SetProjectFps`.
+    #[serde(default = "default_project_fps")]
+    pub project_fps: u32,
     /// Transitions between adjacent timeline clips.
     /// Per-boundary transitions stored by clip UUID so they survive reordering.
     /// Keyed by the TimelineClip ID that comes BEFORE the transition.
     #[serde(default)]
     pub transitions: Vec<TimelineTransition>,
+    /// Logo burned into every exported frame and drawn over the preview.
+    #[serde(default)]
+    pub watermark: Option<Watermark>,
+    /// Ruler markers, kept sorted by time.
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    /// Beat lines for cutting to music; `None` = no grid.
+    #[serde(default)]
+    pub beat_grid: Option<BeatGrid>,
+    /// Ranges flattened by "Nest", for un-nesting.
+    #[serde(default)]
+    pub nests: Vec<Nest>,
     pub pending_probes: Vec<(Uuid, PathBuf)>,
+    /// Startup re-probes of a reopened project held back until the clip's
+    /// library card is on screen or the clip is on the timeline — a large
+    /// library no longer floods the worker at launch.
+    #[serde(skip)]
+    pub deferred_probes: Vec<(Uuid, PathBuf)>,
+    /// Clips of the startup refresh not yet finished (deferred or probing).
+    /// With `refresh_total` it drives "Refreshing media (n/total)".
+    #[serde(skip)]
+    pub refreshing: HashSet<Uuid>,
+    /// Library size when the startup refresh began; 0 once it is done.
+    #[serde(skip)]
+    pub refresh_total: usize,
     /// (clip_id, source_path, timestamp, dest_path)
     #[serde(skip)]
     pub pending_extracts: Vec<(Uuid, PathBuf, f64, PathBuf)>,
@@ -137,6 +636,94 @@ pub struct ProjectState {
     /// Brief status message shown in timeline toolbar after a frame save
     #[serde(skip)]
     pub save_status: Option<String>,
+    /// One-shot horizontal scroll request for the timeline (seconds at the
+    /// visible left edge). Set by `ScrollTimelineTo`, applied by timeline.rs,
+    /// and cleared by app.rs once the timeline panel has drawn.
+    #[serde(skip)]
+    pub timeline_scroll_to: Option<f64>,
+    /// Result of the last Match Frame: library entry and source timestamp.
+    /// The library scrolls that card into view and shows the timestamp;
+    /// cleared when the library selection moves elsewhere.
+    #[serde(skip)]
+    pub match_frame: Option<(Uuid, f64)>,
+    /// Timeline clip whose source is being scanned for scene cuts.
+    #[serde(skip)]
+    pub scene_detecting: Option<Uuid>,
+    /// Last scene-detection result: timeline clip id and cut times in source
+    /// seconds. Drawn as ticks on the clip until split or re-detected.
+    #[serde(skip)]
+    pub scene_cuts: Option<(Uuid, Vec<f64>)>,
+    /// Library clip open in the source viewer, if any.
+    #[serde(skip)]
+    pub source_viewer_clip: Option<Uuid>,
+    /// Timeline clip whose audio is being scanned for silence.
+    #[serde(skip)]
+    pub silence_detecting: Option<Uuid>,
+    /// Last silence-scan result: timeline clip id and the sounding source
+    /// range (`None` = nothing above the threshold). Kept until trimmed or
+    /// re-scanned.
+    #[serde(skip)]
+    pub silence_range: Option<(Uuid, Option<(f64, f64)>)>,
+    /// Timeline clip being audio-synced, and the clip it is synced against.
+    #[serde(skip)]
+    pub sync_detecting: Option<(Uuid, Uuid)>,
+    /// Last audio-sync result: clip id, reference clip id, and the source
+    /// offset between them (`None` = no match). Kept until applied or
+    /// re-scanned.
+    #[serde(skip)]
+    pub audio_sync: Option<(Uuid, Uuid, Option<f64>)>,
+    /// Music clip whose speech partner is being scanned for auto-duck, and
+    /// that speech clip.
+    #[serde(skip)]
+    pub duck_detecting: Option<(Uuid, Uuid)>,
+    /// Last speech scan for auto-duck.
+    #[serde(skip)]
+    pub speech_regions: Option<SpeechRegions>,
+    /// Decode / cache limits. Machine-level, so persisted in `AppStorage`
+    /// rather than with the project; loaded into here at startup so modules
+    /// can show the current values.
+    #[serde(skip)]
+    pub performance: PerformancePrefs,
+    /// Space-key behaviour. Machine-level like `performance`.
+    #[serde(skip)]
+    pub playback_prefs: PlaybackPrefs,
+    /// Side-panel placement and visibility. Machine-level like `performance`.
+    #[serde(skip)]
+    pub layout_prefs: LayoutPrefs,
+    /// Scratch folder for extracted audio. Machine-level like `performance`.
+    #[serde(skip)]
+    pub storage_prefs: StoragePrefs,
+    /// Render-finished chime / OS notification. Machine-level like
+    /// `performance`.
+    #[serde(skip)]
+    pub notify_prefs: NotifyPrefs,
+    /// VeloCut WAVs in the scratch folder: (file count, bytes). Refreshed by
+    /// app.rs on startup and after storage commands, not every frame.
+    #[serde(skip)]
+    pub scratch_usage: Option<(usize, u64)>,
+    /// Preview-only playback rate (one of `PREVIEW_RATES`). Scales how fast
+    /// the playhead advances; export never sees it.
+    #[serde(skip, default = "default_preview_rate")]
+    pub preview_rate: f32,
+    /// Preview shows sources ungraded — clip filters are skipped on the way
+    /// to the screen for before/after comparison. View-only: export always
+    /// applies them and toggling it is not an undo step.
+    #[serde(skip)]
+    pub bypass_effects: bool,
+    /// Running "play around playhead" review: (stop at, return to). Playback
+    /// pauses at the first and puts the playhead back on the second; any
+    /// other transport command or seek cancels it.
+    #[serde(skip)]
+    pub play_around: Option<(f64, f64)>,
+    /// 256-bin luma histogram of the last decoded preview frame (before clip
+    /// filters), for the levels editor. Empty until a frame arrives. Copied
+    /// in from the frame cache by app.rs.
+    #[serde(skip)]
+    pub preview_histogram: Vec<u32>,
+    /// `overlapping_clips()` for the current timeline. Refreshed by app.rs
+    /// once per frame so the timeline and export panels share one O(n²) pass.
+    #[serde(skip)]
+    pub overlaps: HashSet<Uuid>,
 
     // ── Encode status (runtime-only, not serialized) ──────────────────────────
     /// UUID of the currently running encode job, or None when idle.
@@ -150,10 +737,27 @@ pub struct ProjectState {
     /// Set to the output PathBuf on EncodeDone. ExportModule shows a ✓ banner.
     #[serde(skip)]
     pub encode_done: Option<PathBuf>,
+    /// Pre-clamp audio peak of the finished render (linear). The done modal
+    /// warns when it is above 1.0, i.e. the export clipped.
+    #[serde(skip)]
+    pub encode_audio_peak: Option<f32>,
+    /// Audio codec of the finished render, from `EncodeDone`. `None` after a
+    /// done render means it went out video-only (no usable audio encoder).
+    #[serde(skip)]
+    pub encode_audio_codec: Option<&'static str>,
+    /// Corrupt video frames the finished render skipped (error-resilient
+    /// decode). The done modal warns when it is non-zero.
+    #[serde(skip)]
+    pub encode_skipped_frames: u64,
     /// Set to the error/cancel message on EncodeError. ExportModule shows a ✕ banner.
     /// The string "cancelled" is the sentinel for a user-initiated cancel.
     #[serde(skip)]
     pub encode_error: Option<String>,
+    /// Set when `encode_job` is a nest render: the job id and the timeline
+    /// range it flattens. app.rs swaps the range for the render once the
+    /// job is done.
+    #[serde(skip)]
+    pub nest_job: Option<(Uuid, f64, f64)>,
 
     // ── Undo / Redo lengths (runtime-only) ───────────────────────────────────
     /// Number of snapshots on the undo stack. Written by app.rs::sync_undo_len()
@@ -164,6 +768,13 @@ pub struct ProjectState {
     /// Number of snapshots on the redo stack.
     #[serde(skip)]
     pub redo_len: usize,
+    /// Label of the edit the next Undo would revert ("Move clip"), for the
+    /// button tooltip. Written alongside `undo_len`.
+    #[serde(skip)]
+    pub undo_label: Option<String>,
+    /// Label of the edit the next Redo would re-apply.
+    #[serde(skip)]
+    pub redo_label: Option<String>,
 }
 
 fn default_version() -> u32 {
@@ -174,6 +785,20 @@ fn default_volume() -> f32 {
     1.0
 }
 
+fn default_project_fps() -> u32 {
+    DEFAULT_PROJECT_FPS
+}
+
+/// Review speeds offered by the transport bar's rate button.
+pub const PREVIEW_RATES: [f32; 7] = [0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 4.0];
+
+/// Above this preview rate audio is muted instead of sped up.
+pub const PREVIEW_RATE_AUDIO_MAX: f32 = 2.0;
+
+fn default_preview_rate() -> f32 {
+    1.0
+}
+
 impl Default for ProjectState {
     fn default() -> Self {
         Self {
@@ -188,18 +813,54 @@ impl Default for ProjectState {
             timeline_zoom: 50.0,
             volume: 1.0,
             muted: false,
+            project_fps: DEFAULT_PROJECT_FPS,
             transitions: Vec::new(),
+            watermark: None,
+            beat_grid: None,
+            markers: Vec::new(),
+            nests: Vec::new(),
             pending_probes: Vec::new(),
+            deferred_probes: Vec::new(),
+            refreshing: HashSet::new(),
+            refresh_total: 0,
             pending_extracts: Vec::new(),
             pending_audio_cleanup: Vec::new(),
             pending_save_pick: None,
             save_status: None,
+            timeline_scroll_to: None,
+            match_frame: None,
+            scene_detecting: None,
+            scene_cuts: None,
+            source_viewer_clip: None,
+            silence_detecting: None,
+            silence_range: None,
+            sync_detecting: None,
+            audio_sync: None,
+            duck_detecting: None,
+            speech_regions: None,
+            performance: PerformancePrefs::default(),
+            playback_prefs: PlaybackPrefs::default(),
+            layout_prefs: LayoutPrefs::default(),
+            storage_prefs: StoragePrefs::default(),
+            notify_prefs: NotifyPrefs::default(),
+            scratch_usage: None,
+            preview_rate: 1.0,
+            bypass_effects: false,
+            play_around: None,
+            preview_histogram: Vec::new(),
+            overlaps: HashSet::new(),
             encode_job: None,
             encode_progress: None,
             encode_done: None,
+            encode_audio_peak: None,
+            encode_audio_codec: None,
+            encode_skipped_frames: 0,
             encode_error: None,
+            nest_job: None,
             undo_len: 0,
             redo_len: 0,
+            undo_label: None,
+            redo_label: None,
         }
     }
 }
@@ -261,37 +922,147 @@ impl ProjectState {
             .to_string_lossy()
             .to_string();
 
-        let ext = path
-            .extension()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_lowercase();
-
-        let is_audio = matches!(ext.as_str(), "mp3" | "wav" | "aac" | "flac" | "ogg" | "m4a");
+        let clip_type = ClipType::from_path(&path);
+        let is_image = clip_type == ClipType::Image;
         let id = Uuid::new_v4();
 
+        // Stills have no intrinsic length: give them the default up front so
+        // they are placeable immediately; the probe only fetches size + thumbnail.
         self.library.push(LibraryClip {
             id,
             path: path.clone(),
             name,
-            duration: 0.0,
-            clip_type: if is_audio {
-                ClipType::Audio
-            } else {
-                ClipType::Video
-            },
+            duration: if is_image { IMAGE_CLIP_SECS } else { 0.0 },
+            clip_type,
             thumbnail_path: None,
-            duration_probed: false,
+            duration_probed: is_image,
             waveform_peaks: Vec::new(),
             video_size: None,
             audio_path: None,
             audio_trimmed_offset: 0.0,
+            audio_streams: Vec::new(),
+            audio_stream_index: None,
+            color_range: None,
+            frame_rate: None,
+            file_bytes: None,
+            bit_rate: None,
+            probe_error: None,
+            color_range_mode: ColorRangeMode::Auto,
+            interlaced: None,
+            deinterlace_mode: DeinterlaceMode::Auto,
         });
         self.pending_probes.push((id, path));
         id
     }
 
+    /// Merge another project into this one — the "reuse an intro" workflow.
+    ///
+    /// Its library is appended, skipping sources already here (same file and
+    /// type). With `timeline_at`, its timeline follows, shifted so its first
+    /// clip starts there — or at the end of this timeline when that would
+    /// land on existing clips. Every imported clip gets a fresh id, and
+    /// groups, links and transitions are remapped to match. Nested clips come
+    /// in as plain clips. Returns (library entries added, clips added).
+    pub fn import_project(
+        &mut self,
+        other: ProjectState,
+        timeline_at: Option<f64>,
+    ) -> (usize, usize) {
+        let same_file = |a: &std::path::Path, b: &std::path::Path| {
+            a == b
+                || matches!(
+                    (std::fs::canonicalize(a), std::fs::canonicalize(b)),
+                    (Ok(ca), Ok(cb)) if ca == cb
+                )
+        };
+        let mut media: HashMap<Uuid, Uuid> = Default::default();
+        let mut added = 0;
+        for lib in other.library {
+            if let Some(existing) = self
+                .library
+                .iter()
+                .find(|l| l.clip_type == lib.clip_type && same_file(&l.path, &lib.path))
+            {
+                media.insert(lib.id, existing.id);
+                continue;
+            }
+            let id = Uuid::new_v4();
+            media.insert(lib.id, id);
+            // Keep what was probed so the clip is usable right away; the
+            // re-probe rebuilds the thumbnail and the preview WAV, which
+            // belonged to the other project's session.
+            self.pending_probes.push((id, lib.path.clone()));
+            self.library.push(LibraryClip {
+                id,
+                thumbnail_path: None,
+                audio_path: None,
+                audio_trimmed_offset: 0.0,
+                probe_error: None,
+                ..lib
+            });
+            added += 1;
+        }
+
+        let Some(at) = timeline_at else {
+            return (added, 0);
+        };
+        let clips: Vec<TimelineClip> = other
+            .timeline
+            .into_iter()
+            .filter(|c| media.contains_key(&c.media_id))
+            .collect();
+        let Some(first) = clips.iter().map(|c| c.start_time).min_by(f64::total_cmp) else {
+            return (added, 0);
+        };
+        let lands_on_existing = |shift: f64| {
+            clips.iter().any(|c| {
+                let (s, e) = (c.start_time + shift, c.start_time + c.duration + shift);
+                self.timeline.iter().any(|o| {
+                    o.track_row == c.track_row
+                        && o.start_time < e - OVERLAP_EPS
+                        && s < o.start_time + o.duration - OVERLAP_EPS
+                })
+            })
+        };
+        let mut shift = at.max(0.0) - first;
+        if lands_on_existing(shift) {
+            shift = self.total_duration() - first;
+        }
+
+        let ids: HashMap<Uuid, Uuid> = clips.iter().map(|c| (c.id, Uuid::new_v4())).collect();
+        let mut groups: HashMap<Uuid, Uuid> = Default::default();
+        let count = clips.len();
+        for c in clips {
+            let group_id = c
+                .group_id
+                .map(|g| *groups.entry(g).or_insert_with(Uuid::new_v4));
+            self.timeline.push(TimelineClip {
+                id: ids[&c.id],
+                media_id: media[&c.media_id],
+                start_time: c.start_time + shift,
+                linked_clip_id: c.linked_clip_id.and_then(|l| ids.get(&l).copied()),
+                group_id,
+                ..c
+            });
+        }
+        self.transitions
+            .extend(other.transitions.into_iter().filter_map(|tr| {
+                Some(TimelineTransition {
+                    after_clip_id: *ids.get(&tr.after_clip_id)?,
+                    ..tr
+                })
+            }));
+        (added, count)
+    }
+
     pub fn update_clip_duration(&mut self, id: Uuid, duration: f64) {
+        if self
+            .library
+            .iter()
+            .any(|c| c.id == id && c.clip_type == ClipType::Image)
+        {
+            return; // image2 reports a one-frame duration; keep the default
+        }
         if let Some(clip) = self.library.iter_mut().find(|c| c.id == id) {
             clip.duration = duration;
             clip.duration_probed = true;
@@ -318,11 +1089,19 @@ impl ProjectState {
     /// equal to the clip's source_offset for trimmed extractions
     /// (extract_audio_trimmed). AudioModule subtracts this from seek_t so
     /// both code paths land at the correct position in the WAV.
-    pub fn set_audio_path(&mut self, id: Uuid, path: PathBuf, trimmed_offset: f64) {
-        if let Some(clip) = self.library.iter_mut().find(|c| c.id == id) {
-            clip.audio_path = Some(path);
-            clip.audio_trimmed_offset = trimmed_offset;
-        }
+    ///
+    /// Returns the previous WAV path when it differs from `path` (the clip's
+    /// audio stream was switched), so the caller can retire it.
+    pub fn set_audio_path(
+        &mut self,
+        id: Uuid,
+        path: PathBuf,
+        trimmed_offset: f64,
+    ) -> Option<PathBuf> {
+        let clip = self.library.iter_mut().find(|c| c.id == id)?;
+        let old = clip.audio_path.replace(path);
+        clip.audio_trimmed_offset = trimmed_offset;
+        old.filter(|o| Some(o) != clip.audio_path.as_ref())
     }
 
     /// Place a library clip on the timeline.
@@ -332,32 +1111,17 @@ impl ProjectState {
     /// corrected to the nearest valid row if the user drops on the wrong type.
     /// Snaps to 0 if dropped within 0.5 s of the start.
     /// Snaps to just after the previous clip on the same track if within 1 s gap.
-    pub fn add_to_timeline(&mut self, media_id: Uuid, at_time: f64, preferred_row: usize) {
-        let lib_clip = match self.library.iter().find(|c| c.id == media_id) {
-            Some(c) => c.clone(),
-            None => return,
-        };
+    /// Returns the new clip's id, or `None` if `media_id` is not in the library.
+    pub fn add_to_timeline(
+        &mut self,
+        media_id: Uuid,
+        at_time: f64,
+        preferred_row: usize,
+    ) -> Option<Uuid> {
+        let lib_clip = self.library.iter().find(|c| c.id == media_id)?.clone();
 
         // ── Track enforcement ─────────────────────────────────────────────
-        // Video → even rows only (0, 2); Audio → odd rows only (1, 3).
-        let row = match lib_clip.clip_type {
-            ClipType::Video => {
-                let r = if preferred_row.is_multiple_of(2) {
-                    preferred_row
-                } else {
-                    preferred_row.saturating_sub(1)
-                };
-                r.min(2)
-            }
-            ClipType::Audio => {
-                let r = if preferred_row % 2 == 1 {
-                    preferred_row
-                } else {
-                    preferred_row + 1
-                };
-                r.min(3)
-            }
-        };
+        let row = lib_clip.clip_type.legal_row(preferred_row);
 
         let duration = lib_clip.duration.max(1.0);
 
@@ -383,25 +1147,477 @@ impl ProjectState {
             snapped = track_end; // butt up directly after previous clip
         }
 
-        self.timeline.push(TimelineClip {
-            id: Uuid::new_v4(),
-            media_id,
-            start_time: snapped,
-            duration,
-            track_row: row,
-            source_offset: 0.0,
-            volume: 1.0,
-            linked_clip_id: None,
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: crate::filters::FilterParams::none(),
-        });
+        Some(self.place_clip(media_id, snapped, duration, row))
     }
 
-    /// Extracts the audio from a video timeline clip onto the A track directly
+    /// Insert a library clip at `at_time` on its legal track and ripple:
+    /// every clip on that track starting at or after the insert point moves
+    /// right by the new clip's duration, along with any linked partner
+    /// (extracted audio) so A/V pairs stay in sync. If `at_time` falls inside
+    /// an existing clip the insert point moves to that clip's end — ripple
+    /// insert never splits. Returns the new clip's UUID.
+    pub fn ripple_insert(
+        &mut self,
+        media_id: Uuid,
+        at_time: f64,
+        preferred_row: usize,
+    ) -> Option<Uuid> {
+        let lib_clip = self.library.iter().find(|c| c.id == media_id)?;
+        let row = lib_clip.clip_type.legal_row(preferred_row);
+        let duration = lib_clip.duration.max(1.0);
+
+        let at = self.ripple_insert_point(row, at_time);
+
+        self.ripple_shift(row, at, duration);
+        Some(self.place_clip(media_id, at, duration, row))
+    }
+
+    /// Three-point insert: place source range `source_in..source_out` of
+    /// `media_id` at `at` on its legal row. A clip spanning `at` is split
+    /// there, and everything from `at` on shifts right by the range's length.
+    /// Returns the new clip's id.
+    pub fn insert_source_range(
+        &mut self,
+        media_id: Uuid,
+        source_in: f64,
+        source_out: f64,
+        at: f64,
+        preferred_row: usize,
+    ) -> Option<Uuid> {
+        let lib_clip = self.library.iter().find(|c| c.id == media_id)?;
+        let row = lib_clip.clip_type.legal_row(preferred_row);
+        let duration = source_out - source_in;
+        if duration <= 0.0 {
+            return None;
+        }
+        let at = at.max(0.0);
+
+        let under = self
+            .timeline
+            .iter()
+            .find(|c| c.track_row == row && at > c.start_time && at < c.start_time + c.duration)
+            .map(|c| c.id);
+        if let Some(under) = under {
+            self.split_clip_at(under, at);
+        }
+        self.ripple_shift(row, at, duration);
+        let id = self.place_clip(media_id, at, duration, row);
+        if let Some(c) = self.timeline.iter_mut().find(|c| c.id == id) {
+            c.source_offset = source_in;
+        }
+        Some(id)
+    }
+
+    /// Where `ripple_insert` would actually put a clip dropped at `at_time`
+    /// on `row`: the end of the clip under `at_time`, if any. Exposed so the
+    /// timeline can draw the insert marker where the clip will land.
+    pub fn ripple_insert_point(&self, row: usize, at_time: f64) -> f64 {
+        self.timeline
+            .iter()
+            .find(|c| {
+                c.track_row == row && at_time > c.start_time && at_time < c.start_time + c.duration
+            })
+            .map(|c| c.start_time + c.duration)
+            .unwrap_or(at_time)
+            .max(0.0)
+    }
+
+    /// The clip a split at timeline time `t` cuts: the first with more than
+    /// `min_clip_secs` on each side of `t`.
+    pub fn splittable_clip_at(&self, t: f64) -> Option<&TimelineClip> {
+        let min = self.min_clip_secs();
+        self.timeline
+            .iter()
+            .find(|c| t > c.start_time + min && t < c.start_time + c.duration - min)
+    }
+
+    /// Why a split at timeline time `t` is not possible, worded for the
+    /// user; `None` when `splittable_clip_at` finds a clip.
+    pub fn split_blocker(&self, t: f64) -> Option<String> {
+        if self.splittable_clip_at(t).is_some() {
+            return None;
+        }
+        if self.timeline.is_empty() {
+            return Some("The timeline is empty".to_string());
+        }
+        let Some(clip) = self
+            .timeline
+            .iter()
+            .find(|c| t >= c.start_time && t <= c.start_time + c.duration)
+        else {
+            return Some("Move the playhead onto a clip".to_string());
+        };
+        let name = self
+            .library
+            .iter()
+            .find(|l| l.id == clip.media_id)
+            .map_or("the clip", |l| l.name.as_str());
+        let edge = if t - clip.start_time < clip.start_time + clip.duration - t {
+            "start"
+        } else {
+            "end"
+        };
+        Some(format!(
+            "The playhead is too close to the {edge} of {name} — a split needs more than 2 frames on each side"
+        ))
+    }
+
+    /// Split timeline clip `id` at timeline time `t`. The original keeps the
+    /// head (and its fade-in); a new clip takes the tail (and the fade-out).
+    /// Returns the tail's id, or `None` when `t` is not strictly inside the clip.
+    pub fn split_clip_at(&mut self, id: Uuid, t: f64) -> Option<Uuid> {
+        let clip = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id && t > c.start_time && t < c.start_time + c.duration)
+            .cloned()?;
+        let split_offset = t - clip.start_time; // seconds into clip
+
+        // Shorten the original clip to become the first half.
+        // Clear its fade_out — that belongs to the new tail segment now.
+        if let Some(c) = self.timeline.iter_mut().find(|c| c.id == clip.id) {
+            c.duration = split_offset;
+            c.fade_out_secs = 0.0;
+            c.fade_out_end_secs = 0.0;
+            c.poster_offset = c
+                .poster_offset
+                .filter(|&p| p < clip.source_offset + split_offset);
+        }
+
+        // Push the second half as a new clip immediately after.
+        let tail_id = Uuid::new_v4();
+        self.timeline.push(TimelineClip {
+            id: tail_id,
+            media_id: clip.media_id,
+            start_time: t,
+            duration: clip.duration - split_offset,
+            source_offset: clip.source_offset + split_offset,
+            track_row: clip.track_row,
+            volume: clip.volume,
+            linked_clip_id: None,
+            audio_muted: clip.audio_muted,
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: clip.fade_out_secs,
+            fade_out_end_secs: clip.fade_out_end_secs,
+            filter: Default::default(),
+            tag_color: clip.tag_color,
+            note: clip.note.clone(),
+            freeze_at: clip.freeze_at,
+            // The poster stays with whichever half still contains its frame.
+            poster_offset: clip
+                .poster_offset
+                .filter(|&p| p >= clip.source_offset + split_offset),
+            // Regrouped by `split_group_at` when the whole group is cut.
+            group_id: None,
+            enabled: clip.enabled,
+            stabilize: clip.stabilize,
+            volume_keys: clip.volume_keys.clone(),
+            slate: clip.slate,
+        });
+        Some(tail_id)
+    }
+
+    /// Hold the video frame under timeline time `at` for `duration` seconds.
+    ///
+    /// Splits the lowest-row video clip under `at`, ripples everything on that
+    /// row from `at` onward right by `duration` (linked audio partners follow),
+    /// and places a silent freeze clip of the split frame in the gap. Returns
+    /// the freeze clip's id.
+    pub fn insert_freeze_frame(&mut self, at: f64, duration: f64) -> Option<Uuid> {
+        let clip = self
+            .timeline
+            .iter()
+            .filter(|c| {
+                c.track_row % 2 == 0 && at >= c.start_time && at < c.start_time + c.duration
+            })
+            .min_by_key(|c| c.track_row)
+            .cloned()?;
+        let hold_ts = clip.source_time(at - clip.start_time);
+        self.open_insert_gap(&clip, at, duration);
+
+        let id = Uuid::new_v4();
+        self.timeline.push(TimelineClip {
+            id,
+            media_id: clip.media_id,
+            start_time: at,
+            duration,
+            track_row: clip.track_row,
+            source_offset: hold_ts,
+            volume: clip.volume,
+            linked_clip_id: None,
+            audio_muted: true,
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+            filter: clip.filter.clone(),
+            tag_color: clip.tag_color,
+            note: String::new(),
+            freeze_at: Some(hold_ts),
+            poster_offset: None,
+            group_id: None,
+            enabled: clip.enabled,
+            stabilize: None,
+            volume_keys: Vec::new(),
+            slate: clip.slate,
+        });
+        Some(id)
+    }
+
+    /// Insert a solid `color` slate of `duration` seconds on V1 at timeline
+    /// time `at`. A clip under `at` is split and everything on V1 from `at`
+    /// onward ripples right (linked audio follows), as for a freeze frame.
+    /// Returns the slate's id.
+    pub fn insert_slate(&mut self, at: f64, duration: f64, color: [u8; 3]) -> Uuid {
+        let under = self
+            .timeline
+            .iter()
+            .find(|c| c.track_row == 0 && at > c.start_time && at < c.start_time + c.duration)
+            .cloned();
+        match under {
+            Some(clip) => self.open_insert_gap(&clip, at, duration),
+            None => self.ripple_shift(0, at, duration),
+        }
+
+        let id = Uuid::new_v4();
+        self.timeline.push(TimelineClip {
+            id,
+            media_id: Uuid::nil(),
+            start_time: at,
+            duration,
+            track_row: 0,
+            source_offset: 0.0,
+            volume: 1.0,
+            linked_clip_id: None,
+            audio_muted: true,
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+            filter: crate::filters::FilterParams::none(),
+            tag_color: None,
+            note: String::new(),
+            freeze_at: None,
+            poster_offset: None,
+            group_id: None,
+            enabled: true,
+            stabilize: None,
+            volume_keys: Vec::new(),
+            slate: Some(color),
+        });
+        id
+    }
+
+    /// Split `clip` (and its extracted-audio partner) at `at` and ripple its
+    /// row right by `duration`, leaving an empty span for an inserted clip.
+    fn open_insert_gap(&mut self, clip: &TimelineClip, at: f64, duration: f64) {
+        // Split an extracted-audio partner too and re-link the two tails, so
+        // the ripple below carries the tail's audio along with its video.
+        let video_tail = self.split_clip_at(clip.id, at);
+        let audio_tail = clip.linked_clip_id.and_then(|p| self.split_clip_at(p, at));
+        if let (Some(vt), Some(at_id)) = (video_tail, audio_tail) {
+            for c in self.timeline.iter_mut() {
+                if c.id == vt {
+                    c.linked_clip_id = Some(at_id);
+                } else if c.id == at_id {
+                    c.linked_clip_id = Some(vt);
+                }
+            }
+        }
+        // A transition out of the split clip belongs to its tail, not to the
+        // cut into the inserted clip.
+        if let Some(vt) = video_tail {
+            for t in self
+                .transitions
+                .iter_mut()
+                .filter(|t| t.after_clip_id == clip.id)
+            {
+                t.after_clip_id = vt;
+            }
+        }
+        self.ripple_shift(clip.track_row, at, duration);
+    }
+
+    /// Move every clip on `row` starting at or after `at` right by `by`
+    /// seconds, together with any linked partner (extracted audio) so A/V
+    /// pairs stay in sync.
+    fn ripple_shift(&mut self, row: usize, at: f64, by: f64) {
+        let mut shifted: Vec<Uuid> = self
+            .timeline
+            .iter()
+            .filter(|c| c.track_row == row && c.start_time >= at - 1e-9)
+            .map(|c| c.id)
+            .collect();
+        let partners: Vec<Uuid> = self
+            .timeline
+            .iter()
+            .filter(|c| shifted.contains(&c.id))
+            .filter_map(|c| c.linked_clip_id)
+            .collect();
+        for p in partners {
+            if !shifted.contains(&p) {
+                shifted.push(p);
+            }
+        }
+        for c in self.timeline.iter_mut().filter(|c| shifted.contains(&c.id)) {
+            c.start_time += by;
+        }
+    }
+
+    /// End time of the last clip on `row`, or 0.0 when the track is empty.
+    /// The append point for end-to-end assembly.
+    pub fn track_end(&self, row: usize) -> f64 {
+        self.timeline
+            .iter()
+            .filter(|c| c.track_row == row)
+            .map(|c| c.start_time + c.duration)
+            .fold(0.0, f64::max)
+    }
+
+    /// Longest `duration` the clip can have at its current `source_offset`
+    /// before it runs off the end of its source file. `None` when there is no
+    /// limit: freeze clips repeat one frame, and unprobed sources have no
+    /// trustworthy length yet.
+    pub fn max_clip_duration(&self, clip: &TimelineClip) -> Option<f64> {
+        if clip.freeze_at.is_some() {
+            return None;
+        }
+        self.library
+            .iter()
+            .find(|l| l.id == clip.media_id)
+            .filter(|l| l.duration_probed && l.duration > 0.0)
+            .map(|l| (l.duration - clip.source_offset).max(0.0))
+    }
+
+    /// Length of one frame at the project frame rate.
+    pub fn frame_secs(&self) -> f64 {
+        1.0 / self.project_fps.max(1) as f64
+    }
+
+    /// Shortest length a trim or split may leave a clip: two project frames.
+    pub fn min_clip_secs(&self) -> f64 {
+        MIN_CLIP_FRAMES * self.frame_secs()
+    }
+
+    /// `t` rounded to the nearest project frame boundary.
+    pub fn snap_to_frame(&self, t: f64) -> f64 {
+        let frame = self.frame_secs();
+        (t / frame).round() * frame
+    }
+
+    /// Switch the project to `fps` and move every clip edge, marker,
+    /// transition length and the playhead onto the new frame grid. Each edge
+    /// rounds on its own, so clips that butted together still do; a clip
+    /// never conforms shorter than `min_clip_secs` or past its source.
+    /// A clip stretched to that minimum pushes the next clip's head back
+    /// (trimming it) rather than overlapping it.
+    pub fn conform_to_fps(&mut self, fps: u32) {
+        self.project_fps = fps;
+        let min = self.min_clip_secs();
+        let frame = self.frame_secs();
+        let snap = |t: f64| ((t / frame).round() * frame).max(0.0);
+        let maxes: Vec<Option<f64>> = self
+            .timeline
+            .iter()
+            .map(|c| self.max_clip_duration(c))
+            .collect();
+        let mut order: Vec<usize> = (0..self.timeline.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.timeline[a], &self.timeline[b]);
+            a.track_row
+                .cmp(&b.track_row)
+                .then(a.start_time.total_cmp(&b.start_time))
+        });
+        // Per track: the previous clip's end before and after conforming.
+        let mut ends: HashMap<usize, (f64, f64)> = HashMap::new();
+        for i in order {
+            let c = &mut self.timeline[i];
+            let end = snap(c.start_time + c.duration);
+            let mut start = snap(c.start_time);
+            let mut max = maxes[i];
+            if let Some(&(old_end, new_end)) = ends.get(&c.track_row) {
+                // Only clips that didn't overlap before are kept apart.
+                if c.start_time >= old_end - OVERLAP_EPS && start < new_end {
+                    let push = new_end - start;
+                    c.source_offset += push;
+                    max = max.map(|m| (m - push).max(0.0));
+                    start = new_end;
+                }
+            }
+            let d = (end - start).max(min);
+            ends.insert(c.track_row, (c.start_time + c.duration, start + d));
+            c.start_time = start;
+            c.duration = max.map_or(d, |m| d.min(m.max(min)));
+        }
+        for m in &mut self.markers {
+            m.time = snap(m.time);
+        }
+        for t in &mut self.transitions {
+            if t.kind.duration_secs > 0.0 {
+                t.kind.duration_secs = snap(t.kind.duration_secs as f64).max(frame) as f32;
+            }
+        }
+        self.current_time = snap(self.current_time);
+    }
+
+    /// Marker time within `tolerance` seconds of `t`, nearest first.
+    pub fn marker_near(&self, t: f64, tolerance: f64) -> Option<f64> {
+        self.markers
+            .iter()
+            .map(|m| m.time)
+            .filter(|m| (m - t).abs() < tolerance)
+            .min_by(|a, b| (a - t).abs().total_cmp(&(b - t).abs()))
+    }
+
+    /// Marker or beat-grid line within `tolerance` seconds of `t`, nearest
+    /// first — what clip moves, out-point trims and ruler scrubbing snap to.
+    pub fn snap_point_near(&self, t: f64, tolerance: f64) -> Option<f64> {
+        let beat = self.beat_grid.and_then(|g| g.beat_near(t, tolerance));
+        match (self.marker_near(t, tolerance), beat) {
+            (Some(m), Some(b)) if (b - t).abs() < (m - t).abs() => Some(b),
+            (m, b) => m.or(b),
+        }
+    }
+
+    /// Push a fresh, untrimmed timeline clip for `media_id` and return its id.
+    fn place_clip(&mut self, media_id: Uuid, start_time: f64, duration: f64, row: usize) -> Uuid {
+        let id = Uuid::new_v4();
+        // A still is a freeze of frame 0 — the freeze path already decodes one
+        // frame, repeats it for `duration`, and stays silent.
+        let is_image = self
+            .library
+            .iter()
+            .any(|l| l.id == media_id && l.clip_type == ClipType::Image);
+        self.timeline.push(TimelineClip {
+            id,
+            media_id,
+            start_time,
+            duration,
+            track_row: row,
+            source_offset: 0.0,
+            volume: 1.0,
+            linked_clip_id: None,
+            audio_muted: is_image,
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+            filter: crate::filters::FilterParams::none(),
+            tag_color: None,
+            note: String::new(),
+            freeze_at: is_image.then_some(0.0),
+            poster_offset: None,
+            group_id: None,
+            enabled: true,
+            stabilize: None,
+            volume_keys: Vec::new(),
+            slate: None,
+        });
+        id
+    }
+
+    /// Extracts the audio from a video timeline clip onto the A track directly
     /// below it. Creates a linked audio clip, mutes the original video clip's
     /// audio, and returns the new audio clip's UUID.
     /// Returns None if the clip doesn't exist or isn't a video clip.
@@ -422,6 +1638,9 @@ impl ProjectState {
         let audio_row = (clip.track_row + 1).min(3);
 
         let audio_id = Uuid::new_v4();
+        // The pair joins the video's group (or starts one) so it moves and
+        // trims as a unit.
+        let group = clip.group_id.unwrap_or_else(Uuid::new_v4);
         let audio_clip = TimelineClip {
             id: audio_id,
             media_id: clip.media_id,
@@ -437,16 +1656,454 @@ impl ProjectState {
             fade_out_secs: clip.fade_out_secs,
             fade_out_end_secs: clip.fade_out_end_secs,
             filter: crate::filters::FilterParams::none(),
+            tag_color: clip.tag_color,
+            note: clip.note.clone(),
+            freeze_at: None,
+            poster_offset: None,
+            group_id: Some(group),
+            enabled: clip.enabled,
+            stabilize: None,
+            volume_keys: Vec::new(),
+            slate: None,
         };
 
         // Mute audio on the video clip and link it to the new audio clip.
         self.timeline[clip_idx].audio_muted = true;
         self.timeline[clip_idx].linked_clip_id = Some(audio_id);
+        self.timeline[clip_idx].group_id = Some(group);
 
         self.timeline.push(audio_clip);
         Some(audio_id)
     }
 
+    // ── Clip groups ──────────────────────────────────────────────────────────
+
+    /// Ids of every clip in `id`'s group, `id` first. An ungrouped clip is a
+    /// group of one.
+    pub fn group_of(&self, id: Uuid) -> Vec<Uuid> {
+        let group = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id)
+            .and_then(|c| c.group_id);
+        let mut ids = vec![id];
+        if let Some(g) = group {
+            ids.extend(
+                self.timeline
+                    .iter()
+                    .filter(|c| c.group_id == Some(g) && c.id != id)
+                    .map(|c| c.id),
+            );
+        }
+        ids
+    }
+
+    /// Put every clip in `ids` — and everything already grouped with any of
+    /// them — into one group.
+    pub fn link_clips(&mut self, ids: &[Uuid]) {
+        let merged: Vec<Uuid> = self
+            .timeline
+            .iter()
+            .filter(|c| ids.contains(&c.id))
+            .filter_map(|c| c.group_id)
+            .collect();
+        let group = merged.first().copied().unwrap_or_else(Uuid::new_v4);
+        for c in &mut self.timeline {
+            if ids.contains(&c.id) || c.group_id.is_some_and(|g| merged.contains(&g)) {
+                c.group_id = Some(group);
+            }
+        }
+    }
+
+    /// Enable or disable clip `id` together with the rest of its group, so a
+    /// video and its extracted audio drop out as one.
+    pub fn set_group_enabled(&mut self, id: Uuid, enabled: bool) {
+        let members = self.group_of(id);
+        for c in self.timeline.iter_mut().filter(|c| members.contains(&c.id)) {
+            c.enabled = enabled;
+        }
+    }
+
+    /// Take `id` out of its group. A group left with one member dissolves.
+    pub fn unlink_clip(&mut self, id: Uuid) {
+        let Some(group) = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id)
+            .and_then(|c| c.group_id)
+        else {
+            return;
+        };
+        for c in &mut self.timeline {
+            if c.id == id {
+                c.group_id = None;
+            }
+        }
+        let rest: Vec<usize> = (0..self.timeline.len())
+            .filter(|&i| self.timeline[i].group_id == Some(group))
+            .collect();
+        if let [last] = rest[..] {
+            self.timeline[last].group_id = None;
+        }
+    }
+
+    /// Move clip `id` to `new_start` on `new_row`; the rest of its group
+    /// shifts by the same time delta and keeps its rows. The delta is limited
+    /// so no member starts before 0.
+    pub fn move_group(&mut self, id: Uuid, new_start: f64, new_row: usize) {
+        let Some(old_start) = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.start_time)
+        else {
+            return;
+        };
+        let members = self.group_of(id);
+        let earliest = self
+            .timeline
+            .iter()
+            .filter(|c| members.contains(&c.id))
+            .map(|c| c.start_time)
+            .fold(f64::INFINITY, f64::min);
+        let delta = (new_start - old_start).max(-earliest);
+        let Some(delta) = self.clear_move_delta(&members, id, new_row, delta) else {
+            return;
+        };
+        for c in self.timeline.iter_mut().filter(|c| members.contains(&c.id)) {
+            c.start_time += delta;
+            if c.id == id {
+                c.track_row = new_row;
+            }
+        }
+    }
+
+    /// True when moving clip `id` (and its group) to `new_start` on its own
+    /// row fits exactly — nothing starts before 0 and no clip is in the way.
+    /// Moves that would be nudged to fit are refused by callers that need
+    /// an exact position, such as audio sync.
+    pub fn move_is_clear(&self, id: Uuid, new_start: f64) -> bool {
+        let Some(clip) = self.timeline.iter().find(|c| c.id == id) else {
+            return false;
+        };
+        let delta = new_start - clip.start_time;
+        self.clear_move_delta(&self.group_of(id), id, clip.track_row, delta)
+            .is_some_and(|d| (d - delta).abs() < OVERLAP_EPS)
+    }
+
+    /// Timeline start that puts clip `id` in sync with `reference`, given
+    /// `offset` = reference source time minus clip source time for the same
+    /// moment (as measured by audio sync).
+    pub fn synced_start(&self, id: Uuid, reference: Uuid, offset: f64) -> Option<f64> {
+        let clip = self.timeline.iter().find(|c| c.id == id)?;
+        let r = self.timeline.iter().find(|c| c.id == reference)?;
+        Some(r.start_time - r.source_offset + clip.source_offset + offset)
+    }
+
+    /// The move delta closest to `delta` that puts no member of `members`
+    /// on top of another clip on its track: `delta` itself when that is
+    /// clear, otherwise one that butts a member against the clip in the way.
+    /// `id` lands on `new_row`, the other members keep theirs. Overlaps that
+    /// exist before the move are ignored so older projects stay editable.
+    /// `None` when nothing fits.
+    fn clear_move_delta(
+        &self,
+        members: &[Uuid],
+        id: Uuid,
+        new_row: usize,
+        delta: f64,
+    ) -> Option<f64> {
+        let row_of = |c: &TimelineClip| if c.id == id { new_row } else { c.track_row };
+        let (moving, still): (Vec<&TimelineClip>, Vec<&TimelineClip>) =
+            self.timeline.iter().partition(|c| members.contains(&c.id));
+        let clashes = |d: f64| {
+            moving.iter().any(|m| {
+                let start = m.start_time + d;
+                start < -OVERLAP_EPS
+                    || still.iter().any(|o| {
+                        o.track_row == row_of(m)
+                            && spans_overlap(m, start, o)
+                            && !(o.track_row == m.track_row && spans_overlap(m, m.start_time, o))
+                    })
+            })
+        };
+        let mut candidates = vec![delta];
+        for m in &moving {
+            for o in still.iter().filter(|o| o.track_row == row_of(m)) {
+                candidates.push(o.start_time - m.duration - m.start_time);
+                candidates.push(o.start_time + o.duration - m.start_time);
+            }
+        }
+        candidates
+            .into_iter()
+            .filter(|&d| !clashes(d))
+            .min_by(|a, b| (a - delta).abs().total_cmp(&(b - delta).abs()))
+    }
+
+    /// Free time between the end of `clip` and the next clip on its track
+    /// that is not in `members` (infinite when nothing follows). Trims that
+    /// lengthen a clip stop there instead of running over the neighbor.
+    fn room_after(&self, clip: &TimelineClip, members: &[Uuid]) -> f64 {
+        let end = clip.start_time + clip.duration;
+        self.timeline
+            .iter()
+            .filter(|o| {
+                o.track_row == clip.track_row
+                    && !members.contains(&o.id)
+                    && o.start_time >= end - OVERLAP_EPS
+            })
+            .map(|o| o.start_time - end)
+            .fold(f64::INFINITY, f64::min)
+            .max(0.0)
+    }
+
+    /// Smallest `room_after` over every member of `members`.
+    fn group_room_after(&self, members: &[Uuid]) -> f64 {
+        self.timeline
+            .iter()
+            .filter(|c| members.contains(&c.id))
+            .map(|c| self.room_after(c, members))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Clips that overlap another clip on the same track. Export plays a
+    /// track's clips one after another and can't composite them, so the
+    /// timeline flags these and the export panel warns before rendering.
+    pub fn overlapping_clips(&self) -> HashSet<Uuid> {
+        let mut out = HashSet::new();
+        for (i, a) in self.timeline.iter().enumerate() {
+            for b in &self.timeline[i + 1..] {
+                if a.track_row == b.track_row && spans_overlap(a, a.start_time, b) {
+                    out.insert(a.id);
+                    out.insert(b.id);
+                }
+            }
+        }
+        out
+    }
+
+    /// Apply a head trim of clip `id` to its whole group: every member's
+    /// in-point moves by the same source delta. Each member is clamped on its
+    /// own (source offset ≥ 0, at least two frames left). Lengthening stops
+    /// where the first member would run into the next clip on its track.
+    pub fn trim_group_start(
+        &mut self,
+        id: Uuid,
+        mut new_source_offset: f64,
+        mut new_duration: f64,
+    ) {
+        let Some((old_offset, old_duration)) = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| (c.source_offset, c.duration))
+        else {
+            return;
+        };
+        let members = self.group_of(id);
+        let excess = new_duration - old_duration - self.group_room_after(&members);
+        if excess > 0.0 {
+            new_duration -= excess;
+            new_source_offset += excess;
+        }
+        let delta = new_source_offset - old_offset;
+        let min = self.min_clip_secs();
+        for c in self.timeline.iter_mut().filter(|c| members.contains(&c.id)) {
+            if c.id == id {
+                c.source_offset = new_source_offset;
+                c.duration = new_duration;
+            } else {
+                let offset = (c.source_offset + delta).max(0.0);
+                c.duration = (c.duration - (offset - c.source_offset)).max(min);
+                c.source_offset = offset;
+            }
+        }
+    }
+
+    /// Apply a tail trim of clip `id` to its whole group: every member's
+    /// duration changes by the same amount, limited by its own source length
+    /// and by the next clip on its track.
+    pub fn trim_group_end(&mut self, id: Uuid, new_duration: f64) {
+        let Some(old_duration) = self
+            .timeline
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.duration)
+        else {
+            return;
+        };
+        let members = self.group_of(id);
+        let delta = (new_duration - old_duration).min(self.group_room_after(&members));
+        let new_duration = old_duration + delta;
+        let min = self.min_clip_secs();
+        for mid in members {
+            let Some(idx) = self.timeline.iter().position(|c| c.id == mid) else {
+                continue;
+            };
+            let max = self.max_clip_duration(&self.timeline[idx]);
+            let c = &mut self.timeline[idx];
+            c.duration = if mid == id {
+                new_duration
+            } else {
+                let d = (c.duration + delta).max(min);
+                max.map_or(d, |m| d.min(m.max(min)))
+            };
+        }
+    }
+
+    /// Split every member of `id`'s group that spans timeline time `t`, so a
+    /// linked pair is cut together. The tails form a group of their own.
+    /// Returns the tail ids.
+    pub fn split_group_at(&mut self, id: Uuid, t: f64) -> Vec<Uuid> {
+        let tails: Vec<Uuid> = self
+            .group_of(id)
+            .into_iter()
+            .filter_map(|mid| self.split_clip_at(mid, t))
+            .collect();
+        if tails.len() > 1 {
+            self.link_clips(&tails);
+        }
+        tails
+    }
+
+    /// True when enabled video-row clips cover `start..end` without a gap —
+    /// export joins video rows end to end, so a gap would shorten the render.
+    pub fn video_covers(&self, start: f64, end: f64) -> bool {
+        let mut spans: Vec<(f64, f64)> = self
+            .timeline
+            .iter()
+            .filter(|c| c.enabled && c.track_row % 2 == 0)
+            .map(|c| (c.start_time, c.start_time + c.duration))
+            .filter(|&(s, e)| e > start && s < end)
+            .collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut reached = start;
+        for (s, e) in spans {
+            if s > reached + OVERLAP_EPS {
+                return false;
+            }
+            reached = reached.max(e);
+        }
+        reached >= end - OVERLAP_EPS
+    }
+
+    /// Replace everything in `start..end`, on every row, with one clip of
+    /// `media_id` (the range rendered to a file) on row 0. Clips crossing
+    /// either edge are split there first. What was removed is recorded in
+    /// `nests` for `unnest`. Returns the new clip's id.
+    pub fn nest_range(&mut self, start: f64, end: f64, media_id: Uuid) -> Uuid {
+        for t in [start, end] {
+            while let Some(id) = self
+                .timeline
+                .iter()
+                .find(|c| {
+                    t > c.start_time + OVERLAP_EPS && t < c.start_time + c.duration - OVERLAP_EPS
+                })
+                .map(|c| c.id)
+            {
+                self.split_group_at(id, t);
+            }
+        }
+
+        let (clips, kept): (Vec<TimelineClip>, Vec<TimelineClip>) =
+            std::mem::take(&mut self.timeline)
+                .into_iter()
+                .partition(|c| {
+                    c.start_time >= start - OVERLAP_EPS
+                        && c.start_time + c.duration <= end + OVERLAP_EPS
+                });
+        self.timeline = kept;
+        let id = self.place_clip(media_id, start, end - start, 0);
+
+        // Transitions inside the range are baked into the render. One leading
+        // out of it moves over to the nested clip.
+        let removed: HashSet<Uuid> = clips.iter().map(|c| c.id).collect();
+        let exit = clips
+            .iter()
+            .find(|c| c.track_row % 2 == 0 && (c.start_time + c.duration - end).abs() < OVERLAP_EPS)
+            .map(|c| c.id);
+        let (transitions, kept): (Vec<TimelineTransition>, Vec<TimelineTransition>) =
+            std::mem::take(&mut self.transitions)
+                .into_iter()
+                .partition(|tr| removed.contains(&tr.after_clip_id));
+        self.transitions = kept;
+        if let Some(tr) = transitions.iter().find(|tr| Some(tr.after_clip_id) == exit) {
+            self.transitions.push(TimelineTransition {
+                after_clip_id: id,
+                ..tr.clone()
+            });
+        }
+
+        if self
+            .selected_timeline_clip
+            .is_some_and(|s| removed.contains(&s))
+        {
+            self.selected_timeline_clip = Some(id);
+        }
+        self.nests.push(Nest {
+            clip_id: id,
+            start,
+            end,
+            clips,
+            transitions,
+        });
+        id
+    }
+
+    /// Whether nested clip `id` can be expanded: it must still be untrimmed,
+    /// and the clips it holds must not land on anything placed since.
+    pub fn can_unnest(&self, id: Uuid) -> bool {
+        let Some(clip) = self.timeline.iter().find(|c| c.id == id) else {
+            return false;
+        };
+        let Some(nest) = self.nests.iter().find(|n| n.clip_id == id) else {
+            return false;
+        };
+        if clip.source_offset > OVERLAP_EPS
+            || (clip.duration - (nest.end - nest.start)).abs() > OVERLAP_EPS
+        {
+            return false;
+        }
+        let shift = clip.start_time - nest.start;
+        !nest.clips.iter().any(|n| {
+            let (s, e) = (n.start_time + shift, n.start_time + n.duration + shift);
+            self.timeline.iter().any(|o| {
+                o.id != id
+                    && o.track_row == n.track_row
+                    && o.start_time < e - OVERLAP_EPS
+                    && s < o.start_time + o.duration - OVERLAP_EPS
+            })
+        })
+    }
+
+    /// Put the clips nested clip `id` was rendered from back in its place,
+    /// following it if it was moved. Returns false when `can_unnest` fails.
+    pub fn unnest(&mut self, id: Uuid) -> bool {
+        if !self.can_unnest(id) {
+            return false;
+        }
+        let Some(i) = self.nests.iter().position(|n| n.clip_id == id) else {
+            return false;
+        };
+        let nest = self.nests.remove(i);
+        let Some(clip) = self.timeline.iter().find(|c| c.id == id) else {
+            return false;
+        };
+        let shift = clip.start_time - nest.start;
+
+        self.timeline.retain(|c| c.id != id);
+        self.transitions.retain(|tr| tr.after_clip_id != id);
+        self.transitions.extend(nest.transitions);
+        self.timeline.extend(nest.clips.into_iter().map(|mut c| {
+            c.start_time += shift;
+            c
+        }));
+        if self.selected_timeline_clip == Some(id) {
+            self.selected_timeline_clip = None;
+        }
+        true
+    }
+
     pub fn delete_selected(&mut self) {
         if let Some(id) = self.selected_timeline_clip.take() {
             self.timeline.retain(|c| c.id != id);
@@ -480,3 +2137,130 @@ impl ProjectState {
         crate::helpers::geometry::aspect_ratio_value(self.aspect_ratio)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// State holding one 30 s video source, and that source's id.
+    fn state_with_source() -> (ProjectState, Uuid) {
+        let mut state = ProjectState::default();
+        let lib = Uuid::new_v4();
+        state
+            .library
+            .push(LibraryClip::probed_video(lib, "/footage/a.mp4", 30.0));
+        (state, lib)
+    }
+
+    fn add_clip(state: &mut ProjectState, media_id: Uuid, start_time: f64, duration: f64) -> Uuid {
+        let id = Uuid::new_v4();
+        state.timeline.push(TimelineClip {
+            id,
+            media_id,
+            start_time,
+            duration,
+            ..Default::default()
+        });
+        id
+    }
+
+    fn clip(state: &ProjectState, id: Uuid) -> &TimelineClip {
+        state.timeline.iter().find(|c| c.id == id).unwrap()
+    }
+
+    // ── conform_to_fps ────────────────────────────────────────────────────────
+
+    #[test]
+    fn conform_rounds_edits_onto_the_new_frame_grid() {
+        let (mut state, lib) = state_with_source();
+        // Butted together at 30 fps frame 31 (1.0333 s), which isn't a 24 fps frame.
+        let a = add_clip(&mut state, lib, 0.0, 31.0 / 30.0);
+        let b = add_clip(&mut state, lib, 31.0 / 30.0, 2.0);
+        state.markers.push(Marker {
+            id: Uuid::new_v4(),
+            time: 0.52,
+        });
+
+        state.conform_to_fps(24);
+        assert_eq!(state.project_fps, 24);
+        let frame = 1.0 / 24.0;
+        let on_grid = |t: f64| ((t / frame) - (t / frame).round()).abs() < 1e-9;
+        let (ca, cb) = (clip(&state, a), clip(&state, b));
+        assert!(on_grid(cb.start_time) && on_grid(cb.start_time + cb.duration));
+        // Still butted together.
+        assert!((ca.start_time + ca.duration - cb.start_time).abs() < 1e-9);
+        assert!(on_grid(state.markers[0].time));
+        // Two-frame minimum follows the project rate.
+        assert!((state.min_clip_secs() - 2.0 / 24.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn conform_keeps_adjacent_sub_frame_clips_apart() {
+        let (mut state, lib) = state_with_source();
+        state.project_fps = 60;
+        // Two-frame clips at 60 fps are shorter than two frames at 24 fps.
+        let a = add_clip(&mut state, lib, 0.0, 2.0 / 60.0);
+        let b = add_clip(&mut state, lib, 2.0 / 60.0, 2.0 / 60.0);
+        let c = add_clip(&mut state, lib, 4.0 / 60.0, 1.0);
+
+        state.conform_to_fps(24);
+        assert!(state.overlapping_clips().is_empty());
+        let min = state.min_clip_secs();
+        for id in [a, b, c] {
+            assert!(clip(&state, id).duration >= min - 1e-9);
+        }
+        // Still butted together, and `c` keeps its end.
+        let (ca, cb, cc) = (clip(&state, a), clip(&state, b), clip(&state, c));
+        assert!((ca.start_time + ca.duration - cb.start_time).abs() < 1e-9);
+        assert!((cb.start_time + cb.duration - cc.start_time).abs() < 1e-9);
+        assert!((cc.start_time + cc.duration - 26.0 / 24.0).abs() < 1e-9);
+    }
+
+    // ── insert_slate ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn slate_splits_the_clip_under_it_and_ripples_v1() {
+        let (mut state, lib) = state_with_source();
+        let a = add_clip(&mut state, lib, 0.0, 4.0);
+        let b = add_clip(&mut state, lib, 4.0, 2.0);
+
+        let slate = state.insert_slate(1.0, 2.0, [255, 0, 0]);
+        let s = clip(&state, slate);
+        assert_eq!((s.start_time, s.duration, s.track_row), (1.0, 2.0, 0));
+        assert_eq!(s.slate, Some([255, 0, 0]));
+        assert!(s.audio_muted && state.library.iter().all(|l| l.id != s.media_id));
+
+        // Head keeps 1 s, the 3 s tail lands after the slate, `b` follows.
+        assert_eq!(clip(&state, a).duration, 1.0);
+        let tail = state
+            .timeline
+            .iter()
+            .find(|c| c.media_id == lib && c.start_time == 3.0)
+            .unwrap();
+        assert_eq!((tail.duration, tail.source_offset), (3.0, 1.0));
+        assert_eq!(clip(&state, b).start_time, 6.0);
+        assert!(state.overlapping_clips().is_empty());
+
+        // In a gap nothing is split; later clips still ripple.
+        state.insert_slate(10.0, 1.0, [0, 0, 0]);
+        assert_eq!(state.timeline.len(), 5);
+        assert_eq!(clip(&state, b).start_time, 6.0);
+    }
+
+    // ── synced_start ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn synced_start_lines_up_source_times() {
+        let (mut state, lib) = state_with_source();
+        let rec = add_clip(&mut state, lib, 3.0, 20.0);
+        let cam = add_clip(&mut state, lib, 5.0, 20.0);
+        state.timeline[0].source_offset = 2.0;
+        state.timeline[1].source_offset = 1.5;
+
+        // Source 0 of the camera sits at 3.5 s; the recorder's source time
+        // runs 0.25 s behind it, and its first visible frame is 2 s in.
+        let start = state.synced_start(rec, cam, 0.25).unwrap();
+        assert!((start - 5.75).abs() < 1e-9, "start {start}");
+        assert!(state.synced_start(rec, Uuid::new_v4(), 0.25).is_none());
+    }
+}
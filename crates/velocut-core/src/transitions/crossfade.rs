@@ -1,22 +1,24 @@
 // crates/velocut-core/src/transitions/crossfade.rs
 //
-// Linear dissolve between two clips.
+// Dissolve between two clips.
 //
 // The blend runs in gamma-encoded byte space — a correct approximation for
 // SDR content. See `helpers::blend_byte` for the rationale and limitations.
 //
-// The smooth-step easing curve (`ease_in_out`) is applied to the raw linear
-// alpha before blending. This gives a more perceptually even dissolve than a
-// raw linear ramp, which tends to feel "muddy" at the midpoint.
+// The shape of the dissolve comes from the transition's `FadeCurve`. The
+// default S-curve applies smooth-step easing (`ease_in_out`) to the raw
+// linear alpha, which is more perceptually even than a raw linear ramp.
+// Equal-power and logarithmic gains don't sum to one, so those blend each
+// plane around its own black / neutral level (`blend_byte_gains`).
 //
 // To make a wipe or other transition, duplicate this file as `wipe.rs` and
 // replace the `apply()` body. Everything else (registration, call sites) is
 // handled by the registry in `mod.rs`.
 
-use crate::transitions::{TransitionKind, TransitionType, VideoTransition};
-use crate::transitions::helpers::{blend_byte, ease_in_out};
+use crate::transitions::{FadeCurve, TransitionKind, TransitionType, VideoTransition};
+use crate::transitions::helpers::{blend_byte, blend_byte_gains, y_len};
 
-/// Linear dissolve with smooth-step easing.
+/// Dissolve shaped by the transition's `FadeCurve`.
 pub struct Crossfade;
 
 impl VideoTransition for Crossfade {
@@ -40,21 +42,43 @@ impl VideoTransition for Crossfade {
         TransitionType::new(TransitionKind::Crossfade, duration_secs)
     }
 
-    /// Blend two packed YUV420P buffers at `alpha` with smooth-step easing.
-    ///
-    /// The easing is applied here (not at the call site) so that every consumer
-    /// — encode and preview alike — gets the same perceptual curve without
-    /// coordinating separately.
-    ///
-    /// If you want a *linear* dissolve without easing, replace `ease_in_out(alpha)`
-    /// with `alpha` directly. The test below covers both the eased and raw cases.
+    /// Blend two packed YUV420P buffers at `alpha`. The shape comes from
+    /// `FadeCurve`; this entry point uses the default `FadeCurve::SCurve`.
     fn apply(
         &self,
         frame_a: &[u8],
         frame_b: &[u8],
-        _width:  u32,
-        _height: u32,
+        width:   u32,
+        height:  u32,
+        alpha:   f32,
+    ) -> Vec<u8> {
+        self.apply_curve(frame_a, frame_b, width, height, alpha, FadeCurve::SCurve)
+    }
+
+    fn apply_rgba(
+        &self,
+        frame_a: &[u8],
+        frame_b: &[u8],
+        width:   u32,
+        height:  u32,
+        alpha:   f32,
+    ) -> Vec<u8> {
+        self.apply_rgba_curve(frame_a, frame_b, width, height, alpha, FadeCurve::SCurve)
+    }
+
+    /// Blend two packed YUV420P buffers at `alpha` along `curve`.
+    ///
+    /// The curve is applied here (not at the call site) so that every consumer
+    /// — encode and preview alike — gets the same shape without coordinating
+    /// separately.
+    fn apply_curve(
+        &self,
+        frame_a: &[u8],
+        frame_b: &[u8],
+        width:   u32,
+        height:  u32,
         alpha:   f32,
+        curve:   FadeCurve,
     ) -> Vec<u8> {
         use rayon::prelude::*;
 
@@ -66,12 +90,25 @@ impl VideoTransition for Crossfade {
             frame_b.len(),
         );
 
-        let eased = ease_in_out(alpha);
+        let (gain_a, gain_b) = curve.gains(alpha);
         let mut out = vec![0u8; frame_a.len()];
+        if curve.is_unity_gain() {
+            out.par_iter_mut()
+                .zip(frame_a.par_iter())
+                .zip(frame_b.par_iter())
+                .for_each(|((o, &a), &b)| *o = blend_byte(a, b, gain_b));
+            return out;
+        }
+        // Luma pivots on video black (16), chroma on neutral (128).
+        let split = y_len(width, height).min(out.len());
         out.par_iter_mut()
             .zip(frame_a.par_iter())
             .zip(frame_b.par_iter())
-            .for_each(|((o, &a), &b)| *o = blend_byte(a, b, eased));
+            .enumerate()
+            .for_each(|(i, ((o, &a), &b))| {
+                let pivot = if i < split { 16.0 } else { 128.0 };
+                *o = blend_byte_gains(a, b, gain_a, gain_b, pivot);
+            });
         out
     }
 
@@ -80,13 +117,14 @@ impl VideoTransition for Crossfade {
     /// transfer path; converting to YUV and back purely to blend bytes is
     /// wasted work.  Every byte is independent so rayon chunks across pixels
     /// with zero coordination overhead.
-    fn apply_rgba(
+    fn apply_rgba_curve(
         &self,
         frame_a: &[u8],
         frame_b: &[u8],
         _width:  u32,
         _height: u32,
         alpha:   f32,
+        curve:   FadeCurve,
     ) -> Vec<u8> {
         use rayon::prelude::*;
 
@@ -94,17 +132,24 @@ impl VideoTransition for Crossfade {
             "Crossfade::apply_rgba — frame size mismatch: {} vs {}",
             frame_a.len(), frame_b.len());
 
-        let eased = ease_in_out(alpha);
+        let (gain_a, gain_b) = curve.gains(alpha);
+        let unity = curve.is_unity_gain();
         let mut out = vec![0u8; frame_a.len()];
         // 4-byte chunks keep whole pixels together per thread.
         out.par_chunks_mut(4)
             .zip(frame_a.par_chunks(4))
             .zip(frame_b.par_chunks(4))
             .for_each(|((o, a), b)| {
-                o[0] = blend_byte(a[0], b[0], eased);
-                o[1] = blend_byte(a[1], b[1], eased);
-                o[2] = blend_byte(a[2], b[2], eased);
-                o[3] = blend_byte(a[3], b[3], eased);
+                let mix = |x, y| if unity {
+                    blend_byte(x, y, gain_b)
+                } else {
+                    blend_byte_gains(x, y, gain_a, gain_b, 0.0)
+                };
+                o[0] = mix(a[0], b[0]);
+                o[1] = mix(a[1], b[1]);
+                o[2] = mix(a[2], b[2]);
+                // Alpha never runs hot — it's coverage, not light.
+                o[3] = blend_byte(a[3], b[3], gain_b);
             });
         out
     }
@@ -146,6 +191,21 @@ mod tests {
         assert!(result.iter().all(|&v| v == 100));
     }
 
+    #[test]
+    fn equal_power_midpoint_does_not_dip() {
+        let cf = Crossfade;
+        // 4×2 frame: 8 luma bytes, then 2 + 2 chroma bytes.
+        let mut a = make_frame(100, 12);
+        let mut b = make_frame(100, 12);
+        a[8..].fill(128);
+        b[8..].fill(128);
+        let linear = cf.apply_curve(&a, &b, 4, 2, 0.5, FadeCurve::Linear);
+        let power = cf.apply_curve(&a, &b, 4, 2, 0.5, FadeCurve::EqualPower);
+        assert_eq!(linear[0], 100);
+        assert!(power[0] > 100);
+        assert!(power[8..].iter().all(|&v| v == 128));
+    }
+
     #[test]
     fn crossfade_output_length_matches_input() {
         let cf = Crossfade;
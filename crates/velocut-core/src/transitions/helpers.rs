@@ -161,6 +161,19 @@ pub fn blend_byte(a: u8, b: u8, alpha: f32) -> u8 {
     ((1.0 - alpha) * a as f32 + alpha * b as f32).round() as u8
 }
 
+/// Mix two byte values with independent gains, measured from `pivot`.
+///
+/// For curves whose gains don't sum to one (equal-power dissolves). The pivot
+/// is the value that should stay put when both sides sit on it — 16 for video
+/// luma, 128 for chroma, 0 for RGB — so boosting the mix brightens the
+/// picture without lifting black or tinting neutral colors.
+#[inline]
+pub fn blend_byte_gains(a: u8, b: u8, gain_a: f32, gain_b: f32, pivot: f32) -> u8 {
+    (pivot + (a as f32 - pivot) * gain_a + (b as f32 - pivot) * gain_b)
+        .round()
+        .clamp(0.0, 255.0) as u8
+}
+
 // ── Packed YUV420P plane layout ───────────────────────────────────────────────
 //
 // Packed buffers produced by `velocut_media::helpers::yuv::extract_yuv` have
@@ -539,6 +552,14 @@ mod tests {
         assert_eq!(blend_byte(100, 200, 0.5), 150);
     }
 
+    #[test]
+    fn blend_byte_gains_pivots_and_clamps() {
+        // Neutral chroma stays neutral however hot the mix runs.
+        assert_eq!(blend_byte_gains(128, 128, 0.707, 0.707, 128.0), 128);
+        assert_eq!(blend_byte_gains(16, 16, 0.707, 0.707, 16.0), 16);
+        assert_eq!(blend_byte_gains(200, 200, 0.707, 0.707, 0.0), 255);
+    }
+
     #[test]
     fn plane_layout_1080p() {
         let (w, h) = (1920_u32, 1080_u32);
@@ -100,11 +100,15 @@ declare_transitions! {
 /// `declare_transitions!` changes.
 ///
 /// Written to the project file. `kind` identifies the algorithm; `duration_secs`
-/// is the overlap length in seconds (0.0 for Cut, ignored).
+/// is the overlap length in seconds (0.0 for Cut, ignored). `curve` shapes the
+/// dissolve and is ignored by every other kind; projects saved before it
+/// existed load with the default, which is the dissolve they always had.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TransitionType {
     pub kind: TransitionKind,
     pub duration_secs: f32,
+    #[serde(default)]
+    pub curve: FadeCurve,
 }
 
 impl Default for TransitionType {
@@ -112,6 +116,7 @@ impl Default for TransitionType {
         TransitionType {
             kind: TransitionKind::Cut,
             duration_secs: 0.0,
+            curve: FadeCurve::default(),
         }
     }
 }
@@ -122,6 +127,7 @@ impl TransitionType {
         TransitionType {
             kind,
             duration_secs,
+            curve: FadeCurve::default(),
         }
     }
 
@@ -130,8 +136,94 @@ impl TransitionType {
         TransitionType {
             kind: TransitionKind::Cut,
             duration_secs: 0.0,
+            curve: FadeCurve::default(),
         }
     }
+
+    /// Same transition with a different dissolve curve.
+    pub fn with_curve(mut self, curve: FadeCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+}
+
+/// How a dissolve hands over from the outgoing clip to the incoming one.
+///
+/// Each curve maps the linear progress `alpha` to a pair of gains — one for
+/// the outgoing side, one for the incoming — used for both the picture and
+/// the audio mix. Only `Crossfade` reads it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FadeCurve {
+    /// Smooth-step picture, linear audio — the original dissolve.
+    #[default]
+    SCurve,
+    /// Straight ramp on both. Gains always sum to one, which reads as a
+    /// slight dip in brightness and loudness at the midpoint.
+    Linear,
+    /// Sine / cosine gains that keep combined power constant — no mid-dissolve
+    /// darkening or volume dip.
+    EqualPower,
+    /// Each side ramps on a log curve: the incoming clip arrives quickly and
+    /// the outgoing one lingers, so the middle runs hot.
+    Logarithmic,
+}
+
+impl FadeCurve {
+    /// All curves in picker order.
+    pub fn all() -> &'static [FadeCurve] {
+        &[
+            FadeCurve::SCurve,
+            FadeCurve::Linear,
+            FadeCurve::EqualPower,
+            FadeCurve::Logarithmic,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FadeCurve::SCurve => "S-curve",
+            FadeCurve::Linear => "Linear",
+            FadeCurve::EqualPower => "Equal power",
+            FadeCurve::Logarithmic => "Logarithmic",
+        }
+    }
+
+    /// `(outgoing, incoming)` picture gains at linear progress `alpha`.
+    pub fn gains(self, alpha: f32) -> (f32, f32) {
+        let t = helpers::clamp01(alpha);
+        match self {
+            FadeCurve::SCurve => {
+                let e = helpers::ease_in_out(t);
+                (1.0 - e, e)
+            }
+            FadeCurve::Linear => (1.0 - t, t),
+            FadeCurve::EqualPower => {
+                let theta = t * std::f32::consts::FRAC_PI_2;
+                (theta.cos(), theta.sin())
+            }
+            FadeCurve::Logarithmic => (log_ramp(1.0 - t), log_ramp(t)),
+        }
+    }
+
+    /// `(outgoing, incoming)` audio gains. Same as `gains` except the default
+    /// S-curve, whose audio has always been a straight ramp.
+    pub fn audio_gains(self, alpha: f32) -> (f32, f32) {
+        match self {
+            FadeCurve::SCurve => FadeCurve::Linear.gains(alpha),
+            curve => curve.gains(alpha),
+        }
+    }
+
+    /// True when the two gains always sum to one, so the blend is a plain
+    /// weighted average and can never push a pixel out of range.
+    pub fn is_unity_gain(self) -> bool {
+        matches!(self, FadeCurve::SCurve | FadeCurve::Linear)
+    }
+}
+
+/// 0 → 0, 1 → 1, rising fast early: log10(1 + 9t).
+fn log_ramp(t: f32) -> f32 {
+    (1.0 + 9.0 * t).log10()
 }
 
 /// Stored in `ProjectState`, serialized with the project.
@@ -234,6 +326,33 @@ pub trait VideoTransition: Send + Sync {
         height: u32,
         alpha: f32,
     ) -> Vec<u8>;
+
+    /// `apply` with the transition's `FadeCurve`. Only transitions whose blend
+    /// follows a curve override this; the default ignores it.
+    fn apply_curve(
+        &self,
+        frame_a: &[u8],
+        frame_b: &[u8],
+        width: u32,
+        height: u32,
+        alpha: f32,
+        _curve: FadeCurve,
+    ) -> Vec<u8> {
+        self.apply(frame_a, frame_b, width, height, alpha)
+    }
+
+    /// `apply_rgba` with the transition's `FadeCurve`. See `apply_curve`.
+    fn apply_rgba_curve(
+        &self,
+        frame_a: &[u8],
+        frame_b: &[u8],
+        width: u32,
+        height: u32,
+        alpha: f32,
+        _curve: FadeCurve,
+    ) -> Vec<u8> {
+        self.apply_rgba(frame_a, frame_b, width, height, alpha)
+    }
 }
 
 // ── Registry ──────────────────────────────────────────────────────────────────
@@ -257,3 +376,34 @@ pub fn registry() -> &'static HashMap<TransitionKind, Box<dyn VideoTransition>>
     static REGISTRY: OnceLock<HashMap<TransitionKind, Box<dyn VideoTransition>>> = OnceLock::new();
     REGISTRY.get_or_init(|| make_entries().into_iter().map(|t| (t.kind(), t)).collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curves_start_on_a_and_end_on_b() {
+        for &curve in FadeCurve::all() {
+            let (a0, b0) = curve.gains(0.0);
+            let (a1, b1) = curve.gains(1.0);
+            assert!((a0 - 1.0).abs() < 1e-6 && b0.abs() < 1e-6, "{curve:?}");
+            assert!(a1.abs() < 1e-6 && (b1 - 1.0).abs() < 1e-6, "{curve:?}");
+        }
+    }
+
+    #[test]
+    fn equal_power_holds_power_through_the_middle() {
+        let (a, b) = FadeCurve::EqualPower.gains(0.5);
+        assert!((a * a + b * b - 1.0).abs() < 1e-6);
+        let (a, b) = FadeCurve::Linear.gains(0.5);
+        assert!((a + b - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn default_curve_is_the_original_dissolve() {
+        let t = TransitionType::new(TransitionKind::Crossfade, 1.5);
+        assert_eq!(t.curve, FadeCurve::SCurve);
+        // The default S-curve still mixes audio on a straight ramp.
+        assert_eq!(FadeCurve::SCurve.audio_gains(0.25), (0.75, 0.25));
+    }
+}
@@ -0,0 +1,412 @@
+// crates/velocut-core/src/watermark.rs
+//
+// Project-level watermark / logo overlay. The settings live on `ProjectState`
+// and are changed only through `EditorCommand::SetWatermark`. The PNG itself
+// is decoded by velocut-media; placement and the YUV420P compositor live here
+// so the encoder and the preview put the logo on exactly the same pixels.
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+/// Allowed logo width as a fraction of the output frame width.
+pub const WATERMARK_SCALE_RANGE: RangeInclusive<f32> = 0.02..=0.5;
+/// Gap between the logo and the frame edge, as a fraction of the shorter
+/// frame side.
+const MARGIN_FRAC: f32 = 0.03;
+
+/// Frame corner the watermark is anchored to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatermarkCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl WatermarkCorner {
+    pub fn label(self) -> &'static str {
+        match self {
+            WatermarkCorner::TopLeft => "Top left",
+            WatermarkCorner::TopRight => "Top right",
+            WatermarkCorner::BottomLeft => "Bottom left",
+            WatermarkCorner::BottomRight => "Bottom right",
+        }
+    }
+
+    pub fn all() -> &'static [WatermarkCorner] {
+        &[
+            WatermarkCorner::TopLeft,
+            WatermarkCorner::TopRight,
+            WatermarkCorner::BottomLeft,
+            WatermarkCorner::BottomRight,
+        ]
+    }
+}
+
+/// Image burned into every exported frame.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Watermark {
+    /// PNG on disk. Re-read at export time, so edits to the file show up
+    /// without re-picking it.
+    pub path: PathBuf,
+    pub corner: WatermarkCorner,
+    /// Logo width as a fraction of the frame width. Height follows the
+    /// image's own aspect ratio.
+    pub scale: f32,
+    /// 0.0 = invisible, 1.0 = the PNG's own alpha.
+    pub opacity: f32,
+}
+
+impl Watermark {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            corner: WatermarkCorner::default(),
+            scale: 0.15,
+            opacity: 0.8,
+        }
+    }
+
+    /// Reject settings the Export panel can't produce.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.as_os_str().is_empty() {
+            return Err("Watermark image path is empty".into());
+        }
+        let is_png = self
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+        if !is_png {
+            return Err("Watermark must be a PNG image".into());
+        }
+        if !WATERMARK_SCALE_RANGE.contains(&self.scale) {
+            return Err(format!(
+                "Watermark scale must be between {:.0}% and {:.0}%",
+                WATERMARK_SCALE_RANGE.start() * 100.0,
+                WATERMARK_SCALE_RANGE.end() * 100.0
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err("Watermark opacity must be between 0 and 1".into());
+        }
+        Ok(())
+    }
+
+    /// Where the logo lands in a `frame_w`×`frame_h` frame, as
+    /// `[x, y, w, h]`. Works in any unit — output pixels for the encoder,
+    /// points for the preview canvas.
+    pub fn placement(&self, frame_w: f32, frame_h: f32, img_w: f32, img_h: f32) -> [f32; 4] {
+        let w = frame_w * self.scale;
+        let h = if img_w > 0.0 { w * img_h / img_w } else { 0.0 };
+        let margin = frame_w.min(frame_h) * MARGIN_FRAC;
+        let x = match self.corner {
+            WatermarkCorner::TopLeft | WatermarkCorner::BottomLeft => margin,
+            WatermarkCorner::TopRight | WatermarkCorner::BottomRight => frame_w - margin - w,
+        };
+        let y = match self.corner {
+            WatermarkCorner::TopLeft | WatermarkCorner::TopRight => margin,
+            WatermarkCorner::BottomLeft | WatermarkCorner::BottomRight => frame_h - margin - h,
+        };
+        [x, y, w, h]
+    }
+}
+
+/// A watermark resampled to its on-frame size and converted to YUV420P,
+/// ready to blend onto every encoded frame. Built once per export.
+pub struct WatermarkYuv {
+    /// Top-left corner in output pixels. Always even so the chroma planes
+    /// line up with luma.
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+    luma: Vec<u8>,
+    /// Per-luma-pixel alpha with opacity folded in.
+    luma_alpha: Vec<u8>,
+    cb: Vec<u8>,
+    cr: Vec<u8>,
+    /// Per-chroma-sample alpha (mean of the 2×2 luma block).
+    chroma_alpha: Vec<u8>,
+}
+
+impl WatermarkYuv {
+    /// Scale `rgba` (straight alpha, `img_w`×`img_h`) to its place in a
+    /// `frame_w`×`frame_h` frame. Returns `None` when the logo would be
+    /// empty or falls entirely off-frame.
+    pub fn prepare(
+        rgba: &[u8],
+        img_w: u32,
+        img_h: u32,
+        settings: &Watermark,
+        frame_w: u32,
+        frame_h: u32,
+    ) -> Option<Self> {
+        if img_w == 0 || img_h == 0 || rgba.len() < (img_w * img_h * 4) as usize {
+            return None;
+        }
+        let [fx, fy, fw, fh] =
+            settings.placement(frame_w as f32, frame_h as f32, img_w as f32, img_h as f32);
+        let x = (fx.max(0.0) as usize) & !1;
+        let y = (fy.max(0.0) as usize) & !1;
+        // Even size as well, then clip to the frame.
+        let w = ((fw.round() as usize) & !1).min((frame_w as usize).saturating_sub(x) & !1);
+        let h = ((fh.round() as usize) & !1).min((frame_h as usize).saturating_sub(y) & !1);
+        if w == 0 || h == 0 {
+            return None;
+        }
+
+        let scaled = resize_premultiplied(rgba, img_w as usize, img_h as usize, w, h);
+        let opacity = settings.opacity.clamp(0.0, 1.0);
+
+        let mut luma = vec![0u8; w * h];
+        let mut luma_alpha = vec![0u8; w * h];
+        // Premultiplied chroma per luma pixel; summed per 2×2 block below.
+        let mut cb_pm = vec![0.0f32; w * h];
+        let mut cr_pm = vec![0.0f32; w * h];
+        let mut alpha_f = vec![0.0f32; w * h];
+        for i in 0..w * h {
+            let [r, g, b, a] = scaled[i];
+            let a = a * opacity;
+            alpha_f[i] = a;
+            luma_alpha[i] = (a * 255.0).round() as u8;
+            if a <= 0.0 {
+                luma[i] = 16;
+                continue;
+            }
+            // `scaled` is premultiplied by the PNG alpha only; un-premultiply
+            // against that before converting.
+            let pa = scaled[i][3];
+            let (r, g, b) = (r / pa, g / pa, b / pa);
+            // BT.601 limited range, matching the encoder's output matrix.
+            let yv = 16.0 + 65.481 * r + 128.553 * g + 24.966 * b;
+            let u = 128.0 - 37.797 * r - 74.203 * g + 112.0 * b;
+            let v = 128.0 + 112.0 * r - 93.786 * g - 18.214 * b;
+            luma[i] = yv.round().clamp(16.0, 235.0) as u8;
+            cb_pm[i] = u * a;
+            cr_pm[i] = v * a;
+        }
+
+        let (cw, ch) = (w / 2, h / 2);
+        let mut cb = vec![128u8; cw * ch];
+        let mut cr = vec![128u8; cw * ch];
+        let mut chroma_alpha = vec![0u8; cw * ch];
+        for cy in 0..ch {
+            for cx in 0..cw {
+                let mut a_sum = 0.0;
+                let mut u_sum = 0.0;
+                let mut v_sum = 0.0;
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let i = (cy * 2 + dy) * w + cx * 2 + dx;
+                    a_sum += alpha_f[i];
+                    u_sum += cb_pm[i];
+                    v_sum += cr_pm[i];
+                }
+                let ci = cy * cw + cx;
+                chroma_alpha[ci] = (a_sum / 4.0 * 255.0).round() as u8;
+                if a_sum > 0.0 {
+                    cb[ci] = (u_sum / a_sum).round().clamp(16.0, 240.0) as u8;
+                    cr[ci] = (v_sum / a_sum).round().clamp(16.0, 240.0) as u8;
+                }
+            }
+        }
+
+        Some(Self {
+            x,
+            y,
+            w,
+            h,
+            luma,
+            luma_alpha,
+            cb,
+            cr,
+            chroma_alpha,
+        })
+    }
+
+    /// Blend onto one plane of a YUV420P frame. `plane` is 0 (Y), 1 (U) or
+    /// 2 (V); `data`/`stride` are that plane's buffer and line size.
+    pub fn composite_plane(&self, plane: usize, data: &mut [u8], stride: usize) {
+        let (ox, oy, w, h, src, alpha) = match plane {
+            0 => (self.x, self.y, self.w, self.h, &self.luma, &self.luma_alpha),
+            1 => (
+                self.x / 2,
+                self.y / 2,
+                self.w / 2,
+                self.h / 2,
+                &self.cb,
+                &self.chroma_alpha,
+            ),
+            _ => (
+                self.x / 2,
+                self.y / 2,
+                self.w / 2,
+                self.h / 2,
+                &self.cr,
+                &self.chroma_alpha,
+            ),
+        };
+        for row in 0..h {
+            let start = (oy + row) * stride + ox;
+            let Some(dst) = data.get_mut(start..start + w) else {
+                break;
+            };
+            let s = &src[row * w..(row + 1) * w];
+            let a = &alpha[row * w..(row + 1) * w];
+            for ((d, &s), &a) in dst.iter_mut().zip(s).zip(a) {
+                if a == 0 {
+                    continue;
+                }
+                let a = a as u32;
+                *d = ((s as u32 * a + *d as u32 * (255 - a) + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// Resample straight-alpha RGBA8 to `dw`×`dh`, returning premultiplied
+/// `[r, g, b, a]` in 0..=1. Bilinear, supersampled when shrinking so a large
+/// logo scaled down to a corner badge doesn't alias.
+fn resize_premultiplied(src: &[u8], sw: usize, sh: usize, dw: usize, dh: usize) -> Vec<[f32; 4]> {
+    let texel = |x: usize, y: usize| -> [f32; 4] {
+        let i = (y * sw + x) * 4;
+        let a = src[i + 3] as f32 / 255.0;
+        [
+            src[i] as f32 / 255.0 * a,
+            src[i + 1] as f32 / 255.0 * a,
+            src[i + 2] as f32 / 255.0 * a,
+            a,
+        ]
+    };
+    let bilinear = |fx: f32, fy: f32| -> [f32; 4] {
+        let fx = fx.clamp(0.0, (sw - 1) as f32);
+        let fy = fy.clamp(0.0, (sh - 1) as f32);
+        let (x0, y0) = (fx.floor() as usize, fy.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(sw - 1), (y0 + 1).min(sh - 1));
+        let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+        let (p00, p10, p01, p11) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+        let mut out = [0.0; 4];
+        for c in 0..4 {
+            let top = p00[c] + (p10[c] - p00[c]) * tx;
+            let bot = p01[c] + (p11[c] - p01[c]) * tx;
+            out[c] = top + (bot - top) * ty;
+        }
+        out
+    };
+
+    let sx = sw as f32 / dw as f32;
+    let sy = sh as f32 / dh as f32;
+    let nx = sx.ceil().max(1.0) as usize;
+    let ny = sy.ceil().max(1.0) as usize;
+    let weight = 1.0 / (nx * ny) as f32;
+
+    let mut out = Vec::with_capacity(dw * dh);
+    for dy in 0..dh {
+        for dx in 0..dw {
+            let mut acc = [0.0f32; 4];
+            for j in 0..ny {
+                for i in 0..nx {
+                    // Sample points spread evenly across the destination
+                    // pixel's footprint in source space.
+                    let fx = (dx as f32 + (i as f32 + 0.5) / nx as f32) * sx - 0.5;
+                    let fy = (dy as f32 + (j as f32 + 0.5) / ny as f32) * sy - 0.5;
+                    let p = bilinear(fx, fy);
+                    for c in 0..4 {
+                        acc[c] += p[c] * weight;
+                    }
+                }
+            }
+            out.push(acc);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(w: u32, h: u32, px: [u8; 4]) -> Vec<u8> {
+        px.iter()
+            .copied()
+            .cycle()
+            .take((w * h * 4) as usize)
+            .collect()
+    }
+
+    #[test]
+    fn placement_respects_corner_and_margin() {
+        let mut wm = Watermark::new("logo.png".into());
+        wm.scale = 0.1;
+        wm.corner = WatermarkCorner::BottomRight;
+        let [x, y, w, h] = wm.placement(1000.0, 500.0, 200.0, 100.0);
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(close(w, 100.0) && close(h, 50.0));
+        assert!(close(x + w, 1000.0 - 15.0));
+        assert!(close(y + h, 500.0 - 15.0));
+
+        wm.corner = WatermarkCorner::TopLeft;
+        let [x, y, _, _] = wm.placement(1000.0, 500.0, 200.0, 100.0);
+        assert!(close(x, 15.0) && close(y, 15.0));
+    }
+
+    #[test]
+    fn validate_rejects_non_png_and_bad_ranges() {
+        let mut wm = Watermark::new("logo.PNG".into());
+        assert_eq!(wm.validate(), Ok(()));
+        wm.path = "logo.jpg".into();
+        assert!(wm.validate().is_err());
+        wm.path = "logo.png".into();
+        wm.scale = 0.9;
+        assert!(wm.validate().is_err());
+        wm.scale = 0.15;
+        wm.opacity = 1.5;
+        assert!(wm.validate().is_err());
+    }
+
+    #[test]
+    fn opaque_white_logo_paints_white_and_leaves_surroundings() {
+        let mut wm = Watermark::new("logo.png".into());
+        wm.scale = 0.25;
+        wm.opacity = 1.0;
+        wm.corner = WatermarkCorner::TopLeft;
+        let rgba = solid(8, 8, [255, 255, 255, 255]);
+        let prep = WatermarkYuv::prepare(&rgba, 8, 8, &wm, 64, 64).unwrap();
+        assert_eq!((prep.w, prep.h), (16, 16));
+
+        let mut y = vec![16u8; 64 * 64];
+        prep.composite_plane(0, &mut y, 64);
+        assert_eq!(y[prep.y * 64 + prep.x], 235);
+        assert_eq!(y[(prep.y + 15) * 64 + prep.x + 15], 235);
+        assert_eq!(y[(prep.y + 16) * 64 + prep.x], 16);
+        assert_eq!(y[63 * 64 + 63], 16);
+
+        let mut u = vec![90u8; 32 * 32];
+        prep.composite_plane(1, &mut u, 32);
+        assert_eq!(u[(prep.y / 2) * 32 + prep.x / 2], 128);
+    }
+
+    #[test]
+    fn transparent_pixels_and_opacity_blend() {
+        let mut wm = Watermark::new("logo.png".into());
+        wm.scale = 0.5;
+        wm.opacity = 0.5;
+        let clear = solid(4, 4, [255, 0, 0, 0]);
+        let prep = WatermarkYuv::prepare(&clear, 4, 4, &wm, 32, 32).unwrap();
+        let mut y = vec![100u8; 32 * 32];
+        prep.composite_plane(0, &mut y, 32);
+        assert!(y.iter().all(|&v| v == 100));
+
+        let white = solid(4, 4, [255, 255, 255, 255]);
+        let prep = WatermarkYuv::prepare(&white, 4, 4, &wm, 32, 32).unwrap();
+        let mut y = vec![16u8; 32 * 32];
+        prep.composite_plane(0, &mut y, 32);
+        let v = y[(prep.y + 1) * 32 + prep.x + 1];
+        assert!(
+            (125..=126).contains(&v),
+            "half-opacity white over black: {v}"
+        );
+    }
+}
@@ -2,6 +2,9 @@
 //
 // Audio extraction (WAV for rodio playback) and temp file cleanup.
 //
+// WAVs go to the scratch folder — the OS temp dir unless the user picked
+// another one in Storage prefs (`set_scratch_dir`).
+//
 // Previously this module spawned an external `ffmpeg` CLI subprocess, which
 // silently failed when VeloCut was launched by double-clicking the .exe because
 // Windows does not inherit the MSYS2 PATH entries where ffmpeg.exe lives.
@@ -12,12 +15,12 @@
 
 use crossbeam_channel::Sender;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 use uuid::Uuid;
 
 use ffmpeg::format::input;
 use ffmpeg::format::sample::{Sample, Type as SampleType};
-use ffmpeg::media::Type as MediaType;
 use ffmpeg::software::resampling;
 use ffmpeg::util::channel_layout::ChannelLayout;
 use ffmpeg::util::frame::audio::Audio as AudioFrame;
@@ -25,6 +28,8 @@ use ffmpeg_the_third as ffmpeg;
 
 use velocut_core::media_types::MediaResult;
 
+use crate::probe::select_audio_stream;
+
 // ── Constants ─────────────────────────────────────────────────────────────────
 
 /// Output sample rate for extracted WAV files.  Matches the rodio sink rate and
@@ -38,27 +43,95 @@ const OUT_FMT: Sample = Sample::F32(SampleType::Packed);
 /// Output channel layout: stereo.
 const OUT_LAYOUT: ChannelLayout = ChannelLayout::STEREO;
 
+/// User-chosen scratch folder. `None` = OS temp dir.
+static SCRATCH_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
 // ── Public API ────────────────────────────────────────────────────────────────
 
+/// Redirect future WAV extractions to `dir`. Files already written stay
+/// where they are — playing sinks hold them open.
+pub(crate) fn set_scratch_dir(dir: Option<PathBuf>) {
+    *SCRATCH_DIR.write().unwrap() = dir;
+}
+
+/// Folder new WAVs are written to.
+pub fn scratch_dir() -> PathBuf {
+    SCRATCH_DIR
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+fn is_scratch_wav(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.starts_with("velocut_audio_") && name.ends_with(".wav")
+}
+
+/// VeloCut WAVs in `dir`: (file count, total bytes).
+pub fn scratch_usage(dir: &Path) -> (usize, u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .filter(|e| is_scratch_wav(&e.path()))
+        .filter_map(|e| e.metadata().ok())
+        .fold((0, 0), |(n, bytes), m| (n + 1, bytes + m.len()))
+}
+
+/// Delete VeloCut WAVs in `dir` except those in `keep` (the ones library
+/// clips still play from). Returns (files removed, bytes freed).
+pub fn clear_scratch(dir: &Path, keep: &[PathBuf]) -> (usize, u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    let mut removed = (0, 0);
+    for path in entries.flatten().map(|e| e.path()) {
+        if !is_scratch_wav(&path) || keep.contains(&path) {
+            continue;
+        }
+        let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                removed.0 += 1;
+                removed.1 += len;
+            }
+            Err(e) => crate::media_log!("[media] clear_scratch {}: {e}", path.display()),
+        }
+    }
+    removed
+}
+
 /// Decode audio from `path`, restricted to `[source_offset, source_offset + duration)`,
-/// resample to 44100 Hz stereo f32le, write a WAV temp file, and send the path
+/// resample to 44100 Hz stereo f32le, write a WAV to the scratch folder, and send the path
 /// back via `tx` as `MediaResult::AudioPath`.
 ///
 /// Pass `source_offset = 0.0` and `duration = f64::MAX` to decode the full file
 /// (used by the probe pipeline for clips that are not audio-overlay trimmed).
 ///
+/// `stream` selects a specific container audio stream (see
+/// `LibraryClip::audio_stream_index`); `None` uses FFmpeg's best stream. An
+/// explicit stream gets its own WAV name so switching streams never rewrites
+/// a file a playing sink still has open.
+///
 /// Soft-fails on any error (logs via eprintln, sends nothing on tx) so the UI
 /// degrades gracefully to silence rather than crashing.
 pub fn extract_audio(
     path: &PathBuf,
     id: Uuid,
+    stream: Option<usize>,
     source_offset: f64,
     duration: f64,
     tx: &Sender<MediaResult>,
 ) {
-    let wav_path = std::env::temp_dir().join(format!("velocut_audio_{id}.wav"));
+    let wav_name = match stream {
+        Some(s) => format!("velocut_audio_{id}_s{s}.wav"),
+        None => format!("velocut_audio_{id}.wav"),
+    };
+    let wav_path = scratch_dir().join(wav_name);
 
-    match decode_to_wav(path, &wav_path, source_offset, duration) {
+    match decode_to_wav(path, &wav_path, stream, source_offset, duration) {
         Ok(bytes) => {
             crate::media_log!(
                 "[media] audio WAV written ({bytes} bytes PCM) ← {}",
@@ -77,14 +150,14 @@ pub fn extract_audio(
 }
 
 /// Delete a temp WAV that was extracted for a clip.
-/// Only deletes files matching the `velocut_audio_<uuid>.wav` pattern in the OS temp dir.
-pub fn cleanup_audio_temp(path: &std::path::Path) {
+/// Only deletes files matching the `velocut_audio_<uuid>.wav` pattern in the
+/// scratch folder or the OS temp dir.
+pub fn cleanup_audio_temp(path: &Path) {
     let in_temp = path
         .parent()
-        .map(|p| p == std::env::temp_dir())
+        .map(|p| p == scratch_dir() || p == std::env::temp_dir())
         .unwrap_or(false);
-    let name = path.file_name().unwrap_or_default().to_string_lossy();
-    if in_temp && name.starts_with("velocut_audio_") && name.ends_with(".wav") {
+    if in_temp && is_scratch_wav(path) {
         if let Err(e) = std::fs::remove_file(path) {
             crate::media_log!("[media] cleanup_audio_temp: {e}");
         } else {
@@ -103,6 +176,7 @@ pub fn cleanup_audio_temp(path: &std::path::Path) {
 fn decode_to_wav(
     src: &PathBuf,
     dst: &PathBuf,
+    stream: Option<usize>,
     source_offset: f64,
     duration: f64,
 ) -> Result<u64, String> {
@@ -111,11 +185,8 @@ fn decode_to_wav(
     // ── Open input and find audio stream ─────────────────────────────────────
     let mut ictx = input(src).map_err(|e| format!("open: {e}"))?;
 
-    let audio_stream_idx = ictx
-        .streams()
-        .best(MediaType::Audio)
-        .ok_or_else(|| "no audio stream".to_string())?
-        .index();
+    let audio_stream_idx =
+        select_audio_stream(&ictx, stream).ok_or_else(|| "no audio stream".to_string())?;
 
     // ── Build decoder ─────────────────────────────────────────────────────────
     let stream = ictx.stream(audio_stream_idx).unwrap();
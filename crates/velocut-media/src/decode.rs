@@ -5,7 +5,8 @@
 
 use anyhow::Result;
 use crossbeam_channel::Sender;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
 use uuid::Uuid;
 
 use ffmpeg::ffi;
@@ -19,6 +20,22 @@ use velocut_core::filters::FilterParams;
 
 use velocut_core::media_types::MediaResult;
 
+// ── Scrub proxy width ─────────────────────────────────────────────────────────
+// Output width of scrub-mode (`aspect > 0`) decodes. Process-wide because the
+// scrub and transition-scrub threads open decoders without a handle back to
+// MediaWorker; written only by `MediaWorker::set_proxy_width`. A change takes
+// effect the next time a scrub decoder is opened (i.e. on the next clip).
+static SCRUB_PROXY_WIDTH: AtomicU32 = AtomicU32::new(320);
+
+pub(crate) fn set_scrub_proxy_width(w: u32) {
+    // Even and non-zero — swscale and yuv420p both need even dimensions.
+    SCRUB_PROXY_WIDTH.store(w.max(2) & !1, Ordering::Relaxed);
+}
+
+fn scrub_proxy_width() -> u32 {
+    SCRUB_PROXY_WIDTH.load(Ordering::Relaxed)
+}
+
 // ── Stateful per-clip decoder ─────────────────────────────────────────────────
 
 pub struct LiveDecoder {
@@ -353,7 +370,7 @@ impl LiveDecoder {
     pub fn open(
         path: &PathBuf,
         timestamp: f64,
-        aspect: f32, // >0 = scrub mode (320px wide); <=0 = playback/HQ mode (native res)
+        aspect: f32, // >0 = scrub mode (proxy width); <=0 = playback/HQ mode (native res)
         cached_scaler: Option<(SwsContext, Pixel, u32, u32)>,
         forced_size: Option<(u32, u32)>, // when Some, override aspect/native logic entirely
     ) -> Result<Self> {
@@ -434,13 +451,13 @@ impl LiveDecoder {
         //   forced_size     → exact (w, h) override; used by decoder_b so it always
         //                     matches the primary decoder's output — prevents blend
         //                     size mismatches when two clips have different native res.
-        //   aspect > 0      → scrub mode: fixed 320 px wide, native source AR.
+        //   aspect > 0      → scrub mode: proxy width (default 320 px), native source AR.
         //                     Low-res on purpose — shown only during active scrub (L1/L2).
         //   aspect <= 0     → playback / HQ mode: native source dimensions, no downscale.
         let (out_w, out_h) = if let Some((fw, fh)) = forced_size {
             (fw, fh)
         } else if aspect > 0.0 {
-            let w: u32 = 320;
+            let w: u32 = scrub_proxy_width();
             let h: u32 = ((w as f32 * raw_h as f32 / raw_w.max(1) as f32) as u32).max(2) & !1;
             (w, h)
         } else {
@@ -632,7 +649,12 @@ impl LiveDecoder {
                 }
                 if let Some(mut data) = data_opt {
                     if let Some(ref params) = self.filter {
-                        apply_filter_rgba(&mut data, params);
+                        apply_filter_rgba(
+                            &mut data,
+                            self.out_w as usize,
+                            self.out_h as usize,
+                            params,
+                        );
                     }
                     return Some((data, self.out_w, self.out_h, ts_secs));
                 }
@@ -731,7 +753,12 @@ impl LiveDecoder {
                 }
                 if let Some(mut data) = data_opt {
                     if let Some(ref params) = self.filter {
-                        apply_filter_rgba(&mut data, params);
+                        apply_filter_rgba(
+                            &mut data,
+                            self.out_w as usize,
+                            self.out_h as usize,
+                            params,
+                        );
                     }
                     return Some((data, self.out_w, self.out_h));
                 }
@@ -1226,7 +1253,7 @@ fn emit_frame(
 /// pixel data together with the output dimensions.
 ///
 /// `aspect` follows the same convention as `LiveDecoder::open`:
-/// - `aspect > 0`  → scrub mode: output width fixed at 320 px, height from source AR.
+/// - `aspect > 0`  → scrub mode: output width = scrub proxy width, height from source AR.
 /// - `aspect <= 0` → HQ / L3-idle mode: native source resolution, no downscale.
 ///
 /// Other invariants:
@@ -1236,7 +1263,7 @@ fn emit_frame(
 ///   `helpers::seek::seek_to_secs`).
 /// - Falls back to the last decoded frame on EOF (same as `decode_frame`).
 ///
-/// Used by `MediaWorker::request_transition_frame` (320 px scrub) and
+/// Used by `MediaWorker::request_transition_frame` (proxy-width scrub) and
 /// `MediaWorker::request_transition_frame_hq` (native res, L3 idle).
 pub fn decode_one_frame_rgba(path: &PathBuf, ts: f64, aspect: f32) -> Result<(Vec<u8>, u32, u32)> {
     let mut ictx = input(path)?;
@@ -1277,10 +1304,10 @@ pub fn decode_one_frame_rgba(path: &PathBuf, ts: f64, aspect: f32) -> Result<(Ve
     let mut decoder = dec_ctx.decoder().video()?;
     let raw_w = decoder.width().max(2);
     let raw_h = decoder.height().max(2);
-    // aspect > 0: 320-px scrub (fast, low-res).  aspect <= 0: native source
+    // aspect > 0: proxy-width scrub (fast, low-res).  aspect <= 0: native source
     // dimensions (HQ, used by L3-idle transition blend).
     let (out_w, out_h): (u32, u32) = if aspect > 0.0 {
-        let w: u32 = 320;
+        let w: u32 = scrub_proxy_width();
         let h: u32 = ((w as f32 * raw_h as f32 / raw_w.max(1) as f32) as u32).max(2) & !1;
         (w, h)
     } else {
@@ -1350,3 +1377,34 @@ pub fn decode_one_frame_rgba(path: &PathBuf, ts: f64, aspect: f32) -> Result<(Ve
     }
     Err(anyhow::anyhow!("no frame found at t={ts:.3}"))
 }
+
+/// Load a PNG from disk as straight-alpha RGBA8, returning `(rgba, w, h)`.
+///
+/// Palette, grayscale and 16-bit images are normalised to 8-bit colour;
+/// images without an alpha channel come back fully opaque. Used for the
+/// project watermark by both the encoder and the preview.
+pub fn load_png_rgba(path: &Path) -> Result<(Vec<u8>, u32, u32)> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![
+        0u8;
+        reader
+            .output_buffer_size()
+            .ok_or_else(|| anyhow::anyhow!("PNG too large"))?
+    ];
+    let info = reader.next_frame(&mut buf)?;
+    let px = &buf[..info.buffer_size()];
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => px.to_vec(),
+        png::ColorType::Rgb => px.chunks(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => px
+            .chunks(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => px.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        other => return Err(anyhow::anyhow!("unsupported PNG color type {other:?}")),
+    };
+    Ok((rgba, info.width, info.height))
+}
@@ -0,0 +1,155 @@
+// crates/velocut-media/src/duck.rs
+//
+// Auto-duck: find where a speech clip is talking and turn those regions into
+// volume keys that lower the music underneath. The scan runs on the worker
+// (same level windows as silence detection); turning regions into keys is
+// pure and runs on the UI thread when the user applies the duck.
+
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use uuid::Uuid;
+
+use velocut_core::media_types::MediaResult;
+use velocut_core::state::{Duck, VolumeKey};
+
+use crate::silence::scan_levels;
+
+/// Pauses shorter than this stay inside one speech region, so the music
+/// doesn't pump up between words.
+const HOLD_SECS: f64 = 0.35;
+
+/// Scan `path` between source times `from` and `to` for speech louder than
+/// `threshold_db` (dBFS) and send `MediaResult::SpeechDetected` keyed by
+/// `id` with the regions in source seconds. A failed decode reports no
+/// regions so the UI never waits forever.
+#[allow(clippy::too_many_arguments)]
+pub fn detect_speech(
+    path: &PathBuf,
+    id: Uuid,
+    audio_stream: Option<usize>,
+    from: f64,
+    to: f64,
+    threshold_db: f32,
+    tx: &Sender<MediaResult>,
+    shutdown: &AtomicBool,
+) {
+    let mut regions = SpeechRegions::new(threshold_db);
+    let scanned = scan_levels(
+        path,
+        audio_stream,
+        from,
+        to,
+        shutdown,
+        |start, end, level| regions.push(start, end, level),
+    );
+    let regions = match scanned {
+        Ok(()) => regions.spans,
+        Err(e) => {
+            crate::media_log!("[duck] {}: {e}", path.display());
+            Vec::new()
+        }
+    };
+    crate::media_log!(
+        "[duck] {} speech regions in {:.2}–{:.2}s ← {}",
+        regions.len(),
+        from,
+        to,
+        path.display()
+    );
+    let _ = tx.send(MediaResult::SpeechDetected { id, regions });
+}
+
+/// Loud windows merged into regions, bridging pauses under `HOLD_SECS`.
+struct SpeechRegions {
+    threshold: f32,
+    spans: Vec<(f64, f64)>,
+}
+
+impl SpeechRegions {
+    fn new(threshold_db: f32) -> Self {
+        Self {
+            threshold: 10f32.powf(threshold_db / 20.0),
+            spans: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, start: f64, end: f64, level: f32) {
+        if level < self.threshold {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last) if start - last.1 < HOLD_SECS => last.1 = end,
+            _ => self.spans.push((start, end)),
+        }
+    }
+}
+
+/// Volume keys that hold the music at `duck.duck_db` over every region in
+/// `regions` (music source seconds, sorted), ramping down over
+/// `duck.attack` before each and back to 0 dB over `duck.release` after.
+/// Regions whose ramps would overlap are merged so the music stays down.
+pub fn duck_keys(regions: &[(f64, f64)], duck: &Duck) -> Vec<VolumeKey> {
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for &(start, end) in regions {
+        match merged.last_mut() {
+            Some(last) if start - duck.attack <= last.1 + duck.release => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    let key = |source: f64, gain_db: f32| VolumeKey { source, gain_db };
+    merged
+        .into_iter()
+        .flat_map(|(start, end)| {
+            [
+                key(start - duck.attack, 0.0),
+                key(start, duck.duck_db),
+                key(end, duck.duck_db),
+                key(end + duck.release, 0.0),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use velocut_core::state::volume_key_gain;
+
+    fn duck() -> Duck {
+        Duck {
+            duck_db: -12.0,
+            threshold_db: -35.0,
+            attack: 0.25,
+            release: 0.5,
+        }
+    }
+
+    #[test]
+    fn short_pauses_stay_in_one_region() {
+        let mut r = SpeechRegions::new(-40.0);
+        r.push(1.00, 1.01, 0.5);
+        r.push(1.01, 1.20, 0.001); // −60 dBFS pause
+        r.push(1.20, 1.21, 0.5);
+        r.push(2.00, 2.01, 0.5);
+        assert_eq!(r.spans, vec![(1.00, 1.21), (2.00, 2.01)]);
+    }
+
+    #[test]
+    fn keys_ramp_around_each_region() {
+        let keys = duck_keys(&[(2.0, 3.0)], &duck());
+        let at: Vec<f64> = keys.iter().map(|k| k.source).collect();
+        assert_eq!(at, vec![1.75, 2.0, 3.0, 3.5]);
+        assert_eq!(volume_key_gain(&keys, 0.0), 1.0);
+        assert!((volume_key_gain(&keys, 2.5) - 10f32.powf(-0.6)).abs() < 1e-6);
+        assert_eq!(volume_key_gain(&keys, 4.0), 1.0);
+    }
+
+    #[test]
+    fn close_regions_are_merged() {
+        // 0.5 s release + 0.25 s attack covers the 0.4 s gap.
+        let keys = duck_keys(&[(1.0, 2.0), (2.4, 3.0)], &duck());
+        assert_eq!(keys.len(), 4);
+        assert!(volume_key_gain(&keys, 2.2) < 0.5);
+    }
+}
@@ -1,10 +1,11 @@
 // crates/velocut-media/src/encode/audio.rs
 //
-// Audio FIFO, encoder state, overlay decode, and fade envelope.
-// Extracted from encode/mod.rs.
+// Audio encoder selection, FIFO, encoder state, overlay decode, and fade
+// envelope. Extracted from encode/mod.rs.
 
+use ffmpeg::codec;
+use ffmpeg::encoder;
 use ffmpeg::format::sample::{Sample, Type as SampleType};
-use ffmpeg::media::Type as MediaType;
 use ffmpeg::software::resampling;
 use ffmpeg::util::channel_layout::{ChannelLayout, ChannelLayoutMask};
 use ffmpeg::util::frame::audio::Audio as AudioFrame;
@@ -12,20 +13,140 @@ use ffmpeg::util::rational::Rational;
 use ffmpeg::Packet;
 use ffmpeg_the_third as ffmpeg;
 
-use super::{AudioOverlay, AUDIO_RATE};
+use super::AudioOverlay;
+use crate::probe::select_audio_stream;
+use velocut_core::media_types::ExportFormat;
+use velocut_core::state::volume_key_gain;
+
+// ── Audio encoder selection ───────────────────────────────────────────────────
+
+/// One audio encoder a container can carry, looked up by FFmpeg encoder name.
+pub(super) struct AudioCandidate {
+    /// Name shown in the UI and the render log ("AAC", "MP3").
+    pub(super) label: &'static str,
+    /// FFmpeg encoder name passed to `find_by_name`.
+    pub(super) encoder: &'static str,
+    /// Input layout the encoder takes. Only FLTP and interleaved FLT are
+    /// supported — the FIFO is planar and `interleave_stereo` covers the rest.
+    pub(super) format: Sample,
+    /// Needs `strict=experimental` to open.
+    experimental: bool,
+}
+
+const PLANAR: Sample = Sample::F32(SampleType::Planar);
+const PACKED: Sample = Sample::F32(SampleType::Packed);
+
+/// Preferred first. Minimal and distro FFmpeg builds may lack the first
+/// choice; every later entry is one the container muxes just as well.
+///
+/// libopus only takes interleaved input; the native "opus" encoder takes
+/// FLTP but is still flagged experimental, so it is only a fallback.
+const MP4_FAMILY_AUDIO: &[AudioCandidate] = &[
+    AudioCandidate {
+        label: "AAC",
+        encoder: "aac",
+        format: PLANAR,
+        experimental: false,
+    },
+    AudioCandidate {
+        label: "MP3",
+        encoder: "libmp3lame",
+        format: PLANAR,
+        experimental: false,
+    },
+    AudioCandidate {
+        label: "AC-3",
+        encoder: "ac3",
+        format: PLANAR,
+        experimental: false,
+    },
+];
+const WEBM_AUDIO: &[AudioCandidate] = &[
+    AudioCandidate {
+        label: "Opus",
+        encoder: "libopus",
+        format: PACKED,
+        experimental: false,
+    },
+    AudioCandidate {
+        label: "Opus",
+        encoder: "opus",
+        format: PLANAR,
+        experimental: true,
+    },
+    AudioCandidate {
+        label: "Vorbis",
+        encoder: "libvorbis",
+        format: PLANAR,
+        experimental: false,
+    },
+];
+
+pub(super) fn audio_candidates(format: ExportFormat) -> &'static [AudioCandidate] {
+    match format {
+        ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => MP4_FAMILY_AUDIO,
+        ExportFormat::WebM => WEBM_AUDIO,
+    }
+}
+
+/// Label of the audio codec an export in `format` will use, or `None` when
+/// this FFmpeg build has no usable encoder and the export will be video-only.
+/// A lookup only — cheap enough for the export panel to call on demand.
+pub fn probe_audio_encoder(format: ExportFormat) -> Option<&'static str> {
+    audio_candidates(format)
+        .iter()
+        .find(|c| encoder::find_by_name(c.encoder).is_some())
+        .map(|c| c.label)
+}
+
+/// Open `cand` as a 128 kbps stereo encoder at `rate`. Errors when the build
+/// lacks it or it refuses the settings, so the caller can try the next one.
+pub(super) fn open_audio_encoder(
+    cand: &AudioCandidate,
+    rate: i32,
+    global_header: bool,
+) -> Result<encoder::Audio, String> {
+    let codec = encoder::find_by_name(cand.encoder)
+        .ok_or_else(|| format!("{} not in this FFmpeg build", cand.encoder))?;
+    let mut enc = codec::context::Context::new_with_codec(codec)
+        .encoder()
+        .audio()
+        .map_err(|e| format!("create {} encoder context: {e}", cand.encoder))?;
+
+    enc.set_rate(rate);
+    enc.set_ch_layout(ChannelLayout::STEREO);
+    enc.set_format(cand.format);
+    enc.set_bit_rate(128_000);
+    if global_header {
+        enc.set_flags(codec::flag::Flags::GLOBAL_HEADER);
+    }
+
+    let mut opts = ffmpeg::Dictionary::new();
+    if cand.experimental {
+        opts.set("strict", "experimental");
+    }
+    enc.open_as_with(codec, opts)
+        .map_err(|e| format!("open {} encoder: {e}", cand.encoder))
+}
 
 // ── Audio FIFO ────────────────────────────────────────────────────────────────
 
+/// Gain-applied samples waiting for the encoder. Values are NOT clamped on
+/// the way in: overlays are summed on top in `drain_fifo`, which is the single
+/// place the mix is measured (`AudioEncState::peak`) and clamped to [-1, 1].
 pub(super) struct AudioFifo {
     pub(super) left: Vec<f32>,
     pub(super) right: Vec<f32>,
+    /// Output sample rate stamped on popped frames (`EncodeSpec::audio_rate`).
+    pub(super) rate: u32,
 }
 
 impl AudioFifo {
-    pub(super) fn new() -> Self {
+    pub(super) fn new(rate: u32) -> Self {
         Self {
             left: Vec::new(),
             right: Vec::new(),
+            rate,
         }
     }
     pub(super) fn len(&self) -> usize {
@@ -41,8 +162,7 @@ impl AudioFifo {
         unsafe {
             let l_bytes = frame.data(0);
             let l_f32 = std::slice::from_raw_parts(l_bytes.as_ptr() as *const f32, n);
-            self.left
-                .extend(l_f32[skip..].iter().map(|s| (s * volume).clamp(-1.0, 1.0)));
+            self.left.extend(l_f32[skip..].iter().map(|s| s * volume));
 
             let r_bytes = if frame.ch_layout().channels() >= 2 {
                 frame.data(1)
@@ -50,8 +170,7 @@ impl AudioFifo {
                 frame.data(0)
             };
             let r_f32 = std::slice::from_raw_parts(r_bytes.as_ptr() as *const f32, n);
-            self.right
-                .extend(r_f32[skip..].iter().map(|s| (s * volume).clamp(-1.0, 1.0)));
+            self.right.extend(r_f32[skip..].iter().map(|s| s * volume));
         }
     }
 
@@ -63,8 +182,7 @@ impl AudioFifo {
         unsafe {
             let l_bytes = frame.data(0);
             let l_f32 = std::slice::from_raw_parts(l_bytes.as_ptr() as *const f32, n);
-            self.left
-                .extend(l_f32.iter().map(|s| (s * volume).clamp(-1.0, 1.0)));
+            self.left.extend(l_f32.iter().map(|s| s * volume));
 
             let r_bytes = if frame.ch_layout().channels() >= 2 {
                 frame.data(1)
@@ -72,8 +190,7 @@ impl AudioFifo {
                 frame.data(0)
             };
             let r_f32 = std::slice::from_raw_parts(r_bytes.as_ptr() as *const f32, n);
-            self.right
-                .extend(r_f32.iter().map(|s| (s * volume).clamp(-1.0, 1.0)));
+            self.right.extend(r_f32.iter().map(|s| s * volume));
         }
     }
 
@@ -85,7 +202,7 @@ impl AudioFifo {
             n,
             ChannelLayoutMask::STEREO,
         );
-        frame.set_rate(AUDIO_RATE as u32);
+        frame.set_rate(self.rate);
         frame.set_pts(Some(sample_idx));
 
         unsafe {
@@ -120,15 +237,24 @@ pub(super) struct DecodedOverlay {
 }
 
 pub(super) struct AudioEncState {
-    pub(super) encoder: ffmpeg::encoder::Audio,
+    /// `None` when no audio encoder could be opened: the mix still runs (so
+    /// the FIFO stays bounded and the peak is measured) but nothing is sent
+    /// and the output has no audio stream.
+    pub(super) encoder: Option<ffmpeg::encoder::Audio>,
     pub(super) out_sample_idx: i64,
     pub(super) frame_size: usize,
     pub(super) fifo: AudioFifo,
+    /// True when the encoder takes interleaved FLT (libopus) rather than FLTP.
+    /// Frames are mixed planar and repacked just before send_frame.
+    pub(super) packed: bool,
     pub(super) audio_tb: Rational,
     pub(super) ost_audio_tb: Rational,
     pub(super) overlays: Vec<DecodedOverlay>,
     /// Counts FIFO overrun events; used to throttle log spam at 1080p SW encode.
     pub(super) fifo_overrun_count: u64,
+    /// Largest absolute sample of the final mix before clamping. Above 1.0
+    /// the export clipped.
+    pub(super) peak: f32,
 }
 
 impl AudioEncState {
@@ -163,18 +289,37 @@ impl AudioEncState {
                             let ov_s = self.out_sample_idx + i as i64 - ov.start_sample;
                             if ov_s >= 0 && (ov_s as usize) < ov.sample_count {
                                 let idx = ov_s as usize;
-                                ldst[i] = (ldst[i] + ov.left[idx]).clamp(-1.0, 1.0);
-                                rdst[i] = (rdst[i] + ov.right[idx]).clamp(-1.0, 1.0);
+                                ldst[i] += ov.left[idx];
+                                rdst[i] += ov.right[idx];
                             }
                         }
                     }
                 }
             }
 
+            // Final mix is complete: record the pre-clamp peak, then clamp.
+            unsafe {
+                let n = self.frame_size;
+                let fptr = frame.as_mut_ptr();
+                for plane in 0..2 {
+                    let dst = std::slice::from_raw_parts_mut((*fptr).data[plane] as *mut f32, n);
+                    for s in dst.iter_mut() {
+                        self.peak = self.peak.max(s.abs());
+                        *s = s.clamp(-1.0, 1.0);
+                    }
+                }
+            }
+
             self.out_sample_idx += self.frame_size as i64;
 
-            self.encoder
-                .send_frame(&frame)
+            if self.packed {
+                frame = interleave_stereo(&frame);
+            }
+
+            let Some(enc) = self.encoder.as_mut() else {
+                continue;
+            };
+            enc.send_frame(&frame)
                 .map_err(|e| format!("send audio frame to encoder: {e}"))?;
             self.drain_packets(octx)?;
         }
@@ -185,10 +330,19 @@ impl AudioEncState {
         &mut self,
         octx: &mut ffmpeg::format::context::Output,
     ) -> Result<(), String> {
+        let Some(enc) = self.encoder.as_mut() else {
+            return Ok(());
+        };
         let mut pkt = Packet::empty();
-        while self.encoder.receive_packet(&mut pkt).is_ok() {
+        while enc.receive_packet(&mut pkt).is_ok() {
             pkt.set_stream(1);
             pkt.rescale_ts(self.audio_tb, self.ost_audio_tb);
+            crate::render_trace!(
+                "[pkt] a pts={:?} dts={:?} size={}",
+                pkt.pts(),
+                pkt.dts(),
+                pkt.size()
+            );
             pkt.write_interleaved(octx)
                 .map_err(|e| format!("write audio packet: {e}"))?;
         }
@@ -199,13 +353,41 @@ impl AudioEncState {
         &mut self,
         octx: &mut ffmpeg::format::context::Output,
     ) -> Result<(), String> {
-        self.encoder
-            .send_eof()
-            .map_err(|e| format!("send audio EOF: {e}"))?;
+        let Some(enc) = self.encoder.as_mut() else {
+            return Ok(());
+        };
+        enc.send_eof().map_err(|e| format!("send audio EOF: {e}"))?;
         self.drain_packets(octx)
     }
 }
 
+/// Repack a planar stereo FLTP frame into a single interleaved FLT plane.
+///
+/// libopus rejects planar input. Keeping the FIFO and overlay mixer planar and
+/// converting once per encoder frame here avoids a second code path through
+/// every push/mix helper.
+fn interleave_stereo(planar: &AudioFrame) -> AudioFrame {
+    let n = planar.samples();
+    let mut packed = AudioFrame::new(
+        Sample::F32(SampleType::Packed),
+        n,
+        ChannelLayoutMask::STEREO,
+    );
+    packed.set_rate(planar.rate());
+    packed.set_pts(planar.pts());
+    unsafe {
+        let l = std::slice::from_raw_parts(planar.data(0).as_ptr() as *const f32, n);
+        let r = std::slice::from_raw_parts(planar.data(1).as_ptr() as *const f32, n);
+        let dst =
+            std::slice::from_raw_parts_mut(packed.data_mut(0).as_mut_ptr() as *mut f32, n * 2);
+        for i in 0..n {
+            dst[2 * i] = l[i];
+            dst[2 * i + 1] = r[i];
+        }
+    }
+    packed
+}
+
 // ── Audio resampler flush ─────────────────────────────────────────────────────
 
 /// Flush any buffered samples from the SwrContext after decoder EOF.
@@ -230,7 +412,7 @@ pub(super) fn flush_audio_resampler(
             4096,
             ChannelLayoutMask::STEREO,
         );
-        out_frame.set_rate(AUDIO_RATE as u32);
+        out_frame.set_rate(fifo.rate);
 
         unsafe {
             let n_out = ffmpeg::ffi::swr_convert(
@@ -252,20 +434,21 @@ pub(super) fn flush_audio_resampler(
 
 // ── Overlay decode ────────────────────────────────────────────────────────────
 
-pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, String> {
+/// Decode an overlay's audio to planar stereo at `out_rate` (the job's
+/// `EncodeSpec::audio_rate`) with its gain and fade envelope baked in.
+pub(super) fn decode_overlay(
+    overlay: &AudioOverlay,
+    out_rate: u32,
+) -> Result<DecodedOverlay, String> {
     use ffmpeg::format::input as open_input;
 
     let target_fmt = Sample::F32(SampleType::Planar);
-    const OUT_RATE: u32 = 44_100;
 
     let mut ictx = open_input(&overlay.path)
         .map_err(|e| format!("overlay open '{}': {e}", overlay.path.display()))?;
 
-    let audio_idx = ictx
-        .streams()
-        .best(MediaType::Audio)
-        .ok_or_else(|| format!("no audio stream in overlay '{}'", overlay.path.display()))?
-        .index();
+    let audio_idx = select_audio_stream(&ictx, overlay.audio_stream)
+        .ok_or_else(|| format!("no audio stream in overlay '{}'", overlay.path.display()))?;
 
     let ast = ictx.stream(audio_idx).unwrap();
     let in_tb = ast.time_base();
@@ -302,8 +485,8 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                 frame.data(0)
             };
             let r = std::slice::from_raw_parts(r_plane.as_ptr() as *const f32, n);
-            left.extend(l.iter().map(|s| (s * vol).clamp(-1.0, 1.0)));
-            right.extend(r.iter().map(|s| (s * vol).clamp(-1.0, 1.0)));
+            left.extend(l.iter().map(|s| s * vol));
+            right.extend(r.iter().map(|s| s * vol));
         }
     };
 
@@ -335,7 +518,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
 
             let src_channels = raw.ch_layout().channels();
             let needs_resample =
-                raw.format() != target_fmt || raw.rate() != OUT_RATE || src_channels != 2;
+                raw.format() != target_fmt || raw.rate() != out_rate || src_channels != 2;
 
             if needs_resample {
                 let rs = resampler.get_or_insert_with(|| {
@@ -350,7 +533,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                         raw.rate(),
                         target_fmt,
                         ChannelLayout::STEREO,
-                        OUT_RATE,
+                        out_rate,
                     )
                     .expect("overlay resampler")
                 });
@@ -364,7 +547,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                         overlay.fade_in_start_secs,
                         overlay.fade_out_secs,
                         overlay.fade_out_end_secs,
-                    );
+                    ) * volume_key_gain(&overlay.volume_keys, pts_secs);
                     push_frame(&resampled, &mut left, &mut right, overlay.volume * fg);
                 }
             } else {
@@ -376,7 +559,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                     overlay.fade_in_start_secs,
                     overlay.fade_out_secs,
                     overlay.fade_out_end_secs,
-                );
+                ) * volume_key_gain(&overlay.volume_keys, pts_secs);
                 push_frame(&raw, &mut left, &mut right, overlay.volume * fg);
             }
         }
@@ -395,7 +578,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
 
         let src_channels = raw.ch_layout().channels();
         let needs_resample =
-            raw.format() != target_fmt || raw.rate() != OUT_RATE || src_channels != 2;
+            raw.format() != target_fmt || raw.rate() != out_rate || src_channels != 2;
 
         if needs_resample {
             if let Some(rs) = &mut resampler {
@@ -409,7 +592,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                         overlay.fade_in_start_secs,
                         overlay.fade_out_secs,
                         overlay.fade_out_end_secs,
-                    );
+                    ) * volume_key_gain(&overlay.volume_keys, pts_secs);
                     push_frame(&resampled, &mut left, &mut right, overlay.volume * fg);
                 }
             }
@@ -422,7 +605,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                 overlay.fade_in_start_secs,
                 overlay.fade_out_secs,
                 overlay.fade_out_end_secs,
-            );
+            ) * volume_key_gain(&overlay.volume_keys, pts_secs);
             push_frame(&raw, &mut left, &mut right, overlay.volume * fg);
         }
     }
@@ -435,7 +618,7 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
                 4096,
                 ChannelLayoutMask::STEREO,
             );
-            tmp.set_rate(OUT_RATE);
+            tmp.set_rate(out_rate);
             unsafe {
                 let n_out = ffmpeg::ffi::swr_convert(
                     rs.as_mut_ptr(),
@@ -461,12 +644,12 @@ pub(super) fn decode_overlay(overlay: &AudioOverlay) -> Result<DecodedOverlay, S
     }
 
     let sample_count = left.len();
-    let start_sample = (overlay.timeline_start * OUT_RATE as f64).round() as i64;
+    let start_sample = (overlay.timeline_start * out_rate as f64).round() as i64;
 
     crate::media_log!(
         "[encode] overlay decoded: {} samples ({:.2}s) start_sample={} ← {}",
         sample_count,
-        sample_count as f64 / OUT_RATE as f64,
+        sample_count as f64 / out_rate as f64,
         start_sample,
         overlay.path.display(),
     );
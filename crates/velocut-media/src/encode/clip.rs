@@ -25,27 +25,74 @@ use ffmpeg::Packet;
 use ffmpeg_the_third as ffmpeg;
 
 use crate::helpers::seek::seek_to_secs;
-use crate::helpers::yuv::{extract_yuv, write_yuv};
+use crate::helpers::yuv::{
+    apply_levels_to_frame, apply_spatial_to_frame, composite_watermark, deinterlace_frame,
+    dither_frame_16_to_8, extract_yuv, is_high_bit_depth, remap_range, solid_yuv, write_yuv,
+    RangeRemap,
+};
+use crate::probe::select_audio_stream;
+use crate::stabilize::{cached_camera_path, Stabilizer};
 use velocut_core::filters::helpers::apply_filter_yuv;
 use velocut_core::filters::FilterParams;
-use velocut_core::media_types::MediaResult;
-use velocut_core::transitions::VideoTransition;
+use velocut_core::media_types::{DitherMode, MediaResult};
+use velocut_core::state::volume_key_gain;
+use velocut_core::transitions::{FadeCurve, VideoTransition};
+use velocut_core::watermark::WatermarkYuv;
 
 use super::audio::{fade_gain, flush_audio_resampler, AudioEncState};
 use super::hw::{upload_frame_to_hw, HwBackend};
-use super::{ClipSpec, EncodeSpec, AUDIO_RATE, PROGRESS_INTERVAL};
+use super::{ClipSpec, EncodeSpec, PROGRESS_INTERVAL};
 
 // ── Center-crop scaler ────────────────────────────────────────────────────────
 
+/// Pass two of stabilization for `clip`: smooth the camera path measured by
+/// `analyze_stabilized_clips`. `None` when the clip isn't stabilized or its
+/// analysis failed.
+fn clip_stabilizer(clip: &ClipSpec) -> Option<Stabilizer> {
+    let s = clip.stabilize?;
+    let path = cached_camera_path(&clip.path, s.from, s.to)?;
+    Some(Stabilizer::new(&path, s.strength))
+}
+
 pub(super) struct CropScaler {
     ctx: ScaleCtx,
     crop_x: u32,
     crop_y: u32,
     crop_h: u32,
+    /// Range fix-up applied after sws_scale so every clip leaves the scaler
+    /// in limited range, whatever the source used.
+    range_remap: Option<RangeRemap>,
+    /// High-bit-depth sources scale into this YUV420P16LE frame first and
+    /// are then dithered down to 8 bits. `None` = swscale writes 8-bit
+    /// directly.
+    deep: Option<(VideoFrame, DitherMode)>,
+    /// Blend the two fields of each source frame before scaling. The blend
+    /// runs out-of-place into `fields` (allocated on first use at the source
+    /// size) because the decoder may still reference its output frame.
+    deinterlace: bool,
+    fields: Option<VideoFrame>,
+    /// Stabilized clips crop a window `margin` smaller on every edge and
+    /// move it per frame (`track`); `origin` is the window's centered
+    /// position, `max_origin` the furthest it may go, `src_size` the source
+    /// dimensions the offsets are relative to.
+    stabilizer: Option<Stabilizer>,
+    origin: (u32, u32),
+    max_origin: (u32, u32),
+    src_size: (u32, u32),
 }
 
 impl CropScaler {
-    pub(super) fn build(src_fmt: Pixel, src_w: u32, src_h: u32, out_w: u32, out_h: u32) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn build(
+        src_fmt: Pixel,
+        src_w: u32,
+        src_h: u32,
+        out_w: u32,
+        out_h: u32,
+        source_full: bool,
+        dither: DitherMode,
+        stabilizer: Option<Stabilizer>,
+    ) -> Self {
         let src_ar = src_w as f64 / src_h.max(1) as f64;
         let out_ar = out_w as f64 / out_h.max(1) as f64;
 
@@ -60,12 +107,33 @@ impl CropScaler {
             let cy = ((src_h - ch) / 2) & !1;
             (0u32, cy, src_w, ch)
         };
+        let (crop_x, crop_y, crop_w, crop_h) = match &stabilizer {
+            Some(stab) => {
+                let zoom = 1.0 - 2.0 * stab.margin();
+                let w = ((crop_w as f32 * zoom) as u32).max(2) & !1;
+                let h = ((crop_h as f32 * zoom) as u32).max(2) & !1;
+                (
+                    (crop_x + (crop_w - w) / 2) & !1,
+                    (crop_y + (crop_h - h) / 2) & !1,
+                    w,
+                    h,
+                )
+            }
+            None => (crop_x, crop_y, crop_w, crop_h),
+        };
 
+        let deep = (dither != DitherMode::Off && is_high_bit_depth(src_fmt))
+            .then(|| (VideoFrame::new(Pixel::YUV420P16LE, out_w, out_h), dither));
+        let dst_fmt = if deep.is_some() {
+            Pixel::YUV420P16LE
+        } else {
+            Pixel::YUV420P
+        };
         let ctx = ScaleCtx::get(
             src_fmt,
             crop_w.max(2),
             crop_h.max(2),
-            Pixel::YUV420P,
+            dst_fmt,
             out_w,
             out_h,
             ScaleFlags::BILINEAR,
@@ -77,13 +145,58 @@ impl CropScaler {
             crop_x,
             crop_y,
             crop_h,
+            range_remap: RangeRemap::after_swscale(src_fmt, source_full),
+            deep,
+            deinterlace: false,
+            fields: None,
+            stabilizer,
+            origin: (crop_x, crop_y),
+            max_origin: (
+                src_w.saturating_sub(crop_w.max(2)),
+                src_h.saturating_sub(crop_h.max(2)),
+            ),
+            src_size: (src_w, src_h),
         }
     }
 
+    /// Move the crop window to its stabilized position for the frame at
+    /// source time `t`. No-op for clips without stabilization.
+    pub(super) fn track(&mut self, t: f64) {
+        let Some(stab) = &self.stabilizer else {
+            return;
+        };
+        let [ox, oy] = stab.offset_at(t);
+        let place = |origin: u32, off: f32, size: u32, max: u32| {
+            let x = (origin as f32 + off * size as f32)
+                .round()
+                .clamp(0.0, max as f32);
+            (x as u32) & !1
+        };
+        self.crop_x = place(self.origin.0, ox, self.src_size.0, self.max_origin.0);
+        self.crop_y = place(self.origin.1, oy, self.src_size.1, self.max_origin.1);
+    }
+
+    pub(super) fn with_deinterlace(mut self, on: bool) -> Self {
+        self.deinterlace = on;
+        self
+    }
+
     pub(super) fn run(&mut self, src: &VideoFrame, dst: &mut VideoFrame) -> Result<(), String> {
+        let src = if self.deinterlace {
+            let fields = self
+                .fields
+                .get_or_insert_with(|| VideoFrame::new(src.format(), src.width(), src.height()));
+            deinterlace_frame(src, fields);
+            &*fields
+        } else {
+            src
+        };
         unsafe {
             let sf = src.as_ptr();
-            let df = dst.as_mut_ptr();
+            let df = match &mut self.deep {
+                Some((wide, _)) => wide.as_mut_ptr(),
+                None => dst.as_mut_ptr(),
+            };
 
             let (off_y, off_uv): (usize, usize) = match src.format() {
                 Pixel::YUV420P | Pixel::YUVJ420P | Pixel::YUV422P | Pixel::YUVJ422P => {
@@ -129,6 +242,12 @@ impl CropScaler {
                 return Err(format!("CropScaler::run sws_scale returned {ret}"));
             }
         }
+        if let Some((wide, mode)) = &self.deep {
+            dither_frame_16_to_8(wide, dst, *mode);
+        }
+        if let Some(dir) = self.range_remap {
+            remap_range(dst, dir);
+        }
         Ok(())
     }
 }
@@ -173,6 +292,15 @@ pub(super) fn apply_filter_to_yuv_frame(
     if filter.is_identity() {
         return;
     }
+    if let Some(spatial) = filter.spatial {
+        apply_spatial_to_frame(yuv, w as usize, h as usize, &spatial);
+    }
+    if let Some(levels) = filter.levels {
+        apply_levels_to_frame(yuv, w as usize, h as usize, &levels);
+    }
+    if filter.is_color_identity() {
+        return;
+    }
     unsafe {
         let ptr = yuv.as_mut_ptr();
         let y_size = (w * h) as usize;
@@ -190,6 +318,7 @@ pub(super) fn apply_filter_to_yuv_frame(
 pub(super) fn encode_clip(
     clip: &ClipSpec,
     spec: &EncodeSpec,
+    watermark: Option<&WatermarkYuv>,
     octx: &mut ffmpeg::format::context::Output,
     video_encoder: &mut ffmpeg::encoder::Video,
     hw_frames_ctx: *mut ffmpeg::ffi::AVBufferRef,
@@ -201,7 +330,28 @@ pub(super) fn encode_clip(
     cancel: &Arc<AtomicBool>,
     tx: &Sender<MediaResult>,
     last_video_dts: &mut i64,
+    skipped_frames: &mut u64,
 ) -> Result<i64, String> {
+    if let Some(held) = held_frame(clip, spec) {
+        return encode_held_clip(
+            &held?,
+            clip,
+            spec,
+            watermark,
+            octx,
+            video_encoder,
+            hw_frames_ctx,
+            hw_backend,
+            audio_state,
+            out_frame_idx,
+            total_frames,
+            frame_tb,
+            cancel,
+            tx,
+            last_video_dts,
+        );
+    }
+
     let mut ictx =
         open_input(&clip.path).map_err(|e| format!("open '{}': {e}", clip.path.display()))?;
 
@@ -211,7 +361,7 @@ pub(super) fn encode_clip(
         .ok_or_else(|| format!("no video stream in '{}'", clip.path.display()))?
         .index();
 
-    let audio_stream_idx: Option<usize> = ictx.streams().best(MediaType::Audio).map(|s| s.index());
+    let audio_stream_idx: Option<usize> = select_audio_stream(&ictx, clip.audio_stream);
 
     let in_video_tb = ictx.stream(video_stream_idx).unwrap().time_base();
 
@@ -225,8 +375,9 @@ pub(super) fn encode_clip(
         .video()
         .map_err(|e| format!("open video decoder: {e}"))?;
 
+    let audio_rate = spec.audio_rate();
     let mut audio_decoder: Option<ffmpeg::decoder::audio::Audio> = None;
-    let mut in_audio_tb = Rational::new(1, AUDIO_RATE);
+    let mut in_audio_tb = Rational::new(1, audio_rate);
 
     if !clip.skip_audio {
         if let Some(asi) = audio_stream_idx {
@@ -284,6 +435,12 @@ pub(super) fn encode_clip(
     let clip_start_frame_idx = out_frame_idx;
     let mut video_clip_done = false;
     let mut audio_has_started = false;
+    // Error-resilient decode: the last frame sent to the encoder, and whether
+    // a corrupt frame has been dropped since. The next good frame first
+    // repeats `last_good` over the hole so later frames keep their timing.
+    let mut last_good: Option<VideoFrame> = None;
+    let mut lost_since_good = false;
+    let skipped_before = *skipped_frames;
 
     for result in ictx.packets() {
         let (stream, packet) =
@@ -297,9 +454,21 @@ pub(super) fn encode_clip(
 
         // ── Video packet ──────────────────────────────────────────────────────
         if sidx == video_stream_idx {
-            video_decoder
-                .send_packet(&packet)
-                .map_err(|e| format!("send video packet to decoder: {e}"))?;
+            if let Err(e) = video_decoder.send_packet(&packet) {
+                if !spec.error_resilient {
+                    return Err(format!("send video packet to decoder: {e}"));
+                }
+                if !video_clip_done {
+                    crate::media_log!(
+                        "[encode] '{}': skipping corrupt video packet (pts {:?}) — {e}",
+                        clip.path.display(),
+                        packet.pts()
+                    );
+                    *skipped_frames += 1;
+                    lost_since_good = true;
+                }
+                continue;
+            }
 
             if video_clip_done {
                 let mut _discard = VideoFrame::empty();
@@ -330,12 +499,31 @@ pub(super) fn encode_clip(
                         src_display_h,
                         spec.width,
                         spec.height,
+                        clip.full_range,
+                        spec.dither,
+                        clip_stabilizer(clip),
                     )
+                    .with_deinterlace(clip.deinterlace)
                 });
 
                 let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
-                sc.run(&decoded, &mut yuv)?;
+                sc.track(frame_pts_secs);
+                if let Err(e) = sc.run(&decoded, &mut yuv) {
+                    if !spec.error_resilient {
+                        return Err(e);
+                    }
+                    crate::media_log!(
+                        "[encode] '{}': skipping unscalable frame at {frame_pts_secs:.3}s — {e}",
+                        clip.path.display()
+                    );
+                    *skipped_frames += 1;
+                    lost_since_good = true;
+                    continue;
+                }
                 apply_filter_to_yuv_frame(&mut yuv, &clip.filter, spec.width, spec.height);
+                if let Some(wm) = watermark {
+                    composite_watermark(&mut yuv, wm);
+                }
 
                 unsafe {
                     (*yuv.as_mut_ptr()).sample_aspect_ratio =
@@ -346,35 +534,29 @@ pub(super) fn encode_clip(
                 let target_out_pts =
                     clip_start_frame_idx + (src_rel_secs * spec.fps as f64).round() as i64;
 
+                if lost_since_good {
+                    if let Some(held) = last_good.as_mut() {
+                        out_frame_idx = repeat_held_frame(
+                            held,
+                            target_out_pts,
+                            out_frame_idx,
+                            video_encoder,
+                            hw_frames_ctx,
+                            hw_backend,
+                            octx,
+                            frame_tb,
+                            last_video_dts,
+                        )?;
+                    }
+                    lost_since_good = false;
+                }
+
                 if target_out_pts >= out_frame_idx {
                     loop {
                         yuv.set_pts(Some(out_frame_idx));
 
                         send_video_frame(&yuv, video_encoder, hw_frames_ctx, hw_backend)?;
-
-                        let mut pkt = Packet::empty();
-                        while video_encoder.receive_packet(&mut pkt).is_ok() {
-                            pkt.set_stream(0);
-                            pkt.rescale_ts(frame_tb, ost_tb);
-                            let raw_dts = pkt.dts().unwrap_or(0);
-                            if *last_video_dts != i64::MIN {
-                                let prev_s = *last_video_dts as f64 * f64::from(ost_tb);
-                                let dts_s = raw_dts as f64 * f64::from(ost_tb);
-                                if dts_s < prev_s {
-                                    let clamped = *last_video_dts + 1;
-                                    crate::media_log!(
-                                        "[encode] non-monotonic DTS ({prev_s:.4}s → {dts_s:.4}s); \
-                                         clamping {raw_dts} → {clamped}"
-                                    );
-                                    unsafe {
-                                        (*pkt.as_mut_ptr()).dts = clamped;
-                                    }
-                                }
-                            }
-                            *last_video_dts = pkt.dts().unwrap_or(raw_dts);
-                            pkt.write_interleaved(octx)
-                                .map_err(|e| format!("write video packet: {e}"))?;
-                        }
+                        write_video_packets(video_encoder, octx, frame_tb, last_video_dts)?;
 
                         out_frame_idx += 1;
 
@@ -396,6 +578,9 @@ pub(super) fn encode_clip(
                     // HW: yield lets UI/audio preempt the decode+scale hot loop.
                     std::thread::yield_now();
                 }
+                if spec.error_resilient {
+                    last_good = Some(yuv);
+                }
             }
         }
 
@@ -422,12 +607,12 @@ pub(super) fn encode_clip(
 
                     audio_has_started = true;
 
-                    let pre_roll = ((clip.source_offset - pts_secs).max(0.0) * AUDIO_RATE as f64)
+                    let pre_roll = ((clip.source_offset - pts_secs).max(0.0) * audio_rate as f64)
                         .round() as usize;
 
                     let src_channels = raw.ch_layout().channels();
                     let needs_resample = raw.format() != Sample::F32(SampleType::Planar)
-                        || raw.rate() != AUDIO_RATE as u32
+                        || raw.rate() != audio_rate as u32
                         || src_channels != 2;
 
                     if needs_resample {
@@ -443,7 +628,7 @@ pub(super) fn encode_clip(
                                 raw.rate(),
                                 Sample::F32(SampleType::Planar),
                                 ChannelLayout::STEREO,
-                                AUDIO_RATE as u32,
+                                audio_rate as u32,
                             )
                             .expect("create audio resampler")
                         });
@@ -457,7 +642,7 @@ pub(super) fn encode_clip(
                                 clip.fade_in_start_secs,
                                 clip.fade_out_secs,
                                 clip.fade_out_end_secs,
-                            );
+                            ) * volume_key_gain(&clip.volume_keys, pts_secs);
                             audio_state.fifo.push_scaled_from(
                                 &resampled,
                                 clip.volume * fg,
@@ -473,7 +658,7 @@ pub(super) fn encode_clip(
                             clip.fade_in_start_secs,
                             clip.fade_out_secs,
                             clip.fade_out_end_secs,
-                        );
+                        ) * volume_key_gain(&clip.volume_keys, pts_secs);
                         audio_state
                             .fifo
                             .push_scaled_from(&raw, clip.volume * fg, pre_roll);
@@ -504,8 +689,12 @@ pub(super) fn encode_clip(
 
         if let Some(sc) = &mut video_scaler {
             let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+            sc.track(frame_pts_secs);
             if sc.run(&decoded, &mut yuv).is_ok() {
                 apply_filter_to_yuv_frame(&mut yuv, &clip.filter, spec.width, spec.height);
+                if let Some(wm) = watermark {
+                    composite_watermark(&mut yuv, wm);
+                }
                 unsafe {
                     (*yuv.as_mut_ptr()).sample_aspect_ratio =
                         ffmpeg::ffi::AVRational { num: 1, den: 1 };
@@ -534,6 +723,12 @@ pub(super) fn encode_clip(
                             }
                         }
                         *last_video_dts = pkt.dts().unwrap_or(raw_dts);
+                        crate::render_trace!(
+                            "[pkt] v pts={:?} dts={:?} size={}",
+                            pkt.pts(),
+                            pkt.dts(),
+                            pkt.size()
+                        );
                         pkt.write_interleaved(octx)
                             .map_err(|e| format!("write video packet (drain): {e}"))?;
                     }
@@ -549,10 +744,33 @@ pub(super) fn encode_clip(
 
                     std::thread::yield_now();
                 }
+                if spec.error_resilient {
+                    last_good = Some(yuv);
+                }
             }
         }
     }
 
+    // A corrupt stretch at the very end has no later good frame to close the
+    // hole, so hold the last good one out to the clip's length.
+    if *skipped_frames > skipped_before {
+        if let Some(held) = last_good.as_mut() {
+            let clip_end_idx =
+                clip_start_frame_idx + (clip.duration * spec.fps as f64).round() as i64;
+            out_frame_idx = repeat_held_frame(
+                held,
+                clip_end_idx,
+                out_frame_idx,
+                video_encoder,
+                hw_frames_ctx,
+                hw_backend,
+                octx,
+                frame_tb,
+                last_video_dts,
+            )?;
+        }
+    }
+
     if let Some(ref mut adec) = audio_decoder {
         let _ = adec.send_eof();
         let mut raw = AudioFrame::empty();
@@ -567,7 +785,7 @@ pub(super) fn encode_clip(
 
             let src_channels = raw.ch_layout().channels();
             let needs_resample = raw.format() != Sample::F32(SampleType::Planar)
-                || raw.rate() != AUDIO_RATE as u32
+                || raw.rate() != audio_rate as u32
                 || src_channels != 2;
 
             if needs_resample {
@@ -582,7 +800,7 @@ pub(super) fn encode_clip(
                             clip.fade_in_start_secs,
                             clip.fade_out_secs,
                             clip.fade_out_end_secs,
-                        );
+                        ) * volume_key_gain(&clip.volume_keys, pts_secs);
                         audio_state.fifo.push_scaled(&resampled, clip.volume * fg);
                     }
                 }
@@ -595,7 +813,7 @@ pub(super) fn encode_clip(
                     clip.fade_in_start_secs,
                     clip.fade_out_secs,
                     clip.fade_out_end_secs,
-                );
+                ) * volume_key_gain(&clip.volume_keys, pts_secs);
                 audio_state.fifo.push_scaled(&raw, clip.volume * fg);
             }
         }
@@ -614,7 +832,7 @@ pub(super) fn encode_clip(
     // sample count so overlay audio on the NEXT clip sees the right base level.
     if audio_has_started {
         let expected_samples =
-            ((clip.duration * AUDIO_RATE as f64).round() as usize).min(audio_state.fifo.len());
+            ((clip.duration * audio_rate as f64).round() as usize).min(audio_state.fifo.len());
         let excess = audio_state.fifo.len().saturating_sub(expected_samples);
         if excess > 0 {
             crate::media_log!(
@@ -634,12 +852,218 @@ pub(super) fn encode_clip(
     Ok(out_frame_idx)
 }
 
+/// Error-resilient decode: send `held` (the last good frame) as output frames
+/// `out_frame_idx..until`, covering frames dropped as corrupt. Returns the
+/// next output frame index.
+#[allow(clippy::too_many_arguments)]
+fn repeat_held_frame(
+    held: &mut VideoFrame,
+    until: i64,
+    mut out_frame_idx: i64,
+    video_encoder: &mut ffmpeg::encoder::Video,
+    hw_frames_ctx: *mut ffmpeg::ffi::AVBufferRef,
+    hw_backend: HwBackend,
+    octx: &mut ffmpeg::format::context::Output,
+    frame_tb: Rational,
+    last_video_dts: &mut i64,
+) -> Result<i64, String> {
+    while out_frame_idx < until {
+        held.set_pts(Some(out_frame_idx));
+        send_video_frame(held, video_encoder, hw_frames_ctx, hw_backend)?;
+        write_video_packets(video_encoder, octx, frame_tb, last_video_dts)?;
+        out_frame_idx += 1;
+    }
+    Ok(out_frame_idx)
+}
+
+/// Write every packet the video encoder has ready: rescale from `frame_tb`
+/// to the stream time base and clamp a DTS that would run backwards, which
+/// muxers reject.
+fn write_video_packets(
+    video_encoder: &mut ffmpeg::encoder::Video,
+    octx: &mut ffmpeg::format::context::Output,
+    frame_tb: Rational,
+    last_video_dts: &mut i64,
+) -> Result<(), String> {
+    let ost_tb = octx.stream(0).unwrap().time_base();
+    let mut pkt = Packet::empty();
+    while video_encoder.receive_packet(&mut pkt).is_ok() {
+        pkt.set_stream(0);
+        pkt.rescale_ts(frame_tb, ost_tb);
+        let raw_dts = pkt.dts().unwrap_or(0);
+        if *last_video_dts != i64::MIN {
+            let prev_s = *last_video_dts as f64 * f64::from(ost_tb);
+            let dts_s = raw_dts as f64 * f64::from(ost_tb);
+            if dts_s < prev_s {
+                let clamped = *last_video_dts + 1;
+                crate::media_log!(
+                    "[encode] non-monotonic DTS ({prev_s:.4}s → {dts_s:.4}s); \
+                     clamping {raw_dts} → {clamped}"
+                );
+                unsafe {
+                    (*pkt.as_mut_ptr()).dts = clamped;
+                }
+            }
+        }
+        *last_video_dts = pkt.dts().unwrap_or(raw_dts);
+        crate::render_trace!(
+            "[pkt] v pts={:?} dts={:?} size={}",
+            pkt.pts(),
+            pkt.dts(),
+            pkt.size()
+        );
+        pkt.write_interleaved(octx)
+            .map_err(|e| format!("write video packet: {e}"))?;
+    }
+    Ok(())
+}
+
+// ── Freeze frame / slate ──────────────────────────────────────────────────────
+
+/// The single packed YUV420P frame a slate or freeze clip repeats, or `None`
+/// for a clip that plays through its source. Slates never open a file.
+fn held_frame(clip: &ClipSpec, spec: &EncodeSpec) -> Option<Result<Vec<u8>, String>> {
+    if let Some(rgb) = clip.solid {
+        let (w, h) = (spec.width as usize, spec.height as usize);
+        let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+        write_yuv(&solid_yuv(rgb, w, h), &mut yuv, w, h);
+        apply_filter_to_yuv_frame(&mut yuv, &clip.filter, spec.width, spec.height);
+        return Some(Ok(extract_yuv(&yuv, w, h)));
+    }
+    clip.freeze_at.map(|ts| decode_freeze_frame(clip, ts, spec))
+}
+
+/// Emit the `held` frame for the clip's whole duration. The source never
+/// advances and no audio is decoded: the FIFO is padded with silence up to
+/// each frame boundary so later clips stay in sync.
+#[allow(clippy::too_many_arguments)]
+fn encode_held_clip(
+    held: &[u8],
+    clip: &ClipSpec,
+    spec: &EncodeSpec,
+    watermark: Option<&WatermarkYuv>,
+    octx: &mut ffmpeg::format::context::Output,
+    video_encoder: &mut ffmpeg::encoder::Video,
+    hw_frames_ctx: *mut ffmpeg::ffi::AVBufferRef,
+    hw_backend: HwBackend,
+    audio_state: &mut AudioEncState,
+    mut out_frame_idx: i64,
+    total_frames: u64,
+    frame_tb: Rational,
+    cancel: &Arc<AtomicBool>,
+    tx: &Sender<MediaResult>,
+    last_video_dts: &mut i64,
+) -> Result<i64, String> {
+    let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+    write_yuv(held, &mut yuv, spec.width as usize, spec.height as usize);
+    if let Some(wm) = watermark {
+        composite_watermark(&mut yuv, wm);
+    }
+    unsafe {
+        (*yuv.as_mut_ptr()).sample_aspect_ratio = ffmpeg::ffi::AVRational { num: 1, den: 1 };
+    }
+
+    let ost_tb = octx.stream(0).unwrap().time_base();
+    let audio_rate = spec.audio_rate() as i64;
+    let n = (clip.duration * spec.fps as f64).round() as i64;
+
+    for _ in 0..n {
+        if cancel.load(Ordering::Acquire) {
+            return Err("cancelled".into());
+        }
+
+        yuv.set_pts(Some(out_frame_idx));
+        send_video_frame(&yuv, video_encoder, hw_frames_ctx, hw_backend)?;
+
+        let mut pkt = Packet::empty();
+        while video_encoder.receive_packet(&mut pkt).is_ok() {
+            pkt.set_stream(0);
+            pkt.rescale_ts(frame_tb, ost_tb);
+            let raw_dts = pkt.dts().unwrap_or(0);
+            if *last_video_dts != i64::MIN {
+                let prev_s = *last_video_dts as f64 * f64::from(ost_tb);
+                let dts_s = raw_dts as f64 * f64::from(ost_tb);
+                if dts_s < prev_s {
+                    let clamped = *last_video_dts + 1;
+                    unsafe {
+                        (*pkt.as_mut_ptr()).dts = clamped;
+                    }
+                }
+            }
+            *last_video_dts = pkt.dts().unwrap_or(raw_dts);
+            crate::render_trace!(
+                "[pkt] v pts={:?} dts={:?} size={}",
+                pkt.pts(),
+                pkt.dts(),
+                pkt.size()
+            );
+            pkt.write_interleaved(octx)
+                .map_err(|e| format!("write held-frame packet: {e}"))?;
+        }
+
+        out_frame_idx += 1;
+
+        if (out_frame_idx as u64).is_multiple_of(PROGRESS_INTERVAL) {
+            let _ = tx.send(MediaResult::EncodeProgress {
+                job_id: spec.job_id,
+                frame: out_frame_idx as u64,
+                total_frames,
+            });
+        }
+
+        let expected = out_frame_idx * audio_rate / spec.fps as i64;
+        let have = audio_state.out_sample_idx + audio_state.fifo.len() as i64;
+        let gap = (expected - have).max(0) as usize;
+        if gap > 0 {
+            audio_state
+                .fifo
+                .left
+                .extend(std::iter::repeat_n(0.0f32, gap));
+            audio_state
+                .fifo
+                .right
+                .extend(std::iter::repeat_n(0.0f32, gap));
+        }
+        audio_state.drain_fifo(octx, false)?;
+
+        std::thread::yield_now();
+    }
+
+    Ok(out_frame_idx)
+}
+
+/// Decode the frame visible at source time `ts` (the last one at or before
+/// it), scaled and filtered to output size, as packed YUV420P.
+fn decode_freeze_frame(clip: &ClipSpec, ts: f64, spec: &EncodeSpec) -> Result<Vec<u8>, String> {
+    // A short window ending just past `ts` — taking its last frame also works
+    // when `ts` sits on the source's final frame.
+    let window_start = (ts - 0.5).max(0.0);
+    let window = ClipSpec {
+        source_offset: window_start,
+        duration: ts - window_start + 0.001,
+        freeze_at: None,
+        ..clip.clone()
+    };
+    decode_clip_frames(&window, spec)?.pop().ok_or_else(|| {
+        format!(
+            "no frame at {ts:.3}s in '{}' to freeze",
+            clip.path.display()
+        )
+    })
+}
+
 // ── Crossfade helpers ─────────────────────────────────────────────────────────
 
 pub(super) fn decode_clip_frames(
     clip: &ClipSpec,
     spec: &EncodeSpec,
 ) -> Result<Vec<Vec<u8>>, String> {
+    if let Some(held) = held_frame(clip, spec) {
+        let held = held?;
+        let n = (clip.duration * spec.fps as f64).round() as usize;
+        return Ok(vec![held; n]);
+    }
+
     let mut ictx = open_input(&clip.path)
         .map_err(|e| format!("crossfade open '{}': {e}", clip.path.display()))?;
 
@@ -713,10 +1137,15 @@ pub(super) fn decode_clip_frames(
                     src_display_h,
                     spec.width,
                     spec.height,
+                    clip.full_range,
+                    spec.dither,
+                    clip_stabilizer(clip),
                 )
+                .with_deinterlace(clip.deinterlace)
             });
 
             let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+            sc.track(pts_secs);
             sc.run(&decoded, &mut yuv)
                 .map_err(|e| format!("crossfade scale: {e}"))?;
             apply_filter_to_yuv_frame(&mut yuv, &clip.filter, spec.width, spec.height);
@@ -738,6 +1167,7 @@ pub(super) fn decode_clip_frames(
 
         if let Some(sc) = &mut video_scaler {
             let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+            sc.track(pts_secs);
             if sc.run(&decoded, &mut yuv).is_ok() {
                 frames.push(extract_yuv(&yuv, w, h));
             }
@@ -747,12 +1177,19 @@ pub(super) fn decode_clip_frames(
     Ok(frames)
 }
 
-pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>), String> {
+pub(super) fn decode_clip_audio(
+    clip: &ClipSpec,
+    audio_rate: i32,
+) -> Result<(Vec<f32>, Vec<f32>), String> {
+    if clip.freeze_at.is_some() || clip.solid.is_some() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
     let mut ictx = open_input(&clip.path)
         .map_err(|e| format!("transition audio open '{}': {e}", clip.path.display()))?;
 
-    let audio_stream_idx = match ictx.streams().best(MediaType::Audio) {
-        Some(s) => s.index(),
+    let audio_stream_idx = match select_audio_stream(&ictx, clip.audio_stream) {
+        Some(idx) => idx,
         None => return Ok((Vec::new(), Vec::new())),
     };
 
@@ -794,7 +1231,7 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
         unsafe {
             let l_bytes = frame.data(0);
             let l_f32 = std::slice::from_raw_parts(l_bytes.as_ptr() as *const f32, n);
-            left.extend(l_f32[skip..].iter().map(|s| (s * vol).clamp(-1.0, 1.0)));
+            left.extend(l_f32[skip..].iter().map(|s| s * vol));
 
             let r_bytes = if frame.ch_layout().channels() >= 2 {
                 frame.data(1)
@@ -802,7 +1239,7 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
                 frame.data(0)
             };
             let r_f32 = std::slice::from_raw_parts(r_bytes.as_ptr() as *const f32, n);
-            right.extend(r_f32[skip..].iter().map(|s| (s * vol).clamp(-1.0, 1.0)));
+            right.extend(r_f32[skip..].iter().map(|s| s * vol));
         }
     }
 
@@ -830,11 +1267,12 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
             }
 
             let pre_roll =
-                ((clip.source_offset - pts_secs).max(0.0) * AUDIO_RATE as f64).round() as usize;
+                ((clip.source_offset - pts_secs).max(0.0) * audio_rate as f64).round() as usize;
+            let key_gain = volume_key_gain(&clip.volume_keys, pts_secs);
 
             let raw_channels = raw.ch_layout().channels();
             let needs_resample =
-                raw.format() != target_fmt || raw.rate() != AUDIO_RATE as u32 || raw_channels != 2;
+                raw.format() != target_fmt || raw.rate() != audio_rate as u32 || raw_channels != 2;
 
             if needs_resample {
                 let rs = audio_resampler.get_or_insert_with(|| {
@@ -849,16 +1287,28 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
                         raw.rate(),
                         target_fmt,
                         ChannelLayout::STEREO,
-                        AUDIO_RATE as u32,
+                        audio_rate as u32,
                     )
                     .expect("create audio resampler (transition)")
                 });
                 let mut resampled = AudioFrame::empty();
                 if rs.run(&raw, &mut resampled).is_ok() && resampled.samples() > 0 {
-                    push_frame(&resampled, clip.volume, &mut left, &mut right, pre_roll);
+                    push_frame(
+                        &resampled,
+                        clip.volume * key_gain,
+                        &mut left,
+                        &mut right,
+                        pre_roll,
+                    );
                 }
             } else {
-                push_frame(&raw, clip.volume, &mut left, &mut right, pre_roll);
+                push_frame(
+                    &raw,
+                    clip.volume * key_gain,
+                    &mut left,
+                    &mut right,
+                    pre_roll,
+                );
             }
         }
     }
@@ -873,33 +1323,34 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
         if pts_secs >= clip_end {
             break;
         }
+        let key_gain = volume_key_gain(&clip.volume_keys, pts_secs);
 
         let raw_channels = raw.ch_layout().channels();
         let needs_resample =
-            raw.format() != target_fmt || raw.rate() != AUDIO_RATE as u32 || raw_channels != 2;
+            raw.format() != target_fmt || raw.rate() != audio_rate as u32 || raw_channels != 2;
 
         if needs_resample {
             if let Some(rs) = &mut audio_resampler {
                 let mut resampled = AudioFrame::empty();
                 if rs.run(&raw, &mut resampled).is_ok() && resampled.samples() > 0 {
-                    push_frame(&resampled, clip.volume, &mut left, &mut right, 0);
+                    push_frame(&resampled, clip.volume * key_gain, &mut left, &mut right, 0);
                 }
             }
         } else {
-            push_frame(&raw, clip.volume, &mut left, &mut right, 0);
+            push_frame(&raw, clip.volume * key_gain, &mut left, &mut right, 0);
         }
     }
 
     // Flush resampler tail (same fix as encode_clip).
     if let Some(ref mut rs) = audio_resampler {
-        let n_buffered = unsafe { ffmpeg::ffi::swr_get_delay(rs.as_mut_ptr(), AUDIO_RATE as i64) };
+        let n_buffered = unsafe { ffmpeg::ffi::swr_get_delay(rs.as_mut_ptr(), audio_rate as i64) };
         if n_buffered > 0 {
             let mut tmp_frame = AudioFrame::new(
                 Sample::F32(SampleType::Planar),
                 4096,
                 ChannelLayoutMask::STEREO,
             );
-            tmp_frame.set_rate(AUDIO_RATE as u32);
+            tmp_frame.set_rate(audio_rate as u32);
             unsafe {
                 let n_out = ffmpeg::ffi::swr_convert(
                     rs.as_mut_ptr(),
@@ -924,9 +1375,11 @@ pub(super) fn decode_clip_audio(clip: &ClipSpec) -> Result<(Vec<f32>, Vec<f32>),
 #[allow(clippy::too_many_arguments)]
 pub(super) fn apply_transition(
     transition: &dyn VideoTransition,
+    curve: FadeCurve,
     tail_spec: &ClipSpec,
     head_spec: &ClipSpec,
     spec: &EncodeSpec,
+    watermark: Option<&WatermarkYuv>,
     octx: &mut ffmpeg::format::context::Output,
     video_encoder: &mut ffmpeg::encoder::Video,
     hw_frames_ctx: *mut ffmpeg::ffi::AVBufferRef,
@@ -942,12 +1395,22 @@ pub(super) fn apply_transition(
     let tail_frames = decode_clip_frames(tail_spec, spec)?;
     let head_frames = decode_clip_frames(head_spec, spec)?;
 
-    let (tail_audio_l, tail_audio_r) = decode_clip_audio(tail_spec)?;
-    let (head_audio_l, head_audio_r) = decode_clip_audio(head_spec)?;
+    let (tail_audio_l, tail_audio_r) = decode_clip_audio(tail_spec, spec.audio_rate())?;
+    let (head_audio_l, head_audio_r) = decode_clip_audio(head_spec, spec.audio_rate())?;
 
-    let samples_per_frame_f = AUDIO_RATE as f64 / spec.fps as f64;
+    let samples_per_frame_f = spec.audio_rate() as f64 / spec.fps as f64;
 
+    // run_encode clamps the overlap to both clips' lengths, so a mismatch here
+    // means one side hit EOF early (e.g. a container shorter than its probed
+    // duration). Blend what both sides have rather than failing the export.
     let n = tail_frames.len().min(head_frames.len());
+    if tail_frames.len() != head_frames.len() {
+        crate::media_log!(
+            "[transition] frame count mismatch (tail {}, head {}); blending {n}",
+            tail_frames.len(),
+            head_frames.len()
+        );
+    }
     if n == 0 {
         return Ok(out_frame_idx);
     }
@@ -964,12 +1427,13 @@ pub(super) fn apply_transition(
         }
 
         let alpha = velocut_core::transitions::helpers::frame_alpha(i, n);
-        let blended = transition.apply(
+        let blended = transition.apply_curve(
             &tail_frames[i],
             &head_frames[i],
             spec.width,
             spec.height,
             alpha,
+            curve,
         );
 
         let mut yuv = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
@@ -979,6 +1443,9 @@ pub(super) fn apply_transition(
         }
 
         write_yuv(&blended, &mut yuv, w, h);
+        if let Some(wm) = watermark {
+            composite_watermark(&mut yuv, wm);
+        }
 
         send_video_frame(&yuv, video_encoder, hw_frames_ctx, hw_backend)?;
 
@@ -1002,13 +1469,19 @@ pub(super) fn apply_transition(
                 }
             }
             *last_video_dts = pkt.dts().unwrap_or(raw_dts);
+            crate::render_trace!(
+                "[pkt] v pts={:?} dts={:?} size={}",
+                pkt.pts(),
+                pkt.dts(),
+                pkt.size()
+            );
             pkt.write_interleaved(octx)
                 .map_err(|e| format!("transition write packet: {e}"))?;
         }
 
         let sample_start = (i as f64 * samples_per_frame_f).round() as usize;
         let sample_end = ((i + 1) as f64 * samples_per_frame_f).round() as usize;
-        let af = alpha as f32;
+        let (gain_tail, gain_head) = curve.audio_gains(alpha);
 
         let tail_last_l = tail_audio_l.last().copied().unwrap_or(0.0);
         let tail_last_r = tail_audio_r.last().copied().unwrap_or(0.0);
@@ -1020,14 +1493,8 @@ pub(super) fn apply_transition(
             let t_r = tail_audio_r.get(s).copied().unwrap_or(tail_last_r);
             let h_l = head_audio_l.get(s).copied().unwrap_or(head_last_l);
             let h_r = head_audio_r.get(s).copied().unwrap_or(head_last_r);
-            audio_state
-                .fifo
-                .left
-                .push((t_l * (1.0 - af) + h_l * af).clamp(-1.0, 1.0));
-            audio_state
-                .fifo
-                .right
-                .push((t_r * (1.0 - af) + h_r * af).clamp(-1.0, 1.0));
+            audio_state.fifo.left.push(t_l * gain_tail + h_l * gain_head);
+            audio_state.fifo.right.push(t_r * gain_tail + h_r * gain_head);
         }
 
         audio_state.drain_fifo(octx, false)?;
@@ -0,0 +1,82 @@
+// crates/velocut-media/src/encode/cover.rs
+//
+// Cover image (poster frame) for an export: one timeline frame, graded with
+// its clip's filter, cropped to the output size, JPEG-encoded and muxed as an
+// attached-picture stream. MP4 / MOV write it as the `covr` atom players and
+// upload sites show before playback.
+
+use std::io::Cursor;
+
+use ffmpeg::format::context::Output;
+use ffmpeg::Packet;
+use ffmpeg_the_third as ffmpeg;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+
+use velocut_core::filters::helpers::apply_filter_rgba;
+
+use super::CoverArt;
+use crate::decode::decode_one_frame_rgba;
+
+const COVER_JPEG_QUALITY: u8 = 90;
+
+/// Decode `cover`'s frame and return it as a `width`×`height` JPEG,
+/// center-cropped the same way the encoder crops every output frame.
+pub(super) fn cover_jpeg(cover: &CoverArt, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let (mut rgba, w, h) = decode_one_frame_rgba(&cover.path, cover.source_time, 0.0)
+        .map_err(|e| format!("decode {:?} at {:.3}s: {e}", cover.path, cover.source_time))?;
+    apply_filter_rgba(&mut rgba, w as usize, h as usize, &cover.filter);
+    let frame = RgbaImage::from_raw(w, h, rgba).ok_or("decoded frame has the wrong size")?;
+    let rgb = DynamicImage::ImageRgba8(frame)
+        .resize_to_fill(width, height, FilterType::Lanczos3)
+        .to_rgb8();
+
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(Cursor::new(&mut jpeg), COVER_JPEG_QUALITY)
+        .encode_image(&rgb)
+        .map_err(|e| format!("encode JPEG: {e}"))?;
+    Ok(jpeg)
+}
+
+/// Add an MJPEG attached-picture stream to `octx` and return its index.
+/// Must run after the video and audio streams are added (their indices are
+/// fixed at 0 and 1) and before `write_header`.
+pub(super) fn add_cover_stream(
+    octx: &mut Output,
+    width: u32,
+    height: u32,
+) -> Result<usize, String> {
+    unsafe {
+        let st = ffmpeg::ffi::avformat_new_stream(octx.as_mut_ptr(), std::ptr::null());
+        if st.is_null() {
+            return Err("avformat_new_stream (cover) failed".into());
+        }
+        (*st).disposition = ffmpeg::ffi::AV_DISPOSITION_ATTACHED_PIC as i32;
+        let par = (*st).codecpar;
+        (*par).codec_type = ffmpeg::ffi::AVMediaType::AVMEDIA_TYPE_VIDEO;
+        (*par).codec_id = ffmpeg::ffi::AVCodecID::AV_CODEC_ID_MJPEG;
+        (*par).width = width as i32;
+        (*par).height = height as i32;
+        Ok((*st).index as usize)
+    }
+}
+
+/// Write the cover JPEG as the stream's single packet. Written directly
+/// rather than interleaved: an attached picture has no timeline position,
+/// and the interleaver would otherwise hold it back waiting for a second
+/// packet on that stream that never comes.
+pub(super) fn write_cover_packet(
+    octx: &mut Output,
+    stream: usize,
+    jpeg: &[u8],
+) -> Result<(), String> {
+    let mut pkt = Packet::copy(jpeg);
+    pkt.set_stream(stream);
+    pkt.set_pts(Some(0));
+    pkt.set_dts(Some(0));
+    pkt.set_flags(ffmpeg::packet::Flags::KEY);
+    pkt.write(octx)
+        .map(|_| ())
+        .map_err(|e| format!("write cover packet: {e}"))
+}
@@ -10,7 +10,7 @@ use ffmpeg::util::frame::video::Video as VideoFrame;
 use ffmpeg::util::rational::Rational;
 use ffmpeg_the_third as ffmpeg;
 
-use super::HwEncodeCapabilities;
+use super::{HwEncodeCapabilities, RatePass};
 
 // ── Hardware capability probe ─────────────────────────────────────────────────
 
@@ -202,7 +202,7 @@ fn probe_vaapi_device(width: u32, height: u32) -> bool {
 /// Which H.264 encoder backend is active for this encode job.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(super) enum HwBackend {
-    /// libx264 software encoder — universal fallback.
+    /// Software encoder — libx264 fallback for MP4, always libvpx-vp9 for WebM.
     Software,
     /// NVIDIA NVENC via CUDA device frames.
     Nvenc,
@@ -262,7 +262,7 @@ pub(super) fn try_open_hw_encoder(
 
     // ── Software fallback ─────────────────────────────────────────────────────
     crate::media_log!("[encode] HW encoder: none available, using libx264 software");
-    let enc = open_software_encoder(width, height, fps, out_tb, octx)
+    let enc = open_software_encoder(width, height, fps, out_tb, octx, None)
         .expect("libx264 is required — ensure it is compiled in");
     (enc, HwBackend::Software, None)
 }
@@ -704,12 +704,15 @@ fn try_videotoolbox_encoder(
     }
 }
 
+/// Open libx264: CRF 18 normally, or one pass of a two-pass average-bitrate
+/// encode when `pass` is set.
 pub(super) fn open_software_encoder(
     width: u32,
     height: u32,
     fps: u32,
     out_tb: Rational,
     octx: &ffmpeg::format::context::Output,
+    pass: Option<RatePass>,
 ) -> Result<ffmpeg::encoder::Video, String> {
     let h264 = encoder::find(CodecId::H264)
         .ok_or_else(|| "H.264 encoder not found — is libx264 available?".to_string())?;
@@ -725,15 +728,22 @@ pub(super) fn open_software_encoder(
     enc.set_format(Pixel::YUV420P);
     enc.set_time_base(out_tb);
     enc.set_frame_rate(Some(Rational::new(fps as i32, 1)));
-    enc.set_bit_rate(0);
+    enc.set_bit_rate(pass.map_or(0, |p| p.kbps as usize * 1000));
 
+    let mut flags = ffmpeg::codec::flag::Flags::empty();
     if octx
         .format()
         .flags()
         .contains(ffmpeg::format::Flags::GLOBAL_HEADER)
     {
-        enc.set_flags(ffmpeg::codec::flag::Flags::GLOBAL_HEADER);
+        flags |= ffmpeg::codec::flag::Flags::GLOBAL_HEADER;
+    }
+    match pass.map(|p| p.number) {
+        Some(1) => flags |= ffmpeg::codec::flag::Flags::PASS1,
+        Some(_) => flags |= ffmpeg::codec::flag::Flags::PASS2,
+        None => {}
     }
+    enc.set_flags(flags);
 
     // Cap libx264 to half the logical CPU count so the encoder never saturates
     // every core at any resolution (480p through 4K). The OS schedules UI,
@@ -746,7 +756,13 @@ pub(super) fn open_software_encoder(
         .unwrap_or(2);
 
     let mut opts = ffmpeg::Dictionary::new();
-    opts.set("crf", "18");
+    match pass {
+        // Both passes share one statistics file; pass 1 writes it, pass 2
+        // reads it. Without `stats` libx264 would use x264_2pass.log in the
+        // working directory.
+        Some(p) => opts.set("stats", &p.stats.to_string_lossy()),
+        None => opts.set("crf", "18"),
+    }
     // "medium" is more CPU-efficient per thread than "fast" — it does more work
     // per encode pass, so the total core-seconds consumed for equivalent quality
     // is lower.  Combined with the thread cap and per-frame yield_now() below,
@@ -758,3 +774,64 @@ pub(super) fn open_software_encoder(
     enc.open_as_with(h264, opts)
         .map_err(|e| format!("open H.264 encoder: {e}"))
 }
+
+/// Open libvpx-vp9 for WebM output.
+///
+/// VP9 has no HW path here — the HW probes above are all H.264-specific — so
+/// this is the only VP9 encoder and it always reports `HwBackend::Software`.
+/// Rate control is constant quality (`crf` with `b:v 0`), the libvpx
+/// equivalent of libx264's CRF mode; 31 is the upstream-recommended value for
+/// 1080p and looks comparable to x264 CRF 18 at the same resolution.
+pub(super) fn open_vp9_encoder(
+    width: u32,
+    height: u32,
+    fps: u32,
+    out_tb: Rational,
+    octx: &ffmpeg::format::context::Output,
+) -> Result<ffmpeg::encoder::Video, String> {
+    let vp9 = encoder::find_by_name("libvpx-vp9")
+        .or_else(|| encoder::find(CodecId::VP9))
+        .ok_or_else(|| "VP9 encoder not found — is libvpx available?".to_string())?;
+
+    let enc_ctx = codec::context::Context::new_with_codec(vp9);
+    let mut enc = enc_ctx
+        .encoder()
+        .video()
+        .map_err(|e| format!("create VP9 encoder context: {e}"))?;
+
+    enc.set_width(width);
+    enc.set_height(height);
+    enc.set_format(Pixel::YUV420P);
+    enc.set_time_base(out_tb);
+    enc.set_frame_rate(Some(Rational::new(fps as i32, 1)));
+    // b:v 0 is what switches libvpx from constrained to pure constant-quality.
+    enc.set_bit_rate(0);
+
+    if octx
+        .format()
+        .flags()
+        .contains(ffmpeg::format::Flags::GLOBAL_HEADER)
+    {
+        enc.set_flags(ffmpeg::codec::flag::Flags::GLOBAL_HEADER);
+    }
+
+    // Same half-core cap as libx264 so the UI stays responsive.
+    let thread_cap = std::thread::available_parallelism()
+        .map(|n| (n.get() / 2).max(1))
+        .unwrap_or(2);
+
+    let mut opts = ffmpeg::Dictionary::new();
+    opts.set("crf", "31");
+    opts.set("b", "0");
+    // deadline=good + cpu-used=4 is the usual speed/quality balance for
+    // offline VP9; realtime is too lossy, best is an order of magnitude slower.
+    opts.set("deadline", "good");
+    opts.set("cpu-used", "4");
+    // Row-based multithreading — without it libvpx barely uses the thread cap.
+    opts.set("row-mt", "1");
+    opts.set("threads", &thread_cap.to_string());
+    opts.set("g", &fps.to_string());
+
+    enc.open_as_with(vp9, opts)
+        .map_err(|e| format!("open VP9 encoder: {e}"))
+}
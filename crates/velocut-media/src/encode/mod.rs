@@ -1,6 +1,6 @@
 // crates/velocut-media/src/encode.rs
 //
-// Multi-clip H.264 + AAC MP4 encode pipeline.
+// Multi-clip H.264 + AAC MP4 / MKV / MOV (or VP9 + Opus WebM) encode pipeline.
 //
 // Design:
 //   • `ClipSpec`   — everything needed to locate and trim one source clip.
@@ -8,10 +8,32 @@
 //   • `encode_timeline()` — blocking function meant to run on its own thread;
 //     called from MediaWorker::start_encode. Sends EncodeProgress every
 //     PROGRESS_INTERVAL frames and EncodeError / EncodeDone on exit.
+//     Muxes into `partial_output_path()` and renames onto `output` only after
+//     write_trailer; on cancel or error the partial file is deleted.
 //
-// Stream layout in the output MP4:
-//   Stream 0 — H.264 video (YUV420P, CRF 18, preset medium, or HW equivalent)
+// Stream layout in the output MP4 / MKV / MOV:
+//   Stream 0 — H.264 video (YUV420P, CRF 18, preset medium, or HW equivalent;
+//              libx264 two-pass ABR when `EncodeSpec::target_kbps` is set)
 //   Stream 1 — AAC audio  (FLTP stereo, 44100 Hz, 128 kbps)
+//              FFmpeg builds without the native AAC encoder fall back to MP3
+//              (libmp3lame) then AC-3; with none of them the file is written
+//              video-only and `EncodeDone::audio_codec` is `None`.
+//   Last     — MJPEG cover image (attached picture, MP4 / MOV only) when
+//              `EncodeSpec::cover` is set. See cover.rs.
+//   `open_output` picks the muxer from the file extension, which the UI keeps
+//   in sync with `ExportFormat`. All three muxers set GLOBAL_HEADER, so the
+//   encoders emit extradata (avcC / AudioSpecificConfig) out of band; that
+//   flag is read from the muxer rather than assumed. Matroska forces a 1/1000
+//   stream time base like WebM; MP4 / MOV keep the encoder time bases.
+//
+// Stream layout in the output WebM (`ExportFormat::WebM`):
+//   Stream 0 — VP9 video  (YUV420P, libvpx-vp9 constant quality: crf 31, b:v 0)
+//   Stream 1 — Opus audio (stereo, 48000 Hz, 128 kbps), falling back to
+//              Vorbis (libvorbis) the same way.
+//   No HW path — VP9 always uses the software encoder. The WebM muxer forces
+//   a 1/1000 stream time base; packets are rescaled from the encoder time
+//   bases (1/fps, 1/48000) to whatever the muxer picked in write_header, so
+//   the PTS strategy below is unchanged.
 //
 // Hardware encoding:
 //   Attempted in priority order: AMF (D3D11) → NVENC (CUDA) → VAAPI → VideoToolbox → libx264.
@@ -30,12 +52,13 @@
 //
 // PTS strategy:
 //   Video: monotonically increasing frame counter (output_frame_idx) in 1/fps.
-//   Audio: monotonically increasing sample counter (out_sample_idx) in 1/44100.
+//   Audio: monotonically increasing sample counter (out_sample_idx) in
+//          1/audio_rate (44100 for MP4, 48000 for WebM).
 //   Both reset to zero at the start of the encode, eliminating discontinuities
 //   introduced by source file trimming and multi-clip concatenation.
 //
 // Audio FIFO:
-//   AAC requires exactly `encoder.frame_size()` (typically 1024) samples per
+//   AAC / Opus require exactly `encoder.frame_size()` (1024 / 960) samples per
 //   input frame. Decoded audio may arrive in arbitrary chunk sizes, so all
 //   decoded/resampled PCM is drained into a stereo FLTP ring buffer. Full
 //   frames are popped from the front and sent to the encoder; any remainder
@@ -57,13 +80,24 @@
 //   frames for the duration of the tail so the audio is preserved with a blank
 //   screen rather than silently dropped.
 //
+// Two-pass bitrate mode:
+//   With `target_kbps` set (H.264 formats only) the whole timeline is encoded
+//   twice by libx264 — pass 1 writes rate statistics to a scratch file, pass 2
+//   reads them to hit the average bitrate. HW encoders are skipped. Pass 1's
+//   output is overwritten by pass 2. Progress runs 0–50% then 50–100%.
+//
+// Audio stems:
+//   An `EncodeSpec` with no clips but some audio overlays is a single-track
+//   audio export: the overlays are mixed and written as WAV (see stem.rs)
+//   instead of going through the muxer.
+//
 // Cancellation:
 //   `cancel` is an Arc<AtomicBool> checked after every video frame. When set,
 //   EncodeError { msg: "cancelled" } is sent — the UI treats that as an aborted
 //   state distinct from a real error, keeping the cancel path identical to the
 //   error path.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
@@ -72,31 +106,42 @@ use std::sync::{
 use crossbeam_channel::Sender;
 use uuid::Uuid;
 
-use ffmpeg::codec::{self, Id as CodecId};
+use ffmpeg::codec::Id as CodecId;
 use ffmpeg::encoder;
 use ffmpeg::format::sample::Type as SampleType;
 use ffmpeg::format::{output as open_output, Pixel, Sample};
 use ffmpeg::packet::Mut as _;
-use ffmpeg::util::channel_layout::ChannelLayout;
 use ffmpeg::util::frame::video::Video as VideoFrame;
 use ffmpeg::util::rational::Rational;
 use ffmpeg::Packet;
 use ffmpeg_the_third as ffmpeg;
 
+use crate::helpers::log::{begin_render_log, end_render_log};
+use crate::helpers::yuv::{composite_watermark, solid_yuv, write_yuv};
+use crate::stabilize::camera_path;
 use velocut_core::filters::FilterParams;
-use velocut_core::media_types::MediaResult;
+use velocut_core::media_types::{DitherMode, ExportFormat, ExportMetadata, MediaResult};
+use velocut_core::state::VolumeKey;
 use velocut_core::transitions::{registry, ClipTransition, TransitionKind};
+use velocut_core::watermark::{Watermark, WatermarkYuv};
 
 mod hw;
 pub use hw::probe_hw_encode_capabilities;
-use hw::{try_open_hw_encoder, HwBackend};
+use hw::{open_software_encoder, open_vp9_encoder, try_open_hw_encoder, HwBackend};
 
 mod audio;
-use audio::{decode_overlay, AudioEncState, AudioFifo};
+pub use audio::probe_audio_encoder;
+use audio::{audio_candidates, decode_overlay, open_audio_encoder, AudioEncState, AudioFifo};
 
 mod clip;
 use clip::{apply_transition, encode_clip, send_video_frame};
 
+mod cover;
+use cover::{add_cover_stream, cover_jpeg, write_cover_packet};
+
+mod stem;
+use stem::run_audio_stem;
+
 // ── Public types ──────────────────────────────────────────────────────────────
 
 /// One source clip's contribution to the output timeline.
@@ -112,6 +157,25 @@ pub struct ClipSpec {
     pub volume: f32,
     /// When true, no audio is decoded or pushed to the FIFO for this clip.
     pub skip_audio: bool,
+    /// Source audio stream to decode (`None` = ffmpeg's best audio stream).
+    pub audio_stream: Option<usize>,
+    /// Freeze-frame hold: decode the single frame at this source timestamp and
+    /// repeat it for `duration`. No audio is decoded; the FIFO gets silence.
+    pub freeze_at: Option<f64>,
+    /// Solid-color slate (sRGB): `path` is not opened; every frame is this
+    /// color and the FIFO gets silence, like a freeze. Wins over `freeze_at`.
+    pub solid: Option<[u8; 3]>,
+    /// Source video is full range (`LibraryClip::is_full_range`). Frames are
+    /// remapped to limited range after scaling so mixed sources match.
+    pub full_range: bool,
+    /// Source video is interlaced (`LibraryClip::needs_deinterlace`). Fields
+    /// are blended on the decoded frame, before scaling.
+    pub deinterlace: bool,
+    /// Stabilize the picture (`TimelineClip::stabilize`); `None` = off.
+    pub stabilize: Option<StabilizeSpec>,
+    /// Gain automation in source seconds (`TimelineClip::volume_keys`),
+    /// applied on top of `volume` and the fades.
+    pub volume_keys: Vec<VolumeKey>,
     /// Fade-in ramp duration (0.0 = none). Ramp starts after `fade_in_start_secs` of silence.
     pub fade_in_secs: f32,
     /// Silence before the fade-in ramp begins (0.0 = ramp starts at clip boundary).
@@ -123,14 +187,27 @@ pub struct ClipSpec {
     pub filter: FilterParams,
 }
 
+/// Stabilization of one clip. `from..to` is the whole timeline clip's source
+/// range — the camera path is measured over it once, so the head and tail
+/// pieces a transition splits off share the analysis.
+#[derive(Clone, Copy, Debug)]
+pub struct StabilizeSpec {
+    /// 0.1–1.0, see `velocut_core::state::Stabilize`.
+    pub strength: f32,
+    pub from: f64,
+    pub to: f64,
+}
+
 /// A standalone audio clip that runs in parallel with the video timeline.
 #[derive(Clone)]
 pub struct AudioOverlay {
     pub path: PathBuf,
+    pub audio_stream: Option<usize>,
     pub source_offset: f64,
     pub timeline_start: f64,
     pub duration: f64,
     pub volume: f32,
+    pub volume_keys: Vec<VolumeKey>,
     pub fade_in_secs: f32,
     pub fade_in_start_secs: f32,
     pub fade_out_secs: f32,
@@ -147,6 +224,47 @@ pub struct EncodeSpec {
     pub output: PathBuf,
     pub transitions: Vec<ClipTransition>,
     pub audio_overlays: Vec<AudioOverlay>,
+    /// Container/codec pair. Must match the extension of `output`.
+    pub format: ExportFormat,
+    /// Logo composited onto every output frame, including black gap frames.
+    pub watermark: Option<Watermark>,
+    /// Also write per-packet timestamps to the render log (see
+    /// `render_log_path`). Off by default — a long render logs tens of MB.
+    pub verbose_log: bool,
+    /// Error-resilient decode: a video packet or frame the decoder or scaler
+    /// rejects is logged and dropped — the last good frame is held over the
+    /// hole — instead of failing the render. Counted in
+    /// `EncodeDone::skipped_frames`.
+    pub error_resilient: bool,
+    /// Quantization of >8-bit sources to the 8-bit encoder input.
+    pub dither: DitherMode,
+    /// Average video bitrate (kb/s) for a two-pass libx264 encode. `None`
+    /// keeps constant quality. Ignored for formats without two-pass support.
+    pub target_kbps: Option<u32>,
+    /// Container tags (title, artist, comment, date) set on the output
+    /// before the header is written.
+    pub metadata: ExportMetadata,
+    /// Frame embedded as the output's cover image. Ignored by formats
+    /// without cover-art support (`ExportFormat::supports_cover_art`).
+    pub cover: Option<CoverArt>,
+}
+
+/// Source frame for the cover image, resolved from the timeline by the UI.
+#[derive(Clone, Debug)]
+pub struct CoverArt {
+    pub path: PathBuf,
+    pub source_time: f64,
+    /// The clip's color filter, so the cover matches the graded video.
+    pub filter: FilterParams,
+}
+
+impl EncodeSpec {
+    /// Output audio sample rate for this job — see `ExportFormat::audio_rate`.
+    /// Every resampler, FIFO frame, and sample↔frame conversion in the
+    /// pipeline uses this value instead of a fixed constant.
+    pub fn audio_rate(&self) -> i32 {
+        self.format.audio_rate() as i32
+    }
 }
 
 // ── Hardware capability probe ─────────────────────────────────────────────────
@@ -169,7 +287,16 @@ pub struct HwEncodeCapabilities {
 // ── Constants ─────────────────────────────────────────────────────────────────
 
 pub(super) const PROGRESS_INTERVAL: u64 = 15;
-pub(super) const AUDIO_RATE: i32 = 44100;
+
+/// One run of a two-pass average-bitrate encode.
+#[derive(Clone, Copy)]
+pub(super) struct RatePass<'a> {
+    /// 1 = analysis (writes `stats`), 2 = final encode (reads it).
+    pub number: u8,
+    pub kbps: u32,
+    /// libx264 statistics file shared by both passes.
+    pub stats: &'a Path,
+}
 
 // ── Center-crop scaler ────────────────────────────────────────────────────────
 
@@ -178,18 +305,63 @@ pub(super) const AUDIO_RATE: i32 = 44100;
 // ── Public entry point ────────────────────────────────────────────────────────
 
 pub fn encode_timeline(spec: EncodeSpec, cancel: Arc<AtomicBool>, tx: Sender<MediaResult>) {
-    let total_frames: u64 = spec
-        .clips
-        .iter()
-        .map(|c| (c.duration * spec.fps as f64).ceil() as u64)
-        .sum::<u64>()
-        .max(1);
+    let total_frames = planned_output_frames(&spec);
+
+    let log_path = render_log_path(&spec.output);
+    if let Err(e) = begin_render_log(&log_path, spec.verbose_log) {
+        crate::media_log!(
+            "[encode] could not create render log '{}': {e}",
+            log_path.display()
+        );
+    }
+    log_spec(&spec, total_frames);
+    analyze_stabilized_clips(&spec, &cancel);
+
+    // Mux into a side file and move it over `spec.output` only once the
+    // trailer is written, so a cancel, error or crash never leaves a broken
+    // file under the final name — or clobbers a previous good export.
+    let partial = partial_output_path(&spec.output);
+    let two_pass = spec.target_kbps.filter(|_| spec.format.supports_two_pass());
+    let result = if spec.clips.is_empty() && !spec.audio_overlays.is_empty() {
+        run_audio_stem(&spec, &partial, &cancel, total_frames, &tx)
+    } else {
+        match two_pass {
+            Some(kbps) => run_two_pass(&spec, &partial, cancel, total_frames, &tx, kbps),
+            None => run_encode(&spec, &partial, cancel, total_frames, &tx, None),
+        }
+    };
+    let result = result.and_then(|r| {
+        std::fs::rename(&partial, &spec.output)
+            .map(|()| r)
+            .map_err(|e| format!("could not move finished render into place: {e}"))
+    });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    match &result {
+        Ok((peak, _, skipped)) => {
+            crate::media_log!(
+                "[encode] audio peak {:+.1} dBFS{}",
+                20.0 * peak.log10(),
+                if *peak > 1.0 { " — CLIPPED" } else { "" }
+            );
+            if *skipped > 0 {
+                crate::media_log!("[encode] {skipped} corrupt video frame(s) skipped");
+            }
+            crate::media_log!("[encode] done: {}", spec.output.display());
+        }
+        Err(e) => crate::media_log!("[encode] stopped: {e}"),
+    }
+    end_render_log();
 
-    match run_encode(&spec, cancel, total_frames, &tx) {
-        Ok(()) => {
+    match result {
+        Ok((audio_peak, audio_codec, skipped_frames)) => {
             let _ = tx.send(MediaResult::EncodeDone {
                 job_id: spec.job_id,
                 path: spec.output.clone(),
+                audio_peak,
+                audio_codec,
+                skipped_frames,
             });
         }
         Err(e) => {
@@ -201,44 +373,337 @@ pub fn encode_timeline(spec: EncodeSpec, cancel: Arc<AtomicBool>, tx: Sender<Med
     }
 }
 
+/// Per-render diagnostics file written next to the output:
+/// `clip.mp4` → `clip.render.log`.
+pub fn render_log_path(output: &Path) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "render".into());
+    output.with_file_name(format!("{stem}.render.log"))
+}
+
+/// Where a render is written until it finishes: `clip.mp4` →
+/// `clip.partial.mp4`. The extension is kept because `open_output` picks the
+/// muxer from it.
+pub fn partial_output_path(output: &Path) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "render".into());
+    let name = match output.extension() {
+        Some(ext) => format!("{stem}.partial.{}", ext.to_string_lossy()),
+        None => format!("{stem}.partial"),
+    };
+    output.with_file_name(name)
+}
+
+/// Two-pass average-bitrate encode: pass 1 only gathers libx264 rate
+/// statistics into the scratch folder, pass 2 encodes against them over the
+/// same `out_path`. Each pass fills half of one progress bar. The statistics
+/// files are removed however the passes end.
+fn run_two_pass(
+    spec: &EncodeSpec,
+    out_path: &Path,
+    cancel: Arc<AtomicBool>,
+    total_frames: u64,
+    tx: &Sender<MediaResult>,
+    kbps: u32,
+) -> Result<(f32, Option<&'static str>, u64), String> {
+    let stats = crate::audio::scratch_dir().join(format!("velocut_2pass_{}.log", spec.job_id));
+    let run_pass = |number: u8| {
+        crate::media_log!("[encode] pass {number}/2 — {kbps} kb/s average");
+        let pass = RatePass {
+            number,
+            kbps,
+            stats: &stats,
+        };
+        let base = (number as u64 - 1) * total_frames;
+        with_progress_span(tx, base, 2 * total_frames, |ptx| {
+            run_encode(
+                spec,
+                out_path,
+                cancel.clone(),
+                total_frames,
+                ptx,
+                Some(pass),
+            )
+        })
+    };
+    let result = run_pass(1).and_then(|_| run_pass(2));
+    // libx264 also keeps macroblock-tree data and in-progress copies beside
+    // the main statistics file.
+    for suffix in ["", ".temp", ".mbtree", ".mbtree.temp"] {
+        let mut path = stats.clone().into_os_string();
+        path.push(suffix);
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+/// Stabilization pass one: measure the camera path of every stabilized clip
+/// before any frame is written. Paths are cached (see `stabilize.rs`), so
+/// the per-clip code only looks them up and re-renders skip this. A clip
+/// whose analysis fails exports unstabilized.
+fn analyze_stabilized_clips(spec: &EncodeSpec, cancel: &AtomicBool) {
+    for c in &spec.clips {
+        let Some(stab) = c.stabilize else { continue };
+        if cancel.load(Ordering::Acquire) {
+            return;
+        }
+        if let Err(e) = camera_path(&c.path, stab.from, stab.to, cancel) {
+            crate::media_log!(
+                "[encode] stabilization analysis failed for '{}': {e}",
+                c.path.display()
+            );
+        }
+    }
+}
+
+/// Run `f` with a sender that shifts every `EncodeProgress` by `base` frames
+/// and reports `total` as the denominator, forwarding everything else as is.
+/// Lets each pass of a two-pass encode fill its own stretch of one progress
+/// bar without the per-clip code knowing about passes.
+fn with_progress_span<R>(
+    tx: &Sender<MediaResult>,
+    base: u64,
+    total: u64,
+    f: impl FnOnce(&Sender<MediaResult>) -> R,
+) -> R {
+    let (ptx, prx) = crossbeam_channel::unbounded();
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            for msg in prx {
+                let msg = match msg {
+                    MediaResult::EncodeProgress { job_id, frame, .. } => {
+                        MediaResult::EncodeProgress {
+                            job_id,
+                            frame: base + frame,
+                            total_frames: total,
+                        }
+                    }
+                    other => other,
+                };
+                let _ = tx.send(msg);
+            }
+        });
+        // Dropping the sender ends the forwarder before the scope joins it.
+        let out = f(&ptx);
+        drop(ptx);
+        out
+    })
+}
+
+/// Header of the render log: everything needed to reproduce the job.
+fn log_spec(spec: &EncodeSpec, total_frames: u64) {
+    crate::media_log!(
+        "[encode] VeloCut {} render → {}",
+        env!("CARGO_PKG_VERSION"),
+        spec.output.display()
+    );
+    crate::media_log!(
+        "[encode] {} {}x{} @ {} fps, {} clip(s), {} transition(s), {} audio overlay(s), \
+         ~{total_frames} frames, dither {}{}{}{}",
+        spec.format.name(),
+        spec.width,
+        spec.height,
+        spec.fps,
+        spec.clips.len(),
+        spec.transitions.len(),
+        spec.audio_overlays.len(),
+        spec.dither.label(),
+        spec.target_kbps
+            .map(|k| format!(", two-pass {k} kb/s"))
+            .unwrap_or_default(),
+        if spec.verbose_log { ", verbose" } else { "" },
+        if spec.error_resilient {
+            ", error-resilient"
+        } else {
+            ""
+        }
+    );
+    for (i, c) in spec.clips.iter().enumerate() {
+        crate::media_log!(
+            "[encode] clip {i}: '{}' src {:.3}s +{:.3}s vol {:.2}{}{}{}{}{}{}",
+            c.path.display(),
+            c.source_offset,
+            c.duration,
+            c.volume,
+            if c.skip_audio { " no-audio" } else { "" },
+            c.freeze_at
+                .map(|t| format!(" freeze@{t:.3}s"))
+                .unwrap_or_default(),
+            c.solid
+                .map(|[r, g, b]| format!(" slate #{r:02x}{g:02x}{b:02x}"))
+                .unwrap_or_default(),
+            if c.full_range { " full-range" } else { "" },
+            if c.deinterlace { " deinterlace" } else { "" },
+            c.stabilize
+                .map(|s| format!(" stabilize {:.2}", s.strength))
+                .unwrap_or_default()
+        );
+    }
+    for t in &spec.transitions {
+        crate::media_log!(
+            "[encode] transition after clip {}: {:?} {:.3}s",
+            t.after_clip_index,
+            t.kind.kind,
+            t.kind.duration_secs
+        );
+    }
+}
+
 // ── Audio ── see audio.rs
 
+// ── Frame planning ────────────────────────────────────────────────────────────
+
+/// Seconds of overlap actually encoded for the transition after `clip_idx`,
+/// given `skip` seconds already consumed from that clip's head by the
+/// previous transition. 0.0 means a hard cut.
+///
+/// The overlap can never exceed what either side actually has left: the
+/// outgoing clip after its own incoming-transition skip, and the incoming
+/// clip in full. Anything under one output frame is encoded as a cut.
+fn transition_overlap_secs(spec: &EncodeSpec, clip_idx: usize, skip: f64) -> f64 {
+    if clip_idx + 1 >= spec.clips.len() {
+        return 0.0;
+    }
+    let Some(t) = spec
+        .transitions
+        .iter()
+        .find(|t| t.after_clip_index == clip_idx)
+        .filter(|t| t.kind.kind != TransitionKind::Cut)
+    else {
+        return 0.0;
+    };
+    let available = (spec.clips[clip_idx].duration - skip)
+        .min(spec.clips[clip_idx + 1].duration)
+        .max(0.0);
+    let secs = (t.kind.duration_secs as f64).min(available);
+    if (secs * spec.fps as f64).round() < 1.0 {
+        0.0
+    } else {
+        secs
+    }
+}
+
+/// Number of video frames `run_encode` will emit — the denominator for
+/// `EncodeProgress`. Mirrors the per-clip loop: each transition overlaps the
+/// outgoing tail with the incoming head, so it removes its own length from
+/// the total once rather than counting both sides. Also includes the black
+/// tail appended when an audio overlay runs past the last video frame, so
+/// the bar lands on 100% instead of overshooting.
+fn planned_output_frames(spec: &EncodeSpec) -> u64 {
+    let fps = spec.fps as f64;
+    let frames = |secs: f64| (secs.max(0.0) * fps).round() as u64;
+
+    let mut total: u64 = 0;
+    let mut skip = 0.0;
+    for (clip_idx, clip) in spec.clips.iter().enumerate() {
+        let overlap = transition_overlap_secs(spec, clip_idx, skip);
+        total += frames(clip.duration - skip - overlap) + frames(overlap);
+        skip = overlap;
+    }
+
+    let overlay_end = spec
+        .audio_overlays
+        .iter()
+        .map(|ov| ov.timeline_start + ov.duration)
+        .fold(0.0_f64, f64::max);
+    let video_end = total as f64 / fps.max(1.0);
+    if overlay_end > video_end {
+        total += ((overlay_end - video_end) * fps).ceil() as u64;
+    }
+
+    total.max(1)
+}
+
 // ── Internal implementation ───────────────────────────────────────────────────
 
 fn run_encode(
     spec: &EncodeSpec,
+    out_path: &Path,
     cancel: Arc<AtomicBool>,
     total_frames: u64,
     tx: &Sender<MediaResult>,
-) -> Result<(), String> {
+    pass: Option<RatePass>,
+) -> Result<(f32, Option<&'static str>, u64), String> {
     if spec.clips.is_empty() {
         return Err("nothing to encode: timeline is empty".into());
     }
 
+    // Decode and scale the watermark once, before the output file exists, so
+    // a missing or corrupt PNG fails the job without leaving a stub behind.
+    let watermark = match &spec.watermark {
+        Some(wm) => {
+            let (rgba, img_w, img_h) = crate::decode::load_png_rgba(&wm.path)
+                .map_err(|e| format!("watermark '{}': {e}", wm.path.display()))?;
+            WatermarkYuv::prepare(&rgba, img_w, img_h, wm, spec.width, spec.height)
+        }
+        None => None,
+    };
+
+    // The cover is only muxed into the file that survives — pass 1 of a
+    // two-pass encode is overwritten. A frame that won't decode costs the
+    // cover, not the render.
+    let cover = spec
+        .cover
+        .as_ref()
+        .filter(|_| spec.format.supports_cover_art() && pass.is_none_or(|p| p.number == 2))
+        .and_then(|c| match cover_jpeg(c, spec.width, spec.height) {
+            Ok(jpeg) => Some(jpeg),
+            Err(e) => {
+                crate::media_log!("[encode] cover image skipped: {e}");
+                None
+            }
+        });
+
     // ── Output context ────────────────────────────────────────────────────────
-    let mut octx = open_output(&spec.output)
-        .map_err(|e| format!("could not open output '{}': {e}", spec.output.display()))?;
+    let mut octx = open_output(out_path)
+        .map_err(|e| format!("could not open output '{}': {e}", out_path.display()))?;
 
     // ── Video encoder (stream 0) ──────────────────────────────────────────────
     let out_tb = Rational::new(1, spec.fps as i32);
     let frame_tb = Rational::new(1, spec.fps as i32);
+    let audio_rate = spec.audio_rate();
 
     // Determine which codec we'll be registering for the stream.  HW encoders
     // expose themselves under their own codec ID (hevc_nvenc, h264_nvenc, etc.)
-    // but we always want stream 0 to carry H.264, so use the H264 codec ID for
-    // the output stream regardless of which actual encoder won.
-    let h264_for_stream =
-        encoder::find(CodecId::H264).ok_or_else(|| "H.264 codec not registered".to_string())?;
+    // but we always want stream 0 to carry H.264 (MP4) or VP9 (WebM), so use
+    // the generic codec ID for the output stream regardless of which actual
+    // encoder won.
+    let video_for_stream = match spec.format {
+        ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => {
+            encoder::find(CodecId::H264).ok_or_else(|| "H.264 codec not registered".to_string())?
+        }
+        ExportFormat::WebM => {
+            encoder::find(CodecId::VP9).ok_or_else(|| "VP9 codec not registered".to_string())?
+        }
+    };
 
     let mut ost_video = octx
-        .add_stream(h264_for_stream)
+        .add_stream(video_for_stream)
         .map_err(|e| format!("add video stream: {e}"))?;
     ost_video.set_time_base(out_tb);
 
     // Open the best available encoder. This MUST happen before write_header
     // so we can copy codecpar in.  HW context (if any) is kept alive here.
-    let (mut video_encoder, hw_backend, hw_device) =
-        try_open_hw_encoder(spec.width, spec.height, spec.fps, out_tb, &octx);
+    // Two-pass needs libx264's statistics file, so it never tries HW.
+    let (mut video_encoder, hw_backend, hw_device) = match spec.format {
+        ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov if pass.is_some() => (
+            open_software_encoder(spec.width, spec.height, spec.fps, out_tb, &octx, pass)?,
+            HwBackend::Software,
+            None,
+        ),
+        ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => {
+            try_open_hw_encoder(spec.width, spec.height, spec.fps, out_tb, &octx)
+        }
+        ExportFormat::WebM => (
+            open_vp9_encoder(spec.width, spec.height, spec.fps, out_tb, &octx)?,
+            HwBackend::Software,
+            None,
+        ),
+    };
 
     crate::media_log!("[encode] video encoder backend: {hw_backend:?}");
 
@@ -264,73 +729,117 @@ fn run_encode(
                 "avcodec_parameters_from_context (video) failed: {ret}"
             ));
         }
+        // Let each muxer pick its own fourcc. A tag carried over from the
+        // encoder (HW encoders sometimes set one) is rejected by Matroska and
+        // can mismatch MOV's sample-entry table.
+        (*(**(*octx.as_mut_ptr()).streams.add(0)).codecpar).codec_tag = 0;
     }
 
     // ── Audio encoder (stream 1) ──────────────────────────────────────────────
-    let audio_tb = Rational::new(1, AUDIO_RATE);
-
-    let aac = encoder::find(CodecId::AAC).ok_or_else(|| "AAC encoder not found".to_string())?;
-
-    let mut ost_audio = octx
-        .add_stream(aac)
-        .map_err(|e| format!("add audio stream: {e}"))?;
-    ost_audio.set_time_base(audio_tb);
-
-    let audio_enc_ctx = codec::context::Context::new_with_codec(aac);
-    let mut audio_enc = audio_enc_ctx
-        .encoder()
-        .audio()
-        .map_err(|e| format!("create audio encoder context: {e}"))?;
-
-    audio_enc.set_rate(AUDIO_RATE);
-    audio_enc.set_ch_layout(ChannelLayout::STEREO);
-    audio_enc.set_format(Sample::F32(SampleType::Planar));
-    audio_enc.set_bit_rate(128_000);
-
-    if octx
+    // First candidate this FFmpeg build can open wins. With none, the export
+    // goes out video-only rather than failing — the done message says so.
+    let audio_tb = Rational::new(1, audio_rate);
+    let global_header = octx
         .format()
         .flags()
-        .contains(ffmpeg::format::Flags::GLOBAL_HEADER)
-    {
-        audio_enc.set_flags(ffmpeg::codec::flag::Flags::GLOBAL_HEADER);
+        .contains(ffmpeg::format::Flags::GLOBAL_HEADER);
+    let mut audio_choice = None;
+    for cand in audio_candidates(spec.format) {
+        match open_audio_encoder(cand, audio_rate, global_header) {
+            Ok(enc) => {
+                audio_choice = Some((enc, cand));
+                break;
+            }
+            Err(e) => crate::media_log!("[encode] audio encoder {}: {e}", cand.label),
+        }
+    }
+    let audio_codec = audio_choice.as_ref().map(|(_, cand)| cand.label);
+    match audio_codec {
+        Some(label) => crate::media_log!("[encode] audio encoder: {label}"),
+        None => crate::media_log!(
+            "[encode] WARNING: no usable audio encoder in this FFmpeg build — exporting video only"
+        ),
     }
 
-    let audio_encoder = audio_enc
-        .open_as_with(aac, ffmpeg::Dictionary::new())
-        .map_err(|e| format!("open AAC encoder: {e}"))?;
+    // AAC reports 1024, libopus 960 (20 ms @ 48 kHz), MP3 1152, AC-3 1536.
+    // All reject frames of any other size, so only fall back to 1024 when the
+    // encoder reports 0 (variable frame size).
+    let audio_frame_size = match audio_choice.as_ref().map(|(enc, _)| enc.frame_size()) {
+        Some(0) | None => 1024,
+        Some(n) => n as usize,
+    };
+    let audio_packed = audio_choice
+        .as_ref()
+        .is_some_and(|(_, cand)| matches!(cand.format, Sample::F32(SampleType::Packed)));
+
+    if let Some((audio_encoder, cand)) = &audio_choice {
+        let audio_codec_for_stream = encoder::find_by_name(cand.encoder)
+            .ok_or_else(|| format!("{} encoder vanished", cand.label))?;
+        let mut ost_audio = octx
+            .add_stream(audio_codec_for_stream)
+            .map_err(|e| format!("add audio stream: {e}"))?;
+        ost_audio.set_time_base(audio_tb);
+
+        unsafe {
+            let ret = ffmpeg::ffi::avcodec_parameters_from_context(
+                (**(*octx.as_mut_ptr()).streams.add(1)).codecpar,
+                audio_encoder.as_ptr() as *mut ffmpeg::ffi::AVCodecContext,
+            );
+            if ret < 0 {
+                return Err(format!(
+                    "avcodec_parameters_from_context (audio) failed: {ret}"
+                ));
+            }
+            (*(**(*octx.as_mut_ptr()).streams.add(1)).codecpar).codec_tag = 0;
+        }
+    }
 
-    let audio_frame_size = (audio_encoder.frame_size() as usize).max(1024);
+    // ── Cover image (last stream) ─────────────────────────────────────────────
+    let cover_stream = match &cover {
+        Some(_) => Some(add_cover_stream(&mut octx, spec.width, spec.height)?),
+        None => None,
+    };
 
-    unsafe {
-        let ret = ffmpeg::ffi::avcodec_parameters_from_context(
-            (**(*octx.as_mut_ptr()).streams.add(1)).codecpar,
-            audio_encoder.as_ptr() as *mut ffmpeg::ffi::AVCodecContext,
-        );
-        if ret < 0 {
-            return Err(format!(
-                "avcodec_parameters_from_context (audio) failed: {ret}"
-            ));
+    // ── Container metadata ────────────────────────────────────────────────────
+    // Must be set before write_header — that is when the muxer serializes it.
+    let tags = spec.metadata.tags();
+    if !tags.is_empty() {
+        let mut dict = ffmpeg::Dictionary::new();
+        for (key, value) in tags {
+            dict.set(key, value);
         }
+        octx.set_metadata(dict);
     }
 
     // ── Write output header ───────────────────────────────────────────────────
-    ffmpeg::format::context::output::dump(&octx, 0, Some(&spec.output.to_string_lossy()));
+    ffmpeg::format::context::output::dump(&octx, 0, Some(&out_path.to_string_lossy()));
     octx.write_header()
         .map_err(|e| format!("write output header: {e}"))?;
+    if let (Some(jpeg), Some(index)) = (&cover, cover_stream) {
+        write_cover_packet(&mut octx, index, jpeg)?;
+        crate::media_log!("[encode] cover image: {} KB JPEG", jpeg.len() / 1024);
+    }
 
-    let ost_audio_tb = octx.stream(1).unwrap().time_base();
+    let ost_audio_tb = octx.stream(1).map_or(audio_tb, |s| s.time_base());
 
+    // Overlays are skipped outright without an encoder: decoding them would
+    // only extend a video-only export with black frames for unheard audio.
+    let overlays: &[AudioOverlay] = if audio_choice.is_some() {
+        &spec.audio_overlays
+    } else {
+        &[]
+    };
     let mut audio_state = AudioEncState {
-        encoder: audio_encoder,
+        encoder: audio_choice.map(|(enc, _)| enc),
         out_sample_idx: 0,
         frame_size: audio_frame_size,
-        fifo: AudioFifo::new(),
+        fifo: AudioFifo::new(audio_rate as u32),
+        packed: audio_packed,
         audio_tb,
         ost_audio_tb,
-        overlays: spec
-            .audio_overlays
+        overlays: overlays
             .iter()
-            .filter_map(|ov| match decode_overlay(ov) {
+            .filter_map(|ov| match decode_overlay(ov, audio_rate as u32) {
                 Ok(d) => Some(d),
                 Err(e) => {
                     crate::media_log!("[encode] overlay decode failed: {e}");
@@ -345,11 +854,13 @@ fn run_encode(
             })
             .collect(),
         fifo_overrun_count: 0,
+        peak: 0.0,
     };
 
     // ── Per-clip encode loop ──────────────────────────────────────────────────
     let mut output_frame_idx: i64 = 0;
     let mut last_video_dts: i64 = i64::MIN;
+    let mut skipped_frames: u64 = 0;
     let mut incoming_skip_secs: f64 = 0.0;
     let transition_registry = registry();
 
@@ -370,9 +881,19 @@ fn run_encode(
             None
         };
 
-        let transition_secs: f64 = transition_entry
-            .map(|t| t.kind.duration_secs as f64)
-            .unwrap_or(0.0);
+        // Clamp here so both sides decode the same number of frames instead
+        // of apply_transition silently truncating — see transition_overlap_secs.
+        let transition_secs = transition_overlap_secs(spec, clip_idx, skip);
+        if let Some(t) = transition_entry {
+            let requested = t.kind.duration_secs as f64;
+            if transition_secs < requested {
+                crate::media_log!(
+                    "[encode] transition after clip {clip_idx} shortened \
+                     {requested:.3}s → {transition_secs:.3}s (clip too short)"
+                );
+            }
+        }
+        let transition_entry = transition_entry.filter(|_| transition_secs > 0.0);
 
         let effective = ClipSpec {
             path: clip.path.clone(),
@@ -380,6 +901,13 @@ fn run_encode(
             duration: (clip.duration - skip - transition_secs).max(0.0),
             volume: clip.volume,
             skip_audio: clip.skip_audio,
+            audio_stream: clip.audio_stream,
+            freeze_at: clip.freeze_at,
+            solid: clip.solid,
+            full_range: clip.full_range,
+            deinterlace: clip.deinterlace,
+            stabilize: clip.stabilize,
+            volume_keys: clip.volume_keys.clone(),
             fade_in_secs: clip.fade_in_secs,
             fade_in_start_secs: clip.fade_in_start_secs,
             fade_out_secs: clip.fade_out_secs,
@@ -390,6 +918,7 @@ fn run_encode(
         output_frame_idx = encode_clip(
             &effective,
             spec,
+            watermark.as_ref(),
             &mut octx,
             &mut video_encoder,
             hw_frames_ctx_ptr,
@@ -401,6 +930,7 @@ fn run_encode(
             &cancel,
             tx,
             &mut last_video_dts,
+            &mut skipped_frames,
         )?;
 
         if let Some(entry) = transition_entry {
@@ -412,6 +942,13 @@ fn run_encode(
                 duration: transition_secs,
                 volume: clip.volume,
                 skip_audio: false,
+                audio_stream: clip.audio_stream,
+                freeze_at: clip.freeze_at,
+                solid: clip.solid,
+                full_range: clip.full_range,
+                deinterlace: clip.deinterlace,
+                stabilize: clip.stabilize,
+                volume_keys: clip.volume_keys.clone(),
                 fade_in_secs: 0.0,
                 fade_in_start_secs: 0.0,
                 fade_out_secs: 0.0,
@@ -424,6 +961,13 @@ fn run_encode(
                 duration: transition_secs,
                 volume: next_clip.volume,
                 skip_audio: false,
+                audio_stream: next_clip.audio_stream,
+                freeze_at: next_clip.freeze_at,
+                solid: next_clip.solid,
+                full_range: next_clip.full_range,
+                deinterlace: next_clip.deinterlace,
+                stabilize: next_clip.stabilize,
+                volume_keys: next_clip.volume_keys.clone(),
                 fade_in_secs: 0.0,
                 fade_in_start_secs: 0.0,
                 fade_out_secs: 0.0,
@@ -434,9 +978,11 @@ fn run_encode(
             if let Some(transition_impl) = transition_registry.get(&entry.kind.kind) {
                 output_frame_idx = apply_transition(
                     transition_impl.as_ref(),
+                    entry.kind.curve,
                     &tail_spec,
                     &head_spec,
                     spec,
+                    watermark.as_ref(),
                     &mut octx,
                     &mut video_encoder,
                     hw_frames_ctx_ptr,
@@ -467,7 +1013,7 @@ fn run_encode(
     // video ends.  The FIFO is silence-padded each frame so drain_fifo mixes
     // the overlay in normally.
     {
-        let video_end_sample = output_frame_idx * AUDIO_RATE as i64 / spec.fps as i64;
+        let video_end_sample = output_frame_idx * audio_rate as i64 / spec.fps as i64;
         let overlay_end_sample = audio_state
             .overlays
             .iter()
@@ -479,11 +1025,11 @@ fn run_encode(
             let extra_samples = overlay_end_sample - video_end_sample;
             // Round up so the last partial AAC frame is always included.
             let extra_frames =
-                ((extra_samples as f64 * spec.fps as f64 / AUDIO_RATE as f64).ceil() as i64).max(0);
+                ((extra_samples as f64 * spec.fps as f64 / audio_rate as f64).ceil() as i64).max(0);
 
             crate::media_log!(
                 "[encode] overlay tail: {:.3}s past video end — appending {} blank frame(s)",
-                extra_samples as f64 / AUDIO_RATE as f64,
+                extra_samples as f64 / audio_rate as f64,
                 extra_frames,
             );
 
@@ -492,21 +1038,14 @@ fn run_encode(
             // Pre-allocate one black YUV420P frame and reuse it for all blank frames.
             // Y=16 (black, BT.709 limited), U=V=128 (neutral chroma).
             let mut blank = VideoFrame::new(Pixel::YUV420P, spec.width, spec.height);
+            let (w, h) = (spec.width as usize, spec.height as usize);
+            write_yuv(&solid_yuv([0, 0, 0], w, h), &mut blank, w, h);
             unsafe {
-                let ptr = blank.as_mut_ptr();
-                let y_stride = (*ptr).linesize[0] as usize;
-                let uv_stride = (*ptr).linesize[1] as usize;
-                for row in 0..spec.height as usize {
-                    let p = (*ptr).data[0].add(row * y_stride);
-                    std::slice::from_raw_parts_mut(p, spec.width as usize).fill(16u8);
-                }
-                for row in 0..(spec.height as usize / 2) {
-                    let pu = (*ptr).data[1].add(row * uv_stride);
-                    let pv = (*ptr).data[2].add(row * uv_stride);
-                    std::slice::from_raw_parts_mut(pu, spec.width as usize / 2).fill(128u8);
-                    std::slice::from_raw_parts_mut(pv, spec.width as usize / 2).fill(128u8);
-                }
-                (*ptr).sample_aspect_ratio = ffmpeg::ffi::AVRational { num: 1, den: 1 };
+                (*blank.as_mut_ptr()).sample_aspect_ratio =
+                    ffmpeg::ffi::AVRational { num: 1, den: 1 };
+            }
+            if let Some(wm) = &watermark {
+                composite_watermark(&mut blank, wm);
             }
 
             for _ in 0..extra_frames {
@@ -537,6 +1076,12 @@ fn run_encode(
                         }
                     }
                     last_video_dts = pkt.dts().unwrap_or(raw_dts);
+                    crate::render_trace!(
+                        "[pkt] v pts={:?} dts={:?} size={}",
+                        pkt.pts(),
+                        pkt.dts(),
+                        pkt.size()
+                    );
                     pkt.write_interleaved(&mut octx)
                         .map_err(|e| format!("write blank video packet: {e}"))?;
                 }
@@ -553,7 +1098,7 @@ fn run_encode(
                 std::thread::yield_now();
 
                 // Pad silence into the FIFO so drain_fifo can mix the overlay tail.
-                let expected = output_frame_idx * AUDIO_RATE as i64 / spec.fps as i64;
+                let expected = output_frame_idx * audio_rate as i64 / spec.fps as i64;
                 let have = audio_state.out_sample_idx + audio_state.fifo.len() as i64;
                 let gap = (expected - have).max(0) as usize;
                 if gap > 0 {
@@ -607,6 +1152,12 @@ fn run_encode(
             }
         }
         last_video_dts = pkt.dts().unwrap_or(raw_dts);
+        crate::render_trace!(
+            "[pkt] v pts={:?} dts={:?} size={}",
+            pkt.pts(),
+            pkt.dts(),
+            pkt.size()
+        );
         pkt.write_interleaved(&mut octx)
             .map_err(|e| format!("write flush video packet: {e}"))?;
         output_frame_idx = output_frame_idx.max(frame_pts + 1);
@@ -623,7 +1174,7 @@ fn run_encode(
     // is a sub-frame rounding artifact (< frame_size samples) that is safe to
     // trim or flush.
     {
-        let target_audio_samples = output_frame_idx * AUDIO_RATE as i64 / spec.fps as i64;
+        let target_audio_samples = output_frame_idx * audio_rate as i64 / spec.fps as i64;
         let total_audio = audio_state.out_sample_idx + audio_state.fifo.len() as i64;
         let excess = (total_audio - target_audio_samples).max(0) as usize;
         if excess > 0 {
@@ -631,7 +1182,7 @@ fn run_encode(
                 "[encode] trimming {} trailing clip-audio samples ({:.3}s) — \
                  clip audio ran past video end ({:.3}s)",
                 excess,
-                excess as f64 / AUDIO_RATE as f64,
+                excess as f64 / audio_rate as f64,
                 output_frame_idx as f64 / spec.fps as f64,
             );
             let new_len = audio_state.fifo.left.len().saturating_sub(excess);
@@ -641,7 +1192,7 @@ fn run_encode(
             crate::media_log!(
                 "[encode] audio/video end aligned: video={:.3}s audio={:.3}s",
                 output_frame_idx as f64 / spec.fps as f64,
-                total_audio as f64 / AUDIO_RATE as f64,
+                total_audio as f64 / audio_rate as f64,
             );
         }
         if audio_state.fifo_overrun_count > 1 {
@@ -664,7 +1215,7 @@ fn run_encode(
     // reference device memory during the trailer flush.
     drop(hw_device);
 
-    Ok(())
+    Ok((audio_state.peak, audio_codec, skipped_frames))
 }
 
 /// Send one YUV420P software frame to the video encoder, uploading to the HW
@@ -676,6 +1227,133 @@ fn run_encode(
 #[cfg(test)]
 mod tests {
     use super::audio::fade_gain;
+    use super::*;
+    use velocut_core::transitions::TransitionType;
+
+    #[test]
+    fn partial_output_keeps_extension() {
+        assert_eq!(
+            partial_output_path(Path::new("/out/clip.mp4")),
+            PathBuf::from("/out/clip.partial.mp4")
+        );
+        assert_eq!(
+            partial_output_path(Path::new("/out/clip.v2.webm")),
+            PathBuf::from("/out/clip.v2.partial.webm")
+        );
+        assert_eq!(
+            partial_output_path(Path::new("/out/clip")),
+            PathBuf::from("/out/clip.partial")
+        );
+    }
+
+    fn clip(duration: f64) -> ClipSpec {
+        ClipSpec {
+            path: PathBuf::from("clip.mp4"),
+            source_offset: 0.0,
+            duration,
+            volume: 1.0,
+            skip_audio: false,
+            audio_stream: None,
+            freeze_at: None,
+            solid: None,
+            full_range: false,
+            deinterlace: false,
+            stabilize: None,
+            volume_keys: Vec::new(),
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+            filter: FilterParams::default(),
+        }
+    }
+
+    fn spec(clips: Vec<ClipSpec>, transitions: Vec<(usize, f32)>) -> EncodeSpec {
+        EncodeSpec {
+            job_id: Uuid::nil(),
+            clips,
+            width: 1280,
+            height: 720,
+            fps: 30,
+            output: PathBuf::from("out.mp4"),
+            transitions: transitions
+                .into_iter()
+                .map(|(after_clip_index, duration_secs)| ClipTransition {
+                    after_clip_index,
+                    kind: TransitionType::new(TransitionKind::Crossfade, duration_secs),
+                })
+                .collect(),
+            audio_overlays: Vec::new(),
+            format: ExportFormat::Mp4,
+            watermark: None,
+            verbose_log: false,
+            error_resilient: false,
+            dither: DitherMode::default(),
+            target_kbps: None,
+            metadata: ExportMetadata::default(),
+            cover: None,
+        }
+    }
+
+    #[test]
+    fn progress_span_offsets_each_pass() {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        with_progress_span(&tx, 100, 200, |ptx| {
+            let _ = ptx.send(MediaResult::EncodeProgress {
+                job_id: Uuid::nil(),
+                frame: 15,
+                total_frames: 100,
+            });
+        });
+        match rx.try_recv() {
+            Ok(MediaResult::EncodeProgress {
+                frame,
+                total_frames,
+                ..
+            }) => assert_eq!((frame, total_frames), (115, 200)),
+            _ => panic!("progress not forwarded"),
+        }
+    }
+
+    #[test]
+    fn planned_frames_without_transitions_is_sum_of_clips() {
+        let s = spec(vec![clip(2.0), clip(3.0)], vec![]);
+        assert_eq!(planned_output_frames(&s), 150);
+    }
+
+    #[test]
+    fn planned_frames_subtracts_crossfade_overlap_once() {
+        // 2s + 3s with a 1s crossfade → 4s of output.
+        let s = spec(vec![clip(2.0), clip(3.0)], vec![(0, 1.0)]);
+        assert_eq!(planned_output_frames(&s), 120);
+    }
+
+    #[test]
+    fn planned_frames_uses_clamped_overlap() {
+        // Requested 5s but the incoming clip is only 1s long.
+        let s = spec(vec![clip(4.0), clip(1.0)], vec![(0, 5.0)]);
+        assert_eq!(transition_overlap_secs(&s, 0, 0.0), 1.0);
+        assert_eq!(planned_output_frames(&s), 120);
+    }
+
+    #[test]
+    fn planned_frames_includes_overlay_tail() {
+        let mut s = spec(vec![clip(2.0)], vec![]);
+        s.audio_overlays.push(AudioOverlay {
+            path: PathBuf::from("music.wav"),
+            audio_stream: None,
+            source_offset: 0.0,
+            timeline_start: 1.0,
+            duration: 2.0,
+            volume: 1.0,
+            volume_keys: Vec::new(),
+            fade_in_secs: 0.0,
+            fade_in_start_secs: 0.0,
+            fade_out_secs: 0.0,
+            fade_out_end_secs: 0.0,
+        });
+        assert_eq!(planned_output_frames(&s), 90);
+    }
 
     #[test]
     fn fade_gain_no_fades_returns_unity() {
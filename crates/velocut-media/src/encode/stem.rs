@@ -0,0 +1,121 @@
+// crates/velocut-media/src/encode/stem.rs
+//
+// Audio-only export of one timeline track ("stem") for handing to a sound
+// designer. Used when `EncodeSpec::clips` is empty: every audio overlay is
+// decoded with `decode_overlay` — so gain, fades and volume keys match the
+// full render exactly — mixed at its timeline position and written as 16-bit
+// stereo PCM WAV. No video stream, no FIFO, no encoder to probe.
+
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossbeam_channel::Sender;
+use velocut_core::media_types::MediaResult;
+
+use super::audio::decode_overlay;
+use super::EncodeSpec;
+
+/// Mix `spec.audio_overlays` into `out_path`. Returns the pre-clamp peak, the
+/// codec label and no skipped frames, like `run_encode`.
+pub(super) fn run_audio_stem(
+    spec: &EncodeSpec,
+    out_path: &Path,
+    cancel: &AtomicBool,
+    total_frames: u64,
+    tx: &Sender<MediaResult>,
+) -> Result<(f32, Option<&'static str>, u64), String> {
+    let rate = spec.audio_rate() as u32;
+    let mut left: Vec<f32> = Vec::new();
+    let mut right: Vec<f32> = Vec::new();
+
+    let count = spec.audio_overlays.len() as u64;
+    for (i, overlay) in spec.audio_overlays.iter().enumerate() {
+        if cancel.load(Ordering::Acquire) {
+            return Err("cancelled".into());
+        }
+        // A clip that fails to decode is left silent rather than sinking the
+        // whole stem, same as an overlay in a full render.
+        match decode_overlay(overlay, rate) {
+            Ok(dec) => {
+                let start = dec.start_sample.max(0) as usize;
+                let end = start + dec.sample_count;
+                if left.len() < end {
+                    left.resize(end, 0.0);
+                    right.resize(end, 0.0);
+                }
+                for (n, (l, r)) in dec.left.iter().zip(&dec.right).enumerate() {
+                    left[start + n] += l;
+                    right[start + n] += r;
+                }
+            }
+            Err(e) => crate::media_log!("[encode] stem: skipping overlay — {e}"),
+        }
+        let _ = tx.send(MediaResult::EncodeProgress {
+            job_id: spec.job_id,
+            frame: total_frames * (i as u64 + 1) / count.max(1),
+            total_frames,
+        });
+    }
+    if left.is_empty() {
+        return Err("no audio decoded for this track".into());
+    }
+
+    let peak = left
+        .iter()
+        .chain(&right)
+        .fold(0.0_f32, |p, s| p.max(s.abs()));
+    write_wav(out_path, rate, &left, &right)
+        .map_err(|e| format!("write '{}': {e}", out_path.display()))?;
+    Ok((peak, Some("PCM"), 0))
+}
+
+/// 16-bit little-endian stereo PCM WAV.
+fn write_wav(path: &Path, rate: u32, left: &[f32], right: &[f32]) -> std::io::Result<()> {
+    let data_bytes = (left.len() * 4) as u32;
+    let mut w = BufWriter::new(std::fs::File::create(path)?);
+    w.write_all(b"RIFF")?;
+    w.write_all(&(36 + data_bytes).to_le_bytes())?;
+    w.write_all(b"WAVEfmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&1u16.to_le_bytes())?; // PCM
+    w.write_all(&2u16.to_le_bytes())?; // channels
+    w.write_all(&rate.to_le_bytes())?;
+    w.write_all(&(rate * 4).to_le_bytes())?; // byte rate
+    w.write_all(&4u16.to_le_bytes())?; // block align
+    w.write_all(&16u16.to_le_bytes())?; // bits per sample
+    w.write_all(b"data")?;
+    w.write_all(&data_bytes.to_le_bytes())?;
+    for (l, r) in left.iter().zip(right) {
+        for s in [l, r] {
+            let v = (s.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+            w.write_all(&v.to_le_bytes())?;
+        }
+    }
+    w.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_is_interleaved_16_bit_stereo_and_clamped() {
+        let path = std::env::temp_dir().join(format!("velocut_stem_{}.wav", std::process::id()));
+        write_wav(&path, 48_000, &[0.5, 2.0], &[-0.5, 0.0]).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(bytes.len(), 44 + 8);
+        assert_eq!(&bytes[..4], b"RIFF");
+        assert_eq!(
+            u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+            48_000
+        );
+        let samples: Vec<i16> = bytes[44..]
+            .chunks(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(samples, [16384, -16384, i16::MAX, 0]);
+    }
+}
@@ -11,13 +11,24 @@
 //
 // The file handle is held open for the process lifetime via a OnceLock<Mutex<File>>.
 //
+// Render logs: while an encode runs, its thread also owns a per-render log
+// next to the output (`begin_render_log` / `end_render_log`). Every
+// `media_log!` from that thread is teed into it, so a user can attach one
+// self-contained file to a bug report. `render_trace!` lines (per-packet
+// timestamps) go ONLY to the render log, and only when the render was started
+// with verbose logging — they would otherwise run to tens of MB.
+//
 // Usage:
 //   use crate::helpers::log::media_log;
 //   media_log!("[encode] probe: AMF available");
 //   media_log!("[pb] Start received (active), ts={:.3}", ts);
+//   render_trace!("[pkt] v pts={pts:?} dts={dts:?}");
 
-use std::io::Write;
+use std::cell::RefCell;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
 
 static LOG_FILE: OnceLock<Option<Mutex<std::fs::File>>> = OnceLock::new();
 
@@ -42,6 +53,57 @@ pub fn log_impl(msg: &str) {
             let _ = writeln!(f, "[{ts}] {msg}");
         }
     }
+    render_log_write(msg);
+}
+
+struct RenderLog {
+    out: BufWriter<std::fs::File>,
+    verbose: bool,
+    started: Instant,
+}
+
+thread_local! {
+    static RENDER_LOG: RefCell<Option<RenderLog>> = const { RefCell::new(None) };
+}
+
+/// Start teeing this thread's log output into `path` (truncated). Replaces
+/// any render log already open on this thread.
+pub fn begin_render_log(path: &Path, verbose: bool) -> std::io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    RENDER_LOG.with(|slot| {
+        *slot.borrow_mut() = Some(RenderLog {
+            out: BufWriter::new(file),
+            verbose,
+            started: Instant::now(),
+        });
+    });
+    Ok(())
+}
+
+/// Flush and close this thread's render log, if one is open.
+pub fn end_render_log() {
+    RENDER_LOG.with(|slot| {
+        if let Some(mut log) = slot.borrow_mut().take() {
+            let _ = log.out.flush();
+        }
+    });
+}
+
+/// True when this thread has a render log open in verbose mode. Checked by
+/// `render_trace!` before formatting anything.
+pub fn render_log_verbose() -> bool {
+    RENDER_LOG.with(|slot| slot.borrow().as_ref().is_some_and(|l| l.verbose))
+}
+
+/// Append `msg` to this thread's render log, stamped with seconds since the
+/// render started. No-op when none is open.
+pub fn render_log_write(msg: &str) {
+    RENDER_LOG.with(|slot| {
+        if let Some(log) = slot.borrow_mut().as_mut() {
+            let t = log.started.elapsed().as_secs_f64();
+            let _ = writeln!(log.out, "[{t:9.3}] {msg}");
+        }
+    });
 }
 
 /// Convenience macro — formats like `eprintln!` but routes through `log_impl`.
@@ -51,3 +113,15 @@ macro_rules! media_log {
         $crate::helpers::log::log_impl(&format!($($arg)*))
     };
 }
+
+/// Verbose-only render diagnostics. Written to the current render log (never
+/// the global log) and only when that render was started with verbose logging;
+/// the arguments are not even formatted otherwise.
+#[macro_export]
+macro_rules! render_trace {
+    ($($arg:tt)*) => {
+        if $crate::helpers::log::render_log_verbose() {
+            $crate::helpers::log::render_log_write(&format!($($arg)*))
+        }
+    };
+}
@@ -16,7 +16,13 @@
 // "Packed" means strides are removed — each row is exactly w (or w/2) bytes.
 // ffmpeg VideoFrame rows may have padding; extract_yuv strips it.
 
+use ffmpeg_the_third::ffi;
+use ffmpeg_the_third::format::Pixel;
 use ffmpeg_the_third::util::frame::video::Video as VideoFrame;
+use velocut_core::filters::helpers::{apply_levels_luma, apply_spatial_luma};
+use velocut_core::filters::{Levels, SpatialFilter};
+use velocut_core::media_types::DitherMode;
+use velocut_core::watermark::WatermarkYuv;
 
 /// Extract packed (stride-free) YUV420P bytes from a scaled VideoFrame.
 ///
@@ -92,3 +98,381 @@ pub fn write_yuv(packed: &[u8], yuv: &mut VideoFrame, w: usize, h: usize) {
         v_dst[row * v_stride..row * v_stride + uv_w].copy_from_slice(&packed[src..src + uv_w]);
     }
 }
+
+/// A packed YUV420P frame of one solid sRGB `rgb` color, converted with
+/// BT.601 limited range like the encoder's output matrix. `[0, 0, 0]` is the
+/// Y=16 / U=V=128 black used for blank frames.
+pub fn solid_yuv(rgb: [u8; 3], w: usize, h: usize) -> Vec<u8> {
+    let [r, g, b] = rgb.map(|c| c as f32 / 255.0);
+    let y = 16.0 + 65.481 * r + 128.553 * g + 24.966 * b;
+    let u = 128.0 - 37.797 * r - 74.203 * g + 112.0 * b;
+    let v = 128.0 + 112.0 * r - 93.786 * g - 18.214 * b;
+    let uv_len = (w / 2) * (h / 2);
+    let mut raw = vec![y.round().clamp(16.0, 235.0) as u8; w * h];
+    raw.resize(w * h + uv_len, u.round().clamp(16.0, 240.0) as u8);
+    raw.resize(w * h + uv_len * 2, v.round().clamp(16.0, 240.0) as u8);
+    raw
+}
+
+/// Run a clip's sharpen / blur pass on the Y plane of a YUV420P VideoFrame,
+/// in place and stride-aware. Chroma planes are left untouched.
+pub fn apply_spatial_to_frame(yuv: &mut VideoFrame, w: usize, h: usize, filter: &SpatialFilter) {
+    let stride = yuv.stride(0);
+    apply_spatial_luma(yuv.data_mut(0), w, h, stride, filter);
+}
+
+/// Run a clip's levels remap on the Y plane of a YUV420P VideoFrame, in
+/// place and stride-aware. Chroma planes are left untouched.
+pub fn apply_levels_to_frame(yuv: &mut VideoFrame, w: usize, h: usize, levels: &Levels) {
+    let stride = yuv.stride(0);
+    apply_levels_luma(yuv.data_mut(0), w, h, stride, levels);
+}
+
+/// Alpha-blend a prepared watermark onto all three planes of a YUV420P
+/// VideoFrame, in place and stride-aware.
+pub fn composite_watermark(yuv: &mut VideoFrame, watermark: &WatermarkYuv) {
+    for plane in 0..3 {
+        let stride = yuv.stride(plane);
+        watermark.composite_plane(plane, yuv.data_mut(plane), stride);
+    }
+}
+
+/// Direction of a YUV quantization-range conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeRemap {
+    /// 0–255 → luma 16–235, chroma 16–240.
+    FullToLimited,
+    /// Luma 16–235, chroma 16–240 → 0–255 (out-of-range codes clamp).
+    LimitedToFull,
+}
+
+impl RangeRemap {
+    /// The remap needed after swscale has converted a `src_fmt` frame to
+    /// YUV420P, given the range the source actually uses.
+    ///
+    /// swscale treats the yuvj* formats as full range and compresses them to
+    /// limited itself; every other format passes through untouched. So a
+    /// tagged-full yuv420p source still needs compressing, and a yuvj source
+    /// forced to limited needs swscale's compression undone.
+    pub fn after_swscale(src_fmt: Pixel, source_full: bool) -> Option<Self> {
+        let sws_full = matches!(src_fmt, Pixel::YUVJ420P | Pixel::YUVJ422P | Pixel::YUVJ444P);
+        match (sws_full, source_full) {
+            (false, true) => Some(RangeRemap::FullToLimited),
+            (true, false) => Some(RangeRemap::LimitedToFull),
+            _ => None,
+        }
+    }
+
+    /// 256-entry lookup tables `(luma, chroma)` for this direction.
+    fn luts(self) -> ([u8; 256], [u8; 256]) {
+        let mut y_lut = [0u8; 256];
+        let mut c_lut = [0u8; 256];
+        for (v, (y, c)) in y_lut.iter_mut().zip(c_lut.iter_mut()).enumerate() {
+            let f = v as f32;
+            let (ny, nc) = match self {
+                RangeRemap::FullToLimited => (16.0 + f * 219.0 / 255.0, 16.0 + f * 224.0 / 255.0),
+                RangeRemap::LimitedToFull => {
+                    ((f - 16.0) * 255.0 / 219.0, (f - 16.0) * 255.0 / 224.0)
+                }
+            };
+            *y = ny.round().clamp(0.0, 255.0) as u8;
+            *c = nc.round().clamp(0.0, 255.0) as u8;
+        }
+        (y_lut, c_lut)
+    }
+}
+
+/// Remap a YUV420P VideoFrame between full and limited range, in place and
+/// stride-aware. One table lookup per byte.
+pub fn remap_range(yuv: &mut VideoFrame, dir: RangeRemap) {
+    let (w, h) = (yuv.width() as usize, yuv.height() as usize);
+    let (y_lut, c_lut) = dir.luts();
+    for (plane, pw, ph, lut) in [
+        (0, w, h, &y_lut),
+        (1, w / 2, h / 2, &c_lut),
+        (2, w / 2, h / 2, &c_lut),
+    ] {
+        let stride = yuv.stride(plane);
+        let data = yuv.data_mut(plane);
+        for row in 0..ph {
+            for px in &mut data[row * stride..row * stride + pw] {
+                *px = lut[*px as usize];
+            }
+        }
+    }
+}
+
+// ── Deinterlace ───────────────────────────────────────────────────────────────
+
+/// Linear-blend deinterlace of one plane: every row becomes
+/// `(above + 2·row + below) / 4`, edges repeating their neighbor. Blending
+/// the two fields removes combing at the cost of a little vertical detail.
+/// `row_bytes` is the visible width in bytes; `wide` = 16-bit LE samples.
+pub fn blend_deinterlace_plane(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    row_bytes: usize,
+    h: usize,
+    wide: bool,
+) {
+    for y in 0..h {
+        let row = |r: usize| &src[r * src_stride..r * src_stride + row_bytes];
+        let (up, mid, down) = (row(y.saturating_sub(1)), row(y), row((y + 1).min(h - 1)));
+        let out = &mut dst[y * dst_stride..y * dst_stride + row_bytes];
+        if wide {
+            for i in (0..row_bytes - row_bytes % 2).step_by(2) {
+                let s = |r: &[u8]| u16::from_le_bytes([r[i], r[i + 1]]) as u32;
+                let v = ((s(up) + 2 * s(mid) + s(down) + 2) / 4) as u16;
+                out[i..i + 2].copy_from_slice(&v.to_le_bytes());
+            }
+        } else {
+            for i in 0..row_bytes {
+                out[i] = ((up[i] as u16 + 2 * mid[i] as u16 + down[i] as u16 + 2) / 4) as u8;
+            }
+        }
+    }
+}
+
+/// Blend-deinterlace every plane of a decoded software frame into `dst`,
+/// which must have the same format and size. Works on any planar or packed
+/// layout since the blend is purely vertical; samples deeper than 8 bits are
+/// treated as 16-bit little-endian.
+pub fn deinterlace_frame(src: &VideoFrame, dst: &mut VideoFrame) {
+    let fmt = src.format();
+    let wide = is_high_bit_depth(fmt);
+    for plane in 0..src.planes() {
+        // SAFETY: plain lookup in FFmpeg's pixel format tables.
+        let row_bytes =
+            unsafe { ffi::av_image_get_linesize(fmt.into(), src.width() as i32, plane as i32) };
+        if row_bytes <= 0 {
+            continue;
+        }
+        let h = src.plane_height(plane) as usize;
+        let (src_stride, dst_stride) = (src.stride(plane), dst.stride(plane));
+        blend_deinterlace_plane(
+            src.data(plane),
+            src_stride,
+            dst.data_mut(plane),
+            dst_stride,
+            row_bytes as usize,
+            h,
+            wide,
+        );
+    }
+}
+
+// ── High-bit-depth → 8-bit dither ─────────────────────────────────────────────
+
+/// 8×8 Bayer matrix, thresholds 0..64.
+const BAYER_8X8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// One 8-bit step in the 16-bit scale swscale writes (`x8 * 257 = x16`).
+const STEP_16: i64 = 65_535;
+
+/// True when `fmt` stores more than 8 bits per component (yuv420p10le,
+/// p010le, …) — the sources worth dithering on the way down to 8 bits.
+pub fn is_high_bit_depth(fmt: Pixel) -> bool {
+    // SAFETY: av_pix_fmt_desc_get returns a pointer into FFmpeg's static
+    // descriptor table, or null for an unknown format.
+    unsafe {
+        let desc = ffi::av_pix_fmt_desc_get(fmt.into());
+        !desc.is_null() && (*desc).comp[0].depth > 8
+    }
+}
+
+/// Quantize one plane of little-endian 16-bit samples to 8 bits.
+///
+/// Exact 8-bit levels (`x * 257`) come through unchanged in every mode; only
+/// the in-between values the deeper source carries are spread by `mode`.
+pub fn dither_plane_16_to_8(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    w: usize,
+    h: usize,
+    mode: DitherMode,
+) {
+    let sample = |x: usize, y: usize| {
+        let i = y * src_stride + x * 2;
+        u16::from_le_bytes([src[i], src[i + 1]]) as i64 * 255
+    };
+    match mode {
+        DitherMode::Off => {
+            for y in 0..h {
+                for x in 0..w {
+                    dst[y * dst_stride + x] = ((sample(x, y) + STEP_16 / 2) / STEP_16) as u8;
+                }
+            }
+        }
+        DitherMode::Ordered => {
+            for y in 0..h {
+                let row = &BAYER_8X8[y % 8];
+                for x in 0..w {
+                    // Threshold in (0, 1) of one 8-bit step.
+                    let t = (row[x % 8] as i64 * 2 + 1) * STEP_16 / 128;
+                    dst[y * dst_stride + x] = ((sample(x, y) + t) / STEP_16).min(255) as u8;
+                }
+            }
+        }
+        DitherMode::ErrorDiffusion => {
+            // Floyd–Steinberg, errors kept in 1/16ths: 7 right, 3 / 5 / 1
+            // below-left / below / below-right. `next` has a guard column at
+            // each end so edge pixels need no bounds checks.
+            let mut cur = vec![0i64; w + 2];
+            let mut next = vec![0i64; w + 2];
+            for y in 0..h {
+                for x in 0..w {
+                    let v = sample(x, y) + cur[x + 1] / 16;
+                    let q = ((v + STEP_16 / 2) / STEP_16).clamp(0, 255);
+                    dst[y * dst_stride + x] = q as u8;
+                    let err = v - q * STEP_16;
+                    cur[x + 2] += err * 7;
+                    next[x] += err * 3;
+                    next[x + 1] += err * 5;
+                    next[x + 2] += err;
+                }
+                std::mem::swap(&mut cur, &mut next);
+                next.iter_mut().for_each(|e| *e = 0);
+            }
+        }
+    }
+}
+
+/// Quantize a YUV420P16LE frame into a same-size YUV420P frame with `mode`.
+pub fn dither_frame_16_to_8(src: &VideoFrame, dst: &mut VideoFrame, mode: DitherMode) {
+    let (w, h) = (dst.width() as usize, dst.height() as usize);
+    for (plane, pw, ph) in [(0, w, h), (1, w / 2, h / 2), (2, w / 2, h / 2)] {
+        let src_stride = src.stride(plane);
+        let dst_stride = dst.stride(plane);
+        dither_plane_16_to_8(
+            src.data(plane),
+            src_stride,
+            dst.data_mut(plane),
+            dst_stride,
+            pw,
+            ph,
+            mode,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Packs 16-bit samples into the little-endian byte layout of a plane.
+    fn plane_16(samples: &[u16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn dither_keeps_exact_8bit_levels() {
+        let levels: Vec<u16> = (0..=255u16).map(|x| x * 257).collect();
+        let src = plane_16(&levels);
+        for mode in [
+            DitherMode::Off,
+            DitherMode::Ordered,
+            DitherMode::ErrorDiffusion,
+        ] {
+            let mut dst = vec![0u8; 256];
+            dither_plane_16_to_8(&src, 512, &mut dst, 256, 256, 1, mode);
+            let expected: Vec<u8> = (0..=255u8).collect();
+            assert_eq!(dst, expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn dither_spreads_in_between_levels_and_keeps_the_mean() {
+        // Halfway between 8-bit levels 100 and 101.
+        let (w, h) = (16, 16);
+        let v = 100 * 257 + 128;
+        let src = plane_16(&vec![v; w * h]);
+        for mode in [DitherMode::Ordered, DitherMode::ErrorDiffusion] {
+            let mut dst = vec![0u8; w * h];
+            dither_plane_16_to_8(&src, w * 2, &mut dst, w, w, h, mode);
+            assert!(dst.iter().all(|&q| q == 100 || q == 101), "{mode:?}");
+            let mean = dst.iter().map(|&q| q as f64).sum::<f64>() / (w * h) as f64;
+            assert!((mean - 100.5).abs() < 0.05, "{mode:?} mean {mean}");
+        }
+        let mut dst = vec![0u8; w * h];
+        dither_plane_16_to_8(&src, w * 2, &mut dst, w, w, h, DitherMode::Off);
+        assert!(dst.iter().all(|&q| q == dst[0]));
+    }
+
+    #[test]
+    fn solid_yuv_maps_black_white_and_red_to_limited_range() {
+        let (w, h) = (4, 2);
+        let black = solid_yuv([0, 0, 0], w, h);
+        assert_eq!(black.len(), w * h + 2 * (w / 2) * (h / 2));
+        assert!(black[..w * h].iter().all(|&y| y == 16));
+        assert!(black[w * h..].iter().all(|&c| c == 128));
+
+        let white = solid_yuv([255, 255, 255], w, h);
+        assert!(white[..w * h].iter().all(|&y| y == 235));
+        assert!(white[w * h..].iter().all(|&c| c == 128));
+
+        // Red: low luma, blue-difference below neutral, red-difference maxed.
+        let red = solid_yuv([255, 0, 0], w, h);
+        assert_eq!((red[0], red[w * h], red[w * h + 2]), (81, 90, 240));
+    }
+
+    #[test]
+    fn blend_deinterlace_removes_combing() {
+        // Alternating field rows, 0 and 200: fully combed.
+        let (w, h) = (4, 6);
+        let src: Vec<u8> = (0..h)
+            .flat_map(|y| vec![if y % 2 == 0 { 0 } else { 200 }; w])
+            .collect();
+        let mut dst = vec![0u8; w * h];
+        blend_deinterlace_plane(&src, w, &mut dst, w, w, h, false);
+        // Inner rows settle at the field average; edges repeat a neighbor.
+        for y in 1..h - 1 {
+            assert!(dst[y * w..(y + 1) * w].iter().all(|&v| v == 100), "row {y}");
+        }
+        assert_eq!(dst[0], 50);
+        assert_eq!(dst[(h - 1) * w], 150);
+
+        // Flat 16-bit plane passes through unchanged.
+        let src = plane_16(&vec![40_000; w * h]);
+        let mut dst = vec![0u8; w * h * 2];
+        blend_deinterlace_plane(&src, w * 2, &mut dst, w * 2, w * 2, h, true);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    fn range_luts_hit_nominal_endpoints() {
+        let (y, c) = RangeRemap::FullToLimited.luts();
+        assert_eq!((y[0], y[255]), (16, 235));
+        assert_eq!((c[0], c[128], c[255]), (16, 128, 240));
+
+        let (y, c) = RangeRemap::LimitedToFull.luts();
+        assert_eq!((y[16], y[235], y[0], y[255]), (0, 255, 0, 255));
+        assert_eq!((c[16], c[128], c[240]), (0, 128, 255));
+    }
+
+    #[test]
+    fn after_swscale_only_remaps_on_mismatch() {
+        assert_eq!(RangeRemap::after_swscale(Pixel::YUV420P, false), None);
+        assert_eq!(
+            RangeRemap::after_swscale(Pixel::YUV420P, true),
+            Some(RangeRemap::FullToLimited)
+        );
+        assert_eq!(RangeRemap::after_swscale(Pixel::YUVJ420P, true), None);
+        assert_eq!(
+            RangeRemap::after_swscale(Pixel::YUVJ420P, false),
+            Some(RangeRemap::LimitedToFull)
+        );
+    }
+}
@@ -2,9 +2,15 @@
 
 pub mod audio;
 pub mod decode;
+pub mod duck;
 pub mod encode;
 mod helpers;
 pub mod probe;
+pub mod scenes;
+pub mod silence;
+pub mod stabilize;
+pub mod storyboard;
+pub mod sync;
 pub mod waveform;
 pub mod worker; // internal — not pub, not re-exported
 
@@ -1,6 +1,7 @@
 // crates/velocut-media/src/probe.rs
 //
-// In-process FFmpeg probing: duration, video dimensions, thumbnail extraction.
+// In-process FFmpeg probing: duration, video dimensions, color range,
+// thumbnail extraction, audio stream enumeration.
 
 use crossbeam_channel::Sender;
 use std::path::PathBuf;
@@ -12,11 +13,88 @@ use ffmpeg::media::Type;
 use ffmpeg::software::scaling::{context::Context as SwsContext, flag::Flags};
 use ffmpeg_the_third as ffmpeg;
 
-use velocut_core::media_types::MediaResult;
+use velocut_core::media_types::{AudioStreamInfo, ColorRange, MediaResult};
+
+/// Resolve which audio stream to decode: `preferred` when it names an audio
+/// stream in `ictx`, otherwise FFmpeg's "best" audio stream. A stale index
+/// (file replaced, stream removed) silently falls back rather than failing.
+pub fn select_audio_stream(
+    ictx: &ffmpeg::format::context::Input,
+    preferred: Option<usize>,
+) -> Option<usize> {
+    preferred
+        .filter(|&i| {
+            ictx.stream(i)
+                .map(|s| s.parameters().medium() == Type::Audio)
+                .unwrap_or(false)
+        })
+        .or_else(|| ictx.streams().best(Type::Audio).map(|s| s.index()))
+}
+
+/// FFmpeg's best video stream, unless it is an attached picture — the cover
+/// art MP3 / M4A files carry is one still, not the source's video.
+pub fn select_video_stream(ictx: &ffmpeg::format::context::Input) -> Option<usize> {
+    ictx.streams()
+        .best(Type::Video)
+        .filter(|s| {
+            !s.disposition()
+                .contains(ffmpeg::format::stream::Disposition::ATTACHED_PIC)
+        })
+        .map(|s| s.index())
+}
+
+/// Which kinds of stream `path` holds: `(video, audio)`, cover art not
+/// counting as video. Header read only. `None` when the file won't open.
+pub fn probe_stream_kinds(path: &PathBuf) -> Option<(bool, bool)> {
+    let ictx = input(path).ok()?;
+    let audio = ictx.streams().best(Type::Audio).is_some();
+    Some((select_video_stream(&ictx).is_some(), audio))
+}
+
+/// List every audio stream in `path` with its channel count and `language` /
+/// `title` tags, and send them as `MediaResult::AudioStreams`. Cheap — reads
+/// container headers only, no packets are decoded.
+pub fn probe_audio_streams(path: &PathBuf, id: Uuid, tx: &Sender<MediaResult>) {
+    let Ok(ictx) = input(path) else { return };
+
+    let streams: Vec<AudioStreamInfo> = ictx
+        .streams()
+        .filter(|s| s.parameters().medium() == Type::Audio)
+        .map(|s| {
+            let meta = s.metadata();
+            let tag = |k: &str| meta.get(k).map(str::to_string).filter(|v| !v.is_empty());
+            let channels =
+                unsafe { (*s.parameters().as_ptr()).ch_layout.nb_channels.max(0) as u32 };
+            AudioStreamInfo {
+                index: s.index(),
+                channels,
+                language: tag("language"),
+                title: tag("title"),
+            }
+        })
+        .collect();
+
+    if streams.len() > 1 {
+        crate::media_log!(
+            "[media] {} audio streams ← {}",
+            streams.len(),
+            path.display()
+        );
+    }
+    let _ = tx.send(MediaResult::AudioStreams { id, streams });
+}
 
 pub fn probe_duration(path: &PathBuf, id: Uuid, tx: &Sender<MediaResult>) -> f64 {
     match input(path) {
         Ok(ctx) => {
+            if let Ok(meta) = std::fs::metadata(path) {
+                let br = ctx.bit_rate();
+                let _ = tx.send(MediaResult::FileStats {
+                    id,
+                    bytes: meta.len(),
+                    bit_rate: (br > 0).then_some(br as u64),
+                });
+            }
             let dur = ctx.duration() as f64 / ffmpeg::ffi::AV_TIME_BASE as f64;
             if dur > 0.0 {
                 crate::media_log!("[media] duration {dur:.2}s ← {}", path.display());
@@ -24,10 +102,9 @@ pub fn probe_duration(path: &PathBuf, id: Uuid, tx: &Sender<MediaResult>) -> f64
                 return dur;
             }
             // Fall back to stream duration
-            if let Some(stream) = ctx
-                .streams()
-                .best(Type::Video)
-                .or_else(|| ctx.streams().best(Type::Audio))
+            if let Some(stream) = select_video_stream(&ctx)
+                .or_else(|| select_audio_stream(&ctx, None))
+                .and_then(|i| ctx.stream(i))
             {
                 let tb = stream.time_base();
                 let d = stream.duration() as f64 * tb.numerator() as f64 / tb.denominator() as f64;
@@ -65,26 +142,97 @@ pub fn probe_video_size_and_thumbnail(
     id: Uuid,
     duration: f64,
     tx: &Sender<MediaResult>,
+) {
+    // 10% in (at least 1 s) skips black openings; short clips use frame 0.
+    let at = if duration > 2.0 {
+        (duration * 0.1).max(1.0)
+    } else {
+        0.0
+    };
+    decode_thumbnail(path, id, at, false, THUMB_W, tx);
+}
+
+/// Decode the frame at `at` seconds as a thumbnail for a timeline clip's
+/// poster frame. `id` is the timeline clip id, so only `Thumbnail` is sent —
+/// size and color range belong to the library entry and were probed at import.
+/// Unlike the import thumbnail this decodes forward from the keyframe to the
+/// exact frame, since the user picked it.
+pub fn probe_poster_thumbnail(path: &PathBuf, id: Uuid, at: f64, tx: &Sender<MediaResult>) {
+    decode_thumbnail(path, id, at, true, THUMB_W, tx);
+}
+
+/// Decode the exact frame at `at` seconds for the source viewer, `width`
+/// pixels wide (never upscaled past the source). Sent as a `Thumbnail` keyed
+/// by `id`, like a poster frame.
+pub fn probe_source_frame(path: &PathBuf, id: Uuid, at: f64, width: u32, tx: &Sender<MediaResult>) {
+    decode_thumbnail(path, id, at, true, width, tx);
+}
+
+/// Library-card and poster thumbnail width; height follows the source aspect.
+const THUMB_W: u32 = 160;
+
+fn decode_thumbnail(
+    path: &PathBuf,
+    id: Uuid,
+    at: f64,
+    poster: bool,
+    width: u32,
+    tx: &Sender<MediaResult>,
 ) {
     let Ok(mut ictx) = input(path) else { return };
 
-    let video_stream_idx = match ictx.streams().best(Type::Video) {
-        Some(s) => s.index(),
-        None => return, // audio-only file
+    let video_stream_idx = match select_video_stream(&ictx) {
+        Some(i) => i,
+        None => {
+            // Audio-only file in a video container: no size will ever come,
+            // so say so rather than leave the source looking half-probed.
+            if !poster {
+                let _ = tx.send(MediaResult::Error {
+                    id,
+                    msg: "no video stream".into(),
+                });
+            }
+            return;
+        }
     };
 
     // Extract everything we need from the stream borrow in one block,
     // then release the borrow so ictx is free for seeking and packet iteration.
-    let (raw_w, raw_h, seek_ts, dec_ctx) = {
+    let (raw_w, raw_h, range, interlaced, fps, seek_ts, dec_ctx) = {
         let stream = ictx.stream(video_stream_idx).unwrap();
-        let (w, h) = unsafe {
+        // Average rate first; VFR phone footage often has a meaningless
+        // r_frame_rate (e.g. 90000/1) but a sane average.
+        let fps = [stream.avg_frame_rate(), stream.rate()]
+            .into_iter()
+            .filter(|r| r.numerator() > 0 && r.denominator() > 0)
+            .map(|r| r.numerator() as f64 / r.denominator() as f64)
+            .find(|f| (1.0..=240.0).contains(f));
+        let (w, h, range, interlaced) = unsafe {
             let p = stream.parameters().as_ptr();
-            ((*p).width as u32, (*p).height as u32)
+            // The deprecated yuvj* formats imply full range even when the
+            // stream leaves color_range unspecified (MJPEG, many phones).
+            let yuvj = [
+                ffi::AVPixelFormat::AV_PIX_FMT_YUVJ420P,
+                ffi::AVPixelFormat::AV_PIX_FMT_YUVJ422P,
+                ffi::AVPixelFormat::AV_PIX_FMT_YUVJ444P,
+            ]
+            .iter()
+            .any(|&f| f as i32 == (*p).format);
+            let range = if yuvj || (*p).color_range == ffi::AVColorRange::AVCOL_RANGE_JPEG {
+                ColorRange::Full
+            } else {
+                ColorRange::Limited
+            };
+            // Any field order but progressive / unknown means fields to blend.
+            let interlaced = !matches!(
+                (*p).field_order,
+                ffi::AVFieldOrder::AV_FIELD_PROGRESSIVE | ffi::AVFieldOrder::AV_FIELD_UNKNOWN
+            );
+            ((*p).width as u32, (*p).height as u32, range, interlaced)
         };
-        let ts = if duration > 2.0 {
-            let t = (duration * 0.1).max(1.0);
+        let ts = if at > 0.0 {
             let tb = stream.time_base();
-            (t * tb.denominator() as f64 / tb.numerator() as f64) as i64
+            (at * tb.denominator() as f64 / tb.numerator() as f64) as i64
         } else {
             0i64
         };
@@ -97,16 +245,23 @@ pub fn probe_video_size_and_thumbnail(
                 return;
             }
         };
-        (w, h, ts, dec_ctx)
+        (w, h, range, interlaced, fps, ts, dec_ctx)
     };
 
-    if raw_w > 0 && raw_h > 0 {
-        crate::media_log!("[media] video size {raw_w}x{raw_h} ← {}", path.display());
-        let _ = tx.send(MediaResult::VideoSize {
-            id,
-            width: raw_w,
-            height: raw_h,
-        });
+    if !poster {
+        if raw_w > 0 && raw_h > 0 {
+            crate::media_log!("[media] video size {raw_w}x{raw_h} ← {}", path.display());
+            let _ = tx.send(MediaResult::VideoSize {
+                id,
+                width: raw_w,
+                height: raw_h,
+            });
+        }
+        let _ = tx.send(MediaResult::VideoColorRange { id, range });
+        let _ = tx.send(MediaResult::VideoInterlaced { id, interlaced });
+        if let Some(fps) = fps {
+            let _ = tx.send(MediaResult::VideoFrameRate { id, fps });
+        }
     }
 
     // [Fix] Discard non-video streams so the demuxer doesn't buffer audio packets
@@ -125,8 +280,8 @@ pub fn probe_video_size_and_thumbnail(
     // Decoder built from the parameters copied above — no second file open needed.
     let mut decoder = dec_ctx.decoder().video().unwrap();
 
-    // Thumbnail output: 160 wide, proportional height
-    let thumb_w: u32 = 160;
+    // Thumbnail output: `width` wide (at most the source), proportional height
+    let thumb_w: u32 = if raw_w > 0 { width.min(raw_w) } else { width };
     let thumb_h: u32 = ((thumb_w as f64 * raw_h as f64 / raw_w.max(1) as f64) as u32).max(2) & !1; // must be even
 
     // Build the scaler lazily on the first decoded frame using the frame's actual
@@ -151,6 +306,10 @@ pub fn probe_video_size_and_thumbnail(
         }
         let mut decoded = ffmpeg::util::frame::video::Video::empty();
         while decoder.receive_frame(&mut decoded).is_ok() {
+            // Poster: roll forward from the keyframe to the requested frame.
+            if poster && decoded.pts().is_some_and(|pts| pts < seek_ts) {
+                continue;
+            }
             let sc = match scaler {
                 Some(ref mut s) => s,
                 None => match SwsContext::get(
@@ -0,0 +1,251 @@
+// crates/velocut-media/src/scenes.rs
+//
+// Scene-cut detection: decode a source range at a tiny grayscale size and
+// flag frames whose luma histogram differs sharply from the frame before.
+// Histograms ignore motion inside a shot (same light, same content moved) but
+// jump on a hard cut, which is all a "split at shots" pass needs.
+
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
+
+use ffmpeg::ffi;
+use ffmpeg::format::{input, Pixel};
+use ffmpeg::media::Type;
+use ffmpeg::software::scaling::{context::Context as SwsContext, flag::Flags};
+use ffmpeg_the_third as ffmpeg;
+
+use velocut_core::media_types::MediaResult;
+
+/// Analysis frame size. Histograms don't need detail; 64×36 keeps the scale
+/// and the per-frame work negligible next to the decode itself.
+const ANALYSIS_W: u32 = 64;
+const ANALYSIS_H: u32 = 36;
+
+const HIST_BINS: usize = 32;
+
+/// Shortest shot a cut may create. Flashes and strobes produce several
+/// above-threshold frames in a row; only the first becomes a cut.
+const MIN_SHOT_SECS: f64 = 0.5;
+
+/// Detect hard cuts in `path` between source times `from` and `to` and send
+/// `MediaResult::ScenesDetected` with the cut timestamps (source seconds,
+/// ascending). `threshold` is the histogram distance (0–1) a frame must jump
+/// by to count as a cut — lower finds more cuts. A failed decode reports no
+/// cuts so the UI never waits forever.
+pub fn detect_scenes(
+    path: &PathBuf,
+    id: Uuid,
+    from: f64,
+    to: f64,
+    threshold: f32,
+    tx: &Sender<MediaResult>,
+    shutdown: &AtomicBool,
+) {
+    let cuts = match scan(path, from, to, threshold, shutdown) {
+        Ok(cuts) => cuts,
+        Err(e) => {
+            crate::media_log!("[scenes] {}: {e}", path.display());
+            Vec::new()
+        }
+    };
+    crate::media_log!(
+        "[scenes] {} cut(s) in {:.2}–{:.2}s ← {}",
+        cuts.len(),
+        from,
+        to,
+        path.display()
+    );
+    let _ = tx.send(MediaResult::ScenesDetected { id, cuts });
+}
+
+fn scan(
+    path: &PathBuf,
+    from: f64,
+    to: f64,
+    threshold: f32,
+    shutdown: &AtomicBool,
+) -> anyhow::Result<Vec<f64>> {
+    let mut ictx = input(path)?;
+
+    let (video_stream_idx, tb, dec_ctx) = {
+        let stream = ictx
+            .streams()
+            .best(Type::Video)
+            .ok_or_else(|| anyhow::anyhow!("no video stream"))?;
+        let tb = stream.time_base();
+        let dec_ctx = ffmpeg::codec::context::Context::from_parameters(stream.parameters())?;
+        (
+            stream.index(),
+            tb.numerator() as f64 / tb.denominator() as f64,
+            dec_ctx,
+        )
+    };
+
+    for mut stream in ictx.streams_mut() {
+        if stream.index() != video_stream_idx {
+            unsafe {
+                (*stream.as_mut_ptr()).discard = ffi::AVDiscard::AVDISCARD_ALL;
+            }
+        }
+    }
+
+    // Same ts=0 guard as decode_frame — seeking to 0 fails on Windows.
+    let seek_ts = (from / tb) as i64;
+    if seek_ts > 0 {
+        let _ = ictx.seek(seek_ts, ..=seek_ts);
+    }
+    let mut decoder = dec_ctx.decoder().video()?;
+
+    // Lazily built from the first decoded frame (see probe.rs for why).
+    let mut scaler: Option<SwsContext> = None;
+    let mut picker = CutPicker::new(threshold, MIN_SHOT_SECS);
+
+    'outer: for (stream, packet) in ictx.packets().flatten() {
+        if stream.index() != video_stream_idx {
+            continue;
+        }
+        if shutdown.load(Ordering::Acquire) {
+            break;
+        }
+        if decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        let mut decoded = ffmpeg::util::frame::video::Video::empty();
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            let Some(pts) = decoded.pts() else { continue };
+            let t = pts as f64 * tb;
+            if t < from {
+                continue;
+            }
+            if t > to {
+                break 'outer;
+            }
+            let sc = match scaler {
+                Some(ref mut s) => s,
+                None => {
+                    scaler = Some(SwsContext::get(
+                        decoded.format(),
+                        decoded.width(),
+                        decoded.height(),
+                        Pixel::GRAY8,
+                        ANALYSIS_W,
+                        ANALYSIS_H,
+                        Flags::FAST_BILINEAR,
+                    )?);
+                    scaler.as_mut().unwrap()
+                }
+            };
+            let mut gray = ffmpeg::util::frame::video::Video::empty();
+            if sc.run(&decoded, &mut gray).is_err() {
+                continue;
+            }
+            let stride = gray.stride(0);
+            let raw = gray.data(0);
+            let pixels = (0..ANALYSIS_H as usize)
+                .flat_map(|row| &raw[row * stride..row * stride + ANALYSIS_W as usize]);
+            picker.push(t, luma_histogram(pixels));
+        }
+    }
+
+    Ok(picker.cuts)
+}
+
+/// Normalized luma histogram (bins sum to 1).
+fn luma_histogram<'a>(pixels: impl Iterator<Item = &'a u8>) -> [f32; HIST_BINS] {
+    let mut hist = [0f32; HIST_BINS];
+    let mut n = 0usize;
+    for &p in pixels {
+        hist[p as usize * HIST_BINS / 256] += 1.0;
+        n += 1;
+    }
+    if n > 0 {
+        for b in &mut hist {
+            *b /= n as f32;
+        }
+    }
+    hist
+}
+
+/// Half the L1 distance between two normalized histograms: 0 = identical,
+/// 1 = no overlap at all.
+fn histogram_distance(a: &[f32; HIST_BINS], b: &[f32; HIST_BINS]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum::<f32>() * 0.5
+}
+
+/// Turns a stream of (timestamp, histogram) into cut times.
+struct CutPicker {
+    threshold: f32,
+    min_gap: f64,
+    prev: Option<[f32; HIST_BINS]>,
+    /// Start of the current shot — the first frame, then each cut.
+    shot_start: Option<f64>,
+    cuts: Vec<f64>,
+}
+
+impl CutPicker {
+    fn new(threshold: f32, min_gap: f64) -> Self {
+        Self {
+            threshold,
+            min_gap,
+            prev: None,
+            shot_start: None,
+            cuts: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, t: f64, hist: [f32; HIST_BINS]) {
+        let shot_start = *self.shot_start.get_or_insert(t);
+        if let Some(prev) = &self.prev {
+            if histogram_distance(prev, &hist) >= self.threshold && t - shot_start >= self.min_gap {
+                self.cuts.push(t);
+                self.shot_start = Some(t);
+            }
+        }
+        self.prev = Some(hist);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(value: u8) -> [f32; HIST_BINS] {
+        luma_histogram([value; 16].iter())
+    }
+
+    #[test]
+    fn histogram_distance_bounds() {
+        assert_eq!(histogram_distance(&flat(10), &flat(10)), 0.0);
+        assert!((histogram_distance(&flat(10), &flat(240)) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn picker_cuts_on_jump_only() {
+        let mut p = CutPicker::new(0.4, 0.5);
+        p.push(0.0, flat(20));
+        p.push(1.0, flat(20));
+        p.push(2.0, flat(200)); // cut
+        p.push(3.0, flat(200));
+        assert_eq!(p.cuts, vec![2.0]);
+    }
+
+    #[test]
+    fn picker_enforces_min_shot_length() {
+        let mut p = CutPicker::new(0.4, 0.5);
+        p.push(0.0, flat(20));
+        p.push(1.0, flat(200)); // cut
+        p.push(1.2, flat(20)); // strobe back — too soon
+        p.push(2.0, flat(200)); // far enough from 1.0
+        assert_eq!(p.cuts, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn picker_ignores_a_jump_inside_the_first_gap() {
+        let mut p = CutPicker::new(0.4, 0.5);
+        p.push(0.0, flat(20));
+        p.push(0.2, flat(200));
+        assert!(p.cuts.is_empty());
+    }
+}
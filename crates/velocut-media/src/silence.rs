@@ -0,0 +1,206 @@
+// crates/velocut-media/src/silence.rs
+//
+// Silence detection for spoken-word trimming: decode a clip's audio over a
+// source range, measure loudness in short windows and report where sound
+// first starts and last stops. Leading and trailing gaps shorter than the
+// minimum are left alone so a breath before the first word survives.
+
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
+
+use ffmpeg_the_third as ffmpeg;
+
+use velocut_core::media_types::MediaResult;
+
+use crate::probe::select_audio_stream;
+use crate::waveform::append_frame_samples;
+
+/// Loudness window. 10 ms resolves word onsets without reacting to single
+/// sample spikes.
+const WINDOW_SECS: f64 = 0.010;
+
+/// Scan `path` between source times `from` and `to` for audio louder than
+/// `threshold_db` (dBFS) and send `MediaResult::SilenceDetected` with the
+/// sounding range, widened back to `from`/`to` where the silent gap is
+/// shorter than `min_gap` seconds. A failed decode or an all-silent range
+/// reports `None` so the UI never waits forever.
+#[allow(clippy::too_many_arguments)]
+pub fn detect_silence(
+    path: &PathBuf,
+    id: Uuid,
+    audio_stream: Option<usize>,
+    from: f64,
+    to: f64,
+    threshold_db: f32,
+    min_gap: f64,
+    tx: &Sender<MediaResult>,
+    shutdown: &AtomicBool,
+) {
+    let mut span = SoundSpan::new(threshold_db);
+    let scanned = scan_levels(
+        path,
+        audio_stream,
+        from,
+        to,
+        shutdown,
+        |start, end, level| span.push(start, end, level),
+    );
+    let sound = match scanned {
+        Ok(()) => span.range(from, to, min_gap),
+        Err(e) => {
+            crate::media_log!("[silence] {}: {e}", path.display());
+            None
+        }
+    };
+    crate::media_log!(
+        "[silence] sound {:?} in {:.2}–{:.2}s ← {}",
+        sound,
+        from,
+        to,
+        path.display()
+    );
+    let _ = tx.send(MediaResult::SilenceDetected { id, sound });
+}
+
+/// Decode `path`'s audio between source times `from` and `to` and call
+/// `on_window(start, end, peak)` for every `WINDOW_SECS` window, clipped to
+/// the range. Stops early on shutdown.
+pub(crate) fn scan_levels(
+    path: &PathBuf,
+    audio_stream: Option<usize>,
+    from: f64,
+    to: f64,
+    shutdown: &AtomicBool,
+    mut on_window: impl FnMut(f64, f64, f32),
+) -> anyhow::Result<()> {
+    let mut ictx = ffmpeg::format::input(path)?;
+
+    let stream_index = select_audio_stream(&ictx, audio_stream)
+        .ok_or_else(|| anyhow::anyhow!("no audio stream"))?;
+    let (tb, dec_ctx) = {
+        let stream = ictx.stream(stream_index).unwrap();
+        let tb = stream.time_base();
+        (
+            tb.numerator() as f64 / tb.denominator() as f64,
+            ffmpeg::codec::context::Context::from_parameters(stream.parameters())?,
+        )
+    };
+
+    // Same ts=0 guard as decode_frame — seeking to 0 fails on Windows.
+    let seek_ts = (from / tb) as i64;
+    if seek_ts > 0 {
+        let _ = ictx.seek(seek_ts, ..=seek_ts);
+    }
+    let mut decoder = dec_ctx.decoder().audio()?;
+
+    let mut samples: Vec<f32> = Vec::new();
+
+    'outer: for (stream, packet) in ictx.packets().flatten() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        if shutdown.load(Ordering::Acquire) {
+            break;
+        }
+        if decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        let mut frame = ffmpeg::frame::Audio::empty();
+        while decoder.receive_frame(&mut frame).is_ok() {
+            let (Some(pts), rate) = (frame.pts(), frame.rate()) else {
+                continue;
+            };
+            if rate == 0 {
+                continue;
+            }
+            let t0 = pts as f64 * tb;
+            if t0 > to {
+                break 'outer;
+            }
+            samples.clear();
+            append_frame_samples(&frame, &mut samples);
+            let window = ((WINDOW_SECS * rate as f64) as usize).max(1);
+            for (i, chunk) in samples.chunks(window).enumerate() {
+                let start = t0 + (i * window) as f64 / rate as f64;
+                let end = start + chunk.len() as f64 / rate as f64;
+                if end <= from || start >= to {
+                    continue;
+                }
+                on_window(start.max(from), end.min(to), peak(chunk));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn peak(chunk: &[f32]) -> f32 {
+    chunk.iter().fold(0.0f32, |m, s| m.max(s.abs()))
+}
+
+/// First and last loud window seen, in source seconds.
+struct SoundSpan {
+    threshold: f32,
+    first: Option<f64>,
+    last: Option<f64>,
+}
+
+impl SoundSpan {
+    fn new(threshold_db: f32) -> Self {
+        Self {
+            threshold: 10f32.powf(threshold_db / 20.0),
+            first: None,
+            last: None,
+        }
+    }
+
+    fn push(&mut self, start: f64, end: f64, level: f32) {
+        if level >= self.threshold {
+            self.first.get_or_insert(start);
+            self.last = Some(end);
+        }
+    }
+
+    /// Sounding range within `from..to`. A leading or trailing silence
+    /// shorter than `min_gap` is kept. `None` when nothing was loud enough.
+    fn range(&self, from: f64, to: f64, min_gap: f64) -> Option<(f64, f64)> {
+        let (first, last) = (self.first?, self.last?);
+        let start = if first - from >= min_gap { first } else { from };
+        let end = if to - last >= min_gap { last } else { to };
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_windows_are_ignored() {
+        let mut s = SoundSpan::new(-40.0);
+        s.push(0.0, 0.01, 0.001); // −60 dBFS
+        assert_eq!(s.range(0.0, 1.0, 0.1), None);
+    }
+
+    #[test]
+    fn range_covers_first_to_last_loud_window() {
+        let mut s = SoundSpan::new(-40.0);
+        s.push(0.00, 0.50, 0.001);
+        s.push(0.50, 0.51, 0.5);
+        s.push(0.51, 2.00, 0.001);
+        s.push(2.00, 2.01, 0.5);
+        s.push(2.01, 3.00, 0.001);
+        assert_eq!(s.range(0.0, 3.0, 0.25), Some((0.5, 2.01)));
+    }
+
+    #[test]
+    fn short_gaps_are_kept() {
+        let mut s = SoundSpan::new(-40.0);
+        s.push(0.1, 0.2, 0.5);
+        s.push(2.8, 2.9, 0.5);
+        // 0.1 s of lead silence is under the gap; 0.1 s of tail too.
+        assert_eq!(s.range(0.0, 3.0, 0.25), Some((0.0, 3.0)));
+    }
+}
@@ -0,0 +1,374 @@
+// crates/velocut-media/src/stabilize.rs
+//
+// Two-pass clip stabilization. Pass one (`camera_path`) decodes the clip's
+// source range at a small grayscale size and measures the global shift
+// between consecutive frames; the running sum is the path the picture takes.
+// Pass two (`Stabilizer`) smooths that path and, for every exported frame,
+// moves the encoder's crop window by the difference, so the picture follows
+// the smooth path instead of the shaky one. The crop is zoomed in by
+// `Stabilizer::margin` to leave the window room to move.
+//
+// Analysis is the slow half (a full decode of the range), so paths are cached
+// for the process lifetime, keyed by source file and range. Re-renders and
+// strength changes reuse them; only a new trim analyzes again.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use ffmpeg::ffi;
+use ffmpeg::format::{input, Pixel};
+use ffmpeg::media::Type;
+use ffmpeg::software::scaling::{context::Context as SwsContext, flag::Flags};
+use ffmpeg_the_third as ffmpeg;
+
+/// Analysis frame size. Enough detail to match textures between frames; the
+/// measured shift is sub-pixel, so 160 px wide still resolves ~2 px at 1080p.
+const ANALYSIS_W: usize = 160;
+const ANALYSIS_H: usize = 90;
+
+/// Largest shift searched between two frames, in analysis pixels (5 % of the
+/// width). Faster motion is a pan, not shake, and is clamped.
+const MAX_SHIFT: i32 = 8;
+
+/// Crop margin per edge at the lowest and highest strength.
+const MARGIN_MIN: f32 = 0.03;
+const MARGIN_MAX: f32 = 0.10;
+
+/// Half-width of the smoothing window at the lowest and highest strength.
+const SMOOTH_MIN_SECS: f64 = 0.25;
+const SMOOTH_MAX_SECS: f64 = 1.5;
+
+/// Source timestamp and cumulative picture offset at that frame, as
+/// fractions of the frame width / height.
+pub type CameraPath = Vec<(f64, [f32; 2])>;
+
+type CacheKey = (PathBuf, u64, u64);
+
+fn cache() -> &'static Mutex<HashMap<CacheKey, Arc<CameraPath>>> {
+    static CACHE: OnceLock<Mutex<HashMap<CacheKey, Arc<CameraPath>>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// The camera path of `path` between source times `from` and `to`, from the
+/// cache or by decoding the range. A cancelled analysis is not cached.
+pub fn camera_path(
+    path: &Path,
+    from: f64,
+    to: f64,
+    cancel: &AtomicBool,
+) -> anyhow::Result<Arc<CameraPath>> {
+    let key = (path.to_path_buf(), from.to_bits(), to.to_bits());
+    if let Some(hit) = cache().lock().unwrap().get(&key) {
+        return Ok(hit.clone());
+    }
+    let measured = Arc::new(analyze(path, from, to, cancel)?);
+    crate::media_log!(
+        "[stabilize] {} frame(s) analyzed in {:.2}–{:.2}s ← {}",
+        measured.len(),
+        from,
+        to,
+        path.display()
+    );
+    cache().lock().unwrap().insert(key, measured.clone());
+    Ok(measured)
+}
+
+/// The cached camera path of `path` over `from..to`, without analyzing.
+/// `None` until `camera_path` has measured that range.
+pub fn cached_camera_path(path: &Path, from: f64, to: f64) -> Option<Arc<CameraPath>> {
+    let key = (path.to_path_buf(), from.to_bits(), to.to_bits());
+    cache().lock().unwrap().get(&key).cloned()
+}
+
+fn analyze(path: &Path, from: f64, to: f64, cancel: &AtomicBool) -> anyhow::Result<CameraPath> {
+    let mut ictx = input(path)?;
+
+    let (video_stream_idx, tb, dec_ctx) = {
+        let stream = ictx
+            .streams()
+            .best(Type::Video)
+            .ok_or_else(|| anyhow::anyhow!("no video stream"))?;
+        let tb = stream.time_base();
+        let dec_ctx = ffmpeg::codec::context::Context::from_parameters(stream.parameters())?;
+        (
+            stream.index(),
+            tb.numerator() as f64 / tb.denominator() as f64,
+            dec_ctx,
+        )
+    };
+
+    for mut stream in ictx.streams_mut() {
+        if stream.index() != video_stream_idx {
+            unsafe {
+                (*stream.as_mut_ptr()).discard = ffi::AVDiscard::AVDISCARD_ALL;
+            }
+        }
+    }
+
+    // Same ts=0 guard as decode_frame — seeking to 0 fails on Windows.
+    let seek_ts = (from / tb) as i64;
+    if seek_ts > 0 {
+        let _ = ictx.seek(seek_ts, ..=seek_ts);
+    }
+    let mut decoder = dec_ctx.decoder().video()?;
+
+    // Lazily built from the first decoded frame (see probe.rs for why).
+    let mut scaler: Option<SwsContext> = None;
+    let mut prev: Option<Vec<u8>> = None;
+    let mut pos = [0f32; 2];
+    let mut out = CameraPath::new();
+
+    'outer: for (stream, packet) in ictx.packets().flatten() {
+        if stream.index() != video_stream_idx {
+            continue;
+        }
+        if cancel.load(Ordering::Acquire) {
+            anyhow::bail!("cancelled");
+        }
+        if decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        let mut decoded = ffmpeg::util::frame::video::Video::empty();
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            let Some(pts) = decoded.pts() else { continue };
+            let t = pts as f64 * tb;
+            if t < from {
+                continue;
+            }
+            if t > to {
+                break 'outer;
+            }
+            let sc = match scaler {
+                Some(ref mut s) => s,
+                None => {
+                    scaler = Some(SwsContext::get(
+                        decoded.format(),
+                        decoded.width(),
+                        decoded.height(),
+                        Pixel::GRAY8,
+                        ANALYSIS_W as u32,
+                        ANALYSIS_H as u32,
+                        Flags::FAST_BILINEAR,
+                    )?);
+                    scaler.as_mut().unwrap()
+                }
+            };
+            let mut gray = ffmpeg::util::frame::video::Video::empty();
+            if sc.run(&decoded, &mut gray).is_err() {
+                continue;
+            }
+            let stride = gray.stride(0);
+            let raw = gray.data(0);
+            let luma: Vec<u8> = (0..ANALYSIS_H)
+                .flat_map(|row| &raw[row * stride..row * stride + ANALYSIS_W])
+                .copied()
+                .collect();
+            if let Some(prev) = &prev {
+                let [dx, dy] = frame_shift(prev, &luma, ANALYSIS_W, ANALYSIS_H);
+                pos[0] += dx / ANALYSIS_W as f32;
+                pos[1] += dy / ANALYSIS_H as f32;
+            }
+            out.push((t, pos));
+            prev = Some(luma);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Shift `d` (analysis pixels, sub-pixel) that best maps `prev` onto `cur`,
+/// i.e. `cur(x, y) ≈ prev(x - dx, y - dy)`. Exhaustive SAD search over
+/// ±MAX_SHIFT on the interior, refined with a parabola through the best
+/// score and its neighbors on each axis.
+fn frame_shift(prev: &[u8], cur: &[u8], w: usize, h: usize) -> [f32; 2] {
+    let m = MAX_SHIFT as usize;
+    let sad = |dx: i32, dy: i32| -> u64 {
+        let mut sum = 0u64;
+        for y in m..h - m {
+            let py = (y as i32 - dy) as usize;
+            let c = &cur[y * w + m..y * w + w - m];
+            let p0 = py * w + (m as i32 - dx) as usize;
+            let p = &prev[p0..p0 + c.len()];
+            sum += c
+                .iter()
+                .zip(p)
+                .map(|(&a, &b)| a.abs_diff(b) as u64)
+                .sum::<u64>();
+        }
+        sum
+    };
+
+    let n = (2 * MAX_SHIFT + 1) as usize;
+    let mut scores = vec![0u64; n * n];
+    let mut best = (0, 0);
+    let mut best_score = u64::MAX;
+    for dy in -MAX_SHIFT..=MAX_SHIFT {
+        for dx in -MAX_SHIFT..=MAX_SHIFT {
+            let s = sad(dx, dy);
+            scores[(dy + MAX_SHIFT) as usize * n + (dx + MAX_SHIFT) as usize] = s;
+            // Ties go to the smaller shift so flat frames read as still.
+            let shorter = dx.abs() + dy.abs() < best.0.abs() + best.1.abs();
+            if s < best_score || (s == best_score && shorter) {
+                best_score = s;
+                best = (dx, dy);
+            }
+        }
+    }
+    let score =
+        |dx: i32, dy: i32| scores[(dy + MAX_SHIFT) as usize * n + (dx + MAX_SHIFT) as usize];
+    let refine = |lo: u64, mid: u64, hi: u64| -> f32 {
+        let denom = lo as f64 - 2.0 * mid as f64 + hi as f64;
+        if denom <= 0.0 {
+            0.0
+        } else {
+            (0.5 * (lo as f64 - hi as f64) / denom).clamp(-0.5, 0.5) as f32
+        }
+    };
+    let (bx, by) = best;
+    let fx = if bx.abs() < MAX_SHIFT {
+        refine(score(bx - 1, by), score(bx, by), score(bx + 1, by))
+    } else {
+        0.0
+    };
+    let fy = if by.abs() < MAX_SHIFT {
+        refine(score(bx, by - 1), score(bx, by), score(bx, by + 1))
+    } else {
+        0.0
+    };
+    [bx as f32 + fx, by as f32 + fy]
+}
+
+/// Per-frame crop offsets for one clip: the measured path minus its moving
+/// average, clamped to the zoom margin.
+pub struct Stabilizer {
+    times: Vec<f64>,
+    offsets: Vec<[f32; 2]>,
+    margin: f32,
+}
+
+impl Stabilizer {
+    /// `strength` (0.1–1.0) sets both the smoothing window and the margin.
+    pub fn new(path: &CameraPath, strength: f32) -> Self {
+        let s = strength.clamp(0.0, 1.0);
+        let margin = MARGIN_MIN + (MARGIN_MAX - MARGIN_MIN) * s;
+        let radius = SMOOTH_MIN_SECS + (SMOOTH_MAX_SECS - SMOOTH_MIN_SECS) * s as f64;
+
+        // Moving average over ±radius seconds with a sliding window sum.
+        let mut offsets = Vec::with_capacity(path.len());
+        let (mut lo, mut hi) = (0usize, 0usize);
+        let mut sum = [0f64; 2];
+        for &(t, p) in path {
+            while hi < path.len() && path[hi].0 <= t + radius {
+                sum[0] += path[hi].1[0] as f64;
+                sum[1] += path[hi].1[1] as f64;
+                hi += 1;
+            }
+            while path[lo].0 < t - radius {
+                sum[0] -= path[lo].1[0] as f64;
+                sum[1] -= path[lo].1[1] as f64;
+                lo += 1;
+            }
+            let n = (hi - lo) as f64;
+            let smooth = [(sum[0] / n) as f32, (sum[1] / n) as f32];
+            offsets.push([
+                (p[0] - smooth[0]).clamp(-margin, margin),
+                (p[1] - smooth[1]).clamp(-margin, margin),
+            ]);
+        }
+        Self {
+            times: path.iter().map(|&(t, _)| t).collect(),
+            offsets,
+            margin,
+        }
+    }
+
+    /// Zoom margin per edge, as a fraction of the frame size.
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Crop-window offset (fractions of the frame width / height) for the
+    /// analyzed frame nearest source time `t`. Zero outside the analysis.
+    pub fn offset_at(&self, t: f64) -> [f32; 2] {
+        if self.times.is_empty() {
+            return [0.0, 0.0];
+        }
+        let i = self.times.partition_point(|&x| x < t);
+        let nearest = if i == 0 {
+            0
+        } else if i == self.times.len() || t - self.times[i - 1] < self.times[i] - t {
+            i - 1
+        } else {
+            i
+        };
+        self.offsets[nearest]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const W: usize = 64;
+    const H: usize = 48;
+
+    /// Smooth-ish texture sampled at an offset, so shifts are exact.
+    fn texture(ox: i32, oy: i32) -> Vec<u8> {
+        (0..H as i32)
+            .flat_map(|y| {
+                (0..W as i32).map(move |x| {
+                    let (u, v) = ((x - ox) as f32, (y - oy) as f32);
+                    (128.0 + 60.0 * (u * 0.31).sin() + 50.0 * (v * 0.23 + u * 0.07).cos()) as u8
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frame_shift_finds_integer_translation() {
+        let prev = texture(0, 0);
+        let cur = texture(3, -2);
+        let [dx, dy] = frame_shift(&prev, &cur, W, H);
+        assert!((dx - 3.0).abs() < 0.3, "dx {dx}");
+        assert!((dy + 2.0).abs() < 0.3, "dy {dy}");
+    }
+
+    #[test]
+    fn frame_shift_reads_identical_frames_as_still() {
+        let f = texture(0, 0);
+        let [dx, dy] = frame_shift(&f, &f, W, H);
+        assert!(dx.abs() < 0.3 && dy.abs() < 0.3, "({dx}, {dy})");
+        let flat = vec![90u8; W * H];
+        assert_eq!(frame_shift(&flat, &flat, W, H), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn steady_pan_is_kept_and_jitter_removed() {
+        // 30 fps, constant pan of 0.2 %/frame plus ±1 % alternating shake.
+        let path: CameraPath = (0..90)
+            .map(|i| {
+                let shake = if i % 2 == 0 { 0.01 } else { -0.01 };
+                (i as f64 / 30.0, [i as f32 * 0.002 + shake, 0.0])
+            })
+            .collect();
+        let stab = Stabilizer::new(&path, 0.5);
+        // Mid-clip the correction cancels the shake, not the pan.
+        let [ox, oy] = stab.offset_at(1.5); // frame 45: shaken left
+        assert!((ox + 0.01).abs() < 0.002, "ox {ox}");
+        assert_eq!(oy, 0.0);
+        let [ox, _] = stab.offset_at(1.5 + 1.0 / 30.0);
+        assert!((ox - 0.01).abs() < 0.002, "ox {ox}");
+    }
+
+    #[test]
+    fn offsets_stay_within_the_margin() {
+        let path: CameraPath = (0..30)
+            .map(|i| (i as f64 / 30.0, [if i == 15 { 0.5 } else { 0.0 }, 0.0]))
+            .collect();
+        let stab = Stabilizer::new(&path, 0.1);
+        assert!((stab.offset_at(0.5)[0] - stab.margin()).abs() < 1e-6);
+        assert_eq!(Stabilizer::new(&Vec::new(), 1.0).offset_at(2.0), [0.0, 0.0]);
+    }
+}
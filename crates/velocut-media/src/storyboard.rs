@@ -0,0 +1,218 @@
+// crates/velocut-media/src/storyboard.rs
+//
+// Storyboard / contact-sheet export: decode one frame per planned shot,
+// grade it with its clip's filter, letterbox it into a fixed-size cell and
+// lay the cells out in a grid with an optional timecode label, then write a
+// single PNG or JPEG (picked by the destination's extension).
+//
+// Which moments to sample is decided in velocut-core's
+// `helpers::storyboard::plan_shots`; this module only renders.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use velocut_core::filters::helpers::apply_filter_rgba;
+use velocut_core::filters::FilterParams;
+
+use crate::decode::decode_one_frame_rgba;
+
+/// Padding around the grid and between cells, in pixels.
+const GAP: u32 = 8;
+const SHEET_BG: Rgba<u8> = Rgba([24, 24, 28, 255]);
+const CELL_BG: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+/// One cell of the sheet.
+#[derive(Clone, Debug)]
+pub struct StoryboardCell {
+    pub path: PathBuf,
+    pub source_time: f64,
+    pub filter: FilterParams,
+    /// Timecode burned into the cell's bottom-left corner, if any.
+    pub label: Option<String>,
+}
+
+/// Everything needed to render a sheet — built by the UI, consumed by
+/// `MediaWorker::export_storyboard`.
+#[derive(Clone, Debug)]
+pub struct StoryboardSpec {
+    pub cells: Vec<StoryboardCell>,
+    pub columns: u32,
+    pub thumb_width: u32,
+    pub thumb_height: u32,
+    pub dest: PathBuf,
+}
+
+/// Render `spec` and write it to `spec.dest`. A cell whose frame fails to
+/// decode is left black (and logged) rather than failing the whole sheet.
+pub fn export_storyboard(spec: &StoryboardSpec) -> Result<()> {
+    if spec.cells.is_empty() {
+        anyhow::bail!("no frames to put on the storyboard");
+    }
+    let (tw, th) = (spec.thumb_width.max(2), spec.thumb_height.max(2));
+    let (sheet_w, sheet_h) = sheet_size(spec.cells.len(), spec.columns, tw, th);
+    let mut sheet = RgbaImage::from_pixel(sheet_w, sheet_h, SHEET_BG);
+
+    for (i, cell) in spec.cells.iter().enumerate() {
+        let (x, y) = cell_origin(i, spec.columns, tw, th);
+        let mut tile = RgbaImage::from_pixel(tw, th, CELL_BG);
+        match decode_one_frame_rgba(&cell.path, cell.source_time, 0.0) {
+            Ok((mut rgba, w, h)) => {
+                apply_filter_rgba(&mut rgba, w as usize, h as usize, &cell.filter);
+                if let Some(frame) = RgbaImage::from_raw(w, h, rgba) {
+                    let (fw, fh) = fit_within(w, h, tw, th);
+                    let scaled = imageops::resize(&frame, fw, fh, FilterType::Triangle);
+                    imageops::overlay(
+                        &mut tile,
+                        &scaled,
+                        ((tw - fw) / 2) as i64,
+                        ((th - fh) / 2) as i64,
+                    );
+                }
+            }
+            Err(e) => {
+                crate::media_log!(
+                    "[storyboard] frame {i} of {:?} at {:.3}s failed: {e}",
+                    cell.path,
+                    cell.source_time
+                );
+            }
+        }
+        if let Some(label) = &cell.label {
+            draw_label(&mut tile, label, (tw / 160).max(2));
+        }
+        imageops::replace(&mut sheet, &tile, x as i64, y as i64);
+    }
+
+    // JPEG has no alpha channel; the sheet is opaque anyway.
+    DynamicImage::ImageRgba8(sheet)
+        .to_rgb8()
+        .save(&spec.dest)
+        .map_err(|e| anyhow::anyhow!("write {:?}: {e}", spec.dest))
+}
+
+/// Pixel size of a sheet of `cells` cells, `columns` wide.
+fn sheet_size(cells: usize, columns: u32, tw: u32, th: u32) -> (u32, u32) {
+    let columns = columns.clamp(1, cells.max(1) as u32);
+    let rows = (cells as u32).div_ceil(columns);
+    (GAP + columns * (tw + GAP), GAP + rows.max(1) * (th + GAP))
+}
+
+/// Top-left corner of cell `i`.
+fn cell_origin(i: usize, columns: u32, tw: u32, th: u32) -> (u32, u32) {
+    let columns = columns.max(1);
+    let (col, row) = (i as u32 % columns, i as u32 / columns);
+    (GAP + col * (tw + GAP), GAP + row * (th + GAP))
+}
+
+/// Largest `w`×`h`-shaped size that fits in `max_w`×`max_h`.
+fn fit_within(w: u32, h: u32, max_w: u32, max_h: u32) -> (u32, u32) {
+    let scale = (max_w as f64 / w.max(1) as f64).min(max_h as f64 / h.max(1) as f64);
+    (
+        ((w as f64 * scale).round() as u32).clamp(1, max_w),
+        ((h as f64 * scale).round() as u32).clamp(1, max_h),
+    )
+}
+
+// ── Timecode label ────────────────────────────────────────────────────────────
+
+/// 3×5 glyphs for `format_time` output: digits and ':'. One byte per row,
+/// low three bits, bit 2 = left column.
+const GLYPHS: [[u8; 5]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b001, 0b001, 0b001], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+    [0b000, 0b010, 0b000, 0b010, 0b000], // :
+];
+
+fn glyph(c: char) -> Option<&'static [u8; 5]> {
+    match c {
+        '0'..='9' => GLYPHS.get(c as usize - '0' as usize),
+        ':' => Some(&GLYPHS[10]),
+        _ => None,
+    }
+}
+
+/// Draw `text` in white on a darkened box in the tile's bottom-left corner.
+/// Each glyph pixel is a `scale`×`scale` block; unknown characters become
+/// blank space.
+fn draw_label(tile: &mut RgbaImage, text: &str, scale: u32) {
+    let advance = 4 * scale;
+    let pad = scale;
+    let box_w = (text.chars().count() as u32 * advance + pad).min(tile.width());
+    let box_h = (5 * scale + 2 * pad).min(tile.height());
+    let (x0, y0) = (0, tile.height() - box_h);
+
+    for y in y0..y0 + box_h {
+        for x in x0..x0 + box_w {
+            let p = tile.get_pixel_mut(x, y);
+            for c in &mut p.0[..3] {
+                *c /= 3;
+            }
+        }
+    }
+    for (i, ch) in text.chars().enumerate() {
+        let Some(rows) = glyph(ch) else { continue };
+        let gx = x0 + pad + i as u32 * advance;
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..3u32 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let (px, py) = (gx + col * scale + dx, y0 + pad + row as u32 * scale + dy);
+                        if px < tile.width() && py < tile.height() {
+                            tile.put_pixel(px, py, Rgba([255, 255, 255, 255]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheet_wraps_cells_into_rows() {
+        // 5 cells, 3 columns → 2 rows.
+        assert_eq!(sheet_size(5, 3, 100, 50), (8 + 3 * 108, 8 + 2 * 58));
+        assert_eq!(cell_origin(4, 3, 100, 50), (8 + 108, 8 + 58));
+        // Fewer cells than columns → the sheet shrinks to fit.
+        assert_eq!(sheet_size(2, 6, 100, 50), (8 + 2 * 108, 8 + 58));
+    }
+
+    #[test]
+    fn fit_letterboxes_to_cell_shape() {
+        assert_eq!(fit_within(1920, 1080, 320, 180), (320, 180));
+        assert_eq!(fit_within(1080, 1920, 320, 180), (101, 180));
+        assert_eq!(fit_within(1000, 1000, 320, 180), (180, 180));
+    }
+
+    #[test]
+    fn label_draws_white_glyphs_on_dark_box() {
+        let mut tile = RgbaImage::from_pixel(64, 32, Rgba([90, 90, 90, 255]));
+        draw_label(&mut tile, "1:0", 2);
+        let white = tile
+            .pixels()
+            .filter(|p| p.0 == [255, 255, 255, 255])
+            .count();
+        // "1" has 8 lit cells, ":" 2, "0" 12 — each a 2×2 block.
+        assert_eq!(white, (8 + 2 + 12) * 4);
+        // Box corner darkened, area outside untouched.
+        assert_eq!(tile.get_pixel(0, 31).0[0], 30);
+        assert_eq!(tile.get_pixel(63, 0).0[0], 90);
+    }
+}
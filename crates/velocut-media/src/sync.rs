@@ -0,0 +1,313 @@
+// crates/velocut-media/src/sync.rs
+//
+// Audio auto-sync: decode two clips' audio into short-window loudness
+// envelopes, turn those into onset strength (how sharply the level rises)
+// and cross-correlate them to find the offset where the same claps, words
+// and hits line up. Onsets rather than raw loudness so a camera mic and a
+// lavalier with very different gain and room tone still match.
+
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
+
+use ffmpeg_the_third as ffmpeg;
+
+use velocut_core::media_types::MediaResult;
+
+use crate::probe::select_audio_stream;
+use crate::waveform::append_frame_samples;
+
+/// Envelope rate. 10 ms windows keep the final offset well under a frame.
+const ENV_RATE: f64 = 100.0;
+/// The first pass correlates envelopes decimated by this factor (40 ms) over
+/// every lag; the second pass refines around the winner at full rate.
+const COARSE_FACTOR: usize = 4;
+/// At most this much of each clip is analyzed, keeping the brute-force
+/// search to a second or so on long takes.
+const MAX_SCAN_SECS: f64 = 600.0;
+/// Offsets where the clips share less audio than this are not considered —
+/// a match on a sliver of overlap is usually chance.
+const MIN_OVERLAP_SECS: f64 = 5.0;
+/// Normalized correlation below this is reported as "no match".
+const MIN_SCORE: f32 = 0.3;
+
+/// One side of a sync: a source file and the source range the timeline
+/// clip uses.
+pub struct SyncSource {
+    pub path: PathBuf,
+    pub audio_stream: Option<usize>,
+    pub from: f64,
+    pub to: f64,
+}
+
+/// Find how far `clip`'s audio is offset from `reference`'s and send
+/// `MediaResult::AudioSyncFound` keyed by `id`. The offset is reference
+/// source time minus clip source time for the same moment. A failed decode
+/// or a weak match reports `None` so the UI never waits forever.
+pub fn detect_audio_sync(
+    id: Uuid,
+    clip: &SyncSource,
+    reference: &SyncSource,
+    tx: &Sender<MediaResult>,
+    shutdown: &AtomicBool,
+) {
+    let offset = match measure(clip, reference, shutdown) {
+        Ok(offset) => offset,
+        Err(e) => {
+            crate::media_log!("[sync] {}: {e}", clip.path.display());
+            None
+        }
+    };
+    crate::media_log!(
+        "[sync] offset {:?} ← {} vs {}",
+        offset,
+        clip.path.display(),
+        reference.path.display()
+    );
+    let _ = tx.send(MediaResult::AudioSyncFound { id, offset });
+}
+
+fn measure(
+    clip: &SyncSource,
+    reference: &SyncSource,
+    shutdown: &AtomicBool,
+) -> anyhow::Result<Option<f64>> {
+    let c = onsets(&envelope(clip, shutdown)?);
+    let r = onsets(&envelope(reference, shutdown)?);
+    Ok(find_lag(&c, &r).map(|lag| reference.from - clip.from + lag))
+}
+
+/// RMS level per `1 / ENV_RATE` window over the source range.
+fn envelope(src: &SyncSource, shutdown: &AtomicBool) -> anyhow::Result<Vec<f32>> {
+    let to = src.to.min(src.from + MAX_SCAN_SECS);
+    let mut ictx = ffmpeg::format::input(&src.path)?;
+
+    let stream_index = select_audio_stream(&ictx, src.audio_stream)
+        .ok_or_else(|| anyhow::anyhow!("no audio stream"))?;
+    let (tb, dec_ctx) = {
+        let stream = ictx.stream(stream_index).unwrap();
+        let tb = stream.time_base();
+        (
+            tb.numerator() as f64 / tb.denominator() as f64,
+            ffmpeg::codec::context::Context::from_parameters(stream.parameters())?,
+        )
+    };
+
+    // Same ts=0 guard as decode_frame — seeking to 0 fails on Windows.
+    let seek_ts = (src.from / tb) as i64;
+    if seek_ts > 0 {
+        let _ = ictx.seek(seek_ts, ..=seek_ts);
+    }
+    let mut decoder = dec_ctx.decoder().audio()?;
+
+    let bins = ((to - src.from) * ENV_RATE).ceil().max(0.0) as usize;
+    let mut energy = vec![0.0f64; bins];
+    let mut counts = vec![0u32; bins];
+    let mut samples: Vec<f32> = Vec::new();
+
+    'outer: for (stream, packet) in ictx.packets().flatten() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        if shutdown.load(Ordering::Acquire) {
+            anyhow::bail!("shut down");
+        }
+        if decoder.send_packet(&packet).is_err() {
+            continue;
+        }
+        let mut frame = ffmpeg::frame::Audio::empty();
+        while decoder.receive_frame(&mut frame).is_ok() {
+            let (Some(pts), rate) = (frame.pts(), frame.rate()) else {
+                continue;
+            };
+            if rate == 0 {
+                continue;
+            }
+            let t0 = pts as f64 * tb;
+            if t0 > to {
+                break 'outer;
+            }
+            samples.clear();
+            append_frame_samples(&frame, &mut samples);
+            for (i, s) in samples.iter().enumerate() {
+                let t = t0 + i as f64 / rate as f64 - src.from;
+                if t < 0.0 {
+                    continue;
+                }
+                let bin = (t * ENV_RATE) as usize;
+                if bin >= bins {
+                    break;
+                }
+                energy[bin] += (*s as f64) * (*s as f64);
+                counts[bin] += 1;
+            }
+        }
+    }
+
+    Ok(energy
+        .iter()
+        .zip(&counts)
+        .map(|(&e, &n)| {
+            if n == 0 {
+                0.0
+            } else {
+                (e / n as f64).sqrt() as f32
+            }
+        })
+        .collect())
+}
+
+/// Onset strength: the rise in log level from one window to the next,
+/// zero-meaned so long stretches of steady sound don't dominate the score.
+fn onsets(env: &[f32]) -> Vec<f32> {
+    let db: Vec<f32> = env.iter().map(|&v| 20.0 * v.max(1e-4).log10()).collect();
+    let mut rise: Vec<f32> = std::iter::once(0.0)
+        .chain(db.windows(2).map(|w| (w[1] - w[0]).max(0.0)))
+        .collect();
+    let mean = rise.iter().sum::<f32>() / rise.len().max(1) as f32;
+    rise.iter_mut().for_each(|v| *v -= mean);
+    rise
+}
+
+/// Offset in seconds such that `clip[i]` lines up with `reference[i + lag]`,
+/// found coarse-to-fine. `None` when the best match scores below `MIN_SCORE`.
+fn find_lag(clip: &[f32], reference: &[f32]) -> Option<f64> {
+    let min_overlap = ((MIN_OVERLAP_SECS * ENV_RATE) as usize)
+        .min(clip.len())
+        .min(reference.len());
+    if min_overlap < 2 * COARSE_FACTOR {
+        return None;
+    }
+
+    let (cc, cr) = (
+        decimate(clip, COARSE_FACTOR),
+        decimate(reference, COARSE_FACTOR),
+    );
+    let coarse_overlap = min_overlap / COARSE_FACTOR;
+    let (lag, _) = best_lag(
+        &cc,
+        &cr,
+        -(cc.len() as i64) + 1..=cr.len() as i64 - 1,
+        coarse_overlap,
+    )?;
+
+    let center = lag * COARSE_FACTOR as i64;
+    let span = 2 * COARSE_FACTOR as i64;
+    let (lag, score) = best_lag(clip, reference, center - span..=center + span, min_overlap)?;
+    if score < MIN_SCORE {
+        return None;
+    }
+    let at = |l: i64| correlation(clip, reference, l, min_overlap).unwrap_or(score);
+    let frac = peak_offset(at(lag - 1), score, at(lag + 1));
+    Some((lag as f64 + frac) / ENV_RATE)
+}
+
+/// Highest-scoring lag in `lags` and its normalized correlation.
+fn best_lag(
+    clip: &[f32],
+    reference: &[f32],
+    lags: std::ops::RangeInclusive<i64>,
+    min_overlap: usize,
+) -> Option<(i64, f32)> {
+    lags.filter_map(|l| Some((l, correlation(clip, reference, l, min_overlap)?)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Normalized correlation of `clip[i]` against `reference[i + lag]` over the
+/// samples both cover. `None` when that overlap is shorter than `min_overlap`.
+fn correlation(clip: &[f32], reference: &[f32], lag: i64, min_overlap: usize) -> Option<f32> {
+    let start = (-lag).max(0) as usize;
+    let end = (reference.len() as i64 - lag).min(clip.len() as i64);
+    if end - (start as i64) < min_overlap as i64 {
+        return None;
+    }
+    let a = &clip[start..end as usize];
+    let b = &reference[(start as i64 + lag) as usize..(end + lag) as usize];
+    let (mut dot, mut aa, mut bb) = (0.0f64, 0.0f64, 0.0f64);
+    for (&x, &y) in a.iter().zip(b) {
+        dot += (x * y) as f64;
+        aa += (x * x) as f64;
+        bb += (y * y) as f64;
+    }
+    if aa <= 0.0 || bb <= 0.0 {
+        return Some(0.0);
+    }
+    Some((dot / (aa * bb).sqrt()) as f32)
+}
+
+/// Sum each run of `factor` samples — onsets that fall anywhere in a coarse
+/// window still count toward it.
+fn decimate(x: &[f32], factor: usize) -> Vec<f32> {
+    x.chunks(factor).map(|c| c.iter().sum()).collect()
+}
+
+/// Sub-sample position of a peak from its neighbours (parabolic fit),
+/// in −0.5..=0.5.
+fn peak_offset(prev: f32, peak: f32, next: f32) -> f64 {
+    let denom = prev - 2.0 * peak + next;
+    if denom.abs() < 1e-9 {
+        return 0.0;
+    }
+    (0.5 * (prev - next) / denom).clamp(-0.5, 0.5) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic "speech": bursts of varying loudness at irregular gaps.
+    fn burst_env(len: usize, seed: u32) -> Vec<f32> {
+        let mut env = vec![0.001f32; len];
+        let mut s = seed;
+        let mut i = 0;
+        while i < len {
+            s = s.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let gap = 5 + (s >> 16) as usize % 40;
+            let level = 0.05 + ((s >> 8) % 100) as f32 / 200.0;
+            for v in env.iter_mut().skip(i).take(8) {
+                *v = level;
+            }
+            i += 8 + gap;
+        }
+        env
+    }
+
+    #[test]
+    fn finds_known_offset() {
+        let reference = burst_env(3000, 7);
+        // The clip starts 4.37 s into the reference, at a quieter gain.
+        let clip: Vec<f32> = reference[437..2437].iter().map(|v| v * 0.3).collect();
+        let lag = find_lag(&onsets(&clip), &onsets(&reference)).unwrap();
+        assert!((lag - 4.37).abs() < 0.011, "lag {lag}");
+    }
+
+    #[test]
+    fn finds_negative_offset() {
+        let clip = burst_env(3000, 11);
+        // Reference starts 2 s into the clip.
+        let reference = clip[200..].to_vec();
+        let lag = find_lag(&onsets(&clip), &onsets(&reference)).unwrap();
+        assert!((lag + 2.0).abs() < 0.011, "lag {lag}");
+    }
+
+    #[test]
+    fn unrelated_audio_has_no_match() {
+        let clip = burst_env(2000, 3);
+        let reference = burst_env(2000, 99);
+        assert_eq!(find_lag(&onsets(&clip), &onsets(&reference)), None);
+    }
+
+    #[test]
+    fn correlation_needs_overlap() {
+        let a = [1.0, -1.0, 1.0, -1.0];
+        assert_eq!(correlation(&a, &a, 3, 2), None);
+        assert!((correlation(&a, &a, 0, 2).unwrap() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn peak_offset_is_centerd_for_symmetric_neighbours() {
+        assert_eq!(peak_offset(0.5, 1.0, 0.5), 0.0);
+        assert!(peak_offset(0.8, 1.0, 0.2) < 0.0);
+    }
+}
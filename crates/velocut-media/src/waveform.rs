@@ -10,13 +10,20 @@ use uuid::Uuid;
 
 use velocut_core::media_types::MediaResult;
 
+use crate::probe::select_audio_stream;
+
 use ffmpeg::format::sample::{Sample, Type as SampleType};
 use ffmpeg_the_third as ffmpeg;
 
 const WAVEFORM_COLS: usize = 4000;
 
-pub fn extract_waveform(path: &PathBuf, id: Uuid, tx: &Sender<MediaResult>) {
-    let samples = match decode_audio_samples(path) {
+pub fn extract_waveform(
+    path: &PathBuf,
+    id: Uuid,
+    audio_stream: Option<usize>,
+    tx: &Sender<MediaResult>,
+) {
+    let samples = match decode_audio_samples(path, audio_stream) {
         Ok(s) if !s.is_empty() => s,
         Ok(_) => {
             crate::media_log!("[media] waveform: no samples for {}", path.display());
@@ -43,17 +50,16 @@ pub fn extract_waveform(path: &PathBuf, id: Uuid, tx: &Sender<MediaResult>) {
     let _ = tx.send(MediaResult::Waveform { id, peaks });
 }
 
-fn decode_audio_samples(path: &PathBuf) -> Result<Vec<f32>, String> {
+fn decode_audio_samples(path: &PathBuf, audio_stream: Option<usize>) -> Result<Vec<f32>, String> {
     let mut ictx = ffmpeg::format::input(path).map_err(|e| format!("open: {e}"))?;
 
-    let stream = ictx
-        .streams()
-        .best(ffmpeg::media::Type::Audio)
-        .ok_or_else(|| "no audio stream".to_string())?;
-    let stream_index = stream.index();
+    let stream_index =
+        select_audio_stream(&ictx, audio_stream).ok_or_else(|| "no audio stream".to_string())?;
 
-    let ctx = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
-        .map_err(|e| format!("codec context: {e}"))?;
+    let ctx = ffmpeg::codec::context::Context::from_parameters(
+        ictx.stream(stream_index).unwrap().parameters(),
+    )
+    .map_err(|e| format!("codec context: {e}"))?;
     let mut decoder = ctx
         .decoder()
         .audio()
@@ -91,7 +97,7 @@ fn decode_audio_samples(path: &PathBuf) -> Result<Vec<f32>, String> {
 /// Append mono f32 samples from a decoded frame into `out`.
 /// Packed formats: step by channel count to extract channel 0 only.
 /// Planar formats: plane 0 is already channel 0.
-fn append_frame_samples(frame: &ffmpeg::frame::Audio, out: &mut Vec<f32>) {
+pub(crate) fn append_frame_samples(frame: &ffmpeg::frame::Audio, out: &mut Vec<f32>) {
     let channels = frame.ch_layout().channels() as usize;
     let data = frame.data(0);
 
@@ -4,9 +4,10 @@
 // All public API that velocut-ui calls lives here.
 
 use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU32, Ordering},
     Arc, Condvar, Mutex,
 };
 use std::thread;
@@ -17,11 +18,21 @@ use uuid::Uuid;
 use velocut_core::media_types::{
     MediaResult, PlaybackFrame, PlaybackTransitionSpec, TransitionScrubRequest,
 };
+use velocut_core::prefs::PLAYBACK_AHEAD_RANGE;
+use velocut_core::state::ClipType;
 
-use crate::audio::extract_audio;
-use crate::decode::{decode_frame, decode_one_frame_rgba, LiveDecoder};
+use crate::audio::{extract_audio, set_scratch_dir};
+use crate::decode::{decode_frame, decode_one_frame_rgba, set_scrub_proxy_width, LiveDecoder};
+use crate::duck::detect_speech;
 use crate::encode::{encode_timeline, EncodeSpec};
-use crate::probe::{probe_duration, probe_video_size_and_thumbnail};
+use crate::probe::{
+    probe_audio_streams, probe_duration, probe_poster_thumbnail, probe_source_frame,
+    probe_stream_kinds, probe_video_size_and_thumbnail,
+};
+use crate::scenes::detect_scenes;
+use crate::silence::detect_silence;
+use crate::storyboard::{export_storyboard, StoryboardSpec};
+use crate::sync::{detect_audio_sync, SyncSource};
 use crate::waveform::extract_waveform;
 
 mod blend;
@@ -78,8 +89,16 @@ pub struct MediaWorker {
     /// Each request_frame_hq / request_transition_frame* call opens one or two
     /// full native-res FFmpeg decoder contexts; without a cap, rapid L3-idle
     /// updates pile up threads and inflate RSS by ~16 MB per in-flight decode.
-    /// Limit = 2: one in-flight HQ frame plus one transition blend at most.
+    /// Limit defaults to 2 (one in-flight HQ frame plus one transition blend)
+    /// and is read from `hq_limit` when each request is issued.
     hq_sem: Arc<(Mutex<u32>, Condvar)>,
+    /// Concurrency cap for `hq_sem`. Set from the Performance preferences via
+    /// `set_decode_threads`; requests issued after a change use the new cap.
+    hq_limit: Arc<AtomicU32>,
+    /// How many decoded frames the playback thread keeps queued in `pb_rx`.
+    /// Set from the Performance preferences via `set_playback_ahead`; the
+    /// channel itself is sized for the largest allowed depth.
+    pb_ahead: Arc<AtomicU32>,
     /// Latest-wins slot for transition scrub frames (L2 scrub in a transition zone).
     /// Mirrors `frame_req` but carries a full `TransitionScrubRequest` and is
     /// consumed by a dedicated thread that keeps two `LiveDecoder`s alive across
@@ -162,12 +181,12 @@ impl MediaWorker {
                         .map(|d| (d.scaler, d.decoder_fmt, d.decoder_w, d.decoder_h));
                     // When preview_size is known, pass it as forced_size so the
                     // decoder output matches the panel dimensions exactly.
-                    // Otherwise fall back to the aspect-based 320px scrub size.
+                    // Otherwise fall back to the aspect-based proxy scrub size.
                     let forced = if req.preview_size.is_some() {
                         req.preview_size
                     } else {
                         // No preview size and no aspect: let LiveDecoder use its
-                        // 320px default scrub size.
+                        // proxy-width scrub size.
                         None
                     };
                     match LiveDecoder::open(
@@ -292,7 +311,8 @@ impl MediaWorker {
                     },
                 };
 
-                let blended = blend_rgba_transition(&data_a, &data_b, w, h, req.alpha, req.kind);
+                let blended =
+                    blend_rgba_transition(&data_a, &data_b, w, h, req.alpha, req.kind, req.curve);
                 let _ = transition_scrub_result_tx.send(MediaResult::TransitionVideoFrame {
                     id: req.clip_a_id,
                     width: w,
@@ -305,17 +325,22 @@ impl MediaWorker {
         // ── Dedicated playback decode thread ──────────────────────────────────
         // Runs continuously ahead of the UI filling a bounded channel (backpressure).
         //
-        // [Fix 1] Channel reduced from 32 → 6 frames.
+        // [Fix 1] Read-ahead reduced from 32 → 6 frames by default.
         // At 480p RGBA each frame is ~1.2 MB — 32 frames = 38 MB sitting in the
         // channel at all times during playback. burn_to_pts runs synchronously
         // before the send loop starts so the extra headroom was never consumed;
-        // 6 frames (~200ms at 30fps) is sufficient for smooth playback.
+        // 6 frames (~200ms at 30fps) is sufficient for smooth playback of HD.
+        // Slow 4K decodes can use more: the depth is a Performance preference
+        // (`pb_ahead`) enforced by the pb thread, so the channel is allocated
+        // at the largest depth allowed and never resized.
         let (pb_tx, pb_cmd_rx) = bounded::<PlaybackCmd>(8);
-        let (pb_frame_tx, pb_rx) = bounded::<PlaybackFrame>(6);
+        let (pb_frame_tx, pb_rx) = bounded::<PlaybackFrame>(*PLAYBACK_AHEAD_RANGE.end() as usize);
+        let pb_ahead = Arc::new(AtomicU32::new(6));
 
         let pb = PbThread {
             cmd_rx: pb_cmd_rx,
             frame_tx: pb_frame_tx,
+            ahead: pb_ahead.clone(),
         };
         let pb_thread = thread::spawn(move || {
             pb.run();
@@ -333,6 +358,8 @@ impl MediaWorker {
             probe_sem: Arc::new((Mutex::new(0), Condvar::new())),
             encode_cancels: Arc::new(Mutex::new(HashMap::new())),
             hq_sem: Arc::new((Mutex::new(0), Condvar::new())),
+            hq_limit: Arc::new(AtomicU32::new(2)),
+            pb_ahead,
             transition_scrub_req,
             scrub_thread: Some(scrub_thread),
             transition_scrub_thread: Some(transition_scrub_thread),
@@ -368,6 +395,7 @@ impl MediaWorker {
                 clip_b_ts: 0.0,
                 alpha: 0.0,
                 kind: velocut_core::transitions::TransitionKind::Cut,
+                curve: velocut_core::transitions::FadeCurve::default(),
             });
             cvar.notify_one();
         }
@@ -399,7 +427,10 @@ impl MediaWorker {
         }
     }
 
-    pub fn probe_clip(&self, id: Uuid, path: PathBuf) {
+    /// Full import pipeline: duration, thumbnail, audio stream list, waveform
+    /// and preview WAV. `audio_stream` is the clip's chosen stream (`None` =
+    /// ffmpeg's best audio stream).
+    pub fn probe_clip(&self, id: Uuid, path: PathBuf, audio_stream: Option<usize>) {
         let tx = self.tx.clone();
         let sd = self.shutdown.clone();
         let sem = self.probe_sem.clone();
@@ -414,35 +445,91 @@ impl MediaWorker {
             const PROBE_CONCURRENCY: u32 = 2;
             let _guard = SemaphoreGuard::acquire(sem, PROBE_CONCURRENCY);
 
-            if sd.load(Ordering::Acquire) {
-                return;
-            }
-            let dur = probe_duration(&path, id, &tx);
-            if sd.load(Ordering::Acquire) {
-                return;
-            }
-            probe_video_size_and_thumbnail(&path, id, dur, &tx);
+            // Every exit below, early or not, ends in ProbeFinished.
+            let run = || {
+                if sd.load(Ordering::Acquire) {
+                    return;
+                }
+                // Stills: the image2 demuxer yields one frame and no audio, so
+                // only size + thumbnail mean anything. Duration is set at import.
+                if ClipType::from_path(&path) == ClipType::Image {
+                    probe_video_size_and_thumbnail(&path, id, 0.0, &tx);
+                    return;
+                }
+                let dur = probe_duration(&path, id, &tx);
+                if sd.load(Ordering::Acquire) {
+                    return;
+                }
+                // Branch on what the file actually holds rather than its
+                // extension: an audio-only source skips the video steps, so
+                // it gets no thumbnail error and no VideoSize to throw off
+                // the auto aspect ratio.
+                let Some((has_video, has_audio)) = probe_stream_kinds(&path) else {
+                    return; // open failed — probe_duration already reported it
+                };
+                if has_video {
+                    probe_video_size_and_thumbnail(&path, id, dur, &tx);
+                } else if has_audio {
+                    crate::media_log!("[media] audio only ← {}", path.display());
+                    let _ = tx.send(MediaResult::AudioOnly { id });
+                } else {
+                    let _ = tx.send(MediaResult::Error {
+                        id,
+                        msg: "no video or audio stream".into(),
+                    });
+                    return;
+                }
+                probe_audio_streams(&path, id, &tx);
+                if !has_audio {
+                    return;
+                }
 
-            // NOTE: do NOT drop(_guard) here. extract_waveform and extract_audio
-            // must run under the semaphore — they are the expensive operations.
+                // NOTE: do NOT drop(_guard) here. extract_waveform and extract_audio
+                // must run under the semaphore — they are the expensive operations.
+                if sd.load(Ordering::Acquire) {
+                    return;
+                }
+                extract_waveform(&path, id, audio_stream, &tx);
+                if sd.load(Ordering::Acquire) {
+                    return;
+                }
+                if dur > 0.0 {
+                    extract_audio(&path, id, audio_stream, 0.0, f64::MAX, &tx);
+                }
+            };
+            run();
+            let _ = tx.send(MediaResult::ProbeFinished { id });
+        });
+    }
+
+    /// No-op for compat — thumbnails now come back via probe_clip as RGBA data.
+    pub fn reload_thumbnail(&self, id: Uuid, path: PathBuf) {
+        self.probe_clip(id, path, None);
+    }
+
+    /// Re-run waveform + preview WAV extraction after the user picks a
+    /// different audio stream. Results arrive as the usual `Waveform` and
+    /// `AudioPath` messages; the WAV name encodes the stream, so a sink that
+    /// is still playing the old file is never overwritten underneath it.
+    pub fn reload_audio(&self, id: Uuid, path: PathBuf, audio_stream: Option<usize>) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.probe_sem.clone();
+
+        std::thread::spawn(move || {
+            // Same budget as probe_clip — extract_audio is the expensive part.
+            let _guard = SemaphoreGuard::acquire(sem, 2);
             if sd.load(Ordering::Acquire) {
                 return;
             }
-            extract_waveform(&path, id, &tx);
+            extract_waveform(&path, id, audio_stream, &tx);
             if sd.load(Ordering::Acquire) {
                 return;
             }
-            if dur > 0.0 {
-                extract_audio(&path, id, 0.0, f64::MAX, &tx);
-            }
+            extract_audio(&path, id, audio_stream, 0.0, f64::MAX, &tx);
         });
     }
 
-    /// No-op for compat — thumbnails now come back via probe_clip as RGBA data.
-    pub fn reload_thumbnail(&self, id: Uuid, path: PathBuf) {
-        self.probe_clip(id, path);
-    }
-
     /// Re-extract the WAV temp file for an audio overlay, restricted to
     /// `[source_offset, source_offset + duration)`.
     ///
@@ -454,6 +541,7 @@ impl MediaWorker {
         &self,
         id: Uuid,
         path: PathBuf,
+        audio_stream: Option<usize>,
         source_offset: f64,
         duration: f64,
     ) {
@@ -463,7 +551,7 @@ impl MediaWorker {
             if sd.load(Ordering::Acquire) {
                 return;
             }
-            extract_audio(&path, id, source_offset, duration, &tx);
+            extract_audio(&path, id, audio_stream, source_offset, duration, &tx);
         });
     }
 
@@ -496,12 +584,12 @@ impl MediaWorker {
         let tx = self.scrub_tx.clone();
         let sd = self.shutdown.clone();
         let sem = self.hq_sem.clone();
+        let limit = self.hq_limit.load(Ordering::Relaxed);
         thread::spawn(move || {
             // Acquire the HQ semaphore before opening any FFmpeg context.
             // Without this, rapid L3-idle updates spawn N threads simultaneously,
             // each holding a native-res decoder + scaler + frame buffer (~16 MB).
-            const HQ_CONCURRENCY: u32 = 2;
-            let _guard = SemaphoreGuard::acquire(sem, HQ_CONCURRENCY);
+            let _guard = SemaphoreGuard::acquire(sem, limit);
             if sd.load(Ordering::Acquire) {
                 return;
             }
@@ -512,6 +600,40 @@ impl MediaWorker {
         });
     }
 
+    /// Cap the number of concurrent HQ / transition decode threads.
+    /// Clamped to at least 1 so requests can never deadlock on the semaphore.
+    pub fn set_decode_threads(&self, n: u32) {
+        self.hq_limit.store(n.max(1), Ordering::Relaxed);
+    }
+
+    /// Probe / analysis threads and HQ decode threads currently holding a
+    /// slot, for the performance HUD. Threads still queued for a slot are not
+    /// counted.
+    pub fn jobs_in_flight(&self) -> (u32, u32) {
+        let held = |sem: &Arc<(Mutex<u32>, Condvar)>| *sem.0.lock().unwrap();
+        (held(&self.probe_sem), held(&self.hq_sem))
+    }
+
+    /// Set how many frames playback decodes ahead of the playhead. Takes
+    /// effect on the pb thread's next send.
+    pub fn set_playback_ahead(&self, frames: u32) {
+        let frames = frames.clamp(*PLAYBACK_AHEAD_RANGE.start(), *PLAYBACK_AHEAD_RANGE.end());
+        self.pb_ahead.store(frames, Ordering::Relaxed);
+    }
+
+    /// Set the output width of low-res scrub frames. Applies to scrub
+    /// decoders opened after the call; the live one keeps its size until
+    /// the scrub head moves to a different clip.
+    pub fn set_proxy_width(&self, width: u32) {
+        set_scrub_proxy_width(width);
+    }
+
+    /// Set the folder extracted audio WAVs are written to (`None` = OS temp
+    /// dir). Applies to extractions started after the call.
+    pub fn set_scratch_dir(&self, dir: Option<PathBuf>) {
+        set_scratch_dir(dir);
+    }
+
     pub fn request_transition_frame(&self, req: TransitionScrubRequest) {
         let (lock, cvar) = &*self.transition_scrub_req;
         *lock.lock().unwrap() = Some(req);
@@ -522,10 +644,10 @@ impl MediaWorker {
         let scrub_tx = self.scrub_tx.clone();
         let sd = self.shutdown.clone();
         let sem = self.hq_sem.clone();
+        let limit = self.hq_limit.load(Ordering::Relaxed);
         thread::spawn(move || {
             // Two native-res decoders opened per call — gate on hq_sem.
-            const HQ_CONCURRENCY: u32 = 2;
-            let _guard = SemaphoreGuard::acquire(sem, HQ_CONCURRENCY);
+            let _guard = SemaphoreGuard::acquire(sem, limit);
             if sd.load(Ordering::Acquire) {
                 return;
             }
@@ -559,7 +681,8 @@ impl MediaWorker {
             } else {
                 data_b_raw
             };
-            let blended = blend_rgba_transition(&data_a, &data_b, w, h, req.alpha, req.kind);
+            let blended =
+                blend_rgba_transition(&data_a, &data_b, w, h, req.alpha, req.kind, req.curve);
             let _ = scrub_tx.send(MediaResult::VideoFrame {
                 id: req.clip_a_id,
                 width: w,
@@ -661,6 +784,137 @@ impl MediaWorker {
         while self.pb_rx.try_recv().is_ok() {}
     }
 
+    /// Run scene-cut detection over source range `from..to` of `path` and
+    /// report back as `ScenesDetected` keyed by the timeline clip id. Shares
+    /// the probe semaphore — a full decode pass is as heavy as an import probe.
+    pub fn detect_scenes(&self, clip_id: Uuid, path: PathBuf, from: f64, to: f64, threshold: f32) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.probe_sem.clone();
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, 2);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            detect_scenes(&path, clip_id, from, to, threshold, &tx, &sd);
+        });
+    }
+
+    /// Scan source range `from..to` of `path` for leading/trailing silence
+    /// and report the sounding range as `SilenceDetected` keyed by the
+    /// timeline clip id. Same probe-semaphore rule as `detect_scenes`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn detect_silence(
+        &self,
+        clip_id: Uuid,
+        path: PathBuf,
+        audio_stream: Option<usize>,
+        from: f64,
+        to: f64,
+        threshold_db: f32,
+        min_gap: f64,
+    ) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.probe_sem.clone();
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, 2);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            detect_silence(
+                &path,
+                clip_id,
+                audio_stream,
+                from,
+                to,
+                threshold_db,
+                min_gap,
+                &tx,
+                &sd,
+            );
+        });
+    }
+
+    /// Scan source range `from..to` of the speech clip at `path` for
+    /// speech and report the regions as `SpeechDetected` keyed by the music
+    /// clip `clip_id`. Same probe-semaphore rule as `detect_scenes`.
+    pub fn detect_speech(
+        &self,
+        clip_id: Uuid,
+        path: PathBuf,
+        audio_stream: Option<usize>,
+        from: f64,
+        to: f64,
+        threshold_db: f32,
+    ) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.probe_sem.clone();
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, 2);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            detect_speech(
+                &path,
+                clip_id,
+                audio_stream,
+                from,
+                to,
+                threshold_db,
+                &tx,
+                &sd,
+            );
+        });
+    }
+
+    /// Cross-correlate the audio of `clip` against `reference` and report the
+    /// source offset between them as `AudioSyncFound` keyed by the timeline
+    /// clip id. Same probe-semaphore rule as `detect_scenes`.
+    pub fn detect_audio_sync(&self, clip_id: Uuid, clip: SyncSource, reference: SyncSource) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.probe_sem.clone();
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, 2);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            detect_audio_sync(clip_id, &clip, &reference, &tx, &sd);
+        });
+    }
+
+    /// Decode a timeline clip's poster frame at source time `at` and send it
+    /// back as a `Thumbnail` keyed by the timeline clip id.
+    pub fn request_poster_thumbnail(&self, clip_id: Uuid, path: PathBuf, at: f64) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        thread::spawn(move || {
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            probe_poster_thumbnail(&path, clip_id, at, &tx);
+        });
+    }
+
+    /// Decode the source viewer's frame at `at` and send it back as a
+    /// `Thumbnail` keyed by `view_id`. Shares the HQ semaphore so a fast scrub
+    /// can't pile up decoders.
+    pub fn request_source_frame(&self, view_id: Uuid, path: PathBuf, at: f64, width: u32) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.hq_sem.clone();
+        let limit = self.hq_limit.load(Ordering::Relaxed);
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, limit);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            probe_source_frame(&path, view_id, at, width, &tx);
+        });
+    }
+
     pub fn extract_frame_hq(&self, id: Uuid, path: PathBuf, timestamp: f64, dest: PathBuf) {
         let tx = self.tx.clone();
         let sd = self.shutdown.clone();
@@ -674,6 +928,27 @@ impl MediaWorker {
         });
     }
 
+    /// Render a storyboard contact sheet on a worker thread and report the
+    /// outcome as `StoryboardSaved`. Each cell is a full-resolution decode, so
+    /// the job holds one HQ permit for its whole run.
+    pub fn export_storyboard(&self, spec: StoryboardSpec) {
+        let tx = self.tx.clone();
+        let sd = self.shutdown.clone();
+        let sem = self.hq_sem.clone();
+        let limit = self.hq_limit.load(Ordering::Relaxed);
+        thread::spawn(move || {
+            let _guard = SemaphoreGuard::acquire(sem, limit);
+            if sd.load(Ordering::Acquire) {
+                return;
+            }
+            let error = export_storyboard(&spec).err().map(|e| e.to_string());
+            let _ = tx.send(MediaResult::StoryboardSaved {
+                path: spec.dest,
+                error,
+            });
+        });
+    }
+
     pub fn start_encode(&self, spec: EncodeSpec) {
         let job_id = spec.job_id;
         let cancel = Arc::new(AtomicBool::new(false));
@@ -703,7 +978,25 @@ impl MediaWorker {
             // this prevents system lockups during 2K/4K CPU encodes.
             velocut_core::windows::lower_thread_priority();
 
-            encode_timeline(spec, cancel, tx);
+            // A panic inside the pipeline (an ffmpeg edge case hitting one
+            // of the `expect`s) would otherwise end the thread without an
+            // EncodeDone / EncodeError, leaving the modal at "Rendering…"
+            // forever. Report it as a failed render the user can retry.
+            let panic_tx = tx.clone();
+            if let Err(payload) =
+                catch_unwind(AssertUnwindSafe(move || encode_timeline(spec, cancel, tx)))
+            {
+                let what = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".into());
+                crate::media_log!("[encode] render thread panicked: {what}");
+                let _ = panic_tx.send(MediaResult::EncodeError {
+                    job_id,
+                    msg: format!("internal error: {what}"),
+                });
+            }
 
             cancels_ref.lock().unwrap().remove(&job_id);
         });
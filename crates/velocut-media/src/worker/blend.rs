@@ -8,7 +8,7 @@
 
 use std::path::PathBuf;
 
-use velocut_core::transitions::{registry, TransitionKind};
+use velocut_core::transitions::{registry, FadeCurve, TransitionKind};
 
 use crate::decode::LiveDecoder;
 
@@ -163,6 +163,7 @@ pub(super) fn blend_rgba_transition(
     h: u32,
     alpha: f32,
     kind: TransitionKind,
+    curve: FadeCurve,
 ) -> Vec<u8> {
     if kind == TransitionKind::Cut {
         return a.to_vec();
@@ -174,5 +175,5 @@ pub(super) fn blend_rgba_transition(
         .expect(
             "blend_rgba_transition: unregistered TransitionKind — add it to declare_transitions!",
         )
-        .apply_rgba(a, b, w, h, alpha)
+        .apply_rgba_curve(a, b, w, h, alpha, curve)
 }
@@ -4,7 +4,9 @@
 // handles transitions (blend), coast mode, and prebuffer.
 // Extracted from MediaWorker::new() in worker.rs.
 
-use std::time::Instant;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::{Receiver, Sender, TryRecvError};
 use uuid::Uuid;
@@ -19,6 +21,23 @@ use super::types::PlaybackCmd;
 pub(super) struct PbThread {
     pub cmd_rx: Receiver<PlaybackCmd>,
     pub frame_tx: Sender<PlaybackFrame>,
+    /// Read-ahead depth — see `MediaWorker::set_playback_ahead`.
+    pub ahead: Arc<AtomicU32>,
+}
+
+/// Hold the decoder while `ahead` or more frames are already queued, so the
+/// read-ahead depth can change at runtime without resizing the channel. Gives
+/// way as soon as a command is waiting: the send that follows then succeeds
+/// at once (the channel has room past `ahead`) and the loop reaches the
+/// command on its next pass, just as it does after a blocking send.
+fn wait_for_room(
+    frame_tx: &Sender<PlaybackFrame>,
+    cmd_rx: &Receiver<PlaybackCmd>,
+    ahead: &AtomicU32,
+) {
+    while frame_tx.len() >= ahead.load(Ordering::Relaxed) as usize && cmd_rx.is_empty() {
+        std::thread::sleep(Duration::from_millis(2));
+    }
 }
 
 impl PbThread {
@@ -26,6 +45,7 @@ impl PbThread {
         let Self {
             cmd_rx: pb_cmd_rx,
             frame_tx: pb_frame_tx,
+            ahead,
         } = self;
 
         let mut decoder: Option<(Uuid, LiveDecoder)> = None;
@@ -285,14 +305,14 @@ impl PbThread {
                                         Some(_)  => "ready",
                                     };
                                     crate::media_log!("[blend] frame={blend_frame_count} ts={ts_secs:.3} local_t={local_t:.3} alpha={alpha:.3} db={db_state}");
-                                    Some((b.spec.clip_b_path.clone(), b.spec.clip_b_source_start, alpha, b.spec.kind, b.aspect))
+                                    Some((b.spec.clip_b_path.clone(), b.spec.clip_b_source_start, alpha, b.spec.kind, b.spec.curve, b.aspect))
                                 } else {
                                     None
                                 }
                             })
                         };
 
-                        if let Some((_, _, alpha, _, _)) = &blend_params {
+                        if let Some((_, _, alpha, _, _, _)) = &blend_params {
                             last_blend_alpha = *alpha;
                         }
 
@@ -308,7 +328,7 @@ impl PbThread {
 
                         let blend_params = if blend_params
                             .as_ref()
-                            .map(|(_, _, a, _, _)| *a >= 1.0)
+                            .map(|(_, _, a, _, _, _)| *a >= 1.0)
                             .unwrap_or(false)
                         {
                             blend = None;
@@ -327,6 +347,7 @@ impl PbThread {
                             clip_b_start,
                             alpha,
                             kind,
+                            curve,
                             decoder_b_aspect,
                         )) = blend_params
                         {
@@ -371,7 +392,7 @@ impl PbThread {
                                                     if let Some(hb) = held_blend.as_ref() {
                                                         if hb.len() == data.len() {
                                                             return Some(blend_rgba_transition(
-                                                                hb, &data, w, h, alpha, kind,
+                                                                hb, &data, w, h, alpha, kind, curve,
                                                             ));
                                                         }
                                                     }
@@ -390,11 +411,11 @@ impl PbThread {
                                             }
                                             let blended = if invert {
                                                 blend_rgba_transition(
-                                                    &data_b, &data, w, h, alpha, kind,
+                                                    &data_b, &data, w, h, alpha, kind, curve,
                                                 )
                                             } else {
                                                 blend_rgba_transition(
-                                                    &data, &data_b, w, h, alpha, kind,
+                                                    &data, &data_b, w, h, alpha, kind, curve,
                                                 )
                                             };
                                             return Some(blended);
@@ -413,7 +434,7 @@ impl PbThread {
                                                         if hb.len() == data.len() {
                                                             crate::media_log!("[blend] still_burning animated: alpha={alpha:.3}");
                                                             return Some(blend_rgba_transition(
-                                                                hb, &data, w, h, alpha, kind,
+                                                                hb, &data, w, h, alpha, kind, curve,
                                                             ));
                                                         }
                                                     }
@@ -490,6 +511,7 @@ impl PbThread {
                         // gives the UI thread a chance to send Stop/Start commands.
                         // The UI always drains before/after sending Stop, so this
                         // cannot deadlock.
+                        wait_for_room(&pb_frame_tx, &pb_cmd_rx, &ahead);
                         if pb_frame_tx.send(f).is_err() {
                             return;
                         }
@@ -666,6 +688,7 @@ impl PbThread {
                                 coast_id = id;
                                 let bridge_duration = spec.duration;
                                 let bridge_kind = spec.kind;
+                                let bridge_curve = spec.curve;
                                 let mut bridge_ts = ts;
                                 // [Fix 1] BRIDGE_TARGET reduced from 28 → 4 to match
                                 // the new channel size of 6. The old 28/32 fill ratio
@@ -694,6 +717,7 @@ impl PbThread {
                                         coast_h,
                                         coast_last_alpha,
                                         bridge_kind,
+                                        bridge_curve,
                                     );
                                     crate::media_log!("[pb] bridge: ts={bridge_ts:.3} alpha={coast_last_alpha:.3} chan={}", pb_frame_tx.len());
                                     // [Fix 2] Move blended into held_blend, clone for send.
@@ -816,6 +840,7 @@ impl PbThread {
                             coast_h,
                             coast_last_alpha,
                             b.spec.kind,
+                            b.spec.curve,
                         ))
                     })();
 
@@ -840,6 +865,7 @@ impl PbThread {
                             data,
                         };
                         // Blocking send — rate-limits coast to UI consumption speed.
+                        wait_for_room(&pb_frame_tx, &pb_cmd_rx, &ahead);
                         if pb_frame_tx.send(f).is_err() {
                             return;
                         }
@@ -1,12 +1,15 @@
 // src/app.rs (velocut-ui)
 use crate::context::AppContext;
+use crate::helpers::alert;
 use crate::helpers::clip_query;
 use crate::helpers::memory_manager::MemoryManager;
+use crate::helpers::shell::open_with_system;
 use crate::modules::{
     audio_module::AudioModule,
     export_module::ExportModule,
     library::LibraryModule,
     preview_module::PreviewModule,
+    source_viewer::SourceViewerModule,
     timeline::TimelineModule,
     video_module::VideoModule,
     EditorModule, // must be in scope for .ui() calls on concrete module types
@@ -16,21 +19,50 @@ use crate::velocut_log;
 use eframe::egui;
 use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use uuid::Uuid;
 use velocut_core::commands::EditorCommand;
-use velocut_core::state::{ClipType, LibraryClip, ProjectState, TimelineClip};
+use velocut_core::helpers::edl::CutListFormat;
+use velocut_core::helpers::geometry::aspect_ratio_value;
+use velocut_core::helpers::storyboard::{plan_shots, StoryboardOptions};
+use velocut_core::helpers::time::format_time;
+use velocut_core::media_types::{
+    CoverFrame, DitherMode, ExportFormat, ExportMetadata, ExportTracks,
+};
+use velocut_core::prefs::{
+    LayoutPrefs, NotifyPrefs, PerformancePrefs, PlaybackPrefs, StoragePrefs,
+};
+use velocut_core::state::{
+    ClipType, ColorRangeMode, DeinterlaceMode, DropPart, LibraryClip, Marker, ProjectState,
+    TimelineClip,
+};
 use velocut_core::transitions::{
     ClipTransition, TimelineTransition, TransitionKind, TransitionType,
 };
-use velocut_media::audio::cleanup_audio_temp;
-use velocut_media::encode::AudioOverlay;
+use velocut_media::audio::{cleanup_audio_temp, clear_scratch, scratch_usage};
+use velocut_media::duck::duck_keys;
+use velocut_media::encode::{AudioOverlay, CoverArt, StabilizeSpec};
+use velocut_media::storyboard::{StoryboardCell, StoryboardSpec};
+use velocut_media::sync::SyncSource;
 use velocut_media::{ClipSpec, EncodeSpec, MediaWorker};
 
 #[derive(Serialize, Deserialize)]
 struct AppStorage {
     project: ProjectState,
+    /// Machine-level decode / cache limits. `default` so storage written
+    /// before the Performance section existed still loads.
+    #[serde(default)]
+    performance: PerformancePrefs,
+    #[serde(default)]
+    playback: PlaybackPrefs,
+    #[serde(default)]
+    layout: LayoutPrefs,
+    #[serde(default)]
+    storage: StoragePrefs,
+    #[serde(default)]
+    notify: NotifyPrefs,
 }
 
 // ── Undo / Redo ───────────────────────────────────────────────────────────────
@@ -50,6 +82,7 @@ pub struct VeloCutApp {
     // and makes typos a compile error instead of a silently blank panel.
     library: LibraryModule,
     preview: PreviewModule,
+    source_viewer: SourceViewerModule,
     timeline: TimelineModule,
     export: ExportModule,
     /// Stored separately so tick() calls the concrete method, not the trait default no-op.
@@ -64,8 +97,9 @@ pub struct VeloCutApp {
     // audio paths, playback position) are restored from the live state after
     // each undo/redo so they are unaffected by history navigation.
     // VecDeque so cap-eviction uses pop_front() — O(1) vs Vec::remove(0) O(N).
-    undo_stack: VecDeque<ProjectState>,
-    redo_stack: VecDeque<ProjectState>,
+    /// Snapshots paired with the label of the edit that followed them.
+    undo_stack: VecDeque<(ProjectState, &'static str)>,
+    redo_stack: VecDeque<(ProjectState, &'static str)>,
 
     /// True after the first rendered frame has checked the window size.
     /// eframe persists geometry between sessions — if a previous run left the
@@ -88,6 +122,19 @@ pub struct VeloCutApp {
     reset_done: bool,
 
     memory_manager: MemoryManager,
+
+    /// Poster frame last requested per timeline clip id. `poll_media` asks the
+    /// worker again whenever a clip's `poster_offset` differs from this (set,
+    /// moved, undone) and drops the texture when the poster is cleared.
+    poster_requested: HashMap<Uuid, f64>,
+
+    /// Output file of the running encode, for the render-finished
+    /// notification. Set by `start_encode`.
+    encode_output: Option<std::path::PathBuf>,
+
+    /// `total_duration()` as of the previous tick. When an edit shortens the
+    /// timeline past the playhead, `tick_modules` pulls the playhead back.
+    last_total: f64,
 }
 
 impl VeloCutApp {
@@ -104,18 +151,55 @@ impl VeloCutApp {
         // so EnumThreadWindows would find nothing to patch there. The call is deferred
         // to the first update() frame via the taskbar_icon_fixed flag.
 
-        let state = cc
+        let (mut state, performance, playback, layout, mut storage, notify) = cc
             .storage
             .and_then(|s| eframe::get_value::<AppStorage>(s, eframe::APP_KEY))
-            .map(|d| d.project)
+            .map(|d| {
+                (
+                    d.project,
+                    d.performance,
+                    d.playback,
+                    d.layout,
+                    d.storage,
+                    d.notify,
+                )
+            })
             .unwrap_or_default();
+        state.performance = performance.clamped();
+        state.playback_prefs = playback;
+        state.layout_prefs = layout;
+        if layout.always_on_top {
+            cc.egui_ctx.send_viewport_cmd(window_level(&layout));
+        }
+        state.notify_prefs = notify;
+        // A scratch folder on a since-unplugged drive falls back to temp.
+        if storage.validate().is_err() {
+            storage = StoragePrefs::default();
+        }
+        state.scratch_usage = Some(scratch_usage(&storage.resolved()));
+        state.storage_prefs = storage;
 
         let media_worker = MediaWorker::new();
+        // Before the probes below, so their WAVs land in the scratch folder.
+        media_worker.set_scratch_dir(state.storage_prefs.scratch_dir.clone());
+        // Re-probe lazily: media on the timeline now (playback needs its
+        // WAVs), everything else once its library card comes on screen —
+        // see the deferred pass in poll_media.
+        let on_timeline: HashSet<Uuid> = state.timeline.iter().map(|c| c.media_id).collect();
+        let mut deferred = Vec::new();
         for clip in &state.library {
-            media_worker.probe_clip(clip.id, clip.path.clone());
+            if on_timeline.contains(&clip.id) {
+                media_worker.probe_clip(clip.id, clip.path.clone(), clip.audio_stream_index);
+            } else {
+                deferred.push((clip.id, clip.path.clone()));
+            }
         }
+        state.deferred_probes = deferred;
+        state.refreshing = state.library.iter().map(|c| c.id).collect();
+        state.refresh_total = state.refreshing.len();
 
-        let context = AppContext::new(media_worker);
+        let mut context = AppContext::new(media_worker);
+        context.apply_performance_prefs(&state.performance, state.current_time);
         let library = LibraryModule::new();
         let timeline = TimelineModule::new();
         let memory_manager = MemoryManager::new();
@@ -125,6 +209,7 @@ impl VeloCutApp {
             context,
             library,
             preview: PreviewModule::new(),
+            source_viewer: SourceViewerModule::new(),
             timeline,
             export: ExportModule::default(),
             audio: AudioModule::new(),
@@ -135,7 +220,193 @@ impl VeloCutApp {
             taskbar_icon_fixed: false,
             reset_done: false,
             memory_manager,
+            poster_requested: HashMap::new(),
+            encode_output: None,
+            last_total: 0.0,
+        }
+    }
+
+    /// Whether any timeline clip sits on a video row. The aspect ratio follows
+    /// the first one that does, so a music bed placed first doesn't use up
+    /// the auto-detect.
+    fn timeline_has_picture(&self) -> bool {
+        self.state.timeline.iter().any(|c| c.track_row % 2 == 0)
+    }
+
+    /// Auto-set the project aspect ratio from the first video clip placed on
+    /// the timeline. No-op for audio clips or clips whose size isn't probed yet.
+    fn auto_aspect_from_clip(&mut self, media_id: Uuid) {
+        if let Some((width, height)) = self
+            .state
+            .library
+            .iter()
+            .find(|c| c.id == media_id)
+            .filter(|c| c.clip_type != ClipType::Audio)
+            .and_then(|c| c.video_size)
+        {
+            if width > 0 && height > 0 {
+                use velocut_core::state::AspectRatio;
+                let r = width as f32 / height as f32;
+                self.state.aspect_ratio = AspectRatio::from_ratio(r);
+                velocut_log!(
+                    "[app] aspect ratio auto-set from first timeline clip {width}x{height}"
+                );
+            }
+        }
+    }
+
+    /// Add an `[Audio]` library entry for a video source's audio so A-row
+    /// clips carry `ClipType::Audio` (no thumbnails, no video decode). Uses the
+    /// extracted WAV when ready; otherwise the source file with its chosen
+    /// stream — audio_module plays either. Returns the new entry's id.
+    fn push_audio_library_entry(&mut self, media_id: Uuid) -> Option<Uuid> {
+        let lib = self.state.library.iter().find(|l| l.id == media_id)?;
+        let wav_path = lib.audio_path.clone();
+        let entry = LibraryClip {
+            id: Uuid::new_v4(),
+            path: wav_path.clone().unwrap_or_else(|| lib.path.clone()),
+            name: format!("[Audio] {}", lib.name),
+            duration: lib.duration,
+            clip_type: ClipType::Audio,
+            thumbnail_path: None,
+            duration_probed: true,
+            waveform_peaks: lib.waveform_peaks.clone(),
+            video_size: None,
+            // The WAV already holds only the chosen stream; the
+            // source-file fallback must keep selecting it.
+            audio_stream_index: if wav_path.is_some() {
+                None
+            } else {
+                lib.audio_stream_index
+            },
+            audio_path: wav_path,
+            audio_trimmed_offset: 0.0,
+            audio_streams: Vec::new(),
+            color_range: None,
+            frame_rate: None,
+            file_bytes: None,
+            bit_rate: None,
+            probe_error: None,
+            color_range_mode: ColorRangeMode::Auto,
+            interlaced: None,
+            deinterlace_mode: DeinterlaceMode::Auto,
+        };
+        let id = entry.id;
+        self.state.library.push(entry);
+        Some(id)
+    }
+
+    /// Resolve what a library drop actually places. `AudioOnly` reuses an
+    /// existing `[Audio]` entry for the same source so repeated drops don't
+    /// pile up library cards. Returns `(media_id, mute_video)`.
+    fn resolve_drop_part(&mut self, media_id: Uuid, part: DropPart) -> Option<(Uuid, bool)> {
+        match part {
+            DropPart::Both => Some((media_id, false)),
+            DropPart::VideoOnly => Some((media_id, true)),
+            DropPart::AudioOnly => {
+                let lib = self.state.library.iter().find(|l| l.id == media_id)?;
+                let name = format!("[Audio] {}", lib.name);
+                let path = lib.audio_path.clone().unwrap_or_else(|| lib.path.clone());
+                let existing = self
+                    .state
+                    .library
+                    .iter()
+                    .find(|l| l.clip_type == ClipType::Audio && l.name == name && l.path == path)
+                    .map(|l| l.id);
+                existing
+                    .or_else(|| self.push_audio_library_entry(media_id))
+                    .map(|id| (id, false))
+            }
+        }
+    }
+
+    /// Shared body of `AddToTimeline` / `RippleInsertToTimeline`: resolve the
+    /// drop part, place the clip, mute it for video-only, and auto-set the
+    /// aspect ratio if it is the first video clip. Returns the new clip's id.
+    fn place_library_clip(
+        &mut self,
+        media_id: Uuid,
+        at_time: f64,
+        track_row: usize,
+        part: DropPart,
+        ripple: bool,
+    ) -> Option<Uuid> {
+        let (media_id, mute_video) = self.resolve_drop_part(media_id, part)?;
+        // Check *before* placement mutates the vec.
+        let is_first_clip = !self.timeline_has_picture();
+        let placed = if ripple {
+            self.state.ripple_insert(media_id, at_time, track_row)
+        } else {
+            self.state.add_to_timeline(media_id, at_time, track_row)
+        };
+        let id = placed?;
+        if mute_video {
+            if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                tc.audio_muted = true;
+            }
         }
+        if is_first_clip {
+            self.auto_aspect_from_clip(media_id);
+        }
+        Some(id)
+    }
+
+    /// `AddClipsToTimeline`: place `media_ids` end to end from `at_time`.
+    /// Video and stills go on the V row of `track_row`'s V/A pair, audio on
+    /// its A row, so a mixed batch interleaves between the two. Each clip
+    /// starts where the previous one actually landed, so one that butts
+    /// against a neighbour pushes the rest along with it.
+    fn place_library_clips(
+        &mut self,
+        media_ids: &[Uuid],
+        at_time: f64,
+        track_row: usize,
+        part: DropPart,
+        ripple: bool,
+    ) {
+        let v_row = (track_row - track_row % 2).min(2);
+        let mut at = at_time;
+        for &media_id in media_ids {
+            let Some(source) = self
+                .state
+                .library
+                .iter()
+                .find(|l| l.id == media_id)
+                .map(|l| l.clip_type)
+            else {
+                continue;
+            };
+            let part = if source == ClipType::Video {
+                part
+            } else {
+                DropPart::Both
+            };
+            let row = match part.clip_type(source) {
+                ClipType::Audio => v_row + 1,
+                ClipType::Video | ClipType::Image => v_row,
+            };
+            let Some(id) = self.place_library_clip(media_id, at, row, part, ripple) else {
+                continue;
+            };
+            if let Some(tc) = clip_query::timeline_clip(&self.state, id) {
+                at = tc.start_time + tc.duration;
+            }
+        }
+    }
+
+    /// Throw away every cached preview frame and re-request the one under the
+    /// playhead. Clearing `last_frame_req` makes the next video tick treat the
+    /// playhead as moved, so L2 issues a fresh decode at the current aspect.
+    fn invalidate_preview_frames(&mut self) {
+        self.context.cache.clear_frames();
+        self.context.playback.last_frame_req = None;
+        self.context.playback.scrub_coarse_req = None;
+        self.preview.current_frame = None;
+    }
+
+    /// Re-measure VeloCut's files in the scratch folder for the Storage prefs.
+    fn refresh_scratch_usage(&mut self) {
+        self.state.scratch_usage = Some(scratch_usage(&self.state.storage_prefs.resolved()));
     }
 
     // ── Undo / Redo helpers ───────────────────────────────────────────────────
@@ -143,11 +414,11 @@ impl VeloCutApp {
     /// Push the current state onto the undo stack and clear the redo stack.
     /// Called in response to `EditorCommand::PushUndoSnapshot`. Enforces the
     /// depth cap by discarding the oldest entry when over limit.
-    fn push_undo_snapshot(&mut self) {
+    fn push_undo_snapshot(&mut self, label: &'static str) {
         if self.undo_stack.len() >= MAX_UNDO_DEPTH {
             self.undo_stack.pop_front(); // drop oldest — O(1) with VecDeque
         }
-        self.undo_stack.push_back(self.state.clone());
+        self.undo_stack.push_back((self.state.clone(), label));
         self.redo_stack.clear();
         self.sync_undo_len();
     }
@@ -156,17 +427,19 @@ impl VeloCutApp {
     /// (encode progress, pending cleanup, playback time) from the live state
     /// so history navigation never interrupts an ongoing encode or playback.
     fn apply_undo(&mut self) {
-        if let Some(snapshot) = self.undo_stack.pop_back() {
+        // The label travels with the edit: undoing "Move clip" makes it the
+        // next redo.
+        if let Some((snapshot, label)) = self.undo_stack.pop_back() {
             let before = self.state.clone();
-            self.redo_stack.push_back(before);
+            self.redo_stack.push_back((before, label));
             self.restore_snapshot(snapshot);
         }
     }
 
     fn apply_redo(&mut self) {
-        if let Some(snapshot) = self.redo_stack.pop_back() {
+        if let Some((snapshot, label)) = self.redo_stack.pop_back() {
             let before = self.state.clone();
-            self.undo_stack.push_back(before);
+            self.undo_stack.push_back((before, label));
             self.restore_snapshot(snapshot);
         }
     }
@@ -182,13 +455,42 @@ impl VeloCutApp {
         snapshot.encode_job = self.state.encode_job;
         snapshot.encode_progress = self.state.encode_progress;
         snapshot.encode_done = self.state.encode_done.clone();
+        snapshot.encode_audio_peak = self.state.encode_audio_peak;
+        snapshot.encode_audio_codec = self.state.encode_audio_codec;
+        snapshot.encode_skipped_frames = self.state.encode_skipped_frames;
         snapshot.encode_error = self.state.encode_error.clone();
+        snapshot.nest_job = self.state.nest_job;
         // Drain pending queues from live state into the snapshot so they aren't lost.
         snapshot.pending_probes = std::mem::take(&mut self.state.pending_probes);
+        snapshot.deferred_probes = std::mem::take(&mut self.state.deferred_probes);
+        snapshot.refreshing = std::mem::take(&mut self.state.refreshing);
+        snapshot.refresh_total = self.state.refresh_total;
         snapshot.pending_extracts = std::mem::take(&mut self.state.pending_extracts);
         snapshot.pending_audio_cleanup = std::mem::take(&mut self.state.pending_audio_cleanup);
         snapshot.pending_save_pick = self.state.pending_save_pick.take();
         snapshot.save_status = self.state.save_status.take();
+        snapshot.timeline_scroll_to = self.state.timeline_scroll_to.take();
+        snapshot.match_frame = self.state.match_frame;
+        snapshot.scene_detecting = self.state.scene_detecting;
+        snapshot.scene_cuts = self.state.scene_cuts.take();
+        snapshot.source_viewer_clip = self.state.source_viewer_clip;
+        snapshot.silence_detecting = self.state.silence_detecting;
+        snapshot.silence_range = self.state.silence_range.take();
+        snapshot.sync_detecting = self.state.sync_detecting;
+        snapshot.audio_sync = self.state.audio_sync.take();
+        snapshot.duck_detecting = self.state.duck_detecting;
+        snapshot.speech_regions = self.state.speech_regions.take();
+        snapshot.performance = self.state.performance;
+        snapshot.playback_prefs = self.state.playback_prefs;
+        snapshot.layout_prefs = self.state.layout_prefs;
+        snapshot.storage_prefs = self.state.storage_prefs.clone();
+        snapshot.notify_prefs = self.state.notify_prefs;
+        snapshot.scratch_usage = self.state.scratch_usage;
+        snapshot.preview_rate = self.state.preview_rate;
+        snapshot.bypass_effects = self.state.bypass_effects;
+        snapshot.play_around = self.state.play_around;
+        snapshot.preview_histogram = std::mem::take(&mut self.state.preview_histogram);
+        let aspect_changed = snapshot.aspect_ratio != self.state.aspect_ratio;
 
         // Re-queue probes for any library clips whose waveform_peaks are empty
         // in the restored snapshot. This happens when the snapshot was taken while
@@ -196,10 +498,12 @@ impl VeloCutApp {
         // Without this, undoing ExtractAudioTrack before the first probe completes
         // leaves the video clip with no waveform even though audio_muted is cleared.
         for lib_clip in &snapshot.library {
-            if lib_clip.waveform_peaks.is_empty() {
+            // Stills never get peaks, so they would be re-probed on every undo.
+            if lib_clip.waveform_peaks.is_empty() && lib_clip.clip_type != ClipType::Image {
                 let already_queued = snapshot
                     .pending_probes
                     .iter()
+                    .chain(&snapshot.deferred_probes)
                     .any(|(id, _)| *id == lib_clip.id);
                 if !already_queued {
                     snapshot
@@ -209,16 +513,77 @@ impl VeloCutApp {
             }
         }
 
+        // Undoing an audio stream switch: the snapshot's WAV was already retired,
+        // so keep the live file until the re-extracted one arrives via AudioPath.
+        for lib_clip in &mut snapshot.library {
+            let Some(live) = self.state.library.iter().find(|c| c.id == lib_clip.id) else {
+                continue;
+            };
+            if lib_clip.audio_stream_index != live.audio_stream_index {
+                lib_clip.audio_path = live.audio_path.clone();
+                self.context.media_worker.reload_audio(
+                    lib_clip.id,
+                    lib_clip.path.clone(),
+                    lib_clip.audio_stream_index,
+                );
+            }
+        }
+
+        // Library entries the snapshot brings back were deleted: their WAV was
+        // retired and their thumbnail evicted, so probe them again from
+        // scratch. Their timeline clips come back with the snapshot itself.
+        for lib_clip in &mut snapshot.library {
+            if self.state.library.iter().any(|c| c.id == lib_clip.id) {
+                continue;
+            }
+            lib_clip.audio_path = None;
+            lib_clip.audio_trimmed_offset = 0.0;
+            let already_queued = snapshot
+                .pending_probes
+                .iter()
+                .chain(&snapshot.deferred_probes)
+                .any(|(id, _)| *id == lib_clip.id);
+            if !already_queued {
+                snapshot
+                    .pending_probes
+                    .push((lib_clip.id, lib_clip.path.clone()));
+            }
+        }
+        // Entries it drops (an undone import, a redone delete) are cleaned up
+        // the way DeleteLibraryClip does.
+        for lib_clip in &self.state.library {
+            if snapshot.library.iter().any(|c| c.id == lib_clip.id) {
+                continue;
+            }
+            if let Some(apath) = &lib_clip.audio_path {
+                snapshot.pending_audio_cleanup.push(apath.clone());
+            }
+            snapshot.pending_probes.retain(|(id, _)| *id != lib_clip.id);
+            snapshot
+                .deferred_probes
+                .retain(|(id, _)| *id != lib_clip.id);
+            if snapshot.source_viewer_clip == Some(lib_clip.id) {
+                snapshot.source_viewer_clip = None;
+            }
+            self.context.cache.remove_thumbnail(&lib_clip.id);
+        }
+
         self.state = snapshot;
+        if aspect_changed {
+            self.invalidate_preview_frames();
+        }
         self.sync_undo_len();
     }
 
-    /// Write undo/redo stack depths back into ProjectState so the timeline
-    /// module can read them for button enable/disable without needing extra
-    /// parameters threaded through the EditorModule trait.
+    /// Write undo/redo stack depths and next labels back into ProjectState so
+    /// the timeline module can read them for button enable/disable and
+    /// tooltips without needing extra parameters threaded through the
+    /// EditorModule trait.
     fn sync_undo_len(&mut self) {
         self.state.undo_len = self.undo_stack.len();
         self.state.redo_len = self.redo_stack.len();
+        self.state.undo_label = self.undo_stack.back().map(|(_, l)| l.to_string());
+        self.state.redo_label = self.redo_stack.back().map(|(_, l)| l.to_string());
     }
 
     // ── Command processing ────────────────────────────────────────────────────
@@ -226,8 +591,8 @@ impl VeloCutApp {
     fn process_command(&mut self, cmd: EditorCommand, ctx: &egui::Context) {
         match cmd {
             // ── Undo / Redo ──────────────────────────────────────────────────
-            EditorCommand::PushUndoSnapshot => {
-                self.push_undo_snapshot();
+            EditorCommand::PushUndoSnapshot(label) => {
+                self.push_undo_snapshot(label);
             }
             EditorCommand::Undo => {
                 self.apply_undo();
@@ -239,20 +604,37 @@ impl VeloCutApp {
             // ── Playback ─────────────────────────────────────────────────────
             EditorCommand::Play => {
                 let total = self.state.total_duration();
-                if total > 0.0 && self.state.current_time >= total - 0.1 {
+                // Also from the parked last frame, which can sit more than
+                // 0.1 s before the end on low-frame-rate sources.
+                let at_end = self.state.current_time >= total - 0.1
+                    || self.state.current_time >= clip_query::playback_end_time(&self.state);
+                if total > 0.0 && at_end {
                     self.state.current_time = 0.0;
                 }
                 self.state.is_playing = true;
+                self.state.play_around = None;
+            }
+            EditorCommand::PlayAround(at) => {
+                let (pre, post) = self.state.playback_prefs.roll_secs();
+                let total = self.state.total_duration();
+                let at = at.min(total);
+                // Same sink reset as a seek, then play from the pre-roll.
+                self.process_command(EditorCommand::SetPlayhead((at - pre).max(0.0)), ctx);
+                self.state.play_around = Some(((at + post).min(total), at));
+                self.state.is_playing = true;
             }
             EditorCommand::Pause => {
                 self.state.is_playing = false;
+                self.state.play_around = None;
             }
             EditorCommand::Stop => {
                 self.state.is_playing = false;
                 self.state.current_time = 0.0;
+                self.state.play_around = None;
             }
             EditorCommand::SetPlayhead(t) => {
                 self.state.current_time = t;
+                self.state.play_around = None;
                 self.context.audio_sinks.clear();
                 self.context.audio_overlay_sinks.clear();
                 self.context.playback.audio_was_playing = false;
@@ -262,6 +644,17 @@ impl VeloCutApp {
             EditorCommand::SetVolume(v) => {
                 self.state.volume = v;
             }
+            EditorCommand::SetPreviewRate(r) => {
+                self.state.preview_rate = r;
+            }
+            EditorCommand::SetEffectsBypass(on) => {
+                if self.state.bypass_effects != on {
+                    self.state.bypass_effects = on;
+                    // Cached frames have the filters baked in (or not) —
+                    // decode the playhead frame again the other way.
+                    self.invalidate_preview_frames();
+                }
+            }
             EditorCommand::ToggleMute => {
                 self.state.muted = !self.state.muted;
             }
@@ -270,6 +663,12 @@ impl VeloCutApp {
             EditorCommand::ImportFile(path) => {
                 self.state.add_to_library(path);
             }
+            EditorCommand::ImportProject { path, timeline_at } => {
+                self.import_project(&path, timeline_at);
+            }
+            EditorCommand::SaveProjectCopy(path) => {
+                self.save_project_copy(&path);
+            }
             EditorCommand::DeleteLibraryClip(id) => {
                 self.state.selected_library_clip = None;
                 if let Some(apath) = self
@@ -283,12 +682,26 @@ impl VeloCutApp {
                 }
                 self.state.library.retain(|c| c.id != id);
                 self.state.timeline.retain(|c| c.media_id != id);
+                if self.state.source_viewer_clip == Some(id) {
+                    self.state.source_viewer_clip = None;
+                }
+                self.state.deferred_probes.retain(|(d, _)| *d != id);
+                if self.state.refreshing.remove(&id) && self.state.refreshing.is_empty() {
+                    self.state.refresh_total = 0;
+                }
                 // Evict the thumbnail so deleted clips don't leak GPU memory.
                 // No refcount check needed — library entry is the sole owner.
                 self.context.cache.remove_thumbnail(&id);
             }
+            EditorCommand::ProbeDeferredMedia => {
+                let deferred = std::mem::take(&mut self.state.deferred_probes);
+                self.state.pending_probes.extend(deferred);
+            }
             EditorCommand::SelectLibraryClip(id) => {
                 self.state.selected_library_clip = id;
+                if self.state.match_frame.map(|(mid, _)| Some(mid)) != Some(id) {
+                    self.state.match_frame = None;
+                }
             }
 
             // ── Timeline ─────────────────────────────────────────────────────
@@ -296,28 +709,63 @@ impl VeloCutApp {
                 media_id,
                 at_time,
                 track_row,
+                part,
             } => {
-                // Auto-set aspect ratio from the first clip placed on the timeline.
-                // Check emptiness *before* add_to_timeline mutates the vec.
-                let is_first_clip = self.state.timeline.is_empty();
-                self.state.add_to_timeline(media_id, at_time, track_row);
-                if is_first_clip {
-                    if let Some((width, height)) = self
-                        .state
-                        .library
-                        .iter()
-                        .find(|c| c.id == media_id)
-                        .and_then(|c| c.video_size)
-                    {
-                        if width > 0 && height > 0 {
-                            use velocut_core::state::AspectRatio;
-                            let r = width as f32 / height as f32;
-                            self.state.aspect_ratio = AspectRatio::from_ratio(r);
-                            velocut_log!("[app] aspect ratio auto-set from first timeline clip {width}x{height}");
-                        }
+                self.place_library_clip(media_id, at_time, track_row, part, false);
+            }
+            EditorCommand::AddClipsToTimeline {
+                media_ids,
+                at_time,
+                track_row,
+                part,
+                ripple,
+            } => {
+                self.place_library_clips(&media_ids, at_time, track_row, part, ripple);
+            }
+            EditorCommand::RippleInsertToTimeline {
+                media_id,
+                at_time,
+                track_row,
+                part,
+            } => {
+                self.place_library_clip(media_id, at_time, track_row, part, true);
+            }
+            EditorCommand::SetSourceViewer(id) => {
+                self.state.source_viewer_clip = id;
+            }
+            EditorCommand::InsertSourceRange {
+                media_id,
+                source_in,
+                source_out,
+                at_time,
+            } => {
+                let is_first_clip = !self.timeline_has_picture();
+                if let Some(id) = self
+                    .state
+                    .insert_source_range(media_id, source_in, source_out, at_time, 0)
+                {
+                    self.state.selected_timeline_clip = Some(id);
+                    if is_first_clip {
+                        self.auto_aspect_from_clip(media_id);
                     }
                 }
             }
+            EditorCommand::AddMarker(t) => {
+                self.state.markers.push(Marker {
+                    id: Uuid::new_v4(),
+                    time: t,
+                });
+                self.state.markers.sort_by(|a, b| a.time.total_cmp(&b.time));
+            }
+            EditorCommand::MoveMarker { id, time } => {
+                if let Some(m) = self.state.markers.iter_mut().find(|m| m.id == id) {
+                    m.time = time;
+                }
+                self.state.markers.sort_by(|a, b| a.time.total_cmp(&b.time));
+            }
+            EditorCommand::DeleteMarker(id) => {
+                self.state.markers.retain(|m| m.id != id);
+            }
             EditorCommand::DeleteTimelineClip(id) => {
                 // If this clip is linked to a partner (extract audio pair),
                 // un-mute the partner so it doesn't silently stay muted.
@@ -362,45 +810,261 @@ impl VeloCutApp {
                     self.state.selected_timeline_clip = None;
                 }
             }
+            EditorCommand::SetClipAudioStream { media_id, stream } => {
+                if let Some(lib) = self.state.library.iter_mut().find(|c| c.id == media_id) {
+                    if lib.audio_stream_index != stream {
+                        lib.audio_stream_index = stream;
+                        // Waveform + WAV come back through the normal ingest path;
+                        // AudioPath retires the old file and its sinks.
+                        self.context
+                            .media_worker
+                            .reload_audio(media_id, lib.path.clone(), stream);
+                    }
+                }
+            }
+            EditorCommand::SetClipColorRange { media_id, mode } => {
+                if let Some(lib) = self.state.library.iter_mut().find(|c| c.id == media_id) {
+                    lib.color_range_mode = mode;
+                }
+            }
+            EditorCommand::SetClipDeinterlace { media_id, mode } => {
+                if let Some(lib) = self.state.library.iter_mut().find(|c| c.id == media_id) {
+                    lib.deinterlace_mode = mode;
+                }
+            }
+            EditorCommand::MatchFrame(clip_id) => {
+                if let Some((ts, lib_id)) = clip_query::match_frame_source(&self.state, clip_id)
+                    .map(|(ts, lib)| (ts, lib.id))
+                {
+                    self.library.multi_selection.clear();
+                    self.state.selected_library_clip = Some(lib_id);
+                    self.state.match_frame = Some((lib_id, ts));
+                }
+            }
+            EditorCommand::DetectScenes { id, threshold } => {
+                let source = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == id)
+                    .and_then(|tc| {
+                        self.state
+                            .library
+                            .iter()
+                            .find(|l| l.id == tc.media_id)
+                            .map(|l| (l.path.clone(), tc.source_offset, tc.duration))
+                    });
+                if let Some((path, from, dur)) = source {
+                    self.state.scene_detecting = Some(id);
+                    self.state.scene_cuts = None;
+                    self.media_worker
+                        .detect_scenes(id, path, from, from + dur, threshold);
+                }
+            }
+            EditorCommand::SplitAtScenes(id) => {
+                let cuts = match self.state.scene_cuts.take() {
+                    Some((cid, cuts)) if cid == id => cuts,
+                    other => {
+                        self.state.scene_cuts = other;
+                        Vec::new()
+                    }
+                };
+                let origin = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == id)
+                    .map(|c| c.start_time - c.source_offset);
+                if let Some(origin) = origin {
+                    // Last cut first: `split_clip_at` keeps the id on the head,
+                    // so every earlier cut still falls inside clip `id`.
+                    for &cut in cuts.iter().rev() {
+                        self.state.split_clip_at(id, origin + cut);
+                    }
+                }
+            }
+            EditorCommand::DetectSilence {
+                id,
+                threshold_db,
+                min_gap,
+            } => {
+                let source = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == id)
+                    .and_then(|tc| {
+                        self.state
+                            .library
+                            .iter()
+                            .find(|l| l.id == tc.media_id)
+                            .map(|l| {
+                                (
+                                    l.path.clone(),
+                                    l.audio_stream_index,
+                                    tc.source_offset,
+                                    tc.duration,
+                                )
+                            })
+                    });
+                if let Some((path, stream, from, dur)) = source {
+                    self.state.silence_detecting = Some(id);
+                    self.state.silence_range = None;
+                    self.media_worker.detect_silence(
+                        id,
+                        path,
+                        stream,
+                        from,
+                        from + dur,
+                        threshold_db,
+                        min_gap,
+                    );
+                }
+            }
+            EditorCommand::TrimSilence(id) => {
+                let sound = match self.state.silence_range.take() {
+                    Some((cid, Some(sound))) if cid == id => Some(sound),
+                    other => {
+                        self.state.silence_range = other;
+                        None
+                    }
+                };
+                let clip = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == id)
+                    .map(|c| (c.source_offset, c.source_offset + c.duration));
+                if let (Some((start, end)), Some((from, to))) = (sound, clip) {
+                    // Head first, then tail — both keep the clip's start time,
+                    // so the first word lands where the silence used to begin.
+                    let (start, end) = (start.max(from), end.min(to));
+                    self.state.trim_group_start(id, start, to - start);
+                    self.state.trim_group_end(id, end - start);
+                }
+            }
+            EditorCommand::DetectAudioSync { id, reference } => {
+                let source = |cid: Uuid| {
+                    let tc = self.state.timeline.iter().find(|c| c.id == cid)?;
+                    let lib = self.state.library.iter().find(|l| l.id == tc.media_id)?;
+                    Some(SyncSource {
+                        path: lib.path.clone(),
+                        audio_stream: lib.audio_stream_index,
+                        from: tc.source_offset,
+                        to: tc.source_offset + tc.duration,
+                    })
+                };
+                if let (Some(clip), Some(reference_src)) = (source(id), source(reference)) {
+                    self.state.sync_detecting = Some((id, reference));
+                    self.state.audio_sync = None;
+                    self.media_worker.detect_audio_sync(id, clip, reference_src);
+                }
+            }
+            EditorCommand::ApplyAudioSync(id) => {
+                let result = self.state.audio_sync.filter(|(c, _, _)| *c == id);
+                if let Some((_, reference, Some(offset))) = result {
+                    let start = self.state.synced_start(id, reference, offset);
+                    // Only an exact fit keeps the clips in sync; a move
+                    // nudged against a neighbor would not.
+                    if let Some(start) = start.filter(|&s| self.state.move_is_clear(id, s)) {
+                        self.state.audio_sync = None;
+                        let row = self
+                            .state
+                            .timeline
+                            .iter()
+                            .find(|c| c.id == id)
+                            .map_or(0, |c| c.track_row);
+                        self.state.move_group(id, start, row);
+                        self.context.playback.last_frame_req = None;
+                    }
+                }
+            }
+            EditorCommand::DetectSpeech {
+                id,
+                speech,
+                threshold_db,
+            } => {
+                let source = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == speech)
+                    .and_then(|tc| {
+                        self.state
+                            .library
+                            .iter()
+                            .find(|l| l.id == tc.media_id)
+                            .map(|l| {
+                                (
+                                    l.path.clone(),
+                                    l.audio_stream_index,
+                                    tc.source_offset,
+                                    tc.duration,
+                                )
+                            })
+                    });
+                if let Some((path, stream, from, dur)) = source {
+                    self.state.duck_detecting = Some((id, speech));
+                    self.state.speech_regions = None;
+                    self.media_worker.detect_speech(
+                        id,
+                        path,
+                        stream,
+                        from,
+                        from + dur,
+                        threshold_db,
+                    );
+                }
+            }
+            EditorCommand::ApplyDuck { id, duck } => {
+                let result = self.state.speech_regions.as_ref().filter(|r| r.clip == id);
+                let find = |cid: Uuid| self.state.timeline.iter().find(|c| c.id == cid);
+                let pair = result.and_then(|r| Some((find(id)?, find(r.speech)?, &r.regions)));
+                if let Some((music, talk, regions)) = pair {
+                    // Speech source time → timeline → music source time.
+                    let shift = (talk.start_time - talk.source_offset)
+                        - (music.start_time - music.source_offset);
+                    let (from, to) = (music.source_offset, music.source_offset + music.duration);
+                    let mapped: Vec<(f64, f64)> = regions
+                        .iter()
+                        .map(|&(a, b)| (a + shift, b + shift))
+                        .filter(|&(a, b)| b + duck.release > from && a - duck.attack < to)
+                        .collect();
+                    let keys = duck_keys(&mapped, &duck);
+                    self.state.speech_regions = None;
+                    if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                        tc.volume_keys = keys;
+                    }
+                }
+            }
+            EditorCommand::ClearVolumeKeys(id) => {
+                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                    tc.volume_keys.clear();
+                }
+            }
+            EditorCommand::NestRange { start, end } => {
+                self.begin_nest(start, end);
+            }
+            EditorCommand::UnnestClip(id) => {
+                if self.state.unnest(id) {
+                    self.context.playback.last_frame_req = None;
+                } else {
+                    velocut_log!("[nest] cannot un-nest {id}");
+                }
+            }
             EditorCommand::ExtractAudioTrack(clip_id) => {
-                // Gather library info BEFORE mutating state.
-                let lib_info = self
+                // Capture the source BEFORE mutating state.
+                let src_media_id = self
                     .state
                     .timeline
                     .iter()
                     .find(|c| c.id == clip_id)
-                    .and_then(|tc| self.state.library.iter().find(|l| l.id == tc.media_id))
-                    .map(|lib| {
-                        (
-                            lib.path.clone(),
-                            lib.audio_path.clone(),
-                            lib.waveform_peaks.clone(),
-                            lib.duration,
-                            lib.name.clone(),
-                        )
-                    });
+                    .map(|tc| tc.media_id);
 
-                if let Some((src_path, wav_path, peaks, duration, src_name)) = lib_info {
-                    if let Some(audio_clip_id) = self.state.extract_audio_track(clip_id) {
-                        // Create a dedicated Audio library entry so the A-row clip
-                        // has the correct type (no thumbnails, no video decode).
-                        // Use the already-extracted WAV as the source path if ready;
-                        // fall back to the original file (audio_module will use it fine).
-                        let audio_lib_id = Uuid::new_v4();
-                        let effective_path = wav_path.clone().unwrap_or_else(|| src_path.clone());
-                        self.state.library.push(LibraryClip {
-                            id: audio_lib_id,
-                            path: effective_path,
-                            name: format!("[Audio] {src_name}"),
-                            duration,
-                            clip_type: ClipType::Audio,
-                            thumbnail_path: None,
-                            duration_probed: true,
-                            waveform_peaks: peaks,
-                            video_size: None,
-                            audio_path: wav_path,
-                            audio_trimmed_offset: 0.0,
-                        });
+                if let (Some(src_media_id), Some(audio_clip_id)) =
+                    (src_media_id, self.state.extract_audio_track(clip_id))
+                {
+                    if let Some(audio_lib_id) = self.push_audio_library_entry(src_media_id) {
                         // Rewire the A-row timeline clip to the audio library entry.
                         if let Some(tc) = self
                             .state
@@ -413,11 +1077,41 @@ impl VeloCutApp {
                     }
                 }
             }
+            EditorCommand::LinkOverlapping(id) => {
+                if let Some(clip) = self.state.timeline.iter().find(|c| c.id == id) {
+                    let (row, start, end) = (
+                        clip.track_row,
+                        clip.start_time,
+                        clip.start_time + clip.duration,
+                    );
+                    let mut ids: Vec<Uuid> = self
+                        .state
+                        .timeline
+                        .iter()
+                        .filter(|o| {
+                            o.track_row != row
+                                && o.start_time < end
+                                && start < o.start_time + o.duration
+                        })
+                        .map(|o| o.id)
+                        .collect();
+                    ids.push(id);
+                    self.state.link_clips(&ids);
+                }
+            }
+            EditorCommand::UnlinkClip(id) => {
+                self.state.unlink_clip(id);
+            }
             EditorCommand::SetClipVolume { id, volume } => {
                 if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
                     tc.volume = volume.clamp(0.0, 2.0);
                 }
             }
+            EditorCommand::SetClipMuted { id, muted } => {
+                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                    tc.audio_muted = muted;
+                }
+            }
             EditorCommand::SetClipFadeIn { id, secs } => {
                 if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
                     tc.fade_in_secs = secs.max(0.0);
@@ -459,6 +1153,31 @@ impl VeloCutApp {
                 self.context.playback.last_frame_req = None;
                 ctx.request_repaint();
             }
+            EditorCommand::SetClipEnabled { id, enabled } => {
+                self.state.set_group_enabled(id, enabled);
+                // The frame under the playhead may have just appeared or gone.
+                self.context.playback.last_frame_req = None;
+            }
+            EditorCommand::SetClipStabilize { id, stabilize } => {
+                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                    tc.stabilize = stabilize;
+                }
+            }
+            EditorCommand::SetClipTag {
+                id,
+                tag_color,
+                note,
+            } => {
+                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                    tc.tag_color = tag_color;
+                    tc.note = note;
+                }
+            }
+            EditorCommand::SetClipPoster { id, at } => {
+                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
+                    tc.poster_offset = at;
+                }
+            }
             EditorCommand::SelectTimelineClip(id) => {
                 self.state.selected_timeline_clip = id;
             }
@@ -467,69 +1186,46 @@ impl VeloCutApp {
                 new_start,
                 new_row,
             } => {
-                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
-                    tc.start_time = new_start;
-                    tc.track_row = new_row;
-                }
+                self.state.move_group(id, new_start, new_row);
             }
             EditorCommand::SplitClipAt(t) => {
                 // Find a clip that contains t with enough room on each side to be
-                // worth splitting (> 2 frames from either edge at 30fps).
-                let min_dur = 2.0 / 30.0;
-                if let Some(clip) = self
-                    .state
-                    .timeline
-                    .iter()
-                    .find(|c| t > c.start_time + min_dur && t < c.start_time + c.duration - min_dur)
-                    .cloned()
-                {
-                    let split_offset = t - clip.start_time; // seconds into clip
-
-                    // Shorten the original clip to become the first half.
-                    // Clear its fade_out — that belongs to the new tail segment now.
-                    if let Some(c) = self.state.timeline.iter_mut().find(|c| c.id == clip.id) {
-                        c.duration = split_offset;
-                        c.fade_out_secs = 0.0;
-                        c.fade_out_end_secs = 0.0;
-                    }
-
-                    // Push the second half as a new clip immediately after.
-                    self.state.timeline.push(TimelineClip {
-                        id: Uuid::new_v4(),
-                        media_id: clip.media_id,
-                        start_time: t,
-                        duration: clip.duration - split_offset,
-                        source_offset: clip.source_offset + split_offset,
-                        track_row: clip.track_row,
-                        volume: clip.volume,
-                        linked_clip_id: None,
-                        audio_muted: clip.audio_muted,
-                        fade_in_secs: 0.0,
-                        fade_in_start_secs: 0.0,
-                        fade_out_secs: clip.fade_out_secs,
-                        fade_out_end_secs: clip.fade_out_end_secs,
-                        filter: Default::default(),
-                    });
-                    // Any transition keyed on clip.id (original → its successor)
+                // worth splitting (> 2 project frames from either edge). The S
+                // key has no disabled state, so say why nothing happened.
+                if let Some(reason) = self.state.split_blocker(t) {
+                    self.state.save_status = Some(format!("✕ Can't split: {reason}"));
+                }
+                if let Some(id) = self.state.splittable_clip_at(t).map(|c| c.id) {
+                    self.state.split_group_at(id, t);
+                    // Any transition keyed on the original id (original → its successor)
                     // remains valid — the badge system renders from clip positions,
-                    // so the badge will now appear between new_clip and the old
+                    // so the badge will now appear between the new tail and the old
                     // successor. No transition cleanup needed.
                 }
             }
+            EditorCommand::InsertFreezeFrame { at, duration } => {
+                if let Some(id) = self.state.insert_freeze_frame(at, duration) {
+                    self.state.selected_timeline_clip = Some(id);
+                }
+            }
+            EditorCommand::InsertSlate {
+                at,
+                duration,
+                color,
+            } => {
+                self.state.selected_timeline_clip =
+                    Some(self.state.insert_slate(at, duration, color));
+            }
             EditorCommand::TrimClipStart {
                 id,
                 new_source_offset,
                 new_duration,
             } => {
-                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
-                    tc.source_offset = new_source_offset;
-                    tc.duration = new_duration;
-                }
+                self.state
+                    .trim_group_start(id, new_source_offset, new_duration);
             }
             EditorCommand::TrimClipEnd { id, new_duration } => {
-                if let Some(tc) = self.state.timeline.iter_mut().find(|c| c.id == id) {
-                    tc.duration = new_duration;
-                }
+                self.state.trim_group_end(id, new_duration);
             }
 
             // ── Export ───────────────────────────────────────────────────────
@@ -538,8 +1234,41 @@ impl VeloCutApp {
                 width,
                 height,
                 fps,
+                format,
+                verbose_log,
+                error_resilient,
+                dither,
+                target_kbps,
+                limit_secs,
+                next_to_source,
+                metadata,
+                tracks,
+            } => {
+                self.begin_render(
+                    filename,
+                    width,
+                    height,
+                    fps,
+                    format,
+                    verbose_log,
+                    error_resilient,
+                    dither,
+                    target_kbps,
+                    limit_secs,
+                    next_to_source,
+                    metadata,
+                    tracks,
+                );
+            }
+            EditorCommand::ExportCutList {
+                filename,
+                fps,
+                format,
             } => {
-                self.begin_render(filename, width, height, fps);
+                self.export_cut_list(filename, fps, format);
+            }
+            EditorCommand::ExportStoryboard { filename, options } => {
+                self.export_storyboard(filename, options);
             }
             EditorCommand::CancelEncode(job_id) => {
                 self.context.media_worker.cancel_encode(job_id);
@@ -547,12 +1276,27 @@ impl VeloCutApp {
                 // ("cancelled") to arrive over the channel so the UI transition is
                 // driven by the same path as a real error (avoids race conditions).
             }
+            EditorCommand::OpenExternally(path) => {
+                if let Err(e) = open_with_system(&path) {
+                    velocut_log!("[export] could not open {:?}: {e}", path);
+                    self.state.save_status = Some(format!("✕ Could not open file: {e}"));
+                }
+            }
             EditorCommand::ClearEncodeStatus => {
                 self.state.encode_job = None;
                 self.state.encode_progress = None;
                 self.state.encode_done = None;
+                self.state.encode_audio_peak = None;
+                self.state.encode_audio_codec = None;
+                self.state.encode_skipped_frames = 0;
                 self.state.encode_error = None;
             }
+            EditorCommand::SetWatermark(wm) => {
+                self.state.watermark = wm;
+            }
+            EditorCommand::SetBeatGrid(grid) => {
+                self.state.beat_grid = grid;
+            }
 
             // ── Project reset ─────────────────────────────────────────────────
             EditorCommand::ClearProject => {
@@ -566,6 +1310,9 @@ impl VeloCutApp {
                 self.state.library.clear();
                 self.state.timeline.clear();
                 self.state.transitions.clear();
+                self.state.deferred_probes.clear();
+                self.state.refreshing.clear();
+                self.state.refresh_total = 0;
                 self.state.selected_timeline_clip = None;
                 self.state.selected_library_clip = None;
                 self.state.current_time = 0.0;
@@ -575,6 +1322,9 @@ impl VeloCutApp {
                 self.state.encode_job = None;
                 self.state.encode_progress = None;
                 self.state.encode_done = None;
+                self.state.encode_audio_peak = None;
+                self.state.encode_audio_codec = None;
+                self.state.encode_skipped_frames = 0;
                 self.state.encode_error = None;
 
                 // Clear undo/redo — stale snapshots waste memory and there is
@@ -657,11 +1407,62 @@ impl VeloCutApp {
 
             // ── View / UI ────────────────────────────────────────────────────
             EditorCommand::SetAspectRatio(ar) => {
-                self.state.aspect_ratio = ar;
+                if self.state.aspect_ratio != ar {
+                    self.state.aspect_ratio = ar;
+                    self.invalidate_preview_frames();
+                    ctx.request_repaint();
+                }
+            }
+            EditorCommand::SetProjectFps { fps, conform } => {
+                if conform {
+                    self.state.conform_to_fps(fps);
+                    self.state.save_status = Some(format!("✓ Conformed timeline to {fps} fps"));
+                } else {
+                    self.state.project_fps = fps;
+                }
             }
             EditorCommand::SetTimelineZoom(z) => {
                 self.state.timeline_zoom = z;
             }
+            EditorCommand::ScrollTimelineTo(t) => {
+                self.state.timeline_scroll_to = Some(t);
+            }
+            EditorCommand::SetPerformancePrefs(prefs) => {
+                self.state.performance = prefs;
+                self.context
+                    .apply_performance_prefs(&prefs, self.state.current_time);
+            }
+            EditorCommand::SetLayoutPrefs(prefs) => {
+                if prefs.always_on_top != self.state.layout_prefs.always_on_top {
+                    ctx.send_viewport_cmd(window_level(&prefs));
+                }
+                self.state.layout_prefs = prefs;
+            }
+            EditorCommand::SetStoragePrefs(prefs) => {
+                self.context.apply_storage_prefs(&prefs);
+                self.state.storage_prefs = prefs;
+                self.refresh_scratch_usage();
+            }
+            EditorCommand::ClearScratchCache => {
+                let keep: Vec<std::path::PathBuf> = self
+                    .state
+                    .library
+                    .iter()
+                    .filter_map(|c| c.audio_path.clone())
+                    .collect();
+                let (files, bytes) = clear_scratch(&self.state.storage_prefs.resolved(), &keep);
+                velocut_log!("[storage] cleared {files} scratch files ({bytes} bytes)");
+                self.refresh_scratch_usage();
+            }
+            EditorCommand::RefreshScratchUsage => {
+                self.refresh_scratch_usage();
+            }
+            EditorCommand::SetPlaybackPrefs(prefs) => {
+                self.state.playback_prefs = prefs;
+            }
+            EditorCommand::SetNotifyPrefs(prefs) => {
+                self.state.notify_prefs = prefs;
+            }
             EditorCommand::ClearSaveStatus => {
                 self.state.save_status = None;
             }
@@ -688,7 +1489,22 @@ impl VeloCutApp {
     /// This mirrors the pattern used by pending_save_pick / RequestSaveFramePicker:
     /// blocking OS dialogs are fine here because process_command runs after the UI
     /// pass, not inside an egui callback.
-    fn begin_render(&mut self, filename: String, width: u32, height: u32, fps: u32) {
+    fn begin_render(
+        &mut self,
+        filename: String,
+        width: u32,
+        height: u32,
+        fps: u32,
+        format: ExportFormat,
+        verbose_log: bool,
+        error_resilient: bool,
+        dither: DitherMode,
+        target_kbps: Option<u32>,
+        limit_secs: Option<f64>,
+        next_to_source: bool,
+        metadata: ExportMetadata,
+        tracks: ExportTracks,
+    ) {
         // Abort silently if an encode is already running.
         // ExportModule disables the button while is_encoding, but guard here too.
         if self.state.encode_job.is_some() {
@@ -696,26 +1512,127 @@ impl VeloCutApp {
             return;
         }
 
-        let default_name = format!("{filename}.mp4");
-        let dest = match FileDialog::new()
-            .set_file_name(&default_name)
-            .add_filter("MP4 Video", &["mp4"])
-            .save_file()
-        {
-            Some(p) => p,
-            None => return, // user cancelled the dialog — no-op
+        // A single audio track renders a WAV stem instead of a movie.
+        let stem = tracks.is_audio_stem();
+        let output_path = |p: &Path| {
+            if stem {
+                p.with_extension("wav")
+            } else {
+                format.output_path(p)
+            }
+        };
+        let dest = if limit_secs.is_some() {
+            // Test renders are throwaway: fixed temp path (the `velocut_`
+            // prefix lets the temp-file reset sweep it), no dialog.
+            output_path(&std::env::temp_dir().join("velocut_test_render"))
+        } else if next_to_source {
+            let Some(dir) = clip_query::first_source_path(&self.state).and_then(|p| p.parent())
+            else {
+                velocut_log!("[export] no source folder for \"save next to source\"");
+                return;
+            };
+            next_free_path(output_path(&dir.join(&filename)))
+        } else {
+            // A filename typed as "cut.mp4" while MKV is selected must not become
+            // "cut.mp4.mkv", and a name picked in the dialog without the extension
+            // would otherwise hand `open_output` the wrong (or no) muxer.
+            let default_name = output_path(Path::new(&filename))
+                .to_string_lossy()
+                .into_owned();
+            let (filter_name, ext) = if stem {
+                ("WAV audio", "wav")
+            } else {
+                (format.dialog_filter_name(), format.extension())
+            };
+            let picked = match FileDialog::new()
+                .set_file_name(&default_name)
+                .add_filter(filter_name, &[ext])
+                .save_file()
+            {
+                Some(p) => p,
+                None => return, // user cancelled the dialog — no-op
+            };
+            let dest = output_path(&picked);
+            if dest != picked {
+                velocut_log!(
+                    "[export] {:?} → {:?} (extension follows {})",
+                    picked,
+                    dest,
+                    format.name()
+                );
+            }
+            dest
+        };
+
+        // Test render: cut the timeline at `limit_secs`.
+        let truncated: Vec<TimelineClip> = match limit_secs {
+            Some(limit) => clip_query::clips_in_range(&self.state, 0.0, limit),
+            None => Vec::new(),
+        };
+        let source = if limit_secs.is_some() {
+            &truncated
+        } else {
+            &self.state.timeline
         };
 
         // Sort by start_time using refs — avoids cloning all clip data.
-        let mut sorted: Vec<&TimelineClip> = self.state.timeline.iter().collect();
+        // A single-track export keeps only that row; a V-row's linked audio
+        // partners are looked up in `self.state`, so its clips keep their sound.
+        let mut sorted: Vec<&TimelineClip> = source
+            .iter()
+            .filter(|tc| match tracks {
+                ExportTracks::All => true,
+                ExportTracks::Only(row) => tc.track_row == row,
+            })
+            .collect();
         sorted.sort_unstable_by(|a, b| a.start_time.total_cmp(&b.start_time));
 
-        let Some((clip_specs, encode_transitions, audio_overlays)) =
+        // Drop clips that round to zero output frames. They would encode nothing
+        // yet still claim a transition slot; build_encode_plan indexes transitions
+        // by position in the surviving V-row list, so removing them here
+        // re-indexes the remaining transitions automatically.
+        let (kept, skipped): (Vec<&TimelineClip>, Vec<&TimelineClip>) = sorted
+            .into_iter()
+            .partition(|tc| (tc.duration * fps as f64).round() >= 1.0);
+        let sorted = kept;
+        if !skipped.is_empty() {
+            let names: Vec<String> = skipped
+                .iter()
+                .map(|tc| {
+                    let name = clip_query::library_entry_for(&self.state, tc)
+                        .map(|l| l.name.as_str())
+                        .unwrap_or("Unknown");
+                    format!("{name} @ {:.3}s", tc.start_time)
+                })
+                .collect();
+            velocut_log!(
+                "[export] skipping {} clip(s) shorter than one output frame at {fps} fps: {}",
+                skipped.len(),
+                names.join(", ")
+            );
+        }
+
+        // A stem is every sounding clip on the row as an overlay and no
+        // video clips; encode_timeline writes that as WAV.
+        let plan = if stem {
+            let overlays: Vec<AudioOverlay> = sorted
+                .iter()
+                .filter(|tc| tc.enabled && !tc.audio_muted)
+                .filter_map(|tc| audio_overlay(&self.state, tc))
+                .collect();
+            (!overlays.is_empty()).then(|| (Vec::new(), Vec::new(), overlays))
+        } else {
             build_encode_plan(&self.state, &sorted)
-        else {
+        };
+        let Some((clip_specs, encode_transitions, audio_overlays)) = plan else {
             velocut_log!("[export] no resolvable clips — aborting render");
             return;
         };
+        let cover = if format.supports_cover_art() && !stem {
+            resolve_cover(&self.state, metadata.cover, &clip_specs)
+        } else {
+            None
+        };
 
         let job_id = Uuid::new_v4();
         let spec = EncodeSpec {
@@ -727,19 +1644,335 @@ impl VeloCutApp {
             output: dest,
             transitions: encode_transitions,
             audio_overlays,
+            format,
+            watermark: self.state.watermark.clone(),
+            verbose_log,
+            error_resilient,
+            dither,
+            target_kbps,
+            metadata,
+            cover,
         };
 
-        // Arm encode state before handing to the worker so ingest_media_results
-        // can route EncodeProgress into the right fields immediately.
-        self.state.encode_job = Some(job_id);
-        self.state.encode_progress =
-            Some((0, (self.state.total_duration() * fps as f64).ceil() as u64));
+        let render_secs = match limit_secs {
+            Some(limit) => self.state.total_duration().min(limit),
+            None => self.state.total_duration(),
+        };
+        // Two-pass encodes report both passes on one bar.
+        let passes = if target_kbps.is_some() && format.supports_two_pass() {
+            2
+        } else {
+            1
+        };
+        self.start_encode(spec, (render_secs * fps as f64).ceil() as u64 * passes);
+    }
+
+    /// Arm encode state and hand `spec` to the worker. Armed first so
+    /// ingest_media_results can route EncodeProgress into the right fields
+    /// immediately.
+    fn start_encode(&mut self, spec: EncodeSpec, total_frames: u64) {
+        self.state.encode_job = Some(spec.job_id);
+        self.state.encode_progress = Some((0, total_frames));
         self.state.encode_done = None;
+        self.state.encode_audio_peak = None;
+        self.state.encode_audio_codec = None;
+        self.state.encode_skipped_frames = 0;
         self.state.encode_error = None;
+        self.encode_output = Some(spec.output.clone());
 
         self.context.media_worker.start_encode(spec);
     }
 
+    /// Render `start..end` to a scratch file for `NestRange`. The clips are
+    /// swapped for the render by `finish_nest` once the job is done.
+    ///
+    /// Output is 1080 lines at the project aspect ratio and the first
+    /// source's frame rate, without the watermark — export burns that in
+    /// over the nested clip like any other.
+    fn begin_nest(&mut self, start: f64, end: f64) {
+        if self.state.encode_job.is_some() {
+            velocut_log!("[nest] ignoring NestRange: encode already in progress");
+            return;
+        }
+        if !start.is_finite() || end <= start || !self.state.video_covers(start, end) {
+            velocut_log!("[nest] {start:.3}..{end:.3}s has a gap in the video tracks");
+            return;
+        }
+
+        let height = 1080_u32;
+        let ratio = aspect_ratio_value(self.state.aspect_ratio) as f64;
+        let width = ((height as f64 * ratio / 2.0).round() as u32 * 2).max(2);
+        let fps = clip_query::first_source_clip(&self.state)
+            .and_then(|l| l.frame_rate)
+            .map_or(30, |r| (r.round() as u32).clamp(1, 120));
+
+        let range = clip_query::clips_in_range(&self.state, start, end);
+        let mut sorted: Vec<&TimelineClip> = range
+            .iter()
+            .filter(|tc| (tc.duration * fps as f64).round() >= 1.0)
+            .collect();
+        sorted.sort_unstable_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        let Some((clip_specs, encode_transitions, audio_overlays)) =
+            build_encode_plan(&self.state, &sorted)
+        else {
+            velocut_log!("[nest] no resolvable clips in {start:.3}..{end:.3}s");
+            return;
+        };
+
+        let job_id = Uuid::new_v4();
+        let format = ExportFormat::Mp4;
+        let output = format.output_path(
+            &self
+                .state
+                .storage_prefs
+                .resolved()
+                .join(format!("velocut_nest_{job_id}")),
+        );
+        velocut_log!("[nest] rendering {start:.3}..{end:.3}s → {:?}", output);
+        let spec = EncodeSpec {
+            job_id,
+            clips: clip_specs,
+            width,
+            height,
+            fps,
+            output,
+            transitions: encode_transitions,
+            audio_overlays,
+            format,
+            watermark: None,
+            verbose_log: false,
+            error_resilient: false,
+            dither: DitherMode::default(),
+            target_kbps: None,
+            metadata: ExportMetadata::default(),
+            cover: None,
+        };
+        self.state.nest_job = Some((job_id, start, end));
+        self.start_encode(spec, ((end - start) * fps as f64).ceil() as u64);
+    }
+
+    /// Chime and/or post an OS notification when the encode that just
+    /// reported in is a user render. Nest renders (swapped in silently by
+    /// `finish_nest`) and cancels stay quiet; the notification is skipped
+    /// while the window has focus since the render modal already shows.
+    fn alert_render_finished(&self, ctx: &egui::Context) {
+        let error = match (&self.state.encode_done, &self.state.encode_error) {
+            (Some(_), _) => None,
+            (None, Some(msg)) if msg != "cancelled" => Some(msg.clone()),
+            _ => return,
+        };
+        if self.state.nest_job.is_some() {
+            return;
+        }
+        let prefs = self.state.notify_prefs;
+        if prefs.chime {
+            if let Some(stream) = &self.context.audio_stream {
+                alert::play_chime(stream.mixer(), error.is_none());
+            }
+        }
+        let focused = ctx.input(|i| i.viewport().focused.unwrap_or(true));
+        if prefs.notification && !focused {
+            let output = self
+                .state
+                .encode_done
+                .as_ref()
+                .or(self.encode_output.as_ref());
+            alert::notify_render_finished(output.map(|p| p.as_path()), error.as_deref());
+        }
+    }
+
+    /// Swap a finished nest render in for the range it was made from, as one
+    /// undo step. A failed or cancelled render just drops the job; its error
+    /// stays up in the render modal.
+    fn finish_nest(&mut self) {
+        let Some((job_id, start, end)) = self.state.nest_job else {
+            return;
+        };
+        if self.state.encode_job != Some(job_id) || self.state.encode_error.is_some() {
+            self.state.nest_job = None;
+            return;
+        }
+        let Some(path) = self.state.encode_done.clone() else {
+            return;
+        };
+        self.state.nest_job = None;
+        self.state.encode_job = None;
+        self.state.encode_progress = None;
+        self.state.encode_done = None;
+        self.state.encode_audio_peak = None;
+        self.state.encode_audio_codec = None;
+        self.state.encode_skipped_frames = 0;
+
+        // The timeline may have changed while rendering.
+        if !self.state.video_covers(start, end) {
+            velocut_log!("[nest] {start:.3}..{end:.3}s changed during the render — not nesting");
+            return;
+        }
+        self.push_undo_snapshot("Nest clips");
+        let media_id = self.state.add_to_library(path);
+        let fps = self.state.project_fps;
+        if let Some(lib) = self.state.library.iter_mut().find(|l| l.id == media_id) {
+            lib.name = format!("Nest {}–{}", format_time(start, fps), format_time(end, fps));
+        }
+        self.state.nest_range(start, end, media_id);
+        self.context.playback.last_frame_req = None;
+    }
+
+    /// Read a project JSON and merge it via `ProjectState::import_project`,
+    /// reporting the outcome in the timeline toolbar status.
+    fn import_project(&mut self, path: &Path, timeline_at: Option<f64>) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let parsed = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                serde_json::from_str::<ProjectState>(&text).map_err(|e| e.to_string())
+            });
+        match parsed {
+            Ok(other) => {
+                let (media, clips) = self.state.import_project(other, timeline_at);
+                velocut_log!(
+                    "[project] imported {media} media, {clips} clips ← {:?}",
+                    path
+                );
+                self.state.save_status = Some(format!(
+                    "✓ Imported {media} media, {clips} clips from {name}"
+                ));
+            }
+            Err(e) => {
+                velocut_log!("[project] import failed {:?}: {e}", path);
+                self.state.save_status = Some(format!("✕ Could not import {name}: {e}"));
+            }
+        }
+    }
+
+    /// The project as it should be persisted. `audio_path` points to temp WAVs
+    /// that are deleted on exit — cleared so they don't resurrect as dead
+    /// paths when the project is loaded again.
+    fn persisted_project(&self) -> ProjectState {
+        let mut project = self.state.clone();
+        for clip in &mut project.library {
+            clip.audio_path = None;
+        }
+        project
+    }
+
+    /// Write the project JSON to `path`. The running session keeps its own
+    /// state — this is a snapshot, not Save As.
+    fn save_project_copy(&mut self, path: &Path) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let written = serde_json::to_string_pretty(&self.persisted_project())
+            .map_err(|e| e.to_string())
+            .and_then(|text| std::fs::write(path, text).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => {
+                velocut_log!("[project] copy saved → {:?}", path);
+                self.state.save_status = Some(format!("✓ Saved a copy: {name}"));
+            }
+            Err(e) => {
+                velocut_log!("[project] copy failed {:?}: {e}", path);
+                self.state.save_status = Some(format!("✕ Could not save {name}: {e}"));
+            }
+        }
+    }
+
+    /// Ask for a destination and write the timeline as an EDL / JSON cut list.
+    /// Runs synchronously — the output is a few KB of text, no worker needed.
+    fn export_cut_list(&mut self, filename: String, fps: u32, format: CutListFormat) {
+        let default_name = format!("{filename}.{}", format.extension());
+        let Some(dest) = FileDialog::new()
+            .set_file_name(&default_name)
+            .add_filter(format.dialog_filter_name(), &[format.extension()])
+            .save_file()
+        else {
+            return; // user cancelled the dialog — no-op
+        };
+
+        let text = format.render(&self.state, &filename, fps);
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| default_name.clone());
+        match std::fs::write(&dest, text) {
+            Ok(()) => {
+                velocut_log!("[export] cut list written → {:?}", dest);
+                self.state.save_status = Some(format!("✓ Saved: {name}"));
+            }
+            Err(e) => {
+                velocut_log!("[export] cut list write failed {:?}: {e}", dest);
+                self.state.save_status = Some(format!("✕ Could not save {name}: {e}"));
+            }
+        }
+    }
+
+    /// Ask for a destination, plan the storyboard's shots and hand the cells
+    /// to the media worker. The result comes back as `StoryboardSaved`.
+    fn export_storyboard(&mut self, filename: String, options: StoryboardOptions) {
+        let shots = plan_shots(&self.state, options.sampling, options.frames);
+        if shots.is_empty() {
+            self.state.save_status =
+                Some("✕ Storyboard: no video frames on the timeline".to_string());
+            return;
+        }
+        let Some(dest) = FileDialog::new()
+            .set_file_name(format!("{filename}_storyboard.png"))
+            .add_filter("PNG image", &["png"])
+            .add_filter("JPEG image", &["jpg", "jpeg"])
+            .save_file()
+        else {
+            return; // user cancelled the dialog — no-op
+        };
+
+        let cells = shots
+            .iter()
+            .filter_map(|shot| {
+                let path = self
+                    .state
+                    .library
+                    .iter()
+                    .find(|l| l.id == shot.media_id)?
+                    .path
+                    .clone();
+                let filter = self
+                    .state
+                    .timeline
+                    .iter()
+                    .find(|c| c.id == shot.clip_id)
+                    .map(|c| c.filter.clone())
+                    .unwrap_or_default();
+                Some(StoryboardCell {
+                    path,
+                    source_time: shot.source_time,
+                    filter,
+                    label: options
+                        .timecode
+                        .then(|| format_time(shot.timeline_time, self.state.project_fps)),
+                })
+            })
+            .collect();
+        // Cell height follows the project frame; even keeps the grid tidy.
+        let thumb_height = ((options.thumb_width as f32
+            / aspect_ratio_value(self.state.aspect_ratio))
+        .round() as u32
+            / 2
+            * 2)
+        .max(2);
+        velocut_log!("[export] storyboard: {} shots → {:?}", shots.len(), dest);
+        self.context.media_worker.export_storyboard(StoryboardSpec {
+            cells,
+            columns: options.columns,
+            thumb_width: options.thumb_width,
+            thumb_height,
+            dest,
+        });
+        self.state.save_status = Some("Rendering storyboard…".to_string());
+    }
+
     fn poll_media(&mut self, ctx: &egui::Context) {
         // ── Pre-frame housekeeping ────────────────────────────────────────────
         for path in self.state.pending_audio_cleanup.drain(..) {
@@ -757,9 +1990,54 @@ impl VeloCutApp {
             let vis = &self.library.visible_ids;
             pending.sort_by_key(|(id, _)| if vis.contains(id) { 0u8 } else { 1u8 });
         }
+        // ── Deferred startup re-probes ────────────────────────────────────────
+        // Released once the card is on screen (last frame's visible set), the
+        // clip is on the timeline, or it is open in the source viewer. Each
+        // entry leaves the queue when dispatched, so it is probed only once.
+        if !self.state.deferred_probes.is_empty() {
+            let vis = &self.library.visible_ids;
+            let on_timeline: HashSet<Uuid> =
+                self.state.timeline.iter().map(|c| c.media_id).collect();
+            let viewer = self.state.source_viewer_clip;
+            let (due, wait): (Vec<_>, Vec<_>) = std::mem::take(&mut self.state.deferred_probes)
+                .into_iter()
+                .partition(|(id, _)| {
+                    vis.contains(id) || on_timeline.contains(id) || viewer == Some(*id)
+                });
+            self.state.deferred_probes = wait;
+            pending.extend(due);
+        }
         for (id, path) in pending {
-            self.context.media_worker.probe_clip(id, path);
+            let stream = self
+                .state
+                .library
+                .iter()
+                .find(|c| c.id == id)
+                .and_then(|c| c.audio_stream_index);
+            self.context.media_worker.probe_clip(id, path, stream);
+        }
+        // ── Poster frames ─────────────────────────────────────────────────────
+        // Thumbnails come back keyed by the timeline clip id, alongside the
+        // library thumbnails in the same cache.
+        let mut posters: HashMap<Uuid, f64> = HashMap::new();
+        for tc in &self.state.timeline {
+            let Some(at) = tc.poster_offset else { continue };
+            posters.insert(tc.id, at);
+            if self.poster_requested.get(&tc.id) != Some(&at) {
+                if let Some(lib) = clip_query::library_entry_for(&self.state, tc) {
+                    self.context
+                        .media_worker
+                        .request_poster_thumbnail(tc.id, lib.path.clone(), at);
+                }
+            }
+        }
+        for id in self.poster_requested.keys() {
+            if !posters.contains_key(id) {
+                self.context.cache.remove_thumbnail(id);
+            }
         }
+        self.poster_requested = posters;
+
         let extracts: Vec<_> = self.state.pending_extracts.drain(..).collect();
         for (id, path, ts, dest) in extracts {
             self.context
@@ -796,7 +2074,15 @@ impl VeloCutApp {
         VideoModule::poll_playback(&self.state, &mut self.context, ctx);
 
         // ── Dispatch all queued MediaWorker results into caches / state ───────
+        let was_finished = self.state.encode_done.is_some() || self.state.encode_error.is_some();
         self.context.ingest_media_results(&mut self.state, ctx);
+        if let Some(hist) = self.context.cache.preview_histogram.take() {
+            self.state.preview_histogram = hist;
+        }
+        if !was_finished {
+            self.alert_render_finished(ctx);
+        }
+        self.finish_nest();
         if self.context.cache.pending_pb_frame.is_some()
             || self.context.playback.last_frame_req.is_some()
         {
@@ -805,11 +2091,16 @@ impl VeloCutApp {
     }
 
     fn handle_drag_and_drop(&mut self, ctx: &egui::Context) {
-        let files = ctx.input(|i| i.raw.dropped_files.clone());
-        for file in files {
-            if let Some(path) = file.path {
-                self.state.add_to_library(path);
-            }
+        let paths: Vec<_> = ctx
+            .input(|i| i.raw.dropped_files.clone())
+            .into_iter()
+            .filter_map(|file| file.path)
+            .collect();
+        if !paths.is_empty() {
+            self.push_undo_snapshot("Import media");
+        }
+        for path in paths {
+            self.state.add_to_library(path);
         }
     }
 }
@@ -833,13 +2124,24 @@ impl eframe::App for VeloCutApp {
         if self.reset_done {
             return;
         }
-        // audio_path points to temp WAVs that are deleted on exit — clear them
-        // before serializing so they don't resurrect as dead paths on next launch.
-        let mut project = self.state.clone();
-        for clip in &mut project.library {
-            clip.audio_path = None;
-        }
-        eframe::set_value(storage, eframe::APP_KEY, &AppStorage { project });
+        let project = self.persisted_project();
+        let performance = self.state.performance;
+        let playback = self.state.playback_prefs;
+        let layout = self.state.layout_prefs;
+        let storage_prefs = self.state.storage_prefs.clone();
+        let notify = self.state.notify_prefs;
+        eframe::set_value(
+            storage,
+            eframe::APP_KEY,
+            &AppStorage {
+                project,
+                performance,
+                playback,
+                layout,
+                storage: storage_prefs,
+                notify,
+            },
+        );
     }
 
     fn on_exit(&mut self) {
@@ -926,6 +2228,79 @@ impl eframe::App for VeloCutApp {
     }
 }
 
+/// Viewport command putting the window at the level `layout` asks for.
+fn window_level(layout: &LayoutPrefs) -> egui::ViewportCommand {
+    egui::ViewportCommand::WindowLevel(if layout.always_on_top {
+        egui::WindowLevel::AlwaysOnTop
+    } else {
+        egui::WindowLevel::Normal
+    })
+}
+
+/// `path`, or the first of `name_2.ext`, `name_3.ext`, … that does not exist
+/// yet, so repeated "save next to source" renders never overwrite each other.
+fn next_free_path(path: std::path::PathBuf) -> std::path::PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{stem}_{n}{ext}")))
+        .find(|p| !p.exists())
+        .expect("unbounded range always yields a free name")
+}
+
+/// Source frame for the export's cover image. `Playhead` takes the enabled
+/// V-row clip under the playhead (lowest row first) and falls back to the
+/// first frame when the playhead sits in a gap.
+fn resolve_cover(
+    state: &velocut_core::state::ProjectState,
+    cover: CoverFrame,
+    clips: &[ClipSpec],
+) -> Option<CoverArt> {
+    let first = || {
+        // A slate has no source to grab a frame from.
+        clips.iter().find(|c| c.solid.is_none()).map(|c| CoverArt {
+            path: c.path.clone(),
+            source_time: c.freeze_at.unwrap_or(c.source_offset),
+            filter: c.filter.clone(),
+        })
+    };
+    match cover {
+        CoverFrame::Off => None,
+        CoverFrame::First => first(),
+        CoverFrame::Playhead => {
+            let t = state.current_time;
+            state
+                .timeline
+                .iter()
+                .filter(|tc| {
+                    tc.enabled
+                        && tc.track_row % 2 == 0
+                        && t >= tc.start_time
+                        && t < tc.start_time + tc.duration
+                })
+                .min_by_key(|tc| tc.track_row)
+                .and_then(|tc| {
+                    let lib = clip_query::library_entry_for(state, tc)?;
+                    Some(CoverArt {
+                        path: lib.path.clone(),
+                        source_time: tc.source_time(t - tc.start_time),
+                        filter: tc.filter.clone(),
+                    })
+                })
+                .or_else(first)
+        }
+    }
+}
+
 // ── Encode plan builder ──────────────────────────────────────────────────────
 //
 // Pure function — takes state + a pre-sorted slice of timeline refs and returns
@@ -948,16 +2323,43 @@ fn build_encode_plan(
     //      here causes encode_clip to open an audio-only file, find no video
     //      stream, and abort the entire export with "no video stream in '...'".
     //      Their audio is handled separately as AudioOverlay entries below.
-    let filtered: Vec<&TimelineClip> = sorted
+    //
+    // Disabled clips are dropped like a gap: the sequential encode joins the
+    // clips either side of them.
+    let v_rows: Vec<&TimelineClip> = sorted
         .iter()
         .copied()
         .filter(|tc| tc.track_row % 2 == 0) // V-row clips only
         .collect();
+    let filtered: Vec<&TimelineClip> = v_rows.iter().copied().filter(|tc| tc.enabled).collect();
 
     let clip_specs: Vec<ClipSpec> = filtered
         .iter()
         .copied()
         .filter_map(|tc| {
+            // Slates have no library entry: the encoder paints their color
+            // for the clip's length, silent.
+            if let Some(color) = tc.slate {
+                return Some(ClipSpec {
+                    path: std::path::PathBuf::new(),
+                    source_offset: 0.0,
+                    duration: tc.duration,
+                    volume: 0.0,
+                    skip_audio: true,
+                    audio_stream: None,
+                    freeze_at: None,
+                    solid: Some(color),
+                    full_range: false,
+                    deinterlace: false,
+                    stabilize: None,
+                    volume_keys: Vec::new(),
+                    fade_in_secs: 0.0,
+                    fade_in_start_secs: 0.0,
+                    fade_out_secs: 0.0,
+                    fade_out_end_secs: 0.0,
+                    filter: tc.filter.clone(),
+                });
+            }
             state
                 .library
                 .iter()
@@ -997,7 +2399,24 @@ fn build_encode_plan(
                         source_offset: tc.source_offset,
                         duration: tc.duration,
                         volume: effective_volume,
-                        skip_audio: false,
+                        // Muted with no A-row partner = dropped video-only
+                        // (or muted from the 🔊 popup); a muted partner
+                        // silences the pair. Nothing should be heard either way.
+                        skip_audio: tc.audio_muted
+                            && linked_audio.is_none_or(|ac| ac.audio_muted || !ac.enabled),
+                        audio_stream: lc.audio_stream_index,
+                        freeze_at: tc.freeze_at,
+                        solid: None,
+                        full_range: lc.is_full_range(),
+                        deinterlace: lc.needs_deinterlace(),
+                        stabilize: tc.stabilize.map(|s| StabilizeSpec {
+                            strength: s.strength,
+                            from: tc.source_offset,
+                            to: tc.source_offset + tc.duration,
+                        }),
+                        volume_keys: linked_audio
+                            .map_or(&tc.volume_keys, |ac| &ac.volume_keys)
+                            .clone(),
                         fade_in_secs: effective_fi,
                         fade_in_start_secs: effective_fi_start,
                         fade_out_secs: effective_fo,
@@ -1014,10 +2433,16 @@ fn build_encode_plan(
 
     // Map TimelineTransitions (UUID-keyed) → ClipTransitions (index-keyed).
     // Uses position in *filtered* vec, not raw timeline, so A-row clips don't
-    // shift transition indices.
+    // shift transition indices. A transition into a disabled clip is dropped
+    // rather than landing on whatever clip now follows.
     let encode_transitions: Vec<ClipTransition> = state
         .transitions
         .iter()
+        .filter(|t| {
+            let pos = v_rows.iter().position(|tc| tc.id == t.after_clip_id);
+            pos.and_then(|i| v_rows.get(i + 1))
+                .is_some_and(|next| next.enabled)
+        })
         .filter_map(|t| {
             let idx = filtered
                 .iter()
@@ -1048,24 +2473,10 @@ fn build_encode_plan(
     let audio_overlays: Vec<AudioOverlay> = sorted
         .iter()
         .copied()
-        .filter(|tc| tc.track_row % 2 == 1 && tc.linked_clip_id.is_none())
-        .filter_map(|tc| {
-            state
-                .library
-                .iter()
-                .find(|lc| lc.id == tc.media_id)
-                .map(|lc| AudioOverlay {
-                    path: lc.path.clone(),
-                    source_offset: tc.source_offset,
-                    timeline_start: tc.start_time,
-                    duration: tc.duration,
-                    volume: tc.volume,
-                    fade_in_secs: tc.fade_in_secs,
-                    fade_in_start_secs: tc.fade_in_start_secs,
-                    fade_out_secs: tc.fade_out_secs,
-                    fade_out_end_secs: tc.fade_out_end_secs,
-                })
+        .filter(|tc| {
+            tc.track_row % 2 == 1 && tc.linked_clip_id.is_none() && !tc.audio_muted && tc.enabled
         })
+        .filter_map(|tc| audio_overlay(state, tc))
         .collect();
 
     if !audio_overlays.is_empty() {
@@ -1078,6 +2489,28 @@ fn build_encode_plan(
     Some((clip_specs, encode_transitions, audio_overlays))
 }
 
+/// A timeline clip's sound as an `AudioOverlay` at its timeline position,
+/// with its own gain, keys and fades. `None` when its media is gone.
+fn audio_overlay(
+    state: &velocut_core::state::ProjectState,
+    tc: &TimelineClip,
+) -> Option<AudioOverlay> {
+    let lc = state.library.iter().find(|lc| lc.id == tc.media_id)?;
+    Some(AudioOverlay {
+        path: lc.path.clone(),
+        audio_stream: lc.audio_stream_index,
+        source_offset: tc.source_offset,
+        timeline_start: tc.start_time,
+        duration: tc.duration,
+        volume: tc.volume,
+        volume_keys: tc.volume_keys.clone(),
+        fade_in_secs: tc.fade_in_secs,
+        fade_in_start_secs: tc.fade_in_start_secs,
+        fade_out_secs: tc.fade_out_secs,
+        fade_out_end_secs: tc.fade_out_end_secs,
+    })
+}
+
 // ── Sub-frame helpers (called from update) ────────────────────────────────────
 
 impl VeloCutApp {
@@ -1085,6 +2518,7 @@ impl VeloCutApp {
     /// Called from `ui()` after pre-frame housekeeping.
     fn render_panels(&mut self, ui: &mut egui::Ui) {
         let ctx = ui.ctx().clone();
+        self.state.overlaps = self.state.overlapping_clips();
 
         egui::Panel::bottom("timeline_panel")
             .resizable(true)
@@ -1098,34 +2532,68 @@ impl VeloCutApp {
                     &mut self.pending_cmds,
                 );
             });
+        // Scroll requests are one-shot — the timeline has applied it by now.
+        self.state.timeline_scroll_to = None;
+
+        // Side panels keep their ids when swapped, so each keeps its width.
+        let layout = self.state.layout_prefs;
+        if layout.show_library {
+            let panel = if layout.swap_sides {
+                egui::Panel::right("library_panel")
+            } else {
+                egui::Panel::left("library_panel")
+            };
+            panel
+                .resizable(true)
+                .min_size(240.0)
+                .default_size(240.0)
+                .show_inside(ui, |ui| {
+                    self.library.ui(
+                        ui,
+                        &self.state,
+                        &mut self.context.cache.thumbnail_cache,
+                        &mut self.pending_cmds,
+                    );
+                });
+        }
 
-        egui::Panel::left("library_panel")
-            .resizable(true)
-            .min_size(240.0)
-            .default_size(240.0)
-            .show_inside(ui, |ui| {
-                self.library.ui(
-                    ui,
-                    &self.state,
-                    &mut self.context.cache.thumbnail_cache,
-                    &mut self.pending_cmds,
-                );
-            });
-
-        egui::Panel::right("export_panel")
-            .resizable(true)
-            .default_size(220.0)
-            .min_size(220.0)
-            .show_inside(ui, |ui| {
-                self.export.ui(
-                    ui,
-                    &self.state,
-                    &mut self.context.cache.thumbnail_cache,
-                    &mut self.pending_cmds,
-                );
-            });
+        if layout.show_export {
+            let panel = if layout.swap_sides {
+                egui::Panel::left("export_panel")
+            } else {
+                egui::Panel::right("export_panel")
+            };
+            panel
+                .resizable(true)
+                .default_size(220.0)
+                .min_size(220.0)
+                .show_inside(ui, |ui| {
+                    self.export.ui(
+                        ui,
+                        &self.state,
+                        &mut self.context.cache.thumbnail_cache,
+                        &mut self.pending_cmds,
+                    );
+                });
+        }
 
         egui::CentralPanel::default().show_inside(ui, |ui| {
+            // The source viewer splits the monitor area while a clip is open.
+            if self.state.source_viewer_clip.is_some() {
+                egui::Panel::left("source_viewer_panel")
+                    .resizable(true)
+                    .min_size(220.0)
+                    .default_size(ui.available_width() * 0.5)
+                    .show_inside(ui, |ui| {
+                        self.source_viewer.ui(
+                            ui,
+                            &self.state,
+                            &mut self.context.cache.thumbnail_cache,
+                            &mut self.pending_cmds,
+                        );
+                    });
+            }
+
             let active_id = VideoModule::active_media_id(&self.state);
             self.preview.current_frame =
                 active_id.and_then(|id| self.context.cache.frame_cache.get(&id).cloned());
@@ -1140,6 +2608,8 @@ impl VeloCutApp {
 
         self.export
             .show_render_modal(&ctx, &self.state, &mut self.pending_cmds);
+        crate::helpers::shortcuts::show_overlay(&ctx, self.state.playback_prefs.space);
+        crate::helpers::perf_hud::show_overlay(&ctx, &mut self.context);
         crate::helpers::reset::show_uninstall_modal(&ctx, &mut self.export.show_reset_complete);
     }
 
@@ -1156,16 +2626,38 @@ impl VeloCutApp {
             self.preview.last_canvas_size,
         );
         self.audio.tick(&self.state, &mut self.context);
+        self.source_viewer.tick(&self.state, &mut self.context);
         self.memory_manager
             .tick(ctx, &self.state, &mut self.context);
+        // A trim or delete at the tail left the playhead past the new end:
+        // park it on the last frame so the preview isn't blank. Seeking past
+        // the end by hand (total unchanged) is left alone.
+        let total = self.state.total_duration();
+        if total < self.last_total && self.state.current_time > total {
+            self.state.current_time = clip_query::playback_end_time(&self.state);
+            ctx.request_repaint();
+        }
+        self.last_total = total;
+
         if self.state.is_playing {
             let dt = ctx.input(|i| i.stable_dt as f64);
-            self.state.current_time += dt;
-            let total = self.state.total_duration();
+            self.state.current_time += dt * self.state.preview_rate as f64;
+            // Stop on the start of the last frame rather than at the very
+            // end, where no clip is under the playhead and the preview would
+            // go blank. The audio module soft-drains every sink on the
+            // play→stop edge next tick.
             if total > 0.0 && self.state.current_time >= total {
-                self.state.current_time = total - 0.001;
+                self.state.current_time = clip_query::playback_end_time(&self.state);
                 self.state.is_playing = false;
             }
+            // Play-around review finished: stop and go back to the edit point.
+            if let Some((stop_at, home)) = self.state.play_around {
+                if self.state.current_time >= stop_at || !self.state.is_playing {
+                    self.state.play_around = None;
+                    self.state.is_playing = false;
+                    self.process_command(EditorCommand::SetPlayhead(home), ctx);
+                }
+            }
             ctx.request_repaint();
         }
 
@@ -12,6 +12,7 @@
 //     ├── audio_stream        — rodio OutputStream (must outlive all sinks)
 //     └── audio_sinks         — per-clip Sink map (managed by audio_module only)
 
+use crate::helpers::clip_query;
 use crate::modules::ThumbnailCache;
 use crate::velocut_log;
 use eframe::egui;
@@ -19,15 +20,28 @@ use rodio::{MixerDeviceSink, Player};
 use std::collections::HashMap;
 use uuid::Uuid;
 use velocut_core::media_types::PlaybackFrame;
-use velocut_core::state::ProjectState;
+use velocut_core::prefs::{PerformancePrefs, StoragePrefs};
+use velocut_core::state::{ClipType, ProjectState, SpeechRegions};
 use velocut_media::{MediaResult, MediaWorker};
 
 // ── Memory ceiling ────────────────────────────────────────────────────────────
 // Approximate byte budget for GPU-resident frame textures.
-// At 640×360 RGBA each frame is ~900 KB; 192 MB ≈ 213 frames.
-// This constant is checked on every frame_bucket_cache insert.
-// Change it here to tune the scrub cache size without touching eviction logic.
-const MAX_FRAME_CACHE_BYTES: usize = 192 * 1024 * 1024; // 192 MB
+// At 640×360 RGBA each frame is ~900 KB; the 192 MB default ≈ 213 frames.
+// The live value comes from PerformancePrefs::frame_cache_mb and is checked on
+// every frame_bucket_cache insert — see CacheContext::set_frame_cache_budget.
+
+/// Entries evicted per over-budget pass — the furthest from the playhead go first.
+const EVICT_BATCH: usize = 32;
+
+/// Pixels sampled per preview histogram. Frames larger than this are
+/// strided through, which keeps the bin shape and costs well under a ms.
+const HISTOGRAM_SAMPLES: usize = 65_536;
+
+/// Luma histogram of a tight RGBA preview frame for the levels editor.
+pub fn luma_histogram(rgba: &[u8]) -> Vec<u32> {
+    let step = (rgba.len() / 4 / HISTOGRAM_SAMPLES).max(1);
+    velocut_core::filters::helpers::luma_histogram_rgba(rgba, step).to_vec()
+}
 
 // ── CacheContext ──────────────────────────────────────────────────────────────
 // Owns all GPU-resident texture caches and the memory ceiling that governs them.
@@ -50,6 +64,11 @@ pub struct CacheContext {
     /// Prevents the drain-all pattern from racing ahead of wall-clock time.
     pub pending_pb_frame: Option<PlaybackFrame>,
 
+    /// Luma histogram of the newest decoded preview frame, taken before clip
+    /// filters. Written by ingest_media_results and poll_playback; app.rs
+    /// moves it into `ProjectState::preview_histogram`.
+    pub preview_histogram: Option<Vec<u32>>,
+
     /// Decoded frames keyed by (media_id, fine_bucket, is_coarse) - the scrub
     /// look-ahead store.  The `is_coarse` flag prevents L1 fine-bucket entries from
     /// colliding with L2 coarse-bucket entries when the numeric bucket values happen
@@ -62,6 +81,9 @@ pub struct CacheContext {
     /// exact compressed GPU size) — uses raw RGBA bytes as a conservative ceiling.
     pub(crate) frame_cache_bytes: usize,
 
+    /// Ceiling for `frame_cache_bytes`. Set from the Performance preferences.
+    max_frame_cache_bytes: usize,
+
     /// Persistent GPU texture handles for the scrub decode path, keyed by media_id.
     ///
     /// On every scrub frame ingest we call `TextureHandle::set()` on the existing
@@ -85,8 +107,10 @@ impl CacheContext {
             thumbnail_order: Vec::new(),
             frame_cache: HashMap::new(),
             pending_pb_frame: None,
+            preview_histogram: None,
             frame_bucket_cache: HashMap::new(),
             frame_cache_bytes: 0,
+            max_frame_cache_bytes: PerformancePrefs::default().frame_cache_bytes(),
             scrub_textures: HashMap::new(),
             scrub_texture_access: std::collections::HashMap::new(),
         }
@@ -112,27 +136,8 @@ impl CacheContext {
         let frame_bytes = width * height * 4;
 
         // Evict if this insert would exceed the budget.
-        if self.frame_cache_bytes + frame_bytes > MAX_FRAME_CACHE_BYTES {
-            let current_bucket = (current_time * 4.0) as u32;
-            let mut keys: Vec<_> = self.frame_bucket_cache.keys().copied().collect();
-
-            // [Opt 4] O(N) partial select: puts the 32 furthest entries at keys[..32]
-            // without fully sorting the remaining N-32 entries.
-            if keys.len() > 32 {
-                keys.select_nth_unstable_by_key(32, |(_, b, _)| {
-                    std::cmp::Reverse(b.abs_diff(current_bucket))
-                });
-            }
-            keys.truncate(32);
-
-            for k in &keys {
-                // Subtract this entry's own byte count - not the incoming frame size.
-                // Mixed-resolution projects (e.g. 4K + 720p) would cause the budget
-                // estimate to drift if we assumed all entries are the same size.
-                if let Some((_, entry_bytes)) = self.frame_bucket_cache.remove(k) {
-                    self.frame_cache_bytes = self.frame_cache_bytes.saturating_sub(entry_bytes);
-                }
-            }
+        if self.frame_cache_bytes + frame_bytes > self.max_frame_cache_bytes {
+            self.evict_furthest_buckets(current_time);
         }
 
         self.frame_bucket_cache
@@ -141,6 +146,45 @@ impl CacheContext {
         tex
     }
 
+    /// Current ceiling for `frame_cache_bytes`.
+    pub fn max_frame_cache_bytes(&self) -> usize {
+        self.max_frame_cache_bytes
+    }
+
+    /// Drop the `EVICT_BATCH` bucket entries furthest from `current_time`.
+    fn evict_furthest_buckets(&mut self, current_time: f64) {
+        let current_bucket = (current_time * 4.0) as u32;
+        let mut keys: Vec<_> = self.frame_bucket_cache.keys().copied().collect();
+
+        // [Opt 4] O(N) partial select: puts the 32 furthest entries at keys[..32]
+        // without fully sorting the remaining N-32 entries.
+        if keys.len() > EVICT_BATCH {
+            keys.select_nth_unstable_by_key(EVICT_BATCH, |(_, b, _)| {
+                std::cmp::Reverse(b.abs_diff(current_bucket))
+            });
+        }
+        keys.truncate(EVICT_BATCH);
+
+        for k in &keys {
+            // Subtract this entry's own byte count - not the incoming frame size.
+            // Mixed-resolution projects (e.g. 4K + 720p) would cause the budget
+            // estimate to drift if we assumed all entries are the same size.
+            if let Some((_, entry_bytes)) = self.frame_bucket_cache.remove(k) {
+                self.frame_cache_bytes = self.frame_cache_bytes.saturating_sub(entry_bytes);
+            }
+        }
+    }
+
+    /// Change the frame-cache ceiling. Lowering it prunes immediately (furthest
+    /// from the playhead first) rather than waiting for the next insert, so
+    /// the memory is actually released when the user shrinks the cache.
+    pub fn set_frame_cache_budget(&mut self, bytes: usize, current_time: f64) {
+        self.max_frame_cache_bytes = bytes;
+        while self.frame_cache_bytes > bytes && !self.frame_bucket_cache.is_empty() {
+            self.evict_furthest_buckets(current_time);
+        }
+    }
+
     /// Evict every cached texture and reset the byte budget to zero.
     ///
     /// Called by the `ClearProject` handler before wiping `ProjectState`.
@@ -160,6 +204,18 @@ impl CacheContext {
         self.frame_cache_bytes = 0;
     }
 
+    /// Drop every decoded preview frame — the displayed frame per clip and the
+    /// scrub bucket store — so the next decode repopulates them. Thumbnails
+    /// and the persistent scrub textures are kept.
+    ///
+    /// Called when the project aspect ratio changes: frames decoded at the old
+    /// crop would otherwise be shown stretched until the user scrubs.
+    pub fn clear_frames(&mut self) {
+        self.frame_cache.clear();
+        self.frame_bucket_cache.clear();
+        self.frame_cache_bytes = 0;
+    }
+
     /// Remove a thumbnail from the cache, updating insertion-order tracking.
     /// Call this instead of `thumbnail_cache.remove()` directly.
     pub fn remove_thumbnail(&mut self, id: &Uuid) {
@@ -242,6 +298,53 @@ impl PlaybackContext {
     }
 }
 
+// ── PerfStats ─────────────────────────────────────────────────────────────────
+// Counters behind the F12 performance HUD. Always collected — a few integer
+// bumps per frame — and only read while the HUD is showing.
+pub struct PerfStats {
+    /// L1 scrub lookups that found a nearby frame in `frame_bucket_cache`.
+    pub bucket_hits: u64,
+    /// L1 scrub lookups that found nothing and fell back to a decode.
+    pub bucket_misses: u64,
+    /// Milliseconds from the latest scrub request to its frame arriving.
+    pub decode_ms: Option<f32>,
+    /// UI frames drawn per second, over the last full second.
+    pub fps: f32,
+    frames: u32,
+    window_start: std::time::Instant,
+}
+
+impl PerfStats {
+    fn new() -> Self {
+        Self {
+            bucket_hits: 0,
+            bucket_misses: 0,
+            decode_ms: None,
+            fps: 0.0,
+            frames: 0,
+            window_start: std::time::Instant::now(),
+        }
+    }
+
+    /// Count one UI frame. Call once per `render_panels`.
+    pub fn tick_frame(&mut self) {
+        self.frames += 1;
+        let secs = self.window_start.elapsed().as_secs_f32();
+        if secs >= 1.0 {
+            self.fps = self.frames as f32 / secs;
+            self.frames = 0;
+            self.window_start = std::time::Instant::now();
+        }
+    }
+
+    /// Share of L1 lookups served from the bucket cache, or `None` before the
+    /// first scrub.
+    pub fn hit_rate(&self) -> Option<f32> {
+        let total = self.bucket_hits + self.bucket_misses;
+        (total > 0).then(|| self.bucket_hits as f32 / total as f32)
+    }
+}
+
 // ── AppContext ────────────────────────────────────────────────────────────────
 
 pub struct AppContext {
@@ -254,6 +357,9 @@ pub struct AppContext {
     // ── Scrub / playback decode tracking ─────────────────────────────────────
     pub playback: PlaybackContext,
 
+    // ── Performance HUD counters ─────────────────────────────────────────────
+    pub perf: PerfStats,
+
     // ── Audio (rodio 0.22) ───────────────────────────────────────────────────
     // MixerDeviceSink MUST stay alive for the entire app lifetime — dropping it
     // stops all audio.  audio_module borrows it each tick via .mixer().
@@ -272,12 +378,37 @@ impl AppContext {
             media_worker,
             cache: CacheContext::new(),
             playback: PlaybackContext::new(),
+            perf: PerfStats::new(),
             audio_stream: None,
             audio_sinks: HashMap::new(),
             audio_overlay_sinks: HashMap::new(),
         }
     }
 
+    /// Push the Performance preferences into the frame cache and the media
+    /// worker. Called once at startup and on every `SetPerformancePrefs`.
+    pub fn apply_performance_prefs(&mut self, prefs: &PerformancePrefs, current_time: f64) {
+        self.cache
+            .set_frame_cache_budget(prefs.frame_cache_bytes(), current_time);
+        self.media_worker.set_decode_threads(prefs.decode_threads);
+        self.media_worker.set_proxy_width(prefs.proxy_width);
+        self.media_worker.set_playback_ahead(prefs.playback_ahead);
+        velocut_log!(
+            "[perf] frame cache {} MB · {} decode threads · {}px proxy · {} frames ahead",
+            prefs.frame_cache_mb,
+            prefs.decode_threads,
+            prefs.proxy_width,
+            prefs.playback_ahead
+        );
+    }
+
+    /// Point the media worker's audio extraction at the Storage scratch
+    /// folder. Called once at startup and on every `SetStoragePrefs`.
+    pub fn apply_storage_prefs(&mut self, prefs: &StoragePrefs) {
+        self.media_worker.set_scratch_dir(prefs.scratch_dir.clone());
+        velocut_log!("[storage] scratch folder {}", prefs.resolved().display());
+    }
+
     /// Drain the MediaWorker result channel and load everything into the
     /// appropriate cache or state field.  Called once per frame from
     /// `app::poll_media`, after PTS-gated playback frame consumption.
@@ -309,6 +440,9 @@ impl AppContext {
                     height,
                     data,
                 } => {
+                    if let Some(asked) = self.playback.scrub_last_moved {
+                        self.perf.decode_ms = Some(asked.elapsed().as_secs_f32() * 1000.0);
+                    }
                     self.ingest_video_frame(
                         id,
                         width,
@@ -350,7 +484,68 @@ impl AppContext {
                     trimmed_offset,
                 } => {
                     velocut_log!("[audio] AudioPath arrived id={id} path={} trimmed_offset={trimmed_offset:.3}", path.display());
-                    state.set_audio_path(id, path, trimmed_offset);
+                    if let Some(old) = state.set_audio_path(id, path, trimmed_offset) {
+                        // Stream switch: drop sinks still reading the old WAV so
+                        // audio_module reopens them on the new file, then retire it.
+                        for clip in state.timeline.iter().filter(|c| c.media_id == id) {
+                            self.audio_sinks.remove(&clip.id);
+                        }
+                        state.pending_audio_cleanup.push(old);
+                    }
+                }
+
+                MediaResult::AudioStreams { id, streams } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.audio_streams = streams;
+                    }
+                    needs_repaint = true;
+                }
+
+                MediaResult::VideoColorRange { id, range } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.color_range = Some(range);
+                    }
+                }
+
+                MediaResult::VideoInterlaced { id, interlaced } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.interlaced = Some(interlaced);
+                    }
+                }
+
+                MediaResult::VideoFrameRate { id, fps } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.frame_rate = Some(fps);
+                    }
+                }
+
+                MediaResult::AudioOnly { id } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        if clip.clip_type != ClipType::Audio {
+                            velocut_log!("[media] {} has no video — treating as audio", clip.name);
+                        }
+                        clip.clip_type = ClipType::Audio;
+                        clip.video_size = None;
+                    }
+                    needs_repaint = true;
+                }
+
+                MediaResult::ProbeFinished { id } => {
+                    if state.refreshing.remove(&id) && state.refreshing.is_empty() {
+                        state.refresh_total = 0;
+                    }
+                    needs_repaint = true;
+                }
+
+                MediaResult::FileStats {
+                    id,
+                    bytes,
+                    bit_rate,
+                } => {
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.file_bytes = Some(bytes);
+                        clip.bit_rate = bit_rate;
+                    }
                 }
 
                 MediaResult::Duration { id, seconds } => {
@@ -402,6 +597,24 @@ impl AppContext {
                     needs_repaint = true;
                 }
 
+                MediaResult::StoryboardSaved { path, error } => {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "storyboard".into());
+                    state.save_status = Some(match error {
+                        None => {
+                            velocut_log!("[app] storyboard saved → {:?}", path);
+                            format!("✓ Saved: {}", name)
+                        }
+                        Some(msg) => {
+                            velocut_log!("[app] storyboard {:?} failed: {msg}", path);
+                            format!("✕ Storyboard failed: {msg}")
+                        }
+                    });
+                    needs_repaint = true;
+                }
+
                 // VideoFrame on the shared channel = HQ one-shot frame from
                 // extract_frame_hq.  Scrub frames no longer arrive here —
                 // they travel on scrub_rx and are consumed above.
@@ -422,8 +635,47 @@ impl AppContext {
                     );
                 }
 
+                MediaResult::ScenesDetected { id, cuts } => {
+                    // A result for a clip the user has since moved on from is
+                    // still worth showing; only the "detecting" flag is keyed.
+                    if state.scene_detecting == Some(id) {
+                        state.scene_detecting = None;
+                    }
+                    state.scene_cuts = Some((id, cuts));
+                }
+                MediaResult::SilenceDetected { id, sound } => {
+                    if state.silence_detecting == Some(id) {
+                        state.silence_detecting = None;
+                    }
+                    state.silence_range = Some((id, sound));
+                }
+                MediaResult::SpeechDetected { id, regions } => {
+                    // Like audio sync, the speech clip is only known from the
+                    // pending request.
+                    if let Some((clip, speech)) = state.duck_detecting.filter(|(c, _)| *c == id) {
+                        state.duck_detecting = None;
+                        state.speech_regions = Some(SpeechRegions {
+                            clip,
+                            speech,
+                            regions,
+                        });
+                    }
+                }
+                MediaResult::AudioSyncFound { id, offset } => {
+                    // The reference is only known from the pending request,
+                    // so a result nobody is waiting for is dropped.
+                    if let Some((clip, reference)) = state.sync_detecting.filter(|(c, _)| *c == id)
+                    {
+                        state.sync_detecting = None;
+                        state.audio_sync = Some((clip, reference, offset));
+                    }
+                }
                 MediaResult::Error { id, msg } => {
                     velocut_log!("[media] {id}: {msg}");
+                    if let Some(clip) = state.library.iter_mut().find(|c| c.id == id) {
+                        clip.probe_error = Some(msg);
+                    }
+                    needs_repaint = true;
                 }
 
                 // ── Encode results ────────────────────────────────────────────
@@ -441,12 +693,21 @@ impl AppContext {
                     }
                 }
 
-                MediaResult::EncodeDone { job_id, path } => {
+                MediaResult::EncodeDone {
+                    job_id,
+                    path,
+                    audio_peak,
+                    audio_codec,
+                    skipped_frames,
+                } => {
                     if state.encode_job == Some(job_id) {
                         if let Some((_, total)) = state.encode_progress {
                             state.encode_progress = Some((total, total));
                         }
                         state.encode_done = Some(path);
+                        state.encode_audio_peak = Some(audio_peak);
+                        state.encode_audio_codec = audio_codec;
+                        state.encode_skipped_frames = skipped_frames;
                         needs_repaint = true;
                     }
                 }
@@ -483,6 +744,7 @@ impl AppContext {
         needs_repaint: &mut bool,
         ctx: &egui::Context,
     ) {
+        self.cache.preview_histogram = Some(luma_histogram(&data));
         {
             let active_filter = state
                 .timeline
@@ -495,9 +757,9 @@ impl AppContext {
                 .map(|c| c.filter.clone())
                 .unwrap_or_default();
 
-            if !active_filter.is_identity() {
+            if !active_filter.is_identity() && !state.bypass_effects {
                 use velocut_core::filters::helpers::apply_filter_rgba;
-                apply_filter_rgba(&mut data, &active_filter);
+                apply_filter_rgba(&mut data, width as usize, height as usize, &active_filter);
             }
         }
         // ── Build ColorImage ─────────────────────────────────────────────────────
@@ -579,7 +841,7 @@ impl AppContext {
                     .map(|c| {
                         // Match the local_t formula in video_module::tick():
                         // source-relative time = timeline time + source_offset.
-                        let lt = (state.current_time - c.start_time + c.source_offset).max(0.0);
+                        let lt = c.source_time(state.current_time - c.start_time);
                         ((lt * 4.0) as u32, false)
                     })
                     .unwrap_or((0, false))
@@ -595,8 +857,11 @@ impl AppContext {
 
         // During playback the pb channel owns frame_cache — a late-arriving
         // scrub result would overwrite the correct playback frame with a
-        // wrong-position one.  Skip the frame_cache write while playing.
-        if !state.is_playing {
+        // wrong-position one.  Skip the frame_cache write while playing,
+        // except over a freeze clip, whose held frame only comes from here.
+        let freeze_here = clip_query::clip_at_time(state, state.current_time)
+            .is_some_and(|c| c.media_id == id && c.freeze_at.is_some());
+        if !state.is_playing || freeze_here {
             self.cache.frame_cache.insert(id, tex);
             *needs_repaint = true;
         }
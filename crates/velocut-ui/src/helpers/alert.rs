@@ -0,0 +1,54 @@
+// crates/velocut-ui/src/helpers/alert.rs
+//
+// Render-finished alerts: a short synthesized chime on the shared output
+// mixer and an OS notification. Both are fire-and-forget — a missing audio
+// device or notification daemon is logged and otherwise ignored.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rodio::mixer::Mixer;
+use rodio::source::{SineWave, Source};
+
+use crate::velocut_log;
+
+/// Two rising notes for a finished render, two falling ones for a failure.
+pub fn play_chime(mixer: &Mixer, ok: bool) {
+    let notes: [(f32, u64, u64); 2] = if ok {
+        [(660.0, 0, 140), (990.0, 140, 260)]
+    } else {
+        [(440.0, 0, 160), (330.0, 160, 300)]
+    };
+    for (freq, delay_ms, len_ms) in notes {
+        let mut tone = SineWave::new(freq)
+            .amplify(0.15)
+            .take_duration(Duration::from_millis(len_ms));
+        // Ramp to silence instead of cutting off mid-cycle, which clicks.
+        tone.set_filter_fadeout();
+        mixer.add(tone.delay(Duration::from_millis(delay_ms)));
+    }
+}
+
+/// Post an OS notification for a finished (`error == None`) or failed
+/// render of `output`. Sent from a short-lived thread: on Linux `show()`
+/// is a blocking D-Bus round trip.
+pub fn notify_render_finished(output: Option<&Path>, error: Option<&str>) {
+    let name = output
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "render".to_string());
+    let (summary, body) = match error {
+        None => ("Render complete".to_string(), name),
+        Some(msg) => ("Render failed".to_string(), format!("{name}: {msg}")),
+    };
+    std::thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new()
+            .appname("VeloCut")
+            .summary(&summary)
+            .body(&body)
+            .show()
+        {
+            velocut_log!("[alert] notification failed: {e}");
+        }
+    });
+}
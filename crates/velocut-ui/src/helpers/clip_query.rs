@@ -12,7 +12,9 @@
 // fields on state in the same expression.
 
 use uuid::Uuid;
-use velocut_core::state::{LibraryClip, ProjectState, TimelineClip};
+use velocut_core::state::{
+    ClipType, LibraryClip, ProjectState, TimelineClip, PREVIEW_RATE_AUDIO_MAX,
+};
 
 // ── Timeline lookups ──────────────────────────────────────────────────────────
 
@@ -37,7 +39,8 @@ pub fn selected_timeline_clip(state: &ProjectState) -> Option<&TimelineClip> {
 }
 
 /// Return the timeline clip that contains `time` (i.e. the clip currently
-/// under the playhead), or `None` when the playhead is in a gap.
+/// under the playhead), or `None` when the playhead is in a gap. Disabled
+/// clips count as a gap.
 ///
 /// Replaces the inline predicate used in video_module.rs, audio_module.rs,
 /// and app.rs for "what is playing right now" queries.
@@ -46,7 +49,228 @@ pub fn clip_at_time(state: &ProjectState, time: f64) -> Option<&TimelineClip> {
     state
         .timeline
         .iter()
-        .find(|c| time >= c.start_time && time < c.start_time + c.duration)
+        .find(|c| c.enabled && time >= c.start_time && time < c.start_time + c.duration)
+}
+
+/// Where playback parks when it runs off the end: the start of the last
+/// frame of whichever clip ends last, so the preview keeps that frame up
+/// instead of decoding at (or past) the source's EOF. One frame at the
+/// source's probed rate, 30 fps when it has none; never below 0.
+pub fn playback_end_time(state: &ProjectState) -> f64 {
+    let Some(last) = state
+        .timeline
+        .iter()
+        .max_by(|a, b| (a.start_time + a.duration).total_cmp(&(b.start_time + b.duration)))
+    else {
+        return 0.0;
+    };
+    let fps = library_entry_for(state, last)
+        .and_then(|l| l.frame_rate)
+        .filter(|f| *f > 0.0)
+        .unwrap_or(state.project_fps as f64);
+    let end = last.start_time + last.duration;
+    (end - (1.0 / fps).min(last.duration)).max(0.0)
+}
+
+/// The clip Tab / Shift+Tab moves the selection to: the next (`forward`) or
+/// previous clip after `from` in start-time order, wrapping at either end.
+/// Clips starting together are ordered by track row. With `same_track`, only
+/// clips on `from`'s row are considered.
+///
+/// With no current selection, Tab picks the first clip starting at or after
+/// the playhead and Shift+Tab the last one starting before it.
+pub fn neighbor_clip(
+    state: &ProjectState,
+    from: Option<Uuid>,
+    forward: bool,
+    same_track: bool,
+) -> Option<&TimelineClip> {
+    let current = from.and_then(|id| timeline_clip(state, id));
+    let mut order: Vec<&TimelineClip> = state
+        .timeline
+        .iter()
+        .filter(|c| !same_track || current.is_none_or(|cur| c.track_row == cur.track_row))
+        .collect();
+    order.sort_by(|a, b| {
+        a.start_time
+            .total_cmp(&b.start_time)
+            .then(a.track_row.cmp(&b.track_row))
+    });
+    if order.is_empty() {
+        return None;
+    }
+    let n = order.len();
+    let idx = match current.and_then(|cur| order.iter().position(|c| c.id == cur.id)) {
+        Some(i) if forward => (i + 1) % n,
+        Some(i) => (i + n - 1) % n,
+        None if forward => order
+            .iter()
+            .position(|c| c.start_time >= state.current_time)
+            .unwrap_or(0),
+        None => order
+            .iter()
+            .rposition(|c| c.start_time < state.current_time)
+            .unwrap_or(n - 1),
+    };
+    Some(order[idx])
+}
+
+/// Largest gap (s) between two clips on a track that still counts as a
+/// touching boundary the timeline draws a transition badge on.
+pub const TRANSITION_JOIN_TOLERANCE: f64 = 0.25;
+
+/// Every clip boundary a transition can sit on: clips followed by a touching
+/// clip on the same video track, in timeline order. Returns the `after_clip_id`
+/// of each boundary — the same joins the timeline draws transition badges on.
+pub fn transition_boundaries(state: &ProjectState) -> Vec<Uuid> {
+    let mut video: Vec<&TimelineClip> = state
+        .timeline
+        .iter()
+        .filter(|c| c.track_row % 2 == 0)
+        .collect();
+    video.sort_by(|a, b| {
+        a.track_row
+            .cmp(&b.track_row)
+            .then(a.start_time.total_cmp(&b.start_time))
+    });
+    let mut ids: Vec<(f64, Uuid)> = video
+        .windows(2)
+        .filter(|w| {
+            let (a, b) = (w[0], w[1]);
+            a.track_row == b.track_row
+                && (b.start_time - (a.start_time + a.duration)).abs() <= TRANSITION_JOIN_TOLERANCE
+        })
+        .map(|w| (w[0].start_time + w[0].duration, w[0].id))
+        .collect();
+    ids.sort_by(|a, b| a.0.total_cmp(&b.0));
+    ids.into_iter().map(|(_, id)| id).collect()
+}
+
+/// Longest transition `SetTransition` accepts.
+const MAX_CROSSFADE_SECS: f64 = 10.0;
+
+/// The crossfade a drag of clip `id` to `start` on `row` asks for: the clip
+/// on that video track whose tail the dragged clip's head lands on, and the
+/// overlap in seconds. `None` when the head lands in a gap, on an audio
+/// track, on its own group, or the drop would bury the whole neighbor.
+pub fn crossfade_overlap(
+    state: &ProjectState,
+    id: Uuid,
+    row: usize,
+    start: f64,
+) -> Option<(Uuid, f64)> {
+    if row % 2 != 0 {
+        return None;
+    }
+    let clip = timeline_clip(state, id)?;
+    let group = state.group_of(id);
+    let prev = state.timeline.iter().find(|c| {
+        let end = c.start_time + c.duration;
+        c.track_row == row
+            && !group.contains(&c.id)
+            && c.start_time < start
+            && start < end
+            && start + clip.duration > end
+    })?;
+    let secs = prev.start_time + prev.duration - start;
+    // Overlaps shorter than one project frame are drag jitter, not a dissolve.
+    (secs >= state.frame_secs()).then_some((prev.id, secs.min(MAX_CROSSFADE_SECS)))
+}
+
+/// Which clips "Apply adjustments to…" copies a clip's settings onto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApplyScope {
+    /// Every other clip on the timeline.
+    All,
+    /// Clips cut from the same library file (e.g. one camera's shots).
+    SameSource,
+    /// Clips on the same track row.
+    SameTrack,
+}
+
+impl ApplyScope {
+    pub fn all() -> &'static [ApplyScope] {
+        &[
+            ApplyScope::All,
+            ApplyScope::SameSource,
+            ApplyScope::SameTrack,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ApplyScope::All => "All clips",
+            ApplyScope::SameSource => "Clips from the same source",
+            ApplyScope::SameTrack => "Clips on this track",
+        }
+    }
+}
+
+/// Ids of the clips `scope` selects relative to `from`, excluding `from`
+/// itself. `video_only` keeps only video-row clips (color adjustments have
+/// nothing to act on in audio rows).
+pub fn apply_targets(
+    state: &ProjectState,
+    from: &TimelineClip,
+    scope: ApplyScope,
+    video_only: bool,
+) -> Vec<Uuid> {
+    state
+        .timeline
+        .iter()
+        .filter(|c| c.id != from.id)
+        .filter(|c| !video_only || c.track_row % 2 == 0)
+        .filter(|c| match scope {
+            ApplyScope::All => true,
+            ApplyScope::SameSource => c.media_id == from.media_id,
+            ApplyScope::SameTrack => c.track_row == from.track_row,
+        })
+        .map(|c| c.id)
+        .collect()
+}
+
+/// The clip audio sync lines `clip` up against: the one on the track
+/// directly above or below that shares the most time with it (or, when none
+/// overlaps, sits closest). Clips in `clip`'s own group, stills and freeze
+/// frames have no audio to match and are skipped.
+pub fn sync_reference<'s>(
+    state: &'s ProjectState,
+    clip: &TimelineClip,
+) -> Option<&'s TimelineClip> {
+    let group = state.group_of(clip.id);
+    let end = clip.start_time + clip.duration;
+    let is_still = |c: &TimelineClip| {
+        c.freeze_at.is_some()
+            || library_clip(state, c.media_id).is_none_or(|l| l.clip_type == ClipType::Image)
+    };
+    state
+        .timeline
+        .iter()
+        .filter(|c| c.track_row.abs_diff(clip.track_row) == 1)
+        .filter(|c| !group.contains(&c.id) && !is_still(c))
+        .max_by(|a, b| {
+            let shared = |c: &TimelineClip| {
+                (c.start_time + c.duration).min(end) - c.start_time.max(clip.start_time)
+            };
+            shared(a).total_cmp(&shared(b))
+        })
+}
+
+/// Seconds between where `clip`'s source starts on the timeline and where
+/// `reference`'s does. Once the two are in sync this equals the offset audio
+/// sync measures, so it is the readout manual sync nudges against.
+pub fn sync_offset(clip: &TimelineClip, reference: &TimelineClip) -> f64 {
+    (clip.start_time - clip.source_offset) - (reference.start_time - reference.source_offset)
+}
+
+/// Waveform peak of `lib` at source time `t`, or 0 outside the file or
+/// before its waveform has arrived.
+pub fn peak_at(lib: &LibraryClip, t: f64) -> f32 {
+    if lib.duration <= 0.0 || t < 0.0 {
+        return 0.0;
+    }
+    let idx = (t / lib.duration * lib.waveform_peaks.len() as f64) as usize;
+    lib.waveform_peaks.get(idx).copied().unwrap_or(0.0)
 }
 
 // ── Library lookups ───────────────────────────────────────────────────────────
@@ -142,6 +366,8 @@ pub fn linked_audio_clip<'s>(
 ///    + clamp(state.current_time − tc.start_time,  0.0,  tc.duration − one_frame)
 /// ```
 ///
+/// Freeze clips always resolve to their held `freeze_at` frame.
+///
 /// This is the source-truth for "Export this frame" and anything else that
 /// needs to know which frame of the source file is currently visible.
 ///
@@ -155,15 +381,39 @@ pub fn linked_audio_clip<'s>(
 /// }
 /// ```
 pub fn playhead_source_timestamp(state: &ProjectState) -> Option<(f64, &LibraryClip)> {
-    const ONE_FRAME: f64 = 1.0 / 30.0;
-
     let tc = selected_timeline_clip(state)?;
     let lib = library_entry_for(state, tc)?;
+    Some((clamped_source_time(state, tc), lib))
+}
 
-    let offset =
-        (state.current_time - tc.start_time).clamp(0.0, (tc.duration - ONE_FRAME).max(0.0));
+/// Resolve the target of a Match Frame: the source entry and timestamp for
+/// `clip_id`, or — when `None` — for the clip under the playhead (V rows win
+/// over A rows, lower rows first). Same clamping as
+/// [`playhead_source_timestamp`], so a clip picked from its context menu while
+/// the playhead is elsewhere matches its nearest edge frame.
+pub fn match_frame_source(
+    state: &ProjectState,
+    clip_id: Option<Uuid>,
+) -> Option<(f64, &LibraryClip)> {
+    let tc = match clip_id {
+        Some(id) => timeline_clip(state, id)?,
+        None => state
+            .timeline
+            .iter()
+            .filter(|c| {
+                state.current_time >= c.start_time && state.current_time < c.start_time + c.duration
+            })
+            .min_by_key(|c| (c.track_row % 2, c.track_row))?,
+    };
+    let lib = library_entry_for(state, tc)?;
+    Some((clamped_source_time(state, tc), lib))
+}
 
-    Some((tc.source_offset + offset, lib))
+/// Source time of the playhead inside `tc`, clamped to the clip's last frame.
+fn clamped_source_time(state: &ProjectState, tc: &TimelineClip) -> f64 {
+    let offset = (state.current_time - tc.start_time)
+        .clamp(0.0, (tc.duration - state.frame_secs()).max(0.0));
+    tc.source_time(offset)
 }
 
 // ── Transition zone detection ─────────────────────────────────────────────────
@@ -209,6 +459,10 @@ pub fn active_transition_at(state: &ProjectState) -> Option<TransitionZone<'_>>
     for pair in v_clips.windows(2) {
         let clip_a = pair[0];
         let clip_b = pair[1];
+        // A disabled clip on either side leaves nothing to blend.
+        if !clip_a.enabled || !clip_b.enabled {
+            continue;
+        }
 
         // Look for a non-Cut transition recorded after clip_a.
         // Use if-let + continue instead of ? so a missing transition on one pair
@@ -243,7 +497,7 @@ pub fn active_transition_at(state: &ProjectState) -> Option<TransitionZone<'_>>
         let local_blend = t - zone_start; // 0.0 .. D
         let alpha = (local_blend / d).clamp(0.0, 1.0) as f32;
 
-        const ONE_FRAME: f64 = 1.0 / 30.0;
+        let one_frame = state.frame_secs();
 
         // Source timestamps (centered on cut):
         //   clip_a: last D/2 of its source playing out over the full zone.
@@ -251,18 +505,23 @@ pub fn active_transition_at(state: &ProjectState) -> Option<TransitionZone<'_>>
         //     last valid frame — we never request a timestamp past source end.
         //   clip_b: first D/2 of its source; starts at source_offset, freezes
         //     before the cut, then advances from the cut to zone_end.
-        let clip_a_source_ts = (clip_a.source_offset + clip_a.duration - half_d + local_blend)
-            .clamp(
-                clip_a.source_offset,
-                (clip_a.source_offset + clip_a.duration - ONE_FRAME).max(clip_a.source_offset),
-            )
-            .max(0.0);
-        let clip_b_source_ts = (clip_b.source_offset + (local_blend - half_d).max(0.0))
-            .clamp(
-                clip_b.source_offset,
-                (clip_b.source_offset + clip_b.duration - ONE_FRAME).max(clip_b.source_offset),
-            )
-            .max(0.0);
+        //   A freeze clip on either side contributes its held frame throughout.
+        let clip_a_source_ts = clip_a.freeze_at.unwrap_or(
+            (clip_a.source_offset + clip_a.duration - half_d + local_blend)
+                .clamp(
+                    clip_a.source_offset,
+                    (clip_a.source_offset + clip_a.duration - one_frame).max(clip_a.source_offset),
+                )
+                .max(0.0),
+        );
+        let clip_b_source_ts = clip_b.freeze_at.unwrap_or(
+            (clip_b.source_offset + (local_blend - half_d).max(0.0))
+                .clamp(
+                    clip_b.source_offset,
+                    (clip_b.source_offset + clip_b.duration - one_frame).max(clip_b.source_offset),
+                )
+                .max(0.0),
+        );
 
         return Some(TransitionZone {
             clip_a,
@@ -288,7 +547,8 @@ pub fn active_transition_at(state: &ProjectState) -> Option<TransitionZone<'_>>
 /// they play as independent overlays via `active_overlay_clips` so they mix
 /// additively with V-row audio rather than silencing it.
 ///
-/// V-row clips with `audio_muted = true` are skipped.
+/// Clips with `audio_muted = true`, and disabled clips, are skipped on either
+/// row.
 #[inline]
 pub fn active_audio_clip(state: &ProjectState, time: f64) -> Option<&TimelineClip> {
     // Extracted A-row first (linked_clip_id present = V↔A pair, not standalone)
@@ -298,6 +558,8 @@ pub fn active_audio_clip(state: &ProjectState, time: f64) -> Option<&TimelineCli
         .find(|c| {
             matches!(c.track_row, 1 | 3)
                 && c.linked_clip_id.is_some()
+                && !c.audio_muted
+                && c.enabled
                 && c.start_time <= time
                 && time < c.start_time + c.duration
         })
@@ -306,6 +568,7 @@ pub fn active_audio_clip(state: &ProjectState, time: f64) -> Option<&TimelineCli
             state.timeline.iter().find(|c| {
                 matches!(c.track_row, 0 | 2)
                     && !c.audio_muted
+                    && c.enabled
                     && c.start_time <= time
                     && time < c.start_time + c.duration
             })
@@ -326,17 +589,209 @@ pub fn active_overlay_clips(state: &ProjectState, time: f64) -> Vec<&TimelineCli
         .filter(|c| {
             matches!(c.track_row, 1 | 3)
                 && c.linked_clip_id.is_none()
+                && !c.audio_muted
+                && c.enabled
                 && c.start_time <= time
                 && time < c.start_time + c.duration
         })
         .collect()
 }
 
+// ── Project audio overview ────────────────────────────────────────────────────
+
+/// Stitch every audible clip's `waveform_peaks` into one project-wide peak
+/// buffer of `columns` entries covering timeline seconds `0..span`.
+///
+/// Each column takes, per clip, the loudest source peak it spans (scaled by
+/// the clip's volume) and sums the clips, clamped to 1.0 — overlays mix
+/// additively in export too. Disabled clips, muted V-row audio, freeze holds,
+/// and clips whose waveform has not arrived yet contribute nothing.
+pub fn mixdown_peaks(state: &ProjectState, span: f64, columns: usize) -> Vec<f32> {
+    let mut out = vec![0.0_f32; columns];
+    if columns == 0 || span <= 0.0 {
+        return out;
+    }
+    let col_secs = span / columns as f64;
+    for clip in &state.timeline {
+        if !clip.enabled || clip.audio_muted || clip.freeze_at.is_some() || clip.volume <= 0.0 {
+            continue;
+        }
+        let Some(lib) = library_entry_for(state, clip) else {
+            continue;
+        };
+        let peaks = &lib.waveform_peaks;
+        if peaks.is_empty() || lib.duration <= 0.0 {
+            continue;
+        }
+        let per_sec = peaks.len() as f64 / lib.duration;
+        let first = (clip.start_time / col_secs).floor().max(0.0) as usize;
+        let last = (((clip.start_time + clip.duration) / col_secs).ceil() as usize).min(columns);
+        for (col, slot) in out.iter_mut().enumerate().take(last).skip(first) {
+            // Overlap of this column with the clip, in source seconds.
+            let t0 = (col as f64 * col_secs).max(clip.start_time);
+            let t1 = ((col + 1) as f64 * col_secs).min(clip.start_time + clip.duration);
+            if t1 <= t0 {
+                continue;
+            }
+            let s0 = clip.source_offset + (t0 - clip.start_time);
+            let s1 = clip.source_offset + (t1 - clip.start_time);
+            let i0 = ((s0 * per_sec) as usize).min(peaks.len() - 1);
+            let i1 = ((s1 * per_sec).ceil() as usize).clamp(i0 + 1, peaks.len());
+            let peak = peaks[i0..i1].iter().copied().fold(0.0_f32, f32::max);
+            *slot = (*slot + peak * clip.volume).min(1.0);
+        }
+    }
+    out
+}
+
+/// Peak level of the preview mix at timeline `time`, for the transport meter.
+///
+/// Mirrors what the audio module plays: the primary clip plus standalone
+/// overlays, each at its waveform peak within ±50 ms of its source time ×
+/// clip volume, mixed at 1/√n and scaled by the master volume. Not clamped —
+/// above 1.0 means the mix clips. Resolution is the waveform's, so this is a
+/// level meter, not a sample-accurate scope.
+pub fn playback_level(state: &ProjectState, time: f64) -> f32 {
+    const WINDOW_SECS: f64 = 0.05;
+    if state.muted || state.preview_rate > PREVIEW_RATE_AUDIO_MAX {
+        return 0.0;
+    }
+    let clips: Vec<&TimelineClip> = active_audio_clip(state, time)
+        .into_iter()
+        .chain(active_overlay_clips(state, time))
+        .collect();
+    let mix_factor = 1.0 / (clips.len().max(1) as f32).sqrt();
+    let sum: f32 = clips
+        .iter()
+        .filter_map(|clip| {
+            let lib = library_entry_for(state, clip)?;
+            let peaks = &lib.waveform_peaks;
+            if peaks.is_empty() || lib.duration <= 0.0 || clip.freeze_at.is_some() {
+                return None;
+            }
+            let per_sec = peaks.len() as f64 / lib.duration;
+            let src = clip.source_time(time - clip.start_time);
+            let i0 = (((src - WINDOW_SECS) * per_sec).max(0.0) as usize).min(peaks.len() - 1);
+            let i1 = (((src + WINDOW_SECS) * per_sec).ceil() as usize).clamp(i0 + 1, peaks.len());
+            Some(peaks[i0..i1].iter().copied().fold(0.0_f32, f32::max) * clip.volume)
+        })
+        .sum();
+    sum * mix_factor * state.volume
+}
+
+// ── Ranges ────────────────────────────────────────────────────────────────────
+
+/// The span between the markers either side of `time`, with the timeline
+/// start and end standing in where there is no marker. A marker exactly at
+/// `time` opens the span.
+pub fn marker_range_around(state: &ProjectState, time: f64) -> (f64, f64) {
+    let start = state
+        .markers
+        .iter()
+        .map(|m| m.time)
+        .filter(|&t| t <= time)
+        .fold(0.0, f64::max);
+    let end = state
+        .markers
+        .iter()
+        .map(|m| m.time)
+        .filter(|&t| t > time)
+        .fold(state.total_duration(), f64::min);
+    (start, end)
+}
+
+/// Copies of the clips overlapping `start..end`, cut to it and shifted so
+/// `start` lands at 0 — the timeline a range render (test render, nest)
+/// encodes. A clip cut at an edge loses its fade on that side, since the
+/// cut is not a real clip end.
+pub fn clips_in_range(state: &ProjectState, start: f64, end: f64) -> Vec<TimelineClip> {
+    state
+        .timeline
+        .iter()
+        .filter(|tc| tc.start_time < end && tc.start_time + tc.duration > start)
+        .map(|tc| {
+            let mut tc = tc.clone();
+            if tc.start_time < start {
+                let cut = start - tc.start_time;
+                tc.source_offset += cut;
+                tc.duration -= cut;
+                tc.start_time = start;
+                tc.fade_in_secs = 0.0;
+                tc.fade_in_start_secs = 0.0;
+            }
+            if tc.start_time + tc.duration > end {
+                tc.duration = end - tc.start_time;
+                tc.fade_out_secs = 0.0;
+                tc.fade_out_end_secs = 0.0;
+            }
+            tc.start_time -= start;
+            tc
+        })
+        .collect()
+}
+
+/// Library entry of the earliest clip on the timeline — the source export
+/// follows for "save next to source" and "match source" frame rate. Video rows
+/// win over audio rows, because an extracted-audio entry may point at a temp
+/// WAV rather than the user's file.
+pub fn first_source_clip(state: &ProjectState) -> Option<&LibraryClip> {
+    let earliest = |video: bool| {
+        state
+            .timeline
+            .iter()
+            .filter(|c| (c.track_row % 2 == 0) == video)
+            .min_by(|a, b| a.start_time.total_cmp(&b.start_time))
+    };
+    let clip = earliest(true).or_else(|| earliest(false))?;
+    library_entry_for(state, clip)
+}
+
+/// Library entry of the video source that fills the most enabled time on
+/// the video rows — what "Export at source settings" copies its resolution
+/// and frame rate from. Ties go to the entry earlier in the library.
+/// `None` when no video-row clip has a probed frame size.
+pub fn dominant_source_clip(state: &ProjectState) -> Option<&LibraryClip> {
+    let mut best: Option<(&LibraryClip, f64)> = None;
+    for lib in &state.library {
+        if lib.video_size.is_none() {
+            continue;
+        }
+        let covered: f64 = state
+            .timeline
+            .iter()
+            .filter(|c| c.media_id == lib.id && c.enabled && c.track_row % 2 == 0)
+            .map(|c| c.duration)
+            .sum();
+        if covered > 0.0 && best.is_none_or(|(_, b)| covered > b) {
+            best = Some((lib, covered));
+        }
+    }
+    best.map(|(lib, _)| lib)
+}
+
+/// Library entries used on the timeline whose probe has not finished
+/// (`LibraryClip::is_loading`), each listed once, in library order.
+pub fn loading_sources(state: &ProjectState) -> Vec<&LibraryClip> {
+    state
+        .library
+        .iter()
+        .filter(|l| l.is_loading() && state.timeline.iter().any(|tc| tc.media_id == l.id))
+        .collect()
+}
+
+/// Source file of `first_source_clip` — the folder a "save next to source"
+/// render goes to.
+pub fn first_source_path(state: &ProjectState) -> Option<&std::path::Path> {
+    first_source_clip(state).map(|l| l.path.as_path())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use uuid::Uuid;
-    use velocut_core::state::{ClipType, LibraryClip, TimelineClip};
+    use velocut_core::state::{
+        ClipType, ColorRangeMode, DeinterlaceMode, LibraryClip, Marker, TimelineClip,
+    };
     use velocut_core::transitions::{TimelineTransition, TransitionKind, TransitionType};
 
     fn make_state() -> ProjectState {
@@ -362,6 +817,16 @@ mod tests {
             video_size: Some((1920, 1080)),
             audio_path: None,
             audio_trimmed_offset: 0.0,
+            audio_streams: Vec::new(),
+            audio_stream_index: None,
+            color_range: None,
+            frame_rate: None,
+            file_bytes: None,
+            bit_rate: None,
+            probe_error: None,
+            color_range_mode: ColorRangeMode::Auto,
+            interlaced: None,
+            deinterlace_mode: DeinterlaceMode::Auto,
         });
     }
 
@@ -379,15 +844,7 @@ mod tests {
             start_time,
             duration,
             track_row,
-            source_offset: 0.0,
-            volume: 1.0,
-            linked_clip_id: None,
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         });
     }
 
@@ -456,6 +913,16 @@ mod tests {
         assert!(clip_at_time(&state, 3.0).is_none());
     }
 
+    #[test]
+    fn clip_at_time_skips_disabled_clip() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "test", 10.0, ClipType::Video);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 5.0, 0);
+        state.timeline[0].enabled = false;
+        assert!(clip_at_time(&state, 2.0).is_none());
+    }
+
     // ── is_extracted_audio_clip ────────────────────────────────────────────────
 
     #[test]
@@ -466,15 +933,8 @@ mod tests {
             start_time: 0.0,
             duration: 5.0,
             track_row: 1, // odd = audio row
-            source_offset: 0.0,
-            volume: 1.0,
             linked_clip_id: Some(Uuid::new_v4()),
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         };
         assert!(is_extracted_audio_clip(&clip));
     }
@@ -487,15 +947,8 @@ mod tests {
             start_time: 0.0,
             duration: 5.0,
             track_row: 0, // even = video row
-            source_offset: 0.0,
-            volume: 1.0,
             linked_clip_id: Some(Uuid::new_v4()),
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         };
         assert!(!is_extracted_audio_clip(&clip));
     }
@@ -508,15 +961,7 @@ mod tests {
             start_time: 0.0,
             duration: 5.0,
             track_row: 1,
-            source_offset: 0.0,
-            volume: 1.0,
-            linked_clip_id: None,
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         };
         assert!(!is_extracted_audio_clip(&clip));
     }
@@ -560,6 +1005,30 @@ mod tests {
         assert!((z.alpha - 0.25).abs() < 0.01); // 0.5/2.0 = 0.25
     }
 
+    #[test]
+    fn active_transition_at_holds_freeze_frame() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "test", 20.0, ClipType::Video);
+        let clip_a = Uuid::new_v4();
+        let clip_b = Uuid::new_v4();
+        add_timeline_clip(&mut state, clip_a, lib_id, 0.0, 5.0, 0);
+        add_timeline_clip(&mut state, clip_b, lib_id, 5.0, 3.0, 0);
+        state.timeline[1].source_offset = 7.0;
+        state.timeline[1].freeze_at = Some(7.0);
+        state.transitions.push(TimelineTransition {
+            after_clip_id: clip_a,
+            kind: TransitionType::new(TransitionKind::Crossfade, 2.0),
+        });
+
+        // Both halves of the zone show the held frame for clip_b.
+        for t in [4.5, 5.5] {
+            state.current_time = t;
+            let z = active_transition_at(&state).unwrap();
+            assert_eq!(z.clip_b_source_ts, 7.0);
+        }
+    }
+
     #[test]
     fn active_transition_at_outside_zone_returns_none() {
         let mut state = make_state();
@@ -611,15 +1080,8 @@ mod tests {
             start_time: 0.0,
             duration: 5.0,
             track_row: 0,
-            source_offset: 0.0,
-            volume: 1.0,
-            linked_clip_id: None,
             audio_muted: true, // muted!
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         };
         state.timeline.push(clip);
         assert!(active_audio_clip(&state, 2.0).is_none());
@@ -640,21 +1102,24 @@ mod tests {
             start_time: 0.0,
             duration: 10.0,
             track_row: 1,
-            source_offset: 0.0,
-            volume: 1.0,
             linked_clip_id: None, // standalone
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         });
         let overlays = active_overlay_clips(&state, 5.0);
         assert_eq!(overlays.len(), 1);
         assert_eq!(overlays[0].id, overlay_id);
     }
 
+    #[test]
+    fn active_overlay_clips_excludes_muted_audio() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "music", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 10.0, 1);
+        state.timeline[0].audio_muted = true;
+        assert!(active_overlay_clips(&state, 5.0).is_empty());
+    }
+
     #[test]
     fn active_overlay_clips_excludes_linked_audio() {
         let mut state = make_state();
@@ -667,17 +1132,432 @@ mod tests {
             start_time: 0.0,
             duration: 5.0,
             track_row: 1,
-            source_offset: 0.0,
-            volume: 1.0,
             linked_clip_id: Some(Uuid::new_v4()), // linked → extracted audio
-            audio_muted: false,
-            fade_in_secs: 0.0,
-            fade_in_start_secs: 0.0,
-            fade_out_secs: 0.0,
-            fade_out_end_secs: 0.0,
-            filter: Default::default(),
+            ..Default::default()
         });
         let overlays = active_overlay_clips(&state, 2.0);
         assert!(overlays.is_empty());
     }
+
+    // ── match_frame_source ─────────────────────────────────────────────────────
+
+    #[test]
+    fn match_frame_prefers_video_row_under_playhead() {
+        let mut state = make_state();
+        let (v_lib, a_lib) = (Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, a_lib, "music", 30.0, ClipType::Audio);
+        add_lib_clip(&mut state, v_lib, "shot", 30.0, ClipType::Video);
+        add_timeline_clip(&mut state, Uuid::new_v4(), a_lib, 0.0, 10.0, 1);
+        let v_id = Uuid::new_v4();
+        add_timeline_clip(&mut state, v_id, v_lib, 2.0, 10.0, 0);
+        state
+            .timeline
+            .iter_mut()
+            .find(|c| c.id == v_id)
+            .unwrap()
+            .source_offset = 4.0;
+        state.current_time = 5.0;
+
+        let (ts, lib) = match_frame_source(&state, None).unwrap();
+        assert_eq!(lib.id, v_lib);
+        assert!(
+            (ts - 7.0).abs() < 1e-9,
+            "source = 4.0 + (5.0 - 2.0), got {ts}"
+        );
+    }
+
+    #[test]
+    fn match_frame_explicit_clip_clamps_to_its_range() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "shot", 30.0, ClipType::Video);
+        let id = Uuid::new_v4();
+        add_timeline_clip(&mut state, id, lib_id, 10.0, 5.0, 0);
+        state.current_time = 2.0; // before the clip
+
+        let (ts, _) = match_frame_source(&state, Some(id)).unwrap();
+        assert_eq!(ts, 0.0);
+    }
+
+    #[test]
+    fn match_frame_none_in_gap() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "shot", 30.0, ClipType::Video);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 5.0, 0);
+        state.current_time = 8.0;
+        assert!(match_frame_source(&state, None).is_none());
+    }
+
+    // ── mixdown_peaks ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn mixdown_places_clips_at_timeline_positions() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "a", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 5.0, 5.0, 1);
+        let mix = mixdown_peaks(&state, 10.0, 10);
+        assert!(mix[..5].iter().all(|&p| p == 0.0));
+        assert!(mix[5..].iter().all(|&p| (p - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn mixdown_sums_overlaps_and_clamps() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "a", 10.0, ClipType::Audio);
+        for _ in 0..3 {
+            add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 4.0, 1);
+        }
+        let mix = mixdown_peaks(&state, 4.0, 4);
+        assert!(mix.iter().all(|&p| p == 1.0));
+    }
+
+    #[test]
+    fn mixdown_skips_muted_video_audio() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        let id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "v", 10.0, ClipType::Video);
+        add_timeline_clip(&mut state, id, lib_id, 0.0, 4.0, 0);
+        state.timeline[0].audio_muted = true;
+        assert!(mixdown_peaks(&state, 4.0, 8).iter().all(|&p| p == 0.0));
+    }
+
+    // ── playback_level ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn playback_level_follows_clip_and_master_volume() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "a", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 10.0, 1);
+        state.volume = 1.0;
+        state.timeline[0].volume = 2.0;
+        assert!((playback_level(&state, 5.0) - 1.0).abs() < 1e-6);
+        state.volume = 0.5;
+        assert!((playback_level(&state, 5.0) - 0.5).abs() < 1e-6);
+        assert_eq!(playback_level(&state, 12.0), 0.0);
+    }
+
+    #[test]
+    fn playback_level_is_silent_when_muted() {
+        let mut state = make_state();
+        let lib_id = Uuid::new_v4();
+        add_lib_clip(&mut state, lib_id, "a", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib_id, 0.0, 10.0, 1);
+        state.volume = 1.0;
+        state.muted = true;
+        assert_eq!(playback_level(&state, 5.0), 0.0);
+    }
+
+    // ── first_source_path ──────────────────────────────────────────────────────
+
+    #[test]
+    fn neighbor_clip_walks_start_order_and_wraps() {
+        let mut state = make_state();
+        let lib = Uuid::new_v4();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, lib, "v", 10.0, ClipType::Video);
+        add_timeline_clip(&mut state, c, lib, 8.0, 2.0, 0);
+        add_timeline_clip(&mut state, a, lib, 0.0, 4.0, 0);
+        add_timeline_clip(&mut state, b, lib, 2.0, 3.0, 1);
+
+        let next = |from, fwd, same| neighbor_clip(&state, Some(from), fwd, same).map(|c| c.id);
+        assert_eq!(next(a, true, false), Some(b));
+        assert_eq!(next(c, true, false), Some(a));
+        assert_eq!(next(a, false, false), Some(c));
+        // Same-track skips the clip on row 1.
+        assert_eq!(next(a, true, true), Some(c));
+    }
+
+    #[test]
+    fn neighbor_clip_without_selection_starts_at_playhead() {
+        let mut state = make_state();
+        let lib = Uuid::new_v4();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, lib, "v", 10.0, ClipType::Video);
+        add_timeline_clip(&mut state, a, lib, 0.0, 4.0, 0);
+        add_timeline_clip(&mut state, b, lib, 4.0, 4.0, 0);
+        state.current_time = 2.0;
+
+        assert_eq!(
+            neighbor_clip(&state, None, true, false).map(|c| c.id),
+            Some(b)
+        );
+        assert_eq!(
+            neighbor_clip(&state, None, false, false).map(|c| c.id),
+            Some(a)
+        );
+        assert!(neighbor_clip(&make_state(), None, true, false).is_none());
+    }
+
+    #[test]
+    fn apply_targets_follow_scope_and_skip_source_clip() {
+        let mut state = make_state();
+        let (cam, music) = (Uuid::new_v4(), Uuid::new_v4());
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        add_lib_clip(&mut state, cam, "cam", 10.0, ClipType::Video);
+        add_lib_clip(&mut state, music, "music", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, a, cam, 0.0, 2.0, 0);
+        add_timeline_clip(&mut state, b, cam, 2.0, 2.0, 2);
+        add_timeline_clip(&mut state, c, music, 0.0, 4.0, 1);
+        add_timeline_clip(&mut state, d, music, 4.0, 2.0, 0);
+        let from = state.timeline[0].clone();
+
+        let targets = |scope, video_only| apply_targets(&state, &from, scope, video_only);
+        assert_eq!(targets(ApplyScope::All, false), vec![b, c, d]);
+        assert_eq!(targets(ApplyScope::All, true), vec![b, d]);
+        assert_eq!(targets(ApplyScope::SameSource, false), vec![b]);
+        assert_eq!(targets(ApplyScope::SameTrack, false), vec![d]);
+    }
+
+    #[test]
+    fn crossfade_overlap_measures_head_over_previous_tail() {
+        let mut state = make_state();
+        let (cam, a, b, c) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        add_lib_clip(&mut state, cam, "cam", 60.0, ClipType::Video);
+        add_timeline_clip(&mut state, a, cam, 0.0, 5.0, 0);
+        add_timeline_clip(&mut state, b, cam, 5.0, 4.0, 0);
+        add_timeline_clip(&mut state, c, cam, 0.0, 1.0, 1);
+
+        let (prev, secs) = crossfade_overlap(&state, b, 0, 4.5).unwrap();
+        assert_eq!(prev, a);
+        assert!((secs - 0.5).abs() < 1e-9);
+        // Butted, in a gap, on an audio track, or swallowing the neighbor.
+        assert!(crossfade_overlap(&state, b, 0, 5.0).is_none());
+        assert!(crossfade_overlap(&state, b, 2, 4.5).is_none());
+        assert!(crossfade_overlap(&state, c, 1, 4.5).is_none());
+        assert!(crossfade_overlap(&state, b, 0, 0.5).is_none());
+    }
+
+    #[test]
+    fn sync_reference_prefers_most_overlap_on_adjacent_track() {
+        let mut state = make_state();
+        let (cam, rec, still) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (a, b, c, d, e) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        add_lib_clip(&mut state, cam, "cam", 60.0, ClipType::Video);
+        add_lib_clip(&mut state, rec, "rec", 60.0, ClipType::Audio);
+        add_lib_clip(&mut state, still, "still", 5.0, ClipType::Image);
+        add_timeline_clip(&mut state, a, cam, 10.0, 20.0, 0);
+        add_timeline_clip(&mut state, b, rec, 0.0, 12.0, 1);
+        add_timeline_clip(&mut state, c, rec, 14.0, 20.0, 1);
+        add_timeline_clip(&mut state, d, still, 10.0, 20.0, 2); // no audio
+        add_timeline_clip(&mut state, e, rec, 10.0, 20.0, 3); // not adjacent
+        let clip = state.timeline[0].clone();
+        assert_eq!(sync_reference(&state, &clip).map(|c| c.id), Some(c));
+
+        // Nothing overlapping: the nearest clip still wins.
+        state.timeline.retain(|t| t.id != c);
+        assert_eq!(sync_reference(&state, &clip).map(|c| c.id), Some(b));
+
+        // A linked partner is never a reference.
+        state.link_clips(&[a, b]);
+        assert!(sync_reference(&state, &clip).is_none());
+    }
+
+    #[test]
+    fn sync_offset_matches_synced_start() {
+        let mut state = make_state();
+        let (cam, rec, a, b) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        add_lib_clip(&mut state, cam, "cam", 60.0, ClipType::Video);
+        add_lib_clip(&mut state, rec, "rec", 60.0, ClipType::Audio);
+        add_timeline_clip(&mut state, a, rec, 3.0, 20.0, 1);
+        add_timeline_clip(&mut state, b, cam, 5.0, 20.0, 0);
+        state.timeline[0].source_offset = 2.0;
+        state.timeline[1].source_offset = 1.5;
+
+        let start = state.synced_start(a, b, 0.25).unwrap();
+        state.timeline[0].start_time = start;
+        let offset = sync_offset(&state.timeline[0], &state.timeline[1]);
+        assert!((offset - 0.25).abs() < 1e-9, "offset {offset}");
+    }
+
+    #[test]
+    fn peak_at_maps_source_time_to_waveform() {
+        let mut state = make_state();
+        let id = Uuid::new_v4();
+        add_lib_clip(&mut state, id, "a", 10.0, ClipType::Audio);
+        let lib = &mut state.library[0];
+        lib.waveform_peaks = (0..100).map(|i| i as f32 / 100.0).collect();
+        assert_eq!(peak_at(lib, 5.0), 0.5);
+        assert_eq!(peak_at(lib, -1.0), 0.0);
+        assert_eq!(peak_at(lib, 10.0), 0.0);
+        lib.waveform_peaks.clear();
+        assert_eq!(peak_at(lib, 5.0), 0.0);
+    }
+
+    #[test]
+    fn marker_range_around_uses_neighbouring_markers() {
+        let mut state = make_state();
+        let lib = Uuid::new_v4();
+        add_lib_clip(&mut state, lib, "a", 30.0, ClipType::Video);
+        add_timeline_clip(&mut state, Uuid::new_v4(), lib, 0.0, 20.0, 0);
+        for time in [4.0, 12.0] {
+            state.markers.push(Marker {
+                id: Uuid::new_v4(),
+                time,
+            });
+        }
+        assert_eq!(marker_range_around(&state, 2.0), (0.0, 4.0));
+        assert_eq!(marker_range_around(&state, 4.0), (4.0, 12.0));
+        assert_eq!(marker_range_around(&state, 15.0), (12.0, 20.0));
+    }
+
+    #[test]
+    fn clips_in_range_cuts_edges_and_rebases() {
+        let mut state = make_state();
+        let lib = Uuid::new_v4();
+        add_lib_clip(&mut state, lib, "a", 30.0, ClipType::Video);
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        add_timeline_clip(&mut state, a, lib, 0.0, 5.0, 0);
+        add_timeline_clip(&mut state, b, lib, 5.0, 5.0, 0);
+        add_timeline_clip(&mut state, c, lib, 10.0, 5.0, 0);
+        state.timeline[0].fade_in_secs = 1.0;
+        state.timeline[2].fade_out_secs = 1.0;
+
+        let clips = clips_in_range(&state, 3.0, 12.0);
+        assert_eq!(clips.len(), 3);
+        assert_eq!((clips[0].start_time, clips[0].duration), (0.0, 2.0));
+        assert_eq!(clips[0].source_offset, 3.0);
+        assert_eq!(clips[0].fade_in_secs, 0.0);
+        assert_eq!((clips[1].start_time, clips[1].duration), (2.0, 5.0));
+        assert_eq!((clips[2].start_time, clips[2].duration), (7.0, 2.0));
+        assert_eq!(clips[2].fade_out_secs, 0.0);
+
+        assert!(clips_in_range(&state, 20.0, 25.0).is_empty());
+    }
+
+    #[test]
+    fn first_source_prefers_earliest_video_clip() {
+        let mut state = make_state();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, a, "late", 10.0, ClipType::Video);
+        add_lib_clip(&mut state, b, "early", 10.0, ClipType::Video);
+        add_lib_clip(&mut state, c, "music", 10.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), a, 6.0, 2.0, 0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), b, 2.0, 2.0, 2);
+        add_timeline_clip(&mut state, Uuid::new_v4(), c, 0.0, 2.0, 1);
+        assert_eq!(
+            first_source_path(&state),
+            Some(std::path::Path::new("C:\\test\\early.mp4"))
+        );
+    }
+
+    #[test]
+    fn first_source_none_on_empty_timeline() {
+        assert!(first_source_path(&make_state()).is_none());
+    }
+
+    #[test]
+    fn dominant_source_is_longest_enabled_video_coverage() {
+        let mut state = make_state();
+        let (short, long, music) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, short, "short", 20.0, ClipType::Video);
+        add_lib_clip(&mut state, long, "long", 20.0, ClipType::Video);
+        add_lib_clip(&mut state, music, "music", 60.0, ClipType::Audio);
+        add_timeline_clip(&mut state, Uuid::new_v4(), short, 0.0, 4.0, 0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), long, 4.0, 3.0, 0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), long, 7.0, 3.0, 2);
+        add_timeline_clip(&mut state, Uuid::new_v4(), music, 0.0, 30.0, 1);
+        assert_eq!(dominant_source_clip(&state).map(|l| l.id), Some(long));
+
+        // Disabled clips don't render, so they don't count.
+        state.timeline[2].enabled = false;
+        assert_eq!(dominant_source_clip(&state).map(|l| l.id), Some(short));
+    }
+
+    #[test]
+    fn dominant_source_none_without_video() {
+        let mut state = make_state();
+        let music = Uuid::new_v4();
+        add_lib_clip(&mut state, music, "music", 60.0, ClipType::Audio);
+        state.library[0].video_size = None;
+        add_timeline_clip(&mut state, Uuid::new_v4(), music, 0.0, 30.0, 0);
+        assert!(dominant_source_clip(&state).is_none());
+    }
+
+    #[test]
+    fn playback_end_time_backs_off_one_source_frame() {
+        let mut state = make_state();
+        assert_eq!(playback_end_time(&state), 0.0);
+
+        let (video, music) = (Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, video, "video", 20.0, ClipType::Video);
+        add_lib_clip(&mut state, music, "music", 20.0, ClipType::Audio);
+        state.library[0].frame_rate = Some(25.0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), video, 0.0, 10.0, 0);
+        assert!((playback_end_time(&state) - 9.96).abs() < 1e-9);
+
+        // The clip ending last decides, and an unprobed rate counts as the
+        // project's (30 fps by default).
+        add_timeline_clip(&mut state, Uuid::new_v4(), music, 5.0, 6.0, 1);
+        assert!((playback_end_time(&state) - (11.0 - 1.0 / 30.0)).abs() < 1e-9);
+    }
+
+    // ── loading_sources ────────────────────────────────────────────────────────
+
+    #[test]
+    fn loading_sources_lists_unprobed_timeline_media_once() {
+        let mut state = make_state();
+        let (done, sizing, unused) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        add_lib_clip(&mut state, done, "done", 10.0, ClipType::Video);
+        add_lib_clip(&mut state, sizing, "sizing", 10.0, ClipType::Video);
+        add_lib_clip(&mut state, unused, "unused", 10.0, ClipType::Video);
+        state.library[1].video_size = None;
+        state.library[2].duration_probed = false;
+        add_timeline_clip(&mut state, Uuid::new_v4(), done, 0.0, 2.0, 0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), sizing, 2.0, 2.0, 0);
+        add_timeline_clip(&mut state, Uuid::new_v4(), sizing, 4.0, 2.0, 0);
+        let loading: Vec<Uuid> = loading_sources(&state).iter().map(|l| l.id).collect();
+        assert_eq!(loading, vec![sizing]);
+
+        state.library[1].probe_error = Some("no video stream".into());
+        assert!(loading_sources(&state).is_empty());
+    }
+
+    // ── transition_boundaries ─────────────────────────────────────────────────
+
+    #[test]
+    fn transition_boundaries_only_touching_video_joins() {
+        let mut state = make_state();
+        let mid = Uuid::new_v4();
+        add_lib_clip(&mut state, mid, "a", 30.0, ClipType::Video);
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        add_timeline_clip(&mut state, b, mid, 5.0, 5.0, 0);
+        add_timeline_clip(&mut state, a, mid, 0.0, 5.0, 0);
+        // 2 s gap after `b` — no boundary.
+        add_timeline_clip(&mut state, c, mid, 12.0, 5.0, 0);
+        // Touching audio clips never get a transition.
+        add_timeline_clip(&mut state, d, mid, 0.0, 5.0, 1);
+        add_timeline_clip(&mut state, Uuid::new_v4(), mid, 5.0, 5.0, 1);
+        assert_eq!(transition_boundaries(&state), vec![a]);
+    }
 }
@@ -107,3 +107,52 @@ mod tests {
         assert!(t.is_empty() || t == "é" || t.len() <= 1);
     }
 }
+
+/// Formats a byte count with binary units ("812 KB", "1.4 GB"). Bytes below
+/// 1 KB are shown whole.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut v = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while v >= 1024.0 && unit < UNITS.len() - 1 {
+        v /= 1024.0;
+        unit += 1;
+    }
+    if v < 10.0 {
+        format!("{v:.1} {}", UNITS[unit])
+    } else {
+        format!("{v:.0} {}", UNITS[unit])
+    }
+}
+
+/// Formats a bit rate in bits/s as kbps or Mbps ("320 kbps", "8.2 Mbps").
+pub fn format_bitrate(bits_per_sec: u64) -> String {
+    let kbps = bits_per_sec as f64 / 1000.0;
+    if kbps < 1000.0 {
+        format!("{kbps:.0} kbps")
+    } else {
+        format!("{:.1} Mbps", kbps / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod size_tests {
+    use super::*;
+
+    #[test]
+    fn bytes_pick_a_unit() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(300 * 1024 * 1024), "300 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GB");
+    }
+
+    #[test]
+    fn bitrate_switches_to_mbps() {
+        assert_eq!(format_bitrate(320_000), "320 kbps");
+        assert_eq!(format_bitrate(8_200_000), "8.2 Mbps");
+    }
+}
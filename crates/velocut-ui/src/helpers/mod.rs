@@ -1,6 +1,10 @@
 // crates/velocut-ui/src/helpers/mod.rs
+pub mod alert;
 pub mod clip_query;
 pub mod format;
 pub mod log;
 pub mod memory_manager;
+pub mod perf_hud;
 pub mod reset;
+pub mod shell;
+pub mod shortcuts;
@@ -0,0 +1,120 @@
+// crates/velocut-ui/src/helpers/perf_hud.rs
+//
+// Performance HUD (F12) — a small readout in the top-right corner for
+// diagnosing sluggish scrubbing or playback on specific footage: UI frame
+// rate, scrub decode latency, bucket-cache hit rate, in-flight media jobs and
+// what the texture caches hold. Off by default. Every number is measured
+// in-process from `AppContext`; nothing is recorded or sent anywhere.
+//
+// Open/closed state lives in egui temp memory, like the shortcuts sheet, so
+// it resets to hidden on every launch.
+
+use crate::context::AppContext;
+use crate::theme::{ACCENT, DARK_BG_3, DARK_BORDER, DARK_TEXT_DIM};
+use eframe::egui::{self, Color32, RichText, Stroke};
+
+fn open_id() -> egui::Id {
+    egui::Id::new("perf_hud_open")
+}
+
+/// Whether the HUD is currently showing.
+pub fn is_open(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp::<bool>(open_id()).unwrap_or(false))
+}
+
+/// Count this frame, toggle on F12 and draw the HUD if open. Call once per
+/// frame from `render_panels`, after the panels so it paints on top.
+pub fn show_overlay(ctx: &egui::Context, context: &mut AppContext) {
+    context.perf.tick_frame();
+    if ctx.input(|i| i.key_pressed(egui::Key::F12)) {
+        let open = !is_open(ctx);
+        ctx.data_mut(|d| d.insert_temp(open_id(), open));
+    }
+    if !is_open(ctx) {
+        return;
+    }
+    // egui only repaints on input; keep the numbers moving while idle
+    // without turning the HUD into a busy loop that skews the frame rate.
+    ctx.request_repaint_after(std::time::Duration::from_millis(250));
+
+    let perf = &context.perf;
+    let cache = &context.cache;
+    let (probes, decodes) = context.media_worker.jobs_in_flight();
+    let scrub_bytes: usize = cache
+        .scrub_textures
+        .values()
+        .map(|(_, w, h)| *w as usize * *h as usize * 4)
+        .sum();
+    let mb = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+
+    let rows = [
+        ("FPS", format!("{:.0}", perf.fps)),
+        (
+            "Scrub decode",
+            perf.decode_ms
+                .map_or("—".to_string(), |ms| format!("{ms:.0} ms")),
+        ),
+        (
+            "Bucket hits",
+            perf.hit_rate().map_or("—".to_string(), |r| {
+                format!(
+                    "{:.0}%  ({} / {})",
+                    r * 100.0,
+                    perf.bucket_hits,
+                    perf.bucket_hits + perf.bucket_misses
+                )
+            }),
+        ),
+        ("Jobs", format!("{probes} probe · {decodes} HQ decode")),
+        (
+            "Bucket cache",
+            format!(
+                "{} frames · {:.0} / {:.0} MB",
+                cache.frame_bucket_cache.len(),
+                mb(cache.frame_cache_bytes),
+                mb(cache.max_frame_cache_bytes())
+            ),
+        ),
+        (
+            "Scrub textures",
+            format!("{} · {:.0} MB", cache.scrub_textures.len(), mb(scrub_bytes)),
+        ),
+        ("Thumbnails", cache.thumbnail_cache.len().to_string()),
+    ];
+
+    egui::Area::new(egui::Id::new("perf_hud"))
+        .order(egui::Order::Foreground)
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-12.0, 12.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::new()
+                .fill(DARK_BG_3.gamma_multiply(0.92))
+                .stroke(Stroke::new(1.0_f32, DARK_BORDER))
+                .corner_radius(egui::CornerRadius::same(6))
+                .inner_margin(egui::Margin::same(8))
+                .show(ui, |ui| {
+                    ui.label(
+                        RichText::new("Performance  (F12)")
+                            .size(11.0)
+                            .strong()
+                            .color(ACCENT),
+                    );
+                    ui.add_space(4.0);
+                    egui::Grid::new("perf_hud_grid")
+                        .num_columns(2)
+                        .spacing([12.0, 2.0])
+                        .show(ui, |ui| {
+                            for (label, value) in rows {
+                                ui.label(RichText::new(label).size(10.0).color(DARK_TEXT_DIM));
+                                ui.label(
+                                    RichText::new(value)
+                                        .size(10.0)
+                                        .monospace()
+                                        .color(Color32::WHITE),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
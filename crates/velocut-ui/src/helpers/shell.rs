@@ -0,0 +1,32 @@
+// crates/velocut-ui/src/helpers/shell.rs
+//
+// Hand a file to the OS so it opens in whatever the user has associated with
+// it (media player for renders). Spawned detached; VeloCut never waits on it.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Open `path` with the platform's default application.
+pub fn open_with_system(path: &Path) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        // `start` is a cmd builtin; the empty "" is the window title so a
+        // quoted path is not mistaken for one.
+        let mut c = Command::new("cmd");
+        c.args(["/C", "start", ""]).arg(path);
+        c
+    };
+    #[cfg(target_os = "macos")]
+    let mut cmd = {
+        let mut c = Command::new("open");
+        c.arg(path);
+        c
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut cmd = {
+        let mut c = Command::new("xdg-open");
+        c.arg(path);
+        c
+    };
+    cmd.spawn().map(|_| ())
+}
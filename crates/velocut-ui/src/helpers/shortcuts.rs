@@ -0,0 +1,285 @@
+// crates/velocut-ui/src/helpers/shortcuts.rs
+//
+// Keyboard shortcut reference — the single table every shortcut list in the
+// UI is built from, plus the full-window cheat-sheet overlay (F1 / ?).
+//
+// Adding a shortcut: add its row to `sections()` here. The overlay and the
+// timeline's "?" button both read this table, so nothing else needs editing.
+//
+// Open/closed state lives in egui temp memory rather than on a module so any
+// panel can toggle it without threading a flag through `render_panels`.
+
+use crate::theme::{ACCENT, DARK_TEXT_DIM};
+use eframe::egui::{self, Color32, RichText, Stroke};
+use velocut_core::prefs::SpaceBehavior;
+
+/// One titled block of the cheat-sheet: `(key chord, description)` rows.
+pub struct ShortcutSection {
+    pub title: &'static str,
+    pub rows: Vec<(&'static str, &'static str)>,
+}
+
+/// Every shortcut VeloCut responds to, grouped by where it applies.
+///
+/// `space` selects the Space-bar description so the sheet matches the
+/// current playback preference.
+pub fn sections(space: SpaceBehavior) -> Vec<ShortcutSection> {
+    vec![
+        ShortcutSection {
+            title: "General",
+            rows: vec![
+                ("F1  /  ?", "Show / hide this cheat-sheet"),
+                ("Escape", "Close this cheat-sheet"),
+                ("F12", "Show / hide the performance HUD"),
+            ],
+        },
+        ShortcutSection {
+            title: "Playback",
+            rows: vec![
+                (
+                    "Space",
+                    match space {
+                        SpaceBehavior::Toggle => "Play / Pause",
+                        SpaceBehavior::Momentary => {
+                            "Hold to play (release with Shift: back to start)"
+                        }
+                    },
+                ),
+                ("/", "Play around playhead (pre- / post-roll), then return"),
+                ("← →", "Step one frame"),
+                ("⏹  Stop button", "Stop & return to start"),
+                (
+                    "Rate button",
+                    "Click faster · right-click slower · middle 1×",
+                ),
+                (
+                    "\\  (hold)",
+                    "Preview without clip filters (before / after)",
+                ),
+                ("fx button", "Toggle the filter bypass"),
+                ("Shift + M", "Mute / unmute all audio"),
+                ("Drag on preview", "Jog scrub the playhead"),
+                ("Shift + A", "Cycle the project aspect ratio"),
+            ],
+        },
+        ShortcutSection {
+            title: "Timeline",
+            rows: vec![
+                ("Tab  /  Shift + Tab", "Select next / previous clip"),
+                ("Ctrl + Tab", "Next clip on the same track"),
+                ("S", "Split clip at playhead"),
+                ("F", "Freeze frame at playhead"),
+                ("Shift + F", "Match frame: find source in library"),
+                ("B", "Append library clip to track end"),
+                ("Shift + B", "Ripple-insert library clip at playhead"),
+                ("Shift + drop", "Ripple-insert dragged library clip"),
+                ("Alt + drop", "Drop only the audio of a video clip"),
+                ("Ctrl + drop", "Drop only the video (muted)"),
+                ("E", "Disable / enable selected clip"),
+                (
+                    "← →  (fine-tune sync)",
+                    "Nudge clip 10 ms  ·  Shift: 1 ms  ·  Esc: done",
+                ),
+                ("M", "Add marker at playhead"),
+                ("Drag marker", "Move marker (snaps to clip edges)"),
+                ("Alt + drag", "Move / trim / scrub without snapping"),
+                ("Del / Backspace", "Remove selected clip"),
+                ("Ctrl + Z", "Undo"),
+                ("Ctrl + Y", "Redo"),
+                ("Ctrl + Shift + Z", "Redo (alternate)"),
+            ],
+        },
+        ShortcutSection {
+            title: "Media Library",
+            rows: vec![
+                ("Del / Backspace", "Remove selected clip(s)"),
+                ("Ctrl + A", "Select all"),
+                ("Ctrl + Click", "Toggle clip in selection"),
+                ("Shift + Click", "Range select"),
+                ("Escape", "Clear selection"),
+                ("Double-click", "Open clip in source viewer"),
+                ("I  /  O", "Source viewer: mark in / out (pointer over it)"),
+            ],
+        },
+        ShortcutSection {
+            title: "Timeline Clips",
+            rows: vec![
+                ("Click", "Select clip"),
+                ("Drag body", "Move clip"),
+                ("Drag left edge", "Trim clip start"),
+                ("Drag right edge", "Trim clip end"),
+                ("Right-click", "Clip context menu"),
+                ("🔊 badge", "Adjust clip volume"),
+                ("✂ badge", "Set transition type"),
+            ],
+        },
+        ShortcutSection {
+            title: "Zoom",
+            rows: vec![
+                ("🔍+  /  🔍−", "Zoom timeline in / out"),
+                ("Overview strip", "Click / drag to scroll"),
+            ],
+        },
+    ]
+}
+
+fn open_id() -> egui::Id {
+    egui::Id::new("shortcuts_overlay_open")
+}
+
+/// Whether the cheat-sheet overlay is currently showing.
+pub fn is_open(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp::<bool>(open_id()).unwrap_or(false))
+}
+
+/// Show or hide the cheat-sheet overlay.
+pub fn set_open(ctx: &egui::Context, open: bool) {
+    ctx.data_mut(|d| d.insert_temp(open_id(), open));
+}
+
+/// Flip the cheat-sheet overlay.
+pub fn toggle(ctx: &egui::Context) {
+    set_open(ctx, !is_open(ctx));
+}
+
+/// Draw the cheat-sheet if open and handle its keys. Call once per frame from
+/// `render_panels`, after the panels so the overlay paints on top.
+///
+/// F1 always toggles. `?` toggles only while no widget has keyboard focus so
+/// typing a question mark into a text field does not pop the sheet. Escape,
+/// a click on the scrim, or the close button dismiss it.
+pub fn show_overlay(ctx: &egui::Context, space: SpaceBehavior) {
+    let text_focused = ctx.memory(|m| m.focused().is_some());
+    let (f1, question, escape) = ctx.input(|i| {
+        (
+            i.key_pressed(egui::Key::F1),
+            i.key_pressed(egui::Key::Questionmark)
+                || (i.modifiers.shift && i.key_pressed(egui::Key::Slash)),
+            i.key_pressed(egui::Key::Escape),
+        )
+    });
+    if f1 || (question && !text_focused) {
+        toggle(ctx);
+    }
+    if !is_open(ctx) {
+        return;
+    }
+    if escape {
+        set_open(ctx, false);
+        return;
+    }
+
+    let screen = ctx.viewport_rect();
+
+    // ── Scrim ─────────────────────────────────────────────────────────────────
+    // Interactable so clicks never reach the panels underneath; a click on it
+    // closes the sheet.
+    let scrim = egui::Area::new(egui::Id::new("shortcuts_scrim"))
+        .order(egui::Order::Foreground)
+        .fixed_pos(screen.min)
+        .show(ctx, |ui| {
+            let resp = ui.allocate_response(screen.size(), egui::Sense::click());
+            ui.painter()
+                .rect_filled(screen, 0.0, Color32::from_black_alpha(150));
+            resp
+        });
+    if scrim.inner.clicked() {
+        set_open(ctx, false);
+        return;
+    }
+
+    // ── Card ──────────────────────────────────────────────────────────────────
+    const CARD_W: f32 = 720.0;
+    let card_h = (screen.height() - 80.0).clamp(240.0, 520.0);
+    let card_rect = egui::Rect::from_center_size(screen.center(), egui::vec2(CARD_W, card_h));
+
+    let all = sections(space);
+    let split = all.len().div_ceil(2);
+    let mut close = false;
+
+    egui::Area::new(egui::Id::new("shortcuts_card"))
+        .order(egui::Order::Tooltip)
+        .fixed_pos(card_rect.min)
+        .show(ctx, |ui| {
+            egui::Frame::new()
+                .fill(Color32::from_rgb(18, 18, 26))
+                .stroke(Stroke::new(
+                    1.0_f32,
+                    Color32::from_rgba_unmultiplied(0, 229, 255, 90),
+                ))
+                .corner_radius(egui::CornerRadius::same(8))
+                .inner_margin(egui::Margin::same(16))
+                .shadow(egui::Shadow {
+                    offset: [0, 6],
+                    blur: 18,
+                    spread: 0,
+                    color: Color32::from_black_alpha(150),
+                })
+                .show(ui, |ui| {
+                    ui.set_width(CARD_W - 32.0);
+                    ui.set_max_height(card_h - 32.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("⌨  Keyboard Shortcuts")
+                                .size(13.5)
+                                .strong()
+                                .color(ACCENT),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .small_button(
+                                    RichText::new("🗙").size(10.0).color(Color32::from_gray(130)),
+                                )
+                                .clicked()
+                            {
+                                close = true;
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                    ui.separator();
+                    ui.add_space(4.0);
+
+                    egui::ScrollArea::vertical()
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            ui.columns(2, |cols| {
+                                for (i, section) in all.iter().enumerate() {
+                                    section_ui(&mut cols[usize::from(i >= split)], section);
+                                }
+                            });
+                        });
+                });
+        });
+
+    if close {
+        set_open(ctx, false);
+    }
+}
+
+/// One category block: muted uppercase title, then right-aligned key chords
+/// beside dimmed descriptions.
+fn section_ui(ui: &mut egui::Ui, section: &ShortcutSection) {
+    ui.label(
+        RichText::new(section.title.to_uppercase())
+            .size(9.5)
+            .monospace()
+            .color(Color32::from_rgba_unmultiplied(0, 180, 210, 130)),
+    );
+    ui.add_space(2.0);
+
+    for &(key, desc) in &section.rows {
+        ui.horizontal(|ui| {
+            // Fixed-width key column so all descriptions align cleanly.
+            ui.allocate_ui(egui::vec2(120.0, 16.0), |ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(RichText::new(key).size(10.5).monospace().color(ACCENT));
+                });
+            });
+            ui.add_space(6.0);
+            ui.label(RichText::new(desc).size(10.5).color(DARK_TEXT_DIM));
+        });
+    }
+    ui.add_space(8.0);
+}
@@ -16,7 +16,7 @@ use std::io::BufReader;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 use velocut_core::commands::EditorCommand;
-use velocut_core::state::ProjectState;
+use velocut_core::state::{volume_key_gain, ProjectState, PREVIEW_RATE_AUDIO_MAX};
 
 // Diagnostic logging: routed through the shared log helper so all VeloCut
 // output lands in a single %TEMP%\velocut.log regardless of launch mode.
@@ -109,6 +109,10 @@ pub struct AudioModule {
 
     /// Same deduplication cache for overlay sinks.
     overlay_last_volume: HashMap<Uuid, f32>,
+
+    /// `state.muted` as of the previous tick, so a mute toggle can be applied
+    /// to every live sink at once instead of waiting for the per-clip sync.
+    was_muted: bool,
 }
 
 impl AudioModule {
@@ -125,6 +129,7 @@ impl AudioModule {
             draining_sinks: Vec::new(),
             sink_last_volume: HashMap::new(),
             overlay_last_volume: HashMap::new(),
+            was_muted: false,
         }
     }
 
@@ -208,6 +213,33 @@ impl AudioModule {
         }
     }
 
+    /// Apply a master mute toggle to every live sink right away. The per-clip
+    /// volume sync in `tick` only reaches sinks whose clip is under the
+    /// playhead this frame; exhausted, warming-up or just-departed sinks would
+    /// otherwise keep their old level. Unmuting forgets the cached volumes so
+    /// the next sync re-applies each sink's real level instead of deduping it
+    /// against the zero written here.
+    fn apply_master_mute(&mut self, state: &ProjectState, ctx: &AppContext) {
+        if state.muted == self.was_muted {
+            return;
+        }
+        self.was_muted = state.muted;
+        audio_log(&format!("master mute → {}", state.muted));
+        if state.muted {
+            for (id, sink) in &ctx.audio_sinks {
+                sink.set_volume(0.0);
+                self.sink_last_volume.insert(*id, 0.0);
+            }
+            for (id, sink) in &ctx.audio_overlay_sinks {
+                sink.set_volume(0.0);
+                self.overlay_last_volume.insert(*id, 0.0);
+            }
+        } else {
+            self.sink_last_volume.clear();
+            self.overlay_last_volume.clear();
+        }
+    }
+
     /// Called every frame after commands are processed.
     /// Manages rodio sinks: creates on play, clears on stop/seek.
     #[allow(clippy::map_entry)]
@@ -236,6 +268,8 @@ impl AudioModule {
             }
         }
 
+        self.apply_master_mute(state, ctx);
+
         // Don't touch sinks until the warmup window has passed.
         if self.stream_warmup_ticks > 0 {
             self.stream_warmup_ticks -= 1;
@@ -314,6 +348,21 @@ impl AudioModule {
         } else {
             1.0_f32
         };
+        // Preview rate: sinks play at the same rate as the playhead (pitch
+        // follows, like tape). Past PREVIEW_RATE_AUDIO_MAX the chipmunk audio
+        // is more distracting than useful, so it is silenced instead.
+        let mix_factor = if state.preview_rate > PREVIEW_RATE_AUDIO_MAX {
+            0.0
+        } else {
+            mix_factor
+        };
+        for sink in ctx
+            .audio_sinks
+            .values()
+            .chain(ctx.audio_overlay_sinks.values())
+        {
+            sink.set_speed(state.preview_rate);
+        }
 
         if let Some(clip) = active_clip {
             // Use a labeled block so early exits (WAV not ready, exhausted) fall
@@ -448,6 +497,7 @@ impl AudioModule {
                                             state.volume * clip.volume * mix_factor
                                         };
                                         sink.set_volume(initial_vol);
+                                        sink.set_speed(state.preview_rate);
                                         self.sink_last_volume.insert(clip.id, initial_vol);
                                         sink.play();
                                         audio_log(&format!(
@@ -506,11 +556,19 @@ impl AudioModule {
                             };
                             in_gain.min(out_gain)
                         };
+                        // Volume keys (auto-duck) are keyed by source time.
+                        let key_gain =
+                            volume_key_gain(&clip.volume_keys, clip.source_offset + elapsed);
                         if let Some(sink) = ctx.audio_sinks.get(&clip.id) {
                             let vol = if state.muted {
                                 0.0
                             } else {
-                                state.volume * clip.volume * fade_out * clip_fade * mix_factor
+                                state.volume
+                                    * clip.volume
+                                    * fade_out
+                                    * clip_fade
+                                    * key_gain
+                                    * mix_factor
                             };
                             self.set_primary_volume(clip.id, sink, vol);
                         }
@@ -630,6 +688,7 @@ impl AudioModule {
                                 state.volume * clip.volume * mix_factor
                             };
                             sink.set_volume(initial_vol);
+                            sink.set_speed(state.preview_rate);
                             self.overlay_last_volume.insert(clip.id, initial_vol);
                             sink.play();
                             audio_log(&format!(
@@ -678,10 +737,11 @@ impl AudioModule {
                     };
                     in_gain.min(out_gain)
                 };
+                let key_gain = volume_key_gain(&clip.volume_keys, clip.source_offset + elapsed);
                 let vol = if state.muted {
                     0.0
                 } else {
-                    state.volume * clip.volume * fade_out * clip_fade * mix_factor
+                    state.volume * clip.volume * fade_out * clip_fade * key_gain * mix_factor
                 };
                 self.set_overlay_volume(clip.id, sink, vol);
             }
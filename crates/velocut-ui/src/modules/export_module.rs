@@ -1,10 +1,10 @@
 // crates/velocut-ui/src/modules/export_module.rs
 //
-// ExportModule: right-panel UI for configuring and launching an MP4 render.
+// ExportModule: right-panel UI for configuring and launching an MP4 / MKV / MOV / WebM render.
 //
 // State machine (driven by ProjectState encode fields, set by AppContext):
 //
-//   Idle       → user clicks "Render MP4"
+//   Idle       → user clicks "Render MP4" (or "Render WebM")
 //                → app.rs opens rfd save dialog, calls media_worker.start_encode
 //                → state.encode_job = Some(job_id)
 //
@@ -31,13 +31,30 @@
 //   "Aspect Ratio" ComboBox in the settings UI.
 
 use super::EditorModule;
+use crate::helpers::clip_query;
+use crate::helpers::format::format_bytes;
 use crate::helpers::reset;
 use crate::modules::ThumbnailCache;
 use crate::theme::{ACCENT, DARK_BG_2, DARK_BG_3, DARK_BORDER, DARK_TEXT_DIM, RENDER_BTN};
 use egui::{Color32, Context, Margin, RichText, Stroke, Ui};
 use velocut_core::commands::EditorCommand;
+use velocut_core::helpers::edl::CutListFormat;
 use velocut_core::helpers::geometry::aspect_ratio_value;
+use velocut_core::helpers::storyboard::{
+    StoryboardOptions, StoryboardSampling, STORYBOARD_COLUMNS_RANGE, STORYBOARD_FRAMES_RANGE, STORYBOARD_THUMB_WIDTHS,
+};
+use velocut_core::helpers::time::format_iso_date;
+use velocut_core::media_types::{
+    CoverFrame, DitherMode, ExportFormat, ExportMetadata, ExportTracks, TARGET_KBPS_RANGE,
+    TEST_RENDER_SECS,
+};
+use velocut_core::prefs::{
+    AutoScroll, PerformancePrefs, SpaceBehavior, StoragePrefs, DECODE_THREADS_RANGE, FRAME_CACHE_MB_RANGE,
+    PLAYBACK_AHEAD_RANGE, PREVIEW_SCRUB_SECS_RANGE, PROXY_WIDTHS, ROLL_MS_RANGE,
+};
 use velocut_core::state::{AspectRatio, ProjectState};
+use velocut_core::transitions::TransitionKind;
+use velocut_core::watermark::{Watermark, WatermarkCorner, WATERMARK_SCALE_RANGE};
 use velocut_media::encode::HwEncodeCapabilities;
 
 // ── Colour palette extensions (local to this module) ─────────────────────────
@@ -46,6 +63,8 @@ use velocut_media::encode::HwEncodeCapabilities;
 const GREEN_DIM: Color32 = Color32::from_rgb(100, 220, 140);
 /// Muted red used for error / cancel banners.
 const RED_DIM: Color32 = Color32::from_rgb(230, 100, 100);
+/// Amber used for non-fatal render warnings (e.g. clipped audio).
+const AMBER: Color32 = Color32::from_rgb(255, 160, 50);
 /// Background fill for the progress bar track.
 const TRACK_BG: Color32 = Color32::from_rgb(60, 60, 75);
 /// Filled portion of the progress bar.
@@ -136,16 +155,55 @@ const ALL_ASPECT_RATIOS: &[AspectRatio] = &[
 pub struct ExportModule {
     filename: String,
     quality: QualityPreset,
+    /// Exact output size copied from a source by "Export at source settings".
+    /// Overrides `quality` and the aspect ratio until either is picked again.
+    source_size: Option<(u32, u32)>,
     fps: u32,
+    /// Follow the first timeline clip's probed frame rate instead of `fps`.
+    match_source: bool,
+    /// Container/codec pair — MP4 / MKV / MOV (H.264 + AAC) or WebM (VP9 + Opus).
+    format: ExportFormat,
     /// Export aspect ratio override. `None` = follow the project's aspect ratio.
     export_aspect: Option<AspectRatio>,
+    /// Add per-packet timestamps to the render log. Session-only: it is a
+    /// support tool, not something to leave on.
+    verbose_log: bool,
+    /// Drop corrupt source frames instead of failing the render.
+    /// Session-only, like the other render settings.
+    error_resilient: bool,
+    /// How 10-bit (and deeper) sources are brought down to 8 bits.
+    dither: DitherMode,
+    /// Encode H.264 in two passes to an average `bitrate_kbps` instead of
+    /// constant quality. Session-only, like the other render settings.
+    two_pass: bool,
+    bitrate_kbps: u32,
+    /// Render beside the first timeline clip's source instead of asking
+    /// where to save. Session-only; the dialog stays the default.
+    next_to_source: bool,
+    /// Title / artist / comment / date tags for the output file. Session-only.
+    /// An empty title falls back to the output name when rendering.
+    metadata: ExportMetadata,
+    /// Every track, or one V-row / A-row on its own (an A-row renders a WAV
+    /// stem). Session-only.
+    tracks: ExportTracks,
+    /// Transition and duration the "Apply to all cuts" batch action sets.
+    batch_transition: TransitionKind,
+    batch_transition_secs: f32,
+    /// Storyboard / contact-sheet export settings. Session-only.
+    storyboard: StoryboardOptions,
     /// Timestamp of when the first "Reset" click happened.
     clear_confirm_at: Option<std::time::Instant>,
+    /// Performance prefs while a slider is being dragged; shown in place of
+    /// `state.performance` and applied once the drag ends.
+    perf_draft: Option<PerformancePrefs>,
     /// Set to true when the user confirms a reset.
     pub show_reset_complete: bool,
     /// Cached result of the HW encoder probe. `None` until first render of this
     /// panel — probed lazily so startup is not blocked. Once set, never changes.
     hw_caps: Option<HwEncodeCapabilities>,
+    /// Audio encoder the selected format will use (`None` inside = video-only
+    /// export), keyed by the format it was looked up for.
+    audio_encoder: Option<(ExportFormat, Option<&'static str>)>,
 }
 
 impl Default for ExportModule {
@@ -153,11 +211,27 @@ impl Default for ExportModule {
         Self {
             filename: "sequence_01".into(),
             quality: QualityPreset::FHD1080,
+            source_size: None,
             fps: 30,
+            match_source: false,
+            format: ExportFormat::Mp4,
             export_aspect: None,
+            verbose_log: false,
+            error_resilient: false,
+            dither: DitherMode::default(),
+            two_pass: false,
+            bitrate_kbps: 8_000,
+            next_to_source: false,
+            metadata: ExportMetadata::default(),
+            tracks: ExportTracks::default(),
+            batch_transition: TransitionKind::Crossfade,
+            batch_transition_secs: 0.5,
+            storyboard: StoryboardOptions::default(),
             clear_confirm_at: None,
+            perf_draft: None,
             show_reset_complete: false,
             hw_caps: None,
+            audio_encoder: None,
         }
     }
 }
@@ -283,6 +357,16 @@ impl EditorModule for ExportModule {
                     ui.vertical(|ui| {
                         ui.add_space(4.0);
                         self.show_settings_ui(ui, state, cmd, is_encoding);
+                        ui.add_space(12.0);
+                        show_watermark_ui(ui, state, cmd);
+                        ui.add_space(6.0);
+                        show_performance_ui(ui, state, &mut self.perf_draft, cmd);
+                        ui.add_space(6.0);
+                        show_playback_ui(ui, state, cmd);
+                        ui.add_space(6.0);
+                        show_storage_ui(ui, state, cmd);
+                        ui.add_space(6.0);
+                        show_notify_ui(ui, state, cmd);
                     });
                 });
         });
@@ -292,6 +376,24 @@ impl EditorModule for ExportModule {
 // ── Private helpers ───────────────────────────────────────────────────────────
 
 impl ExportModule {
+    /// Two-pass bitrate for the next render, when picked and the format has one.
+    fn target_kbps(&self) -> Option<u32> {
+        (self.two_pass && self.format.supports_two_pass()).then_some(self.bitrate_kbps)
+    }
+
+    /// Tags for the next render — the title defaults to the output name and
+    /// a date that isn't `YYYY-MM-DD` is left out (the field shows it red).
+    fn render_metadata(&self) -> ExportMetadata {
+        let mut metadata = self.metadata.clone();
+        if metadata.title.trim().is_empty() {
+            metadata.title = self.filename.trim().to_string();
+        }
+        if !metadata.date_is_valid() {
+            metadata.date.clear();
+        }
+        metadata
+    }
+
     /// Full-screen modal overlay for all render status (encoding / done / error).
     ///
     /// Call this from app.rs::update() *after* all panels so it paints on top.
@@ -398,9 +500,16 @@ impl ExportModule {
         let fraction = (frame as f32 / total as f32).clamp(0.0, 1.0);
         let pct = (fraction * 100.0) as u32;
 
-        // Title
+        // Title — two-pass renders fill the first half of the bar with pass 1.
+        let title = if state.nest_job.is_some() {
+            "Nesting clips…".to_string()
+        } else if self.target_kbps().is_some() {
+            format!("Rendering… pass {} of 2", if frame * 2 < total { 1 } else { 2 })
+        } else {
+            "Rendering…".to_string()
+        };
         ui.label(
-            RichText::new("Rendering…")
+            RichText::new(title)
                 .size(13.0)
                 .strong()
                 .color(Color32::WHITE),
@@ -482,10 +591,101 @@ impl ExportModule {
                         .size(11.0)
                         .color(GREEN_DIM),
                 );
+                ui.label(
+                    RichText::new(match state.encode_audio_codec {
+                        Some(codec) => format!("Audio: {codec}"),
+                        None => "Audio: none".to_string(),
+                    })
+                    .size(10.0)
+                    .color(DARK_TEXT_DIM),
+                );
             });
 
+        // A fallback or a missing encoder is the FFmpeg build's doing, not the
+        // project's — say so, or the missing sound looks like a bug.
+        let fallback_note = match state.encode_audio_codec {
+            None => Some(
+                "⚠  Exported without audio — this FFmpeg build has no usable audio encoder."
+                    .to_string(),
+            ),
+            Some(codec) if codec != "AAC" && codec != "Opus" => Some(format!(
+                "⚠  Audio encoded as {codec} — the preferred encoder is missing from this \
+                 FFmpeg build."
+            )),
+            Some(_) => None,
+        };
+        if let Some(note) = fallback_note {
+            ui.add_space(8.0);
+            egui::Frame::new()
+                .fill(Color32::from_rgb(55, 38, 10))
+                .stroke(Stroke::new(1.0_f32, Color32::from_rgb(180, 110, 25)))
+                .corner_radius(egui::CornerRadius::same(4))
+                .inner_margin(Margin::same(8))
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.label(RichText::new(note).size(11.0).color(AMBER));
+                });
+        }
+
+        // Error-resilient decode papered over damaged source frames — the
+        // file plays, but it isn't a clean copy of the edit.
+        if state.encode_skipped_frames > 0 {
+            ui.add_space(8.0);
+            egui::Frame::new()
+                .fill(Color32::from_rgb(55, 38, 10))
+                .stroke(Stroke::new(1.0_f32, Color32::from_rgb(180, 110, 25)))
+                .corner_radius(egui::CornerRadius::same(4))
+                .inner_margin(Margin::same(8))
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.label(
+                        RichText::new(format!(
+                            "⚠  Skipped {} corrupt video frame(s) — the last good frame \
+                             was held in their place. See the render log for where.",
+                            state.encode_skipped_frames
+                        ))
+                        .size(11.0)
+                        .color(AMBER),
+                    );
+                });
+        }
+
+        // The encoder clamps the final mix to [-1, 1], so clipping is silent
+        // unless it is reported here.
+        if let Some(peak) = state.encode_audio_peak.filter(|p| *p > 1.0) {
+            ui.add_space(8.0);
+            egui::Frame::new()
+                .fill(Color32::from_rgb(55, 38, 10))
+                .stroke(Stroke::new(1.0_f32, Color32::from_rgb(180, 110, 25)))
+                .corner_radius(egui::CornerRadius::same(4))
+                .inner_margin(Margin::same(8))
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.label(
+                        RichText::new(format!(
+                            "⚠  Audio clipped — peaked at {:+.1} dB. Consider lowering \
+                             clip or master volume.",
+                            20.0 * peak.log10()
+                        ))
+                        .size(11.0)
+                        .color(AMBER),
+                    );
+                });
+        }
+
         ui.add_space(14.0);
 
+        if let Some(path) = &state.encode_done {
+            let open = egui::Button::new(RichText::new("▶  Open").size(11.0).color(Color32::WHITE))
+                .stroke(Stroke::new(1.0_f32, GREEN_DIM))
+                .fill(DARK_BG_2)
+                .min_size(egui::vec2(ui.available_width(), 28.0));
+            if ui.add(open).on_hover_text("Play in the system video player").clicked() {
+                cmd.push(EditorCommand::OpenExternally(path.clone()));
+            }
+            ui.add_space(6.0);
+        }
+
         let dismiss = egui::Button::new(RichText::new("Dismiss").size(11.0).color(DARK_TEXT_DIM))
             .stroke(Stroke::new(1.0_f32, DARK_BORDER))
             .fill(DARK_BG_2)
@@ -535,7 +735,7 @@ impl ExportModule {
         }
     }
 
-    /// Filename / aspect ratio / quality / fps / stats / render button.
+    /// Filename / format / aspect ratio / quality / fps / stats / render button.
     fn show_settings_ui(
         &mut self,
         ui: &mut Ui,
@@ -548,8 +748,25 @@ impl ExportModule {
         let hw_caps = self
             .hw_caps
             .get_or_insert_with(velocut_media::encode::probe_hw_encode_capabilities);
-        let sw_only = hw_caps.sw_only;
-        let backend_name = hw_caps.backend_name;
+        // WebM always encodes with libvpx-vp9 regardless of the H.264 HW probe.
+        let (sw_only, backend_name) = match self.format {
+            ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => {
+                (hw_caps.sw_only, hw_caps.backend_name)
+            }
+            ExportFormat::WebM => (true, "Software (libvpx-vp9)"),
+        };
+        // Audio encoder for this format — a codec-list lookup, redone only when
+        // the format changes. Builds without AAC / Opus fall back (or go
+        // video-only); the summary below says which.
+        let format = self.format;
+        let audio_codec = match self.audio_encoder {
+            Some((f, codec)) if f == format => codec,
+            _ => {
+                let codec = velocut_media::encode::probe_audio_encoder(format);
+                self.audio_encoder = Some((format, codec));
+                codec
+            }
+        };
 
         // Resolve the effective aspect ratio and its f32 value for dimension math.
         let effective_ar = self.export_aspect.unwrap_or(state.aspect_ratio);
@@ -584,6 +801,207 @@ impl ExportModule {
             });
         }
 
+        ui.add_space(2.0);
+        let source_dir = clip_query::first_source_path(state).and_then(|p| p.parent());
+        ui.add_enabled(
+            !is_encoding,
+            egui::Checkbox::new(
+                &mut self.next_to_source,
+                RichText::new("Save next to source").size(10.0).color(DARK_TEXT_DIM),
+            ),
+        )
+        .on_hover_text(match source_dir {
+            Some(dir) => format!(
+                "Skip the save dialog and render into {} — an existing file gets \
+                 _2, _3, … appended instead of being overwritten",
+                dir.display()
+            ),
+            None => "Skip the save dialog and render beside the first timeline clip's \
+                     source file"
+                .to_string(),
+        });
+
+        // ── Metadata ──────────────────────────────────────────────────────────
+        // Written into the container for media managers and upload sites.
+        ui.add_space(2.0);
+        ui.add_enabled_ui(!is_encoding, |ui| {
+            egui::CollapsingHeader::new(
+                RichText::new("Metadata").size(10.0).color(DARK_TEXT_DIM),
+            )
+            .id_salt("export_metadata")
+            .show(ui, |ui| {
+                egui::Grid::new("export_metadata_grid")
+                    .num_columns(2)
+                    .spacing([6.0, 4.0])
+                    .show(ui, |ui| {
+                        let title_hint = self.filename.clone();
+                        for (label, value, hint) in [
+                            ("Title", &mut self.metadata.title, title_hint.as_str()),
+                            ("Artist", &mut self.metadata.artist, "author…"),
+                            ("Comment", &mut self.metadata.comment, "description…"),
+                        ] {
+                            ui.label(RichText::new(label).size(10.0).color(DARK_TEXT_DIM));
+                            ui.add(egui::TextEdit::singleline(value)
+                                .desired_width(f32::INFINITY)
+                                .hint_text(hint));
+                            ui.end_row();
+                        }
+                        ui.label(RichText::new("Date").size(10.0).color(DARK_TEXT_DIM));
+                        ui.horizontal(|ui| {
+                            let valid = self.metadata.date_is_valid();
+                            let date = ui.add(egui::TextEdit::singleline(&mut self.metadata.date)
+                                .desired_width(80.0)
+                                .hint_text("YYYY-MM-DD")
+                                .text_color_opt((!valid).then_some(Color32::from_rgb(230, 120, 80))));
+                            if !valid {
+                                date.on_hover_text("Not a YYYY-MM-DD date — it will be left out");
+                            }
+                            if ui.small_button("Today").clicked() {
+                                let now = std::time::SystemTime::now()
+                                    .duration_since(std::time::UNIX_EPOCH)
+                                    .map_or(0, |d| d.as_secs());
+                                self.metadata.date = format_iso_date(now);
+                            }
+                        });
+                        ui.end_row();
+                        // Cover image — the poster players show before playback.
+                        ui.label(RichText::new("Cover").size(10.0).color(DARK_TEXT_DIM));
+                        let supported = self.format.supports_cover_art();
+                        ui.add_enabled_ui(supported, |ui| {
+                            egui::ComboBox::from_id_salt("export_cover")
+                                .selected_text(if supported {
+                                    self.metadata.cover.label()
+                                } else {
+                                    CoverFrame::Off.label()
+                                })
+                                .width(ui.available_width())
+                                .show_ui(ui, |ui| {
+                                    for &c in CoverFrame::all() {
+                                        ui.selectable_value(&mut self.metadata.cover, c, c.label());
+                                    }
+                                });
+                        })
+                        .response
+                        .on_hover_text(if supported {
+                            "Frame embedded as the file's thumbnail (graded, cropped to the output size)"
+                        } else {
+                            "Cover images are written to MP4 and MOV only"
+                        });
+                        ui.end_row();
+                    });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        // ── Format ────────────────────────────────────────────────────────────
+        // WebM is software-only (libvpx-vp9) — no HW annotation applies, so
+        // the note below the quality picker is switched to VP9 wording.
+        ui.label(RichText::new("Format").size(11.0).color(DARK_TEXT_DIM));
+        ui.add_space(2.0);
+        ui.add_enabled_ui(!is_encoding, |ui| {
+            egui::ComboBox::from_id_salt("export_format")
+                .selected_text(self.format.label())
+                .width(ui.available_width())
+                .show_ui(ui, |ui| {
+                    for &f in ExportFormat::all() {
+                        if ui.selectable_label(self.format == f, f.label()).clicked() {
+                            self.format = f;
+                        }
+                    }
+                });
+        });
+
+        ui.add_space(10.0);
+
+        // ── Tracks ────────────────────────────────────────────────────────────
+        // One track on its own: a clean video pass or an audio stem for a
+        // sound designer.
+        ui.label(RichText::new("Tracks").size(11.0).color(DARK_TEXT_DIM));
+        ui.add_space(2.0);
+        ui.add_enabled_ui(!is_encoding, |ui| {
+            egui::ComboBox::from_id_salt("export_tracks")
+                .selected_text(self.tracks.label())
+                .width(ui.available_width())
+                .show_ui(ui, |ui| {
+                    for &t in ExportTracks::all() {
+                        if ui.selectable_label(self.tracks == t, t.label()).clicked() {
+                            self.tracks = t;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "A single video track keeps its clips' own sound but leaves out \
+                     the A-row music. A single audio track is written as a WAV with \
+                     its gain, fades and volume keys — no picture.",
+                );
+        });
+
+        ui.add_space(10.0);
+
+        // ── Dither ────────────────────────────────────────────────────────────
+        // Only touches sources deeper than 8 bits; 8-bit footage encodes
+        // exactly as before whatever is picked here.
+        ui.label(
+            RichText::new("Dither (10-bit sources)")
+                .size(11.0)
+                .color(DARK_TEXT_DIM),
+        );
+        ui.add_space(2.0);
+        ui.add_enabled_ui(!is_encoding, |ui| {
+            egui::ComboBox::from_id_salt("export_dither")
+                .selected_text(self.dither.label())
+                .width(ui.available_width())
+                .show_ui(ui, |ui| {
+                    for &d in DitherMode::all() {
+                        if ui.selectable_label(self.dither == d, d.label()).clicked() {
+                            self.dither = d;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Breaks up banding in skies and gradients when HDR / 10-bit \
+                     phone and camera footage is reduced to 8-bit H.264 / VP9. \
+                     Ordered is stable frame to frame; error diffusion is finer \
+                     but noisier for the encoder.",
+                );
+        });
+
+        ui.add_space(10.0);
+
+        // ── Rate control ──────────────────────────────────────────────────────
+        // Constant quality by default; two-pass trades render time (every
+        // frame is encoded twice) for a predictable file size.
+        ui.label(
+            RichText::new("Bitrate")
+                .size(11.0)
+                .color(DARK_TEXT_DIM),
+        );
+        ui.add_space(2.0);
+        ui.add_enabled_ui(!is_encoding && self.format.supports_two_pass(), |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.two_pass, false, "Constant quality");
+                ui.radio_value(&mut self.two_pass, true, "Target (2-pass)");
+            });
+            if self.two_pass {
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.bitrate_kbps)
+                        .range(TARGET_KBPS_RANGE)
+                        .speed(50.0)
+                        .suffix(" kb/s"));
+                    // Video target plus the fixed 128 kb/s audio track.
+                    let mb = (self.bitrate_kbps + 128) as f64 * state.total_duration() / 8_000.0;
+                    ui.label(RichText::new(format!("≈ {mb:.0} MB"))
+                        .size(10.0)
+                        .color(DARK_TEXT_DIM));
+                });
+            }
+        })
+        .response
+        .on_disabled_hover_text("WebM (VP9) always renders at constant quality");
+
         ui.add_space(10.0);
 
         // ── Aspect Ratio ──────────────────────────────────────────────────────
@@ -597,7 +1015,9 @@ impl ExportModule {
         ui.add_space(2.0);
         ui.add_enabled_ui(!is_encoding, |ui| {
             // Label shown in the collapsed combo.
-            let combo_label = if self.export_aspect.is_none() {
+            let combo_label = if let Some((w, h)) = self.source_size {
+                format!("Source  ({w}×{h})")
+            } else if self.export_aspect.is_none() {
                 format!("↩ Match Project  ({})", state.aspect_ratio)
             } else {
                 effective_ar.to_string()
@@ -608,19 +1028,21 @@ impl ExportModule {
                 .width(ui.available_width())
                 .show_ui(ui, |ui| {
                     // "Match Project" option always at the top.
-                    let match_selected = self.export_aspect.is_none();
+                    let match_selected = self.export_aspect.is_none() && self.source_size.is_none();
                     let match_label = format!("↩ Match Project  ({})", state.aspect_ratio);
                     if ui.selectable_label(match_selected, &match_label).clicked() {
                         self.export_aspect = None;
+                        self.source_size = None;
                     }
 
                     ui.separator();
 
                     // One entry per aspect ratio variant.
                     for &ar in ALL_ASPECT_RATIOS {
-                        let selected = self.export_aspect == Some(ar);
+                        let selected = self.export_aspect == Some(ar) && self.source_size.is_none();
                         if ui.selectable_label(selected, ar.to_string()).clicked() {
                             self.export_aspect = Some(ar);
+                            self.source_size = None;
                         }
                     }
                 });
@@ -628,11 +1050,49 @@ impl ExportModule {
 
         ui.add_space(10.0);
 
-        ui.label(RichText::new("Quality").size(11.0).color(DARK_TEXT_DIM));
+        // "Source settings" copies the frame size and rate of the source that
+        // fills most of the timeline, so a plain trim renders at its original
+        // quality. Every field stays editable afterwards.
+        let dominant = clip_query::dominant_source_clip(state);
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Quality").size(11.0).color(DARK_TEXT_DIM));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let resp = ui
+                    .add_enabled(
+                        !is_encoding && dominant.is_some(),
+                        egui::Button::new(RichText::new("⟲ Source settings").size(10.0)),
+                    )
+                    .on_disabled_hover_text("No probed video source on the timeline yet");
+                let Some(lib) = dominant else { return };
+                let Some((w, h)) = lib.video_size else { return };
+                let rate = lib
+                    .frame_rate
+                    .map_or(String::new(), |f| format!(" @ {f:.3} fps"));
+                if resp
+                    .on_hover_text(format!(
+                        "Set size and frame rate to match {} ({w}×{h}{rate}), \
+                         the source that fills most of the timeline",
+                        lib.name
+                    ))
+                    .clicked()
+                {
+                    // Even dimensions for YUV 4:2:0, like the presets.
+                    self.source_size = Some(((w + 1) & !1, (h + 1) & !1));
+                    if let Some(f) = lib.frame_rate {
+                        self.fps = (f.round() as u32).clamp(1, 120);
+                        self.match_source = false;
+                    }
+                }
+            });
+        });
         ui.add_space(2.0);
         ui.add_enabled_ui(!is_encoding, |ui| {
+            let quality_label = match self.source_size {
+                Some((w, h)) => format!("Source  — {w}×{h}"),
+                None => self.quality.label().to_string(),
+            };
             egui::ComboBox::from_id_salt("quality_preset")
-                .selected_text(self.quality.label())
+                .selected_text(quality_label)
                 .width(ui.available_width())
                 .show_ui(ui, |ui| {
                     for q in [
@@ -656,9 +1116,11 @@ impl ExportModule {
                             format!("{}  — {w}×{h}", q.label())
                         };
 
-                        let resp = ui.add(egui::Button::selectable(self.quality == q, &label));
+                        let selected = self.quality == q && self.source_size.is_none();
+                        let resp = ui.add(egui::Button::selectable(selected, &label));
                         if resp.clicked() {
                             self.quality = q;
+                            self.source_size = None;
                         }
 
                         // Hover tooltip for SW-only machines — same text for all
@@ -696,7 +1158,9 @@ impl ExportModule {
         }
 
         // Show the resolved pixel dimensions below the ComboBox as a hint.
-        let (res_w, res_h) = self.quality.dimensions(effective_ratio);
+        let (res_w, res_h) = self
+            .source_size
+            .unwrap_or_else(|| self.quality.dimensions(effective_ratio));
         ui.add_space(2.0);
         ui.label(
             RichText::new(format!("{res_w} × {res_h} px"))
@@ -709,10 +1173,17 @@ impl ExportModule {
         // ── Frame Rate ────────────────────────────────────────────────────────
         ui.label(RichText::new("Frame Rate").size(11.0).color(DARK_TEXT_DIM));
         ui.add_space(2.0);
+        // "Source" follows the first timeline clip's probed rate, rounded to a
+        // whole fps (29.97 → 30) since the encoder time base is 1/fps.
+        let source_fps = clip_query::first_source_clip(state).and_then(|l| l.frame_rate);
+        let fps = match source_fps {
+            Some(f) if self.match_source => (f.round() as u32).clamp(1, 120),
+            _ => self.fps,
+        };
         ui.add_enabled_ui(!is_encoding, |ui| {
             ui.horizontal(|ui| {
                 for &rate in &[24u32, 30, 60] {
-                    let selected = self.fps == rate;
+                    let selected = fps == rate && !self.match_source;
                     let btn = egui::Button::new(
                         RichText::new(format!("{rate} fps"))
                             .size(11.0)
@@ -726,11 +1197,87 @@ impl ExportModule {
 
                     if ui.add(btn).clicked() {
                         self.fps = rate;
+                        self.match_source = false;
                     }
                 }
+                let selected = self.match_source && source_fps.is_some();
+                let btn = egui::Button::new(
+                    RichText::new("Source")
+                        .size(11.0)
+                        .color(if selected { ACCENT } else { DARK_TEXT_DIM }),
+                )
+                .stroke(Stroke::new(
+                    1.0_f32,
+                    if selected { ACCENT } else { DARK_BORDER },
+                ))
+                .fill(if selected { DARK_BG_3 } else { DARK_BG_2 });
+                let resp = ui
+                    .add_enabled(source_fps.is_some(), btn)
+                    .on_disabled_hover_text("No probed video frame rate on the timeline yet");
+                let resp = match source_fps {
+                    Some(f) => resp.on_hover_text(format!(
+                        "Match the first clip's source ({f:.3} fps) — no frames \
+                         duplicated or dropped"
+                    )),
+                    None => resp,
+                };
+                if resp.clicked() {
+                    self.match_source = !self.match_source;
+                }
+                // Any whole rate the encoder accepts; typing one leaves "Source".
+                let mut custom = fps;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut custom)
+                            .range(1..=120)
+                            .suffix(" fps"),
+                    )
+                    .on_hover_text("Custom frame rate (1–120)")
+                    .changed()
+                {
+                    self.fps = custom;
+                    self.match_source = false;
+                }
             });
         });
 
+        ui.add_space(6.0);
+
+        // ── Project Frame Rate ────────────────────────────────────────────────
+        // The grid the timeline steps, snaps and trims in — independent of the
+        // export rate above. Conforming rounds every edit onto the export rate.
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Project").size(11.0).color(DARK_TEXT_DIM));
+            egui::ComboBox::from_id_salt("project_fps")
+                .selected_text(format!("{} fps", state.project_fps))
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    for rate in [24u32, 25, 30, 50, 60] {
+                        if ui
+                            .selectable_label(state.project_fps == rate, format!("{rate} fps"))
+                            .clicked()
+                            && state.project_fps != rate
+                        {
+                            cmd.push(EditorCommand::PushUndoSnapshot("Project frame rate"));
+                            cmd.push(EditorCommand::SetProjectFps { fps: rate, conform: false });
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Timeline frame rate: arrow-key steps, trim minimums and the ruler's frame count");
+            if fps != state.project_fps
+                && ui
+                    .add_enabled(!is_encoding, egui::Button::new(
+                        RichText::new(format!("Conform to {fps} fps")).size(11.0),
+                    ))
+                    .on_hover_text("Switch the project to the export rate and round every clip edge, marker and transition onto its frames")
+                    .clicked()
+            {
+                cmd.push(EditorCommand::PushUndoSnapshot("Conform frame rate"));
+                cmd.push(EditorCommand::SetProjectFps { fps, conform: true });
+            }
+        });
+
         ui.add_space(10.0);
 
         // ── Transitions ───────────────────────────────────────────────────────
@@ -749,7 +1296,7 @@ impl ExportModule {
                     .transitions
                     .iter()
                     .filter(|t| {
-                        t.kind.kind != velocut_core::transitions::TransitionKind::Cut
+                        t.kind.kind != TransitionKind::Cut
                             && timeline_ids.contains(&t.after_clip_id)
                     })
                     .count();
@@ -776,6 +1323,82 @@ impl ExportModule {
                         .size(10.0)
                         .color(DARK_TEXT_DIM),
                 );
+
+                // ── Batch: one transition on every touching cut ───────────────
+                let boundaries = clip_query::transition_boundaries(state);
+                if !boundaries.is_empty() {
+                    ui.add_space(6.0);
+                    let registry = velocut_core::transitions::registry();
+                    ui.horizontal(|ui| {
+                        let selected = registry
+                            .get(&self.batch_transition)
+                            .map_or("Dissolve", |e| e.label());
+                        egui::ComboBox::from_id_salt("batch_transition")
+                            .selected_text(RichText::new(selected).size(11.0))
+                            .width(110.0)
+                            .show_ui(ui, |ui| {
+                                for entry in velocut_core::transitions::registered() {
+                                    let label = format!("{}  {}", entry.icon(), entry.label());
+                                    if ui
+                                        .selectable_label(self.batch_transition == entry.kind(), label)
+                                        .clicked()
+                                    {
+                                        self.batch_transition = entry.kind();
+                                    }
+                                }
+                            });
+                        ui.add(
+                            egui::DragValue::new(&mut self.batch_transition_secs)
+                                .range(0.1..=3.0)
+                                .speed(0.01)
+                                .fixed_decimals(2)
+                                .suffix("s"),
+                        );
+                    });
+                    ui.add_space(2.0);
+                    ui.horizontal(|ui| {
+                        let n = boundaries.len();
+                        let apply = ui
+                            .button(
+                                RichText::new(format!(
+                                    "Apply to all {n} cut{}",
+                                    if n == 1 { "" } else { "s" }
+                                ))
+                                .size(11.0),
+                            )
+                            .on_hover_text("Replace the transition on every boundary between touching clips");
+                        if apply.clicked() {
+                            if let Some(entry) = registry.get(&self.batch_transition) {
+                                let kind = entry.build(self.batch_transition_secs);
+                                cmd.push(EditorCommand::PushUndoSnapshot("Set all transitions"));
+                                for after_clip_id in &boundaries {
+                                    cmd.push(EditorCommand::SetTransition {
+                                        after_clip_id: *after_clip_id,
+                                        kind: kind.clone(),
+                                    });
+                                }
+                            }
+                        }
+                        let removable: Vec<_> = boundaries
+                            .iter()
+                            .copied()
+                            .filter(|id| state.transitions.iter().any(|t| t.after_clip_id == *id))
+                            .collect();
+                        if ui
+                            .add_enabled(
+                                !removable.is_empty(),
+                                egui::Button::new(RichText::new("Remove all").size(11.0)),
+                            )
+                            .on_hover_text("Revert every boundary to a hard cut")
+                            .clicked()
+                        {
+                            cmd.push(EditorCommand::PushUndoSnapshot("Remove all transitions"));
+                            for after_clip_id in removable {
+                                cmd.push(EditorCommand::RemoveTransition(after_clip_id));
+                            }
+                        }
+                    });
+                }
             });
 
         ui.add_space(10.0);
@@ -795,7 +1418,7 @@ impl ExportModule {
                     .map(|c| c.start_time + c.duration)
                     .fold(0.0_f64, f64::max);
                 let clips = state.timeline.len();
-                let est_frames = (total * self.fps as f64).ceil() as u64;
+                let est_frames = (total * fps as f64).ceil() as u64;
                 let has_audio = state.library.iter().any(|lc| {
                     state.timeline.iter().any(|tc| tc.media_id == lc.id) && lc.audio_path.is_some()
                 });
@@ -811,7 +1434,7 @@ impl ExportModule {
                         .monospace(),
                 );
                 ui.label(
-                    RichText::new(format!("Output:    {res_w}×{res_h} @ {}fps", self.fps))
+                    RichText::new(format!("Output:    {res_w}×{res_h} @ {}fps", fps))
                         .size(11.0)
                         .monospace(),
                 );
@@ -820,20 +1443,25 @@ impl ExportModule {
                         .size(11.0)
                         .monospace(),
                 );
-                ui.label(
-                    RichText::new(format!(
-                        "Audio:     {}",
-                        if has_audio {
-                            "AAC 128kbps stereo"
-                        } else {
-                            "none detected"
-                        }
-                    ))
+                let (video_codec, preferred_audio) = match self.format {
+                    ExportFormat::Mp4 | ExportFormat::Mkv | ExportFormat::Mov => ("H.264", "AAC"),
+                    ExportFormat::WebM => ("VP9", "Opus"),
+                };
+                let audio_desc = match audio_codec {
+                    _ if !has_audio => "none detected".to_string(),
+                    Some(c) if c == preferred_audio => format!("{c} 128kbps stereo"),
+                    Some(c) => format!("{c} 128kbps stereo ({preferred_audio} unavailable)"),
+                    None => "none — no audio encoder in this FFmpeg build".to_string(),
+                };
+                let mut audio_text = RichText::new(format!("Audio:     {audio_desc}"))
                     .size(11.0)
-                    .monospace(),
-                );
+                    .monospace();
+                if has_audio && audio_codec != Some(preferred_audio) {
+                    audio_text = audio_text.color(AMBER);
+                }
+                ui.label(audio_text);
                 ui.label(
-                    RichText::new(format!("Video:     H.264 via {backend_name}"))
+                    RichText::new(format!("Video:     {video_codec} via {backend_name}"))
                         .size(11.0)
                         .monospace(),
                 );
@@ -843,18 +1471,58 @@ impl ExportModule {
 
         // ── Render button (hidden while encoding; replaced by Cancel) ─────────
         if !is_encoding {
-            let no_clips = state.timeline.is_empty();
-            let render_btn =
-                egui::Button::new(RichText::new("⚡ Render MP4").size(13.0).strong().color(
-                    if no_clips {
+            // Rendering before the probe finishes gets the frame count and
+            // crop wrong, so hold the buttons until every source has reported.
+            let loading = clip_query::loading_sources(state);
+            if !loading.is_empty() {
+                let more = loading.len().saturating_sub(3);
+                let more_text = format!("and {more} more");
+                let mut names: Vec<&str> =
+                    loading.iter().take(3).map(|l| l.name.as_str()).collect();
+                if more > 0 {
+                    names.push(&more_text);
+                }
+                ui.label(
+                    RichText::new(format!("⏳ Media still loading… {}", names.join(", ")))
+                        .size(10.0)
+                        .color(AMBER),
+                );
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_millis(250));
+                ui.add_space(4.0);
+            }
+            // Export plays each track's clips in sequence and can't composite
+            // two clips crossing on one track.
+            let overlapping = state.overlaps.len();
+            if overlapping > 0 {
+                ui.label(
+                    RichText::new(format!(
+                        "⚠ {overlapping} clips overlap on the same track (outlined red \
+                         on the timeline) — separate them or the render will drop parts"
+                    ))
+                    .size(10.0)
+                    .color(AMBER),
+                );
+                ui.add_space(4.0);
+            }
+            let no_clips = state.timeline.is_empty() || !loading.is_empty();
+            let render_btn = egui::Button::new(
+                RichText::new(if self.tracks.is_audio_stem() {
+                    format!("⚡ Render {}", self.tracks.label())
+                } else {
+                    format!("⚡ Render {}", self.format.name())
+                })
+                    .size(13.0)
+                    .strong()
+                    .color(if no_clips {
                         Color32::DARK_GRAY
                     } else {
                         Color32::WHITE
-                    },
-                ))
-                .fill(if no_clips { DARK_BG_3 } else { RENDER_BTN })
-                .stroke(Stroke::NONE)
-                .min_size(egui::vec2(ui.available_width(), 34.0));
+                    }),
+            )
+            .fill(if no_clips { DARK_BG_3 } else { RENDER_BTN })
+            .stroke(Stroke::NONE)
+            .min_size(egui::vec2(ui.available_width(), 34.0));
 
             let response = ui.add_enabled(!no_clips, render_btn);
             if response.clicked() {
@@ -862,12 +1530,573 @@ impl ExportModule {
                     filename: self.filename.clone(),
                     width: res_w,
                     height: res_h,
-                    fps: self.fps,
+                    fps,
+                    format: self.format,
+                    verbose_log: self.verbose_log,
+                    error_resilient: self.error_resilient,
+                    dither: self.dither,
+                    target_kbps: self.target_kbps(),
+                    limit_secs: None,
+                    next_to_source: self.next_to_source,
+                    metadata: self.render_metadata(),
+                    tracks: self.tracks,
                 });
             }
-            if no_clips {
+            if state.timeline.is_empty() {
                 response.on_hover_text("Add clips to the timeline first");
+            } else if !loading.is_empty() {
+                response.on_hover_text("Waiting for imported media to finish probing");
             }
+
+            let test_btn = egui::Button::new(
+                RichText::new(format!("▶ Test render (first {TEST_RENDER_SECS:.0}s)"))
+                    .size(11.0)
+                    .color(DARK_TEXT_DIM),
+            )
+            .stroke(Stroke::new(1.0_f32, DARK_BORDER))
+            .fill(DARK_BG_2)
+            .min_size(egui::vec2(ui.available_width(), 24.0));
+            if ui
+                .add_enabled(!no_clips, test_btn)
+                .on_hover_text(
+                    "Render the opening seconds with the current settings to a temp \
+                     file — check aspect, color and audio before the full export",
+                )
+                .clicked()
+            {
+                cmd.push(EditorCommand::RenderMP4 {
+                    filename: self.filename.clone(),
+                    width: res_w,
+                    height: res_h,
+                    fps,
+                    format: self.format,
+                    verbose_log: self.verbose_log,
+                    error_resilient: self.error_resilient,
+                    dither: self.dither,
+                    target_kbps: self.target_kbps(),
+                    limit_secs: Some(TEST_RENDER_SECS),
+                    next_to_source: false,
+                    metadata: self.render_metadata(),
+                    tracks: self.tracks,
+                });
+            }
+
+            ui.checkbox(
+                &mut self.verbose_log,
+                RichText::new("Verbose render log").size(10.0).color(DARK_TEXT_DIM),
+            )
+            .on_hover_text(
+                "Every render writes <name>.render.log next to the output — attach \
+                 it to bug reports. Verbose adds per-packet timestamps (large).",
+            );
+            ui.checkbox(
+                &mut self.error_resilient,
+                RichText::new("Error-resilient decode")
+                    .size(10.0)
+                    .color(DARK_TEXT_DIM),
+            )
+            .on_hover_text(
+                "Skip frames a damaged source can't decode, holding the last good \
+                 frame in their place, instead of stopping the render. For salvaging \
+                 recovered or partly corrupt recordings.",
+            );
+
+            // ── Cut list export — for finishing the edit in another NLE ──────
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let half = (ui.available_width() - ui.spacing().item_spacing.x) * 0.5;
+                for (format, label, tip) in [
+                    (
+                        CutListFormat::Edl,
+                        "📄 EDL",
+                        "Export a CMX3600 edit decision list",
+                    ),
+                    (
+                        CutListFormat::Json,
+                        "📄 Cut List",
+                        "Export a JSON cut list (source / record in-out per clip)",
+                    ),
+                ] {
+                    let btn = egui::Button::new(RichText::new(label).size(11.0))
+                        .min_size(egui::vec2(half, 24.0));
+                    let resp = ui
+                        .add_enabled(!no_clips, btn)
+                        .on_hover_text(format!("{tip} at {} fps", fps));
+                    if resp.clicked() {
+                        cmd.push(EditorCommand::ExportCutList {
+                            filename: self.filename.clone(),
+                            fps,
+                            format,
+                        });
+                    }
+                }
+            });
+
+            // ── Storyboard — a contact sheet of stills for review ────────────
+            ui.add_space(4.0);
+            egui::CollapsingHeader::new(
+                RichText::new("🖼 Storyboard").size(11.0).color(DARK_TEXT_DIM),
+            )
+            .id_salt("export_storyboard")
+            .default_open(false)
+            .show(ui, |ui| {
+                let sb = &mut self.storyboard;
+                ui.horizontal(|ui| {
+                    for sampling in [StoryboardSampling::Even, StoryboardSampling::PerClip] {
+                        ui.radio_value(
+                            &mut sb.sampling,
+                            sampling,
+                            RichText::new(sampling.label()).size(10.0),
+                        );
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if sb.sampling == StoryboardSampling::Even {
+                        ui.label(RichText::new("Frames").size(10.0).color(DARK_TEXT_DIM));
+                        ui.add(
+                            egui::DragValue::new(&mut sb.frames)
+                                .range(STORYBOARD_FRAMES_RANGE)
+                                .speed(0.2),
+                        );
+                    }
+                    ui.label(RichText::new("Columns").size(10.0).color(DARK_TEXT_DIM));
+                    ui.add(
+                        egui::DragValue::new(&mut sb.columns)
+                            .range(STORYBOARD_COLUMNS_RANGE)
+                            .speed(0.1),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Cell width").size(10.0).color(DARK_TEXT_DIM));
+                    egui::ComboBox::from_id_salt("storyboard_thumb_width")
+                        .selected_text(format!("{} px", sb.thumb_width))
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for w in STORYBOARD_THUMB_WIDTHS {
+                                ui.selectable_value(&mut sb.thumb_width, w, format!("{w} px"));
+                            }
+                        });
+                    ui.checkbox(&mut sb.timecode, RichText::new("Timecode").size(10.0))
+                        .on_hover_text("Burn each frame's timeline position into its corner");
+                });
+                let btn = egui::Button::new(RichText::new("🖼 Export storyboard…").size(11.0))
+                    .min_size(egui::vec2(ui.available_width(), 24.0));
+                if ui
+                    .add_enabled(!no_clips, btn)
+                    .on_hover_text("Save a PNG / JPEG grid of stills from the timeline")
+                    .clicked()
+                {
+                    cmd.push(EditorCommand::ExportStoryboard {
+                        filename: self.filename.clone(),
+                        options: *sb,
+                    });
+                }
+            });
         }
     }
 }
+
+/// Collapsible project watermark settings — PNG path, corner, size and
+/// opacity. Like the performance sliders, a value is only committed once it
+/// settles; each commit is undoable.
+fn show_watermark_ui(ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+    egui::CollapsingHeader::new(
+        RichText::new("◈ Watermark")
+            .size(11.0)
+            .color(DARK_TEXT_DIM),
+    )
+    .id_salt("export_watermark")
+    .default_open(state.watermark.is_some())
+    .show(ui, |ui| {
+        let mut next = state.watermark.clone();
+        let mut commit = false;
+
+        ui.horizontal(|ui| {
+            let pick_label = if next.is_some() { "Change PNG…" } else { "Choose PNG…" };
+            if ui
+                .button(RichText::new(pick_label).size(11.0))
+                .on_hover_text("Logo burned into every exported frame. Transparency is kept.")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("PNG image", &["png"])
+                    .pick_file()
+                {
+                    match &mut next {
+                        Some(wm) => wm.path = path,
+                        None => next = Some(Watermark::new(path)),
+                    }
+                    commit = true;
+                }
+            }
+            if next.is_some()
+                && ui
+                    .button(RichText::new("✕ Remove").size(11.0))
+                    .clicked()
+            {
+                next = None;
+                commit = true;
+            }
+        });
+
+        if let Some(wm) = &mut next {
+            let file_name = wm
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            ui.label(RichText::new(file_name).size(10.0).color(DARK_TEXT_DIM))
+                .on_hover_text(wm.path.display().to_string());
+
+            ui.add_space(6.0);
+            ui.label(RichText::new("Corner").size(11.0).color(DARK_TEXT_DIM));
+            ui.horizontal_wrapped(|ui| {
+                for &corner in WatermarkCorner::all() {
+                    let selected = wm.corner == corner;
+                    if ui.radio(selected, corner.label()).clicked() && !selected {
+                        wm.corner = corner;
+                        commit = true;
+                    }
+                }
+            });
+
+            ui.add_space(6.0);
+            ui.label(RichText::new("Size").size(11.0).color(DARK_TEXT_DIM));
+            let mut pct = wm.scale * 100.0;
+            let resp = ui
+                .add(
+                    egui::Slider::new(
+                        &mut pct,
+                        WATERMARK_SCALE_RANGE.start() * 100.0..=WATERMARK_SCALE_RANGE.end() * 100.0,
+                    )
+                    .suffix("% of width")
+                    .fixed_decimals(0),
+                );
+            wm.scale = pct / 100.0;
+            commit |= resp.drag_stopped() || (resp.changed() && !resp.dragged());
+
+            ui.add_space(6.0);
+            ui.label(RichText::new("Opacity").size(11.0).color(DARK_TEXT_DIM));
+            let mut pct = wm.opacity * 100.0;
+            let resp = ui.add(
+                egui::Slider::new(&mut pct, 0.0..=100.0)
+                    .suffix("%")
+                    .fixed_decimals(0),
+            );
+            wm.opacity = pct / 100.0;
+            commit |= resp.drag_stopped() || (resp.changed() && !resp.dragged());
+        }
+
+        if commit && next != state.watermark {
+            cmd.push(EditorCommand::PushUndoSnapshot("Watermark"));
+            cmd.push(EditorCommand::SetWatermark(next));
+        }
+    });
+}
+
+/// Collapsible "Performance" preferences — frame cache budget, HQ decode
+/// thread cap, and scrub proxy width. A slider drag edits `draft` and emits
+/// `SetPerformancePrefs` once no slider is held, so dragging does not
+/// re-apply the prefs every frame; any other change applies at once.
+fn show_performance_ui(
+    ui: &mut Ui,
+    state: &ProjectState,
+    draft: &mut Option<PerformancePrefs>,
+    cmd: &mut Vec<EditorCommand>,
+) {
+    egui::CollapsingHeader::new(
+        RichText::new("⚙ Performance")
+            .size(11.0)
+            .color(DARK_TEXT_DIM),
+    )
+    .id_salt("export_performance")
+    .default_open(false)
+    .show(ui, |ui| {
+        let mut prefs = draft.unwrap_or(state.performance);
+        let mut dragging = false;
+
+        ui.label(RichText::new("Frame Cache").size(11.0).color(DARK_TEXT_DIM));
+        let resp = ui
+            .add(
+                egui::Slider::new(&mut prefs.frame_cache_mb, FRAME_CACHE_MB_RANGE)
+                    .suffix(" MB")
+                    .logarithmic(true),
+            )
+            .on_hover_text(
+                "GPU memory for cached scrub frames. Larger = smoother re-scrubbing, \
+                     more VRAM. Lowering it frees memory immediately.",
+            );
+        dragging |= resp.dragged();
+        // 640×360 RGBA ≈ 900 KB — the common scrub-cache frame size.
+        ui.label(
+            RichText::new(format!(
+                "≈ {} frames at 640×360",
+                prefs.frame_cache_bytes() / (640 * 360 * 4)
+            ))
+            .size(10.0)
+            .color(DARK_TEXT_DIM),
+        );
+
+        ui.add_space(6.0);
+        ui.label(
+            RichText::new("Decode Threads")
+                .size(11.0)
+                .color(DARK_TEXT_DIM),
+        );
+        let resp = ui
+            .add(egui::Slider::new(
+                &mut prefs.decode_threads,
+                DECODE_THREADS_RANGE,
+            ))
+            .on_hover_text(
+                "Concurrent full-resolution decodes for paused-preview and \
+                     transition frames. Each holds ~16 MB while running.",
+            );
+        dragging |= resp.dragged();
+
+        ui.add_space(6.0);
+        ui.label(
+            RichText::new("Playback Read-ahead")
+                .size(11.0)
+                .color(DARK_TEXT_DIM),
+        );
+        let resp = ui
+            .add(
+                egui::Slider::new(&mut prefs.playback_ahead, PLAYBACK_AHEAD_RANGE)
+                    .suffix(" frames"),
+            )
+            .on_hover_text(
+                "Frames decoded ahead of the playhead during playback. Raise it if \
+                     4K or high-bit-rate sources stutter; each frame is held at \
+                     source resolution (~33 MB at 4K).",
+            );
+        dragging |= resp.dragged();
+
+        ui.add_space(6.0);
+        ui.label(
+            RichText::new("Scrub Proxy Width")
+                .size(11.0)
+                .color(DARK_TEXT_DIM),
+        );
+        ui.horizontal(|ui| {
+            for w in PROXY_WIDTHS {
+                let selected = prefs.proxy_width == w;
+                let btn = egui::Button::new(
+                    RichText::new(format!("{w}")).size(11.0).color(if selected {
+                        ACCENT
+                    } else {
+                        DARK_TEXT_DIM
+                    }),
+                )
+                .stroke(Stroke::new(
+                    1.0_f32,
+                    if selected { ACCENT } else { DARK_BORDER },
+                ))
+                .fill(if selected { DARK_BG_3 } else { DARK_BG_2 });
+                if ui
+                    .add(btn)
+                    .on_hover_text("Applies the next time the scrub head enters a clip")
+                    .clicked()
+                {
+                    prefs.proxy_width = w;
+                }
+            }
+        });
+
+        ui.add_space(6.0);
+        if ui
+            .add_enabled(
+                state.performance != PerformancePrefs::default(),
+                egui::Button::new(RichText::new("↺ Defaults").size(10.0)),
+            )
+            .clicked()
+        {
+            prefs = PerformancePrefs::default();
+        }
+
+        if dragging {
+            *draft = Some(prefs);
+        } else {
+            *draft = None;
+            if prefs != state.performance {
+                cmd.push(EditorCommand::SetPerformancePrefs(prefs));
+            }
+        }
+    });
+}
+
+/// Collapsible "Playback" preferences — what Space does, how the timeline
+/// follows the playhead, the play-around pre-/post-roll, and whether dropped
+/// overlaps crossfade unasked.
+fn show_playback_ui(ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+    egui::CollapsingHeader::new(RichText::new("⚙ Playback").size(11.0).color(DARK_TEXT_DIM))
+        .id_salt("export_playback")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(RichText::new("Space Key").size(11.0).color(DARK_TEXT_DIM));
+            ui.horizontal(|ui| {
+                for behavior in [SpaceBehavior::Toggle, SpaceBehavior::Momentary] {
+                    let selected = state.playback_prefs.space == behavior;
+                    if ui.radio(selected, behavior.label()).clicked() && !selected {
+                        let mut prefs = state.playback_prefs;
+                        prefs.space = behavior;
+                        cmd.push(EditorCommand::SetPlaybackPrefs(prefs));
+                    }
+                }
+            })
+            .response
+            .on_hover_text(
+                "Hold to play: playback runs while Space is held and pauses on \
+                 release. Release with Shift held to jump back to where it started.",
+            );
+            ui.add_space(4.0);
+            ui.label(RichText::new("Timeline Auto-scroll").size(11.0).color(DARK_TEXT_DIM));
+            ui.horizontal(|ui| {
+                for &mode in AutoScroll::all() {
+                    let selected = state.playback_prefs.auto_scroll == mode;
+                    if ui.radio(selected, mode.label()).clicked() && !selected {
+                        let mut prefs = state.playback_prefs;
+                        prefs.auto_scroll = mode;
+                        cmd.push(EditorCommand::SetPlaybackPrefs(prefs));
+                    }
+                }
+            })
+            .response
+            .on_hover_text(
+                "Keeps the playhead on screen during playback and keyboard stepping. \
+                 Page jumps a screen at a time; Follow keeps it centred.",
+            );
+            ui.add_space(4.0);
+            ui.label(RichText::new("Preview Drag Span").size(11.0).color(DARK_TEXT_DIM));
+            let mut secs = state.playback_prefs.preview_scrub_secs;
+            let resp = ui
+                .add(egui::Slider::new(&mut secs, PREVIEW_SCRUB_SECS_RANGE).suffix(" s"))
+                .on_hover_text(
+                    "Drag left or right across the preview image to scrub. A drag \
+                     across its full width moves the playhead this far.",
+                );
+            if resp.changed() && secs != state.playback_prefs.preview_scrub_secs {
+                let mut prefs = state.playback_prefs;
+                prefs.preview_scrub_secs = secs;
+                cmd.push(EditorCommand::SetPlaybackPrefs(prefs));
+            }
+            ui.add_space(4.0);
+            ui.label(RichText::new("Play Around Playhead  ( / )").size(11.0).color(DARK_TEXT_DIM));
+            let mut prefs = state.playback_prefs;
+            let as_secs = |v: f64, _| format!("{:.1} s", v / 1000.0);
+            ui.add(egui::Slider::new(&mut prefs.pre_roll_ms, ROLL_MS_RANGE)
+                    .step_by(100.0)
+                    .custom_formatter(as_secs)
+                    .text("before"))
+                .on_hover_text("How far before the playhead review playback starts");
+            ui.add(egui::Slider::new(&mut prefs.post_roll_ms, ROLL_MS_RANGE)
+                    .step_by(100.0)
+                    .custom_formatter(as_secs)
+                    .text("after"))
+                .on_hover_text("How far past the playhead it plays before jumping back");
+            if prefs != state.playback_prefs {
+                cmd.push(EditorCommand::SetPlaybackPrefs(prefs));
+            }
+            ui.add_space(4.0);
+            let mut prefs = state.playback_prefs;
+            ui.checkbox(&mut prefs.auto_crossfade, "Crossfade overlaps without asking")
+                .on_hover_text(
+                    "Dragging a clip over the end of the one before it turns the \
+                     overlap into a crossfade. Off: the timeline asks each time.",
+                );
+            if prefs != state.playback_prefs {
+                cmd.push(EditorCommand::SetPlaybackPrefs(prefs));
+            }
+        });
+}
+
+/// Collapsible "Storage" preferences — where extracted audio WAVs go, how
+/// much room they take, and a way to clear the ones nothing uses anymore.
+fn show_storage_ui(ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+    egui::CollapsingHeader::new(RichText::new("⚙ Storage").size(11.0).color(DARK_TEXT_DIM))
+        .id_salt("export_storage")
+        .default_open(false)
+        .show(ui, |ui| {
+            let dir = state.storage_prefs.resolved();
+            ui.label(RichText::new("Scratch Folder").size(11.0).color(DARK_TEXT_DIM));
+            let shown = if state.storage_prefs.scratch_dir.is_some() {
+                dir.display().to_string()
+            } else {
+                "System temp folder".to_string()
+            };
+            ui.label(RichText::new(shown).size(10.0))
+                .on_hover_text(dir.display().to_string());
+            ui.horizontal(|ui| {
+                if ui
+                    .button(RichText::new("Browse…").size(11.0))
+                    .on_hover_text(
+                        "Extracted audio for playback is written here. \
+                         Existing files stay where they are.",
+                    )
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new().set_directory(&dir).pick_folder() {
+                        cmd.push(EditorCommand::SetStoragePrefs(StoragePrefs {
+                            scratch_dir: Some(path),
+                        }));
+                    }
+                }
+                if ui
+                    .add_enabled(
+                        state.storage_prefs.scratch_dir.is_some(),
+                        egui::Button::new(RichText::new("↺ System temp").size(10.0)),
+                    )
+                    .clicked()
+                {
+                    cmd.push(EditorCommand::SetStoragePrefs(StoragePrefs::default()));
+                }
+            });
+
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let usage = match state.scratch_usage {
+                    Some((files, bytes)) => format!("{files} files · {}", format_bytes(bytes)),
+                    None => "—".to_string(),
+                };
+                ui.label(
+                    RichText::new(format!("In use: {usage}"))
+                        .size(10.0)
+                        .color(DARK_TEXT_DIM),
+                );
+                if ui.small_button("↻").on_hover_text("Measure again").clicked() {
+                    cmd.push(EditorCommand::RefreshScratchUsage);
+                }
+            });
+            if ui
+                .add_enabled(
+                    state.scratch_usage.is_some_and(|(files, _)| files > 0),
+                    egui::Button::new(RichText::new("🧹 Clear cache").size(11.0)),
+                )
+                .on_hover_text("Delete audio files no clip in the library is playing from")
+                .clicked()
+            {
+                cmd.push(EditorCommand::ClearScratchCache);
+            }
+        });
+}
+
+/// Collapsible "Notifications" preferences — what happens when a render
+/// finishes in the background.
+fn show_notify_ui(ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+    egui::CollapsingHeader::new(RichText::new("⚙ Notifications").size(11.0).color(DARK_TEXT_DIM))
+        .id_salt("export_notify")
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut prefs = state.notify_prefs;
+            ui.checkbox(&mut prefs.chime, RichText::new("Chime when a render ends").size(11.0))
+                .on_hover_text("A short rising tone on success, a falling one on failure.");
+            ui.checkbox(&mut prefs.notification, RichText::new("System notification").size(11.0))
+                .on_hover_text(
+                    "Names the output file. Only sent while VeloCut is in the \
+                     background. Cancelled renders stay silent.",
+                );
+            if prefs != state.notify_prefs {
+                cmd.push(EditorCommand::SetNotifyPrefs(prefs));
+            }
+        });
+}
@@ -18,7 +18,7 @@
 // reliably inside a vertical ScrollArea regardless of egui version.
 
 use super::EditorModule;
-use crate::helpers::format::truncate;
+use crate::helpers::format::{format_bitrate, format_bytes, truncate};
 use crate::modules::ThumbnailCache;
 use crate::theme::{
     ACCENT, ACCENT_DUR, DARK_BG_0, DARK_BG_2, DARK_BG_3, DARK_BG_4, DARK_BORDER, DARK_TEXT,
@@ -29,8 +29,8 @@ use rfd::FileDialog;
 use std::collections::HashSet;
 use uuid::Uuid;
 use velocut_core::commands::EditorCommand;
-use velocut_core::helpers::time::format_duration;
-use velocut_core::state::{ClipType, ProjectState};
+use velocut_core::helpers::time::{format_duration, format_source_time};
+use velocut_core::state::{ClipType, ColorRangeMode, DeinterlaceMode, ProjectState};
 
 // ── Layout constants ──────────────────────────────────────────────────────────
 const CARD_W: f32 = 96.0; // outer width  (includes border)
@@ -52,6 +52,9 @@ pub struct LibraryModule {
     /// pending_probes — visible clips are dispatched to the probe semaphore
     /// first so their thumbnails appear before off-screen clips.
     pub visible_ids: HashSet<Uuid>,
+    /// The `ProjectState::match_frame` already scrolled into view, so the
+    /// card is brought on-screen once per Match Frame rather than every frame.
+    scrolled_match: Option<(Uuid, f64)>,
 }
 
 impl LibraryModule {
@@ -59,6 +62,7 @@ impl LibraryModule {
         Self {
             multi_selection: HashSet::new(),
             visible_ids: HashSet::new(),
+            scrolled_match: None,
         }
     }
 
@@ -99,12 +103,14 @@ impl EditorModule for LibraryModule {
         // ── Hotkeys ──────────────────────────────────────────────────────────
         if ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace)) {
             if !self.multi_selection.is_empty() {
-                // Delete every selected clip
+                // Delete every selected clip — one undo step for the batch.
+                cmd.push(EditorCommand::PushUndoSnapshot("Delete media"));
                 for id in self.multi_selection.drain() {
                     cmd.push(EditorCommand::DeleteLibraryClip(id));
                 }
                 cmd.push(EditorCommand::SelectLibraryClip(None));
             } else if let Some(id) = state.selected_library_clip {
+                cmd.push(EditorCommand::PushUndoSnapshot("Delete media"));
                 cmd.push(EditorCommand::DeleteLibraryClip(id));
             }
         }
@@ -126,8 +132,8 @@ impl EditorModule for LibraryModule {
 
         // ── Layout ───────────────────────────────────────────────────────────
         ui.vertical(|ui| {
-            header_bar(ui, cmd);
-            status_strip(ui, state, &self.multi_selection);
+            header_bar(ui, state, cmd);
+            status_strip(ui, state, &self.multi_selection, cmd);
             ui.add_space(1.0);
 
             egui::ScrollArea::vertical()
@@ -165,6 +171,10 @@ impl EditorModule for LibraryModule {
                         .and_then(|a| ids.iter().position(|&x| x == a));
 
                     let mut to_delete: Vec<Uuid> = Vec::new();
+                    let mut stream_pick: Option<(Uuid, Option<usize>)> = None;
+                    let mut range_pick: Option<(Uuid, ColorRangeMode)> = None;
+                    let mut deinterlace_pick: Option<(Uuid, DeinterlaceMode)> = None;
+                    let mut open_source: Option<Uuid> = None;
                     let mut new_single: Option<Uuid> = None;
                     let mut toggle_id: Option<Uuid> = None;
                     let mut range_to_idx: Option<usize> = None;
@@ -182,13 +192,17 @@ impl EditorModule for LibraryModule {
                                 let is_dragging = ui.ctx().is_being_dragged(item_id);
 
                                 if is_dragging {
-                                    paint_drag_ghost(
-                                        ui,
-                                        id,
-                                        &clip.name,
-                                        clip.clip_type,
-                                        thumb_cache,
-                                    );
+                                    let batch = ui.memory(|m| {
+                                        m.data
+                                            .get_temp::<Vec<Uuid>>(Id::new("DND_PAYLOAD"))
+                                            .map_or(1, |ids| ids.len())
+                                    });
+                                    let name = if batch > 1 {
+                                        format!("{} +{}", clip.name, batch - 1)
+                                    } else {
+                                        clip.name.clone()
+                                    };
+                                    paint_drag_ghost(ui, id, &name, clip.clip_type, thumb_cache);
                                     // DND_PAYLOAD is written below in the drag_started_id branch
                                     // (fires on the first drag frame). The is_dragging branch
                                     // runs on every subsequent drag frame — writing here would be
@@ -196,14 +210,24 @@ impl EditorModule for LibraryModule {
                                     // mid-drag. Keep the single canonical write in drag_started_id.
                                 }
 
+                                let matched_at = state
+                                    .match_frame
+                                    .filter(|(mid, _)| *mid == id)
+                                    .map(|(_, ts)| ts);
                                 let card_resp = paint_card(
                                     ui,
                                     clip,
                                     is_selected,
                                     in_multi,
                                     is_dragging,
+                                    matched_at,
                                     thumb_cache,
                                 );
+                                if matched_at.is_some() && self.scrolled_match != state.match_frame
+                                {
+                                    card_resp.scroll_to_me(Some(Align::Center));
+                                    self.scrolled_match = state.match_frame;
+                                }
 
                                 // Record whether this card is within the scroll
                                 // viewport so poll_media can probe it first.
@@ -211,8 +235,11 @@ impl EditorModule for LibraryModule {
                                     self.visible_ids.insert(id);
                                 }
 
-                                let interact =
+                                let mut interact =
                                     ui.interact(card_resp.rect, item_id, Sense::click_and_drag());
+                                if let Some(stats) = file_stats_text(clip) {
+                                    interact = interact.on_hover_text(stats);
+                                }
 
                                 // ── Click handling ────────────────────────────
                                 if interact.clicked() {
@@ -229,6 +256,10 @@ impl EditorModule for LibraryModule {
                                     }
                                 }
 
+                                if interact.double_clicked() && clip.clip_type != ClipType::Image {
+                                    open_source = Some(id);
+                                }
+
                                 if interact.drag_started() {
                                     drag_started_id = Some(id);
                                 }
@@ -243,13 +274,18 @@ impl EditorModule for LibraryModule {
 
                                 // ── Context menu ──────────────────────────────
                                 interact.context_menu(|ui| {
-                                    context_menu(
+                                    if context_menu(
                                         ui,
                                         clip,
                                         is_selected,
                                         &self.multi_selection,
                                         &mut to_delete,
-                                    );
+                                        &mut stream_pick,
+                                        &mut range_pick,
+                                        &mut deinterlace_pick,
+                                    ) {
+                                        open_source = Some(id);
+                                    }
                                 });
 
                                 ui.add_space(CARD_GAP);
@@ -262,9 +298,23 @@ impl EditorModule for LibraryModule {
                     // (egui forbids state mutation inside layout closures above)
 
                     if let Some(id) = drag_started_id {
-                        // Drag always uses the card under pointer, regardless of multi-select
+                        // Dragging a card that is part of a multi-selection
+                        // takes the whole selection along, in library order
+                        // (the order the timeline places them in); any other
+                        // card drags alone.
+                        let dragged: Vec<Uuid> = if self.multi_selection.contains(&id) {
+                            ids.iter()
+                                .copied()
+                                .filter(|x| {
+                                    self.multi_selection.contains(x)
+                                        || state.selected_library_clip == Some(*x)
+                                })
+                                .collect()
+                        } else {
+                            vec![id]
+                        };
                         cmd.push(EditorCommand::SelectLibraryClip(Some(id)));
-                        ui.memory_mut(|m| m.data.insert_temp(Id::new("DND_PAYLOAD"), id));
+                        ui.memory_mut(|m| m.data.insert_temp(Id::new("DND_PAYLOAD"), dragged));
                     }
 
                     if let Some(id) = toggle_id {
@@ -300,6 +350,26 @@ impl EditorModule for LibraryModule {
                         cmd.push(EditorCommand::SelectTimelineClip(None));
                     }
 
+                    if let Some(id) = open_source {
+                        cmd.push(EditorCommand::SetSourceViewer(Some(id)));
+                    }
+
+                    if let Some((media_id, stream)) = stream_pick {
+                        cmd.push(EditorCommand::PushUndoSnapshot("Audio stream"));
+                        cmd.push(EditorCommand::SetClipAudioStream { media_id, stream });
+                    }
+                    if let Some((media_id, mode)) = range_pick {
+                        cmd.push(EditorCommand::PushUndoSnapshot("Color range"));
+                        cmd.push(EditorCommand::SetClipColorRange { media_id, mode });
+                    }
+                    if let Some((media_id, mode)) = deinterlace_pick {
+                        cmd.push(EditorCommand::PushUndoSnapshot("Deinterlace"));
+                        cmd.push(EditorCommand::SetClipDeinterlace { media_id, mode });
+                    }
+
+                    if !to_delete.is_empty() {
+                        cmd.push(EditorCommand::PushUndoSnapshot("Delete media"));
+                    }
                     for id in &to_delete {
                         self.multi_selection.remove(id);
                         cmd.push(EditorCommand::DeleteLibraryClip(*id));
@@ -316,7 +386,7 @@ impl EditorModule for LibraryModule {
 
 // ── Header bar ────────────────────────────────────────────────────────────────
 
-fn header_bar(ui: &mut Ui, cmd: &mut Vec<EditorCommand>) {
+fn header_bar(ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
     egui::Frame::new()
         .fill(DARK_BG_2)
         .inner_margin(egui::Margin {
@@ -342,15 +412,79 @@ fn header_bar(ui: &mut Ui, cmd: &mut Vec<EditorCommand>) {
                         if let Some(paths) = FileDialog::new()
                             .add_filter(
                                 "Media",
-                                &["mp4", "mov", "mkv", "avi", "mp3", "wav", "webm", "m4v"],
+                                &[
+                                    "mp4", "mov", "mkv", "avi", "mp3", "wav", "webm", "m4v", "png",
+                                    "jpg", "jpeg",
+                                ],
                             )
                             .pick_files()
                         {
+                            if !paths.is_empty() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Import media"));
+                            }
                             for path in paths {
                                 cmd.push(EditorCommand::ImportFile(path));
                             }
                         }
                     }
+                    // Reuse another project's media (and optionally its
+                    // timeline, e.g. an intro sequence) in this one.
+                    ui.menu_button(RichText::new("🗂 Project").size(11.0), |ui| {
+                        let pick = |with_timeline: bool| {
+                            FileDialog::new()
+                                .add_filter("VeloCut project", &["json"])
+                                .pick_file()
+                                .map(|path| (path, with_timeline))
+                        };
+                        let mut picked = None;
+                        if ui
+                            .button("Import media from project…")
+                            .on_hover_text(
+                                "Add the other project's library, skipping files already here",
+                            )
+                            .clicked()
+                        {
+                            picked = pick(false);
+                            ui.close();
+                        }
+                        if ui
+                            .button("Import media + timeline at playhead…")
+                            .on_hover_text(
+                                "Also place the other project's clips, transitions and links \
+                                 starting at the playhead (or after the timeline end if that \
+                                 spot is taken)",
+                            )
+                            .clicked()
+                        {
+                            picked = pick(true);
+                            ui.close();
+                        }
+                        if let Some((path, with_timeline)) = picked {
+                            cmd.push(EditorCommand::PushUndoSnapshot("Import project"));
+                            cmd.push(EditorCommand::ImportProject {
+                                path,
+                                timeline_at: with_timeline.then_some(state.current_time),
+                            });
+                        }
+                        ui.separator();
+                        if ui
+                            .button("Save a copy…")
+                            .on_hover_text(
+                                "Write this project to a new file and keep working here — \
+                                 a snapshot to return to before a risky change",
+                            )
+                            .clicked()
+                        {
+                            ui.close();
+                            if let Some(path) = FileDialog::new()
+                                .set_file_name("VeloCut project copy.json")
+                                .add_filter("VeloCut project", &["json"])
+                                .save_file()
+                            {
+                                cmd.push(EditorCommand::SaveProjectCopy(path));
+                            }
+                        }
+                    });
                 });
             });
         });
@@ -358,7 +492,12 @@ fn header_bar(ui: &mut Ui, cmd: &mut Vec<EditorCommand>) {
 
 // ── Status strip ─────────────────────────────────────────────────────────────
 
-fn status_strip(ui: &mut Ui, state: &ProjectState, multi: &HashSet<Uuid>) {
+fn status_strip(
+    ui: &mut Ui,
+    state: &ProjectState,
+    multi: &HashSet<Uuid>,
+    cmd: &mut Vec<EditorCommand>,
+) {
     if state.library.is_empty() {
         return;
     }
@@ -374,14 +513,42 @@ fn status_strip(ui: &mut Ui, state: &ProjectState, multi: &HashSet<Uuid>) {
         .show(ui, |ui| {
             ui.horizontal(|ui| {
                 let n = state.library.len();
-                ui.label(
-                    RichText::new(format!("{n} clip{}", if n == 1 { "" } else { "s" }))
-                        .size(10.0)
-                        .color(DARK_TEXT_DIM),
-                );
+                let mut text = format!("{n} clip{}", if n == 1 { "" } else { "s" });
+                // Project footprint: sum of every probed source file.
+                let total: u64 = state.library.iter().filter_map(|c| c.file_bytes).sum();
+                if total > 0 {
+                    text.push_str(&format!(" · {}", format_bytes(total)));
+                }
+                ui.label(RichText::new(text).size(10.0).color(DARK_TEXT_DIM));
 
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                    if multi.len() > 1 {
+                    if state.refresh_total > 0 {
+                        // Startup re-probe of a reopened project. Cards off
+                        // screen wait until scrolled to; ⏩ queues them all.
+                        if !state.deferred_probes.is_empty()
+                            && ui
+                                .small_button("⏩")
+                                .on_hover_text(
+                                    "Refresh the rest now instead of as they scroll into view",
+                                )
+                                .clicked()
+                        {
+                            cmd.push(EditorCommand::ProbeDeferredMedia);
+                        }
+                        let done = state.refresh_total - state.refreshing.len();
+                        ui.label(
+                            RichText::new(format!(
+                                "⟳ Refreshing media ({done}/{})",
+                                state.refresh_total
+                            ))
+                            .size(9.5)
+                            .color(ACCENT),
+                        )
+                        .on_hover_text(
+                            "Clips are re-probed as their cards come into view or \
+                             they are placed on the timeline",
+                        );
+                    } else if multi.len() > 1 {
                         // Multi-select hint
                         ui.label(
                             RichText::new(format!("{} selected  ⌫", multi.len()))
@@ -408,6 +575,7 @@ fn paint_card(
     is_selected: bool,
     in_multi: bool,
     is_dragging: bool,
+    matched_at: Option<f64>,
     thumb_cache: &ThumbnailCache,
 ) -> egui::Response {
     let highlight = is_selected || is_dragging;
@@ -454,10 +622,12 @@ fn paint_card(
                     egui::Label::new(RichText::new(clip.name.as_str()).size(10.0).color(name_col))
                         .truncate(),
                 );
-                let dur = if clip.duration_probed {
-                    format_duration(clip.duration)
-                } else {
-                    "⏳".into()
+                // A Match Frame target shows the matched source time in
+                // place of its length.
+                let (dur, dur_col) = match matched_at {
+                    Some(ts) => (format!("⌖ {}", format_source_time(ts)), ACCENT),
+                    None if clip.duration_probed => (format_duration(clip.duration), dur_col),
+                    None => ("⏳".into(), dur_col),
                 };
                 ui.label(RichText::new(dur).size(9.0).monospace().color(dur_col));
             });
@@ -487,14 +657,31 @@ fn paint_card(
 
 // ── Context menu ──────────────────────────────────────────────────────────────
 
+/// "1.2 GB · 8.4 Mbps" for a probed source, or just the size when the
+/// container reports no bit rate. `None` until the probe has run.
+fn file_stats_text(clip: &velocut_core::state::LibraryClip) -> Option<String> {
+    let bytes = clip.file_bytes?;
+    Some(match clip.bit_rate {
+        Some(br) => format!("{} · {}", format_bytes(bytes), format_bitrate(br)),
+        None => format_bytes(bytes),
+    })
+}
+
+/// Right-click menu for a library card. Returns `true` when "Open in source
+/// viewer" was picked.
+#[allow(clippy::too_many_arguments)]
 fn context_menu(
     ui: &mut Ui,
     clip: &velocut_core::state::LibraryClip,
     is_sel: bool,
     multi: &HashSet<Uuid>,
     to_delete: &mut Vec<Uuid>,
-) {
+    stream_pick: &mut Option<(Uuid, Option<usize>)>,
+    range_pick: &mut Option<(Uuid, ColorRangeMode)>,
+    deinterlace_pick: &mut Option<(Uuid, DeinterlaceMode)>,
+) -> bool {
     ui.set_min_width(160.0);
+    let mut open_source = false;
 
     // Clip info header
     ui.label(
@@ -516,9 +703,94 @@ fn context_menu(
                 .color(DARK_TEXT_DIM),
         );
     }
+    if let Some(stats) = file_stats_text(clip) {
+        ui.label(RichText::new(stats).size(9.5).color(DARK_TEXT_DIM));
+    }
+
+    // Audio stream picker — only for files with more than one audio stream
+    // (multi-language films, dual-mic recordings).
+    if clip.audio_streams.len() > 1 {
+        ui.separator();
+        ui.label(
+            RichText::new(format!(
+                "Audio stream ({} available)",
+                clip.audio_streams.len()
+            ))
+            .size(9.5)
+            .color(DARK_TEXT_DIM),
+        );
+        if ui
+            .radio(clip.audio_stream_index.is_none(), "Auto (best)")
+            .clicked()
+            && clip.audio_stream_index.is_some()
+        {
+            *stream_pick = Some((clip.id, None));
+            ui.close();
+        }
+        for info in &clip.audio_streams {
+            let current = clip.audio_stream_index == Some(info.index);
+            if ui.radio(current, info.label()).clicked() && !current {
+                *stream_pick = Some((clip.id, Some(info.index)));
+                ui.close();
+            }
+        }
+    }
+
+    // Color range override — once the probe has seen a video stream. Export
+    // converts full-range sources to limited so mixed footage matches.
+    if let Some(detected) = clip.color_range {
+        ui.separator();
+        ui.label(
+            RichText::new(format!("Color range (file: {})", detected.label()))
+                .size(9.5)
+                .color(DARK_TEXT_DIM),
+        );
+        for &mode in ColorRangeMode::all() {
+            let current = clip.color_range_mode == mode;
+            if ui.radio(current, mode.label()).clicked() && !current {
+                *range_pick = Some((clip.id, mode));
+                ui.close();
+            }
+        }
+    }
+
+    // Deinterlace override — interlaced camcorder / broadcast footage combs
+    // once it is scaled and encoded progressive.
+    if let Some(interlaced) = clip.interlaced {
+        ui.separator();
+        ui.label(
+            RichText::new(format!(
+                "Deinterlace (file: {})",
+                if interlaced {
+                    "interlaced"
+                } else {
+                    "progressive"
+                }
+            ))
+            .size(9.5)
+            .color(DARK_TEXT_DIM),
+        );
+        for &mode in DeinterlaceMode::all() {
+            let current = clip.deinterlace_mode == mode;
+            if ui.radio(current, mode.label()).clicked() && !current {
+                *deinterlace_pick = Some((clip.id, mode));
+                ui.close();
+            }
+        }
+    }
 
     ui.separator();
 
+    if clip.clip_type != ClipType::Image
+        && ui
+            .button("📼  Open in source viewer")
+            .on_hover_text("Mark in/out and insert the range at the playhead (or double-click)")
+            .clicked()
+    {
+        open_source = true;
+        ui.close();
+    }
+
     let multi_count = multi.len();
     if is_sel && multi_count > 1 {
         // Offer to delete all selected
@@ -535,6 +807,7 @@ fn context_menu(
         to_delete.push(clip.id);
         ui.close();
     }
+    open_source
 }
 
 // ── Drag ghost ────────────────────────────────────────────────────────────────
@@ -633,5 +906,6 @@ fn clip_icon(t: ClipType) -> &'static str {
     match t {
         ClipType::Video => "▶",
         ClipType::Audio => "♪",
+        ClipType::Image => "▣",
     }
 }
@@ -9,6 +9,7 @@ pub mod audio_module;
 pub mod export_module;
 pub mod library;
 pub mod preview_module;
+pub mod source_viewer;
 pub mod timeline;
 pub mod video_module;
 
@@ -1,11 +1,15 @@
 // crates/velocut-ui/src/modules/preview.rs
 use super::EditorModule;
+use crate::helpers::{clip_query, shortcuts};
 use crate::modules::ThumbnailCache;
-use crate::theme::{ACCENT, DARK_BG_2, DARK_BG_3, DARK_BORDER};
+use crate::theme::{ACCENT, DARK_BG_2, DARK_BG_3, DARK_BORDER, DARK_TEXT_DIM};
 use egui::{Color32, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2};
+use std::path::PathBuf;
 use velocut_core::commands::EditorCommand;
-use velocut_core::helpers::time::format_time;
-use velocut_core::state::{AspectRatio, ProjectState};
+use velocut_core::helpers::geometry::{aspect_ratio_short_label, next_aspect_ratio};
+use velocut_core::helpers::time::{format_source_time, format_time};
+use velocut_core::state::{AspectRatio, ProjectState, PREVIEW_RATES};
+use velocut_core::watermark::Watermark;
 
 // ── Transport bar layout constants ───────────────────────────────────────────
 const BAR_H: f32 = 48.0;
@@ -15,11 +19,22 @@ const ICON_SZ: f32 = 9.0; // half-size of painted icon geometry
 const GAP: f32 = 4.0; // gap between buttons in the same group
 const SEP: f32 = 18.0; // gap between groups
 const VOL_W: f32 = 80.0; // volume slider width
-                         // CONTENT_W = skip(30)+gap(4)+play(30)+gap(4)+stop(30) = 98
-                         //           + sep(18) + timecode(66) + sep(18)         = 102
-                         //           + mute(30)+gap(4)+vol(80)                  = 114
-                         //           ──────────────────────────────────────────── 314
-const CONTENT_W: f32 = 314.0;
+const RATE_W: f32 = 40.0; // preview rate button width
+const METER_W: f32 = 8.0; // level meter width
+                          // CONTENT_W = skip(30)+gap(4)+play(30)+gap(4)+stop(30) = 98
+                          //           + sep(18) + timecode(66) + sep(18)         = 102
+                          //           + rate(40)+gap(4)+fx(30) + sep(18)         = 92
+                          //           + mute(30)+gap(4)+vol(80)+gap(4)+meter(8)  = 126
+                          //           ──────────────────────────────────────────── 418
+const CONTENT_W: f32 = 418.0;
+
+// ── Level meter ──────────────────────────────────────────────────────────────
+const METER_FLOOR_DB: f32 = -60.0; // bottom of the meter scale
+const METER_FALL_DB_PER_SEC: f32 = 20.0; // peak fall-back rate
+const CLIP_HOLD_SECS: f64 = 1.5; // how long the over-0 dBFS cap stays lit
+
+// ── Aspect badge ─────────────────────────────────────────────────────────────
+const ASPECT_BADGE_SECS: f64 = 1.2; // how long Shift+A's aspect label shows
 
 // ── UV crop helper ────────────────────────────────────────────────────────────
 
@@ -65,6 +80,23 @@ pub struct PreviewModule {
     /// instead of native 1080p cuts swscale CPU from ~8% to ~0.5% and drops
     /// channel memory from 48 MB to ~6 MB.
     pub last_canvas_size: Option<(u32, u32)>,
+    /// Decoded watermark PNG, keyed by the path it was loaded from. `None`
+    /// texture = the load failed; kept so a bad path isn't re-read every frame.
+    watermark_tex: Option<(PathBuf, Option<egui::TextureHandle>)>,
+    /// Smoothed meter reading in dBFS (rises instantly, falls at
+    /// METER_FALL_DB_PER_SEC).
+    meter_db: f32,
+    /// egui time the mix last reached 0 dBFS — lights the clip cap.
+    clip_at: Option<f64>,
+    /// While the picture is being drag-scrubbed: pointer x and playhead time
+    /// when the drag began.
+    jog_from: Option<(f32, f64)>,
+    /// Last playhead time a jog scrub emitted, for the same 1/30 s dedup the
+    /// timeline ruler uses.
+    last_scrub_emitted_time: f64,
+    /// egui time Shift+A last cycled the aspect ratio — shows the new ratio
+    /// over the picture for ASPECT_BADGE_SECS.
+    aspect_cycled_at: Option<f64>,
 }
 
 impl PreviewModule {
@@ -73,6 +105,12 @@ impl PreviewModule {
             current_frame: None,
             held_frame: None,
             last_canvas_size: None,
+            watermark_tex: None,
+            meter_db: METER_FLOOR_DB,
+            clip_at: None,
+            jog_from: None,
+            last_scrub_emitted_time: -1.0,
+            aspect_cycled_at: None,
         }
     }
 
@@ -81,6 +119,48 @@ impl PreviewModule {
         self.held_frame = None;
         // Do not clear last_canvas_size — panel size persists across playback resets.
     }
+
+    /// Draw the project watermark over `canvas` with the same placement the
+    /// encoder uses, loading (or reloading) its texture when the path changes.
+    fn paint_watermark(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        canvas: Rect,
+        wm: &Watermark,
+    ) {
+        let stale = self
+            .watermark_tex
+            .as_ref()
+            .is_none_or(|(path, _)| *path != wm.path);
+        if stale {
+            let tex = match velocut_media::decode::load_png_rgba(&wm.path) {
+                Ok((rgba, w, h)) => Some(ctx.load_texture(
+                    "watermark",
+                    egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &rgba),
+                    egui::TextureOptions::LINEAR,
+                )),
+                Err(e) => {
+                    eprintln!("[preview] watermark '{}': {e}", wm.path.display());
+                    None
+                }
+            };
+            self.watermark_tex = Some((wm.path.clone(), tex));
+        }
+        let Some((_, Some(tex))) = &self.watermark_tex else {
+            return;
+        };
+        let [tw, th] = tex.size();
+        let [x, y, w, h] = wm.placement(canvas.width(), canvas.height(), tw as f32, th as f32);
+        let rect = Rect::from_min_size(canvas.min + Vec2::new(x, y), Vec2::new(w, h));
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        painter.image(
+            tex.id(),
+            rect,
+            uv,
+            Color32::WHITE.gamma_multiply(wm.opacity),
+        );
+    }
 }
 
 impl EditorModule for PreviewModule {
@@ -108,20 +188,35 @@ impl EditorModule for PreviewModule {
                 .show(ui, |ui| {
                     ui.horizontal(|ui| {
                         ui.label(RichText::new("🖼 Monitor").size(12.0).strong());
+                        // Source timecode of the selected clip under the
+                        // playhead — what to match against another camera or
+                        // a transcript.
+                        let selected_src = state
+                            .selected_timeline_clip
+                            .and_then(|id| state.timeline.iter().find(|c| c.id == id))
+                            .filter(|c| {
+                                state.current_time >= c.start_time
+                                    && state.current_time < c.start_time + c.duration
+                            });
+                        if let Some(clip) = selected_src {
+                            let src = clip.source_time(state.current_time - clip.start_time);
+                            let name = state
+                                .library
+                                .iter()
+                                .find(|m| m.id == clip.media_id)
+                                .map_or("", |m| m.name.as_str());
+                            ui.label(
+                                RichText::new(format!("source {}", format_source_time(src)))
+                                    .monospace()
+                                    .size(11.0)
+                                    .color(DARK_TEXT_DIM),
+                            )
+                            .on_hover_text(format!("Playhead position in {name}"));
+                        }
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             let mut ar = state.aspect_ratio;
                             egui::ComboBox::from_id_salt("aspect_ratio")
-                                .selected_text(match ar {
-                                    AspectRatio::SixteenNine => "16:9",
-                                    AspectRatio::NineSixteen => "9:16",
-                                    AspectRatio::TwoThree => "2:3",
-                                    AspectRatio::ThreeTwo => "3:2",
-                                    AspectRatio::FourThree => "4:3",
-                                    AspectRatio::OneOne => "1:1",
-                                    AspectRatio::FourFive => "4:5",
-                                    AspectRatio::TwentyOneNine => "21:9",
-                                    AspectRatio::Anamorphic => "2.39:1",
-                                })
+                                .selected_text(aspect_ratio_short_label(ar))
                                 .show_ui(ui, |ui| {
                                     ui.selectable_value(
                                         &mut ar,
@@ -169,6 +264,20 @@ impl EditorModule for PreviewModule {
                                         "2.39:1 — Anamorphic",
                                     );
                                 });
+                            // Shift+A — step through the presets to compare
+                            // framings; not while typing (capital A).
+                            let text_focused = ui.memory(|m| m.focused().is_some());
+                            if !text_focused
+                                && !shortcuts::is_open(ui.ctx())
+                                && ui.input(|i| {
+                                    i.modifiers.shift
+                                        && !i.modifiers.ctrl
+                                        && i.key_pressed(egui::Key::A)
+                                })
+                            {
+                                ar = next_aspect_ratio(state.aspect_ratio);
+                                self.aspect_cycled_at = Some(ui.input(|i| i.time));
+                            }
                             if ar != state.aspect_ratio {
                                 cmd.push(EditorCommand::SetAspectRatio(ar));
                             }
@@ -227,7 +336,11 @@ impl EditorModule for PreviewModule {
                 state.current_time >= c.start_time && state.current_time < c.start_time + c.duration
             });
 
-            if let Some(clip) = current_clip {
+            if let Some([r, g, b]) = current_clip.and_then(|c| c.slate) {
+                // Slates have no frames to decode — paint the color itself.
+                self.held_frame = None;
+                painter.rect_filled(canvas, 3.0, Color32::from_rgb(r, g, b));
+            } else if let Some(clip) = current_clip {
                 if let Some(media) = state.library.iter().find(|m| m.id == clip.media_id) {
                     // Update held_frame whenever we have a fresh decoded frame.
                     // This persists across ticks so clip transitions and scrub latency
@@ -269,11 +382,31 @@ impl EditorModule for PreviewModule {
                 // stale frame when the user scrubs to an empty region of the timeline.
                 self.held_frame = None;
                 painter.text(
-                    canvas.center(),
+                    canvas.center() - egui::vec2(0.0, 14.0),
+                    egui::Align2::CENTER_CENTER,
+                    "No clip at playhead",
+                    egui::FontId::proportional(14.0),
+                    Color32::from_gray(90),
+                );
+                painter.text(
+                    canvas.center() + egui::vec2(0.0, 6.0),
                     egui::Align2::CENTER_CENTER,
-                    "NO SIGNAL",
-                    egui::FontId::monospace(14.0),
-                    Color32::from_gray(40),
+                    format_time(state.current_time, state.project_fps),
+                    egui::FontId::monospace(12.0),
+                    Color32::from_gray(70),
+                );
+                // Point new users at the next step; in a gap, at the way out.
+                let hint = if state.timeline.is_empty() {
+                    "Drag a clip from the library onto the timeline"
+                } else {
+                    "Move the playhead onto a clip"
+                };
+                painter.text(
+                    canvas.center() + egui::vec2(0.0, 26.0),
+                    egui::Align2::CENTER_CENTER,
+                    hint,
+                    egui::FontId::proportional(11.0),
+                    Color32::from_gray(60),
                 );
                 let mut y = canvas.min.y;
                 while y < canvas.max.y {
@@ -285,6 +418,84 @@ impl EditorModule for PreviewModule {
                 }
             }
 
+            // Export burns the watermark into every frame; mirror it here
+            // whenever there is picture to put it on.
+            if let (Some(wm), Some(_)) = (&state.watermark, current_clip) {
+                self.paint_watermark(ui.ctx(), painter, canvas, wm);
+            }
+
+            // Say so while grading is hidden, so an ungraded frame is never
+            // mistaken for the export.
+            if state.bypass_effects {
+                painter.text(
+                    canvas.left_top() + egui::vec2(8.0, 8.0),
+                    egui::Align2::LEFT_TOP,
+                    "EFFECTS BYPASSED",
+                    egui::FontId::proportional(11.0),
+                    Color32::from_rgb(255, 190, 90),
+                );
+            }
+
+            // Name the ratio just picked with Shift+A, fading out.
+            if let Some(at) = self.aspect_cycled_at {
+                let age = ui.input(|i| i.time) - at;
+                if age < ASPECT_BADGE_SECS {
+                    let alpha = (1.0 - age / ASPECT_BADGE_SECS).min(0.5) as f32 * 2.0;
+                    let galley = painter.layout_no_wrap(
+                        aspect_ratio_short_label(state.aspect_ratio).to_string(),
+                        egui::FontId::proportional(28.0),
+                        Color32::WHITE.gamma_multiply(alpha),
+                    );
+                    let badge = Rect::from_center_size(
+                        canvas.center(),
+                        galley.size() + Vec2::new(28.0, 14.0),
+                    );
+                    painter.rect_filled(
+                        badge,
+                        6.0,
+                        Color32::from_black_alpha((150.0 * alpha) as u8),
+                    );
+                    painter.galley(badge.center() - galley.size() / 2.0, galley, Color32::WHITE);
+                    ui.ctx().request_repaint();
+                } else {
+                    self.aspect_cycled_at = None;
+                }
+            }
+
+            // ── Jog scrub ─────────────────────────────────────────────────────
+            // Dragging across the picture moves the playhead relative to where
+            // the drag began; a full canvas width covers the Playback prefs'
+            // span. Same 1/30 s dedup as the timeline ruler.
+            if !state.timeline.is_empty() {
+                let jog = ui.interact(canvas, ui.id().with("preview_jog"), Sense::drag());
+                if jog.drag_started() {
+                    if let Some(pos) = jog.interact_pointer_pos() {
+                        self.jog_from = Some((pos.x, state.current_time));
+                        self.last_scrub_emitted_time = state.current_time;
+                        cmd.push(EditorCommand::Pause);
+                    }
+                }
+                if let (true, Some((x0, t0)), Some(pos)) =
+                    (jog.dragged(), self.jog_from, jog.interact_pointer_pos())
+                {
+                    let span = state.playback_prefs.preview_scrub_span();
+                    let t = (t0 + (pos.x - x0) as f64 / canvas.width() as f64 * span)
+                        .clamp(0.0, state.total_duration().max(0.0));
+                    if (t - self.last_scrub_emitted_time).abs() >= state.frame_secs() {
+                        cmd.push(EditorCommand::SetPlayhead(t));
+                        self.last_scrub_emitted_time = t;
+                    }
+                }
+                if jog.drag_stopped() {
+                    self.jog_from = None;
+                }
+                if jog.dragged() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                } else if jog.hovered() {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                }
+            }
+
             ui.add_space(6.0);
 
             // ── Transport Bar ─────────────────────────────────────────────────
@@ -305,7 +516,7 @@ impl EditorModule for PreviewModule {
             let content_w = if show_volume {
                 CONTENT_W
             } else {
-                CONTENT_W - GAP - VOL_W
+                CONTENT_W - GAP - VOL_W - GAP - METER_W
             };
             let mut x = (bar_rect.center().x - content_w / 2.0).max(bar_rect.min.x + 6.0);
 
@@ -415,12 +626,91 @@ impl EditorModule for PreviewModule {
             painter.text(
                 Pos2::new(x, cy),
                 egui::Align2::LEFT_CENTER,
-                format_time(state.current_time),
+                format_time(state.current_time, state.project_fps),
                 egui::FontId::monospace(12.0),
                 ACCENT,
             );
             x += 66.0 + SEP;
 
+            // ── Preview rate ──────────────────────────────────────────────
+            // Click steps faster, right-click slower. Review-only: export and
+            // per-clip timing are untouched.
+            let rate = state.preview_rate;
+            let rate_rect = Rect::from_min_size(
+                Pos2::new(x, cy - BTN_SIZE / 2.0),
+                Vec2::new(RATE_W, BTN_SIZE),
+            );
+            let rate_resp = ui
+                .interact(rate_rect, ui.id().with("preview_rate"), Sense::click())
+                .on_hover_text(
+                    "Preview speed — click: faster, right-click: slower, middle-click: 1×\n\
+                     Review only; does not affect export",
+                );
+            let off_speed = rate != 1.0;
+            painter.rect_filled(
+                rate_rect,
+                BTN_R,
+                if rate_resp.hovered() {
+                    DARK_BG_2
+                } else {
+                    DARK_BG_3
+                },
+            );
+            if rate_resp.hovered() || off_speed {
+                painter.rect_stroke(
+                    rate_rect,
+                    BTN_R,
+                    Stroke::new(1.0_f32, ACCENT.gamma_multiply(0.35)),
+                    egui::StrokeKind::Outside,
+                );
+            }
+            painter.text(
+                rate_rect.center(),
+                egui::Align2::CENTER_CENTER,
+                format!("{rate}×"),
+                egui::FontId::monospace(11.0),
+                if off_speed {
+                    ACCENT
+                } else {
+                    Color32::from_gray(175)
+                },
+            );
+            let idx = PREVIEW_RATES.iter().position(|r| *r == rate).unwrap_or(3);
+            if rate_resp.clicked() && idx + 1 < PREVIEW_RATES.len() {
+                cmd.push(EditorCommand::SetPreviewRate(PREVIEW_RATES[idx + 1]));
+            } else if rate_resp.secondary_clicked() && idx > 0 {
+                cmd.push(EditorCommand::SetPreviewRate(PREVIEW_RATES[idx - 1]));
+            } else if rate_resp.middle_clicked() {
+                cmd.push(EditorCommand::SetPreviewRate(1.0));
+            }
+            x += RATE_W + GAP;
+
+            // ── Effects bypass ────────────────────────────────────────────
+            // Lit while the preview skips clip filters (this button, or `\`
+            // held). Review-only like the rate: export always grades.
+            let bypassed = state.bypass_effects;
+            if tbtn!("fx_bypass", bypassed, |c: Pos2, col: Color32| {
+                painter.text(
+                    c,
+                    egui::Align2::CENTER_CENTER,
+                    "fx",
+                    egui::FontId::proportional(13.0),
+                    col,
+                );
+                if bypassed {
+                    painter.line_segment(
+                        [
+                            Pos2::new(c.x - ICON_SZ, c.y + ICON_SZ * 0.7),
+                            Pos2::new(c.x + ICON_SZ, c.y - ICON_SZ * 0.7),
+                        ],
+                        Stroke::new(1.5_f32, col),
+                    );
+                }
+            }) {
+                cmd.push(EditorCommand::SetEffectsBypass(!bypassed));
+            }
+            x += SEP;
+
             // ── Mute ──────────────────────────────────────────────────────
             let muted = state.muted;
             let vol_val = state.volume;
@@ -490,6 +780,81 @@ impl EditorModule for PreviewModule {
                 {
                     cmd.push(EditorCommand::SetVolume(vol));
                 }
+                x += VOL_W + GAP;
+
+                // ── Level meter ───────────────────────────────────────────
+                // Peak of the preview mix under the playhead, read from the
+                // clips' waveform data (clip_query::playback_level).
+                let level = if state.is_playing {
+                    clip_query::playback_level(state, state.current_time)
+                } else {
+                    0.0
+                };
+                let (dt, now) = ui.input(|i| (i.stable_dt, i.time));
+                let db = if level > 0.0 {
+                    20.0 * level.log10()
+                } else {
+                    METER_FLOOR_DB
+                };
+                self.meter_db = db
+                    .max(self.meter_db - METER_FALL_DB_PER_SEC * dt)
+                    .max(METER_FLOOR_DB);
+                if level >= 1.0 {
+                    self.clip_at = Some(now);
+                }
+                let clipped = self.clip_at.is_some_and(|t| now - t < CLIP_HOLD_SECS);
+
+                let meter_rect = Rect::from_min_size(
+                    Pos2::new(x, cy - BTN_SIZE / 2.0),
+                    Vec2::new(METER_W, BTN_SIZE),
+                );
+                let painter = ui.painter();
+                painter.rect_filled(meter_rect, 2.0, DARK_BG_2);
+                let frac = ((self.meter_db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0);
+                if frac > 0.0 {
+                    let color = if self.meter_db > -3.0 {
+                        Color32::from_rgb(220, 60, 50)
+                    } else if self.meter_db > -12.0 {
+                        Color32::from_rgb(230, 200, 60)
+                    } else {
+                        Color32::from_rgb(70, 200, 100)
+                    };
+                    let fill = Rect::from_min_max(
+                        Pos2::new(
+                            meter_rect.min.x,
+                            meter_rect.max.y - meter_rect.height() * frac,
+                        ),
+                        meter_rect.max,
+                    );
+                    painter.rect_filled(fill, 2.0, color);
+                }
+                let cap = Rect::from_min_size(meter_rect.min, Vec2::new(METER_W, 3.0));
+                painter.rect_filled(
+                    cap,
+                    1.0,
+                    if clipped {
+                        Color32::from_rgb(255, 40, 40)
+                    } else {
+                        DARK_BG_3
+                    },
+                );
+                let meter_resp = ui
+                    .interact(meter_rect, ui.id().with("level_meter"), Sense::click())
+                    .on_hover_text(if self.meter_db > METER_FLOOR_DB {
+                        format!(
+                            "Peak {:.1} dBFS — click to reset the clip light",
+                            self.meter_db
+                        )
+                    } else {
+                        "Level meter".to_string()
+                    });
+                if meter_resp.clicked() {
+                    self.clip_at = None;
+                }
+                // Keep repainting while the bar falls back or the cap is lit.
+                if self.meter_db > METER_FLOOR_DB || clipped {
+                    ui.ctx().request_repaint();
+                }
             }
         }); // ui.vertical
     }
@@ -0,0 +1,307 @@
+// crates/velocut-ui/src/modules/source_viewer.rs
+//
+// Source viewer: a second monitor for one library clip, with its own
+// playhead and in/out marks — the first two points of a three-point edit.
+// The third is the timeline playhead; "Insert" places the marked range there
+// via `InsertSourceRange`, pushing the rest of the track right.
+//
+// Frames come from the worker's exact-frame thumbnail path
+// (`request_source_frame`) rather than the Monitor's scrub pipeline, so the
+// two viewers never fight over the single scrub slot. They are keyed by
+// `frame_key`, a per-viewer id, so they never replace a library card's
+// thumbnail.
+
+use super::EditorModule;
+use crate::context::AppContext;
+use crate::helpers::format::truncate;
+use crate::modules::ThumbnailCache;
+use crate::theme::{ACCENT, DARK_BG_0, DARK_BG_2, DARK_BG_3, DARK_TEXT_DIM};
+use egui::{Color32, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use velocut_core::commands::EditorCommand;
+use velocut_core::helpers::time::format_source_time;
+use velocut_core::state::{ClipType, ProjectState};
+
+/// Scrubbing decodes at most one frame per interval; the last position is
+/// always requested once the pointer settles.
+const REQUEST_INTERVAL: Duration = Duration::from_millis(80);
+/// Decode width bounds. The canvas width picks a size in between.
+const FRAME_W_MIN: u32 = 160;
+const FRAME_W_MAX: u32 = 1280;
+const SCRUB_H: f32 = 18.0;
+pub struct SourceViewerModule {
+    /// Thumbnail-cache key for this viewer's frame.
+    frame_key: Uuid,
+    /// Library clip the playhead and marks belong to. Opening a different
+    /// clip resets them.
+    clip: Option<Uuid>,
+    /// Source playhead (seconds).
+    time: f64,
+    mark_in: Option<f64>,
+    mark_out: Option<f64>,
+    /// Clip and time of the last frame request, and when it was issued.
+    requested: Option<(Uuid, f64)>,
+    last_request: Option<Instant>,
+    /// Canvas width last frame — the decode width for the next request.
+    canvas_w: u32,
+}
+
+impl SourceViewerModule {
+    pub fn new() -> Self {
+        Self {
+            frame_key: Uuid::new_v4(),
+            clip: None,
+            time: 0.0,
+            mark_in: None,
+            mark_out: None,
+            requested: None,
+            last_request: None,
+            canvas_w: FRAME_W_MIN,
+        }
+    }
+
+    /// Marked range, defaulting to the whole clip for a missing mark.
+    fn range(&self, duration: f64) -> (f64, f64) {
+        (
+            self.mark_in.unwrap_or(0.0),
+            self.mark_out.unwrap_or(duration),
+        )
+    }
+
+    fn set_in(&mut self) {
+        self.mark_in = Some(self.time);
+        if self.mark_out.is_some_and(|out| out <= self.time) {
+            self.mark_out = None;
+        }
+    }
+
+    fn set_out(&mut self) {
+        self.mark_out = Some(self.time);
+        if self.mark_in.is_some_and(|inp| inp >= self.time) {
+            self.mark_in = None;
+        }
+    }
+}
+
+impl EditorModule for SourceViewerModule {
+    fn name(&self) -> &str {
+        "Source"
+    }
+
+    fn ui(
+        &mut self,
+        ui: &mut Ui,
+        state: &ProjectState,
+        thumb_cache: &mut ThumbnailCache,
+        cmd: &mut Vec<EditorCommand>,
+    ) {
+        let Some(lib) = state
+            .source_viewer_clip
+            .and_then(|id| state.library.iter().find(|c| c.id == id))
+        else {
+            return;
+        };
+        if self.clip != Some(lib.id) {
+            self.clip = Some(lib.id);
+            self.time = 0.0;
+            self.mark_in = None;
+            self.mark_out = None;
+            self.requested = None;
+        }
+        let duration = lib.duration.max(0.0);
+        self.time = self.time.clamp(0.0, duration);
+
+        ui.vertical(|ui| {
+            // ── Header ───────────────────────────────────────────────────────
+            egui::Frame::new()
+                .fill(DARK_BG_2)
+                .inner_margin(egui::Margin {
+                    left: 8,
+                    right: 8,
+                    top: 5,
+                    bottom: 5,
+                })
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new("📼 Source").size(12.0).strong());
+                        ui.label(
+                            RichText::new(truncate(&lib.name, 32))
+                                .size(11.0)
+                                .color(DARK_TEXT_DIM),
+                        )
+                        .on_hover_text(lib.path.display().to_string());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("✕").on_hover_text("Close").clicked() {
+                                cmd.push(EditorCommand::SetSourceViewer(None));
+                            }
+                        });
+                    });
+                });
+
+            // ── Canvas ───────────────────────────────────────────────────────
+            let controls_h = SCRUB_H + 64.0;
+            let canvas_size = Vec2::new(
+                ui.available_width(),
+                (ui.available_height() - controls_h).max(60.0),
+            );
+            let (canvas, canvas_resp) = ui.allocate_exact_size(canvas_size, Sense::hover());
+            self.canvas_w = (canvas.width() as u32).clamp(FRAME_W_MIN, FRAME_W_MAX);
+            let painter = ui.painter();
+            painter.rect_filled(canvas, 0.0, DARK_BG_0);
+            let frame = thumb_cache
+                .get(&self.frame_key)
+                .filter(|_| self.requested.is_some_and(|(id, _)| id == lib.id));
+            if lib.clip_type == ClipType::Audio {
+                painter.text(
+                    canvas.center(),
+                    egui::Align2::CENTER_CENTER,
+                    "♪ Audio clip",
+                    egui::FontId::proportional(14.0),
+                    DARK_TEXT_DIM,
+                );
+            } else if let Some(tex) = frame {
+                let [tw, th] = tex.size();
+                let scale = (canvas.width() / tw as f32).min(canvas.height() / th as f32);
+                let img = Rect::from_center_size(
+                    canvas.center(),
+                    Vec2::new(tw as f32 * scale, th as f32 * scale),
+                );
+                painter.image(
+                    tex.id(),
+                    img,
+                    Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                    Color32::WHITE,
+                );
+            }
+
+            // ── Scrub bar: marked range + source playhead ────────────────────
+            let (bar, bar_resp) = ui.allocate_exact_size(
+                Vec2::new(ui.available_width(), SCRUB_H),
+                Sense::click_and_drag(),
+            );
+            let to_x = |t: f64| {
+                bar.min.x + bar.width() * (t / duration.max(f64::EPSILON)) as f32
+            };
+            if let Some(pos) = bar_resp.interact_pointer_pos() {
+                let frac = ((pos.x - bar.min.x) / bar.width().max(1.0)).clamp(0.0, 1.0);
+                self.time = frac as f64 * duration;
+            }
+            let painter = ui.painter();
+            painter.rect_filled(bar, 2.0, DARK_BG_3);
+            if self.mark_in.is_some() || self.mark_out.is_some() {
+                let (a, b) = self.range(duration);
+                let span = Rect::from_min_max(
+                    Pos2::new(to_x(a), bar.min.y),
+                    Pos2::new(to_x(b), bar.max.y),
+                );
+                painter.rect_filled(span, 2.0, ACCENT.gamma_multiply(0.35));
+            }
+            let px = to_x(self.time);
+            painter.line_segment(
+                [Pos2::new(px, bar.min.y), Pos2::new(px, bar.max.y)],
+                Stroke::new(2.0, Color32::WHITE),
+            );
+
+            // ── Marks + insert ───────────────────────────────────────────────
+            let (src_in, src_out) = self.range(duration);
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format_source_time(self.time))
+                        .monospace()
+                        .size(11.0),
+                );
+                if ui.button("[ In").on_hover_text("Mark in  [I]").clicked() {
+                    self.set_in();
+                }
+                if ui.button("Out ]").on_hover_text("Mark out  [O]").clicked() {
+                    self.set_out();
+                }
+                if ui
+                    .add_enabled(
+                        self.mark_in.is_some() || self.mark_out.is_some(),
+                        egui::Button::new("✕"),
+                    )
+                    .on_hover_text("Clear marks")
+                    .clicked()
+                {
+                    self.mark_in = None;
+                    self.mark_out = None;
+                }
+            });
+            ui.label(
+                RichText::new(format!(
+                    "In {}  Out {}  ({:.2}s)",
+                    format_source_time(src_in),
+                    format_source_time(src_out),
+                    src_out - src_in
+                ))
+                .monospace()
+                .size(10.0)
+                .color(DARK_TEXT_DIM),
+            );
+            let can_insert = lib.duration_probed && src_out - src_in >= state.frame_secs();
+            if ui
+                .add_enabled(can_insert, egui::Button::new("⤓  Insert at playhead"))
+                .on_hover_text(
+                    "Insert In→Out at the timeline playhead, pushing later clips on the track right",
+                )
+                .on_disabled_hover_text("Mark a range of at least one frame")
+                .clicked()
+            {
+                cmd.push(EditorCommand::PushUndoSnapshot("Insert edit"));
+                cmd.push(EditorCommand::InsertSourceRange {
+                    media_id: lib.id,
+                    source_in: src_in,
+                    source_out: src_out,
+                    at_time: state.current_time,
+                });
+            }
+
+            // I / O mark at the source playhead while the pointer is over the
+            // viewer, so they never clash with timeline shortcuts.
+            if canvas_resp.hovered() || bar_resp.hovered() {
+                if ui.input(|i| !i.modifiers.ctrl && i.key_pressed(egui::Key::I)) {
+                    self.set_in();
+                }
+                if ui.input(|i| !i.modifiers.ctrl && i.key_pressed(egui::Key::O)) {
+                    self.set_out();
+                }
+            }
+
+            // A throttled request is still owed — come back for it.
+            if lib.clip_type != ClipType::Audio && self.requested != Some((lib.id, self.time)) {
+                ui.ctx().request_repaint_after(REQUEST_INTERVAL);
+            }
+        });
+    }
+
+    /// Request the frame under the source playhead when it moved, at most
+    /// once per `REQUEST_INTERVAL`.
+    fn tick(&mut self, state: &ProjectState, ctx: &mut AppContext) {
+        let Some(lib) = state
+            .source_viewer_clip
+            .and_then(|id| state.library.iter().find(|c| c.id == id))
+        else {
+            return;
+        };
+        if lib.clip_type == ClipType::Audio || self.clip != Some(lib.id) {
+            return;
+        }
+        if self.requested == Some((lib.id, self.time))
+            || self
+                .last_request
+                .is_some_and(|t| t.elapsed() < REQUEST_INTERVAL)
+        {
+            return;
+        }
+        ctx.media_worker.request_source_frame(
+            self.frame_key,
+            lib.path.clone(),
+            self.time,
+            self.canvas_w,
+        );
+        self.requested = Some((lib.id, self.time));
+        self.last_request = Some(Instant::now());
+    }
+}
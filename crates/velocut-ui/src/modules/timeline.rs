@@ -2,18 +2,46 @@
 use super::EditorModule;
 use crate::helpers::clip_query;
 use crate::helpers::format::fit_label;
+use crate::helpers::shortcuts;
 use crate::modules::ThumbnailCache;
 use crate::theme::{
-    ACCENT, ACTION_BTN_FILL, ACTION_BTN_STROKE, CLIP_AUDIO, CLIP_SELECTED, CLIP_VIDEO, DARK_BG_0,
-    DARK_BG_2, DARK_BG_3, DARK_BORDER, DARK_TEXT_DIM, PLAYHEAD_BTN_FILL, PLAYHEAD_BTN_STROKE,
+    ACCENT, ACTION_BTN_FILL, ACTION_BTN_STROKE, BEAT, CLIP_AUDIO, CLIP_OVERLAP, CLIP_SELECTED,
+    CLIP_TAG_PALETTE, CLIP_VIDEO, DARK_BG_0, DARK_BG_2, DARK_BG_3, DARK_BORDER, DARK_TEXT_DIM,
+    MARKER, PLAYHEAD_BTN_FILL, PLAYHEAD_BTN_STROKE,
 };
 use egui::{Align2, Color32, FontId, Id, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2};
 use uuid::Uuid;
+use velocut_core::beat_grid::{
+    tempo_from_taps, BeatGrid, BEATS_PER_BAR_RANGE, BPM_RANGE, TAP_RESET_SECS,
+};
 use velocut_core::commands::EditorCommand;
-use velocut_core::filters::{FilterKind, FilterParams};
+use velocut_core::filters::{FilterKind, FilterParams, Levels, SpatialFilter, SpatialKind, LEVELS_MIN_SPAN};
 use velocut_core::helpers::time::format_time;
-use velocut_core::state::{ClipType, ProjectState};
-use velocut_core::transitions::TransitionType;
+use velocut_core::prefs::{SpaceBehavior, CLIP_RADIUS_RANGE};
+use velocut_core::state::{
+    ClipType, DropPart, Duck, LibraryClip, ProjectState, SpeechRegions, Stabilize, TimelineClip,
+};
+use velocut_core::transitions::{FadeCurve, TransitionKind, TransitionType};
+
+/// Timeline zoom limits in px/s, shared by the zoom buttons, the numeric
+/// field and the span presets.
+const ZOOM_MIN: f32 = 1.0;
+const ZOOM_MAX: f32 = 1000.0;
+/// Width of the fixed track-label column left of the lanes.
+const LABEL_COL: f32 = 48.0;
+/// Range for a user-set track height (px). Auto-fit stays within 28–54.
+const TRACK_H_MIN: f32 = 20.0;
+const TRACK_H_MAX: f32 = 120.0;
+/// Trim handle width on clips wide enough to spare it. Narrower clips get
+/// `TRIM_HANDLE_FRAC` of their width per handle so the body stays grabbable.
+const TRIM_HANDLE_W: f32 = 7.0;
+const TRIM_HANDLE_FRAC: f32 = 0.3;
+/// How far (px) an edge must come to a snap point before it snaps.
+const SNAP_PX: f64 = 8.0;
+/// How long a newly engaged snap line flashes, in seconds.
+const SNAP_FLASH_SECS: f64 = 0.25;
+/// Clip-edge / track-end snap line color (markers and beats use `MARKER`).
+const SNAP_EDGE: Color32 = Color32::from_rgb(255, 200, 50);
 
 pub struct TimelineModule {
     /// Which clip ID's outgoing transition popup is open, and where to show it.
@@ -39,14 +67,10 @@ pub struct TimelineModule {
     /// Tracks (clip_id, target_row) during a cross-track drag for lane highlight.
     /// One-frame latency is imperceptible and avoids a second pass over clips.
     drag_target: Option<(Uuid, usize)>,
-
-    /// Whether the hotkey reference popup is currently visible.
-    /// Whether the hotkey reference popup is currently visible.
-    hotkeys_open: bool,
-    /// True on the frame the hotkey popup is first opened — suppresses the
-    /// click-outside-to-close check so the opening click doesn't immediately
-    /// close the popup it just spawned.
-    hotkeys_just_opened: bool,
+    /// Lane under the pointer during that drag when it is the wrong kind for
+    /// the clip (video over an A track or vice versa) — painted as refused
+    /// while the clip stays on `drag_target`.
+    drag_refused: Option<usize>,
 
     /// Last timeline position (seconds) for which a scrub decode was emitted.
     ///
@@ -58,6 +82,62 @@ pub struct TimelineModule {
     /// Reset to a negative sentinel on construction.  Updated whenever a
     /// `SetPlayhead` is actually pushed so the filter stays tight.
     last_scrub_emitted_time: f64,
+
+    /// Horizontal window of the track ScrollArea as of the previous frame:
+    /// (scroll offset px, viewport width px). The overview strip is drawn
+    /// above the ScrollArea, so it can only see last frame's values.
+    view_scroll: (f32, f32),
+    /// While the overview viewport rectangle is being dragged: the grab point
+    /// in seconds, measured from the viewport's left edge.
+    overview_grab: Option<f64>,
+    /// Hold length for the freeze-frame button / F key (seconds).
+    freeze_secs: f64,
+    /// Length and color for the slate button (seconds, sRGB).
+    slate_secs: f64,
+    slate_color: [u8; 3],
+    /// Playhead position when a hold-to-play Space press began. `Some` while
+    /// Space is held in `SpaceBehavior::Momentary`.
+    momentary_from: Option<f64>,
+    /// `\` is held for a momentary effects-bypass; released → filters back on.
+    bypass_held: bool,
+    /// Scene-detection sensitivity for the clip context menu (0–1, lower
+    /// finds more cuts).
+    scene_threshold: f32,
+    /// Silence-trim loudness floor (dBFS) and shortest lead/tail gap worth
+    /// trimming (seconds) for the clip context menu.
+    silence_db: f32,
+    silence_gap: f64,
+    /// Auto-duck amount, speech threshold and ramp times for the clip
+    /// context menu.
+    duck: Duck,
+    /// Project audio mixdown for the overview strip, tagged with the
+    /// `mixdown_key` it was built for so it is only restitched when clips,
+    /// waveforms or the strip width change.
+    mixdown: Option<(u64, Vec<f32>)>,
+    /// Grouped clip awaiting "delete the whole group?" confirmation.
+    delete_confirm: Option<Uuid>,
+    /// Track height set from the ▦ menu or Ctrl+wheel. `None` fits the
+    /// tracks to the panel height.
+    track_height: Option<f32>,
+    /// Tap-tempo presses (egui input time, seconds) of the current sequence.
+    beat_taps: Vec<f64>,
+    /// Playhead time seen last frame, so auto-scroll can tell a keyboard step
+    /// (follow it) from a still playhead (leave the view alone).
+    follow_last_time: f64,
+    /// Clip in manual sync fine-tune: its waveform is drawn against its sync
+    /// reference's and ←/→ nudge it instead of stepping the playhead.
+    sync_adjust: Option<Uuid>,
+    /// Snap point (seconds) engaged during the current drag and the egui time
+    /// it engaged at, so a new snap flashes once. `None` while nothing snaps.
+    snap_flash: Option<(f64, f64)>,
+    /// Clip being moved and its start when the drag began, so the drop can
+    /// be measured from the pointer even where `move_group` holds it back.
+    move_origin: Option<(Uuid, f64)>,
+    /// Crossfade the current move would make: clip before, dragged clip,
+    /// overlap in seconds.
+    drag_overlap: Option<(Uuid, Uuid, f64)>,
+    /// A dropped overlap waiting for "crossfade?" confirmation, same shape.
+    crossfade_offer: Option<(Uuid, Uuid, f64)>,
 }
 
 impl TimelineModule {
@@ -67,14 +147,71 @@ impl TimelineModule {
             transition_popup_just_opened: false,
             vol_popup: None,
             vol_popup_just_opened: false,
-            hotkeys_open: false,
-            hotkeys_just_opened: false,
             last_scrub_emitted_time: f64::NEG_INFINITY,
             filter_popup: None,
             filter_popup_just_opened: false,
             drag_target: None,
+            drag_refused: None,
+            view_scroll: (0.0, 0.0),
+            overview_grab: None,
+            freeze_secs: 2.0,
+            slate_secs: 2.0,
+            slate_color: [0, 0, 0],
+            momentary_from: None,
+            bypass_held: false,
+            scene_threshold: 0.35,
+            silence_db: -45.0,
+            silence_gap: 0.25,
+            duck: Duck::default(),
+            mixdown: None,
+            delete_confirm: None,
+            track_height: None,
+            beat_taps: Vec::new(),
+            follow_last_time: 0.0,
+            sync_adjust: None,
+            snap_flash: None,
+            move_origin: None,
+            drag_overlap: None,
+            crossfade_offer: None,
         }
     }
+
+    /// Draw a snap line at timeline time `t` (screen x `x`) from `top` to
+    /// `bottom`. A snap that has just engaged flashes a wide halo that fades
+    /// over `SNAP_FLASH_SECS`, so the catch reads even with the eye on the clip.
+    #[allow(clippy::too_many_arguments)]
+    fn paint_snap_line(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        t: f64,
+        x: f32,
+        top: f32,
+        bottom: f32,
+        color: Color32,
+    ) {
+        let now = ctx.input(|i| i.time);
+        let since = match self.snap_flash {
+            Some((at, since)) if (at - t).abs() < 1e-9 => since,
+            _ => {
+                self.snap_flash = Some((t, now));
+                now
+            }
+        };
+        let fade = 1.0 - ((now - since) / SNAP_FLASH_SECS) as f32;
+        if fade > 0.0 {
+            painter.line_segment([Pos2::new(x, top), Pos2::new(x, bottom)],
+                Stroke::new(2.0 + 6.0 * fade, color.gamma_multiply(0.35 * fade)));
+            ctx.request_repaint();
+        }
+        painter.line_segment([Pos2::new(x, top), Pos2::new(x, bottom)], Stroke::new(1.5_f32, color));
+    }
+}
+
+/// Snap reach in seconds for a drag: `SNAP_PX` at the current zoom, or zero
+/// while Alt is held so the drag positions freely.
+fn snap_tolerance(ui: &Ui, zoom: f32) -> f64 {
+    if ui.input(|i| i.modifiers.alt) { 0.0 } else { SNAP_PX / zoom as f64 }
 }
 
 // ── Small styling helpers ──────────────────────────────────────────────────────
@@ -113,6 +250,10 @@ impl EditorModule for TimelineModule {
         thumb_cache: &mut ThumbnailCache,
         cmd: &mut Vec<EditorCommand>,
     ) {
+        // A released drag forgets its snap, so the next drag flashes afresh.
+        if !ui.input(|i| i.pointer.any_down()) {
+            self.snap_flash = None;
+        }
         // Auto-clear save status after 3 seconds (pure UI memory, no state mutation)
         if state.save_status.is_some() {
             let t = ui.input(|i| i.time);
@@ -130,39 +271,177 @@ impl EditorModule for TimelineModule {
         }
 
         // ── Keyboard shortcuts (only when no popup is open) ───────────────────
-        if self.transition_popup.is_none() {
+        // Skipped while a text field (clip note, metadata, zoom, BPM) has
+        // focus: egui doesn't consume keys there, so typing "f" or "m" would
+        // also edit the timeline, and Tab must stay free to move focus.
+        let text_focused = ui.ctx().memory(|m| m.focused().is_some());
+        if self.transition_popup.is_none() && !shortcuts::is_open(ui.ctx()) && !text_focused {
             if ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace))
             {
                 if let Some(id) = state.selected_timeline_clip {
-                    cmd.push(EditorCommand::PushUndoSnapshot);
-                    cmd.push(EditorCommand::DeleteTimelineClip(id));
+                    self.request_delete(state, id, cmd);
                 }
             }
-            if ui.input(|i| i.key_pressed(egui::Key::Space)) {
-                if state.is_playing {
-                    cmd.push(EditorCommand::Pause);
-                } else {
-                    cmd.push(EditorCommand::Play);
+            match state.playback_prefs.space {
+                SpaceBehavior::Toggle => {
+                    if ui.input(|i| i.key_pressed(egui::Key::Space)) {
+                        if state.is_playing {
+                            cmd.push(EditorCommand::Pause);
+                        } else {
+                            cmd.push(EditorCommand::Play);
+                        }
+                    }
+                }
+                SpaceBehavior::Momentary => {
+                    if let Some(from) = self.momentary_from {
+                        // Polled with key_down rather than key_released so a
+                        // release lost to a focus change still stops playback.
+                        let (held, review) = ui.input(|i| (i.key_down(egui::Key::Space), i.modifiers.shift));
+                        if !held {
+                            self.momentary_from = None;
+                            cmd.push(EditorCommand::Pause);
+                            if review {
+                                cmd.push(EditorCommand::SetPlayhead(from));
+                            }
+                        }
+                    } else if ui.input(|i| i.key_pressed(egui::Key::Space)) {
+                        if state.is_playing {
+                            // Playing from the transport button — a tap stops it.
+                            cmd.push(EditorCommand::Pause);
+                        } else {
+                            self.momentary_from = Some(state.current_time);
+                            cmd.push(EditorCommand::Play);
+                        }
+                    }
                 }
             }
-            if ui.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
-                cmd.push(EditorCommand::Pause);
-                cmd.push(EditorCommand::SetPlayhead(
-                    (state.current_time - 1.0 / 30.0).max(0.0),
-                ));
+            // / — play around the playhead: pre-roll in, post-roll out, then
+            // back. Shift+/ is the `?` that opens the cheat-sheet.
+            if ui.input(|i| !i.modifiers.shift && i.key_pressed(egui::Key::Slash))
+                && state.total_duration() > 0.0
+            {
+                cmd.push(EditorCommand::PlayAround(state.current_time));
+            }
+            // Sync fine-tune takes ←/→ over: nudge the clip 10 ms (Shift:
+            // 1 ms). Ends on Escape or once the clip has no reference left.
+            let adjusting = self.sync_adjust
+                .and_then(|id| clip_query::timeline_clip(state, id))
+                .filter(|c| clip_query::sync_reference(state, c).is_some());
+            if adjusting.is_none() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                self.sync_adjust = None;
             }
-            if ui.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
-                let total = state.total_duration();
-                cmd.push(EditorCommand::Pause);
-                cmd.push(EditorCommand::SetPlayhead(
-                    (state.current_time + 1.0 / 30.0).min(total.max(0.0)),
-                ));
+            if let Some(clip) = adjusting {
+                let nudge = ui.input(|i| {
+                    let step = if i.modifiers.shift { 0.001 } else { 0.010 };
+                    if i.key_pressed(egui::Key::ArrowLeft) { -step }
+                    else if i.key_pressed(egui::Key::ArrowRight) { step }
+                    else { 0.0 }
+                });
+                let to = clip.start_time + nudge;
+                if nudge != 0.0 && state.move_is_clear(clip.id, to) {
+                    cmd.push(EditorCommand::PushUndoSnapshot("Nudge sync"));
+                    cmd.push(EditorCommand::MoveTimelineClip { id: clip.id, new_start: to, new_row: clip.track_row });
+                }
+            } else {
+                if ui.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
+                    cmd.push(EditorCommand::Pause);
+                    cmd.push(EditorCommand::SetPlayhead(
+                        (state.current_time - state.frame_secs()).max(0.0),
+                    ));
+                }
+                if ui.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
+                    let total = state.total_duration();
+                    cmd.push(EditorCommand::Pause);
+                    cmd.push(EditorCommand::SetPlayhead(
+                        (state.current_time + state.frame_secs()).min(total.max(0.0)),
+                    ));
+                }
+            }
+            // Tab / Shift+Tab — select the next / previous clip by start time
+            // and park the playhead on it. Ctrl stays on the selected track.
+            if let Some((back, same_track)) = ui.input(|i| {
+                i.key_pressed(egui::Key::Tab).then_some((i.modifiers.shift, i.modifiers.ctrl))
+            }) {
+                if let Some(next) =
+                    clip_query::neighbor_clip(state, state.selected_timeline_clip, !back, same_track)
+                {
+                    cmd.push(EditorCommand::Pause);
+                    cmd.push(EditorCommand::SelectTimelineClip(Some(next.id)));
+                    cmd.push(EditorCommand::SetPlayhead(next.start_time));
+                }
             }
-            // S — split clip at playhead
+            // S — split clip at playhead. A blocked split still goes through
+            // so app.rs can report why; it just isn't an undo step.
             if ui.input(|i| i.key_pressed(egui::Key::S)) {
-                cmd.push(EditorCommand::PushUndoSnapshot);
+                if state.splittable_clip_at(state.current_time).is_some() {
+                    cmd.push(EditorCommand::PushUndoSnapshot("Split clip"));
+                }
                 cmd.push(EditorCommand::SplitClipAt(state.current_time));
             }
+            // Shift+F — match frame: select the source of the clip under the
+            // playhead in the library, at the matching source time
+            if ui.input(|i| !i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::F)) {
+                cmd.push(EditorCommand::MatchFrame(None));
+            }
+            // F — freeze the frame under the playhead for `freeze_secs`
+            if ui.input(|i| !i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::F)) {
+                cmd.push(EditorCommand::PushUndoSnapshot("Freeze frame"));
+                cmd.push(EditorCommand::InsertFreezeFrame {
+                    at: state.current_time,
+                    duration: self.freeze_secs,
+                });
+            }
+            // B — bring in the selected library clip: append it to the end of
+            // its track (V1 / A1). Shift+B ripple-inserts it at the playhead.
+            if ui.input(|i| !i.modifiers.ctrl && i.key_pressed(egui::Key::B)) {
+                if let Some(lib) = state
+                    .selected_library_clip
+                    .and_then(|id| state.library.iter().find(|c| c.id == id))
+                {
+                    let row = lib.clip_type.legal_row(0);
+                    cmd.push(EditorCommand::PushUndoSnapshot("Add clip"));
+                    if ui.input(|i| i.modifiers.shift) {
+                        cmd.push(EditorCommand::RippleInsertToTimeline {
+                            media_id: lib.id,
+                            at_time: state.current_time,
+                            track_row: row,
+                            part: DropPart::Both,
+                        });
+                    } else {
+                        cmd.push(EditorCommand::AddToTimeline {
+                            media_id: lib.id,
+                            at_time: state.track_end(row),
+                            track_row: row,
+                            part: DropPart::Both,
+                        });
+                    }
+                }
+            }
+            // \ (hold) — preview without clip filters, for before/after.
+            // Polled with key_down like hold-to-play so a release lost to a
+            // focus change still turns them back on.
+            let bypass_down = ui.input(|i| i.key_down(egui::Key::Backslash));
+            if bypass_down != self.bypass_held {
+                self.bypass_held = bypass_down;
+                cmd.push(EditorCommand::SetEffectsBypass(bypass_down));
+            }
+            // E — disable / re-enable the selected clip (and its group)
+            if ui.input(|i| !i.modifiers.ctrl && i.key_pressed(egui::Key::E)) {
+                if let Some(clip) = clip_query::selected_timeline_clip(state) {
+                    let enabled = !clip.enabled;
+                    cmd.push(EditorCommand::PushUndoSnapshot(if enabled { "Enable clip" } else { "Disable clip" }));
+                    cmd.push(EditorCommand::SetClipEnabled { id: clip.id, enabled });
+                }
+            }
+            // M — drop a ruler marker at the playhead
+            if ui.input(|i| !i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::M)) {
+                cmd.push(EditorCommand::PushUndoSnapshot("Add marker"));
+                cmd.push(EditorCommand::AddMarker(state.current_time));
+            }
+            // Shift+M — master mute, same as the transport speaker button
+            if ui.input(|i| !i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::M)) {
+                cmd.push(EditorCommand::ToggleMute);
+            }
             // Ctrl+Z — Undo
             if ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Z)) {
                 cmd.push(EditorCommand::Undo);
@@ -175,6 +454,11 @@ impl EditorModule for TimelineModule {
             }) {
                 cmd.push(EditorCommand::Redo);
             }
+        } else if self.bypass_held {
+            // Focus moved into a text field mid-hold; the release would never
+            // be seen, so turn the clip filters back on now.
+            self.bypass_held = false;
+            cmd.push(EditorCommand::SetEffectsBypass(false));
         }
 
         ui.vertical(|ui| {
@@ -203,8 +487,7 @@ impl EditorModule for TimelineModule {
                                 tool_btn("🗑"),
                             ).clicked() {
                                 if let Some(id) = state.selected_timeline_clip {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
-                                    cmd.push(EditorCommand::DeleteTimelineClip(id));
+                                    self.request_delete(state, id, cmd);
                                 }
                             }
                         });
@@ -250,7 +533,7 @@ impl EditorModule for TimelineModule {
                             {
                                 if let Some(tc) = clip_query::selected_timeline_clip(state) {
                                     if let Some(lib) = clip_query::library_entry_for(state, tc) {
-                                        let ts = (tc.source_offset + tc.duration - 1.0 / 30.0).max(0.0);
+                                        let ts = tc.source_time((tc.duration - state.frame_secs()).max(0.0));
                                         cmd.push(EditorCommand::RequestSaveFramePicker {
                                             path: lib.path.clone(),
                                             timestamp: ts,
@@ -280,7 +563,7 @@ impl EditorModule for TimelineModule {
                                 .clicked()
                             {
                                 if let Some(id) = state.selected_timeline_clip {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Extract audio"));
                                     cmd.push(EditorCommand::ExtractAudioTrack(id));
                                 }
                             }
@@ -289,15 +572,24 @@ impl EditorModule for TimelineModule {
                             let can_undo = state.undo_len > 0;
                             let can_redo = state.redo_len > 0;
 
+                            let undo_tip = match &state.undo_label {
+                                Some(label) => format!("Undo {label}  [Ctrl+Z]"),
+                                None => "Undo  [Ctrl+Z]".to_string(),
+                            };
+                            let redo_tip = match &state.redo_label {
+                                Some(label) => format!("Redo {label}  [Ctrl+Y]"),
+                                None => "Redo  [Ctrl+Y]".to_string(),
+                            };
+
                             if ui.add_enabled(can_undo, tool_btn("↩"))
-                                .on_hover_text("Undo  [Ctrl+Z]")
+                                .on_hover_text(undo_tip)
                                 .on_disabled_hover_text("Nothing to undo")
                                 .clicked()
                             {
                                 cmd.push(EditorCommand::Undo);
                             }
                             if ui.add_enabled(can_redo, tool_btn("↪"))
-                                .on_hover_text("Redo  [Ctrl+Y]")
+                                .on_hover_text(redo_tip)
                                 .on_disabled_hover_text("Nothing to redo")
                                 .clicked()
                             {
@@ -308,59 +600,171 @@ impl EditorModule for TimelineModule {
                         // ── Split — accented, enabled when playhead is over a
                         //    splittable clip with > 2 frames on each side. ─────
                         {
-                            let min_dur = 2.0 / 30.0;
-                            let can_split = state.timeline.iter().any(|c| {
-                                state.current_time > c.start_time + min_dur
-                                    && state.current_time < c.start_time + c.duration - min_dur
-                            });
+                            let blocker = state.split_blocker(state.current_time);
                             ui.group(|ui| {
-                                if ui.add_enabled(can_split, action_btn("✂"))
+                                if ui.add_enabled(blocker.is_none(), action_btn("✂"))
                                     .on_hover_text("Split clip at playhead  [S]")
+                                    .on_disabled_hover_text(blocker.as_deref().unwrap_or_default())
                                     .clicked()
                                 {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Split clip"));
                                     cmd.push(EditorCommand::SplitClipAt(state.current_time));
                                 }
                             });
                         }
 
+                        // ── Freeze frame — hold the video frame under the
+                        //    playhead for the chosen number of seconds. ────────
+                        {
+                            let can_freeze = state.timeline.iter().any(|c| {
+                                c.track_row % 2 == 0
+                                    && state.current_time >= c.start_time
+                                    && state.current_time < c.start_time + c.duration
+                            });
+                            ui.group(|ui| {
+                                if ui.add_enabled(can_freeze, tool_btn("❄"))
+                                    .on_hover_text("Freeze frame at playhead  [F]")
+                                    .on_disabled_hover_text("Move the playhead over a video clip")
+                                    .clicked()
+                                {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Freeze frame"));
+                                    cmd.push(EditorCommand::InsertFreezeFrame {
+                                        at: state.current_time,
+                                        duration: self.freeze_secs,
+                                    });
+                                }
+                                ui.add(egui::DragValue::new(&mut self.freeze_secs)
+                                    .range(0.1..=60.0)
+                                    .speed(0.1)
+                                    .suffix(" s"))
+                                    .on_hover_text("Freeze length");
+                            });
+                        }
+
+                        // ── Slate — ripple-insert a solid-color clip on V1 at
+                        //    the playhead. ───────────────────────────────────
+                        ui.group(|ui| {
+                            if ui.add(tool_btn("▬"))
+                                .on_hover_text("Insert color slate at playhead")
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Insert slate"));
+                                cmd.push(EditorCommand::InsertSlate {
+                                    at: state.current_time,
+                                    duration: self.slate_secs,
+                                    color: self.slate_color,
+                                });
+                            }
+                            ui.color_edit_button_srgb(&mut self.slate_color)
+                                .on_hover_text("Slate color");
+                            ui.add(egui::DragValue::new(&mut self.slate_secs)
+                                .range(0.1..=60.0)
+                                .speed(0.1)
+                                .suffix(" s"))
+                                .on_hover_text("Slate length");
+                        });
+
                         // ── Hotkey reference ──────────────────────────────────
+                        // Opens the global cheat-sheet (also F1 / ?).
                         ui.group(|ui| {
+                            let sheet_open = shortcuts::is_open(ui.ctx());
                             let btn = egui::Button::new(
                                     egui::RichText::new("?")
                                         .size(13.0)
-                                        .color(if self.hotkeys_open {
+                                        .color(if sheet_open {
                                             ACCENT
                                         } else {
                                             egui::Color32::from_gray(175)
                                         }),
                                 )
-                                .fill(if self.hotkeys_open {
+                                .fill(if sheet_open {
                                     egui::Color32::from_rgba_unmultiplied(80, 50, 10, 60)
                                 } else {
                                     egui::Color32::TRANSPARENT
                                 })
                                 .min_size(egui::vec2(0.0, 26.0));
                             if ui.add(btn)
-                                .on_hover_text("Keyboard shortcuts reference")
+                                .on_hover_text("Keyboard shortcuts reference (F1 / ?)")
                                 .clicked()
                             {
-                                self.hotkeys_open = !self.hotkeys_open;
-                                if self.hotkeys_open {
-                                    self.hotkeys_just_opened = true;
-                                }
+                                shortcuts::toggle(ui.ctx());
                             }
+                            // Panel arrangement lives here because the timeline
+                            // is the one panel that can't be hidden.
+                            ui.menu_button(egui::RichText::new("▦").size(13.0), |ui| {
+                                let mut layout = state.layout_prefs;
+                                ui.checkbox(&mut layout.swap_sides, "Swap side panels")
+                                    .on_hover_text("Library on the right, export on the left");
+                                ui.checkbox(&mut layout.show_library, "Show library");
+                                ui.checkbox(&mut layout.show_export, "Show export panel");
+                                ui.checkbox(&mut layout.always_on_top, "Keep window on top")
+                                    .on_hover_text("Stay above other windows while reviewing against reference material or notes");
+                                ui.separator();
+                                ui.checkbox(&mut layout.show_thumbnails, "Clip thumbnails")
+                                    .on_hover_text("Off: plain clip bodies with just the waveform");
+                                ui.checkbox(&mut layout.show_durations, "Clip durations");
+                                ui.add(egui::Slider::new(&mut layout.waveform_opacity, 0..=100)
+                                    .suffix(" %")
+                                    .text("Waveforms"));
+                                ui.add(egui::Slider::new(&mut layout.clip_radius, CLIP_RADIUS_RANGE)
+                                    .suffix(" px")
+                                    .text("Clip corners"));
+                                ui.separator();
+                                let mut auto = self.track_height.is_none();
+                                if ui.checkbox(&mut auto, "Fit tracks to panel").changed() {
+                                    self.track_height = if auto { None } else { Some(54.0) };
+                                }
+                                if let Some(h) = self.track_height.as_mut() {
+                                    ui.add(egui::Slider::new(h, TRACK_H_MIN..=TRACK_H_MAX)
+                                        .suffix(" px")
+                                        .text("Track height"))
+                                        .on_hover_text("Also Ctrl+wheel over the tracks");
+                                }
+                                if layout != state.layout_prefs {
+                                    cmd.push(EditorCommand::SetLayoutPrefs(layout));
+                                }
+                            })
+                            .response
+                            .on_hover_text("Panel layout and clip appearance");
+                            self.beat_grid_menu(ui, state, cmd);
                         });
 
                         // ── Right side: zoom + status ─────────────────────────
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             if ui.add(tool_btn("🔍+")).clicked() {
-                                cmd.push(EditorCommand::SetTimelineZoom((state.timeline_zoom * 1.25).min(500.0)));
+                                cmd.push(EditorCommand::SetTimelineZoom((state.timeline_zoom * 1.25).min(ZOOM_MAX)));
                             }
                             if ui.add(tool_btn("🔍-")).clicked() {
-                                cmd.push(EditorCommand::SetTimelineZoom((state.timeline_zoom * 0.8).max(10.0)));
+                                cmd.push(EditorCommand::SetTimelineZoom((state.timeline_zoom * 0.8).max(ZOOM_MIN)));
+                            }
+                            // Exact zoom: drag or double-click to type a px/s value.
+                            let mut zoom = state.timeline_zoom;
+                            if ui.add(egui::DragValue::new(&mut zoom)
+                                    .range(ZOOM_MIN..=ZOOM_MAX)
+                                    .speed(1.0)
+                                    .max_decimals(1)
+                                    .suffix(" px/s"))
+                                .on_hover_text("Timeline zoom — drag, or double-click to type")
+                                .changed()
+                            {
+                                cmd.push(EditorCommand::SetTimelineZoom(zoom));
+                            }
+                            // Span presets, right-to-left so they read 1s · 10s · Full.
+                            if ui.add(tool_btn("Full"))
+                                .on_hover_text("Fit the whole project in view")
+                                .clicked()
+                            {
+                                self.zoom_to_span(state, state.total_duration().max(1.0), 0.0, cmd);
+                            }
+                            for secs in [10.0, 1.0] {
+                                if ui.add(tool_btn(format!("{secs:.0}s")))
+                                    .on_hover_text(format!("Show {secs:.0} s around the playhead"))
+                                    .clicked()
+                                {
+                                    let from = (state.current_time - secs * 0.5).max(0.0);
+                                    self.zoom_to_span(state, secs, from, cmd);
+                                }
                             }
-                            ui.label(format!("Zoom: {:.0}px/s", state.timeline_zoom));
                             ui.separator();
                             if let Some(msg) = &state.save_status {
                                 ui.label(
@@ -369,7 +773,7 @@ impl EditorModule for TimelineModule {
                                 );
                             } else {
                                 ui.label(
-                                    egui::RichText::new("Space=Play  Del=Remove  ⬅➡=Frame  S=Split  Ctrl+Z=Undo")
+                                    egui::RichText::new("Space=Play  Del=Remove  ⬅➡=Frame  S=Split  Ctrl+Z=Undo  F1=Shortcuts")
                                         .size(9.0).color(egui::Color32::from_gray(80))
                                 );
                             }
@@ -377,106 +781,6 @@ impl EditorModule for TimelineModule {
                     });
                 });
 
-            // ── Hotkey reference popup ────────────────────────────────────────
-            // Floats above the timeline panel, anchored near the bottom-left of
-            // the toolbar. Closed by clicking ? again or clicking outside.
-            if self.hotkeys_open {
-                let anchor    = ui.next_widget_position();
-                let popup_w   = 360.0_f32;
-                let popup_h   = 420.0_f32;
-                let popup_pos = egui::pos2(anchor.x + 6.0, anchor.y - popup_h - 40.0);
-
-                let area_resp = egui::Area::new(egui::Id::new("hotkeys_popup"))
-                    .fixed_pos(popup_pos)
-                    .order(egui::Order::Tooltip)
-                    .interactable(true)
-                    .show(ui.ctx(), |ui| {
-                        egui::Frame::new()
-                            .fill(egui::Color32::from_rgb(18, 18, 26))
-                            .stroke(egui::Stroke::new(
-                                1.0_f32,
-                                egui::Color32::from_rgba_unmultiplied(255, 160, 50, 90),
-                            ))
-                            .corner_radius(egui::CornerRadius::same(8))
-                            .inner_margin(egui::Margin::same(14))
-                            .shadow(egui::Shadow {
-                                offset: [0, 6],
-                                blur: 18,
-                                spread: 0,
-                                color: egui::Color32::from_black_alpha(150),
-                            })
-                            .show(ui, |ui| {
-                                ui.set_min_width(popup_w - 28.0);
-                                ui.set_max_width(popup_w - 28.0);
-
-                                // Title row
-                                ui.horizontal(|ui| {
-                                    ui.label(egui::RichText::new("⌨  Keyboard Shortcuts")
-                                        .size(12.5).strong().color(ACCENT));
-                                    ui.with_layout(
-                                        egui::Layout::right_to_left(egui::Align::Center),
-                                        |ui| {
-                                            if ui.small_button(
-                                                egui::RichText::new("🗙")
-                                                    .size(10.0)
-                                                    .color(egui::Color32::from_gray(130)),
-                                            ).clicked() {
-                                                self.hotkeys_open = false;
-                                            }
-                                        },
-                                    );
-                                });
-                                ui.add_space(4.0);
-                                ui.separator();
-                                ui.add_space(4.0);
-
-                                hotkey_section(ui, "Playback", &[
-                                    ("Space",            "Play / Pause"),
-                                    ("← →",             "Step one frame"),
-                                    ("⏹  Stop button",  "Stop & return to start"),
-                                ]);
-                                hotkey_section(ui, "Timeline", &[
-                                    ("S",                "Split clip at playhead"),
-                                    ("Del / Backspace",  "Remove selected clip"),
-                                    ("Ctrl + Z",         "Undo"),
-                                    ("Ctrl + Y",         "Redo"),
-                                    ("Ctrl + Shift + Z", "Redo (alternate)"),
-                                ]);
-                                hotkey_section(ui, "Media Library", &[
-                                    ("Del / Backspace",  "Remove selected clip(s)"),
-                                    ("Ctrl + A",         "Select all"),
-                                    ("Ctrl + Click",     "Toggle clip in selection"),
-                                    ("Shift + Click",    "Range select"),
-                                    ("Escape",           "Clear selection"),
-                                ]);
-                                hotkey_section(ui, "Timeline Clips", &[
-                                    ("Click",            "Select clip"),
-                                    ("Drag body",        "Move clip"),
-                                    ("Drag left edge",   "Trim clip start"),
-                                    ("Drag right edge",  "Trim clip end"),
-                                    ("Right-click",      "Clip context menu"),
-                                    ("🔊 badge",         "Adjust clip volume"),
-                                    ("✂ badge",          "Set transition type"),
-                                ]);
-                                hotkey_section(ui, "Zoom", &[
-                                    ("🔍+  /  🔍−",     "Zoom timeline in / out"),
-                                ]);
-                            });
-                    });
-
-                // Click outside → close (same guard as transition_popup / vol_popup)
-                if !self.hotkeys_just_opened
-                    && ui.input(|i| {
-                        i.pointer.any_click()
-                            && i.pointer.interact_pos()
-                                .map(|p| !area_resp.response.rect.contains(p))
-                                .unwrap_or(false)
-                    }) {
-                    self.hotkeys_open = false;
-                }
-                self.hotkeys_just_opened = false;
-            }
-
             ui.separator();
 
             // ── Adaptive track sizing ─────────────────────────────────────────
@@ -484,15 +788,30 @@ impl EditorModule for TimelineModule {
             // driven with exact_height every frame, so available_height() here is
             // always a real, stable value — immune to the minimize/restore shrink
             // bug. Track heights scale between [28, 54] px to fill the space as
-            // the user resizes the panel via the drag handle at the top.
+            // the user resizes the panel via the drag handle at the top, unless
+            // a fixed height was picked (▦ menu / Ctrl+wheel) — then the
+            // ScrollArea scrolls vertically when the tracks overflow.
             let header_height = 28.0_f32;
-            let label_col     = 48.0_f32;  // fixed track-label column width
+            let label_col     = LABEL_COL;
             let track_gap     = 4.0_f32;
             let num_tracks    = 4_usize;
+
+            // ── Overview strip ────────────────────────────────────────────────
+            // Allocated before the track sizing below so available_height()
+            // already excludes it.
+            self.overview_strip(ui, state, label_col, cmd);
+
             let track_height  = {
                 let avail = ui.available_height();
                 let natural = (avail - header_height) / num_tracks as f32 - track_gap;
-                natural.clamp(28.0, 54.0)
+                let fitted = self.track_height.unwrap_or(natural.clamp(28.0, 54.0));
+                // Ctrl+wheel over the tracks switches to (and scales) a fixed
+                // height; egui reports it as a zoom delta.
+                let zoom = ui.input(|i| i.zoom_delta());
+                if zoom != 1.0 && ui.rect_contains_pointer(ui.available_rect_before_wrap()) {
+                    self.track_height = Some((fitted * zoom).clamp(TRACK_H_MIN, TRACK_H_MAX));
+                }
+                self.track_height.unwrap_or(fitted)
             };
 
             // Floor for the ScrollArea: never measure smaller than all 4 tracks at
@@ -500,11 +819,23 @@ impl EditorModule for TimelineModule {
             // outer scroll frame to content size when the timeline is empty.
             let min_content_h = header_height + (28.0 + track_gap) * num_tracks as f32;
 
-            egui::ScrollArea::both()
+            let mut scroll_area = egui::ScrollArea::both()
                 .id_salt("timeline_scroll")
                 .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::VisibleWhenNeeded)
                 .auto_shrink([false, false])
-                .min_scrolled_height(min_content_h)
+                .min_scrolled_height(min_content_h);
+            // One-shot scroll request from the overview strip. Content x = 0 sits
+            // under the pinned label column, so offset = t * zoom puts `t` at the
+            // first visible lane pixel.
+            if let Some(t) = state.timeline_scroll_to {
+                scroll_area = scroll_area.horizontal_scroll_offset(t as f32 * state.timeline_zoom);
+            } else if let Some(x) = self.auto_scroll_offset(ui, state) {
+                scroll_area = scroll_area.horizontal_scroll_offset(x);
+            }
+            // Join badge the pending crossfade offer hangs from, found while
+            // the badges are laid out.
+            let mut offer_anchor: Option<Pos2> = None;
+            let scroll_out = scroll_area
                 .show(ui, |ui: &mut egui::Ui| {
 
                     let max_time = state.total_duration().max(60.0);
@@ -566,21 +897,59 @@ impl EditorModule for TimelineModule {
                             Stroke::new(1.0_f32, Color32::from_gray(60)));
                         if is_major {
                             painter.text(Pos2::new(x + 3.0, rect.min.y + 4.0),
-                                Align2::LEFT_TOP, format_time(s), FontId::monospace(10.0),
+                                Align2::LEFT_TOP, format_time(s, state.project_fps), FontId::monospace(10.0),
                                 Color32::from_gray(140));
                         }
                         s += step;
                     }
-                    let snap_secs = 8.0_f64 / state.timeline_zoom as f64;
+                    // Beat grid — faint line per beat through ruler and lanes,
+                    // brighter on each bar's downbeat. Beats closer than 6 px
+                    // are dropped so only bars remain when zoomed out.
+                    if let Some(grid) = state.beat_grid {
+                        let visible = ui.clip_rect();
+                        let from = ((visible.min.x - time_origin_x) / state.timeline_zoom) as f64;
+                        let to = ((visible.max.x - time_origin_x) / state.timeline_zoom) as f64;
+                        let beat_px = grid.beat_secs() as f32 * state.timeline_zoom;
+                        let bar_px = beat_px * grid.beats_per_bar as f32;
+                        if bar_px >= 6.0 {
+                            for (t, downbeat) in grid.beats_in(from, to) {
+                                if !downbeat && beat_px < 6.0 {
+                                    continue;
+                                }
+                                let x = time_origin_x + (t as f32 * state.timeline_zoom);
+                                let alpha = if downbeat { 0.45 } else { 0.15 };
+                                painter.line_segment(
+                                    [Pos2::new(x, rect.min.y + header_height * 0.6), Pos2::new(x, rect.max.y)],
+                                    Stroke::new(1.0_f32, BEAT.gamma_multiply(alpha)));
+                            }
+                        }
+                    }
+                    // Markers — flag in the ruler, faint guide through the lanes
+                    for m in &state.markers {
+                        let x = time_origin_x + (m.time as f32 * state.timeline_zoom);
+                        painter.line_segment(
+                            [Pos2::new(x, rect.min.y + header_height), Pos2::new(x, rect.max.y)],
+                            Stroke::new(1.0_f32, MARKER.gamma_multiply(0.35)));
+                        painter.add(egui::Shape::convex_polygon(
+                            vec![Pos2::new(x - 5.0, rect.min.y + header_height - 10.0),
+                                 Pos2::new(x + 5.0, rect.min.y + header_height - 10.0),
+                                 Pos2::new(x, rect.min.y + header_height)],
+                            MARKER, Stroke::NONE));
+                    }
+                    let snap_secs = snap_tolerance(ui, state.timeline_zoom);
                     let video_clip_ends: Vec<f64> = state.timeline.iter()
                         .filter(|c| c.track_row % 2 == 0)
-                        .map(|c| c.start_time + c.duration - (1.0 / 30.0))
+                        .map(|c| c.start_time + c.duration - state.frame_secs())
                         .collect();
+                    // Markers and beats win over clip ends so scrubbing lands
+                    // exactly on a cue.
                     let snap_to_video_end = |t: f64| -> f64 {
-                        video_clip_ends.iter()
-                            .copied()
-                            .find(|&e| (t - e).abs() < snap_secs)
-                            .unwrap_or(t)
+                        state.snap_point_near(t, snap_secs).unwrap_or_else(|| {
+                            video_clip_ends.iter()
+                                .copied()
+                                .find(|&e| (t - e).abs() < snap_secs)
+                                .unwrap_or(t)
+                        })
                     };
                     // Ruler click/drag → seek
                     let ruler_rect = Rect::from_min_size(Pos2::new(time_origin_x, rect.min.y), egui::vec2(fill_w, header_height));
@@ -594,7 +963,7 @@ impl EditorModule for TimelineModule {
                                 cmd.push(EditorCommand::Pause);
                                 cmd.push(EditorCommand::SetPlayhead(snapped));
                                 self.last_scrub_emitted_time = snapped;
-                            } else if (t_clamped - self.last_scrub_emitted_time).abs() >= 1.0 / 30.0 {
+                            } else if (t_clamped - self.last_scrub_emitted_time).abs() >= state.frame_secs() {
                                 let snapped = snap_to_video_end(t_clamped);
                                 cmd.push(EditorCommand::SetPlayhead(snapped));
                                 self.last_scrub_emitted_time = snapped;
@@ -605,21 +974,81 @@ impl EditorModule for TimelineModule {
                         ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                     }
 
-                    // DnD drop zone
-                    let payload: Option<Uuid> = ui.memory(|m| m.data.get_temp(Id::new("DND_PAYLOAD")));
+                    // Marker drag — interacted after the ruler so the flag wins the
+                    // hit test. The dragged marker snaps to clip edges and the playhead.
+                    for m in &state.markers {
+                        let x = time_origin_x + (m.time as f32 * state.timeline_zoom);
+                        let flag_rect = Rect::from_min_max(
+                            Pos2::new(x - 6.0, rect.min.y + header_height - 12.0),
+                            Pos2::new(x + 6.0, rect.min.y + header_height));
+                        let resp = ui.interact(flag_rect, Id::new(("marker", m.id)), Sense::click_and_drag())
+                            .on_hover_text(format!("Marker {}", format_time(m.time, state.project_fps)));
+                        if resp.hovered() || resp.dragged() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+                        }
+                        if resp.drag_started() {
+                            cmd.push(EditorCommand::PushUndoSnapshot("Move marker"));
+                        }
+                        if resp.dragged() {
+                            if let Some(ptr) = resp.interact_pointer_pos() {
+                                let t = ((ptr.x - time_origin_x) / state.timeline_zoom).max(0.0) as f64;
+                                let snapped = std::iter::once(state.current_time)
+                                    .chain(state.timeline.iter()
+                                        .flat_map(|c| [c.start_time, c.start_time + c.duration]))
+                                    .filter(|e| (t - e).abs() < snap_secs)
+                                    .min_by(|a, b| (a - t).abs().total_cmp(&(b - t).abs()));
+                                if let Some(e) = snapped {
+                                    let sx = time_origin_x + (e as f32 * state.timeline_zoom);
+                                    self.paint_snap_line(ui.ctx(), &painter, e, sx, rect.min.y, rect.max.y, MARKER);
+                                } else {
+                                    self.snap_flash = None;
+                                }
+                                cmd.push(EditorCommand::MoveMarker { id: m.id, time: snapped.unwrap_or(t) });
+                            }
+                        }
+                        let marker_id = m.id;
+                        resp.context_menu(|ui: &mut egui::Ui| {
+                            if ui.button("🗑  Delete marker").clicked() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Delete marker"));
+                                cmd.push(EditorCommand::DeleteMarker(marker_id));
+                                ui.close();
+                            }
+                        });
+                    }
+
+                    // DnD drop zone. The payload lists every card being
+                    // dragged, in library order; the first sets the lane.
+                    let payload: Option<Vec<Uuid>> = ui.memory(|m| m.data.get_temp(Id::new("DND_PAYLOAD")));
                     let content_rect = Rect::from_min_max(
                         Pos2::new(time_origin_x, rect.min.y + header_height), rect.max);
 
                     if payload.is_some() && !ui.input(|i| i.pointer.any_down()) {
-                        ui.memory_mut(|mem| mem.data.remove::<Uuid>(Id::new("DND_PAYLOAD")));
+                        ui.memory_mut(|mem| mem.data.remove::<Vec<Uuid>>(Id::new("DND_PAYLOAD")));
                     }
 
-                    if let Some(clip_id) = payload {
+                    if let Some(&clip_id) = payload.as_ref().and_then(|ids| ids.first()) {
+                        let batch = payload.as_ref().map_or(1, |ids| ids.len());
                         // Resolve clip type for track enforcement before any hover logic.
-                        let drag_clip_type = state.library.iter()
+                        let source_type = state.library.iter()
                             .find(|l| l.id == clip_id)
                             .map(|l| l.clip_type)
                             .unwrap_or(ClipType::Video);
+                        // Alt → audio only (lands on an A row), Ctrl → video
+                        // only (muted). Only a combined source can be split.
+                        let drop_part = if source_type == ClipType::Video {
+                            ui.input(|i| {
+                                if i.modifiers.alt {
+                                    DropPart::AudioOnly
+                                } else if i.modifiers.ctrl {
+                                    DropPart::VideoOnly
+                                } else {
+                                    DropPart::Both
+                                }
+                            })
+                        } else {
+                            DropPart::Both
+                        };
+                        let drag_clip_type = drop_part.clip_type(source_type);
 
                         if let Some(hover) = ui.input(|i| i.pointer.hover_pos()) {
                             if content_rect.contains(hover) {
@@ -634,7 +1063,7 @@ impl EditorModule for TimelineModule {
                                 // ── Track enforcement ──────────────────────────
                                 // Video → even rows (V1=0, V2=2); Audio → odd rows (A1=1, A2=3).
                                 let enforced_row = match drag_clip_type {
-                                    ClipType::Video => {
+                                    ClipType::Video | ClipType::Image => {
                                         let r = if raw_row % 2 == 0 { raw_row } else { raw_row.saturating_sub(1) };
                                         r.min(2)
                                     }
@@ -653,6 +1082,13 @@ impl EditorModule for TimelineModule {
                                 if track_end.is_finite() && (snapped - track_end).abs() < 1.0 {
                                     snapped = track_end;
                                 }
+                                // Shift held → insert: later clips on the lane
+                                // ripple right instead of being overlapped. The
+                                // marker moves to where the clip will really land.
+                                let insert_mode = ui.input(|i| i.modifiers.shift);
+                                if insert_mode {
+                                    snapped = state.ripple_insert_point(enforced_row, snapped);
+                                }
 
                                 // Draw snap indicator only within the enforced lane.
                                 let lane_y = rect.min.y + header_height
@@ -671,21 +1107,82 @@ impl EditorModule for TimelineModule {
                                     Stroke::new(1.0_f32, ACCENT.linear_multiply(0.5)),
                                     egui::StrokeKind::Inside
                                 );
-                                painter.line_segment(
-                                    [Pos2::new(line_x, lane_rect.min.y),
-                                     Pos2::new(line_x, lane_rect.max.y)],
-                                    Stroke::new(2.0_f32, if snapping {
-                                        Color32::from_rgb(255, 200, 50)
-                                    } else { ACCENT }));
-
-                                if ui.input(|i| i.pointer.any_released()) {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
-                                    cmd.push(EditorCommand::AddToTimeline {
-                                        media_id:  clip_id,
+                                if insert_mode {
+                                    // Insert marker: heavier line with ▶ heads
+                                    // top and bottom pointing the ripple direction.
+                                    let ins_col = Color32::from_rgb(120, 230, 120);
+                                    painter.line_segment(
+                                        [Pos2::new(line_x, lane_rect.min.y),
+                                         Pos2::new(line_x, lane_rect.max.y)],
+                                        Stroke::new(3.0_f32, ins_col));
+                                    for y in [lane_rect.min.y, lane_rect.max.y - 7.0] {
+                                        painter.add(egui::Shape::convex_polygon(
+                                            vec![Pos2::new(line_x, y),
+                                                 Pos2::new(line_x + 7.0, y + 3.5),
+                                                 Pos2::new(line_x, y + 7.0)],
+                                            ins_col, Stroke::NONE));
+                                    }
+                                    painter.text(
+                                        Pos2::new(line_x + 10.0, lane_rect.center().y),
+                                        Align2::LEFT_CENTER, "INSERT",
+                                        FontId::monospace(9.0), ins_col);
+                                } else if snapping {
+                                    self.paint_snap_line(ui.ctx(), &painter, snapped, line_x,
+                                        lane_rect.min.y, lane_rect.max.y, SNAP_EDGE);
+                                } else {
+                                    self.snap_flash = None;
+                                    painter.line_segment(
+                                        [Pos2::new(line_x, lane_rect.min.y),
+                                         Pos2::new(line_x, lane_rect.max.y)],
+                                        Stroke::new(2.0_f32, ACCENT));
+                                }
+
+                                let part_label = match drop_part {
+                                    DropPart::Both      => None,
+                                    DropPart::VideoOnly => Some("VIDEO ONLY"),
+                                    DropPart::AudioOnly => Some("AUDIO ONLY"),
+                                };
+                                let batch_label = (batch > 1).then(|| format!("{batch} CLIPS"));
+                                let label = match (part_label, &batch_label) {
+                                    (Some(p), Some(b)) => Some(format!("{b} · {p}")),
+                                    (Some(p), None)    => Some(p.to_string()),
+                                    (None, b)          => b.clone(),
+                                };
+                                if let Some(label) = label {
+                                    painter.text(
+                                        Pos2::new(line_x + 10.0, lane_rect.min.y + 4.0),
+                                        Align2::LEFT_TOP, label,
+                                        FontId::monospace(9.0), ACCENT);
+                                }
+
+                                if ui.input(|i| i.pointer.any_released()) && batch > 1 {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Add clips"));
+                                    cmd.push(EditorCommand::AddClipsToTimeline {
+                                        media_ids: payload.clone().unwrap_or_default(),
                                         at_time:   snapped,
                                         track_row: enforced_row,
+                                        part:      drop_part,
+                                        ripple:    insert_mode,
                                     });
-                                    ui.memory_mut(|mem| mem.data.remove::<Uuid>(Id::new("DND_PAYLOAD")));
+                                    ui.memory_mut(|mem| mem.data.remove::<Vec<Uuid>>(Id::new("DND_PAYLOAD")));
+                                } else if ui.input(|i| i.pointer.any_released()) {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Add clip"));
+                                    if insert_mode {
+                                        cmd.push(EditorCommand::RippleInsertToTimeline {
+                                            media_id:  clip_id,
+                                            at_time:   snapped,
+                                            track_row: enforced_row,
+                                            part:      drop_part,
+                                        });
+                                    } else {
+                                        cmd.push(EditorCommand::AddToTimeline {
+                                            media_id:  clip_id,
+                                            at_time:   snapped,
+                                            track_row: enforced_row,
+                                            part:      drop_part,
+                                        });
+                                    }
+                                    ui.memory_mut(|mem| mem.data.remove::<Vec<Uuid>>(Id::new("DND_PAYLOAD")));
                                 }
                             }
                         }
@@ -706,12 +1203,33 @@ impl EditorModule for TimelineModule {
                             Stroke::new(1.0_f32, ACCENT.linear_multiply(0.45)),
                             egui::StrokeKind::Inside);
                     }
+                    // The lane the pointer is actually over, when the clip
+                    // can't go there: the clip stays in the nearest legal lane.
+                    if let Some(row) = self.drag_refused {
+                        let hy = rect.min.y + header_height
+                            + row as f32 * (track_height + track_gap);
+                        let hr = Rect::from_min_size(
+                            Pos2::new(time_origin_x, hy),
+                            egui::vec2(fill_w, track_height),
+                        );
+                        painter.rect_filled(hr, 0.0, CLIP_OVERLAP.linear_multiply(0.12));
+                        painter.text(Pos2::new(time_origin_x + 8.0, hr.center().y), Align2::LEFT_CENTER,
+                            if row % 2 == 0 { "🚫  Audio clips go on A tracks" } else { "🚫  Video clips go on V tracks" },
+                            FontId::proportional(11.0), CLIP_OVERLAP);
+                    }
                     // ── Timeline Clips ─────────────────────────────────────────────
                     let mut to_delete: Option<Uuid> = None;
+                    // Same-track overlaps (older projects, drops onto a clip)
+                    // export wrong, so outline them in red until fixed.
+                    let overlapping = &state.overlaps;
+                    // Appearance prefs from the ▦ menu.
+                    let look = state.layout_prefs;
+                    let radius = look.clip_corner();
 
                     for clip in &state.timeline {
                         let lib        = clip_query::library_entry_for(state, clip);
-                        let media_name = lib.map(|l| l.name.as_str()).unwrap_or("Unknown");
+                        let media_name = if clip.slate.is_some() { "Slate" }
+                            else { lib.map(|l| l.name.as_str()).unwrap_or("Unknown") };
                         let clip_type  = lib.map(|l| l.clip_type).unwrap_or(ClipType::Video);
                         let waveform   = lib.map(|l| l.waveform_peaks.as_slice()).unwrap_or(&[]);
 
@@ -719,8 +1237,11 @@ impl EditorModule for TimelineModule {
                         // track row is an extracted-audio clip.  Give it audio rendering so
                         // it looks like an audio clip (green, waveform-only, no thumbnail).
                         let is_extracted_audio_clip = clip_query::is_extracted_audio_clip(clip);
+                        // Stills draw and drag exactly like video (thumbnail strip, V rows).
                         let render_type = if is_extracted_audio_clip {
                             ClipType::Audio
+                        } else if clip_type == ClipType::Image {
+                            ClipType::Video
                         } else {
                             clip_type
                         };
@@ -735,16 +1256,21 @@ impl EditorModule for TimelineModule {
 
                         let is_selected = state.selected_timeline_clip == Some(clip.id);
                         let body_color  = if is_selected { CLIP_SELECTED }
+                            else if let Some([r, g, b]) = clip.slate { Color32::from_rgb(r, g, b) }
                             else if render_type == ClipType::Audio { CLIP_AUDIO }
                             else { CLIP_VIDEO };
 
-                        painter.rect_filled(clip_rect, 4.0, body_color);
+                        painter.rect_filled(clip_rect, radius, body_color);
 
                         // Thumbnail strip on video clips only — extracted audio clips
                         // sit on audio tracks and should show only waveform, not frames.
-                        if render_type == ClipType::Video && width > 20.0 {
+                        if render_type == ClipType::Video && width > 20.0 && look.show_thumbnails {
                             if let Some(media) = lib {
                                 if let Some(tex) = thumb_cache.get(&media.id) {
+                                    // A poster frame (keyed by the timeline clip id)
+                                    // replaces the first tile only.
+                                    let mut poster = clip.poster_offset
+                                        .and_then(|_| thumb_cache.get(&clip.id));
                                     let tex_aspect = tex.size_vec2().x / tex.size_vec2().y.max(1.0);
                                     let tile_w = (track_height * tex_aspect).min(width);
                                     let mut tx_start = clip_rect.min.x;
@@ -758,8 +1284,11 @@ impl EditorModule for TimelineModule {
                                             Pos2::new(tile_end, clip_rect.max.y));
                                         let tile_uv = egui::Rect::from_min_max(
                                             egui::Pos2::ZERO, egui::Pos2::new(uv_frac, uv.max.y));
-                                        painter.image(tex.id(), tile_rect, tile_uv,
-                                            Color32::from_rgba_unmultiplied(255, 255, 255, 120));
+                                        let (tile_tex, tint) = match poster.take() {
+                                            Some(p) => (p, Color32::from_rgba_unmultiplied(255, 255, 255, 200)),
+                                            None    => (tex, Color32::from_rgba_unmultiplied(255, 255, 255, 120)),
+                                        };
+                                        painter.image(tile_tex.id(), tile_rect, tile_uv, tint);
                                         tx_start += tile_w;
                                         if tile_w <= 0.0 { break; }
                                     }
@@ -767,27 +1296,91 @@ impl EditorModule for TimelineModule {
                             }
                         }
 
+                        // Detected scene cuts — source-time ticks, drawn until the
+                        // clip is split at them or detection runs again.
+                        if let Some((_, cuts)) = state.scene_cuts.as_ref().filter(|(id, _)| *id == clip.id) {
+                            let tick = Stroke::new(1.5_f32, Color32::from_rgb(255, 200, 60));
+                            for &cut in cuts {
+                                let x = start_x + ((cut - clip.source_offset) as f32 * state.timeline_zoom);
+                                if x > clip_rect.min.x && x < clip_rect.max.x {
+                                    painter.line_segment([Pos2::new(x, clip_rect.min.y + 3.0), Pos2::new(x, clip_rect.max.y)], tick);
+                                }
+                            }
+                        }
+                        if state.scene_detecting == Some(clip.id) && width > 60.0 {
+                            painter.text(clip_rect.right_top() + Vec2::new(-4.0, 6.0),
+                                Align2::RIGHT_TOP, "detecting cuts…",
+                                FontId::monospace(9.0),
+                                Color32::from_rgb(255, 200, 60));
+                        }
+
                         // Waveform overlay.
                         // · Video clip with audio extracted (audio_muted=true): hide waveform —
                         //   the audio now lives on the extracted audio clip below.
                         // · Extracted audio clip (is_extracted_audio_clip): always show waveform.
                         // · Regular audio clip: always show waveform.
-                        if !waveform.is_empty() && width > 10.0 && !clip.audio_muted {
-                            draw_waveform(&painter, clip_rect, waveform, render_type, clip.volume, state.volume, clip.fade_in_secs, clip.fade_in_start_secs, clip.fade_out_secs, clip.fade_out_end_secs, clip.duration as f32);
+                        if !waveform.is_empty() && width > 10.0 && !clip.audio_muted && look.waveform_opacity > 0 {
+                            draw_waveform(&painter, clip_rect, waveform, render_type, clip.volume, state.volume, clip.fade_in_secs, clip.fade_in_start_secs, clip.fade_out_secs, clip.fade_out_end_secs, clip.duration as f32, look.waveform_alpha());
                         }
 
-                        // Top stripe
+                        // Top stripe — a user tag replaces the type color, but the
+                        // selection highlight still wins so selection stays obvious.
                         let stripe_color = if is_selected { ACCENT }
+                            else if let Some([r, g, b]) = clip.tag_color { Color32::from_rgb(r, g, b) }
                             else if render_type == ClipType::Audio { Color32::from_rgb(80, 200, 140) }
                             else { Color32::from_rgb(100, 140, 220) };
                         painter.rect_filled(
                             Rect::from_min_size(clip_rect.min, egui::vec2(clip_rect.width(), 3.0)),
-                            egui::CornerRadius { nw: 4, ne: 4, sw: 0, se: 0 }, stripe_color);
+                            egui::CornerRadius { nw: radius, ne: radius, sw: 0, se: 0 }, stripe_color);
+
+                        // Disabled — dim the body and hatch it so it reads as
+                        // "still here, but skipped" at any zoom level.
+                        if !clip.enabled {
+                            painter.rect_filled(clip_rect, radius, Color32::from_black_alpha(150));
+                            let hatch_painter = painter.with_clip_rect(clip_rect.intersect(painter.clip_rect()));
+                            let hatch = Stroke::new(1.0, Color32::from_white_alpha(28));
+                            let h = clip_rect.height();
+                            let mut x = clip_rect.left() - h;
+                            while x < clip_rect.right() {
+                                hatch_painter.line_segment(
+                                    [egui::pos2(x, clip_rect.bottom()), egui::pos2(x + h, clip_rect.top())], hatch);
+                                x += 8.0;
+                            }
+                        }
+
+                        // Sync fine-tune — waveforms at timeline positions, this
+                        // clip's above the center line and the reference's below,
+                        // so a nudge visibly slides one against the other. The
+                        // readout is the offset audio sync would report.
+                        if self.sync_adjust == Some(clip.id) {
+                            if let (Some(lib), Some(r)) = (lib, clip_query::sync_reference(state, clip)) {
+                                if let Some(r_lib) = clip_query::library_entry_for(state, r) {
+                                    draw_sync_overlay(&painter, clip_rect, clip, lib, r, r_lib, state.timeline_zoom);
+                                }
+                                let offset = clip_query::sync_offset(clip, r);
+                                let mut readout = format!("⇆ {:+.0} ms", offset * 1000.0);
+                                if let Some((_, _, Some(matched))) = state.audio_sync
+                                    .filter(|(id, rid, _)| *id == clip.id && *rid == r.id)
+                                {
+                                    readout += &format!("  ·  match {:+.0} ms (Δ {:+.0})",
+                                        matched * 1000.0, (matched - offset) * 1000.0);
+                                }
+                                readout += "  ·  ←/→ 10 ms, Shift 1 ms, Esc done";
+                                let galley = painter.layout_no_wrap(readout, FontId::monospace(9.5), Color32::WHITE);
+                                let text_pos = Pos2::new(clip_rect.min.x.max(painter.clip_rect().min.x) + 4.0,
+                                    clip_rect.max.y - galley.size().y - 3.0);
+                                painter.rect_filled(Rect::from_min_size(text_pos, galley.size()).expand(2.0),
+                                    2.0, Color32::from_black_alpha(190));
+                                painter.galley(text_pos, galley, Color32::WHITE);
+                            }
+                        }
 
                         // Border
-                        painter.rect_stroke(clip_rect, 4,
-                            Stroke::new(if is_selected { 1.5_f32 } else { 1.0_f32 },
-                                if is_selected { ACCENT } else { DARK_BORDER }),
+                        let is_overlapping = overlapping.contains(&clip.id);
+                        painter.rect_stroke(clip_rect, radius,
+                            Stroke::new(if is_selected || is_overlapping { 1.5_f32 } else { 1.0_f32 },
+                                if is_overlapping { CLIP_OVERLAP }
+                                else if is_selected { ACCENT } else { DARK_BORDER }),
                             egui::StrokeKind::Outside);
 
                         // Name label — capped to half the clip width so it never
@@ -796,7 +1389,17 @@ impl EditorModule for TimelineModule {
                         // is the only visual that fits.
                         if width > 30.0 && track_height > 36.0 {
                             let label_font = FontId::proportional(11.0);
-                            let label_text = fit_label(media_name, width * 0.5);
+                            let label_text = if clip.slate.is_some() {
+                                fit_label(&format!("▬ {media_name}"), width * 0.5)
+                            } else if clip_type == ClipType::Image {
+                                fit_label(&format!("▣ {media_name}"), width * 0.5)
+                            } else if clip.freeze_at.is_some() {
+                                fit_label(&format!("❄ {media_name}"), width * 0.5)
+                            } else if clip.group_id.is_some() {
+                                fit_label(&format!("🔗 {media_name}"), width * 0.5)
+                            } else {
+                                fit_label(media_name, width * 0.5)
+                            };
                             let label_pos  = clip_rect.min + Vec2::new(6.0, 8.0);
                             painter.text(label_pos, Align2::LEFT_TOP, label_text,
                                 label_font,
@@ -805,7 +1408,7 @@ impl EditorModule for TimelineModule {
 
 
                         // Duration badge — only at comfortable track heights.
-                        if width > 50.0 && track_height > 36.0 {
+                        if width > 50.0 && track_height > 36.0 && look.show_durations {
                             painter.text(clip_rect.right_bottom() - Vec2::new(4.0, 4.0),
                                 Align2::RIGHT_BOTTOM, format!("{:.1}s", clip.duration),
                                 FontId::monospace(9.0),
@@ -863,11 +1466,13 @@ impl EditorModule for TimelineModule {
                         } else { None };
 
                         // ── Trim handles ──────────────────────────────────────
-                        // 7px interactive strips at each clip edge. Dragging the
-                        // left edge adjusts source_offset + start_time (TrimClipStart);
-                        // dragging the right edge adjusts duration (TrimClipEnd).
+                        // 7px interactive strips at each clip edge, narrowed to 30%
+                        // of the clip each on short clips so at least 40% stays body
+                        // (select / move). Dragging the left edge adjusts
+                        // source_offset + start_time (TrimClipStart); dragging the
+                        // right edge adjusts duration (TrimClipEnd).
                         // Interacted before the body so they take priority for hover.
-                        let trim_w = 7.0_f32;
+                        let trim_w = TRIM_HANDLE_W.min(width * TRIM_HANDLE_FRAC);
                         let left_trim_rect = Rect::from_min_size(
                             clip_rect.min, egui::vec2(trim_w, track_height));
                         let right_trim_rect = Rect::from_min_max(
@@ -886,22 +1491,60 @@ impl EditorModule for TimelineModule {
                         // Push undo snapshot once at the start of each trim drag —
                         // not every frame, so the stack stays clean.
                         if left_trim.drag_started() || right_trim.drag_started() {
-                            cmd.push(EditorCommand::PushUndoSnapshot);
+                            cmd.push(EditorCommand::PushUndoSnapshot("Trim clip"));
                         }
 
                         if left_trim.dragged() {
                             let delta = left_trim.drag_delta().x as f64 / state.timeline_zoom as f64;
                             // Clamp so source_offset never goes below 0 and duration stays > 2 frames.
+                            let min_clip = state.min_clip_secs();
                             let new_source_offset = (clip.source_offset + delta).max(0.0);
                             let actual_delta      = new_source_offset - clip.source_offset;
-                            let new_duration      = (clip.duration - actual_delta).max(2.0 / 30.0);
+                            let new_duration      = (clip.duration - actual_delta).max(min_clip);
+                            // Say which limit stopped the edge.
+                            let stop = if clip.source_offset + delta <= 0.0 {
+                                Some("source start")
+                            } else if clip.duration - actual_delta <= min_clip {
+                                Some("2-frame minimum")
+                            } else {
+                                None
+                            };
+                            if let Some(label) = stop {
+                                painter.text(Pos2::new(clip_rect.min.x + 3.0, clip_rect.max.y - 2.0), Align2::LEFT_BOTTOM,
+                                    label, FontId::proportional(9.0), Color32::from_rgb(230, 80, 80));
+                            }
                             cmd.push(EditorCommand::TrimClipStart {
                                 id: clip.id, new_source_offset, new_duration });
                             ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                         }
                         if right_trim.dragged() {
                             let delta        = right_trim.drag_delta().x as f64 / state.timeline_zoom as f64;
-                            let new_duration = (clip.duration + delta).max(2.0 / 30.0);
+                            let min_clip = state.min_clip_secs();
+                            let mut new_duration = (clip.duration + delta).max(min_clip);
+                            if clip.duration + delta <= min_clip {
+                                painter.text(Pos2::new(clip_rect.max.x - 3.0, clip_rect.max.y - 2.0), Align2::RIGHT_BOTTOM,
+                                    "2-frame minimum", FontId::proportional(9.0), Color32::from_rgb(230, 80, 80));
+                            }
+                            // Snap the trimmed out-point onto a nearby marker or beat.
+                            let snap_px = snap_tolerance(ui, state.timeline_zoom);
+                            match state.snap_point_near(clip.start_time + new_duration, snap_px) {
+                                Some(mt) if mt - clip.start_time >= min_clip => {
+                                    new_duration = mt - clip.start_time;
+                                    let sx = time_origin_x + (mt as f32 * state.timeline_zoom);
+                                    self.paint_snap_line(ui.ctx(), &painter, mt, sx, rect.min.y, rect.max.y, MARKER);
+                                }
+                                _ => self.snap_flash = None,
+                            }
+                            // Never trim past the last frame of the source — show
+                            // where it ends so the stop isn't a mystery.
+                            if let Some(max) = state.max_clip_duration(clip) {
+                                new_duration = new_duration.min(max.max(min_clip));
+                                let lx = time_origin_x + ((clip.start_time + max) as f32 * state.timeline_zoom);
+                                painter.line_segment([Pos2::new(lx, clip_rect.min.y), Pos2::new(lx, clip_rect.max.y)],
+                                    Stroke::new(1.5_f32, Color32::from_rgb(230, 80, 80)));
+                                painter.text(Pos2::new(lx + 3.0, clip_rect.min.y + 2.0), Align2::LEFT_TOP,
+                                    "source end", FontId::proportional(9.0), Color32::from_rgb(230, 80, 80));
+                            }
                             cmd.push(EditorCommand::TrimClipEnd { id: clip.id, new_duration });
                             ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
                         }
@@ -912,15 +1555,27 @@ impl EditorModule for TimelineModule {
                             let handle_col = Color32::from_rgba_unmultiplied(255, 255, 255,
                                 if is_trimming { 180 } else { 80 });
                             painter.rect_filled(left_trim_rect.shrink2(egui::vec2(2.0, 0.0)),
-                                egui::CornerRadius { nw: 4, ne: 0, sw: 4, se: 0 }, handle_col);
+                                egui::CornerRadius { nw: radius, ne: 0, sw: radius, se: 0 }, handle_col);
                             painter.rect_filled(right_trim_rect.shrink2(egui::vec2(2.0, 0.0)),
-                                egui::CornerRadius { nw: 0, ne: 4, sw: 0, se: 4 }, handle_col);
+                                egui::CornerRadius { nw: 0, ne: radius, sw: 0, se: radius }, handle_col);
                         }
 
                         // ── Click / drag (body) ────────────────────────────────
                         // Click/drag interact: skipped while trimming so edges don't
                         // accidentally move the whole clip.
                         let clip_interact = ui.interact(clip_rect, Id::new(clip.id), Sense::click_and_drag());
+                        let clip_interact = if clip.note.is_empty() {
+                            clip_interact
+                        } else {
+                            clip_interact.on_hover_text(clip.note.as_str())
+                        };
+                        let clip_interact = if is_overlapping {
+                            clip_interact.on_hover_text(RichText::new(
+                                "⚠ Overlaps another clip on this track — export can't mix them")
+                                .color(CLIP_OVERLAP))
+                        } else {
+                            clip_interact
+                        };
                         if !is_trimming {
                             if clip_interact.clicked() {
                                 cmd.push(EditorCommand::SelectTimelineClip(Some(clip.id)));
@@ -930,19 +1585,21 @@ impl EditorModule for TimelineModule {
                             }
                             if clip_interact.drag_started() {
                                 // Push undo snapshot once at start of move drag.
-                                cmd.push(EditorCommand::PushUndoSnapshot);
+                                cmd.push(EditorCommand::PushUndoSnapshot("Move clip"));
                                 cmd.push(EditorCommand::SelectTimelineClip(Some(clip.id)));
                                 cmd.push(EditorCommand::SelectLibraryClip(None));
                                 self.transition_popup = None;
+                                self.move_origin = Some((clip.id, clip.start_time));
+                                self.crossfade_offer = None;
                             }
                             if clip_interact.dragged() {
                                 let delta_t = clip_interact.drag_delta().x as f64 / state.timeline_zoom as f64;
-                                let snap_px = 8.0_f64 / state.timeline_zoom as f64;
+                                let snap_px = snap_tolerance(ui, state.timeline_zoom);
                                 let clip_id = clip.id;
 
                                 // Compute target row from the pointer's current Y position so the
                                 // clip follows the cursor across tracks, not just horizontally.
-                                let target_row = clip_interact.interact_pointer_pos()
+                                let (raw_row, target_row) = clip_interact.interact_pointer_pos()
                                     .map(|ptr| {
                                         let rel_y = (ptr.y - (rect.min.y + header_height)).max(0.0);
                                         let raw_row = ((rel_y / (track_height + track_gap)) as usize)
@@ -950,8 +1607,8 @@ impl EditorModule for TimelineModule {
                                         // Enforce track-type constraints:
                                         //   Video (incl. clips on V-rows) → even rows (V1=0, V2=2)
                                         //   Audio (incl. extracted-audio)  → odd  rows (A1=1, A2=3)
-                                        match render_type {
-                                            ClipType::Video => {
+                                        let target = match render_type {
+                                            ClipType::Video | ClipType::Image => {
                                                 let r = if raw_row.is_multiple_of(2) { raw_row }
                                                         else { raw_row.saturating_sub(1) };
                                                 r.min(2)
@@ -961,12 +1618,14 @@ impl EditorModule for TimelineModule {
                                                         else { (raw_row + 1).min(3) };
                                                 r.min(3)
                                             }
-                                        }
+                                        };
+                                        (raw_row, target)
                                     })
-                                    .unwrap_or(clip.track_row);
+                                    .unwrap_or((clip.track_row, clip.track_row));
 
                                 // Store for the lane highlight painted on the NEXT frame.
                                 self.drag_target = Some((clip_id, target_row));
+                                self.drag_refused = (raw_row % 2 != target_row % 2).then_some(raw_row);
 
                                 // Snap against neighbors in the TARGET row so edge-snapping
                                 // works correctly when moving between tracks.
@@ -975,38 +1634,541 @@ impl EditorModule for TimelineModule {
                                     .flat_map(|c| [c.start_time, c.start_time + c.duration])
                                     .collect();
                                 let mut new_start = (clip.start_time + delta_t).max(0.0);
+                                // (snap time, line color) of whatever caught the drag.
+                                let mut snap: Option<(f64, Color32)> = None;
                             if new_start < snap_px {
                                 new_start = 0.0;
+                                snap = Some((0.0, SNAP_EDGE));
+                            } else if let Some(mt) = state.snap_point_near(new_start, snap_px) {
+                                new_start = mt;
+                                snap = Some((mt, MARKER));
+                            } else if let Some(mt) = state.snap_point_near(new_start + clip.duration, snap_px) {
+                                new_start = (mt - clip.duration).max(0.0);
+                                snap = Some((mt, MARKER));
+                            } else if let Some(&edge) = neighbors.iter().find(|&&e| (new_start - e).abs() < snap_px) {
+                                new_start = edge;
+                                snap = Some((edge, SNAP_EDGE));
+                            }
+                            if let Some((st, col)) = snap {
+                                let sx = time_origin_x + (st as f32 * state.timeline_zoom);
+                                self.paint_snap_line(ui.ctx(), &painter, st, sx, rect.min.y, rect.max.y, col);
+                            } else {
+                                self.snap_flash = None;
+                            }
+                            cmd.push(EditorCommand::MoveTimelineClip { id: clip_id, new_start, new_row: target_row });
+                            ui.ctx().set_cursor_icon(if self.drag_refused.is_some() {
+                                egui::CursorIcon::NotAllowed
                             } else {
-                                for edge in &neighbors {
-                                    if (new_start - edge).abs() < snap_px {
-                                        new_start = *edge;
-                                        break;
+                                egui::CursorIcon::Grabbing
+                            });
+
+                            // Pushing the clip's head into the one before it
+                            // asks for a crossfade. move_group keeps the clip
+                            // butted, so the overlap is measured from where
+                            // the pointer would have put it.
+                            let intended = self.move_origin
+                                .filter(|(id, _)| *id == clip_id)
+                                .and_then(|(_, from)| {
+                                    let origin = ui.input(|i| i.pointer.press_origin())?;
+                                    let ptr = clip_interact.interact_pointer_pos()?;
+                                    Some(from + ((ptr.x - origin.x) / state.timeline_zoom) as f64)
+                                });
+                            self.drag_overlap = intended
+                                .and_then(|t| clip_query::crossfade_overlap(state, clip_id, target_row, t))
+                                .map(|(prev, secs)| (prev, clip_id, secs));
+                            if let Some((prev, secs)) = self.drag_overlap
+                                .and_then(|(p, _, secs)| Some((clip_query::timeline_clip(state, p)?, secs)))
+                            {
+                                let end = prev.start_time + prev.duration;
+                                let top = rect.min.y + header_height + target_row as f32 * (track_height + track_gap);
+                                let band = Rect::from_min_max(
+                                    Pos2::new(time_origin_x + ((end - secs) as f32 * state.timeline_zoom), top),
+                                    Pos2::new(time_origin_x + (end as f32 * state.timeline_zoom), top + track_height),
+                                );
+                                painter.rect_filled(band, 2.0, ACCENT.linear_multiply(0.25));
+                                painter.text(Pos2::new(band.center().x, band.min.y - 2.0), Align2::CENTER_BOTTOM,
+                                    format!("⟷ crossfade {secs:.2}s"), FontId::proportional(10.0), ACCENT);
+                            }
+                        } else {
+                            // Clear drag target when this clip is no longer being dragged.
+                            if self.drag_target.map(|(id, _)| id) == Some(clip.id) {
+                                self.drag_target = None;
+                                self.drag_refused = None;
+                            }
+                            // Dropped over the clip before it: the move left it
+                            // butted there, so the overlap becomes a crossfade of
+                            // the same length and the cut lands where the drop
+                            // put it. The drag's "Move clip" snapshot undoes both.
+                            if clip_interact.drag_stopped() {
+                                self.move_origin = None;
+                                let offer = self.drag_overlap.take().filter(|&(prev, _, _)| {
+                                    clip_query::timeline_clip(state, prev).is_some_and(|p| {
+                                        p.track_row == clip.track_row
+                                            && (p.start_time + p.duration - clip.start_time).abs() < 1e-3
+                                    })
+                                });
+                                if let Some((prev, _, secs)) = offer {
+                                    if state.playback_prefs.auto_crossfade {
+                                        cmd.push(EditorCommand::SetTransition {
+                                            after_clip_id: prev,
+                                            kind: TransitionType::new(TransitionKind::Crossfade, secs as f32),
+                                        });
+                                    } else {
+                                        self.crossfade_offer = offer;
                                     }
                                 }
                             }
-                            cmd.push(EditorCommand::MoveTimelineClip { id: clip_id, new_start, new_row: target_row });
-                            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
-                        } else {
-                            // Clear drag target when this clip is no longer being dragged.
-                            if self.drag_target.map(|(id, _)| id) == Some(clip.id) {
-                                self.drag_target = None;
+                            if clip_interact.hovered() && !left_trim.hovered() && !right_trim.hovered() {
+                                ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                            }
+                        }
+                        }
+
+                        // Right-click context menu
+                        clip_interact.context_menu(|ui: &mut egui::Ui| {
+                            ui.set_min_width(160.0);
+                            let clip_id = clip.id;
+                            if ui.button("🗑  Remove from timeline").clicked() {
+                                to_delete = Some(clip_id);
+                                ui.close();
+                            }
+                            if ui.button("⌖  Match frame in library")
+                                .on_hover_text("Select this clip's source at the playhead's source time  [Shift+F]")
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::MatchFrame(Some(clip_id)));
+                                ui.close();
+                            }
+                            // Link with whatever sits on other tracks at the
+                            // same time (a video and its audio), or break out.
+                            let can_link = state.timeline.iter().any(|o| {
+                                o.track_row != clip.track_row
+                                    && o.start_time < clip.start_time + clip.duration
+                                    && clip.start_time < o.start_time + o.duration
+                                    && (o.group_id.is_none() || o.group_id != clip.group_id)
+                            });
+                            if ui.add_enabled(can_link, egui::Button::new("🔗  Link with overlapping clips"))
+                                .on_hover_text("Linked clips move, trim, split and delete together")
+                                .on_disabled_hover_text("No unlinked clip overlaps this one on another track")
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Link clips"));
+                                cmd.push(EditorCommand::LinkOverlapping(clip_id));
+                                ui.close();
+                            }
+                            if clip.group_id.is_some() && ui.button("⛓  Unlink").clicked() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Unlink clip"));
+                                cmd.push(EditorCommand::UnlinkClip(clip_id));
+                                ui.close();
+                            }
+                            let (toggle_label, toggle_undo) = if clip.enabled {
+                                ("⊘  Disable clip", "Disable clip")
+                            } else {
+                                ("✓  Enable clip", "Enable clip")
+                            };
+                            if ui.button(toggle_label)
+                                .on_hover_text("Disabled clips stay on the timeline but are skipped by preview and export (E)")
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot(toggle_undo));
+                                cmd.push(EditorCommand::SetClipEnabled { id: clip_id, enabled: !clip.enabled });
+                                ui.close();
+                            }
+                            // Poster frame — only moving video clips with the
+                            // playhead over them have a frame to pick.
+                            let playhead_in = state.current_time >= clip.start_time
+                                && state.current_time < clip.start_time + clip.duration;
+                            let can_poster = clip.track_row % 2 == 0
+                                && clip.freeze_at.is_none()
+                                && playhead_in;
+                            if ui.add_enabled(can_poster, egui::Button::new("🖼  Set poster frame at playhead"))
+                                .on_disabled_hover_text("Put the playhead over this video clip")
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Set poster frame"));
+                                cmd.push(EditorCommand::SetClipPoster {
+                                    id: clip_id,
+                                    at: Some(clip.source_time(state.current_time - clip.start_time)),
+                                });
+                                ui.close();
+                            }
+                            if clip.poster_offset.is_some()
+                                && ui.button("🖼  Clear poster frame").clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Clear poster frame"));
+                                cmd.push(EditorCommand::SetClipPoster { id: clip_id, at: None });
+                                ui.close();
+                            }
+                            // Stabilization — export-only, so any moving video
+                            // clip qualifies; the strength edits in place.
+                            let can_stabilize = clip.track_row % 2 == 0
+                                && clip.freeze_at.is_none()
+                                && clip_type != ClipType::Image;
+                            ui.horizontal(|ui| {
+                                let mut on = clip.stabilize.is_some();
+                                if ui.add_enabled(can_stabilize, egui::Checkbox::new(&mut on, "〰  Stabilize"))
+                                    .on_hover_text("Smooth out camera shake on export. Zooms in slightly; the first render measures the motion")
+                                    .on_disabled_hover_text("Needs a moving video clip")
+                                    .changed()
+                                {
+                                    cmd.push(EditorCommand::PushUndoSnapshot(if on { "Stabilize clip" } else { "Unstabilize clip" }));
+                                    cmd.push(EditorCommand::SetClipStabilize { id: clip_id, stabilize: on.then(Stabilize::default) });
+                                }
+                                if let Some(mut stab) = clip.stabilize {
+                                    let resp = ui.add(egui::DragValue::new(&mut stab.strength)
+                                        .range(0.1..=1.0)
+                                        .speed(0.01)
+                                        .fixed_decimals(2))
+                                        .on_hover_text("Strength: higher smooths more and zooms in further");
+                                    if resp.drag_started() || (resp.changed() && !resp.dragged()) {
+                                        cmd.push(EditorCommand::PushUndoSnapshot("Stabilize strength"));
+                                    }
+                                    if resp.changed() {
+                                        cmd.push(EditorCommand::SetClipStabilize { id: clip_id, stabilize: Some(stab) });
+                                    }
+                                }
+                            });
+                            // Scene detection — same moving-video rule as posters,
+                            // but over the whole clip rather than the playhead.
+                            let can_detect = clip.track_row % 2 == 0
+                                && clip.freeze_at.is_none()
+                                && state.scene_detecting.is_none();
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(can_detect, egui::Button::new("🎬  Detect scenes"))
+                                    .on_hover_text("Scan this clip for hard cuts")
+                                    .on_disabled_hover_text(if state.scene_detecting.is_some() {
+                                        "Scene detection already running"
+                                    } else {
+                                        "Needs a moving video clip"
+                                    })
+                                    .clicked()
+                                {
+                                    cmd.push(EditorCommand::DetectScenes { id: clip_id, threshold: self.scene_threshold });
+                                    ui.close();
+                                }
+                                ui.add(egui::DragValue::new(&mut self.scene_threshold)
+                                    .range(0.05..=0.95)
+                                    .speed(0.01)
+                                    .fixed_decimals(2))
+                                    .on_hover_text("Sensitivity: lower finds more cuts");
+                            });
+                            let scanned = state.scene_cuts.as_ref().filter(|(id, _)| *id == clip_id);
+                            let cut_count = scanned.map_or(0, |(_, cuts)| cuts.len());
+                            if scanned.is_some() && cut_count == 0 {
+                                ui.label(RichText::new("No cuts found — try a lower value").size(10.0).color(DARK_TEXT_DIM));
+                            }
+                            if cut_count > 0
+                                && ui.button(format!("✂  Split at {cut_count} detected cut{}", if cut_count == 1 { "" } else { "s" })).clicked()
+                            {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Split at scenes"));
+                                cmd.push(EditorCommand::SplitAtScenes(clip_id));
+                                ui.close();
+                            }
+                            // Silence trim — same audio rule as the mute toggle:
+                            // a video whose audio was extracted is scanned via
+                            // its A-row clip.
+                            let has_audio = clip_type != ClipType::Image
+                                && clip.freeze_at.is_none()
+                                && !(clip.track_row % 2 == 0 && clip.linked_clip_id.is_some());
+                            let can_scan = has_audio && state.silence_detecting.is_none();
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(can_scan, egui::Button::new("🔇  Detect silence"))
+                                    .on_hover_text("Find leading and trailing silence in this clip's audio")
+                                    .on_disabled_hover_text(if state.silence_detecting.is_some() {
+                                        "Silence detection already running"
+                                    } else {
+                                        "Needs a clip with audio"
+                                    })
+                                    .clicked()
+                                {
+                                    cmd.push(EditorCommand::DetectSilence {
+                                        id: clip_id,
+                                        threshold_db: self.silence_db,
+                                        min_gap: self.silence_gap,
+                                    });
+                                    ui.close();
+                                }
+                                ui.add(egui::DragValue::new(&mut self.silence_db)
+                                    .range(-80.0..=-10.0)
+                                    .speed(0.5)
+                                    .suffix(" dB")
+                                    .fixed_decimals(0))
+                                    .on_hover_text("Threshold: anything quieter counts as silence");
+                                ui.add(egui::DragValue::new(&mut self.silence_gap)
+                                    .range(0.0..=5.0)
+                                    .speed(0.05)
+                                    .suffix(" s")
+                                    .fixed_decimals(2))
+                                    .on_hover_text("Minimum gap: shorter lead/tail silence is kept");
+                            });
+                            if let Some((_, sound)) = state.silence_range.filter(|(id, _)| *id == clip_id) {
+                                let (from, to) = (clip.source_offset, clip.source_offset + clip.duration);
+                                match sound {
+                                    None => {
+                                        ui.label(RichText::new("No sound above the threshold").size(10.0).color(DARK_TEXT_DIM));
+                                    }
+                                    Some((start, end)) => {
+                                        let lead = (start - from).max(0.0);
+                                        let tail = (to - end).max(0.0);
+                                        if lead < 1e-3 && tail < 1e-3 {
+                                            ui.label(RichText::new("No silence to trim").size(10.0).color(DARK_TEXT_DIM));
+                                        } else if ui.button(format!("✂  Trim silence ({lead:.2}s head, {tail:.2}s tail)")).clicked() {
+                                            cmd.push(EditorCommand::PushUndoSnapshot("Trim silence"));
+                                            cmd.push(EditorCommand::TrimSilence(clip_id));
+                                            ui.close();
+                                        }
+                                    }
+                                }
+                            }
+                            // Audio sync — line this clip up with the clip on
+                            // the track above/below by matching their audio.
+                            // The source file is matched, so a video whose
+                            // audio was extracted still qualifies.
+                            let has_audio_source = clip_type != ClipType::Image && clip.freeze_at.is_none();
+                            let reference = if has_audio_source { clip_query::sync_reference(state, clip) } else { None };
+                            let can_sync = reference.is_some() && state.sync_detecting.is_none();
+                            if ui.add_enabled(can_sync, egui::Button::new("⇆  Sync audio to adjacent track"))
+                                .on_hover_text("Match this clip's audio against the overlapping clip on the track above or below and find the offset that syncs them")
+                                .on_disabled_hover_text(if state.sync_detecting.is_some() {
+                                    "Audio sync already running"
+                                } else if !has_audio_source {
+                                    "Needs a clip with audio"
+                                } else {
+                                    "No clip with audio on the track above or below"
+                                })
+                                .clicked()
+                            {
+                                if let Some(r) = reference {
+                                    cmd.push(EditorCommand::DetectAudioSync { id: clip_id, reference: r.id });
+                                }
+                                ui.close();
+                            }
+                            if state.sync_detecting.is_some_and(|(id, _)| id == clip_id) {
+                                ui.label(RichText::new("Matching audio…").size(10.0).color(DARK_TEXT_DIM));
+                            }
+                            if let Some((_, reference, offset)) = state.audio_sync.filter(|(id, _, _)| *id == clip_id) {
+                                match offset.and_then(|o| state.synced_start(clip_id, reference, o)) {
+                                    None => {
+                                        ui.label(RichText::new("No audio match found").size(10.0).color(DARK_TEXT_DIM));
+                                    }
+                                    Some(start) => {
+                                        let shift = start - clip.start_time;
+                                        if shift.abs() < 0.001 {
+                                            ui.label(RichText::new("Already in sync").size(10.0).color(DARK_TEXT_DIM));
+                                        } else if ui.add_enabled(state.move_is_clear(clip_id, start),
+                                                egui::Button::new(format!("⇆  Move {shift:+.3}s to sync")))
+                                            .on_disabled_hover_text("The synced position starts before 0 or overlaps another clip on the track")
+                                            .clicked()
+                                        {
+                                            cmd.push(EditorCommand::PushUndoSnapshot("Sync audio"));
+                                            cmd.push(EditorCommand::ApplyAudioSync(clip_id));
+                                            ui.close();
+                                        }
+                                    }
+                                }
+                            }
+                            // Manual fine-tune — for when auto-sync finds no match
+                            // or lands a few ms off.
+                            let adjusting = self.sync_adjust == Some(clip_id);
+                            if ui.add_enabled(adjusting || reference.is_some(), egui::Button::new(
+                                    if adjusting { "⇆  Stop fine-tuning sync" } else { "⇆  Fine-tune sync by hand" }))
+                                .on_hover_text("Overlay this clip's waveform on the adjacent track's and nudge it with ←/→ (10 ms, Shift 1 ms) while reading the offset in ms")
+                                .on_disabled_hover_text("No clip with audio on the track above or below")
+                                .clicked()
+                            {
+                                self.sync_adjust = (!adjusting).then_some(clip_id);
+                                if !adjusting {
+                                    cmd.push(EditorCommand::SelectTimelineClip(Some(clip_id)));
+                                }
+                                ui.close();
+                            }
+                            // Auto-duck — lower this clip (the music) wherever the
+                            // clip on the adjacent track (the speech) is talking.
+                            // Both need audible audio of their own.
+                            let speech = reference.filter(|r| !r.audio_muted && !clip.audio_muted);
+                            let can_duck = has_audio && speech.is_some() && state.duck_detecting.is_none();
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(can_duck, egui::Button::new("🎚  Duck under speech"))
+                                    .on_hover_text("Find where the clip on the adjacent track is talking and lower this clip underneath it")
+                                    .on_disabled_hover_text(if state.duck_detecting.is_some() {
+                                        "Speech detection already running"
+                                    } else if !has_audio || clip.audio_muted {
+                                        "Needs a clip with audio"
+                                    } else {
+                                        "No clip with audio on the track above or below"
+                                    })
+                                    .clicked()
+                                {
+                                    if let Some(r) = speech {
+                                        cmd.push(EditorCommand::DetectSpeech {
+                                            id: clip_id,
+                                            speech: r.id,
+                                            threshold_db: self.duck.threshold_db,
+                                        });
+                                    }
+                                    ui.close();
+                                }
+                                ui.add(egui::DragValue::new(&mut self.duck.threshold_db)
+                                    .range(-70.0..=-10.0)
+                                    .speed(0.5)
+                                    .suffix(" dB")
+                                    .fixed_decimals(0))
+                                    .on_hover_text("Threshold: speech louder than this ducks the music");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut self.duck.duck_db)
+                                    .range(-40.0..=-1.0)
+                                    .speed(0.25)
+                                    .prefix("duck ")
+                                    .suffix(" dB")
+                                    .fixed_decimals(0))
+                                    .on_hover_text("How far the music drops under speech");
+                                ui.add(egui::DragValue::new(&mut self.duck.attack)
+                                    .range(0.0..=2.0)
+                                    .speed(0.01)
+                                    .prefix("attack ")
+                                    .suffix(" s")
+                                    .fixed_decimals(2))
+                                    .on_hover_text("Ramp down this long before speech starts");
+                                ui.add(egui::DragValue::new(&mut self.duck.release)
+                                    .range(0.0..=5.0)
+                                    .speed(0.01)
+                                    .prefix("release ")
+                                    .suffix(" s")
+                                    .fixed_decimals(2))
+                                    .on_hover_text("Ramp back up over this long after speech ends");
+                            });
+                            if state.duck_detecting.is_some_and(|(id, _)| id == clip_id) {
+                                ui.label(RichText::new("Listening for speech…").size(10.0).color(DARK_TEXT_DIM));
+                            }
+                            if let Some(SpeechRegions { regions, .. }) = state.speech_regions.as_ref().filter(|r| r.clip == clip_id) {
+                                if regions.is_empty() {
+                                    ui.label(RichText::new("No speech above the threshold").size(10.0).color(DARK_TEXT_DIM));
+                                } else if ui.button(format!("🎚  Apply duck ({} speech regions)", regions.len())).clicked() {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Auto-duck"));
+                                    cmd.push(EditorCommand::ApplyDuck { id: clip_id, duck: self.duck });
+                                    ui.close();
+                                }
+                            }
+                            if !clip.volume_keys.is_empty() && ui.button("🎚  Clear volume keys").clicked() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Clear volume keys"));
+                                cmd.push(EditorCommand::ClearVolumeKeys(clip_id));
+                                ui.close();
                             }
-                            if clip_interact.hovered() && !left_trim.hovered() && !right_trim.hovered() {
-                                ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+                            // Nest — flatten everything between the markers
+                            // around the playhead into one rendered clip. The
+                            // undo snapshot is pushed by app.rs when the
+                            // render lands.
+                            let (nest_a, nest_b) = clip_query::marker_range_around(state, state.current_time);
+                            let can_nest = nest_b > nest_a
+                                && state.encode_job.is_none()
+                                && state.video_covers(nest_a, nest_b);
+                            if ui.add_enabled(can_nest, egui::Button::new(format!(
+                                    "⧉  Nest {}–{}", format_time(nest_a, state.project_fps), format_time(nest_b, state.project_fps))))
+                                .on_hover_text("Render everything between the markers around the playhead to one clip. Un-nest expands it again.")
+                                .on_disabled_hover_text(if state.encode_job.is_some() {
+                                    "A render is already running"
+                                } else {
+                                    "The range needs unbroken video between the markers"
+                                })
+                                .clicked()
+                            {
+                                cmd.push(EditorCommand::NestRange { start: nest_a, end: nest_b });
+                                ui.close();
                             }
-                        }
-                        }
+                            if state.nests.iter().any(|n| n.clip_id == clip_id) {
+                                if ui.add_enabled(state.can_unnest(clip_id), egui::Button::new("⧉  Un-nest"))
+                                    .on_hover_text("Put back the clips this render was made from")
+                                    .on_disabled_hover_text("Trimmed nested clips, or ones whose clips would land on others, can't be expanded")
+                                    .clicked()
+                                {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Un-nest clip"));
+                                    cmd.push(EditorCommand::UnnestClip(clip_id));
+                                    ui.close();
+                                }
+                            }
+                            ui.separator();
 
-                        // Right-click context menu
-                        clip_interact.context_menu(|ui: &mut egui::Ui| {
-                            ui.set_min_width(160.0);
-                            let clip_id = clip.id;
-                            if ui.button("🗑  Remove from timeline").clicked() {
-                                to_delete = Some(clip_id);
-                                ui.close();
+                            // ── Color tag + note ───────────────────────────────
+                            ui.label(RichText::new("TAG").size(8.5).monospace()
+                                .color(Color32::from_rgba_unmultiplied(0, 180, 210, 130)));
+                            ui.horizontal(|ui| {
+                                ui.spacing_mut().item_spacing.x = 3.0;
+                                let none_btn = egui::Button::new(RichText::new("∅").size(10.0))
+                                    .selected(clip.tag_color.is_none())
+                                    .min_size(egui::vec2(18.0, 18.0));
+                                if ui.add(none_btn).on_hover_text("No tag").clicked() {
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Tag clip"));
+                                    cmd.push(EditorCommand::SetClipTag {
+                                        id: clip_id, tag_color: None, note: clip.note.clone() });
+                                }
+                                for (name, color) in CLIP_TAG_PALETTE {
+                                    let rgb = [color.r(), color.g(), color.b()];
+                                    let selected = clip.tag_color == Some(rgb);
+                                    let swatch = egui::Button::new("")
+                                        .fill(color)
+                                        .stroke(Stroke::new(
+                                            if selected { 2.0_f32 } else { 1.0_f32 },
+                                            if selected { Color32::WHITE } else { DARK_BORDER }))
+                                        .min_size(egui::vec2(18.0, 18.0));
+                                    if ui.add(swatch).on_hover_text(name).clicked() {
+                                        cmd.push(EditorCommand::PushUndoSnapshot("Tag clip"));
+                                        cmd.push(EditorCommand::SetClipTag {
+                                            id: clip_id, tag_color: Some(rgb), note: clip.note.clone() });
+                                    }
+                                }
+                            });
+                            let mut note = clip.note.clone();
+                            let note_resp = ui.add(
+                                egui::TextEdit::singleline(&mut note)
+                                    .hint_text("Note…")
+                                    .char_limit(200)
+                                    .desired_width(160.0));
+                            // One snapshot per editing session, not per keystroke.
+                            if note_resp.gained_focus() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Edit note"));
                             }
+                            if note_resp.changed() {
+                                cmd.push(EditorCommand::SetClipTag {
+                                    id: clip_id, tag_color: clip.tag_color, note });
+                            }
+                            ui.separator();
+
+                            // ── Copy adjustments to other clips ────────────────
+                            // One snapshot for the whole batch so a single undo
+                            // reverts every clip it touched.
+                            ui.menu_button("⇉  Apply color to…", |ui| {
+                                for &scope in clip_query::ApplyScope::all() {
+                                    let targets = clip_query::apply_targets(state, clip, scope, true);
+                                    let resp = ui.add_enabled(!targets.is_empty(),
+                                        egui::Button::new(format!("{}  ({})", scope.label(), targets.len())))
+                                        .on_hover_text("Copy this clip's color adjustment onto these video clips");
+                                    if resp.clicked() {
+                                        cmd.push(EditorCommand::PushUndoSnapshot("Apply color to clips"));
+                                        for id in targets {
+                                            cmd.push(EditorCommand::SetClipFilter { id, filter: clip.filter.clone() });
+                                        }
+                                        ui.close();
+                                    }
+                                }
+                            });
+                            ui.menu_button("⇉  Apply volume to…", |ui| {
+                                for &scope in clip_query::ApplyScope::all() {
+                                    let targets = clip_query::apply_targets(state, clip, scope, false);
+                                    let resp = ui.add_enabled(!targets.is_empty(),
+                                        egui::Button::new(format!("{}  ({})", scope.label(), targets.len())))
+                                        .on_hover_text("Copy this clip's gain onto these clips (fades stay per clip)");
+                                    if resp.clicked() {
+                                        cmd.push(EditorCommand::PushUndoSnapshot("Apply volume to clips"));
+                                        for id in targets {
+                                            cmd.push(EditorCommand::SetClipVolume { id, volume: clip.volume });
+                                        }
+                                        ui.close();
+                                    }
+                                }
+                            });
                             ui.separator();
+
                             ui.label(RichText::new(media_name).size(10.0)
                                 .color(egui::Color32::from_gray(120)));
                             ui.label(RichText::new(format!("Duration: {:.2}s", clip.duration))
@@ -1031,7 +2193,7 @@ impl EditorModule for TimelineModule {
                                 if fopen {
                                     self.filter_popup = None;
                                 } else {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Color adjust"));
                                     self.filter_popup             = Some((clip_id, fr.center_bottom()));
                                     self.filter_popup_just_opened = true;
                                 }
@@ -1064,7 +2226,7 @@ impl EditorModule for TimelineModule {
                                 if vol_is_open {
                                     self.vol_popup = None;
                                 } else {
-                                    cmd.push(EditorCommand::PushUndoSnapshot);
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Volume & fades"));
                                     self.vol_popup             = Some((clip_id, badge_center));
                                     self.vol_popup_just_opened = true;
                                 }
@@ -1085,7 +2247,7 @@ impl EditorModule for TimelineModule {
                             let clip_a = track_clips[i];
                             let clip_b = track_clips[i + 1];
                             let gap = clip_b.start_time - (clip_a.start_time + clip_a.duration);
-                            if gap.abs() > 0.25 { continue; } // only touching clips
+                            if gap.abs() > clip_query::TRANSITION_JOIN_TOLERANCE { continue; } // only touching clips
                             if track_row % 2 == 1 { continue; } // audio rows — no video transitions
 
                             let join_x = time_origin_x
@@ -1096,6 +2258,10 @@ impl EditorModule for TimelineModule {
                                 rect.min.y + y_off + track_height * 0.5,
                             );
 
+                            if self.crossfade_offer.is_some_and(|(a, b, _)| a == clip_a.id && b == clip_b.id) {
+                                offer_anchor = Some(badge_center);
+                            }
+
                             let current_kind = state.transitions.iter()
                                 .find(|t| t.after_clip_id == clip_a.id)
                                 .map(|t| &t.kind);
@@ -1173,6 +2339,9 @@ impl EditorModule for TimelineModule {
                                 if is_open {
                                     self.transition_popup = None;
                                 } else {
+                                    // One snapshot per popup session, like the volume
+                                    // and color popups — every edit inside undoes together.
+                                    cmd.push(EditorCommand::PushUndoSnapshot("Transition"));
                                     self.transition_popup             = Some((clip_a.id, badge_center));
                                     self.transition_popup_just_opened = true;
                                 }
@@ -1181,12 +2350,30 @@ impl EditorModule for TimelineModule {
                     }
 
                     if let Some(del_id) = to_delete {
-                        cmd.push(EditorCommand::PushUndoSnapshot);
-                        cmd.push(EditorCommand::DeleteTimelineClip(del_id));
+                        self.request_delete(state, del_id, cmd);
                     }
 
-                    // Playhead
+                    // End of timeline — where playback stops. Dashed so it
+                    // reads as a boundary, not a clip edge.
                     let total_duration = state.total_duration();
+                    if total_duration > 0.0 {
+                        let end_x = time_origin_x + total_duration as f32 * state.timeline_zoom;
+                        painter.extend(egui::Shape::dashed_line(
+                            &[Pos2::new(end_x, rect.min.y), Pos2::new(end_x, rect.max.y)],
+                            Stroke::new(1.0_f32, Color32::from_white_alpha(70)),
+                            4.0,
+                            4.0,
+                        ));
+                        painter.text(
+                            Pos2::new(end_x + 3.0, rect.min.y + header_height + 2.0),
+                            Align2::LEFT_TOP,
+                            "END",
+                            FontId::proportional(9.0),
+                            Color32::from_white_alpha(110),
+                        );
+                    }
+
+                    // Playhead
                     let clamped_time = if total_duration > 0.0 {
                         state.current_time.min(total_duration)
                     } else {
@@ -1207,7 +2394,7 @@ impl EditorModule for TimelineModule {
 
                     // ── Floating timecode label — foreground, inside ruler strip ──
                     {
-                        let time_str = format_time(clamped_time);
+                        let time_str = format_time(clamped_time, state.project_fps);
                         let font     = FontId::monospace(10.0);
                         let fg_painter = ui.ctx().layer_painter(egui::LayerId::new(egui::Order::Foreground, Id::new("ph_timecode")));
                         let galley  = fg_painter.layout_no_wrap(time_str, font, ACCENT);
@@ -1239,7 +2426,7 @@ impl EditorModule for TimelineModule {
                                 cmd.push(EditorCommand::Pause);
                                 cmd.push(EditorCommand::SetPlayhead(snapped));
                                 self.last_scrub_emitted_time = snapped;
-                            } else if (t_clamped - self.last_scrub_emitted_time).abs() >= 1.0 / 30.0 {
+                            } else if (t_clamped - self.last_scrub_emitted_time).abs() >= state.frame_secs() {
                                 let snapped = snap_to_video_end(t_clamped);
                                 cmd.push(EditorCommand::SetPlayhead(snapped));
                                 self.last_scrub_emitted_time = snapped;
@@ -1256,6 +2443,7 @@ impl EditorModule for TimelineModule {
                         cmd.push(EditorCommand::SelectLibraryClip(None));
                     }
                 });
+            self.view_scroll = (scroll_out.state.offset.x, scroll_out.inner_rect.width());
 
             // ── Transition popup ───────────────────────────────────────────────
             // Rendered outside the ScrollArea so it floats above everything.
@@ -1352,7 +2540,7 @@ impl EditorModule for TimelineModule {
                                                 };
                                                 cmd.push(EditorCommand::SetTransition {
                                                     after_clip_id,
-                                                    kind: entry.build(dur),
+                                                    kind: entry.build(dur).with_curve(current_kind.curve),
                                                 });
                                             }
                                             col += 1;
@@ -1377,11 +2565,40 @@ impl EditorModule for TimelineModule {
                                         if let Some(entry) = reg.get(&current_kind.kind) {
                                             cmd.push(EditorCommand::SetTransition {
                                                 after_clip_id,
-                                                kind: entry.build(dur),
+                                                kind: entry.build(dur).with_curve(current_kind.curve),
                                             });
                                         }
                                     }
                                 }
+
+                                // Curve — only the dissolve blends along one.
+                                if current_kind.kind == TransitionKind::Crossfade {
+                                    ui.add_space(8.0);
+                                    ui.label(
+                                        RichText::new("Curve").size(10.0).color(DARK_TEXT_DIM),
+                                    );
+                                    ui.add_space(2.0);
+                                    ui.horizontal(|ui| {
+                                        for &curve in FadeCurve::all() {
+                                            if ui
+                                                .selectable_label(current_kind.curve == curve, RichText::new(curve.label()).size(11.0))
+                                                .on_hover_text(match curve {
+                                                    FadeCurve::SCurve => "Eased picture, straight audio ramp (original)",
+                                                    FadeCurve::Linear => "Straight ramp — slight dip at the midpoint",
+                                                    FadeCurve::EqualPower => "No mid-dissolve darkening or volume dip",
+                                                    FadeCurve::Logarithmic => "Incoming clip arrives fast; middle runs hot",
+                                                })
+                                                .clicked()
+                                                && current_kind.curve != curve
+                                            {
+                                                cmd.push(EditorCommand::SetTransition {
+                                                    after_clip_id,
+                                                    kind: current_kind.clone().with_curve(curve),
+                                                });
+                                            }
+                                        }
+                                    });
+                                }
                             });
                     });
 
@@ -1405,14 +2622,62 @@ impl EditorModule for TimelineModule {
                 self.transition_popup_just_opened = false;
             }
 
+            // ── Crossfade offer ───────────────────────────────────────────────
+            // Asked after a clip is dropped over the end of the one before it
+            // (unless Playback → "Crossfade overlaps without asking" is on).
+            // Gone once the join no longer exists.
+            match (self.crossfade_offer, offer_anchor) {
+                (Some((after_clip_id, _, secs)), Some(anchor)) => {
+                    let area_resp = egui::Area::new(Id::new("crossfade_offer_area"))
+                        .fixed_pos(Pos2::new(anchor.x - 80.0, anchor.y + 16.0))
+                        .order(egui::Order::Foreground)
+                        .show(ui.ctx(), |ui| {
+                            egui::Frame::new()
+                                .fill(DARK_BG_3)
+                                .stroke(Stroke::new(1.0_f32, DARK_BORDER))
+                                .corner_radius(egui::CornerRadius::same(6))
+                                .inner_margin(egui::Margin::same(8))
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new(format!("Clips overlap by {secs:.2}s"))
+                                        .size(11.0).color(DARK_TEXT_DIM));
+                                    ui.horizontal(|ui| {
+                                        if ui.button(format!("⟷  Crossfade {secs:.2}s")).clicked() {
+                                            cmd.push(EditorCommand::PushUndoSnapshot("Crossfade overlap"));
+                                            cmd.push(EditorCommand::SetTransition {
+                                                after_clip_id,
+                                                kind: TransitionType::new(TransitionKind::Crossfade, secs as f32),
+                                            });
+                                            self.crossfade_offer = None;
+                                        }
+                                        if ui.button("✂  Keep cut").clicked() {
+                                            self.crossfade_offer = None;
+                                        }
+                                    });
+                                });
+                        });
+                    let clicked_outside = ui.input(|i| {
+                        i.pointer.any_click()
+                            && i.pointer.interact_pos().is_some_and(|p| !area_resp.response.rect.contains(p))
+                    });
+                    if clicked_outside {
+                        self.crossfade_offer = None;
+                    }
+                }
+                (Some(_), None) => self.crossfade_offer = None,
+                _ => {}
+            }
+
             // ── Volume / Fade popup ───────────────────────────────────────────────────
             // Three-section floating panel: [Fade In | Volume | Fade Out].
             if let Some((vol_clip_id, anchor)) = self.vol_popup {
                 let clip_data = state.timeline.iter()
                     .find(|c| c.id == vol_clip_id)
-                    .map(|c| (c.volume, c.fade_in_secs, c.fade_in_start_secs, c.fade_out_secs, c.fade_out_end_secs));
+                    .map(|c| (c.volume, c.fade_in_secs, c.fade_in_start_secs, c.fade_out_secs, c.fade_out_end_secs,
+                              c.audio_muted,
+                              // Extracted video: its sound lives on the A-row partner.
+                              c.track_row % 2 == 0 && c.linked_clip_id.is_some()));
 
-                if let Some((cur_vol, cur_fi, cur_fi_start, cur_fo, cur_fo_end)) = clip_data {
+                if let Some((cur_vol, cur_fi, cur_fi_start, cur_fo, cur_fo_end, cur_muted, extracted)) = clip_data {
                     let vol_to_db = |v: f32| -> f32 {
                         if v <= 0.0001 { -60.0 } else { 20.0 * v.log10() }
                     };
@@ -1450,6 +2715,28 @@ impl EditorModule for TimelineModule {
 
                                     ui.horizontal(|ui| {
                                         ui.label(RichText::new("🔊  Clip Audio").size(11.0).strong().color(ACCENT));
+                                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                            ui.spacing_mut().item_spacing.x = 3.0;
+                                            // Both ride the snapshot pushed when the popup opened.
+                                            let mute_btn = egui::Button::new(RichText::new("🔇").size(10.0))
+                                                .selected(cur_muted && !extracted)
+                                                .min_size(egui::vec2(20.0, 16.0));
+                                            if ui.add_enabled(!extracted, mute_btn)
+                                                .on_hover_text(if cur_muted { "Unmute clip" } else { "Mute clip" })
+                                                .on_disabled_hover_text("Audio was extracted — mute the linked audio clip")
+                                                .clicked()
+                                            {
+                                                cmd.push(EditorCommand::SetClipMuted { id: vol_clip_id, muted: !cur_muted });
+                                            }
+                                            let unity = (cur_vol - 1.0).abs() < 1e-4;
+                                            if ui.add_enabled(!unity, egui::Button::new(RichText::new("0 dB").size(9.0))
+                                                    .min_size(egui::vec2(0.0, 16.0)))
+                                                .on_hover_text("Reset volume to unity gain")
+                                                .clicked()
+                                            {
+                                                cmd.push(EditorCommand::SetClipVolume { id: vol_clip_id, volume: 1.0 });
+                                            }
+                                        });
                                     });
                                     ui.add_space(5.0);
                                     ui.separator();
@@ -1573,7 +2860,7 @@ impl EditorModule for TimelineModule {
                     let popup_w = 160.0_f32;
                     let popup_pos = Pos2::new(
                         (anchor.x - popup_w * 0.5).max(4.0),
-                        anchor.y - 14.0 - 360.0,  // popup height approx
+                        anchor.y - 14.0 - 540.0,  // popup height approx
                     );
 
                     let farea_resp = egui::Area::new(Id::new("filter_popup_area"))
@@ -1632,6 +2919,8 @@ impl EditorModule for TimelineModule {
                                                 if ui.add_sized([57.0, 24.0], btn).clicked() {
                                                     let mut p = FilterParams::from_preset(*kind);
                                                     p.strength = cur_filter.strength;
+                                                    p.spatial  = cur_filter.spatial;
+                                                    p.levels   = cur_filter.levels;
                                                     cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
                                                 }
                                             }
@@ -1722,6 +3011,82 @@ impl EditorModule for TimelineModule {
                                         p.strength = 1.0;
                                         cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
                                     }
+
+                                    ui.add_space(6.0);
+                                    ui.separator();
+                                    ui.add_space(4.0);
+
+                                    // ── Sharpen / Blur — luma-only spatial pass ───────
+                                    ui.label(RichText::new("DETAIL").size(8.5).monospace()
+                                        .color(Color32::from_rgba_unmultiplied(0, 180, 210, 130)));
+                                    ui.add_space(3.0);
+                                    let cur_spatial = cur_filter.spatial;
+                                    ui.horizontal(|ui| {
+                                        ui.spacing_mut().item_spacing.x = 4.0;
+                                        let options = std::iter::once(None)
+                                            .chain(SpatialKind::all().iter().copied().map(Some));
+                                        for kind in options {
+                                            let selected = cur_spatial.map(|s| s.kind) == kind;
+                                            let label = kind.map_or("Off", |k| k.label());
+                                            let btn = egui::Button::new(
+                                                RichText::new(label)
+                                                    .size(10.0)
+                                                    .color(if selected { Color32::BLACK } else { DARK_TEXT_DIM }),
+                                            )
+                                            .fill(if selected { ACCENT } else { DARK_BG_2 })
+                                            .stroke(Stroke::new(1.0_f32, if selected { ACCENT } else { DARK_BORDER }));
+                                            if ui.add_sized([57.0, 22.0], btn).clicked() && !selected {
+                                                let mut p = cur_filter.clone();
+                                                p.spatial = kind.map(|kind| SpatialFilter {
+                                                    kind,
+                                                    strength: cur_spatial.map_or(0.5, |s| s.strength),
+                                                });
+                                                cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
+                                            }
+                                        }
+                                    });
+                                    if let Some(spatial) = cur_spatial {
+                                        ui.add_space(4.0);
+                                        let mut amount = spatial.strength;
+                                        if slider_row(ui, spatial.kind.label(), &mut amount, 0.0..=1.0, 0.01, "{}") {
+                                            let mut p = cur_filter.clone();
+                                            p.spatial = Some(SpatialFilter { strength: amount, ..spatial });
+                                            cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
+                                        }
+                                    }
+
+                                    ui.add_space(6.0);
+                                    ui.separator();
+                                    ui.add_space(4.0);
+
+                                    // ── Levels — black / gamma / white over the preview histogram ──
+                                    let cur_levels = cur_filter.levels.unwrap_or_default();
+                                    ui.horizontal(|ui| {
+                                        ui.label(RichText::new("LEVELS").size(8.5).monospace()
+                                            .color(Color32::from_rgba_unmultiplied(0, 180, 210, 130)));
+                                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                            if cur_filter.has_levels()
+                                                && ui.small_button(RichText::new("Reset").size(9.0).color(Color32::from_gray(130))).clicked()
+                                            {
+                                                let mut p = cur_filter.clone();
+                                                p.levels = None;
+                                                cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
+                                            }
+                                        });
+                                    });
+                                    ui.add_space(3.0);
+                                    if let Some(levels) = levels_editor(ui, &state.preview_histogram, cur_levels) {
+                                        let mut p = cur_filter.clone();
+                                        p.levels = (!levels.is_identity()).then_some(levels);
+                                        cmd.push(EditorCommand::SetClipFilter { id: filter_clip_id, filter: p });
+                                    }
+                                    ui.add_space(2.0);
+                                    ui.label(RichText::new(format!(
+                                        "in {:.0}–{:.0}   γ {:.2}",
+                                        cur_levels.black * 255.0,
+                                        cur_levels.white * 255.0,
+                                        cur_levels.gamma,
+                                    )).size(9.0).monospace().color(ACCENT));
                                 });
                         });
 
@@ -1737,45 +3102,455 @@ impl EditorModule for TimelineModule {
             } else {
                 self.filter_popup_just_opened = false;
             }
+
+            self.delete_confirm_popup(ui, state, cmd);
         });
     }
 }
 
-// ── Hotkey reference popup helper ─────────────────────────────────────────────
-
-/// Renders one labelled category block inside the hotkey popup.
-///
-/// Each row is a two-column table: key chord on the left (monospace, accented),
-/// description on the right (proportional, dimmed). The section header is
-/// rendered in a muted uppercase style to visually separate categories without
-/// taking up much vertical space.
-fn hotkey_section(ui: &mut egui::Ui, title: &str, rows: &[(&str, &str)]) {
-    ui.label(
-        egui::RichText::new(title.to_uppercase())
-            .size(9.5)
-            .monospace()
-            .color(egui::Color32::from_rgba_unmultiplied(0, 180, 210, 130)),
-    );
-    ui.add_space(2.0);
+// ── Zoom presets ──────────────────────────────────────────────────────────────
+
+impl TimelineModule {
+    /// Zoom so `secs` of timeline fill the visible track area, then scroll its
+    /// left edge to `from`. Uses last frame's viewport width, like the
+    /// overview strip.
+    fn zoom_to_span(&self, state: &ProjectState, secs: f64, from: f64, cmd: &mut Vec<EditorCommand>) {
+        let view_w = (self.view_scroll.1 - LABEL_COL).max(100.0);
+        let zoom = (view_w as f64 / secs) as f32;
+        let zoom = zoom.clamp(ZOOM_MIN, ZOOM_MAX);
+        if (zoom - state.timeline_zoom).abs() > f32::EPSILON {
+            cmd.push(EditorCommand::SetTimelineZoom(zoom));
+        }
+        cmd.push(EditorCommand::ScrollTimelineTo(from));
+    }
+}
+
+// ── Auto-scroll ───────────────────────────────────────────────────────────────
+
+impl TimelineModule {
+    /// Scroll offset that keeps the playhead in view, per the Playback prefs'
+    /// `AutoScroll` mode. Applies while playing and when the playhead jumps
+    /// without the mouse (keyboard stepping); a mouse scrub already sits
+    /// where the user is looking. Uses last frame's viewport, like the
+    /// overview strip.
+    fn auto_scroll_offset(&mut self, ui: &Ui, state: &ProjectState) -> Option<f32> {
+        let moved = state.current_time != self.follow_last_time;
+        self.follow_last_time = state.current_time;
+        let stepped = moved && !ui.input(|i| i.pointer.any_down());
+        if !state.is_playing && !stepped {
+            return None;
+        }
+        let (offset, view_w) = self.view_scroll;
+        let lane_w = view_w - LABEL_COL;
+        if lane_w <= 0.0 {
+            return None;
+        }
+        let playhead_x = state.current_time as f32 * state.timeline_zoom;
+        state.playback_prefs.auto_scroll.scroll_for(playhead_x, offset, lane_w)
+    }
+}
+
+// ── Beat grid ─────────────────────────────────────────────────────────────────
 
-    for &(key, desc) in rows {
-        ui.horizontal(|ui| {
-            // Fixed-width key column so all descriptions align cleanly.
-            ui.allocate_ui(egui::vec2(148.0, 16.0), |ui| {
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+impl TimelineModule {
+    /// Toolbar ♩ menu: tempo, bar length and downbeat of the beat grid, plus
+    /// tap tempo. Tapping during playback also puts the downbeat on the
+    /// first tap.
+    fn beat_grid_menu(&mut self, ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+        ui.menu_button(RichText::new("♩").size(13.0), |ui| {
+            let mut grid = state.beat_grid;
+            let mut undo = false;
+            let mut on = grid.is_some();
+            if ui.checkbox(&mut on, "Beat grid").changed() {
+                grid = on.then(|| BeatGrid {
+                    offset: state.current_time,
+                    ..Default::default()
+                });
+                undo = true;
+            }
+            if let Some(g) = grid.as_mut() {
+                ui.horizontal(|ui| {
+                    let resp = ui.add(
+                        egui::DragValue::new(&mut g.bpm)
+                            .range(BPM_RANGE)
+                            .speed(0.1)
+                            .max_decimals(2)
+                            .suffix(" BPM"),
+                    );
+                    undo |= resp.drag_started() || (resp.changed() && !resp.dragged());
+                    let resp = ui.add(
+                        egui::DragValue::new(&mut g.beats_per_bar)
+                            .range(BEATS_PER_BAR_RANGE)
+                            .suffix(" / bar"),
+                    );
+                    undo |= resp.drag_started() || (resp.changed() && !resp.dragged());
+                });
+                ui.horizontal(|ui| {
                     ui.label(
-                        egui::RichText::new(key)
-                            .size(10.5)
-                            .monospace()
-                            .color(ACCENT),
+                        RichText::new(format!("Downbeat {}", format_time(g.offset, state.project_fps)))
+                            .size(11.0)
+                            .color(DARK_TEXT_DIM),
                     );
+                    if ui
+                        .small_button("At playhead")
+                        .on_hover_text("Put a bar's first beat on the playhead")
+                        .clicked()
+                    {
+                        g.offset = state.current_time;
+                        undo = true;
+                    }
                 });
+
+                let now = ui.input(|i| i.time);
+                let tap = ui
+                    .button(format!("Tap tempo ({})", self.beat_taps.len()))
+                    .on_hover_text(
+                        "Click on every beat. Pause for 2 s to start over. \
+                         During playback the first tap also sets the downbeat.",
+                    );
+                if tap.clicked() {
+                    if self
+                        .beat_taps
+                        .last()
+                        .is_none_or(|last| now - last > TAP_RESET_SECS)
+                    {
+                        self.beat_taps.clear();
+                        if state.is_playing {
+                            g.offset = state.current_time;
+                        }
+                    }
+                    self.beat_taps.push(now);
+                    if self.beat_taps.len() > 16 {
+                        self.beat_taps.remove(0);
+                    }
+                    if let Some(bpm) = tempo_from_taps(&self.beat_taps) {
+                        g.bpm = (bpm * 10.0).round() / 10.0;
+                    }
+                    undo = self.beat_taps.len() == 1 || undo;
+                }
+            }
+            if grid != state.beat_grid {
+                if undo {
+                    cmd.push(EditorCommand::PushUndoSnapshot("Beat grid"));
+                }
+                cmd.push(EditorCommand::SetBeatGrid(grid));
+            }
+        })
+        .response
+        .on_hover_text("Beat grid — lines to cut on, with snapping");
+    }
+}
+
+// ── Grouped delete ────────────────────────────────────────────────────────────
+
+impl TimelineModule {
+    /// Delete clip `id`, or ask first when it is linked to other clips.
+    fn request_delete(&mut self, state: &ProjectState, id: Uuid, cmd: &mut Vec<EditorCommand>) {
+        if state.group_of(id).len() > 1 {
+            self.delete_confirm = Some(id);
+        } else {
+            cmd.push(EditorCommand::PushUndoSnapshot("Delete clip"));
+            cmd.push(EditorCommand::DeleteTimelineClip(id));
+        }
+    }
+
+    /// Centered prompt for deleting a grouped clip: the whole group, only
+    /// this clip (unlinking it first), or nothing.
+    fn delete_confirm_popup(&mut self, ui: &mut Ui, state: &ProjectState, cmd: &mut Vec<EditorCommand>) {
+        let Some(id) = self.delete_confirm else { return };
+        let group = state.group_of(id);
+        if group.len() < 2 {
+            // The group dissolved (undo, unlink) while the prompt was up.
+            self.delete_confirm = None;
+            return;
+        }
+        let screen = ui.ctx().screen_rect();
+        egui::Area::new(Id::new("group_delete_confirm"))
+            .fixed_pos(screen.center() - egui::vec2(140.0, 50.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ui.ctx(), |ui| {
+                egui::Frame::new()
+                    .fill(DARK_BG_3)
+                    .stroke(Stroke::new(1.0_f32, DARK_BORDER))
+                    .corner_radius(egui::CornerRadius::same(6))
+                    .inner_margin(egui::Margin::same(12))
+                    .shadow(egui::Shadow {
+                        offset: [0, 4],
+                        blur: 12,
+                        spread: 0,
+                        color: Color32::from_black_alpha(120),
+                    })
+                    .show(ui, |ui| {
+                        ui.set_width(280.0);
+                        ui.label(RichText::new("🔗  Delete linked clips?").size(12.0).strong().color(ACCENT));
+                        ui.add_space(4.0);
+                        ui.label(RichText::new(format!(
+                            "This clip is linked to {} other clip{}.",
+                            group.len() - 1,
+                            if group.len() == 2 { "" } else { "s" }))
+                            .size(11.0).color(DARK_TEXT_DIM));
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            if ui.button(format!("Delete all {}", group.len())).clicked() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Delete clip"));
+                                for &member in &group {
+                                    cmd.push(EditorCommand::DeleteTimelineClip(member));
+                                }
+                                self.delete_confirm = None;
+                            }
+                            if ui.button("Only this").clicked() {
+                                cmd.push(EditorCommand::PushUndoSnapshot("Delete clip"));
+                                cmd.push(EditorCommand::UnlinkClip(id));
+                                cmd.push(EditorCommand::DeleteTimelineClip(id));
+                                self.delete_confirm = None;
+                            }
+                            if ui.button("Cancel").clicked()
+                                || ui.input(|i| i.key_pressed(egui::Key::Escape))
+                            {
+                                self.delete_confirm = None;
+                            }
+                        });
+                    });
             });
-            ui.add_space(6.0);
-            ui.label(egui::RichText::new(desc).size(10.5).color(DARK_TEXT_DIM));
-        });
     }
-    ui.add_space(6.0);
+}
+
+// ── Overview strip ────────────────────────────────────────────────────────────
+
+impl TimelineModule {
+    /// Thin whole-project overview drawn between the toolbar and the tracks.
+    ///
+    /// Every clip is painted as a sliver in its track row, compressed so the
+    /// full project fits the panel width, with a rectangle marking the region
+    /// currently visible in the zoomed track area. Below the lanes runs the
+    /// project audio mixdown (`clip_query::mixdown_peaks`), so loud and quiet
+    /// passages show regardless of zoom. Dragging the rectangle or clicking
+    /// elsewhere on the strip emits `ScrollTimelineTo`.
+    fn overview_strip(
+        &mut self,
+        ui: &mut Ui,
+        state: &ProjectState,
+        label_col: f32,
+        cmd: &mut Vec<EditorCommand>,
+    ) {
+        let strip_h = 30.0_f32;
+        let wave_h = 11.0_f32;
+        let (strip_rect, resp) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width(), strip_h),
+            Sense::click_and_drag(),
+        );
+        let painter = ui.painter_at(strip_rect);
+        painter.rect_filled(strip_rect, 0.0, Color32::from_rgb(16, 16, 20));
+
+        // Lane area lines up with the track lanes below the label column.
+        let map_rect = Rect::from_min_max(
+            Pos2::new(strip_rect.min.x + label_col, strip_rect.min.y + 2.0),
+            Pos2::new(strip_rect.max.x - 2.0, strip_rect.max.y - 2.0),
+        );
+        if map_rect.width() <= 0.0 {
+            return;
+        }
+        painter.text(
+            Pos2::new(strip_rect.min.x + label_col * 0.5, strip_rect.center().y),
+            Align2::CENTER_CENTER,
+            "MAP",
+            FontId::monospace(8.0),
+            Color32::from_rgba_unmultiplied(120, 120, 138, 200),
+        );
+
+        // Visible window in seconds, from last frame's ScrollArea output.
+        let zoom = state.timeline_zoom as f64;
+        let (offset_x, view_w) = self.view_scroll;
+        let view_start = offset_x as f64 / zoom;
+        let view_len = ((view_w - label_col).max(0.0) as f64 / zoom).max(0.0);
+
+        // The strip always covers the whole project, or the visible window if
+        // that reaches further (short projects, or scrolled past the end).
+        let span = state.total_duration().max(view_start + view_len).max(1.0);
+        let scale = map_rect.width() as f64 / span;
+        let to_x = |t: f64| map_rect.min.x + (t * scale) as f32;
+        let to_t = |x: f32| ((x - map_rect.min.x) as f64 / scale).clamp(0.0, span);
+
+        let lanes_h = map_rect.height() - wave_h;
+        let row_h = lanes_h / 4.0;
+        for clip in &state.timeline {
+            let y = map_rect.min.y + clip.track_row.min(3) as f32 * row_h;
+            let x0 = to_x(clip.start_time);
+            let x1 = to_x(clip.start_time + clip.duration).max(x0 + 1.0);
+            let color = if state.selected_timeline_clip == Some(clip.id) {
+                ACCENT
+            } else if let Some([r, g, b]) = clip.tag_color {
+                Color32::from_rgb(r, g, b)
+            } else if clip.track_row % 2 == 1 {
+                Color32::from_rgb(80, 200, 140)
+            } else {
+                Color32::from_rgb(100, 140, 220)
+            };
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(x0, y + 0.5), Pos2::new(x1, y + row_h - 0.5)),
+                0.0,
+                color.linear_multiply(0.8),
+            );
+        }
+
+        // ── Audio mixdown ─────────────────────────────────────────────────────
+        let columns = map_rect.width().max(1.0) as usize;
+        let key = mixdown_key(state, span, columns);
+        if self.mixdown.as_ref().map(|(k, _)| *k) != Some(key) {
+            self.mixdown = Some((key, clip_query::mixdown_peaks(state, span, columns)));
+        }
+        if let Some((_, peaks)) = &self.mixdown {
+            let wave_mid = map_rect.max.y - wave_h * 0.5;
+            let wave_color = Color32::from_rgba_unmultiplied(100, 240, 165, 150);
+            for (i, &p) in peaks.iter().enumerate() {
+                if p <= 0.0 {
+                    continue;
+                }
+                let x = map_rect.min.x + i as f32 + 0.5;
+                let half = (p * wave_h * 0.5).max(0.5);
+                painter.line_segment(
+                    [Pos2::new(x, wave_mid - half), Pos2::new(x, wave_mid + half)],
+                    Stroke::new(1.0_f32, wave_color),
+                );
+            }
+        }
+
+        let ph_x = to_x(state.current_time.min(span));
+        painter.line_segment(
+            [
+                Pos2::new(ph_x, strip_rect.min.y),
+                Pos2::new(ph_x, strip_rect.max.y),
+            ],
+            Stroke::new(1.0_f32, ACCENT),
+        );
+
+        let view_rect = Rect::from_min_max(
+            Pos2::new(to_x(view_start), strip_rect.min.y + 1.0),
+            Pos2::new(
+                to_x(view_start + view_len).max(to_x(view_start) + 4.0),
+                strip_rect.max.y - 1.0,
+            ),
+        );
+        painter.rect_filled(
+            view_rect,
+            2.0,
+            Color32::from_rgba_unmultiplied(255, 255, 255, 14),
+        );
+        painter.rect_stroke(
+            view_rect,
+            2.0,
+            Stroke::new(
+                1.0_f32,
+                ACCENT.linear_multiply(if resp.dragged() { 0.9 } else { 0.6 }),
+            ),
+            egui::StrokeKind::Inside,
+        );
+
+        // Grabbing inside the rectangle keeps the grab point under the cursor;
+        // pressing anywhere else centers the visible window on that spot.
+        if resp.drag_started() || resp.clicked() {
+            self.overview_grab = resp.interact_pointer_pos().map(|p| {
+                if view_rect.contains(p) {
+                    to_t(p.x) - view_start
+                } else {
+                    view_len * 0.5
+                }
+            });
+        }
+        if resp.clicked() || resp.dragged() {
+            if let (Some(p), Some(grab)) = (resp.interact_pointer_pos(), self.overview_grab) {
+                let new_start = (to_t(p.x) - grab).max(0.0);
+                if (new_start - view_start).abs() * zoom >= 1.0 {
+                    cmd.push(EditorCommand::ScrollTimelineTo(new_start));
+                }
+            }
+        }
+        if resp.drag_stopped() || resp.clicked() {
+            self.overview_grab = None;
+        }
+        if resp.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+        } else if resp.hovered() {
+            let over_view = ui
+                .input(|i| i.pointer.hover_pos())
+                .map(|p| view_rect.contains(p))
+                .unwrap_or(false);
+            ui.ctx().set_cursor_icon(if over_view {
+                egui::CursorIcon::Grab
+            } else {
+                egui::CursorIcon::PointingHand
+            });
+        }
+    }
+}
+
+/// Everything the overview mixdown depends on, hashed. Cheap next to the
+/// stitch itself, so it runs every frame and the stitch only on change.
+fn mixdown_key(state: &ProjectState, span: f64, columns: usize) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    span.to_bits().hash(&mut h);
+    columns.hash(&mut h);
+    for c in &state.timeline {
+        c.media_id.hash(&mut h);
+        c.start_time.to_bits().hash(&mut h);
+        c.duration.to_bits().hash(&mut h);
+        c.source_offset.to_bits().hash(&mut h);
+        c.volume.to_bits().hash(&mut h);
+        c.audio_muted.hash(&mut h);
+        c.freeze_at.map(f64::to_bits).hash(&mut h);
+        if let Some(lib) = clip_query::library_entry_for(state, c) {
+            lib.waveform_peaks.len().hash(&mut h);
+            lib.audio_stream_index.hash(&mut h);
+            lib.duration.to_bits().hash(&mut h);
+        }
+    }
+    h.finish()
+}
+
+/// Sync fine-tune overlay: over the visible part of `clip_rect`, `clip`'s
+/// waveform above the center line (amber) and `reference`'s below it (cyan),
+/// each sampled at the source time under that pixel.
+fn draw_sync_overlay(
+    painter: &egui::Painter,
+    clip_rect: Rect,
+    clip: &TimelineClip,
+    lib: &LibraryClip,
+    reference: &TimelineClip,
+    ref_lib: &LibraryClip,
+    zoom: f32,
+) {
+    let visible = clip_rect.intersect(painter.clip_rect());
+    if visible.width() <= 0.0 || zoom <= 0.0 {
+        return;
+    }
+    painter.rect_filled(clip_rect, 4.0, Color32::from_black_alpha(140));
+    let mid_y = clip_rect.center().y;
+    let half_h = clip_rect.height() * 0.44;
+    painter.line_segment(
+        [Pos2::new(visible.min.x, mid_y), Pos2::new(visible.max.x, mid_y)],
+        Stroke::new(1.0_f32, Color32::from_white_alpha(40)),
+    );
+    let top = Stroke::new(1.0_f32, Color32::from_rgba_unmultiplied(255, 190, 60, 210));
+    let bottom = Stroke::new(1.0_f32, Color32::from_rgba_unmultiplied(90, 210, 255, 210));
+    let ref_end = reference.start_time + reference.duration;
+
+    let mut x = visible.min.x.floor();
+    while x < visible.max.x {
+        let t = clip.start_time + ((x - clip_rect.min.x) / zoom) as f64;
+        let up = clip_query::peak_at(lib, clip.source_offset + t - clip.start_time);
+        if up > 0.0 {
+            painter.line_segment([Pos2::new(x, mid_y), Pos2::new(x, mid_y - up * half_h)], top);
+        }
+        if t >= reference.start_time && t < ref_end {
+            let down = clip_query::peak_at(ref_lib, reference.source_offset + t - reference.start_time);
+            if down > 0.0 {
+                painter.line_segment([Pos2::new(x, mid_y), Pos2::new(x, mid_y + down * half_h)], bottom);
+            }
+        }
+        x += 1.0;
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1791,6 +3566,7 @@ fn draw_waveform(
     fade_out_secs: f32,
     fade_out_end_secs: f32,
     duration: f32,
+    opacity: f32,
 ) {
     if peaks.is_empty() {
         return;
@@ -1804,7 +3580,8 @@ fn draw_waveform(
         Color32::from_rgba_unmultiplied(100, 240, 165, 220)
     } else {
         Color32::from_rgba_unmultiplied(160, 200, 255, 38)
-    };
+    }
+    .gamma_multiply(opacity);
 
     // Equal-power 3-zone gain: silence → sqrt-ramp → full (fade-in)
     //                          full → sqrt-ramp → silence (fade-out)
@@ -1927,6 +3704,81 @@ fn draw_waveform(
         }
     }
 }
+
+// ── Levels editor ─────────────────────────────────────────────────────────────
+
+/// Luma histogram of the preview frame with draggable black (left), gamma
+/// (middle) and white (right) markers underneath. `hist` is the 256-bin
+/// `ProjectState::preview_histogram` and may be empty. Returns the new levels
+/// while a marker is being dragged.
+fn levels_editor(ui: &mut Ui, hist: &[u32], levels: Levels) -> Option<Levels> {
+    const PLOT_H: f32 = 46.0;
+    const HANDLE_H: f32 = 9.0;
+    let w = ui.available_width();
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(w, PLOT_H + HANDLE_H), Sense::hover());
+    let plot = Rect::from_min_size(rect.min, Vec2::new(w, PLOT_H));
+    let painter = ui.painter().clone();
+    painter.rect_filled(plot, 2.0, DARK_BG_2);
+
+    // Square-root scale keeps a thin shadow or highlight tail visible next
+    // to a tall midtone peak.
+    let peak = hist.iter().copied().max().unwrap_or(0);
+    if peak > 0 {
+        let bar_w = w / hist.len() as f32;
+        let norm = (peak as f32).sqrt();
+        for (i, &n) in hist.iter().enumerate().filter(|&(_, &n)| n > 0) {
+            let h = (n as f32).sqrt() / norm * (PLOT_H - 2.0);
+            let x = plot.left() + i as f32 * bar_w;
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(x, plot.bottom() - h), Pos2::new(x + bar_w.max(1.0), plot.bottom())),
+                0.0,
+                Color32::from_gray(150),
+            );
+        }
+    } else {
+        painter.text(plot.center(), Align2::CENTER_CENTER, "no preview frame", FontId::monospace(8.5), DARK_TEXT_DIM);
+    }
+
+    let x_of = |v: f32| plot.left() + v.clamp(0.0, 1.0) * w;
+    // Shade the input ranges that clip to pure black / white.
+    let shade = Color32::from_black_alpha(110);
+    painter.rect_filled(Rect::from_min_max(plot.min, Pos2::new(x_of(levels.black), plot.bottom())), 0.0, shade);
+    painter.rect_filled(Rect::from_min_max(Pos2::new(x_of(levels.white), plot.top()), plot.max), 0.0, shade);
+
+    // The gamma marker sits on the input level that comes out as mid-gray.
+    let span = levels.white - levels.black;
+    let mid = levels.black + span * 0.5_f32.powf(levels.gamma);
+    let markers = [(levels.black, Color32::from_gray(20)), (mid, Color32::from_gray(128)), (levels.white, Color32::WHITE)];
+
+    let mut out = levels;
+    for (i, (v, fill)) in markers.into_iter().enumerate() {
+        let x = x_of(v);
+        let handle = Rect::from_center_size(Pos2::new(x, plot.bottom() + HANDLE_H * 0.5), Vec2::new(12.0, HANDLE_H));
+        let resp = ui.interact(handle, ui.id().with(("levels_marker", i)), Sense::drag());
+        if resp.dragged() {
+            if let Some(pos) = resp.interact_pointer_pos() {
+                let t = ((pos.x - plot.left()) / w).clamp(0.0, 1.0);
+                match i {
+                    0 => out.black = t.min(levels.white - LEVELS_MIN_SPAN),
+                    1 => {
+                        let frac = ((t - levels.black) / span).clamp(0.01, 0.99);
+                        out.gamma = frac.ln() / 0.5_f32.ln();
+                    }
+                    _ => out.white = t.max(levels.black + LEVELS_MIN_SPAN),
+                }
+            }
+        }
+        let edge = if resp.hovered() || resp.dragged() { ACCENT } else { Color32::from_gray(90) };
+        painter.line_segment([Pos2::new(x, plot.top()), Pos2::new(x, plot.bottom())], Stroke::new(1.0_f32, edge.gamma_multiply(0.6)));
+        painter.add(egui::Shape::convex_polygon(
+            vec![Pos2::new(x, plot.bottom()), Pos2::new(x + 5.0, rect.bottom()), Pos2::new(x - 5.0, rect.bottom())],
+            fill,
+            Stroke::new(1.0_f32, edge),
+        ));
+    }
+    (out != levels).then(|| out.validated())
+}
+
 fn ruler_step(zoom: f32) -> f64 {
     if zoom >= 1200.0 {
         0.0333
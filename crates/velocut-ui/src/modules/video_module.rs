@@ -42,7 +42,14 @@ impl VideoModule {
         let current_clip = clip_query::clip_at_time(state, state.current_time);
         let current_media_id = current_clip.map(|c| c.media_id);
         let pb_local_t: Option<f64> =
-            current_clip.map(|c| (state.current_time - c.start_time + c.source_offset).max(0.0));
+            current_clip.map(|c| c.source_time(state.current_time - c.start_time));
+
+        // Freeze clips have no stream: tick() parks the pb thread and the scrub
+        // path holds the frame. Leave frame_cache alone so it isn't evicted.
+        // Slates have no stream either; the preview paints their color.
+        if current_clip.is_some_and(|c| c.freeze_at.is_some() || c.slate.is_some()) {
+            return;
+        }
 
         // ── Clip-transition eviction (must run before the UI renders) ────────────
         // tick() also calls frame_cache.remove on clip change, but tick() runs
@@ -202,6 +209,7 @@ impl VideoModule {
 
         if frame_due {
             if let Some(mut f) = ctx.cache.pending_pb_frame.take() {
+                ctx.cache.preview_histogram = Some(crate::context::luma_histogram(&f.data));
                 // Look up the filter for the clip currently under the playhead (V-row only).
                 let active_filter = state
                     .timeline
@@ -214,9 +222,14 @@ impl VideoModule {
                     .map(|c| c.filter.clone())
                     .unwrap_or_default();
 
-                if !active_filter.is_identity() {
+                if !active_filter.is_identity() && !state.bypass_effects {
                     use velocut_core::filters::helpers::apply_filter_rgba;
-                    apply_filter_rgba(&mut f.data, &active_filter);
+                    apply_filter_rgba(
+                        &mut f.data,
+                        f.width as usize,
+                        f.height as usize,
+                        &active_filter,
+                    );
                 }
 
                 let tex = egui_ctx.load_texture(
@@ -260,8 +273,22 @@ impl VideoModule {
         // single source of truth, no risk of the two copies drifting.
         let current_clip = clip_query::clip_at_time(state, state.current_time).cloned();
 
+        // ── Freeze clip / slate during playback ───────────────────────────────
+        // Nothing to stream: park the pb thread once and fall through to the
+        // scrub path, which requests and holds the single frame (a slate has
+        // none; the preview paints its color). Clearing
+        // playback_media_id makes the next clip reopen its decoder.
+        let playing_freeze = state.is_playing
+            && current_clip
+                .as_ref()
+                .is_some_and(|c| c.freeze_at.is_some() || c.slate.is_some());
+        if playing_freeze && ctx.playback.playback_media_id.take().is_some() {
+            ctx.media_worker.stop_playback();
+            ctx.cache.pending_pb_frame = None;
+        }
+
         // ── Playback mode ─────────────────────────────────────────────────────
-        if state.is_playing {
+        if state.is_playing && !playing_freeze {
             if let Some(clip) = &current_clip {
                 let clip_changed = Some(clip.media_id) != ctx.playback.playback_media_id;
                 if just_started || clip_changed {
@@ -286,8 +313,7 @@ impl VideoModule {
                         .frame_bucket_cache
                         .retain(|(id, _, _), _| *id != clip.media_id);
                     if let Some(lib) = clip_query::library_entry_for(state, clip) {
-                        let local_ts =
-                            (state.current_time - clip.start_time + clip.source_offset).max(0.0);
+                        let local_ts = clip.source_time(state.current_time - clip.start_time);
                         // Pass aspect=0.0 → LiveDecoder opens at native source resolution.
                         // The pb thread is the preview player; it must be full quality.
                         // crop_uv_rect in preview_module handles any AR mismatch on the GPU.
@@ -334,7 +360,8 @@ impl VideoModule {
                         .timeline
                         .iter()
                         .filter(|c| c.track_row % 2 == 0 && !clip_query::is_extracted_audio_clip(c))
-                        .find(|c| (c.start_time - clip_end).abs() < 0.05);
+                        .find(|c| (c.start_time - clip_end).abs() < 0.05)
+                        .filter(|c| c.freeze_at.is_none());
                     if let Some(nc) = next_clip {
                         if let Some(lib) = clip_query::library_entry_for(state, nc) {
                             ctx.playback.prebuffer_sent_for = Some(clip.id);
@@ -367,12 +394,18 @@ impl VideoModule {
             ctx.cache.frame_bucket_cache.clear();
         }
 
-        let Some(clip) = current_clip else {
+        // A slate decodes nothing — to the scrub path it is a gap.
+        let Some(clip) = current_clip.filter(|c| c.slate.is_none()) else {
             if let Some((prev_id, _)) = ctx.playback.last_frame_req {
-                // Playhead moved into empty space - evict that clip's bucket cache.
+                // Playhead moved into empty space - evict that clip's bucket cache
+                // and its displayed frame. app.rs looks frames up by the active
+                // media id, so a leftover entry would reappear as a ghost of the
+                // wrong moment the next time a clip of that file is under the
+                // playhead.
                 ctx.cache
                     .frame_bucket_cache
                     .retain(|(id, _, _), _| *id != prev_id);
+                ctx.cache.frame_cache.remove(&prev_id);
             }
             ctx.playback.last_frame_req = None;
             ctx.playback.scrub_last_moved = None;
@@ -380,7 +413,7 @@ impl VideoModule {
             return;
         };
 
-        let local_t = (state.current_time - clip.start_time + clip.source_offset).max(0.0);
+        let local_t = clip.source_time(state.current_time - clip.start_time);
         let fine_bucket = (local_t * 4.0) as u32; // ¼s grid — cache key only
         let coarse_bucket = (local_t / 2.0) as u32; // 2s grid — prefetch key
 
@@ -454,6 +487,9 @@ impl VideoModule {
                 });
                 if let Some(cached) = found_nearby {
                     ctx.cache.frame_cache.insert(clip.media_id, cached);
+                    ctx.perf.bucket_hits += 1;
+                } else {
+                    ctx.perf.bucket_misses += 1;
                 }
             }
 
@@ -498,6 +534,7 @@ impl VideoModule {
                             clip_b_ts: zone.clip_b_source_ts,
                             alpha: zone.alpha,
                             kind: zone.transition.kind,
+                            curve: zone.transition.curve,
                         });
                 }
             } else if let Some(lib) = clip_query::library_entry_for(state, &clip) {
@@ -581,6 +618,7 @@ impl VideoModule {
                             clip_b_ts: zone.clip_b_source_ts,
                             alpha: zone.alpha,
                             kind: zone.transition.kind,
+                            curve: zone.transition.curve,
                         });
                     ctx.playback.scrub_last_moved = None;
                 }
@@ -640,6 +678,12 @@ fn build_blend_spec(
         // Snapped clips are exactly adjacent; allow a small epsilon for float drift.
         .find(|c| (c.start_time - clip_end).abs() < 0.05)?;
 
+    // The pb thread can only blend two moving streams; a freeze clip plays as
+    // a hard cut in preview (export still renders the transition).
+    if next_clip.freeze_at.is_some() {
+        return None;
+    }
+
     let next_lib = clip_query::library_entry_for(state, next_clip)?;
 
     // Source timestamp in clip_a at which blending begins.
@@ -654,6 +698,7 @@ fn build_blend_spec(
         blend_start_ts,
         duration: tr.kind.duration_secs,
         kind: tr.kind.kind,
+        curve: tr.kind.curve,
         alpha_start: 0.0, // clip_a side: ramps 0.0 → 0.5 at cut
         invert_ab: false,
     })
@@ -691,6 +736,11 @@ fn build_incoming_blend_spec(
         }
     };
 
+    // Freeze clips play as a hard cut in preview — see build_blend_spec.
+    if clip_a.freeze_at.is_some() || clip_b.freeze_at.is_some() {
+        return None;
+    }
+
     // Find the non-Cut transition recorded after clip_a.
     let tr = state
         .transitions
@@ -777,6 +827,7 @@ fn build_incoming_blend_spec(
         blend_start_ts: clip_b.source_offset, // blend from clip_b's first frame
         duration: tr.kind.duration_secs,      // full D; alpha_start offsets into it
         kind: tr.kind.kind,
+        curve: tr.kind.curve,
         alpha_start,     // dynamic: 0.5 + elapsed/duration, clamped to [0.5, 1.0]
         invert_ab: true, // primary=clip_b is "b"; secondary=clip_a is "a"
     })
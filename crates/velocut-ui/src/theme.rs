@@ -25,6 +25,19 @@ pub const DARK_BORDER: Color32 = Color32::from_rgb(55, 55, 68);
 pub const CLIP_VIDEO: Color32 = Color32::from_rgb(52, 98, 168);
 pub const CLIP_AUDIO: Color32 = Color32::from_rgb(42, 138, 98);
 pub const CLIP_SELECTED: Color32 = Color32::from_rgb(0, 180, 210);
+pub const MARKER: Color32 = Color32::from_rgb(255, 170, 60); // ruler markers and marker snap lines
+pub const BEAT: Color32 = Color32::from_rgb(150, 130, 230); // beat-grid lines (bar downbeats brighter)
+pub const CLIP_OVERLAP: Color32 = Color32::from_rgb(230, 70, 70); // border of clips overlapping on one track
+
+/// Clip tag swatches offered in the timeline context menu (name, stripe color).
+pub const CLIP_TAG_PALETTE: [(&str, Color32); 6] = [
+    ("Red", Color32::from_rgb(230, 70, 70)),
+    ("Orange", Color32::from_rgb(240, 150, 50)),
+    ("Yellow", Color32::from_rgb(235, 210, 60)),
+    ("Green", Color32::from_rgb(90, 200, 90)),
+    ("Blue", Color32::from_rgb(80, 140, 240)),
+    ("Purple", Color32::from_rgb(170, 100, 230)),
+];
 
 pub const SEL_MULTI: Color32 = Color32::from_rgb(0, 229, 255); // card border / badge fill when multi-selected
 pub const SEL_CHECK: Color32 = Color32::from_rgb(80, 240, 255); // ✓ checkmark tint on badge